@@ -1,18 +1,30 @@
+use crate::batch::{BatchPlanner, SourceCleaner};
+use crate::confirm::{ConfirmAction, Confirmation};
+use crate::db::Hash;
+use crate::errors::log_error;
+use crate::fs::{get_dir_size, get_free_space};
+use crate::hosting::Shutdown;
+use crate::logging::set_log_source;
+use crate::notify::{Notifier, NotifyEvent};
 use crate::options::{
-    BatchOptions, CacheOptions, FileOptions, Options, SharedOptions, SpectrogramOptions,
-    TargetOptions, UploadOptions, VerifyOptions,
+    BatchOptions, BeetsOptions, CacheOptions, EnrichOptions, FileOptions, HookOptions,
+    NotifyOptions, OptionRule, Options, PermissionOptions, SharedOptions, SpectrogramOptions,
+    TargetOptions, TorrentOptions, UploadOptions, VerifyOptions,
 };
-use crate::queue::Queue;
+use crate::queue::{Queue, QueueItem};
 use crate::source::*;
 use crate::spectrogram::SpectrogramCommand;
 use crate::transcode::TranscodeCommand;
+use crate::tui::{ItemStatus, Tui, TuiState};
 use crate::upload::UploadCommand;
-use crate::verify::{VerifyCommand, VerifyStatus};
+use crate::verify::{get_size_issues, VerifyCommand, VerifyStatus};
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use log::{debug, error, info, trace, warn};
 use reqwest::StatusCode;
 use rogue_logging::Error;
+use std::collections::VecDeque;
+use std::time::Instant;
 use tokio::time::sleep;
 
 /// Batch a FLAC source is suitable for transcoding.
@@ -22,35 +34,72 @@ pub struct BatchCommand {
     shared_options: Ref<SharedOptions>,
     verify_options: Ref<VerifyOptions>,
     target_options: Ref<TargetOptions>,
+    torrent_options: Ref<TorrentOptions>,
     upload_options: Ref<UploadOptions>,
     spectrogram_options: Ref<SpectrogramOptions>,
     file_options: Ref<FileOptions>,
     batch_options: Ref<BatchOptions>,
+    notify_options: Ref<NotifyOptions>,
+    hook_options: Ref<HookOptions>,
+    enrich_options: Ref<EnrichOptions>,
+    beets_options: Ref<BeetsOptions>,
+    permission_options: Ref<PermissionOptions>,
     source_provider: RefMut<SourceProvider>,
+    locker: Ref<SourceLocker>,
     verify: RefMut<VerifyCommand>,
     spectrogram: Ref<SpectrogramCommand>,
     transcode: Ref<TranscodeCommand>,
     upload: RefMut<UploadCommand>,
     queue: RefMut<Queue>,
+    notifier: Ref<Notifier>,
+    shutdown: Ref<Shutdown>,
+    tui: Ref<Tui>,
+    tui_state: Ref<TuiState>,
+    confirmation: Ref<Confirmation>,
+    planner: Ref<BatchPlanner>,
+    cleaner: Ref<SourceCleaner>,
 }
 
 impl BatchCommand {
     /// Execute [`BatchCommand`] from the CLI.
     ///
     /// Returns `true` if the batch process succeeds.
-    #[allow(clippy::too_many_lines)]
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.cache_options.validate()
-            || !self.shared_options.validate()
-            || !self.verify_options.validate()
-            || !self.target_options.validate()
-            || !self.spectrogram_options.validate()
-            || !self.file_options.validate()
-            || !self.batch_options.validate()
-            || !self.upload_options.validate()
-        {
-            return Ok(false);
+        let errors = self.get_errors();
+        OptionRule::check(&errors)?;
+        if let Some(indexer) = &self.shared_options.indexer {
+            self.torrent_options.warn_unsupported_version(indexer);
         }
+        self.execute().await
+    }
+
+    /// Get every [`OptionRule`] not satisfied by the options of [`BatchCommand`].
+    #[must_use]
+    pub fn get_errors(&self) -> Vec<OptionRule> {
+        [
+            self.cache_options.get_errors(),
+            self.shared_options.get_errors(),
+            self.verify_options.get_errors(),
+            self.target_options.get_errors(),
+            self.torrent_options.get_errors(),
+            self.spectrogram_options.get_errors(),
+            self.file_options.get_errors(),
+            self.batch_options.get_errors(),
+            self.notify_options.get_errors(),
+            self.hook_options.get_errors(),
+            self.upload_options.get_errors(),
+            self.enrich_options.get_errors(),
+            self.beets_options.get_errors(),
+            self.permission_options.get_errors(),
+        ]
+        .concat()
+    }
+
+    /// Execute a batch process of the unprocessed items in the queue.
+    ///
+    /// Returns `true` if the batch process succeeds.
+    #[allow(clippy::too_many_lines)]
+    pub async fn execute(&mut self) -> Result<bool, Error> {
         let mut queue = self.queue.write().expect("Queue should be writeable");
         let mut source_provider = self
             .source_provider
@@ -98,12 +147,115 @@ impl BatchCommand {
             items.len(),
             indexer.to_uppercase()
         );
+        if transcode_enabled && upload_enabled && self.upload_options.dry_run != Some(true) {
+            let count = limit.map_or(items.len(), |limit| limit.min(items.len()));
+            let question = format!(
+                "Upload the transcodes of up to {count} sources to {}?",
+                indexer.to_uppercase()
+            );
+            if !self
+                .confirmation
+                .confirm(ConfirmAction::Upload, &question)?
+            {
+                warn!(
+                    "{} batch as the upload was not confirmed",
+                    "Cancelled".bold()
+                );
+                return Ok(false);
+            }
+            // Asked once rather than before each upload
+            self.confirmation.approve(ConfirmAction::Upload);
+        }
+        if self.cleaner.is_enabled() && !self.cleaner.is_dry_run() {
+            let count = limit.map_or(items.len(), |limit| limit.min(items.len()));
+            let cleanup = self.batch_options.source_cleanup.unwrap_or_default();
+            let question =
+                format!("Then {cleanup} the source directories of up to {count} sources?");
+            if !self
+                .confirmation
+                .confirm(ConfirmAction::RemoveSource, &question)?
+            {
+                warn!(
+                    "{} batch as the source cleanup was not confirmed",
+                    "Cancelled".bold()
+                );
+                return Ok(false);
+            }
+            // Asked once rather than before each source
+            self.confirmation.approve(ConfirmAction::RemoveSource);
+        }
+        let items: Vec<QueueItem> = items
+            .into_iter()
+            .filter_map(|hash| queue.get(hash).ok().flatten())
+            .collect();
+        let (items, mut planned) = self.planner.plan(items, &mut source_provider).await;
+        let tui = self.tui.start();
+        let interactive = tui.is_some();
+        if interactive {
+            let names = items
+                .iter()
+                .map(|hash| {
+                    let name = queue
+                        .get(*hash)
+                        .ok()
+                        .flatten()
+                        .map_or_else(|| hash.to_string(), |item| item.name);
+                    (*hash, name)
+                })
+                .collect();
+            self.tui_state.set_items(names);
+        }
+        let start = Instant::now();
+        let max_duration = self.batch_options.get_max_duration();
+        let mut pending: VecDeque<Hash<20>> = items.into();
         let mut count = 0;
-        for hash in items {
+        let mut failed = 0;
+        let mut disk_usage = 0;
+        let mut too_large: Vec<String> = Vec::new();
+        loop {
+            pending.extend(self.tui_state.take_retries());
+            let Some(hash) = pending.pop_front() else {
+                if !interactive {
+                    break;
+                }
+                info!(
+                    "{} every item. Press r to retry an item or q to quit",
+                    "Processed".bold()
+                );
+                let Some(retries) = self.tui_state.wait_for_retry(&self.shutdown).await else {
+                    break;
+                };
+                pending.extend(retries);
+                continue;
+            };
+            self.tui_state.wait_while_paused(&self.shutdown).await;
+            if self.shutdown.is_requested() {
+                warn!("{} batch before the next item", "Stopped".bold());
+                break;
+            }
+            if let Some(max_duration) = max_duration.filter(|x| start.elapsed() >= *x) {
+                info!(
+                    "{} batch before the next item as the max_duration of {} was reached",
+                    "Stopped".bold(),
+                    humantime::format_duration(max_duration)
+                );
+                break;
+            }
+            if let Some(reason) = self.get_disk_limit(disk_usage).await {
+                warn!(
+                    "{} batch before the next item as {reason}",
+                    "Stopped".bold()
+                );
+                break;
+            }
             let Some(mut item) = queue.get(hash)? else {
                 error!("{} to retrieve {hash} from the queue", "Failed".bold());
                 continue;
             };
+            if self.is_skip_requested(&item) {
+                continue;
+            }
+            self.tui_state.set_status(hash, ItemStatus::Active);
             trace!("{} {item}", "Processing".bold());
             let Some(id) = item.id else {
                 debug!("{} {item} as it doesn't have an id", "Skipping".bold());
@@ -112,9 +264,17 @@ impl BatchCommand {
                 });
                 item.verify = Some(status);
                 queue.set(item).await?;
+                self.tui_state.set_status(hash, ItemStatus::Skipped);
                 continue;
             };
-            let source = match source_provider.get(id).await {
+            self.tui_state.set_step("Verifying");
+            let result = if let Some(source) = planned.remove(&id) {
+                set_log_source(Some(id.to_string()));
+                Ok(source)
+            } else {
+                source_provider.get(id).await
+            };
+            let mut source = match result {
                 Ok(source) => source,
                 Err(issue) => {
                     if let SourceIssue::ApiResponse {
@@ -144,6 +304,41 @@ impl BatchCommand {
                         item.verify = Some(VerifyStatus::from_issue(issue));
                         queue.set(item).await?;
                     }
+                    self.tui_state.set_status(hash, ItemStatus::Skipped);
+                    continue;
+                }
+            };
+            let size_issues = get_size_issues(
+                source.torrent.size,
+                source.torrent.get_flacs().len(),
+                self.verify_options.get_max_source_size(),
+                self.verify_options.max_source_tracks,
+            );
+            if !size_issues.is_empty() {
+                debug!("{} {source}", "Skipping".bold());
+                for issue in &size_issues {
+                    debug!("{issue}");
+                }
+                too_large.push(source.to_string());
+                item.verify = Some(VerifyStatus::from_issues(size_issues));
+                queue.set(item).await?;
+                self.tui_state.set_status(hash, ItemStatus::Skipped);
+                continue;
+            }
+            let _lock = match self.locker.acquire(&source) {
+                Ok(Some(lock)) => lock,
+                Ok(None) => {
+                    // The item is left in the queue to be processed by a later batch
+                    info!(
+                        "{} {source} as it's being processed by another caesura process",
+                        "Skipping".bold()
+                    );
+                    self.tui_state.set_status(hash, ItemStatus::Skipped);
+                    continue;
+                }
+                Err(error) => {
+                    log_error(&error);
+                    self.tui_state.set_status(hash, ItemStatus::Skipped);
                     continue;
                 }
             };
@@ -166,28 +361,58 @@ impl BatchCommand {
                 }
                 item.verify = Some(status);
                 queue.set(item).await?;
+                self.tui_state.set_status(hash, ItemStatus::Skipped);
                 continue;
             }
             if spectrogram_enabled {
+                if self.is_skip_requested(&item) {
+                    queue.set(item).await?;
+                    continue;
+                }
+                self.tui_state.set_step("Generating spectrograms");
                 let status = self.spectrogram.execute(&source).await;
                 if let Some(error) = &status.error {
                     warn!("{error}");
                 }
+                if let Some(path) = &status.path {
+                    disk_usage += get_dir_size(path);
+                }
                 item.spectrogram = Some(status);
             }
+            let mut item_failed = false;
             if transcode_enabled {
-                let status = self.transcode.execute(&source).await;
+                if self.is_skip_requested(&item) {
+                    queue.set(item).await?;
+                    continue;
+                }
+                self.tui_state.set_step("Transcoding");
+                let status = self.transcode.execute(&mut source).await;
+                if !status.success && self.shutdown.is_requested() {
+                    // The transcode was cancelled so the item is left to be processed again
+                    warn!("{} batch during transcode of {source}", "Stopped".bold());
+                    break;
+                }
                 if let Some(error) = &status.error {
-                    error.log();
+                    log_error(error);
+                }
+                for format in status.formats.iter().flatten() {
+                    disk_usage += get_dir_size(&format.path);
                 }
                 if status.success {
                     item.transcode = Some(status);
                 } else {
                     item.transcode = Some(status);
+                    queue.set(item).await?;
+                    failed += 1;
+                    self.tui_state.set_status(hash, ItemStatus::Failed);
+                    continue;
+                }
+                if upload_enabled && self.is_skip_requested(&item) {
                     queue.set(item).await?;
                     continue;
                 }
                 if upload_enabled {
+                    self.tui_state.set_step("Uploading");
                     if let Some(wait_before_upload) = self.batch_options.get_wait_before_upload() {
                         info!("{} {wait_before_upload:?} before upload", "Waiting".bold());
                         sleep(wait_before_upload).await;
@@ -198,6 +423,10 @@ impl BatchCommand {
                         .expect("UploadCommand should be writeable")
                         .execute(&source)
                         .await;
+                    if !status.success {
+                        failed += 1;
+                        item_failed = true;
+                    }
                     if self.upload_options.dry_run != Some(true) {
                         item.upload = Some(status);
                     }
@@ -205,6 +434,22 @@ impl BatchCommand {
                 }
             }
             queue.set(item).await?;
+            if !item_failed && self.cleaner.is_enabled() {
+                if upload_enabled && self.upload_options.dry_run == Some(true) {
+                    debug!(
+                        "{} source cleanup of {source} as the upload was a dry run",
+                        "Skipped".bold()
+                    );
+                } else if let Err(error) = self.cleaner.execute(&source).await {
+                    log_error(&error);
+                }
+            }
+            let status = if item_failed {
+                ItemStatus::Failed
+            } else {
+                ItemStatus::Done
+            };
+            self.tui_state.set_status(hash, status);
             count += 1;
             if let Some(limit) = limit {
                 if count >= limit {
@@ -213,7 +458,75 @@ impl BatchCommand {
                 }
             }
         }
+        drop(tui);
         info!("{} batch process of {count} items", "Completed".bold());
+        if !too_large.is_empty() {
+            warn!(
+                "{} {} sources that exceed max_source_size or max_source_tracks. Transcode them manually:",
+                "Skipped".bold(),
+                too_large.len()
+            );
+            for source in &too_large {
+                warn!("{source}");
+            }
+        }
+        if failed > 0 {
+            self.notifier
+                .notify(
+                    NotifyEvent::BatchFailed,
+                    format!("Batch process completed with {failed} failed transcodes or uploads"),
+                )
+                .await;
+        }
         Ok(true)
     }
+
+    /// Get why the batch must stop if `max_disk_usage` or `min_free_space` is reached.
+    ///
+    /// `usage` is the bytes written by the batch so far.
+    async fn get_disk_limit(&self, usage: u64) -> Option<String> {
+        if let Some(max) = self.batch_options.get_max_disk_usage() {
+            if usage >= max {
+                return Some(format!(
+                    "{} MiB were written which reaches the max_disk_usage of {} MiB",
+                    usage >> 20,
+                    max >> 20
+                ));
+            }
+        }
+        let min = self.batch_options.get_min_free_space()?;
+        let output = self
+            .shared_options
+            .output
+            .clone()
+            .expect("output should be set");
+        let Some(available) = get_free_space(&output).await else {
+            warn!(
+                "{} to determine the free space of {}",
+                "Failed".bold(),
+                output.display()
+            );
+            return None;
+        };
+        if available < min {
+            return Some(format!(
+                "{} MiB is free which is below the min_free_space of {} MiB",
+                available >> 20,
+                min >> 20
+            ));
+        }
+        None
+    }
+
+    /// Has the item been requested to be skipped from the keyboard of [`Tui`]?
+    ///
+    /// The item is left in the queue to be processed by a later batch.
+    fn is_skip_requested(&self, item: &QueueItem) -> bool {
+        if !self.tui_state.is_skip_requested(item.hash) {
+            return false;
+        }
+        info!("{} {item} as requested", "Skipping".bold());
+        self.tui_state.set_status(item.hash, ItemStatus::Skipped);
+        true
+    }
 }
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::metadata;
+use std::time::SystemTime;
+
+use clap::ValueEnum;
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::db::Hash;
+use crate::formats::TargetFormatProvider;
+use crate::options::BatchOptions;
+use crate::queue::QueueItem;
+use crate::source::{Source, SourceProvider};
+use crate::torrent::TorrentFile;
+
+/// Criteria to order the items of a batch by.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum BatchOrder {
+    /// Alphabetical order of the source name.
+    Name,
+    /// Smallest content size of the torrent file first.
+    Smallest,
+    /// Fewest seeders on the indexer first.
+    FewestSeeders,
+    /// Oldest modified time of the torrent file first, which is usually when it was
+    /// snatched.
+    OldestSnatch,
+    /// Most target formats missing from the group first.
+    MostFormats,
+}
+
+impl BatchOrder {
+    /// Does the criteria require the source to be retrieved from the API?
+    #[must_use]
+    pub fn is_api_required(self) -> bool {
+        matches!(self, BatchOrder::FewestSeeders | BatchOrder::MostFormats)
+    }
+
+    /// Get the name of the criteria as it's serialized.
+    #[must_use]
+    pub fn get_name(self) -> &'static str {
+        match self {
+            BatchOrder::Name => "name",
+            BatchOrder::Smallest => "smallest",
+            BatchOrder::FewestSeeders => "fewest_seeders",
+            BatchOrder::OldestSnatch => "oldest_snatch",
+            BatchOrder::MostFormats => "most_formats",
+        }
+    }
+}
+
+/// Properties of a queued item that a batch can be ordered by.
+///
+/// A property is `None` if it could not be determined, and such items are ordered last.
+#[derive(Clone, Debug, Default)]
+pub struct BatchCandidate {
+    pub hash: Hash<20>,
+    pub name: String,
+    /// Content size of the torrent file in bytes.
+    pub size: Option<u64>,
+    /// Modified time of the torrent file.
+    pub snatched: Option<SystemTime>,
+    /// Seeders of the source on the indexer.
+    pub seeders: Option<u32>,
+    /// Target formats missing from the group.
+    pub formats: Option<usize>,
+}
+
+/// Order the items of a batch by the `batch_order` criteria.
+#[injectable]
+pub struct BatchPlanner {
+    options: Ref<BatchOptions>,
+    targets: Ref<TargetFormatProvider>,
+}
+
+impl BatchPlanner {
+    /// Order the items of a batch.
+    ///
+    /// If a criteria requires the API then each source is retrieved before the batch
+    /// starts. The sources are returned by id so they're not retrieved again.
+    pub async fn plan(
+        &self,
+        items: Vec<QueueItem>,
+        source_provider: &mut SourceProvider,
+    ) -> (Vec<Hash<20>>, BTreeMap<u32, Source>) {
+        let order = self.options.batch_order.clone().unwrap_or_default();
+        let mut sources = BTreeMap::new();
+        if order.is_empty() || order == [BatchOrder::Name] {
+            return (items.into_iter().map(|item| item.hash).collect(), sources);
+        }
+        let is_api_required = order.iter().any(|order| order.is_api_required());
+        if is_api_required {
+            info!(
+                "{} {} sources to order the batch",
+                "Retrieving".bold(),
+                items.len()
+            );
+        }
+        let mut candidates = Vec::with_capacity(items.len());
+        for item in items {
+            let mut candidate = get_candidate(&item).await;
+            if is_api_required {
+                if let Some(id) = item.id {
+                    if let Ok(source) = source_provider.get(id).await {
+                        candidate.seeders = Some(source.torrent.seeders);
+                        candidate.formats =
+                            Some(self.targets.get(source.format, &source.existing).len());
+                        sources.insert(id, source);
+                    }
+                }
+            }
+            candidates.push(candidate);
+        }
+        sort_candidates(&mut candidates, &order);
+        let names: Vec<&str> = order.iter().map(|order| order.get_name()).collect();
+        debug!("{} batch by {}", "Ordered".bold(), names.join(", "));
+        let hashes = candidates
+            .into_iter()
+            .map(|candidate| candidate.hash)
+            .collect();
+        (hashes, sources)
+    }
+}
+
+/// Get the properties of an item that are read from its torrent file.
+async fn get_candidate(item: &QueueItem) -> BatchCandidate {
+    let path = item.path.as_path();
+    let size = if path.as_os_str().is_empty() {
+        None
+    } else {
+        TorrentFile::read(path)
+            .await
+            .ok()
+            .map(|torrent| torrent.info.get_content_size())
+    };
+    let snatched = metadata(path).and_then(|x| x.modified()).ok();
+    BatchCandidate {
+        hash: item.hash,
+        name: item.name.clone(),
+        size,
+        snatched,
+        ..BatchCandidate::default()
+    }
+}
+
+/// Sort the candidates by each criteria in turn, then by name.
+pub fn sort_candidates(candidates: &mut [BatchCandidate], order: &[BatchOrder]) {
+    candidates.sort_by(|a, b| {
+        order
+            .iter()
+            .fold(Ordering::Equal, |ordering, order| {
+                ordering.then_with(|| compare(a, b, *order))
+            })
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+fn compare(a: &BatchCandidate, b: &BatchCandidate, order: BatchOrder) -> Ordering {
+    match order {
+        BatchOrder::Name => a.name.cmp(&b.name),
+        BatchOrder::Smallest => compare_known_first(a.size, b.size),
+        BatchOrder::FewestSeeders => compare_known_first(a.seeders, b.seeders),
+        BatchOrder::OldestSnatch => compare_known_first(a.snatched, b.snatched),
+        BatchOrder::MostFormats => match (a.formats, b.formats) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (a, b) => compare_known_first(a, b),
+        },
+    }
+}
+
+/// Compare ascending with unknown values last.
+fn compare_known_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl Display for BatchOrder {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_name().fmt(formatter)
+    }
+}
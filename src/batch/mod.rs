@@ -1,3 +1,9 @@
 pub use batch_command::*;
+pub use batch_planner::*;
+pub use source_cleaner::*;
 
 pub(crate) mod batch_command;
+pub(crate) mod batch_planner;
+pub(crate) mod source_cleaner;
+#[cfg(test)]
+mod tests;
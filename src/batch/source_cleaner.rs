@@ -0,0 +1,138 @@
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::info;
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{remove_dir_all, rename};
+
+use crate::errors::{error, path_error};
+use crate::fs::{copy_dir, LinkStrategy};
+use crate::options::BatchOptions;
+use crate::source::Source;
+
+/// What to do with the source directory once every step of the batch succeeded.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+#[value(rename_all = "lowercase")]
+pub enum SourceCleanup {
+    /// Leave the source directory in place.
+    #[default]
+    Keep,
+    /// Move the source directory to `source_archive`.
+    Archive,
+    /// Delete the source directory.
+    Delete,
+}
+
+/// Archive or delete the source directory after a successful batch item.
+#[injectable]
+pub struct SourceCleaner {
+    options: Ref<BatchOptions>,
+}
+
+impl SourceCleaner {
+    /// Is the source directory archived or deleted?
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.options.source_cleanup.unwrap_or_default() != SourceCleanup::Keep
+    }
+
+    /// Is `source_cleanup_dry_run` set?
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.options.source_cleanup_dry_run.unwrap_or_default()
+    }
+
+    /// Archive or delete the directory of a source according to `source_cleanup`.
+    ///
+    /// If `source_cleanup_dry_run` is set then the action is only logged.
+    pub async fn execute(&self, source: &Source) -> Result<(), Error> {
+        let directory = &source.directory;
+        match self.options.source_cleanup.unwrap_or_default() {
+            SourceCleanup::Keep => Ok(()),
+            SourceCleanup::Archive => {
+                let archive = self
+                    .options
+                    .source_archive
+                    .clone()
+                    .expect("source_archive should be set");
+                let target = get_archive_path(directory, &archive)?;
+                if self.is_dry_run() {
+                    info!(
+                        "{} move {} to {}",
+                        "Would".bold(),
+                        directory.display(),
+                        target.display()
+                    );
+                    return Ok(());
+                }
+                archive_dir(directory, &target).await?;
+                info!("{} {source} to {}", "Archived".bold(), target.display());
+                Ok(())
+            }
+            SourceCleanup::Delete => {
+                if self.is_dry_run() {
+                    info!("{} delete {}", "Would".bold(), directory.display());
+                    return Ok(());
+                }
+                remove_dir_all(directory)
+                    .await
+                    .map_err(|e| path_error(e, "delete source", directory))?;
+                info!("{} {source} from {}", "Deleted".bold(), directory.display());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Get the path a source directory is moved to in the archive directory.
+///
+/// Returns an [`Error`] if the path already exists so nothing is overwritten.
+pub fn get_archive_path(directory: &Path, archive: &Path) -> Result<PathBuf, Error> {
+    let action = "archive source";
+    let name = directory.file_name().ok_or_else(|| {
+        error(
+            action,
+            format!("source has no directory name: {}", directory.display()),
+        )
+    })?;
+    let target = archive.join(name);
+    if target.exists() {
+        return Err(error(
+            action,
+            format!("archive already exists: {}", target.display()),
+        ));
+    }
+    Ok(target)
+}
+
+/// Move a directory, copying then deleting it if the target is on another filesystem.
+async fn archive_dir(directory: &Path, target: &Path) -> Result<(), Error> {
+    let action = "archive source";
+    match rename(directory, target).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            copy_dir(directory, target, LinkStrategy::Copy).await?;
+            remove_dir_all(directory)
+                .await
+                .map_err(|e| path_error(e, action, directory))
+        }
+        Err(e) => Err(path_error(e, action, directory)),
+    }
+}
+
+impl Display for SourceCleanup {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceCleanup::Keep => write!(formatter, "keep"),
+            SourceCleanup::Archive => write!(formatter, "archive"),
+            SourceCleanup::Delete => write!(formatter, "delete"),
+        }
+    }
+}
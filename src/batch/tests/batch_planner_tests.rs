@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime};
+
+use crate::batch::{sort_candidates, BatchCandidate, BatchOrder};
+
+fn get_candidate(name: &str, size: Option<u64>, formats: Option<usize>) -> BatchCandidate {
+    BatchCandidate {
+        name: name.to_owned(),
+        size,
+        formats,
+        ..BatchCandidate::default()
+    }
+}
+
+fn get_names(candidates: &[BatchCandidate]) -> Vec<&str> {
+    candidates.iter().map(|x| x.name.as_str()).collect()
+}
+
+#[test]
+fn sort_candidates_smallest_first_with_unknown_last() {
+    // Arrange
+    let mut candidates = vec![
+        get_candidate("a", None, None),
+        get_candidate("b", Some(300), None),
+        get_candidate("c", Some(100), None),
+        get_candidate("d", Some(300), None),
+    ];
+
+    // Act
+    sort_candidates(&mut candidates, &[BatchOrder::Smallest]);
+
+    // Assert
+    assert_eq!(get_names(&candidates), vec!["c", "b", "d", "a"]);
+}
+
+#[test]
+fn sort_candidates_breaks_ties_with_next_criteria() {
+    // Arrange
+    let mut candidates = vec![
+        get_candidate("a", Some(100), Some(1)),
+        get_candidate("b", Some(300), Some(2)),
+        get_candidate("c", Some(200), Some(2)),
+        get_candidate("d", Some(50), None),
+    ];
+
+    // Act
+    sort_candidates(
+        &mut candidates,
+        &[BatchOrder::MostFormats, BatchOrder::Smallest],
+    );
+
+    // Assert
+    assert_eq!(get_names(&candidates), vec!["c", "b", "a", "d"]);
+}
+
+#[test]
+fn sort_candidates_oldest_snatch_first() {
+    // Arrange
+    let now = SystemTime::now();
+    let mut candidates = vec![
+        BatchCandidate {
+            name: "a".to_owned(),
+            snatched: Some(now),
+            ..BatchCandidate::default()
+        },
+        BatchCandidate {
+            name: "b".to_owned(),
+            snatched: Some(now - Duration::from_hours(1)),
+            ..BatchCandidate::default()
+        },
+    ];
+
+    // Act
+    sort_candidates(&mut candidates, &[BatchOrder::OldestSnatch]);
+
+    // Assert
+    assert_eq!(get_names(&candidates), vec!["b", "a"]);
+}
@@ -0,0 +1,2 @@
+mod batch_planner_tests;
+mod source_cleaner_tests;
@@ -0,0 +1,32 @@
+use std::fs::create_dir_all;
+
+use crate::batch::get_archive_path;
+use crate::testing::TempDirectory;
+
+#[test]
+fn source_cleaner_archive_path() {
+    // Arrange
+    let archive = TempDirectory::create("source_cleaner_archive_path");
+    let directory = archive.join("content").join("Artist - Album [2020] [FLAC]");
+
+    // Act
+    let target = get_archive_path(&directory, &archive).expect("path should be available");
+
+    // Assert
+    assert_eq!(target, archive.join("Artist - Album [2020] [FLAC]"));
+}
+
+#[test]
+fn source_cleaner_archive_path_exists() {
+    // Arrange
+    let archive = TempDirectory::create("source_cleaner_archive_path_exists");
+    create_dir_all(archive.join("Album")).expect("Should be able to create dir");
+    let directory = archive.join("content").join("Album");
+
+    // Act
+    let result = get_archive_path(&directory, &archive);
+
+    // Assert
+    let error = result.expect_err("existing archive should not be overwritten");
+    assert!(error.message.starts_with("archive already exists"));
+}
@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{info, trace, warn};
+use rogue_logging::Error;
+use tokio::process::Command;
+
+use crate::beets::BeetsMode;
+use crate::dependencies::BEET;
+use crate::errors::{command_error, OutputHandler};
+use crate::formats::TargetFormat;
+use crate::options::{BeetsOptions, SharedOptions};
+use crate::source::Source;
+
+/// Import transcodes into a beets library.
+///
+/// Transcodes are imported as-is so the tags written by caesura are kept, and the
+/// source is recorded as flexible attributes so it can be queried in beets.
+pub struct BeetsImporter {
+    options: Ref<BeetsOptions>,
+    shared_options: Ref<SharedOptions>,
+}
+
+#[injectable]
+impl BeetsImporter {
+    #[must_use]
+    pub fn new(options: Ref<BeetsOptions>, shared_options: Ref<SharedOptions>) -> Self {
+        Self {
+            options,
+            shared_options,
+        }
+    }
+
+    /// Print or run the import command of a transcode if `beets` is set and the format is
+    /// one of `beets_formats`.
+    ///
+    /// Failures are logged as warnings but don't stop the command.
+    pub async fn execute(&self, source: &Source, format: TargetFormat, dir: &Path) {
+        let Some(mode) = self.options.beets else {
+            return;
+        };
+        let formats = self
+            .options
+            .beets_formats
+            .as_ref()
+            .expect("beets_formats should be set");
+        if !formats.contains(&format) {
+            return;
+        }
+        let args = get_import_args(
+            self.options
+                .beets_args
+                .as_ref()
+                .expect("beets_args should be set"),
+            &self.get_attributes(source, format),
+            dir,
+        );
+        match mode {
+            BeetsMode::Print => {
+                info!("{} {format} with:", "Import".bold());
+                info!("{}", get_command_line(BEET, &args));
+            }
+            BeetsMode::Import => match execute_beet(&args).await {
+                Ok(()) => info!("{} {format} of {source} into beets", "Imported".bold()),
+                Err(error) => {
+                    warn!(
+                        "{} to import {format} of {source} into beets",
+                        "Failed".bold()
+                    );
+                    warn!("{error}");
+                }
+            },
+        }
+    }
+
+    fn get_attributes(&self, source: &Source, format: TargetFormat) -> Vec<(&str, String)> {
+        vec![
+            (
+                "caesura_indexer",
+                self.shared_options.indexer.clone().unwrap_or_default(),
+            ),
+            ("caesura_group_id", source.group.id.to_string()),
+            ("caesura_torrent_id", source.torrent.id.to_string()),
+            ("caesura_format", format.to_string()),
+        ]
+    }
+}
+
+/// Get the arguments of `beet` to import a directory.
+///
+/// `attributes` are set on each imported item with `--set`.
+#[must_use]
+pub fn get_import_args(args: &[String], attributes: &[(&str, String)], dir: &Path) -> Vec<String> {
+    let mut output = vec!["import".to_owned()];
+    output.extend(args.iter().cloned());
+    for (key, value) in attributes {
+        output.push("--set".to_owned());
+        output.push(format!("{key}={value}"));
+    }
+    output.push(dir.to_string_lossy().to_string());
+    output
+}
+
+/// Get a command line that can be pasted into a POSIX shell.
+#[must_use]
+pub fn get_command_line(program: &str, args: &[String]) -> String {
+    let mut parts = vec![program.to_owned()];
+    parts.extend(args.iter().map(|arg| quote(arg)));
+    parts.join(" ")
+}
+
+fn quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_=./,:@+%".contains(c));
+    if is_safe {
+        arg.to_owned()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+async fn execute_beet(args: &[String]) -> Result<(), Error> {
+    let output = Command::new(BEET)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| command_error(e, "import into beets", BEET))?;
+    let output = OutputHandler::execute(output, "import into beets", BEET)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        trace!("{}", stdout.trim_end());
+    }
+    Ok(())
+}
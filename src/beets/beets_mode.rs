@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How transcodes are handed to a beets library.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BeetsMode {
+    /// Log a `beet import` command for each transcode so it can be run later.
+    Print,
+    /// Run `beet import` for each transcode.
+    Import,
+}
@@ -0,0 +1,7 @@
+pub use beets_importer::*;
+pub use beets_mode::*;
+
+pub(crate) mod beets_importer;
+pub(crate) mod beets_mode;
+#[cfg(test)]
+mod tests;
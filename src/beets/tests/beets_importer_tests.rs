@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use crate::beets::{get_command_line, get_import_args};
+
+#[test]
+fn get_import_args_sets_attributes_before_dir() {
+    // Arrange
+    let args = vec!["--quiet".to_owned(), "--noautotag".to_owned()];
+    let attributes = vec![
+        ("caesura_indexer", "red".to_owned()),
+        ("caesura_torrent_id", "123".to_owned()),
+    ];
+
+    // Act
+    let output = get_import_args(&args, &attributes, Path::new("/output/Album [V0]"));
+
+    // Assert
+    assert_eq!(
+        output,
+        vec![
+            "import",
+            "--quiet",
+            "--noautotag",
+            "--set",
+            "caesura_indexer=red",
+            "--set",
+            "caesura_torrent_id=123",
+            "/output/Album [V0]",
+        ]
+    );
+}
+
+#[test]
+fn get_command_line_quotes_unsafe_args() {
+    // Arrange
+    let args = vec![
+        "import".to_owned(),
+        "--quiet".to_owned(),
+        "/output/Artist's Album [V0]".to_owned(),
+    ];
+
+    // Act
+    let output = get_command_line("beet", &args);
+
+    // Assert
+    assert_eq!(
+        output,
+        "beet import --quiet '/output/Artist'\\''s Album [V0]'"
+    );
+}
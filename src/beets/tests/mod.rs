@@ -0,0 +1 @@
+mod beets_importer_tests;
@@ -0,0 +1,275 @@
+use std::collections::BTreeSet;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info, warn};
+use rogue_logging::Error;
+use tokio::fs::{remove_dir_all, remove_file};
+
+use crate::clean::{
+    get_expired_entries, get_output_names, is_content_present, is_expired, is_inside, ArtifactKind,
+    StaleArtifact, StaleReason,
+};
+use crate::errors::path_error;
+use crate::fs::{PathManager, STAGING_DIR_NAME};
+use crate::options::{CacheOptions, CleanArgs, OptionRule, Options, SharedOptions};
+use crate::queue::{Queue, QueueItem};
+use crate::summary::RunSummary;
+use crate::torrent::TorrentFile;
+
+/// Directories of the cache with entries that are removed once older than `max_age`.
+///
+/// Each entry is created again when it's next required.
+const EXPIRING_CACHE_DIRS: [&str; 4] = ["torrents", "verify", "musicbrainz", "hash_check"];
+
+/// Remove files and directories that are no longer required.
+///
+/// - Spectrograms of queued sources that are no longer in a content directory, if they're
+///   in the cache or staging directory
+/// - Staging directories of sources that are no longer in a content directory, or that
+///   have not been modified within `max_age`, as left by a failed or interrupted transcode
+/// - Cache entries that have not been modified within `max_age`
+/// - `.torrent` files in the cache or output directory whose content was deleted
+///
+/// Only the cache and staging directories are written to by caesura alone so nothing else
+/// is removed apart from `.torrent` files. Spectrograms in the output directory or beside
+/// the source may be in a layout managed by the user so they're kept.
+///
+/// If `dry_run` is set then each file or directory is only logged.
+#[injectable]
+pub struct CleanCommand {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    args: Ref<CleanArgs>,
+    paths: Ref<PathManager>,
+    summary: Ref<RunSummary>,
+}
+
+impl CleanCommand {
+    /// Execute [`CleanCommand`] from the CLI.
+    ///
+    /// Returns `false` if any file or directory could not be removed.
+    pub async fn execute_cli(&self) -> Result<bool, Error> {
+        let errors = [
+            self.shared_options.get_errors(),
+            self.cache_options.get_errors(),
+            self.args.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
+        let artifacts = self.get_stale().await?;
+        if self.summary.is_json() {
+            self.summary.set_data(&artifacts);
+        }
+        if artifacts.is_empty() {
+            info!("{} stale files or directories", "No".bold());
+            return Ok(true);
+        }
+        if self.args.dry_run.expect("dry_run should be set") {
+            for artifact in &artifacts {
+                info!("{} remove {artifact}", "Would".bold());
+            }
+            info!(
+                "{} remove {} stale files or directories",
+                "Would".bold(),
+                artifacts.len()
+            );
+            return Ok(true);
+        }
+        let mut removed = 0;
+        for artifact in &artifacts {
+            match remove(&artifact.path).await {
+                Ok(()) => {
+                    debug!("{} {artifact}", "Removed".bold());
+                    removed += 1;
+                }
+                Err(e) => warn!(
+                    "{} to remove {}: {e}",
+                    "Failed".bold(),
+                    artifact.path.display()
+                ),
+            }
+        }
+        info!("{} {removed} stale files or directories", "Removed".bold());
+        Ok(removed == artifacts.len())
+    }
+
+    /// Get the files and directories to remove.
+    ///
+    /// Each path is included once, for the first reason found.
+    pub async fn get_stale(&self) -> Result<Vec<StaleArtifact>, Error> {
+        let now = SystemTime::now();
+        let max_age = self.args.get_max_age().expect("max_age should be set");
+        let mut queue = Queue::from_options(self.cache_options.clone());
+        let items = queue.get_all().await?;
+        let missing: Vec<&QueueItem> = items
+            .values()
+            .filter(|item| self.is_source_missing(item))
+            .collect();
+        let mut artifacts = Vec::new();
+        artifacts.append(&mut self.get_spectrograms(&missing));
+        artifacts.append(&mut self.get_staging(&missing, now, max_age));
+        artifacts.append(&mut self.get_expired_cache(now, max_age));
+        artifacts.append(&mut self.get_orphaned_torrents().await);
+        let mut paths = BTreeSet::new();
+        artifacts.retain(|artifact| paths.insert(artifact.path.clone()));
+        Ok(artifacts)
+    }
+
+    fn is_source_missing(&self, item: &QueueItem) -> bool {
+        !item.name.is_empty() && !is_content_present(&self.get_content(), &item.name)
+    }
+
+    /// Get the spectrogram directories of sources that are no longer present.
+    ///
+    /// Spectrograms outside the cache and staging directories are kept.
+    fn get_spectrograms(&self, missing: &[&QueueItem]) -> Vec<StaleArtifact> {
+        let owned = [self.paths.get_cache_dir(), self.get_staging_dir()];
+        missing
+            .iter()
+            .filter_map(|item| item.spectrogram.as_ref()?.path.clone())
+            .filter(|path| path.is_dir())
+            .filter(|path| {
+                let is_owned = is_inside(path, &owned);
+                if !is_owned {
+                    debug!(
+                        "{} spectrograms outside the cache and staging directories: {}",
+                        "Keeping".bold(),
+                        path.display()
+                    );
+                }
+                is_owned
+            })
+            .map(|path| StaleArtifact {
+                kind: ArtifactKind::Spectrograms,
+                reason: StaleReason::SourceMissing,
+                path,
+            })
+            .collect()
+    }
+
+    fn get_staging(
+        &self,
+        missing: &[&QueueItem],
+        now: SystemTime,
+        max_age: Duration,
+    ) -> Vec<StaleArtifact> {
+        let missing: BTreeSet<String> = missing
+            .iter()
+            .filter_map(|item| item.id)
+            .map(|id| id.to_string())
+            .collect();
+        get_entries(&self.get_staging_dir())
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                let reason = if missing.contains(&name) {
+                    StaleReason::SourceMissing
+                } else if is_expired(&path, now, max_age) {
+                    StaleReason::Expired
+                } else {
+                    return None;
+                };
+                Some(StaleArtifact {
+                    kind: ArtifactKind::Staging,
+                    reason,
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    fn get_expired_cache(&self, now: SystemTime, max_age: Duration) -> Vec<StaleArtifact> {
+        let cache = self.paths.get_cache_dir();
+        EXPIRING_CACHE_DIRS
+            .iter()
+            .flat_map(|dir| get_expired_entries(&cache.join(dir), now, max_age))
+            .map(|path| StaleArtifact {
+                kind: ArtifactKind::Cache,
+                reason: StaleReason::Expired,
+                path,
+            })
+            .collect()
+    }
+
+    /// Get the `.torrent` files whose content was deleted.
+    ///
+    /// The content of a cached source `.torrent` file must be in a content directory, and
+    /// of a transcode `.torrent` file must be a directory or archive at any depth of the
+    /// output directory so transcodes created with an earlier `output_layout` are found.
+    async fn get_orphaned_torrents(&self) -> Vec<StaleArtifact> {
+        let content = self.get_content();
+        let output = self.paths.get_output_dir();
+        let transcodes = get_output_names(&output);
+        let mut artifacts = Vec::new();
+        let cached = self.paths.get_cache_dir().join("torrents");
+        for path in get_torrent_files(&cached) {
+            if let Ok(torrent) = TorrentFile::read(&path).await {
+                if !is_content_present(&content, &torrent.info.name) {
+                    artifacts.push(get_orphaned_torrent(path));
+                }
+            }
+        }
+        for path in get_torrent_files(&output) {
+            if let Ok(torrent) = TorrentFile::read(&path).await {
+                if !transcodes.contains(&torrent.info.name) {
+                    artifacts.push(get_orphaned_torrent(path));
+                }
+            }
+        }
+        artifacts
+    }
+
+    fn get_staging_dir(&self) -> PathBuf {
+        self.paths.get_output_dir().join(STAGING_DIR_NAME)
+    }
+
+    fn get_content(&self) -> Vec<PathBuf> {
+        self.shared_options
+            .content
+            .clone()
+            .expect("content should be set")
+    }
+}
+
+fn get_orphaned_torrent(path: PathBuf) -> StaleArtifact {
+    StaleArtifact {
+        kind: ArtifactKind::Torrent,
+        reason: StaleReason::ContentMissing,
+        path,
+    }
+}
+
+fn get_entries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    paths
+}
+
+fn get_torrent_files(dir: &Path) -> Vec<PathBuf> {
+    get_entries(dir)
+        .into_iter()
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "torrent")
+        })
+        .collect()
+}
+
+async fn remove(path: &Path) -> Result<(), Error> {
+    let result = if path.is_dir() {
+        remove_dir_all(path).await
+    } else {
+        remove_file(path).await
+    };
+    result.map_err(|e| path_error(e, "remove stale file", path))
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+use std::fs::{canonicalize, read_dir};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::fs::ArchiveFormat;
+use crate::source::get_source_directories;
+use crate::watch::get_last_modified;
+
+/// Has neither the path nor any of its descendants been modified within `max_age`?
+///
+/// Returns `false` if the modified time can't be read.
+#[must_use]
+pub fn is_expired(path: &Path, now: SystemTime, max_age: Duration) -> bool {
+    get_last_modified(path)
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age > max_age)
+}
+
+/// Get the entries of a directory that are [`is_expired`].
+///
+/// Returns an empty list if the directory doesn't exist.
+#[must_use]
+pub fn get_expired_entries(dir: &Path, now: SystemTime, max_age: Duration) -> Vec<PathBuf> {
+    let Ok(entries) = read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut expired: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_expired(path, now, max_age))
+        .collect();
+    expired.sort();
+    expired
+}
+
+/// Is the source at `path` in any of the content directories?
+///
+/// The source is found the same way as by the other commands.
+#[must_use]
+pub fn is_content_present(content: &[PathBuf], path: &str) -> bool {
+    !get_source_directories(content, path).is_empty()
+}
+
+/// Get the names of every directory and archive below `dir`.
+///
+/// The depth of the transcodes depends on the `output_layout` they were created with so
+/// every depth is included. An archive is included by the name of the directory it was
+/// created from.
+///
+/// Hidden directories such as the staging directory are skipped.
+#[must_use]
+pub fn get_output_names(dir: &Path) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if is_hidden(&path) {
+                continue;
+            }
+            let Some(name) = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            if path.is_dir() {
+                names.insert(name);
+                dirs.push(path);
+            } else if let Some(name) = strip_archive_extension(&name) {
+                names.insert(name.to_owned());
+            }
+        }
+    }
+    names
+}
+
+fn strip_archive_extension(name: &str) -> Option<&str> {
+    [ArchiveFormat::Zip, ArchiveFormat::TarGz]
+        .iter()
+        .find_map(|format| name.strip_suffix(&format!(".{}", format.get_extension())))
+}
+
+/// Is `path` inside one of `dirs`?
+///
+/// Both are resolved to absolute paths first. Returns `false` if either can't be
+/// resolved, or if `path` is one of `dirs`.
+#[must_use]
+pub fn is_inside(path: &Path, dirs: &[PathBuf]) -> bool {
+    let Ok(path) = canonicalize(path) else {
+        return false;
+    };
+    dirs.iter()
+        .filter_map(|dir| canonicalize(dir).ok())
+        .any(|dir| path != dir && path.starts_with(&dir))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
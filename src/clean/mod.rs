@@ -0,0 +1,9 @@
+pub use clean_command::*;
+pub use clean_helpers::*;
+pub use stale_artifact::*;
+
+pub(crate) mod clean_command;
+pub(crate) mod clean_helpers;
+pub(crate) mod stale_artifact;
+#[cfg(test)]
+mod tests;
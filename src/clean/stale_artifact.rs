@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+/// Kind of file or directory removed by [`CleanCommand`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Spectrogram directory of a source.
+    Spectrograms,
+    /// Staging directory of a transcode that failed or was interrupted.
+    Staging,
+    /// Cached `.torrent` file, verification, `MusicBrainz` match or hash check link.
+    Cache,
+    /// `.torrent` file of a source or transcode.
+    Torrent,
+}
+
+/// Reason a file or directory is removed by [`CleanCommand`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleReason {
+    /// The source is no longer in any of the content directories.
+    SourceMissing,
+    /// It has not been modified within `max_age`.
+    Expired,
+    /// The content of the `.torrent` file has been deleted.
+    ContentMissing,
+}
+
+/// A file or directory that's no longer required.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StaleArtifact {
+    pub kind: ArtifactKind,
+    pub reason: StaleReason,
+    pub path: PathBuf,
+}
+
+impl ArtifactKind {
+    /// Get a description of the kind.
+    #[must_use]
+    pub fn get_name(self) -> &'static str {
+        match self {
+            ArtifactKind::Spectrograms => "spectrograms",
+            ArtifactKind::Staging => "staging directory",
+            ArtifactKind::Cache => "cache entry",
+            ArtifactKind::Torrent => "torrent file",
+        }
+    }
+}
+
+impl Display for ArtifactKind {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_name().fmt(formatter)
+    }
+}
+
+impl Display for StaleReason {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            StaleReason::SourceMissing => "the source is no longer present",
+            StaleReason::Expired => "it's older than max_age",
+            StaleReason::ContentMissing => "its content was deleted",
+        };
+        output.fmt(formatter)
+    }
+}
+
+impl Display for StaleArtifact {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} {} as {}",
+            self.kind,
+            self.path.display(),
+            self.reason
+        )
+    }
+}
@@ -0,0 +1,164 @@
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+
+use di::Ref;
+use rogue_logging::Error;
+
+use crate::clean::{ArtifactKind, CleanCommand};
+use crate::db::Hash;
+use crate::fs::ArchiveFormat;
+use crate::hosting::HostBuilder;
+use crate::options::{CacheOptions, FileOptions, SharedOptions};
+use crate::queue::{Queue, QueueItem, TimeStamp};
+use crate::spectrogram::SpectrogramStatus;
+use crate::testing::options::TestOptionsFactory;
+use crate::testing::TempDirectory;
+use crate::torrent::{PieceLength, TorrentCreator, TorrentVersion};
+
+struct CleanDirs {
+    content: PathBuf,
+    output: PathBuf,
+    cache: PathBuf,
+    scratch: PathBuf,
+}
+
+impl CleanDirs {
+    fn create(name: &str) -> Self {
+        let dir = TempDirectory::create(name);
+        let dirs = Self {
+            content: dir.join("content"),
+            output: dir.join("output"),
+            cache: dir.join("cache"),
+            scratch: dir.join("scratch"),
+        };
+        for dir in [&dirs.content, &dirs.output, &dirs.cache, &dirs.scratch] {
+            create_dir_all(dir).expect("should create dir");
+        }
+        dirs
+    }
+
+    fn get_command(&self, output_layout: &str) -> Ref<CleanCommand> {
+        let shared_options = TestOptionsFactory::from(SharedOptions {
+            content: Some(vec![self.content.clone()]),
+            output: Some(self.output.clone()),
+            output_layout: Some(output_layout.to_owned()),
+            ..SharedOptions::default()
+        });
+        let cache_options = TestOptionsFactory::from(CacheOptions {
+            cache: Some(self.cache.clone()),
+        });
+        let file_options = TestOptionsFactory::from(FileOptions {
+            archive: Some(ArchiveFormat::Zip),
+            ..FileOptions::default()
+        });
+        let host = HostBuilder::new()
+            .with_options(shared_options)
+            .with_options(cache_options)
+            .with_options(file_options)
+            .build();
+        host.services.get_required::<CleanCommand>()
+    }
+
+    /// Create a `.torrent` file for content named `name` without keeping the content.
+    async fn create_torrent(&self, name: &str, path: &Path) -> Result<(), Error> {
+        let content_dir = self.scratch.join(name);
+        create_dir_all(&content_dir).expect("should create content dir");
+        write(content_dir.join("01 Track.mp3"), vec![1; 1_000]).expect("should write file");
+        TorrentCreator::create(
+            &content_dir,
+            path,
+            "https://example.com/announce".to_owned(),
+            "red".to_owned(),
+            &PieceLength::default(),
+            TorrentVersion::V1,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn clean_get_stale_finds_transcodes_of_any_layout_and_archives() -> Result<(), Error> {
+    // Arrange
+    let dirs = CleanDirs::create("clean_get_stale_layout_and_archives");
+    let nested = dirs.output.join("A").join("Artist").join("Album [320]");
+    create_dir_all(&nested).expect("should create transcode dir");
+    dirs.create_torrent("Album [320]", &dirs.output.join("Album [320].red.torrent"))
+        .await?;
+    // Created by an earlier output_layout at a different depth
+    create_dir_all(dirs.output.join("Flat [V0]")).expect("should create transcode dir");
+    dirs.create_torrent("Flat [V0]", &dirs.output.join("Flat [V0].red.torrent"))
+        .await?;
+    // Only the archive is kept once the upload is done
+    write(dirs.output.join("A").join("Archived [FLAC].zip"), b"zip").expect("should write archive");
+    dirs.create_torrent(
+        "Archived [FLAC]",
+        &dirs.output.join("Archived [FLAC].red.torrent"),
+    )
+    .await?;
+    let orphan = dirs.output.join("Orphan [320].red.torrent");
+    dirs.create_torrent("Orphan [320]", &orphan).await?;
+    let command = dirs.get_command("{artist_initial}/{artist}/{album} [{format}]");
+
+    // Act
+    let artifacts = command.get_stale().await?;
+
+    // Assert
+    let paths: Vec<PathBuf> = artifacts
+        .into_iter()
+        .map(|artifact| artifact.path)
+        .collect();
+    assert_eq!(paths, vec![orphan]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_get_stale_keeps_spectrograms_outside_cache() -> Result<(), Error> {
+    // Arrange
+    let dirs = CleanDirs::create("clean_get_stale_spectrograms");
+    let output_spectrograms = dirs.output.join("spectrograms").join("Missing - Album");
+    let cached_spectrograms = dirs.cache.join("spectrograms").join("Other - Album");
+    create_dir_all(&output_spectrograms).expect("should create spectrogram dir");
+    create_dir_all(&cached_spectrograms).expect("should create spectrogram dir");
+    let spectrogram = |path: &Path| SpectrogramStatus {
+        success: true,
+        path: Some(path.to_path_buf()),
+        count: 1,
+        completed: TimeStamp::now(),
+        error: None,
+    };
+    let mut queue = Queue::from_options(Ref::new(CacheOptions {
+        cache: Some(dirs.cache.clone()),
+    }));
+    queue
+        .set(QueueItem {
+            name: "Missing - Album".to_owned(),
+            hash: Hash::<20>::from_string("0100000000000000000000000000000000000000")?,
+            id: Some(1),
+            spectrogram: Some(spectrogram(&output_spectrograms)),
+            ..QueueItem::default()
+        })
+        .await?;
+    queue
+        .set(QueueItem {
+            name: "Other - Album".to_owned(),
+            hash: Hash::<20>::from_string("0200000000000000000000000000000000000000")?,
+            id: Some(2),
+            spectrogram: Some(spectrogram(&cached_spectrograms)),
+            ..QueueItem::default()
+        })
+        .await?;
+    let command = dirs.get_command("{artist} - {album} [{format}]");
+
+    // Act
+    let artifacts = command.get_stale().await?;
+
+    // Assert
+    let spectrograms: Vec<PathBuf> = artifacts
+        .into_iter()
+        .filter(|artifact| artifact.kind == ArtifactKind::Spectrograms)
+        .map(|artifact| artifact.path)
+        .collect();
+    assert_eq!(spectrograms, vec![cached_spectrograms]);
+    Ok(())
+}
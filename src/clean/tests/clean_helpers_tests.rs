@@ -0,0 +1,178 @@
+use std::collections::BTreeSet;
+use std::fs::{create_dir, create_dir_all, File};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::clean::*;
+use crate::testing::TempDirectory;
+
+const DAY: Duration = Duration::from_hours(24);
+
+fn create_file(path: &Path, age: Duration) {
+    let file = File::create(path).expect("Should be able to create file");
+    file.set_modified(SystemTime::now() - age)
+        .expect("Should be able to set modified time");
+}
+
+#[test]
+fn is_expired_file() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let path = dir.join("123.red.torrent");
+    create_file(&path, DAY * 2);
+    let now = SystemTime::now();
+
+    // Act
+    let is_expired_after_day = is_expired(&path, now, DAY);
+    let is_expired_after_week = is_expired(&path, now, DAY * 7);
+
+    // Assert
+    assert!(is_expired_after_day);
+    assert!(!is_expired_after_week);
+}
+
+#[test]
+fn is_expired_directory_uses_descendants() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let staging = dir.join("123");
+    create_dir(&staging).expect("Should be able to create dir");
+    create_file(&staging.join("01.flac"), DAY * 2);
+
+    // Act
+    let is_staging_expired = is_expired(&staging, SystemTime::now(), DAY);
+
+    // Assert
+    assert!(!is_staging_expired);
+}
+
+#[test]
+fn is_expired_missing() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+
+    // Act
+    let is_missing_expired = is_expired(&dir.join("missing"), SystemTime::now(), Duration::ZERO);
+
+    // Assert
+    assert!(!is_missing_expired);
+}
+
+#[test]
+fn get_expired_entries_old_only() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    create_file(&dir.join("old.yml"), DAY * 2);
+    create_file(&dir.join("new.yml"), Duration::ZERO);
+
+    // Act
+    let expired = get_expired_entries(&dir, SystemTime::now(), DAY);
+    let missing = get_expired_entries(&dir.join("missing"), SystemTime::now(), DAY);
+
+    // Assert
+    assert_eq!(expired, vec![dir.join("old.yml")]);
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn is_content_present_directory() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let content = vec![dir.join("missing"), dir.clone()];
+    create_dir(dir.join("Artist - Album [2020] [FLAC]")).expect("Should be able to create dir");
+    create_file(&dir.join("file.flac"), Duration::ZERO);
+
+    // Act
+    let is_album_present = is_content_present(&content, "Artist - Album [2020] [FLAC]");
+    let is_other_present = is_content_present(&content, "Other - Album [2020] [FLAC]");
+    let is_file_present = is_content_present(&content, "file.flac");
+
+    // Assert
+    assert!(is_album_present);
+    assert!(!is_other_present);
+    assert!(!is_file_present);
+}
+
+#[test]
+fn is_content_present_html_encoded() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let content = vec![dir.clone()];
+    create_dir(dir.join("Artist & Band - Album [2020] [FLAC]"))
+        .expect("Should be able to create dir");
+
+    // Act
+    let is_present = is_content_present(&content, "Artist &amp; Band - Album [2020] [FLAC]");
+
+    // Assert
+    assert!(is_present);
+}
+
+#[test]
+fn get_output_names_includes_every_depth_and_archives() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    create_dir_all(dir.join("A").join("Artist").join("Album [320]"))
+        .expect("Should be able to create dir");
+    create_dir_all(dir.join("Other [V0]")).expect("Should be able to create dir");
+    create_dir_all(dir.join(".staging").join("123")).expect("Should be able to create dir");
+    create_file(&dir.join("Archived [FLAC].zip"), Duration::ZERO);
+    create_file(&dir.join("Album [320].torrent"), Duration::ZERO);
+
+    // Act
+    let names = get_output_names(&dir);
+
+    // Assert
+    assert_eq!(
+        names,
+        BTreeSet::from([
+            "A".to_owned(),
+            "Album [320]".to_owned(),
+            "Archived [FLAC]".to_owned(),
+            "Artist".to_owned(),
+            "Other [V0]".to_owned(),
+        ])
+    );
+}
+
+#[test]
+fn is_inside_requires_a_descendant() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let cache = dir.join("cache");
+    let output = dir.join("output");
+    create_dir_all(cache.join("spectrograms")).expect("Should be able to create dir");
+    create_dir_all(output.join("spectrograms")).expect("Should be able to create dir");
+    let dirs = vec![cache.clone()];
+
+    // Act
+    let is_cache_inside = is_inside(&cache.join("spectrograms"), &dirs);
+    let is_cache_itself_inside = is_inside(&cache, &dirs);
+    let is_output_inside = is_inside(&output.join("spectrograms"), &dirs);
+    let is_escaped_inside = is_inside(&cache.join("..").join("output"), &dirs);
+
+    // Assert
+    assert!(is_cache_inside);
+    assert!(!is_cache_itself_inside);
+    assert!(!is_output_inside);
+    assert!(!is_escaped_inside);
+}
+
+#[test]
+fn stale_artifact_display() {
+    // Arrange
+    let artifact = StaleArtifact {
+        kind: ArtifactKind::Staging,
+        reason: StaleReason::SourceMissing,
+        path: Path::new("output/.staging/123").to_path_buf(),
+    };
+
+    // Act
+    let output = artifact.to_string();
+
+    // Assert
+    assert_eq!(
+        output,
+        "staging directory output/.staging/123 as the source is no longer present"
+    );
+}
@@ -0,0 +1,2 @@
+mod clean_command_tests;
+mod clean_helpers_tests;
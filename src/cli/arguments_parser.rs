@@ -30,6 +30,24 @@ impl ArgumentsParser {
         }
     }
 
+    /// Get the name of the command by parsing the arguments, such as `cross_seed`.
+    ///
+    /// The name is in snake case so it matches the sections of the config file.
+    #[must_use]
+    pub fn get_command_name() -> Option<String> {
+        let matches = ArgumentsParser::command().try_get_matches().ok()?;
+        matches.subcommand_name().map(to_section_name)
+    }
+
+    /// Get the name of every command in snake case.
+    #[must_use]
+    pub fn get_command_names() -> Vec<String> {
+        ArgumentsParser::command()
+            .get_subcommands()
+            .map(|command| to_section_name(command.get_name()))
+            .collect()
+    }
+
     /// Get the [`CommandArguments`] by parsing the arguments.
     #[must_use]
     pub fn get() -> Option<CommandArguments> {
@@ -39,3 +57,7 @@ impl ArgumentsParser {
         }
     }
 }
+
+fn to_section_name(name: &str) -> String {
+    name.replace('-', "_")
+}
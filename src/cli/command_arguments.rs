@@ -3,15 +3,42 @@ use clap::Subcommand;
 use crate::options::source_arg::SourceArg;
 use crate::options::verify_options::VerifyOptions;
 use crate::options::{
-    BatchOptions, CacheOptions, CopyOptions, FileOptions, QueueAddArgs, RunnerOptions,
-    SharedOptions, SpectrogramOptions, TargetOptions, UploadOptions,
+    BatchOptions, BeetsOptions, CacheOptions, CleanArgs, CopyOptions, CrossSeedOptions,
+    DesktopOptions, EnrichOptions, FileOptions, HistoryArgs, HistoryOptions, HookOptions,
+    NotifyOptions, PermissionOptions, QueueAddArgs, RunnerOptions, SelfUpdateArgs, ServeOptions,
+    SharedOptions, SpectrogramOptions, TargetOptions, TorrentOptions, UploadOptions, WatchOptions,
 };
 
 /// Cli sub-commands and arguments
 #[derive(Subcommand, Debug, Clone)]
 pub enum CommandArguments {
     /// Read the config file if it exists and concatenate default values.
-    Config,
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommandArguments>,
+    },
+
+    /// Interactively create a config file.
+    Init {
+        #[command(flatten)]
+        shared: SharedOptions,
+    },
+
+    /// Check the dependencies, options, API key and directories are working.
+    Doctor {
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+    },
+
+    /// Update caesura to the latest release.
+    SelfUpdate {
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        args: SelfUpdateArgs,
+    },
 
     /// Verify, transcode, and upload from multiple FLAC sources in one command.
     Batch {
@@ -28,13 +55,47 @@ pub enum CommandArguments {
         #[command(flatten)]
         copy: CopyOptions,
         #[command(flatten)]
+        torrent: TorrentOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        beets: BeetsOptions,
+        #[command(flatten)]
         file: FileOptions,
         #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
         batch: BatchOptions,
         #[command(flatten)]
         cache: CacheOptions,
         #[command(flatten)]
         upload: UploadOptions,
+        #[command(flatten)]
+        notify: NotifyOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+        #[command(flatten)]
+        desktop: DesktopOptions,
+    },
+
+    /// Show the history of processed sources.
+    History {
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        args: HistoryArgs,
+    },
+
+    /// Remove stale spectrograms, staging directories, cache entries and `.torrent` files.
+    Clean {
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        args: CleanArgs,
     },
 
     /// Add FLAC sources to the queue without transcoding
@@ -43,6 +104,18 @@ pub enum CommandArguments {
         command: QueueCommandArguments,
     },
 
+    /// Find where a FLAC source and its transcodes can be cross-seeded on other indexers.
+    CrossSeed {
+        #[command(flatten)]
+        source: SourceArg,
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        cross_seed: CrossSeedOptions,
+    },
+
     /// Generate spectrograms for each track of a FLAC source.
     Spectrogram {
         #[command(flatten)]
@@ -52,7 +125,11 @@ pub enum CommandArguments {
         #[command(flatten)]
         spectrogram: SpectrogramOptions,
         #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
         runner: RunnerOptions,
+        #[command(flatten)]
+        desktop: DesktopOptions,
     },
 
     /// Transcode each track of a FLAC source to the target formats.
@@ -66,9 +143,27 @@ pub enum CommandArguments {
         #[command(flatten)]
         copy: CopyOptions,
         #[command(flatten)]
+        torrent: TorrentOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        beets: BeetsOptions,
+        #[command(flatten)]
         file: FileOptions,
         #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
         runner: RunnerOptions,
+        #[command(flatten)]
+        notify: NotifyOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        history: HistoryOptions,
+        #[command(flatten)]
+        desktop: DesktopOptions,
     },
 
     /// Upload transcodes of a FLAC source.
@@ -81,6 +176,18 @@ pub enum CommandArguments {
         target: TargetOptions,
         #[command(flatten)]
         upload: UploadOptions,
+        #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        notify: NotifyOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        desktop: DesktopOptions,
     },
 
     /// Verify a FLAC source is suitable for transcoding.
@@ -93,6 +200,159 @@ pub enum CommandArguments {
         target: TargetOptions,
         #[command(flatten)]
         verify: VerifyOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        history: HistoryOptions,
+        #[command(flatten)]
+        desktop: DesktopOptions,
+    },
+
+    /// Serve an HTTP API to add sources to the queue and batch process them.
+    Serve {
+        #[command(flatten)]
+        serve: ServeOptions,
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        verify: VerifyOptions,
+        #[command(flatten)]
+        runner: RunnerOptions,
+        #[command(flatten)]
+        spectrogram: SpectrogramOptions,
+        #[command(flatten)]
+        copy: CopyOptions,
+        #[command(flatten)]
+        torrent: TorrentOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        beets: BeetsOptions,
+        #[command(flatten)]
+        file: FileOptions,
+        #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
+        batch: BatchOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        upload: UploadOptions,
+        #[command(flatten)]
+        notify: NotifyOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+    },
+
+    /// Watch a directory and batch process new `.torrent` files.
+    Watch {
+        #[command(flatten)]
+        watch: WatchOptions,
+        #[command(flatten)]
+        shared: SharedOptions,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        verify: VerifyOptions,
+        #[command(flatten)]
+        runner: RunnerOptions,
+        #[command(flatten)]
+        spectrogram: SpectrogramOptions,
+        #[command(flatten)]
+        copy: CopyOptions,
+        #[command(flatten)]
+        torrent: TorrentOptions,
+        #[command(flatten)]
+        enrich: EnrichOptions,
+        #[command(flatten)]
+        beets: BeetsOptions,
+        #[command(flatten)]
+        file: FileOptions,
+        #[command(flatten)]
+        permission: PermissionOptions,
+        #[command(flatten)]
+        batch: BatchOptions,
+        #[command(flatten)]
+        cache: CacheOptions,
+        #[command(flatten)]
+        upload: UploadOptions,
+        #[command(flatten)]
+        notify: NotifyOptions,
+        #[command(flatten)]
+        hook: HookOptions,
+    },
+}
+
+impl CommandArguments {
+    /// Get the name of the subcommand as it's entered on the command line.
+    #[must_use]
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            CommandArguments::Config { command: None } => "config",
+            CommandArguments::Config {
+                command: Some(ConfigCommandArguments::SetKey { .. }),
+            } => "config set-key",
+            CommandArguments::Config {
+                command: Some(ConfigCommandArguments::Show { .. }),
+            } => "config show",
+            CommandArguments::Init { .. } => "init",
+            CommandArguments::Doctor { .. } => "doctor",
+            CommandArguments::SelfUpdate { .. } => "self-update",
+            CommandArguments::Batch { .. } => "batch",
+            CommandArguments::History { .. } => "history",
+            CommandArguments::Clean { .. } => "clean",
+            CommandArguments::Queue {
+                command: QueueCommandArguments::Add { .. },
+            } => "queue add",
+            CommandArguments::Queue {
+                command: QueueCommandArguments::List { .. },
+            } => "queue list",
+            CommandArguments::Queue {
+                command: QueueCommandArguments::Summary { .. },
+            } => "queue summary",
+            CommandArguments::CrossSeed { .. } => "cross-seed",
+            CommandArguments::Spectrogram { .. } => "spectrogram",
+            CommandArguments::Transcode { .. } => "transcode",
+            CommandArguments::Upload { .. } => "upload",
+            CommandArguments::Verify { .. } => "verify",
+            CommandArguments::Serve { .. } => "serve",
+            CommandArguments::Watch { .. } => "watch",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommandArguments {
+    /// Read the config file if it exists and concatenate default values.
+    Show {
+        #[command(flatten)]
+        shared: SharedOptions,
+        /// Annotate each value with where it came from.
+        ///
+        /// The origin is the default, the config file and line, an environment variable,
+        /// a command line argument or the OS credential store.
+        #[arg(long, default_value_t = false)]
+        origin: bool,
+    },
+
+    /// Store the API key in the OS credential store.
+    ///
+    /// The API key is prompted for so it's not saved in the shell history.
+    SetKey {
+        /// Store the session cookie of the indexer website instead of the API key.
+        ///
+        /// The session cookie is only used for features that aren't available from the API.
+        #[arg(long, default_value_t = false)]
+        cookie: bool,
+
+        #[command(flatten)]
+        shared: SharedOptions,
     },
 }
 
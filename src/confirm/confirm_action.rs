@@ -0,0 +1,23 @@
+use std::fmt::{Display, Formatter};
+
+/// An action that can't be undone so is confirmed by [`Confirmation`] first.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ConfirmAction {
+    /// Upload a transcode to the indexer.
+    Upload,
+    /// Replace an existing output directory.
+    Overwrite,
+    /// Archive or delete a source directory.
+    RemoveSource,
+}
+
+impl Display for ConfirmAction {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfirmAction::Upload => write!(formatter, "upload"),
+            ConfirmAction::Overwrite => write!(formatter, "replace output directory"),
+            ConfirmAction::RemoveSource => write!(formatter, "archive or delete source"),
+        }
+    }
+}
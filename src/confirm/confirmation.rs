@@ -0,0 +1,119 @@
+use std::collections::BTreeSet;
+use std::io::{stderr, stdin, IsTerminal, Write};
+use std::sync::Mutex;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::debug;
+use rogue_logging::Error;
+
+use crate::confirm::ConfirmAction;
+use crate::errors::{error, io_error};
+use crate::options::SharedOptions;
+use crate::tui::is_tui_active;
+
+/// An answer to a confirmation prompt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfirmAnswer {
+    Yes,
+    No,
+    /// Yes to this and every later prompt for the same [`ConfirmAction`].
+    All,
+}
+
+/// Confirm actions that can't be undone before they're performed.
+///
+/// Every prompt is answered yes if `yes` is set.
+pub struct Confirmation {
+    yes: bool,
+    approved: Mutex<BTreeSet<ConfirmAction>>,
+}
+
+#[injectable]
+impl Confirmation {
+    #[must_use]
+    pub fn new(options: Ref<SharedOptions>) -> Self {
+        Self {
+            yes: options.yes.unwrap_or_default(),
+            approved: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Ask whether an action should be performed.
+    ///
+    /// Returns `true` without asking if `yes` is set or the action was already approved.
+    ///
+    /// Returns an [`Error`] if stdin is not a terminal, or the console is being drawn
+    /// by [`Tui`], as there's nobody to answer.
+    pub fn confirm(&self, action: ConfirmAction, question: &str) -> Result<bool, Error> {
+        if self.yes || self.is_approved(action) {
+            return Ok(true);
+        }
+        if !stdin().is_terminal() || is_tui_active() {
+            return Err(error(
+                "confirm",
+                format!("Unable to confirm {action} as stdin is not a terminal. Set `yes` to confirm without a prompt"),
+            ));
+        }
+        loop {
+            match parse_answer(&read_answer(question)?) {
+                Some(ConfirmAnswer::Yes) => return Ok(true),
+                Some(ConfirmAnswer::No) => {
+                    debug!("{} {action} as it was not confirmed", "Skipping".bold());
+                    return Ok(false);
+                }
+                Some(ConfirmAnswer::All) => {
+                    self.approve(action);
+                    return Ok(true);
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Approve every later prompt for an action.
+    pub fn approve(&self, action: ConfirmAction) {
+        if let Ok(mut approved) = self.approved.lock() {
+            approved.insert(action);
+        }
+    }
+
+    /// Has the action been approved for the rest of the command?
+    #[must_use]
+    pub fn is_approved(&self, action: ConfirmAction) -> bool {
+        self.yes
+            || self
+                .approved
+                .lock()
+                .is_ok_and(|approved| approved.contains(&action))
+    }
+}
+
+/// Parse an answer to a confirmation prompt.
+///
+/// An empty answer is no. Returns `None` if the answer isn't recognized.
+#[must_use]
+pub fn parse_answer(answer: &str) -> Option<ConfirmAnswer> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(ConfirmAnswer::Yes),
+        "" | "n" | "no" => Some(ConfirmAnswer::No),
+        "a" | "all" => Some(ConfirmAnswer::All),
+        _ => None,
+    }
+}
+
+/// Write the question to stderr, so stdout is left for the result, and read a line.
+fn read_answer(question: &str) -> Result<String, Error> {
+    let mut stderr = stderr();
+    write!(stderr, "{} [y/N/a]: ", question.bold())
+        .and_then(|()| stderr.flush())
+        .map_err(|e| io_error(e, "write prompt"))?;
+    let mut answer = String::new();
+    let length = stdin()
+        .read_line(&mut answer)
+        .map_err(|e| io_error(e, "read answer"))?;
+    if length == 0 {
+        return Err(error("read answer", "No input is available".to_owned()));
+    }
+    Ok(answer)
+}
@@ -0,0 +1,7 @@
+pub use confirm_action::*;
+pub use confirmation::*;
+
+pub(crate) mod confirm_action;
+pub(crate) mod confirmation;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use di::Ref;
+
+use crate::confirm::{parse_answer, ConfirmAction, ConfirmAnswer, Confirmation};
+use crate::options::SharedOptions;
+
+#[test]
+fn confirmation_parse_answer() {
+    // Arrange
+    let answers = [
+        ("y", Some(ConfirmAnswer::Yes)),
+        ("Yes\n", Some(ConfirmAnswer::Yes)),
+        ("", Some(ConfirmAnswer::No)),
+        ("n", Some(ConfirmAnswer::No)),
+        ("a", Some(ConfirmAnswer::All)),
+        ("maybe", None),
+    ];
+
+    for (answer, expected) in answers {
+        // Act
+        let actual = parse_answer(answer);
+
+        // Assert
+        assert_eq!(actual, expected, "{answer:?}");
+    }
+}
+
+#[test]
+fn confirmation_confirm_with_yes() {
+    // Arrange
+    let confirmation = Confirmation::new(Ref::new(SharedOptions {
+        yes: Some(true),
+        ..SharedOptions::default()
+    }));
+
+    // Act
+    let confirmed = confirmation.confirm(ConfirmAction::Upload, "Upload?");
+
+    // Assert
+    assert!(confirmed.expect("should not error"));
+}
+
+#[test]
+fn confirmation_approve() {
+    // Arrange
+    let confirmation = Confirmation::new(Ref::new(SharedOptions::default()));
+    assert!(!confirmation.is_approved(ConfirmAction::Upload));
+
+    // Act
+    confirmation.approve(ConfirmAction::Upload);
+
+    // Assert
+    assert!(confirmation.is_approved(ConfirmAction::Upload));
+    assert!(!confirmation.is_approved(ConfirmAction::Overwrite));
+    let confirmed = confirmation.confirm(ConfirmAction::Upload, "Upload?");
+    assert!(confirmed.expect("should not error"));
+}
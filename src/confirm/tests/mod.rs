@@ -0,0 +1 @@
+mod confirmation_tests;
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::ValueEnum;
+use colored::Colorize;
+use di::{injectable, Ref, RefMut};
+use gazelle_api::GazelleClient;
+use log::{debug, info, trace};
+use reqwest::Client;
+use rogue_logging::Error;
+use tokio::fs::{create_dir_all, write};
+use tokio::time::sleep;
+
+use crate::cross_seed::{CrossSeedIndexer, CrossSeedMatch};
+use crate::errors::{io_error, path_error};
+use crate::formats::TargetFormat;
+use crate::fs::PathManager;
+use crate::indexer::RateLimiter;
+use crate::metrics::Metrics;
+use crate::options::{
+    CacheOptions, CrossSeedOptions, OptionRule, Options, OptionsProvider, SharedOptions, SourceArg,
+};
+use crate::source::{Source, SourceProvider};
+use crate::summary::RunSummary;
+use crate::torrent::TorrentFile;
+
+/// Duration to wait between requests to the API of another indexer.
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Find where a FLAC source and its transcodes can be cross-seeded on other indexers.
+///
+/// The `source` of each `.torrent` file is replaced with that of the other indexer and the
+/// resulting info hash is looked up with its API. A match has exactly the same files and
+/// pieces so the existing content can be seeded without downloading anything.
+#[injectable]
+pub struct CrossSeedCommand {
+    arg: Ref<SourceArg>,
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    cross_seed_options: Ref<CrossSeedOptions>,
+    provider: Ref<OptionsProvider>,
+    source_provider: RefMut<SourceProvider>,
+    api: RefMut<GazelleClient>,
+    paths: Ref<PathManager>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    summary: Ref<RunSummary>,
+}
+
+impl CrossSeedCommand {
+    /// Execute [`CrossSeedCommand`] from the CLI.
+    ///
+    /// [`Source`] is retrieved from the CLI arguments.
+    ///
+    /// Returns `true` if every indexer was searched, even if there were no matches.
+    pub async fn execute_cli(&mut self) -> Result<bool, Error> {
+        let errors = [
+            self.arg.get_errors(),
+            self.shared_options.get_errors(),
+            self.cache_options.get_errors(),
+            self.cross_seed_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
+        let indexers = self.get_indexers()?;
+        let source = self
+            .source_provider
+            .write()
+            .expect("Source provider should be writeable")
+            .get_from_options()
+            .await
+            .map_err(|issue| issue.to_error("get source from options"))?;
+        let torrents = self.get_torrents(&source).await?;
+        let matches = self.execute(&torrents, &indexers).await?;
+        if matches.is_empty() {
+            info!("{} cross-seeds for {source}", "No".bold());
+        } else {
+            info!(
+                "{} {} cross-seeds for {source}",
+                "Found".bold(),
+                matches.len()
+            );
+            for item in &matches {
+                info!("{item}");
+                if let Some(path) = &item.torrent {
+                    debug!("{}", path.display());
+                }
+            }
+        }
+        if self.summary.is_json() {
+            self.summary.set_data(&matches);
+        }
+        Ok(true)
+    }
+
+    /// Look up each torrent on each indexer.
+    ///
+    /// If `cross_seed_torrent_dir` is set then a `.torrent` file is written for each match.
+    pub async fn execute(
+        &self,
+        torrents: &[(String, PathBuf)],
+        indexers: &[CrossSeedIndexer],
+    ) -> Result<Vec<CrossSeedMatch>, Error> {
+        let client = Client::new();
+        let mut matches = Vec::new();
+        let mut is_first = true;
+        for indexer in indexers {
+            for (format, path) in torrents {
+                let torrent = TorrentFile::read(path).await?;
+                // Older torrents may have been uploaded without a source
+                for source in [Some(indexer.get_source()), None] {
+                    if !is_first {
+                        sleep(REQUEST_INTERVAL).await;
+                    }
+                    is_first = false;
+                    let other = torrent.with_source(source, indexer.announce_url.clone());
+                    let info_hash = other.get_info_hash();
+                    trace!(
+                        "{} {format} on {} by hash {info_hash}",
+                        "Searching".bold(),
+                        indexer.profile
+                    );
+                    let result = indexer.lookup(&client, &info_hash).await;
+                    self.metrics.record_api("look up torrent by hash", &result);
+                    let Some(response) = result? else {
+                        continue;
+                    };
+                    let torrent_path = self.write_torrent(&other, indexer).await?;
+                    matches.push(CrossSeedMatch {
+                        profile: indexer.profile.clone(),
+                        format: format.clone(),
+                        id: response.torrent.id,
+                        group_id: response.group.id,
+                        url: indexer.get_torrent_url(response.group.id, response.torrent.id),
+                        torrent: torrent_path,
+                    });
+                    break;
+                }
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Get the indexer of each `cross_seed_profile`.
+    ///
+    /// Profiles of the indexer the source is from are skipped.
+    fn get_indexers(&self) -> Result<Vec<CrossSeedIndexer>, Error> {
+        let own = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        let profiles = self
+            .cross_seed_options
+            .cross_seed_profile
+            .clone()
+            .unwrap_or_default();
+        CrossSeedIndexer::from_profiles(&self.provider, &profiles, &own)
+    }
+
+    /// Get the `.torrent` file of the source and of each transcode that exists.
+    ///
+    /// The source `.torrent` file is downloaded if it's not cached.
+    async fn get_torrents(&self, source: &Source) -> Result<Vec<(String, PathBuf)>, Error> {
+        let path = self.paths.get_source_torrent_path(source);
+        if !path.is_file() {
+            trace!(
+                "{} torrent file as it's not cached: {}",
+                "Downloading".bold(),
+                path.display()
+            );
+            let mut api = self.api.write().expect("API should be available");
+            self.rate_limiter.wait().await;
+            let result = api.get_torrent_file_as_buffer(source.torrent.id).await;
+            self.metrics.record_api("get torrent file", &result);
+            write(&path, result?)
+                .await
+                .map_err(|e| path_error(e, "write torrent file", &path))?;
+        }
+        let mut torrents = vec![(source.format.get_name().to_owned(), path)];
+        for target in TargetFormat::value_variants() {
+            let path = self.paths.get_torrent_path(source, *target, true);
+            if path.is_file() {
+                torrents.push((target.get_name().to_owned(), path));
+            }
+        }
+        Ok(torrents)
+    }
+
+    /// Write the `.torrent` file for the other indexer if `cross_seed_torrent_dir` is set.
+    async fn write_torrent(
+        &self,
+        torrent: &TorrentFile,
+        indexer: &CrossSeedIndexer,
+    ) -> Result<Option<PathBuf>, Error> {
+        let Some(dir) = &self.cross_seed_options.cross_seed_torrent_dir else {
+            return Ok(None);
+        };
+        create_dir_all(dir)
+            .await
+            .map_err(|e| io_error(e, "create cross seed torrent directory"))?;
+        let path = get_torrent_path(
+            dir,
+            &self.paths.get_output_name(&torrent.info.name),
+            indexer,
+        );
+        torrent.write(&path).await?;
+        Ok(Some(path))
+    }
+}
+
+/// Get the path of a `.torrent` file written for another indexer.
+#[must_use]
+pub fn get_torrent_path(dir: &Path, name: &str, indexer: &CrossSeedIndexer) -> PathBuf {
+    dir.join(format!("{name}.{}.torrent", indexer.indexer))
+}
@@ -0,0 +1,145 @@
+use colored::Colorize;
+use gazelle_api::{ApiResponse, TorrentResponse};
+use log::warn;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::Client;
+use rogue_logging::Error;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::errors::error;
+use crate::options::{get_stored_api_key, OptionsProvider, SharedOptions};
+
+/// Another indexer to search for cross-seeds, read from a profile of the config file.
+#[derive(Clone, Debug)]
+pub struct CrossSeedIndexer {
+    /// Name of the profile.
+    pub profile: String,
+    pub indexer: String,
+    pub indexer_url: String,
+    pub api_key: String,
+    pub announce_url: String,
+}
+
+impl CrossSeedIndexer {
+    /// Create a [`CrossSeedIndexer`] from the [`SharedOptions`] of a profile.
+    ///
+    /// If the profile does not set `api_key` then the OS credential store is used.
+    pub fn from_options(profile: &str, options: &SharedOptions) -> Result<Self, Error> {
+        let get = |key: &str, value: Option<String>| {
+            value.ok_or_else(|| {
+                error(
+                    "read cross seed profile",
+                    format!("Profile `{profile}` does not set {key}"),
+                )
+            })
+        };
+        let indexer = get("indexer", options.indexer.clone())?;
+        let api_key = options
+            .api_key
+            .clone()
+            .or_else(|| get_stored_api_key(&indexer));
+        Ok(Self {
+            profile: profile.to_owned(),
+            indexer_url: get("indexer_url", options.indexer_url.clone())?,
+            api_key: get("api_key", api_key)?,
+            announce_url: get("announce_url", options.announce_url.clone())?,
+            indexer,
+        })
+    }
+
+    /// Create a [`CrossSeedIndexer`] for each profile.
+    ///
+    /// Profiles of the `own` indexer are skipped.
+    pub fn from_profiles(
+        provider: &OptionsProvider,
+        profiles: &[String],
+        own: &str,
+    ) -> Result<Vec<Self>, Error> {
+        let mut indexers = Vec::new();
+        for profile in profiles {
+            let options: SharedOptions = provider.get_profile(profile)?;
+            let indexer = Self::from_options(profile, &options)?;
+            if indexer.indexer == own {
+                warn!(
+                    "{} profile {profile} as it's the same indexer as the source",
+                    "Skipping".bold()
+                );
+                continue;
+            }
+            indexers.push(indexer);
+        }
+        Ok(indexers)
+    }
+
+    /// Get the value of the `source` field of torrents uploaded to the indexer.
+    #[must_use]
+    pub fn get_source(&self) -> String {
+        self.indexer.to_uppercase()
+    }
+
+    /// Get the url to look up a torrent by info hash.
+    #[must_use]
+    pub fn get_lookup_url(&self, info_hash: &str) -> String {
+        format!(
+            "{}/ajax.php?action=torrent&hash={}",
+            self.indexer_url,
+            info_hash.to_uppercase()
+        )
+    }
+
+    /// Get the url of a torrent.
+    #[must_use]
+    pub fn get_torrent_url(&self, group_id: u32, id: u32) -> String {
+        format!(
+            "{}/torrents.php?id={group_id}&torrentid={id}#torrent{id}",
+            self.indexer_url
+        )
+    }
+
+    /// Look up a torrent by info hash.
+    ///
+    /// Returns `None` if the indexer does not have the torrent.
+    pub async fn lookup(
+        &self,
+        client: &Client,
+        info_hash: &str,
+    ) -> Result<Option<TorrentResponse>, Error> {
+        let action = "look up torrent by hash";
+        let response = client
+            .get(self.get_lookup_url(info_hash))
+            .header(
+                USER_AGENT,
+                format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+            )
+            .header(ACCEPT, "application/json")
+            .header(AUTHORIZATION, &self.api_key)
+            .send()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        let status = response.status();
+        let json = response
+            .text()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        if status.is_client_error() && status.as_u16() != 400 {
+            return Err(Error {
+                action: action.to_owned(),
+                message: format!("{} responded with {status}", self.indexer_url),
+                status_code: Some(status.as_u16()),
+                ..Error::default()
+            });
+        }
+        Ok(parse_lookup(&json))
+    }
+}
+
+/// Get the torrent from the response of a look up by info hash.
+///
+/// Gazelle responds with a failure if the hash is not found.
+#[must_use]
+pub fn parse_lookup(json: &str) -> Option<TorrentResponse> {
+    serde_json::from_str::<ApiResponse<TorrentResponse>>(json)
+        .ok()
+        .filter(|response| response.status == "success")
+        .and_then(|response| response.response)
+}
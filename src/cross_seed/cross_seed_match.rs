@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A torrent on another indexer with the same content as a local torrent.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CrossSeedMatch {
+    /// Profile of the other indexer.
+    pub profile: String,
+    /// Format of the local torrent such as `FLAC` for the source or `V0` for a transcode.
+    pub format: String,
+    /// Id of the torrent on the other indexer.
+    pub id: u32,
+    /// Id of the torrent group on the other indexer.
+    pub group_id: u32,
+    /// Url of the torrent on the other indexer.
+    pub url: String,
+    /// Path of the `.torrent` file written for the other indexer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub torrent: Option<PathBuf>,
+}
+
+impl Display for CrossSeedMatch {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} on {}: {}",
+            self.format, self.profile, self.url
+        )
+    }
+}
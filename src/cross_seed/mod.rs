@@ -0,0 +1,9 @@
+pub use cross_seed_command::*;
+pub use cross_seed_indexer::*;
+pub use cross_seed_match::*;
+
+pub(crate) mod cross_seed_command;
+pub(crate) mod cross_seed_indexer;
+pub(crate) mod cross_seed_match;
+#[cfg(test)]
+mod tests;
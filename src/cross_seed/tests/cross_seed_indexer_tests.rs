@@ -0,0 +1,88 @@
+use crate::cross_seed::{parse_lookup, CrossSeedIndexer};
+use crate::options::SharedOptions;
+
+const SUCCESS: &str = r#"{
+    "status": "success",
+    "response": {
+        "group": {
+            "wikiBody": "", "wikiImage": "", "id": 456, "name": "Album", "year": 2012,
+            "recordLabel": "", "catalogueNumber": "", "releaseType": 1, "categoryId": 1,
+            "categoryName": "Music", "time": "2012-01-01 00:00:00", "vanityHouse": false,
+            "isBookmarked": false, "tags": []
+        },
+        "torrent": {
+            "id": 123, "media": "WEB", "format": "FLAC", "encoding": "Lossless",
+            "remastered": false, "remasterTitle": "", "remasterRecordLabel": "",
+            "remasterCatalogueNumber": "", "scene": false, "hasLog": false, "hasCue": false,
+            "logScore": 0, "fileCount": 1, "size": 1000, "seeders": 1, "leechers": 0,
+            "snatched": 0, "reported": false, "time": "2012-01-01 00:00:00",
+            "description": "", "fileList": "", "filePath": "", "userId": 1, "username": "user"
+        }
+    }
+}"#;
+
+fn get_options() -> SharedOptions {
+    SharedOptions {
+        indexer: Some("ops".to_owned()),
+        indexer_url: Some("https://orpheus.network".to_owned()),
+        api_key: Some("key".to_owned()),
+        announce_url: Some("https://home.opsfet.ch/key/announce".to_owned()),
+        ..SharedOptions::default()
+    }
+}
+
+#[test]
+fn cross_seed_indexer_parse_lookup() {
+    // Act
+    let response = parse_lookup(SUCCESS).expect("should parse");
+
+    // Assert
+    assert_eq!(response.torrent.id, 123);
+    assert_eq!(response.group.id, 456);
+}
+
+#[test]
+fn cross_seed_indexer_parse_lookup_failure() {
+    // Arrange
+    let json = r#"{"status": "failure", "error": "bad hash parameter"}"#;
+
+    // Act
+    let response = parse_lookup(json);
+
+    // Assert
+    assert!(response.is_none());
+}
+
+#[test]
+fn cross_seed_indexer_from_options() {
+    // Act
+    let indexer = CrossSeedIndexer::from_options("other", &get_options()).expect("should be valid");
+
+    // Assert
+    assert_eq!(indexer.profile, "other");
+    assert_eq!(indexer.get_source(), "OPS");
+    assert_eq!(
+        indexer.get_lookup_url("abc123"),
+        "https://orpheus.network/ajax.php?action=torrent&hash=ABC123"
+    );
+    assert_eq!(
+        indexer.get_torrent_url(456, 123),
+        "https://orpheus.network/torrents.php?id=456&torrentid=123#torrent123"
+    );
+}
+
+#[test]
+fn cross_seed_indexer_from_options_missing_announce_url() {
+    // Arrange
+    let options = SharedOptions {
+        announce_url: None,
+        ..get_options()
+    };
+
+    // Act
+    let result = CrossSeedIndexer::from_options("other", &options);
+
+    // Assert
+    let error = result.expect_err("should be missing announce_url");
+    assert!(error.message.contains("announce_url"));
+}
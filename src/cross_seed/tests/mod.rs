@@ -0,0 +1 @@
+mod cross_seed_indexer_tests;
@@ -1,6 +1,7 @@
 use rogue_logging::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::fmt::Write;
 use std::fmt::{Debug, Display, Formatter};
 
 const HEXADECIMAL_RADIX: u32 = 16;
@@ -26,7 +27,7 @@ impl<const N: usize> Hash<N> {
     /// Get the hash as a hexadecimal string.
     pub fn to_hex(&self) -> String {
         self.bytes.iter().fold(String::new(), |mut acc, &b| {
-            acc.push_str(&format!("{b:02x}"));
+            write!(acc, "{b:02x}").expect("should be able to write to string");
             acc
         })
     }
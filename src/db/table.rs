@@ -92,12 +92,28 @@ where
             .map_err(|e| io_error(e, "read entry"))?
         {
             let path = entry.path();
-            let extension = path
-                .extension()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-            if !path.is_file() || extension != CHUNK_FILE_EXTENSION {
+            if !is_chunk_file(&path) {
+                trace!("Skipping non-chunk file: {}", path.display());
+                continue;
+            }
+            let chunk = read_chunk::<K, C, T>(&path)?;
+            items.extend(chunk);
+        }
+        Ok(items)
+    }
+
+    /// Get all items without the async runtime.
+    ///
+    /// Items are unsorted.
+    pub fn read_all(&self) -> Result<BTreeMap<Hash<K>, T>, Error> {
+        let mut items = BTreeMap::new();
+        let dir = self
+            .directory
+            .read_dir()
+            .map_err(|e| io_error(e, "read directory"))?;
+        for entry in dir {
+            let path = entry.map_err(|e| io_error(e, "read entry"))?.path();
+            if !is_chunk_file(&path) {
                 trace!("Skipping non-chunk file: {}", path.display());
                 continue;
             }
@@ -182,6 +198,14 @@ fn group_by_chunk<const K: usize, const C: usize, T>(
     chunks
 }
 
+/// Is the path a chunk file?
+fn is_chunk_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension == CHUNK_FILE_EXTENSION)
+}
+
 /// Read a chunk from a file.
 fn read_chunk<const K: usize, const C: usize, T>(
     path: &PathBuf,
@@ -22,6 +22,14 @@ pub const FLAC: &str = "flac.exe";
 #[cfg(not(target_os = "windows"))]
 pub const FLAC: &str = "flac";
 
+/// Path to the metaflac binary.
+#[cfg(target_os = "windows")]
+pub const METAFLAC: &str = "metaflac.exe";
+
+/// Path to the metaflac binary.
+#[cfg(not(target_os = "windows"))]
+pub const METAFLAC: &str = "metaflac";
+
 // TODO MUST confirm binary name on windows
 /// Path to the imagemagick convert binary.
 #[cfg(target_os = "windows")]
@@ -31,18 +39,41 @@ pub const CONVERT: &str = "convert.exe";
 #[cfg(not(target_os = "windows"))]
 pub const CONVERT: &str = "convert";
 
-/// Path to the imdl binary.
+/// Path to the eyeD3 binary.
 #[cfg(target_os = "windows")]
-pub const IMDL: &str = "imdl.exe";
+pub const EYED3: &str = "eyeD3.exe";
 
 /// Path to the imdl binary.
 #[cfg(not(target_os = "windows"))]
-pub const IMDL: &str = "imdl";
+pub const EYED3: &str = "eyeD3";
 
-/// Path to the eyeD3 binary.
+/// Path to the osascript binary used for desktop notifications on macOS.
+#[cfg(target_os = "macos")]
+pub const DESKTOP_NOTIFIER: &str = "osascript";
+
+/// Path to the notify-send binary used for desktop notifications on Linux.
+#[cfg(not(target_os = "macos"))]
+pub const DESKTOP_NOTIFIER: &str = "notify-send";
+
+/// Path to the cp binary used to create reflinks.
+pub const CP: &str = "cp";
+
+/// Path to the df binary used to check the free space of a directory.
+pub const DF: &str = "df";
+
+/// Path to the beets binary.
 #[cfg(target_os = "windows")]
-pub const EYED3: &str = "eyeD3.exe";
+pub const BEET: &str = "beet.exe";
 
-/// Path to the imdl binary.
+/// Path to the beets binary.
 #[cfg(not(target_os = "windows"))]
-pub const EYED3: &str = "eyeD3";
+pub const BEET: &str = "beet";
+
+/// Path to the zip binary used to create zip archives.
+pub const ZIP: &str = "zip";
+
+/// Path to the tar binary used to create tar archives.
+pub const TAR: &str = "tar";
+
+/// Path to the gzip binary used to compress tar archives.
+pub const GZIP: &str = "gzip";
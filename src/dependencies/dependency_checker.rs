@@ -0,0 +1,46 @@
+use std::process::Stdio;
+
+use tokio::process::Command;
+
+use crate::dependencies::*;
+
+/// Binaries required to verify, transcode and upload.
+pub const REQUIRED_DEPENDENCIES: [&str; 4] = [FLAC, LAME, SOX, CONVERT];
+
+/// Check if a binary can be executed.
+pub async fn is_dependency_available(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok()
+}
+
+/// Get the first line of the version output of a binary.
+///
+/// Returns `None` if the binary can't be executed.
+pub async fn get_dependency_version(program: &str) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().await.ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    Some(line.to_owned())
+}
+
+/// Get the required binaries that can't be executed.
+pub async fn get_missing_dependencies() -> Vec<&'static str> {
+    let mut missing = Vec::new();
+    for program in REQUIRED_DEPENDENCIES {
+        if !is_dependency_available(program).await {
+            missing.push(program);
+        }
+    }
+    missing
+}
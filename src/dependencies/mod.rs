@@ -1,3 +1,5 @@
 pub use binaries::*;
+pub use dependency_checker::*;
 
 pub(crate) mod binaries;
+pub(crate) mod dependency_checker;
@@ -0,0 +1,53 @@
+use std::fmt::{Display, Formatter};
+
+use colored::Colorize;
+
+/// Outcome of a check by [`DoctorCommand`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorCheck {
+    /// Name of what was checked.
+    pub name: String,
+    pub passed: bool,
+    /// Version, path or reason for the outcome.
+    pub details: String,
+    /// How to resolve the failure.
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    #[must_use]
+    pub fn pass(name: &str, details: String) -> Self {
+        Self {
+            name: name.to_owned(),
+            passed: true,
+            details,
+            fix: None,
+        }
+    }
+
+    #[must_use]
+    pub fn fail(name: &str, details: String, fix: String) -> Self {
+        Self {
+            name: name.to_owned(),
+            passed: false,
+            details,
+            fix: Some(fix),
+        }
+    }
+}
+
+impl Display for DoctorCheck {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = if self.passed {
+            "PASS".green().bold()
+        } else {
+            "FAIL".red().bold()
+        };
+        write!(formatter, "{status}  {:<18} {}", self.name, self.details)?;
+        if let Some(fix) = &self.fix {
+            write!(formatter, "\n      {:<18} {fix}", "Fix:".bold())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use std::fs::{create_dir_all, remove_file, write};
+use std::path::Path;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{info, warn};
+use rogue_logging::Error;
+
+use crate::dependencies::{
+    get_dependency_version, DESKTOP_NOTIFIER, DF, EYED3, METAFLAC, REQUIRED_DEPENDENCIES,
+};
+use crate::doctor::DoctorCheck;
+use crate::errors::ErrorCode;
+use crate::fs::get_free_space;
+use crate::indexer::SessionClient;
+use crate::options::init_command::get_username;
+use crate::options::{CacheOptions, DesktopOptions, Options, SharedOptions};
+
+/// Least free space of the output directory that passes the check.
+const MIN_FREE_SPACE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Name of the file written to check a directory is writable.
+const PROBE_FILE_NAME: &str = ".caesura-doctor";
+
+/// Page of the indexer website requested to check the session cookie.
+const SESSION_CHECK_PAGE: &str = "index.php";
+
+/// Check the dependencies, options, API key and directories are working.
+///
+/// The session cookie is checked if `session_cookie` is set.
+#[injectable]
+pub struct DoctorCommand {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    desktop_options: Ref<DesktopOptions>,
+    session: Ref<SessionClient>,
+}
+
+impl DoctorCommand {
+    /// Execute [`DoctorCommand`] from the CLI.
+    ///
+    /// Every check is run even if an earlier check fails. The directories are created if
+    /// they don't exist. The desktop notification binary is only checked if
+    /// `desktop_notify` is set.
+    ///
+    /// Returns `true` if every check passes.
+    pub async fn execute_cli(&self) -> Result<bool, Error> {
+        let mut checks = Vec::new();
+        for program in REQUIRED_DEPENDENCIES.iter().chain([&EYED3, &METAFLAC]) {
+            checks.push(check_dependency(program).await);
+        }
+        if self.desktop_options.desktop_notify == Some(true) {
+            checks.push(check_dependency(DESKTOP_NOTIFIER).await);
+        }
+        let output = self.shared_options.output.clone().unwrap_or_default();
+        checks.push(check_writable("Output directory", &output));
+        checks.push(check_free_space(&output).await);
+        let cache = self.cache_options.cache.clone().unwrap_or_default();
+        checks.push(check_writable("Cache directory", &cache));
+        checks.push(self.check_options());
+        checks.push(self.check_api().await);
+        if self.session.is_enabled() {
+            checks.push(self.check_session().await);
+        }
+        for check in &checks {
+            info!("{check}");
+        }
+        let failed = checks.iter().filter(|check| !check.passed).count();
+        if failed == 0 {
+            info!("{} all {} checks", "Passed".bold(), checks.len());
+        } else {
+            warn!("{} {failed} of {} checks", "Failed".bold(), checks.len());
+        }
+        Ok(failed == 0)
+    }
+
+    fn check_options(&self) -> DoctorCheck {
+        let errors = [
+            self.shared_options.get_errors(),
+            self.cache_options.get_errors(),
+        ]
+        .concat();
+        if errors.is_empty() {
+            return DoctorCheck::pass("Options", "Valid".to_owned());
+        }
+        let details = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        DoctorCheck::fail(
+            "Options",
+            details,
+            "Set the options in the config file or as arguments".to_owned(),
+        )
+    }
+
+    async fn check_api(&self) -> DoctorCheck {
+        match get_username(&self.shared_options).await {
+            Ok(username) => DoctorCheck::pass("API key", format!("Valid for user: {username}")),
+            Err(error) => {
+                let fix = ErrorCode::from_error(&error)
+                    .get_hint(&error)
+                    .unwrap_or_else(|| "Check indexer_url and api_key are correct".to_owned());
+                DoctorCheck::fail("API key", error.message, fix)
+            }
+        }
+    }
+
+    async fn check_session(&self) -> DoctorCheck {
+        let name = "Session cookie";
+        match self
+            .session
+            .get_page("check session cookie", SESSION_CHECK_PAGE, &[])
+            .await
+        {
+            Ok(_) => DoctorCheck::pass(name, "Valid".to_owned()),
+            Err(error) => DoctorCheck::fail(
+                name,
+                error.message,
+                "Copy the session cookie from your browser after logging in to the indexer"
+                    .to_owned(),
+            ),
+        }
+    }
+}
+
+async fn check_dependency(program: &str) -> DoctorCheck {
+    match get_dependency_version(program).await {
+        Some(version) => DoctorCheck::pass(program, version),
+        None => DoctorCheck::fail(
+            program,
+            "Not found".to_owned(),
+            format!("Install {program} and check it is on your PATH"),
+        ),
+    }
+}
+
+/// Check a directory can be created and written to.
+fn check_writable(name: &str, dir: &Path) -> DoctorCheck {
+    let probe = dir.join(PROBE_FILE_NAME);
+    let result = create_dir_all(dir)
+        .and_then(|()| write(&probe, b""))
+        .and_then(|()| remove_file(&probe));
+    match result {
+        Ok(()) => DoctorCheck::pass(name, format!("Writable: {}", dir.display())),
+        Err(error) => DoctorCheck::fail(
+            name,
+            format!("{error}: {}", dir.display()),
+            "Check the directory exists and is writable by the user running caesura".to_owned(),
+        ),
+    }
+}
+
+async fn check_free_space(dir: &Path) -> DoctorCheck {
+    let name = "Free space";
+    let Some(available) = get_free_space(dir).await else {
+        return DoctorCheck::fail(
+            name,
+            format!("Could not be determined: {}", dir.display()),
+            format!("Check {DF} is available and the output directory exists"),
+        );
+    };
+    let details = format!("{} GiB available", available >> 30);
+    if available >= MIN_FREE_SPACE {
+        DoctorCheck::pass(name, details)
+    } else {
+        DoctorCheck::fail(
+            name,
+            details,
+            format!(
+                "Free at least {} GiB in the output directory",
+                MIN_FREE_SPACE >> 30
+            ),
+        )
+    }
+}
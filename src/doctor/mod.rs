@@ -0,0 +1,7 @@
+pub use doctor_check::*;
+pub use doctor_command::*;
+
+pub(crate) mod doctor_check;
+pub(crate) mod doctor_command;
+#[cfg(test)]
+mod tests;
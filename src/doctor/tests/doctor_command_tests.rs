@@ -0,0 +1,27 @@
+use crate::fs::parse_df_output;
+
+#[test]
+fn parse_df_output_available() {
+    // Arrange
+    let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on
+/dev/sda1        102400000  51200000  51200000      50% /
+";
+
+    // Act
+    let available = parse_df_output(output);
+
+    // Assert
+    assert_eq!(available, Some(51_200_000 * 1024));
+}
+
+#[test]
+fn parse_df_output_invalid() {
+    // Arrange
+    let output = "df: /missing: No such file or directory\n";
+
+    // Act
+    let available = parse_df_output(output);
+
+    // Assert
+    assert_eq!(available, None);
+}
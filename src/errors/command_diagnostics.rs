@@ -0,0 +1,64 @@
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::errors::CommandError;
+use crate::logging::get_log_source;
+
+/// Directory the full output of each failed command is saved to, if `diagnostics_dir`
+/// is set.
+static DIAGNOSTICS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Name of the diagnostics file if no source is being processed.
+const UNKNOWN_SOURCE: &str = "caesura";
+
+/// Save the full output of failed external commands so a failure can be diagnosed when
+/// the tail included in the [`Error`](rogue_logging::Error) isn't enough.
+///
+/// Each failure is appended to a file named after the source being processed.
+pub struct CommandDiagnostics;
+
+impl CommandDiagnostics {
+    /// Start saving the output of failed commands if `diagnostics_dir` is set.
+    pub fn init(dir: Option<PathBuf>) {
+        if let Some(dir) = dir {
+            let _ = DIAGNOSTICS_DIR.set(dir);
+        }
+    }
+
+    /// Append the report of a failed command to the file of the current source.
+    ///
+    /// Returns the path of the file, or `None` if `diagnostics_dir` isn't set or the
+    /// file could not be written.
+    pub fn save(error: &CommandError, action: &str, program: &str) -> Option<PathBuf> {
+        let dir = DIAGNOSTICS_DIR.get()?;
+        let path = get_diagnostics_path(dir, get_log_source().as_deref());
+        match append(&path, &error.get_report(action, program)) {
+            Ok(()) => Some(path),
+            Err(e) => {
+                eprintln!("Failed to write diagnostics to {}: {e}", path.display());
+                None
+            }
+        }
+    }
+}
+
+/// Get the path of the diagnostics file of a source.
+#[must_use]
+pub fn get_diagnostics_path(dir: &Path, source: Option<&str>) -> PathBuf {
+    let name = source.unwrap_or(UNKNOWN_SOURCE).replace(['/', '\\'], "_");
+    dir.join(format!("{name}.log"))
+}
+
+fn append(path: &Path, report: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{report}").map_err(|e| e.to_string())
+}
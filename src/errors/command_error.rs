@@ -1,5 +1,6 @@
 use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
+use std::fmt::{Debug, Display, Formatter, Write};
+use tokio::process::Command;
 
 #[allow(dead_code)]
 pub struct CommandError {
@@ -8,6 +9,77 @@ pub struct CommandError {
     pub exit_code: Option<i32>,
     pub exit_signal: Option<i32>,
     pub exit_stopped_signal: Option<i32>,
+    /// Command line that was executed, if known.
+    pub command: Option<String>,
+}
+
+impl CommandError {
+    /// Get a description of the exit status such as `exit code 2`.
+    #[must_use]
+    pub fn get_status(&self) -> String {
+        match (self.exit_code, self.exit_signal) {
+            (Some(code), _) => format!("exit code {code}"),
+            (None, Some(signal)) => format!("signal {signal}"),
+            (None, None) => "an unknown status".to_owned(),
+        }
+    }
+
+    /// Get the command line, exit status and full output of the command.
+    #[must_use]
+    pub fn get_report(&self, action: &str, program: &str) -> String {
+        let mut report = format!(
+            "Failed to {action}: {program} exited with {}\n",
+            self.get_status()
+        );
+        if let Some(command) = &self.command {
+            let _ = writeln!(report, "Command: {command}");
+        }
+        let _ = writeln!(report, "--- stderr");
+        let _ = writeln!(report, "{}", self.stderr.trim_end());
+        let _ = writeln!(report, "--- stdout");
+        let _ = writeln!(report, "{}", self.stdout.trim_end());
+        report
+    }
+
+    /// Get the last lines of stderr, or stdout if stderr is empty.
+    #[must_use]
+    pub fn get_tail(&self, count: usize) -> String {
+        let output = if self.stderr.trim().is_empty() {
+            &self.stdout
+        } else {
+            &self.stderr
+        };
+        let lines: Vec<&str> = output.trim_end().lines().collect();
+        if lines.is_empty() {
+            return "unexplained failure".to_owned();
+        }
+        let start = lines.len().saturating_sub(count);
+        lines.get(start..).unwrap_or_default().join("\n")
+    }
+}
+
+/// Get a string representation of the command line of a [`Command`].
+///
+/// If an arg contains spaces it will be wrapped in double quotes, but no other escaping is
+/// applied so the result is not safe for execution.
+#[must_use]
+pub fn get_command_line(command: &Command) -> String {
+    let command = command.as_std();
+    command.get_args().fold(
+        command.get_program().to_string_lossy().to_string(),
+        |mut acc, arg| {
+            let arg = arg.to_string_lossy();
+            acc.push(' ');
+            if arg.contains(' ') {
+                acc.push('"');
+                acc.push_str(&arg);
+                acc.push('"');
+            } else {
+                acc.push_str(&arg);
+            }
+            acc
+        },
+    )
 }
 
 impl Debug for CommandError {
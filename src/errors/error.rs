@@ -1,8 +1,40 @@
+use std::path::Path;
+
 use rogue_logging::Error;
 use tokio::task::JoinError;
 
 use crate::errors::CommandError;
 
+/// Domain of an [`Error`] from the file system.
+pub const FILE_SYSTEM_DOMAIN: &str = "file system";
+
+/// Domain of an [`Error`] from an external command that exited unsuccessfully.
+pub const COMMAND_DOMAIN: &str = "external command";
+
+/// Domain of an [`Error`] from an external command that could not be found.
+pub const DEPENDENCY_DOMAIN: &str = "dependency";
+
+/// Domain of an [`Error`] from reading a FLAC file.
+pub const FLAC_DOMAIN: &str = "FLAC";
+
+/// Domain of an [`Error`] from deserializing JSON or YAML.
+pub const DESERIALIZATION_DOMAIN: &str = "deserialization";
+
+/// Domain of an [`Error`] from the credential store.
+pub const CREDENTIAL_DOMAIN: &str = "credential store";
+
+/// Domain of an [`Error`] from the history database.
+pub const DATABASE_DOMAIN: &str = "database";
+
+/// Domain of an [`Error`] from a background task.
+pub const TASK_DOMAIN: &str = "task";
+
+/// Prefix of the message of an [`Error`] with [`DEPENDENCY_DOMAIN`].
+pub const DEPENDENCY_MESSAGE: &str = "Could not find dependency: ";
+
+/// Number of lines of the output of a failed command to include in an [`Error`].
+const OUTPUT_TAIL_LINES: usize = 10;
+
 #[allow(clippy::absolute_paths)]
 pub fn error(action: &str, message: String) -> Error {
     Error {
@@ -16,7 +48,7 @@ pub fn claxon_error(error: claxon::Error, action: &str) -> Error {
     Error {
         action: action.to_owned(),
         message: error.to_string(),
-        domain: Some("FLAC".to_owned()),
+        domain: Some(FLAC_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
@@ -27,7 +59,8 @@ pub fn command_error(error: std::io::Error, action: &str, program: &str) -> Erro
     match error.kind() {
         std::io::ErrorKind::NotFound => Error {
             action: action.to_owned(),
-            message: format!("Could not find dependency: {program}"),
+            message: format!("{DEPENDENCY_MESSAGE}{program}"),
+            domain: Some(DEPENDENCY_DOMAIN.to_owned()),
             ..Error::default()
         },
         _ => io_error(error, action),
@@ -39,16 +72,38 @@ pub fn io_error(error: std::io::Error, action: &str) -> Error {
     Error {
         action: action.to_owned(),
         message: error.to_string(),
-        domain: Some("file system".to_owned()),
+        domain: Some(FILE_SYSTEM_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
 
-pub fn output_error(error: CommandError, action: &str, domain: &str) -> Error {
+/// Create an [`Error`] from a file system error including the path it occurred on.
+#[allow(clippy::absolute_paths)]
+pub fn path_error(error: std::io::Error, action: &str, path: &Path) -> Error {
     Error {
         action: action.to_owned(),
-        message: error.to_string(),
-        domain: Some(domain.to_owned()),
+        message: format!("{error}: {}", path.display()),
+        domain: Some(FILE_SYSTEM_DOMAIN.to_owned()),
+        ..Error::default()
+    }
+}
+
+/// Create an [`Error`] from a command that exited unsuccessfully.
+///
+/// The message includes the program, its exit status, the command line if known and the
+/// last lines of its output.
+pub fn output_error(error: CommandError, action: &str, program: &str) -> Error {
+    let mut message = format!("{program} exited with {}\n", error.get_status());
+    if let Some(command) = &error.command {
+        message.push_str("Command: ");
+        message.push_str(command);
+        message.push('\n');
+    }
+    message.push_str(&error.get_tail(OUTPUT_TAIL_LINES));
+    Error {
+        action: action.to_owned(),
+        message,
+        domain: Some(COMMAND_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
@@ -57,7 +112,7 @@ pub fn task_error(error: JoinError, action: &str) -> Error {
     Error {
         action: action.to_owned(),
         message: error.to_string(),
-        domain: Some("task".to_owned()),
+        domain: Some(TASK_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
@@ -66,7 +121,7 @@ pub fn json_error(error: serde_json::Error, action: &str) -> Error {
     Error {
         action: action.to_owned(),
         message: error.to_string(),
-        domain: Some("deserialization".to_owned()),
+        domain: Some(DESERIALIZATION_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
@@ -75,7 +130,25 @@ pub fn yaml_error(error: serde_yaml::Error, action: &str) -> Error {
     Error {
         action: action.to_owned(),
         message: error.to_string(),
-        domain: Some("deserialization".to_owned()),
+        domain: Some(DESERIALIZATION_DOMAIN.to_owned()),
+        ..Error::default()
+    }
+}
+
+pub fn keyring_error(error: keyring::Error, action: &str) -> Error {
+    Error {
+        action: action.to_owned(),
+        message: error.to_string(),
+        domain: Some(CREDENTIAL_DOMAIN.to_owned()),
+        ..Error::default()
+    }
+}
+
+pub fn sqlite_error(error: rusqlite::Error, action: &str) -> Error {
+    Error {
+        action: action.to_owned(),
+        message: error.to_string(),
+        domain: Some(DATABASE_DOMAIN.to_owned()),
         ..Error::default()
     }
 }
@@ -0,0 +1,136 @@
+use colored::Colorize;
+use log::{error, info};
+use rogue_logging::Error;
+
+use crate::errors::{
+    COMMAND_DOMAIN, CREDENTIAL_DOMAIN, DATABASE_DOMAIN, DEPENDENCY_DOMAIN, DEPENDENCY_MESSAGE,
+    DESERIALIZATION_DOMAIN, FILE_SYSTEM_DOMAIN, FLAC_DOMAIN, TASK_DOMAIN,
+};
+use crate::options::CONFIGURATION_DOMAIN;
+use crate::report::report_error;
+use crate::source::API_DOMAIN;
+
+/// Category of an [`Error`] with a stable code.
+///
+/// The code is logged with the error so issues can be searched and reported
+/// without relying on the wording of the message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    /// `E100` The command line arguments or config file options are invalid.
+    InvalidOptions,
+    /// `E200` A file system operation failed.
+    FileSystem,
+    /// `E201` A file or directory could not be written or read due to its permissions.
+    PermissionDenied,
+    /// `E210` The history database could not be read or written.
+    Database,
+    /// `E300` An external command exited unsuccessfully.
+    CommandFailed,
+    /// `E301` An external command could not be found.
+    MissingDependency,
+    /// `E400` The API responded with an error.
+    Api,
+    /// `E401` The API rejected the API key.
+    Unauthorized,
+    /// `E500` A FLAC file could not be read.
+    Flac,
+    /// `E600` A response or file could not be deserialized.
+    Deserialization,
+    /// `E700` The credential store could not be accessed.
+    CredentialStore,
+    /// `E800` A background task failed.
+    Task,
+    /// `E900` An unexpected error occurred.
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Get the [`ErrorCode`] of an [`Error`].
+    #[must_use]
+    pub fn from_error(error: &Error) -> Self {
+        if matches!(error.status_code, Some(401 | 403)) {
+            return ErrorCode::Unauthorized;
+        }
+        match error.domain.as_deref() {
+            Some(CONFIGURATION_DOMAIN) => ErrorCode::InvalidOptions,
+            Some(FILE_SYSTEM_DOMAIN) if is_permission_denied(&error.message) => {
+                ErrorCode::PermissionDenied
+            }
+            Some(FILE_SYSTEM_DOMAIN) => ErrorCode::FileSystem,
+            Some(DATABASE_DOMAIN) => ErrorCode::Database,
+            Some(COMMAND_DOMAIN) => ErrorCode::CommandFailed,
+            Some(DEPENDENCY_DOMAIN) => ErrorCode::MissingDependency,
+            Some(API_DOMAIN) => ErrorCode::Api,
+            Some(FLAC_DOMAIN) => ErrorCode::Flac,
+            Some(DESERIALIZATION_DOMAIN) => ErrorCode::Deserialization,
+            Some(CREDENTIAL_DOMAIN) => ErrorCode::CredentialStore,
+            Some(TASK_DOMAIN) => ErrorCode::Task,
+            _ if error.status_code.is_some() => ErrorCode::Api,
+            _ => ErrorCode::Unknown,
+        }
+    }
+
+    /// Get the stable code.
+    #[must_use]
+    pub fn get_code(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidOptions => "E100",
+            ErrorCode::FileSystem => "E200",
+            ErrorCode::PermissionDenied => "E201",
+            ErrorCode::Database => "E210",
+            ErrorCode::CommandFailed => "E300",
+            ErrorCode::MissingDependency => "E301",
+            ErrorCode::Api => "E400",
+            ErrorCode::Unauthorized => "E401",
+            ErrorCode::Flac => "E500",
+            ErrorCode::Deserialization => "E600",
+            ErrorCode::CredentialStore => "E700",
+            ErrorCode::Task => "E800",
+            ErrorCode::Unknown => "E900",
+        }
+    }
+
+    /// Get a one line suggestion of how to resolve a common [`Error`].
+    #[allow(clippy::wildcard_enum_match_arm)]
+    #[must_use]
+    pub fn get_hint(self, error: &Error) -> Option<String> {
+        match self {
+            ErrorCode::MissingDependency => {
+                let program = error
+                    .message
+                    .strip_prefix(DEPENDENCY_MESSAGE)
+                    .unwrap_or("the program");
+                Some(format!(
+                    "Install {program} and check it is on your PATH, or use the Docker image which includes every dependency"
+                ))
+            }
+            ErrorCode::Unauthorized => Some(
+                "Check api_key is correct and has not been revoked. It can be found in the Access Settings of your indexer profile".to_owned(),
+            ),
+            ErrorCode::PermissionDenied => Some(
+                "Check the output directory exists and is writable by the user running caesura"
+                    .to_owned(),
+            ),
+            ErrorCode::InvalidOptions => {
+                Some("Run `caesura config` to check the resolved options".to_owned())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Log an [`Error`] followed by its [`ErrorCode`] and a hint if one is available.
+pub fn log_error(error: &Error) {
+    error.log();
+    report_error(error);
+    let code = ErrorCode::from_error(error);
+    error!("Error code: {}", code.get_code());
+    if let Some(hint) = code.get_hint(error) {
+        info!("{} {hint}", "Hint:".bold());
+    }
+}
+
+fn is_permission_denied(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("permission denied") || message.contains("read-only file system")
+}
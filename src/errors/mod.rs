@@ -1,7 +1,13 @@
+pub use command_diagnostics::*;
 pub use command_error::*;
 pub use error::*;
+pub use error_code::*;
 pub use output_handler::*;
 
+pub(crate) mod command_diagnostics;
 pub(crate) mod command_error;
 mod error;
+pub(crate) mod error_code;
 pub(crate) mod output_handler;
+#[cfg(test)]
+mod tests;
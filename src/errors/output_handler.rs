@@ -1,5 +1,5 @@
 use crate::errors::command_error::CommandError;
-use crate::errors::output_error;
+use crate::errors::{output_error, CommandDiagnostics};
 use rogue_logging::Error;
 use std::os::unix::process::ExitStatusExt;
 use std::process::Output;
@@ -7,7 +7,27 @@ use std::process::Output;
 pub struct OutputHandler {}
 
 impl OutputHandler {
-    pub fn execute(output: Output, action: &str, domain: &str) -> Result<Output, Error> {
+    pub fn execute(output: Output, action: &str, program: &str) -> Result<Output, Error> {
+        Self::handle(output, action, program, None)
+    }
+
+    /// Check the output of a command, including the command line in the error if it
+    /// exited unsuccessfully.
+    pub fn execute_command(
+        output: Output,
+        action: &str,
+        program: &str,
+        command: &str,
+    ) -> Result<Output, Error> {
+        Self::handle(output, action, program, Some(command.to_owned()))
+    }
+
+    fn handle(
+        output: Output,
+        action: &str,
+        program: &str,
+        command: Option<String>,
+    ) -> Result<Output, Error> {
         if output.status.success() {
             Ok(output)
         } else {
@@ -17,8 +37,15 @@ impl OutputHandler {
                 exit_code: output.status.code(),
                 exit_signal: output.status.signal(),
                 exit_stopped_signal: output.status.stopped_signal(),
+                command,
             };
-            Err(output_error(error, action, domain))
+            let path = CommandDiagnostics::save(&error, action, program);
+            let mut error = output_error(error, action, program);
+            if let Some(path) = path {
+                error.message.push_str("\nFull output: ");
+                error.message.push_str(&path.to_string_lossy());
+            }
+            Err(error)
         }
     }
 }
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use tokio::process::Command;
+
+use crate::errors::{get_command_line, get_diagnostics_path, CommandError};
+
+#[test]
+fn command_diagnostics_path_of_source() {
+    // Arrange
+    let dir = Path::new("/diagnostics");
+
+    // Act
+    let source = get_diagnostics_path(dir, Some("123"));
+    let unknown = get_diagnostics_path(dir, None);
+    let nested = get_diagnostics_path(dir, Some("a/b"));
+
+    // Assert
+    assert_eq!(source, Path::new("/diagnostics/123.log"));
+    assert_eq!(unknown, Path::new("/diagnostics/caesura.log"));
+    assert_eq!(nested, Path::new("/diagnostics/a_b.log"));
+}
+
+#[test]
+fn command_diagnostics_report_includes_full_output() {
+    // Arrange
+    let stderr = (1..=20)
+        .map(|x| format!("line {x}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let error = CommandError {
+        stderr,
+        stdout: "done".to_owned(),
+        exit_code: None,
+        exit_signal: Some(9),
+        exit_stopped_signal: None,
+        command: Some("sox in.flac -n spectrogram".to_owned()),
+    };
+
+    // Act
+    let report = error.get_report("generate spectrogram", "sox");
+
+    // Assert
+    assert!(report.starts_with(
+        "Failed to generate spectrogram: sox exited with signal 9\nCommand: sox in.flac -n spectrogram\n--- stderr\nline 1\n"
+    ));
+    assert!(report.ends_with("line 20\n--- stdout\ndone\n"));
+}
+
+#[test]
+fn command_diagnostics_command_line_quotes_spaces() {
+    // Arrange
+    let mut command = Command::new("flac");
+    command.arg("-dcs").arg("01 Track.flac");
+
+    // Act
+    let line = get_command_line(&command);
+
+    // Assert
+    assert_eq!(line, "flac -dcs \"01 Track.flac\"");
+}
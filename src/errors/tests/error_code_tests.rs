@@ -0,0 +1,136 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+
+use rogue_logging::Error;
+
+use crate::errors::{command_error, error, output_error, path_error, CommandError, ErrorCode};
+use crate::options::CONFIGURATION_DOMAIN;
+
+#[test]
+fn error_code_missing_dependency() {
+    // Arrange
+    let error = command_error(IoError::from(ErrorKind::NotFound), "spawn", "sox");
+
+    // Act
+    let code = ErrorCode::from_error(&error);
+    let hint = code.get_hint(&error).expect("should have a hint");
+
+    // Assert
+    assert_eq!(code, ErrorCode::MissingDependency);
+    assert_eq!(code.get_code(), "E301");
+    assert!(hint.starts_with("Install sox "));
+}
+
+#[test]
+fn error_code_unauthorized() {
+    // Arrange
+    let error = Error {
+        action: "get torrent".to_owned(),
+        message: "Unauthorized".to_owned(),
+        status_code: Some(401),
+        ..Error::default()
+    };
+
+    // Act
+    let code = ErrorCode::from_error(&error);
+
+    // Assert
+    assert_eq!(code, ErrorCode::Unauthorized);
+    assert!(code.get_hint(&error).is_some_and(|x| x.contains("api_key")));
+}
+
+#[test]
+fn error_code_permission_denied() {
+    // Arrange
+    let error = path_error(
+        IoError::from(ErrorKind::PermissionDenied),
+        "create transcode output directory",
+        Path::new("/output"),
+    );
+
+    // Act
+    let code = ErrorCode::from_error(&error);
+
+    // Assert
+    assert_eq!(code, ErrorCode::PermissionDenied);
+    assert!(error.message.ends_with(": /output"));
+    assert!(code.get_hint(&error).is_some());
+}
+
+#[test]
+fn error_code_command_failed() {
+    // Arrange
+    let stderr = (1..=20)
+        .map(|x| format!("line {x}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let error = output_error(
+        CommandError {
+            stderr,
+            stdout: String::new(),
+            exit_code: Some(2),
+            exit_signal: None,
+            exit_stopped_signal: None,
+            command: None,
+        },
+        "execute transcode job",
+        "lame",
+    );
+
+    // Act
+    let code = ErrorCode::from_error(&error);
+
+    // Assert
+    assert_eq!(code, ErrorCode::CommandFailed);
+    assert!(code.get_hint(&error).is_none());
+    assert!(error
+        .message
+        .starts_with("lame exited with exit code 2\nline 11\n"));
+    assert!(error.message.ends_with("line 20"));
+}
+
+#[test]
+fn error_code_other() {
+    // Arrange
+    let options = Error {
+        domain: Some(CONFIGURATION_DOMAIN.to_owned()),
+        ..Error::default()
+    };
+    let unknown = error("do something", "Something went wrong".to_owned());
+
+    // Act
+    let options = ErrorCode::from_error(&options);
+    let unknown = ErrorCode::from_error(&unknown);
+
+    // Assert
+    assert_eq!(options, ErrorCode::InvalidOptions);
+    assert_eq!(unknown, ErrorCode::Unknown);
+    assert_eq!(unknown.get_code(), "E900");
+}
+
+#[test]
+fn error_code_command_failed_with_command_line() {
+    // Arrange
+    let error = output_error(
+        CommandError {
+            stderr: "lame: invalid option".to_owned(),
+            stdout: String::new(),
+            exit_code: Some(1),
+            exit_signal: None,
+            exit_stopped_signal: None,
+            command: Some("lame -V 0 - \"01 Track.mp3\"".to_owned()),
+        },
+        "execute transcode job",
+        "lame",
+    );
+
+    // Act
+    let code = ErrorCode::from_error(&error);
+
+    // Assert
+    assert_eq!(code, ErrorCode::CommandFailed);
+    assert_eq!(
+        error.message,
+        "lame exited with exit code 1\nCommand: lame -V 0 - \"01 Track.mp3\"\nlame: invalid option"
+    );
+}
@@ -0,0 +1,2 @@
+mod command_diagnostics_tests;
+mod error_code_tests;
@@ -17,7 +17,7 @@ impl EyeD3Command {
             .output()
             .await
             .map_err(|e| command_error(e, "get details", EYED3))?;
-        let output = OutputHandler::execute(output, "get details", "eyeD3")?;
+        let output = OutputHandler::execute(output, "get details", EYED3)?;
         Ok(String::from_utf8(output.stdout).unwrap_or_default())
     }
 }
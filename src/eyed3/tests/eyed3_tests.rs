@@ -4,7 +4,7 @@ use rogue_logging::Error;
 use std::path::PathBuf;
 
 #[tokio::test]
-#[ignore]
+#[ignore = "requires sample mp3 files in ./output"]
 async fn show() -> Result<(), Error> {
     // Arrange
     let paths = DirectoryReader::new()
@@ -0,0 +1,135 @@
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+
+use crate::formats::TargetFormat;
+
+/// Separator between the name and settings of a [`CustomTarget`].
+const SEPARATOR: &str = "=";
+
+/// Bit rates in kbps that `lame` accepts for a constant bit rate.
+const CBR_BIT_RATES: [u16; 14] = [
+    32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+];
+
+/// Lowest average bit rate in kbps that `lame` accepts.
+const MIN_ABR_BIT_RATE: u16 = 8;
+
+/// Highest average bit rate in kbps that `lame` accepts.
+const MAX_ABR_BIT_RATE: u16 = 320;
+
+/// MP3 target with user-defined `lame` settings for a personal library.
+///
+/// Defined as `NAME=SETTINGS` where the settings are `V0` to `V9` for a variable bit
+/// rate, `ABR` and a bit rate for an average bit rate, or `CBR` and a bit rate for a
+/// constant bit rate.
+///
+/// The name is used in place of the format in the output directory. It must not be the
+/// name of a [`TargetFormat`] so a custom target is never mistaken for an indexer target.
+///
+/// Examples:
+/// - `V3=V3` an MP3 V3 (VBR)
+/// - `ABR 192=ABR192` an MP3 with an average bit rate of 192 kbps
+/// - `Car=CBR128` an MP3 with a constant bit rate of 128 kbps named `Car`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomTarget {
+    name: String,
+    encoding: Mp3Encoding,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mp3Encoding {
+    Vbr(u8),
+    Abr(u16),
+    Cbr(u16),
+}
+
+impl CustomTarget {
+    /// Parse a custom target from `NAME=SETTINGS`.
+    ///
+    /// Returns a description of the problem if it can't be parsed.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let Some((name, settings)) = value.split_once(SEPARATOR) else {
+            return Err(format!("Expected `NAME{SEPARATOR}SETTINGS`"));
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Name must not be empty".to_owned());
+        }
+        let is_reserved = TargetFormat::value_variants()
+            .iter()
+            .any(|format| format.get_name().eq_ignore_ascii_case(name));
+        if is_reserved {
+            return Err(format!(
+                "Name must not be the name of a target format: `{name}`"
+            ));
+        }
+        Ok(Self {
+            name: name.to_owned(),
+            encoding: parse_encoding(settings.trim())?,
+        })
+    }
+
+    /// Get the name used in place of the format in the output directory.
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the `lame` arguments that set the bit rate.
+    #[must_use]
+    pub fn get_lame_args(&self) -> Vec<String> {
+        match self.encoding {
+            Mp3Encoding::Vbr(quality) => {
+                vec!["-V".to_owned(), quality.to_string(), "--vbr-new".to_owned()]
+            }
+            Mp3Encoding::Abr(bit_rate) => {
+                vec!["-h".to_owned(), "--abr".to_owned(), bit_rate.to_string()]
+            }
+            Mp3Encoding::Cbr(bit_rate) => {
+                vec!["-h".to_owned(), "-b".to_owned(), bit_rate.to_string()]
+            }
+        }
+    }
+}
+
+impl Display for CustomTarget {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.name)
+    }
+}
+
+fn parse_encoding(settings: &str) -> Result<Mp3Encoding, String> {
+    let upper = settings.to_ascii_uppercase();
+    if let Some(quality) = upper.strip_prefix('V') {
+        return match quality.parse::<u8>() {
+            Ok(quality) if quality <= 9 => Ok(Mp3Encoding::Vbr(quality)),
+            _ => Err(format!("VBR quality must be from V0 to V9: `{settings}`")),
+        };
+    }
+    if let Some(bit_rate) = upper.strip_prefix("ABR") {
+        return match bit_rate.trim().parse::<u16>() {
+            Ok(bit_rate) if (MIN_ABR_BIT_RATE..=MAX_ABR_BIT_RATE).contains(&bit_rate) => {
+                Ok(Mp3Encoding::Abr(bit_rate))
+            }
+            _ => Err(format!(
+                "Average bit rate must be from {MIN_ABR_BIT_RATE} to {MAX_ABR_BIT_RATE}: `{settings}`"
+            )),
+        };
+    }
+    if let Some(bit_rate) = upper.strip_prefix("CBR") {
+        return match bit_rate.trim().parse::<u16>() {
+            Ok(bit_rate) if CBR_BIT_RATES.contains(&bit_rate) => Ok(Mp3Encoding::Cbr(bit_rate)),
+            _ => Err(format!(
+                "Constant bit rate must be one of {}: `{settings}`",
+                CBR_BIT_RATES
+                    .map(|bit_rate| bit_rate.to_string())
+                    .join(", ")
+            )),
+        };
+    }
+    Err(format!(
+        "Expected settings of `V0` to `V9`, `ABR` and a bit rate, or `CBR` and a bit rate: `{settings}`"
+    ))
+}
@@ -17,6 +17,10 @@ pub enum ExistingFormat {
     #[serde(rename = "320")]
     _320 = 2,
     V0 = 3,
+    V1 = 4,
+    V2 = 5,
+    #[serde(rename = "256")]
+    _256 = 6,
 }
 
 impl ExistingFormat {
@@ -26,6 +30,9 @@ impl ExistingFormat {
             ("FLAC", "24bit Lossless") => Some(Flac24),
             ("MP3", "320") => Some(_320),
             ("MP3", "V0 (VBR)") => Some(V0),
+            ("MP3", "V1 (VBR)") => Some(V1),
+            ("MP3", "V2 (VBR)") => Some(V2),
+            ("MP3", "256") => Some(_256),
             (format, encoding) => {
                 trace!(
                     "{} to determine ExistingFormat of `{format}` with encoding `{encoding}`",
@@ -43,6 +50,9 @@ impl ExistingFormat {
             Flac => "FLAC",
             _320 => "320",
             V0 => "V0",
+            V1 => "V1",
+            V2 => "V2",
+            _256 => "256",
         }
     }
 
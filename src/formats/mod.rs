@@ -1,9 +1,12 @@
+pub use custom_target::*;
 pub use existing_format::*;
 pub use existing_format_provider::*;
 pub use source_format::*;
 pub use target_format::*;
 pub use target_format_provider::*;
+pub use transcode_target::*;
 
+pub(crate) mod custom_target;
 pub(crate) mod existing_format;
 pub(crate) mod existing_format_provider;
 pub(crate) mod source_format;
@@ -11,3 +14,4 @@ pub(crate) mod target_format;
 pub(crate) mod target_format_provider;
 #[cfg(test)]
 mod tests;
+pub(crate) mod transcode_target;
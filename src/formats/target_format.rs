@@ -1,11 +1,17 @@
 use crate::formats::ExistingFormat;
-use crate::formats::TargetFormat::{Flac, V0, _320};
+use crate::formats::TargetFormat::{Flac, _256, _320, V0, V1, V2};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 
 /// Format to transcode to.
+///
+/// `V1`, `V2` and `256` are personal targets that are transcoded alongside the others but
+/// are only uploaded if `upload_extra_targets` is set.
+///
+/// Each target is a fixed preset named by its encoding on the indexer, so a target can
+/// always be uploaded.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum TargetFormat {
@@ -13,6 +19,11 @@ pub enum TargetFormat {
     #[serde(rename = "320")]
     _320 = 2,
     V0 = 3,
+    V1 = 4,
+    V2 = 5,
+    /// MP3 with an average bit rate of 256 kbps.
+    #[serde(rename = "256")]
+    _256 = 6,
 }
 
 impl TargetFormat {
@@ -22,15 +33,33 @@ impl TargetFormat {
             Flac => "FLAC",
             _320 => "320",
             V0 => "V0",
+            V1 => "V1",
+            V2 => "V2",
+            _256 => "256",
         }
     }
 
+    /// Is this a personal target that is not uploaded by default?
+    #[must_use]
+    pub fn is_extra(self) -> bool {
+        matches!(self, V1 | V2 | _256)
+    }
+
+    /// Is this an MP3 target?
+    #[must_use]
+    pub fn is_mp3(self) -> bool {
+        !matches!(self, Flac)
+    }
+
     #[must_use]
     pub fn to_existing(self) -> ExistingFormat {
         match self {
             Flac => ExistingFormat::Flac,
             _320 => ExistingFormat::_320,
             V0 => ExistingFormat::V0,
+            V1 => ExistingFormat::V1,
+            V2 => ExistingFormat::V2,
+            _256 => ExistingFormat::_256,
         }
     }
 
@@ -38,7 +67,7 @@ impl TargetFormat {
     pub fn get_file_extension(self) -> String {
         match self {
             Flac => "flac".to_owned(),
-            _320 | V0 => "mp3".to_owned(),
+            _320 | V0 | V1 | V2 | _256 => "mp3".to_owned(),
         }
     }
 
@@ -48,6 +77,9 @@ impl TargetFormat {
             Flac => "Lossless",
             _320 => "320",
             V0 => "V0 (VBR)",
+            V1 => "V1 (VBR)",
+            V2 => "V2 (VBR)",
+            _256 => "256",
         }
     }
 }
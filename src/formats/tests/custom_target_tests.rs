@@ -0,0 +1,75 @@
+use crate::formats::{CustomTarget, TranscodeTarget};
+
+#[test]
+fn custom_target_parse_vbr() {
+    // Arrange
+    let value = "V3=V3";
+
+    // Act
+    let target = &CustomTarget::parse(value).expect("should parse");
+
+    // Assert
+    assert_eq!(target.get_name(), "V3");
+    assert_eq!(target.get_lame_args(), vec!["-V", "3", "--vbr-new"]);
+    assert!(target.is_mp3());
+    assert_eq!(target.get_file_extension(), "mp3");
+}
+
+#[test]
+fn custom_target_parse_abr_and_cbr() {
+    // Arrange
+    let abr = "ABR 192 = abr192";
+    let cbr = "Car=CBR 128";
+
+    // Act
+    let abr = CustomTarget::parse(abr).expect("should parse");
+    let cbr = CustomTarget::parse(cbr).expect("should parse");
+
+    // Assert
+    assert_eq!(abr.get_name(), "ABR 192");
+    assert_eq!(abr.get_lame_args(), vec!["-h", "--abr", "192"]);
+    assert_eq!(cbr.get_name(), "Car");
+    assert_eq!(cbr.get_lame_args(), vec!["-h", "-b", "128"]);
+}
+
+#[test]
+fn custom_target_parse_invalid() {
+    // Arrange
+    let values = [
+        "V3",
+        "=V3",
+        "V3=V10",
+        "Low=ABR 400",
+        "Odd=CBR 100",
+        "Opus=OPUS128",
+    ];
+
+    // Act
+    let results: Vec<_> = values
+        .iter()
+        .map(|value| CustomTarget::parse(value))
+        .collect();
+
+    // Assert
+    for (value, result) in values.iter().zip(results) {
+        assert!(result.is_err(), "{value} should not parse");
+    }
+}
+
+#[test]
+fn custom_target_parse_reserved_name() {
+    // Arrange
+    let values = ["v0=V0", "FLAC=CBR320", "320=CBR320"];
+
+    // Act
+    let results: Vec<_> = values
+        .iter()
+        .map(|value| CustomTarget::parse(value))
+        .collect();
+
+    // Assert
+    for (value, result) in values.iter().zip(results) {
+        let error = result.expect_err("reserved name should not parse");
+        assert!(error.contains("target format"), "{value}: {error}");
+    }
+}
@@ -1 +1,2 @@
+mod custom_target_tests;
 mod target_format_provider_tests;
@@ -106,6 +106,49 @@ fn create_provider(target: BTreeSet<TargetFormat>, allow_existing: bool) -> Targ
         options: Ref::new(TargetOptions {
             target: Some(target.iter().copied().collect()),
             allow_existing: Some(allow_existing),
+            ..TargetOptions::default()
         }),
     }
 }
+
+#[test]
+fn from_flac_excludes_existing_extra_targets() {
+    // Arrange
+    let source = SourceFormat::Flac;
+    let target = BTreeSet::from([TargetFormat::V0, TargetFormat::V2, TargetFormat::_256]);
+    let existing = BTreeSet::from([ExistingFormat::Flac, ExistingFormat::V2]);
+    let provider = create_provider(target, false);
+
+    // Act
+    let result = provider.get(source, &existing);
+
+    // Assert
+    let expected = BTreeSet::from([TargetFormat::V0, TargetFormat::_256]);
+    assert_eq!(result, expected);
+    assert!(result.iter().all(|target| target.is_mp3()));
+    assert!(!TargetFormat::V0.is_extra());
+    assert!(TargetFormat::_256.is_extra());
+}
+
+#[test]
+fn from_flac_excludes_custom_targets() {
+    // Arrange
+    // Upload and cross-post take their targets from the provider so custom targets are
+    // never uploaded.
+    let source = SourceFormat::Flac;
+    let existing = BTreeSet::from([ExistingFormat::Flac]);
+    let provider = TargetFormatProvider {
+        options: Ref::new(TargetOptions {
+            target: Some(vec![TargetFormat::V0]),
+            custom_target: Some(vec!["V3=V3".to_owned(), "Car=CBR128".to_owned()]),
+            allow_existing: Some(false),
+            ..TargetOptions::default()
+        }),
+    };
+
+    // Act
+    let result = provider.get(source, &existing);
+
+    // Assert
+    assert_eq!(result, BTreeSet::from([TargetFormat::V0]));
+}
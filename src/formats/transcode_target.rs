@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use crate::formats::{CustomTarget, TargetFormat};
+
+/// Target that a source is transcoded to, either a [`TargetFormat`] or a [`CustomTarget`].
+///
+/// Only the naming and the checks of the output depend on the target so they accept
+/// either.
+pub trait TranscodeTarget: Copy + Display {
+    /// Get the name used for the format in the output directory.
+    fn get_name(&self) -> &str;
+
+    /// Get the file extension of each track.
+    fn get_file_extension(&self) -> String;
+
+    /// Is each track an MP3?
+    fn is_mp3(&self) -> bool;
+}
+
+impl TranscodeTarget for TargetFormat {
+    fn get_name(&self) -> &str {
+        TargetFormat::get_name(self)
+    }
+
+    fn get_file_extension(&self) -> String {
+        TargetFormat::get_file_extension(*self)
+    }
+
+    fn is_mp3(&self) -> bool {
+        TargetFormat::is_mp3(*self)
+    }
+}
+
+impl TranscodeTarget for &CustomTarget {
+    fn get_name(&self) -> &str {
+        CustomTarget::get_name(self)
+    }
+
+    fn get_file_extension(&self) -> String {
+        "mp3".to_owned()
+    }
+
+    fn is_mp3(&self) -> bool {
+        true
+    }
+}
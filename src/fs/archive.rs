@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use clap::ValueEnum;
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use tokio::fs::remove_file;
+use tokio::process::Command;
+
+use crate::dependencies::{GZIP, TAR, ZIP};
+use crate::errors::{command_error, path_error, OutputHandler};
+
+/// Highest compression level of an archive.
+pub const MAX_ARCHIVE_COMPRESSION: u32 = 9;
+
+/// Format of the archive a transcode directory is packaged into.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+pub enum ArchiveFormat {
+    /// Zip archive which can be opened without additional software on most systems.
+    #[serde(rename = "zip")]
+    #[value(name = "zip")]
+    Zip,
+    /// Gzip compressed tar archive.
+    #[serde(rename = "tar.gz")]
+    #[value(name = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Get the file extension without the leading period.
+    #[must_use]
+    pub fn get_extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Get the path of the archive of a directory.
+///
+/// The archive is placed beside the directory with the extension of the format appended
+/// to the directory name.
+#[must_use]
+pub fn get_archive_path(directory: &Path, format: ArchiveFormat) -> PathBuf {
+    let name = directory
+        .file_name()
+        .expect("directory should have a name")
+        .to_string_lossy();
+    directory.with_file_name(format!("{name}.{}", format.get_extension()))
+}
+
+/// Package a directory into an archive beside it.
+///
+/// The archive contains the directory itself so it extracts to a single directory.
+/// An existing archive is replaced.
+///
+/// `compression` is a level from `0` to `9`. Gzip has no level `0` so `tar.gz`
+/// archives are compressed with at least level `1`.
+///
+/// Returns the path of the archive.
+pub async fn create_archive(
+    directory: &Path,
+    format: ArchiveFormat,
+    compression: u32,
+) -> Result<PathBuf, Error> {
+    let action = "create archive";
+    let path = get_archive_path(directory, format);
+    if path.exists() {
+        remove_file(&path)
+            .await
+            .map_err(|e| path_error(e, action, &path))?;
+    }
+    let parent = directory.parent().expect("directory should have a parent");
+    let name = directory.file_name().expect("directory should have a name");
+    let compression = compression.min(MAX_ARCHIVE_COMPRESSION);
+    let result = match format {
+        ArchiveFormat::Zip => create_zip(parent, name.as_ref(), &path, compression).await,
+        ArchiveFormat::TarGz => create_tar_gz(parent, name.as_ref(), &path, compression).await,
+    };
+    if result.is_err() && path.exists() {
+        let _ = remove_file(&path).await;
+    }
+    result.map(|()| path)
+}
+
+async fn create_zip(
+    parent: &Path,
+    name: &Path,
+    path: &Path,
+    compression: u32,
+) -> Result<(), Error> {
+    let action = "create zip archive";
+    let output = Command::new(ZIP)
+        .current_dir(parent)
+        .arg("-r")
+        .arg("-q")
+        .arg(format!("-{compression}"))
+        .arg(path)
+        .arg(name)
+        .output()
+        .await
+        .map_err(|e| command_error(e, action, ZIP))?;
+    OutputHandler::execute(output, action, ZIP)?;
+    Ok(())
+}
+
+async fn create_tar_gz(
+    parent: &Path,
+    name: &Path,
+    path: &Path,
+    compression: u32,
+) -> Result<(), Error> {
+    let action = "create tar.gz archive";
+    let file = File::create(path).map_err(|e| path_error(e, action, path))?;
+    let mut tar = Command::new(TAR)
+        .arg("-c")
+        .arg("-f")
+        .arg("-")
+        .arg("-C")
+        .arg(parent)
+        .arg(name)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| command_error(e, action, TAR))?;
+    let tar_stdout: Stdio = tar
+        .stdout
+        .take()
+        .expect("stdout should be piped")
+        .try_into()
+        .map_err(|e| command_error(e, action, TAR))?;
+    let gzip = Command::new(GZIP)
+        .arg(format!("-{}", compression.max(1)))
+        .stdin(tar_stdout)
+        .stdout(file)
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| command_error(e, action, GZIP))?;
+    let gzip_output = gzip
+        .wait_with_output()
+        .await
+        .map_err(|e| command_error(e, action, GZIP))?;
+    let tar_output = tar
+        .wait_with_output()
+        .await
+        .map_err(|e| command_error(e, action, TAR))?;
+    OutputHandler::execute(tar_output, action, TAR)?;
+    OutputHandler::execute(gzip_output, action, GZIP)?;
+    Ok(())
+}
+
+impl Display for ArchiveFormat {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_extension().fmt(formatter)
+    }
+}
@@ -0,0 +1,58 @@
+use std::fs::File;
+use std::io::{copy, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use rogue_logging::Error;
+use sha2::{Digest, Sha256};
+
+use crate::db::Hash;
+use crate::errors::{error, path_error};
+use crate::fs::DirectoryReader;
+
+/// File name of the checksum manifest written to a transcode directory.
+pub const CHECKSUM_MANIFEST_NAME: &str = "SHA256SUMS";
+
+/// Write a `SHA256SUMS` manifest of every file in a directory.
+///
+/// Each line is the SHA-256 hash and relative path of a file in the format of `sha256sum`
+/// so the files can be checked with `sha256sum --check SHA256SUMS`.
+///
+/// Returns the path of the manifest.
+pub fn create_checksum_manifest(directory: &Path) -> Result<PathBuf, Error> {
+    let action = "create checksum manifest";
+    let manifest = directory.join(CHECKSUM_MANIFEST_NAME);
+    let mut lines = Vec::new();
+    for path in DirectoryReader::new()
+        .read(directory)
+        .map_err(|e| path_error(e, action, directory))?
+    {
+        if path == manifest {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(directory)
+            .expect("file should be in directory")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        lines.push((relative, get_file_hash(&path)?));
+    }
+    lines.sort();
+    let file = File::create(&manifest).map_err(|e| path_error(e, action, &manifest))?;
+    let mut writer = BufWriter::new(file);
+    for (relative, hash) in lines {
+        writeln!(writer, "{hash}  {relative}").map_err(|e| path_error(e, action, &manifest))?;
+    }
+    writer.flush().map_err(|e| error(action, e.to_string()))?;
+    Ok(manifest)
+}
+
+/// Get the SHA-256 hash of a file as lowercase hex.
+pub fn get_file_hash(path: &Path) -> Result<String, Error> {
+    let action = "hash file";
+    let file = File::open(path).map_err(|e| path_error(e, action, path))?;
+    let mut hasher = Sha256::new();
+    copy(&mut BufReader::new(file), &mut hasher).map_err(|e| path_error(e, action, path))?;
+    Ok(Hash::<32>::new(hasher.finalize().into()).to_hex())
+}
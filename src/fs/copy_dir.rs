@@ -1,17 +1,18 @@
 use crate::errors::{error, io_error};
+use crate::fs::LinkStrategy;
 use rogue_logging::Error;
 use std::path::Path;
-use tokio::fs::{copy, create_dir, hard_link, read_dir};
+use tokio::fs::{create_dir, read_dir};
 
 /// Copy the contents of one directory to another.
 ///
 /// The target directory will be created if it does not exist, but its parent must exist.
 ///
-/// If `use_hard_link` is true, the files will be hard linked instead of copied.
+/// Each file is placed with the [`LinkStrategy`], falling back to a copy if it fails.
 pub async fn copy_dir(
     source_dir: &Path,
     target_dir: &Path,
-    use_hard_link: bool,
+    strategy: LinkStrategy,
 ) -> Result<(), Error> {
     if !source_dir.exists() {
         return Err(error(
@@ -60,15 +61,9 @@ pub async fn copy_dir(
         let source_entry_path = entry.path();
         let target_path = target_dir.join(entry.file_name());
         if source_entry_path.is_dir() {
-            Box::pin(copy_dir(&source_entry_path, &target_path, use_hard_link)).await?;
-        } else if use_hard_link {
-            hard_link(&source_entry_path, &target_path)
-                .await
-                .map_err(|e| io_error(e, "hard link file to target directory"))?;
+            Box::pin(copy_dir(&source_entry_path, &target_path, strategy)).await?;
         } else {
-            copy(&source_entry_path, &target_path)
-                .await
-                .map_err(|e| io_error(e, "copy file to target directory"))?;
+            strategy.execute(&source_entry_path, &target_path).await?;
         }
     }
     Ok(())
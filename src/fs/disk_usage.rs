@@ -0,0 +1,47 @@
+use std::fs::{read_dir, symlink_metadata};
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::dependencies::DF;
+
+/// Get the bytes available in the file system of a directory.
+pub async fn get_free_space(dir: &Path) -> Option<u64> {
+    let output = Command::new(DF)
+        .arg("-Pk")
+        .arg(PathBuf::from(dir))
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse the bytes available from the POSIX output of `df -Pk`.
+pub(crate) fn parse_df_output(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let kibibytes: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    kibibytes.checked_mul(1024)
+}
+
+/// Get the total bytes of the files in a directory and its sub directories.
+///
+/// Symlinks are not followed and files that can't be read are ignored.
+#[must_use]
+pub fn get_dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    let Ok(entries) = read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| get_dir_size(&entry.path()))
+        .sum()
+}
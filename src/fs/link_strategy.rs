@@ -0,0 +1,105 @@
+use std::io;
+use std::path::{absolute, Path};
+
+use clap::ValueEnum;
+use colored::Colorize;
+use log::debug;
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{copy, hard_link, symlink};
+use tokio::process::Command;
+
+use crate::dependencies::CP;
+use crate::errors::io_error;
+
+/// Strategy to place a file at a destination.
+///
+/// If the strategy fails, such as a hard link across filesystems or a reflink on a
+/// filesystem without copy-on-write support, then the file is copied instead.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkStrategy {
+    /// Copy the file.
+    #[default]
+    Copy,
+    /// Hard link the file which requires the same filesystem.
+    Hardlink,
+    /// Clone the file on a copy-on-write filesystem such as Btrfs, XFS or APFS.
+    Reflink,
+    /// Create a symbolic link to the absolute path of the file.
+    Symlink,
+}
+
+impl LinkStrategy {
+    /// Get the strategy implied by the deprecated `hard_link` option.
+    #[must_use]
+    pub fn from_hard_link(hard_link: bool) -> Self {
+        if hard_link {
+            LinkStrategy::Hardlink
+        } else {
+            LinkStrategy::Copy
+        }
+    }
+
+    /// Get a past tense verb describing the strategy for logging.
+    #[must_use]
+    pub fn get_verb(self) -> &'static str {
+        match self {
+            LinkStrategy::Copy => "Copied",
+            LinkStrategy::Hardlink => "Hard Linked",
+            LinkStrategy::Reflink => "Reflinked",
+            LinkStrategy::Symlink => "Symlinked",
+        }
+    }
+
+    /// Place the file at `source` at `target` falling back to a copy if the strategy fails.
+    ///
+    /// Returns the strategy that was used.
+    pub async fn execute(self, source: &Path, target: &Path) -> Result<LinkStrategy, Error> {
+        if self != LinkStrategy::Copy {
+            match self.execute_without_fallback(source, target).await {
+                Ok(()) => return Ok(self),
+                Err(error) => {
+                    debug!(
+                        "{} to place {} with {self:?} so it will be copied: {error}",
+                        "Failed".bold(),
+                        source.display()
+                    );
+                }
+            }
+        }
+        copy(source, target)
+            .await
+            .map_err(|e| io_error(e, "copy file"))?;
+        Ok(LinkStrategy::Copy)
+    }
+
+    async fn execute_without_fallback(self, source: &Path, target: &Path) -> io::Result<()> {
+        match self {
+            LinkStrategy::Copy => copy(source, target).await.map(|_| ()),
+            LinkStrategy::Hardlink => hard_link(source, target).await,
+            LinkStrategy::Reflink => reflink(source, target).await,
+            LinkStrategy::Symlink => symlink(absolute(source)?, target).await,
+        }
+    }
+}
+
+async fn reflink(source: &Path, target: &Path) -> io::Result<()> {
+    let flag = if cfg!(target_os = "macos") {
+        "-c"
+    } else {
+        "--reflink=always"
+    };
+    let output = Command::new(CP)
+        .arg(flag)
+        .arg(source)
+        .arg(target)
+        .output()
+        .await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(io::Error::other(stderr.trim().to_owned()))
+    }
+}
@@ -1,17 +1,31 @@
 pub use additional_file::*;
+pub use archive::*;
+pub use checksum_manifest::*;
 pub use collector::*;
 pub use copy_dir::*;
 pub use directory_reader::*;
+pub use disk_usage::*;
 pub use flac_file::*;
+pub use link_strategy::*;
+pub use move_staged::*;
+pub use nested_dirs::*;
 pub use path_manager::*;
+pub use permissions::*;
 pub(crate) use tags::*;
 
 mod additional_file;
+pub(crate) mod archive;
+pub(crate) mod checksum_manifest;
 pub(crate) mod collector;
 pub(crate) mod copy_dir;
 pub(crate) mod directory_reader;
+pub(crate) mod disk_usage;
 pub(crate) mod flac_file;
+pub(crate) mod link_strategy;
+pub(crate) mod move_staged;
+pub(crate) mod nested_dirs;
 pub(crate) mod path_manager;
+pub(crate) mod permissions;
 pub(crate) mod tags;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,30 @@
+use crate::errors::io_error;
+use rogue_logging::Error;
+use std::path::Path;
+use tokio::fs::{create_dir_all, remove_dir_all, remove_file, rename};
+
+/// Move a staged file or directory to its final location.
+///
+/// Any existing file or directory at the target is replaced so a partial output from an
+/// earlier attempt is not mixed with the new output.
+///
+/// The staged path and the target must be on the same file system so the move is a rename.
+pub async fn move_staged(staged: &Path, target: &Path) -> Result<(), Error> {
+    if target.is_dir() {
+        remove_dir_all(target)
+            .await
+            .map_err(|e| io_error(e, "remove existing output directory"))?;
+    } else if target.exists() {
+        remove_file(target)
+            .await
+            .map_err(|e| io_error(e, "remove existing output file"))?;
+    }
+    if let Some(parent) = target.parent() {
+        create_dir_all(parent)
+            .await
+            .map_err(|e| io_error(e, "create output directory"))?;
+    }
+    rename(staged, target)
+        .await
+        .map_err(|e| io_error(e, "move staged output"))
+}
@@ -0,0 +1,67 @@
+use std::fs::read_dir;
+use std::path::{Component, Path, PathBuf};
+
+/// Get the directory inside a source that actually contains the content.
+///
+/// Sources are sometimes wrapped in junk folders such as `Album/Album/` or `Album/FLAC/`.
+/// Starting from the source directory, each directory that contains no FLAC files and
+/// exactly one sub directory is treated as a wrapper and skipped.
+///
+/// Returns the source directory if it's not wrapped.
+#[must_use]
+pub fn get_content_root(source_dir: &Path) -> PathBuf {
+    let mut root = source_dir.to_path_buf();
+    while let Some(child) = get_wrapped_dir(&root) {
+        root = child;
+    }
+    root
+}
+
+/// Get the only sub directory of a directory that contains no FLAC files.
+fn get_wrapped_dir(dir: &Path) -> Option<PathBuf> {
+    let mut sub_dirs = Vec::new();
+    for entry in read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            sub_dirs.push(path);
+        } else if is_flac(&path) {
+            return None;
+        }
+    }
+    if sub_dirs.len() == 1 {
+        sub_dirs.pop()
+    } else {
+        None
+    }
+}
+
+fn is_flac(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("flac"))
+}
+
+/// Remove directories that are nested inside a directory of the same name.
+///
+/// The names are compared case insensitively so `Disc 1/DISC 1/` becomes `Disc 1/`.
+#[must_use]
+pub fn collapse_nested_dirs(sub_dir: &Path) -> PathBuf {
+    let mut output = PathBuf::new();
+    let mut previous: Option<String> = None;
+    for component in sub_dir.components() {
+        if let Component::Normal(name) = component {
+            let name = name.to_string_lossy().to_lowercase();
+            if previous.as_ref() == Some(&name) {
+                continue;
+            }
+            previous = Some(name);
+        }
+        output.push(component);
+    }
+    output
+}
+
+/// Check if a sub directory contains a directory nested inside a directory of the same name.
+#[must_use]
+pub fn has_nested_dirs(sub_dir: &Path) -> bool {
+    collapse_nested_dirs(sub_dir) != sub_dir
+}
@@ -1,19 +1,28 @@
 use std::fs::create_dir;
-use std::path::PathBuf;
-
-use crate::formats::TargetFormat;
-use crate::fs::FlacFile;
-use crate::imdl::ImdlCommand;
-use crate::naming::{SpectrogramName, TranscodeName};
-use crate::options::{CacheOptions, SharedOptions};
-use crate::source::Source;
+use std::path::{absolute, Component, Path, PathBuf};
+
+use crate::formats::{TargetFormat, TranscodeTarget};
+use crate::fs::{collapse_nested_dirs, get_content_root, Collector, FlacFile};
+use crate::naming::{
+    LayoutTemplate, NameSubstitution, Sanitizer, ShortenedName, Shortener, SpectrogramLayout,
+    TrackName, TranscodeName, Transliterator, UnicodeForm,
+};
+use crate::options::{CacheOptions, SharedOptions, TorrentOptions};
+use crate::source::{Metadata, Source, MAX_PATH_LENGTH};
+use crate::torrent::{AnnounceProvider, TorrentCreator};
 use di::{injectable, Ref};
 use rogue_logging::Error;
 
+/// Name of the directory inside the output directory where transcodes are staged.
+pub const STAGING_DIR_NAME: &str = ".staging";
+
+#[allow(clippy::struct_field_names)]
 #[injectable]
 pub struct PathManager {
     shared_options: Ref<SharedOptions>,
     cache_options: Ref<CacheOptions>,
+    torrent_options: Ref<TorrentOptions>,
+    announce: Ref<AnnounceProvider>,
 }
 
 impl PathManager {
@@ -27,7 +36,12 @@ impl PathManager {
 
     #[must_use]
     pub fn get_source_torrent_path(&self, source: &Source) -> PathBuf {
-        let id = source.torrent.id;
+        self.get_cached_torrent_path(source.torrent.id)
+    }
+
+    /// Get the path of the cached `.torrent` file of a torrent id.
+    #[must_use]
+    pub fn get_cached_torrent_path(&self, id: u32) -> PathBuf {
         let indexer = self
             .shared_options
             .indexer
@@ -40,40 +54,386 @@ impl PathManager {
         torrents_dir.join(format!("{id}.{indexer}.torrent"))
     }
 
+    /// Get the path of the cached verification of a source.
+    #[must_use]
+    pub fn get_verify_cache_path(&self, source: &Source) -> PathBuf {
+        let id = source.torrent.id;
+        let indexer = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        self.get_cache_dir()
+            .join("verify")
+            .join(format!("{id}.{indexer}.yml"))
+    }
+
+    /// Get the path of the cached `MusicBrainz` match of a source.
+    #[must_use]
+    pub fn get_musicbrainz_cache_path(&self, source: &Source) -> PathBuf {
+        let id = source.torrent.id;
+        let indexer = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        self.get_cache_dir()
+            .join("musicbrainz")
+            .join(format!("{id}.{indexer}.yml"))
+    }
+
+    /// Get the output directory.
+    ///
+    /// On Windows this is an extended-length path so the output can exceed 260 characters.
     #[must_use]
     pub fn get_output_dir(&self) -> PathBuf {
-        self.shared_options
+        let output = self
+            .shared_options
             .output
             .clone()
-            .expect("output should be set")
+            .expect("output should be set");
+        if cfg!(target_os = "windows") {
+            get_extended_length_path(&output)
+        } else {
+            output
+        }
+    }
+
+    /// Get the name of an output file or directory.
+    ///
+    /// The `name_substitution` rules are applied, then the name is transliterated to ASCII
+    /// if `transliterate` is set, characters that are not `allowed_characters` are replaced,
+    /// it's stripped of leading and repeated spaces if `normalize_names` is set, made safe
+    /// to open on Windows and normalized to `unicode_form`.
+    #[must_use]
+    pub fn get_output_name(&self, name: &str) -> String {
+        let name = self
+            .shared_options
+            .get_character_whitelist()
+            .execute(&self.get_substituted_name(name));
+        let name = if self.is_normalize_names() {
+            Sanitizer::execute_spaces(&name)
+        } else {
+            name
+        };
+        self.get_unicode_form()
+            .normalize(&Sanitizer::execute_windows(&name))
+    }
+
+    /// Get the sub directory of an output file.
+    ///
+    /// The `name_substitution` rules are applied to each name, then each is transliterated
+    /// to ASCII if `transliterate` is set, characters that are not `allowed_characters` are
+    /// replaced, it's stripped of leading and repeated spaces if `normalize_names` is set,
+    /// made safe to open on Windows and normalized to `unicode_form`.
+    #[must_use]
+    pub fn get_output_sub_dir(&self, sub_dir: &Path) -> PathBuf {
+        let substitutions = self.shared_options.get_name_substitutions();
+        let sub_dir: PathBuf = sub_dir
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => PathBuf::from(NameSubstitution::apply_all(
+                    &substitutions,
+                    &name.to_string_lossy(),
+                )),
+                _ => PathBuf::from(component.as_os_str()),
+            })
+            .collect();
+        let sub_dir = if self.is_transliterate() {
+            Transliterator::execute_path(&sub_dir)
+        } else {
+            sub_dir
+        };
+        let sub_dir = self
+            .shared_options
+            .get_character_whitelist()
+            .execute_path(&sub_dir);
+        let sub_dir = if self.is_normalize_names() {
+            sub_dir
+                .components()
+                .map(|component| match component {
+                    Component::Normal(name) => {
+                        PathBuf::from(Sanitizer::execute_spaces(&name.to_string_lossy()))
+                    }
+                    _ => PathBuf::from(component.as_os_str()),
+                })
+                .collect()
+        } else {
+            sub_dir
+        };
+        self.get_unicode_form()
+            .normalize_path(&Sanitizer::execute_windows_path(&sub_dir))
     }
 
+    /// Get the characters of a name that are replaced as they're not `allowed_characters`.
+    ///
+    /// The `name_substitution` rules and `transliterate` are applied first so only the
+    /// characters that would change the output name are included.
+    #[must_use]
+    pub fn get_disallowed_characters(&self, name: &str) -> Vec<char> {
+        self.shared_options
+            .get_character_whitelist()
+            .get_disallowed(&self.get_substituted_name(name))
+    }
+
+    /// Apply the `name_substitution` rules and transliterate if `transliterate` is set.
+    fn get_substituted_name(&self, name: &str) -> String {
+        let name = NameSubstitution::apply_all(&self.shared_options.get_name_substitutions(), name);
+        if self.is_transliterate() {
+            Transliterator::execute(&name)
+        } else {
+            name
+        }
+    }
+
+    fn is_transliterate(&self) -> bool {
+        self.shared_options
+            .transliterate
+            .expect("transliterate should be set")
+    }
+
+    fn is_normalize_names(&self) -> bool {
+        self.shared_options
+            .normalize_names
+            .expect("normalize_names should be set")
+    }
+
+    fn get_unicode_form(&self) -> UnicodeForm {
+        self.shared_options
+            .unicode_form
+            .expect("unicode_form should be set")
+    }
+
+    /// Get the spectrogram directory of a [`Source`].
+    ///
+    /// The path is resolved from the `spectrogram_layout` template, relative to the output
+    /// directory unless it starts with `{source_parent}`.
     #[must_use]
     pub fn get_spectrogram_dir(&self, source: &Source) -> PathBuf {
-        self.get_output_dir()
-            .join(SpectrogramName::get(&source.metadata))
+        let template = self
+            .shared_options
+            .spectrogram_layout
+            .clone()
+            .expect("spectrogram_layout should be set");
+        let layout = self.get_output_sub_dir(&SpectrogramLayout::get(&template, &source.metadata));
+        if SpectrogramLayout::is_source_relative(&template) {
+            source
+                .directory
+                .parent()
+                .map_or_else(|| self.get_output_dir(), Path::to_path_buf)
+                .join(layout)
+        } else {
+            self.get_output_dir().join(layout)
+        }
     }
 
     #[must_use]
-    pub fn get_transcode_target_dir(&self, source: &Source, target: TargetFormat) -> PathBuf {
+    pub fn get_transcode_target_dir(
+        &self,
+        source: &Source,
+        target: impl TranscodeTarget,
+    ) -> PathBuf {
         self.get_output_dir()
-            .join(TranscodeName::get(&source.metadata, target))
+            .join(self.get_transcode_layout(source, target))
+    }
+
+    /// Get the path of the transcode directory relative to the output directory.
+    ///
+    /// The path is resolved from the `output_layout` template.
+    ///
+    /// If `auto_shorten` is set and a transcode path would exceed [`MAX_PATH_LENGTH`] then
+    /// the album name is shortened.
+    #[must_use]
+    pub fn get_transcode_layout(&self, source: &Source, target: impl TranscodeTarget) -> PathBuf {
+        let layout = self.get_layout(&source.metadata, target);
+        if !self.is_auto_shorten() || !source.directory.is_dir() {
+            return layout;
+        }
+        let Some(shortened) = Shortener::shorten_album(&source.metadata) else {
+            return layout;
+        };
+        let dir = self.get_output_dir().join(&layout);
+        let is_too_long = Collector::get_flacs(&source.directory)
+            .iter()
+            .any(|flac| is_too_long(&dir.join(self.get_file_path_in(&dir, source, target, flac))));
+        if is_too_long {
+            self.get_layout(&shortened, target)
+        } else {
+            layout
+        }
+    }
+
+    fn get_layout(&self, metadata: &Metadata, target: impl TranscodeTarget) -> PathBuf {
+        let template = self
+            .shared_options
+            .output_layout
+            .clone()
+            .expect("output_layout should be set");
+        self.get_output_sub_dir(&LayoutTemplate::get(&template, metadata, target))
     }
 
     #[must_use]
     pub fn get_transcode_path(
         &self,
         source: &Source,
-        target: TargetFormat,
+        target: impl TranscodeTarget,
         flac: &FlacFile,
     ) -> PathBuf {
-        let extension = target.get_file_extension();
-        let filename = flac.file_name.clone() + "." + extension.as_str();
         self.get_transcode_target_dir(source, target)
-            .join(&flac.sub_dir)
+            .join(self.get_transcode_file_path(source, target, flac))
+    }
+
+    /// Get the staging directory of a [`Source`].
+    ///
+    /// Transcodes are written to the staging directory and only renamed into the output
+    /// directory once every target has succeeded so other tools never see a partial album.
+    ///
+    /// The staging directory is inside the output directory so the rename is atomic.
+    #[must_use]
+    pub fn get_staging_dir(&self, source: &Source) -> PathBuf {
+        self.get_output_dir()
+            .join(STAGING_DIR_NAME)
+            .join(source.torrent.id.to_string())
+    }
+
+    #[must_use]
+    pub fn get_staged_target_dir(&self, source: &Source, target: impl TranscodeTarget) -> PathBuf {
+        self.get_staging_dir(source)
+            .join(self.get_transcode_layout(source, target))
+    }
+
+    #[must_use]
+    pub fn get_staged_transcode_path(
+        &self,
+        source: &Source,
+        target: impl TranscodeTarget,
+        flac: &FlacFile,
+    ) -> PathBuf {
+        self.get_staged_target_dir(source, target)
+            .join(self.get_transcode_file_path(source, target, flac))
+    }
+
+    #[must_use]
+    pub fn get_staged_torrent_path(&self, source: &Source, target: TargetFormat) -> PathBuf {
+        let filename = self.get_output_name(&TranscodeName::get(&source.metadata, target));
+        self.get_staging_dir(source).join(filename + ".torrent")
+    }
+
+    /// Get the path of a transcoded [`FlacFile`] relative to the target directory.
+    fn get_transcode_file_path(
+        &self,
+        source: &Source,
+        target: impl TranscodeTarget,
+        flac: &FlacFile,
+    ) -> PathBuf {
+        if self.is_auto_shorten() {
+            let dir = self.get_transcode_target_dir(source, target);
+            self.get_file_path_in(&dir, source, target, flac)
+        } else {
+            self.get_unshortened_file_path(source, target, flac)
+        }
+    }
+
+    /// Get the path of a transcoded [`FlacFile`] relative to the target directory `dir`.
+    ///
+    /// If `auto_shorten` is set and the path would exceed [`MAX_PATH_LENGTH`] then the
+    /// track is named from its track number and title if that's shorter.
+    fn get_file_path_in(
+        &self,
+        dir: &Path,
+        source: &Source,
+        target: impl TranscodeTarget,
+        flac: &FlacFile,
+    ) -> PathBuf {
+        let path = self.get_unshortened_file_path(source, target, flac);
+        if !self.is_auto_shorten() || !is_too_long(&dir.join(&path)) {
+            return path;
+        }
+        match TrackName::get(flac) {
+            Some(name) if name.len() < flac.file_name.len() => {
+                let filename = self.get_output_name(&name) + "." + &target.get_file_extension();
+                path.with_file_name(filename)
+            }
+            _ => path,
+        }
+    }
+
+    fn get_unshortened_file_path(
+        &self,
+        source: &Source,
+        target: impl TranscodeTarget,
+        flac: &FlacFile,
+    ) -> PathBuf {
+        let extension = target.get_file_extension();
+        let filename = self.get_output_name(&flac.file_name) + "." + extension.as_str();
+        self.get_output_sub_dir(&self.get_flac_sub_dir(source, flac))
             .join(filename)
     }
 
+    /// Get the names that `auto_shorten` will shorten in the transcode of a [`Source`].
+    #[must_use]
+    pub fn get_shortened_names(&self, source: &Source, target: TargetFormat) -> Vec<ShortenedName> {
+        let mut names = Vec::new();
+        if !self.is_auto_shorten() || !source.directory.is_dir() {
+            return names;
+        }
+        let before = self.get_layout(&source.metadata, target);
+        let after = self.get_transcode_layout(source, target);
+        if before != after {
+            names.push(ShortenedName::new(&before, &after));
+        }
+        let dir = self.get_output_dir().join(&after);
+        for flac in Collector::get_flacs(&source.directory) {
+            let before = self.get_unshortened_file_path(source, target, &flac);
+            let after = self.get_file_path_in(&dir, source, target, &flac);
+            if before != after {
+                names.push(ShortenedName::new(&before, &after));
+            }
+        }
+        names
+    }
+
+    fn is_auto_shorten(&self) -> bool {
+        self.shared_options
+            .auto_shorten
+            .expect("auto_shorten should be set")
+    }
+
+    /// Get the directory of the source that transcodes and additional files are taken from.
+    ///
+    /// If `flatten_nested_dirs` is set then junk wrapper folders are skipped.
+    #[must_use]
+    pub fn get_content_root(&self, source: &Source) -> PathBuf {
+        if self.is_flatten_nested_dirs() {
+            get_content_root(&source.directory)
+        } else {
+            source.directory.clone()
+        }
+    }
+
+    /// Get the sub directory of a [`FlacFile`] relative to the content root.
+    ///
+    /// If `flatten_nested_dirs` is set then nested folders of the same name are collapsed.
+    fn get_flac_sub_dir(&self, source: &Source, flac: &FlacFile) -> PathBuf {
+        if !self.is_flatten_nested_dirs() {
+            return flac.sub_dir.clone();
+        }
+        let root = get_content_root(&source.directory);
+        let sub_dir = flac
+            .path
+            .strip_prefix(&root)
+            .ok()
+            .and_then(Path::parent)
+            .map_or_else(|| flac.sub_dir.clone(), Path::to_path_buf);
+        collapse_nested_dirs(&sub_dir)
+    }
+
+    fn is_flatten_nested_dirs(&self) -> bool {
+        self.shared_options
+            .flatten_nested_dirs
+            .expect("flatten_nested_dirs should be set")
+    }
+
     #[must_use]
     pub fn get_torrent_path(
         &self,
@@ -81,7 +441,7 @@ impl PathManager {
         target: TargetFormat,
         include_indexer: bool,
     ) -> PathBuf {
-        let mut filename = TranscodeName::get(&source.metadata, target);
+        let mut filename = self.get_output_name(&TranscodeName::get(&source.metadata, target));
         if include_indexer {
             let indexer = self
                 .shared_options
@@ -95,6 +455,23 @@ impl PathManager {
         self.get_output_dir().join(filename)
     }
 
+    /// Get the path of the `.torrent` file of a transcode cross-posted to another indexer.
+    ///
+    /// Example `path/to/Artist - Album [2012] [WEB FLAC].ops.torrent`
+    #[must_use]
+    pub fn get_cross_post_torrent_path(
+        &self,
+        source: &Source,
+        target: TargetFormat,
+        indexer: &str,
+    ) -> PathBuf {
+        let mut filename = self.get_output_name(&TranscodeName::get(&source.metadata, target));
+        filename.push('.');
+        filename.push_str(indexer);
+        filename.push_str(".torrent");
+        self.get_output_dir().join(filename)
+    }
+
     /// Get the *torrent path with suffix* if it exists.
     ///
     /// Example `path/to/Artist - Album [2012] [WEB FLAC].abc.torrent`
@@ -122,23 +499,23 @@ impl PathManager {
             return Ok(None);
         }
         let transcode_dir = self.get_transcode_target_dir(source, target);
-        let announce_url = self
-            .shared_options
-            .announce_url
-            .clone()
-            .expect("announce should be set");
+        let announce_url = self.announce.get().await?;
         let indexer = self
             .shared_options
             .indexer
             .clone()
             .expect("indexer should be set")
             .to_lowercase();
-        let success = ImdlCommand::duplicate_torrent(
+        let success = TorrentCreator::duplicate(
             &path_without_indexer,
             &path_with_indexer,
             &transcode_dir,
             announce_url,
             indexer,
+            &self.torrent_options.get_piece_length(),
+            self.torrent_options
+                .torrent_version
+                .expect("torrent_version should be set"),
         )
         .await?;
         if success {
@@ -148,3 +525,31 @@ impl PathManager {
         }
     }
 }
+
+/// Get an extended-length Windows path that isn't limited to 260 characters.
+///
+/// The path is made absolute and prefixed with `\\?\` or `\\?\UNC\` for network shares.
+#[must_use]
+pub fn get_extended_length_path(path: &Path) -> PathBuf {
+    let path = absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    PathBuf::from(to_extended_length(&path.to_string_lossy()))
+}
+
+/// Prefix an absolute Windows path so it's not limited to 260 characters.
+#[must_use]
+pub fn to_extended_length(path: &str) -> String {
+    let path = path.replace('/', r"\");
+    if path.starts_with(r"\\?\") {
+        path
+    } else if let Some(share) = path.strip_prefix(r"\\") {
+        format!(r"\\?\UNC\{share}")
+    } else {
+        format!(r"\\?\{path}")
+    }
+}
+
+/// Check if a transcode path exceeds [`MAX_PATH_LENGTH`].
+fn is_too_long(path: &Path) -> bool {
+    let length = isize::try_from(path.to_string_lossy().len()).unwrap_or(isize::MAX);
+    length > MAX_PATH_LENGTH
+}
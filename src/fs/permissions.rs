@@ -0,0 +1,136 @@
+use std::fs::{read_dir, read_to_string, set_permissions, symlink_metadata};
+use std::os::unix::fs::{lchown, PermissionsExt};
+use std::os::unix::prelude::MetadataExt;
+use std::path::Path;
+
+use rogue_logging::Error;
+
+use crate::errors::path_error;
+
+/// Largest permission mode including the setuid, setgid and sticky bits.
+const MAX_MODE: u32 = 0o7777;
+
+/// User and group to own created files and directories.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Owner {
+    /// User id or `None` to leave the user unchanged.
+    pub uid: Option<u32>,
+    /// Group id or `None` to leave the group unchanged.
+    pub gid: Option<u32>,
+}
+
+/// Permissions to apply to created files and directories.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OutputPermissions {
+    /// Mode of files or `None` to leave the mode unchanged.
+    pub file_mode: Option<u32>,
+    /// Mode of directories or `None` to leave the mode unchanged.
+    pub dir_mode: Option<u32>,
+    /// Owner of files and directories or `None` to leave the owner unchanged.
+    pub owner: Option<Owner>,
+}
+
+impl OutputPermissions {
+    /// Check if there are no permissions to apply.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.file_mode.is_none() && self.dir_mode.is_none() && self.owner.is_none()
+    }
+
+    /// Apply the permissions to a file, or a directory and everything inside it.
+    ///
+    /// Symbolic links are changed but not followed, and files with more than one hard
+    /// link are skipped, so the files of a linked source are left untouched.
+    pub fn apply(&self, path: &Path) -> Result<(), Error> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let action = "set output permissions";
+        let metadata = symlink_metadata(path).map_err(|e| path_error(e, action, path))?;
+        if !metadata.is_dir() && metadata.nlink() > 1 {
+            return Ok(());
+        }
+        if let Some(owner) = self.owner {
+            lchown(path, owner.uid, owner.gid).map_err(|e| path_error(e, action, path))?;
+        }
+        if metadata.is_symlink() {
+            return Ok(());
+        }
+        let mode = if metadata.is_dir() {
+            self.dir_mode
+        } else {
+            self.file_mode
+        };
+        if let Some(mode) = mode {
+            let mut permissions = metadata.permissions();
+            permissions.set_mode(mode);
+            set_permissions(path, permissions).map_err(|e| path_error(e, action, path))?;
+        }
+        if metadata.is_dir() {
+            for entry in read_dir(path).map_err(|e| path_error(e, action, path))? {
+                let entry = entry.map_err(|e| path_error(e, action, path))?;
+                self.apply(&entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse an octal permission mode such as `664` or `0o775`.
+#[must_use]
+pub fn parse_mode(value: &str) -> Option<u32> {
+    let digits = value.strip_prefix("0o").unwrap_or(value);
+    if digits.is_empty() {
+        return None;
+    }
+    u32::from_str_radix(digits, 8)
+        .ok()
+        .filter(|mode| *mode <= MAX_MODE)
+}
+
+/// Parse an owner such as `1000:1000`, `media:media`, `media` or `:media`.
+///
+/// Names are resolved with `/etc/passwd` and `/etc/group`.
+#[must_use]
+pub fn get_owner(value: &str) -> Option<Owner> {
+    let passwd = read_to_string("/etc/passwd").unwrap_or_default();
+    let group = read_to_string("/etc/group").unwrap_or_default();
+    parse_owner(value, &passwd, &group)
+}
+
+/// Parse an owner such as `1000:1000`, `media:media`, `media` or `:media`.
+///
+/// Names are resolved with the content of `passwd` and `group` files.
+#[must_use]
+pub fn parse_owner(value: &str, passwd: &str, group: &str) -> Option<Owner> {
+    let (user, group_name) = match value.split_once(':') {
+        Some((user, group_name)) => (user, group_name),
+        None => (value, ""),
+    };
+    let uid = match user {
+        "" => None,
+        user => Some(resolve_id(user, passwd)?),
+    };
+    let gid = match group_name {
+        "" => None,
+        group_name => Some(resolve_id(group_name, group)?),
+    };
+    if uid.is_none() && gid.is_none() {
+        return None;
+    }
+    Some(Owner { uid, gid })
+}
+
+/// Resolve a numeric id or a name in a `passwd` or `group` file.
+fn resolve_id(value: &str, database: &str) -> Option<u32> {
+    if let Ok(id) = value.parse() {
+        return Some(id);
+    }
+    database.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        if fields.next()? != value {
+            return None;
+        }
+        fields.nth(1)?.parse().ok()
+    })
+}
@@ -0,0 +1,47 @@
+use std::fs::{create_dir_all, read, write};
+use std::path::PathBuf;
+
+use rogue_logging::Error;
+
+use crate::fs::{create_archive, get_archive_path, ArchiveFormat};
+use crate::testing::TempDirectory;
+
+#[test]
+fn get_archive_path_appends_extension() {
+    // Arrange
+    let dir = PathBuf::from("/output/Artist - Album [2020] [WEB 320]");
+
+    // Act
+    let zip = get_archive_path(&dir, ArchiveFormat::Zip);
+    let tar_gz = get_archive_path(&dir, ArchiveFormat::TarGz);
+
+    // Assert
+    assert_eq!(
+        zip,
+        PathBuf::from("/output/Artist - Album [2020] [WEB 320].zip")
+    );
+    assert_eq!(
+        tar_gz,
+        PathBuf::from("/output/Artist - Album [2020] [WEB 320].tar.gz")
+    );
+}
+
+#[tokio::test]
+async fn create_archive_writes_zip_and_tar_gz() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("create_archive").join("Album [2020] [WEB 320]");
+    create_dir_all(dir.join("CD1")).expect("should create sub dir");
+    write(dir.join("CD1").join("01 Track.mp3"), "abc").expect("should write file");
+    write(get_archive_path(&dir, ArchiveFormat::Zip), "stale").expect("should write file");
+
+    // Act
+    let zip = create_archive(&dir, ArchiveFormat::Zip, 0).await?;
+    let tar_gz = create_archive(&dir, ArchiveFormat::TarGz, 9).await?;
+
+    // Assert
+    let zip = read(zip).expect("should read zip");
+    assert_eq!(zip.get(..4), Some(b"PK\x03\x04".as_slice()));
+    let tar_gz = read(tar_gz).expect("should read tar.gz");
+    assert_eq!(tar_gz.get(..2), Some([0x1f, 0x8b].as_slice()));
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use std::fs::{create_dir_all, read_to_string, write};
+
+use rogue_logging::Error;
+
+use crate::fs::{create_checksum_manifest, CHECKSUM_MANIFEST_NAME};
+use crate::testing::TempDirectory;
+
+#[test]
+fn create_checksum_manifest_lists_every_file() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("create_checksum_manifest");
+    create_dir_all(dir.join("CD1")).expect("should create sub dir");
+    write(dir.join("CD1").join("01 Track.flac"), "").expect("should write file");
+    write(dir.join("cover.jpg"), "abc").expect("should write file");
+    write(dir.join(CHECKSUM_MANIFEST_NAME), "stale").expect("should write file");
+
+    // Act
+    let path = create_checksum_manifest(&dir)?;
+
+    // Assert
+    let manifest = read_to_string(path).expect("should read manifest");
+    assert_eq!(
+        manifest,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  CD1/01 Track.flac\n\
+         ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  cover.jpg\n"
+    );
+    Ok(())
+}
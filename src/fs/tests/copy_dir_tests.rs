@@ -1,5 +1,5 @@
 use crate::errors::io_error;
-use crate::fs::{copy_dir, DirectoryReader};
+use crate::fs::{copy_dir, DirectoryReader, LinkStrategy};
 use crate::testing::TempDirectory;
 use rogue_logging::Error;
 use std::fs::read_dir;
@@ -17,7 +17,7 @@ async fn test_copy_dir() -> Result<(), Error> {
     let target_dir = TempDirectory::create("caesura").join("target");
 
     // Act
-    copy_dir(&source_dir, &target_dir, false).await?;
+    copy_dir(&source_dir, &target_dir, LinkStrategy::Copy).await?;
 
     // Assert
     let source_files: Vec<PathBuf> = DirectoryReader::new()
@@ -0,0 +1,22 @@
+use std::fs::{create_dir_all, write};
+
+use crate::fs::get_dir_size;
+use crate::testing::TempDirectory;
+
+#[test]
+fn get_dir_size_includes_nested_files() {
+    // Arrange
+    let dir = TempDirectory::create("get_dir_size_includes_nested_files");
+    let nested = dir.join("CD1");
+    create_dir_all(&nested).expect("should create nested dir");
+    write(dir.join("cover.jpg"), [0_u8; 100]).expect("should write file");
+    write(nested.join("01 Intro.flac"), [0_u8; 250]).expect("should write file");
+
+    // Act
+    let size = get_dir_size(&dir);
+    let missing = get_dir_size(&dir.join("missing"));
+
+    // Assert
+    assert_eq!(size, 350);
+    assert_eq!(missing, 0);
+}
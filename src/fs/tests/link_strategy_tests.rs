@@ -0,0 +1,85 @@
+use std::fs::{read_to_string, symlink_metadata, write};
+use std::os::unix::prelude::MetadataExt;
+
+use rogue_logging::Error;
+
+use crate::fs::LinkStrategy;
+use crate::options::{CopyOptions, Options};
+use crate::testing::TempDirectory;
+
+#[tokio::test]
+async fn link_strategy_execute() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("link_strategy");
+    let source = dir.join("cover.jpg");
+    write(&source, "image").expect("should write source");
+    let strategies = [
+        LinkStrategy::Copy,
+        LinkStrategy::Hardlink,
+        LinkStrategy::Symlink,
+        LinkStrategy::Reflink,
+    ];
+
+    // Act
+    let mut results = Vec::new();
+    for strategy in strategies {
+        let target = dir.join(format!("{strategy:?}.jpg"));
+        let used = strategy.execute(&source, &target).await?;
+        results.push((used, target));
+    }
+
+    // Assert
+    let inode = source.metadata().expect("should read metadata").ino();
+    for (used, target) in results {
+        assert_eq!(
+            read_to_string(&target).expect("should read target"),
+            "image"
+        );
+        let metadata = symlink_metadata(&target).expect("should read metadata");
+        match used {
+            LinkStrategy::Copy | LinkStrategy::Reflink => assert!(metadata.is_file()),
+            LinkStrategy::Hardlink => assert_eq!(metadata.ino(), inode),
+            LinkStrategy::Symlink => assert!(metadata.is_symlink()),
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn link_strategy_falls_back_to_copy() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("link_strategy");
+    let source = dir.join("cover.jpg");
+    write(&source, "image").expect("should write source");
+    let target = dir.join("cover-link.jpg");
+    write(&target, "existing").expect("should write target");
+
+    // Act
+    let used = LinkStrategy::Hardlink.execute(&source, &target).await?;
+
+    // Assert
+    assert_eq!(used, LinkStrategy::Copy);
+    assert_eq!(
+        read_to_string(&target).expect("should read target"),
+        "image"
+    );
+    Ok(())
+}
+
+#[test]
+fn copy_options_defaults_follow_hard_link() {
+    // Arrange
+    let mut options = CopyOptions {
+        hard_link: Some(true),
+        link_torrent: Some(LinkStrategy::Symlink),
+        ..CopyOptions::default()
+    };
+
+    // Act
+    options.apply_defaults();
+
+    // Assert
+    assert_eq!(options.link_additional, Some(LinkStrategy::Hardlink));
+    assert_eq!(options.link_content, Some(LinkStrategy::Hardlink));
+    assert_eq!(options.link_torrent, Some(LinkStrategy::Symlink));
+}
@@ -1,2 +1,10 @@
+mod archive_tests;
+mod checksum_manifest_tests;
 mod copy_dir_tests;
+mod disk_usage_tests;
+mod link_strategy_tests;
+mod move_staged_tests;
+mod nested_dirs_tests;
+mod path_manager_tests;
+mod permissions_tests;
 mod tags_tests;
@@ -0,0 +1,50 @@
+use std::fs::{create_dir_all, read_to_string, write};
+
+use rogue_logging::Error;
+
+use crate::fs::move_staged;
+use crate::testing::TempDirectory;
+
+#[tokio::test]
+async fn move_staged_replaces_partial_output() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("move_staged_dir");
+    let staged = dir.join(".staging").join("123").join("Album [FLAC]");
+    create_dir_all(&staged).expect("should create staged dir");
+    write(staged.join("01 Track.flac"), "complete").expect("should write staged file");
+    let target = dir.join("Album [FLAC]");
+    create_dir_all(&target).expect("should create target dir");
+    write(target.join("02 Track.flac"), "partial").expect("should write partial file");
+
+    // Act
+    move_staged(&staged, &target).await?;
+
+    // Assert
+    assert!(!staged.exists());
+    assert_eq!(
+        read_to_string(target.join("01 Track.flac")).expect("should read moved file"),
+        "complete"
+    );
+    assert!(!target.join("02 Track.flac").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn move_staged_file() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("move_staged_file");
+    let staged = dir.join("staged.torrent");
+    write(&staged, "torrent").expect("should write staged file");
+    let target = dir.join("output").join("Album [FLAC].torrent");
+
+    // Act
+    move_staged(&staged, &target).await?;
+
+    // Assert
+    assert!(!staged.exists());
+    assert_eq!(
+        read_to_string(&target).expect("should read moved file"),
+        "torrent"
+    );
+    Ok(())
+}
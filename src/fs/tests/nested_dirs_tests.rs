@@ -0,0 +1,51 @@
+use std::fs::{create_dir_all, write};
+use std::path::PathBuf;
+
+use crate::fs::{collapse_nested_dirs, get_content_root, has_nested_dirs};
+use crate::testing::TempDirectory;
+
+#[test]
+fn get_content_root_skips_wrappers() {
+    // Arrange
+    let dir = TempDirectory::create("nested_dirs_wrappers");
+    let content = dir.join("Album").join("FLAC");
+    create_dir_all(&content).expect("should create dirs");
+    write(dir.join("Album").join("info.txt"), "").expect("should write file");
+    write(content.join("01 Track.flac"), "").expect("should write file");
+
+    // Act
+    let root = get_content_root(&dir);
+
+    // Assert
+    assert_eq!(root, content);
+}
+
+#[test]
+fn get_content_root_keeps_discs() {
+    // Arrange
+    let dir = TempDirectory::create("nested_dirs_discs");
+    for disc in ["CD1", "CD2"] {
+        let disc = dir.join(disc);
+        create_dir_all(&disc).expect("should create dirs");
+        write(disc.join("01 Track.flac"), "").expect("should write file");
+    }
+
+    // Act
+    let root = get_content_root(&dir);
+
+    // Assert
+    assert_eq!(root, dir);
+}
+
+#[test]
+fn collapse_nested_dirs_removes_duplicates() {
+    // Act
+    let collapsed = collapse_nested_dirs(&PathBuf::from("Disc 1/DISC 1/Bonus"));
+    let unchanged = collapse_nested_dirs(&PathBuf::from("Disc 1/Bonus"));
+
+    // Assert
+    assert_eq!(collapsed, PathBuf::from("Disc 1/Bonus"));
+    assert_eq!(unchanged, PathBuf::from("Disc 1/Bonus"));
+    assert!(has_nested_dirs(&PathBuf::from("Disc 1/Disc 1")));
+    assert!(!has_nested_dirs(&PathBuf::from("Disc 1")));
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeSet;
+use std::fs::{create_dir_all, write};
+use std::path::PathBuf;
+
+use gazelle_api::{Group, Torrent};
+
+use crate::formats::{SourceFormat, TargetFormat};
+use crate::fs::{to_extended_length, PathManager};
+use crate::hosting::HostBuilder;
+use crate::options::SharedOptions;
+use crate::source::{Metadata, Source};
+use crate::testing::options::TestOptionsFactory;
+use crate::testing::TempDirectory;
+
+#[test]
+fn to_extended_length_prefixes_path() {
+    // Act
+    let local = to_extended_length(r"C:\output/Artist - Album [2012] [WEB FLAC]");
+    let share = to_extended_length(r"\\server\share\output");
+    let existing = to_extended_length(r"\\?\C:\output");
+
+    // Assert
+    assert_eq!(local, r"\\?\C:\output\Artist - Album [2012] [WEB FLAC]");
+    assert_eq!(share, r"\\?\UNC\server\share\output");
+    assert_eq!(existing, r"\\?\C:\output");
+}
+
+#[test]
+fn get_transcode_layout_auto_shorten() {
+    // Arrange
+    let output = TempDirectory::create("path_manager_auto_shorten");
+    let directory = output.join("source");
+    create_dir_all(&directory).expect("should create source dir");
+    let file_name =
+        "01 A Track With a Title That Is Long Enough to Exceed the Limit (Extended Mix)";
+    write(directory.join(format!("{file_name}.flac")), "").expect("should write flac");
+    let shared_options = TestOptionsFactory::from(SharedOptions {
+        output: Some(output),
+        auto_shorten: Some(true),
+        ..SharedOptions::default()
+    });
+    let host = HostBuilder::new().with_options(shared_options).build();
+    let paths = host.services.get_required::<PathManager>();
+    let source = Source {
+        torrent: Torrent {
+            id: 123,
+            ..Torrent::default()
+        },
+        group: Group::default(),
+        existing: BTreeSet::new(),
+        format: SourceFormat::Flac,
+        directory,
+        metadata: Metadata {
+            artist: "Artist".to_owned(),
+            album: "Album (A Parenthetical Statement That Makes the Directory Name Very Long)"
+                .to_owned(),
+            remaster_title: String::new(),
+            year: 2012,
+            media: "WEB".to_owned(),
+        },
+    };
+
+    // Act
+    let layout = paths.get_transcode_layout(&source, TargetFormat::Flac);
+    let shortened = paths.get_shortened_names(&source, TargetFormat::Flac);
+
+    // Assert
+    assert_eq!(layout, PathBuf::from("Artist - Album [2012] [WEB FLAC]"));
+    assert_eq!(shortened.len(), 1);
+}
@@ -0,0 +1,122 @@
+use std::fs::{create_dir_all, hard_link, metadata, set_permissions, write, Permissions};
+use std::os::unix::fs::PermissionsExt;
+
+use rogue_logging::Error;
+
+use crate::fs::{parse_mode, parse_owner, OutputPermissions, Owner};
+use crate::testing::TempDirectory;
+
+const PASSWD: &str = "root:x:0:0:root:/root:/bin/bash\nmedia:x:1001:1002::/home/media:/bin/sh\n";
+const GROUP: &str = "root:x:0:\nmedia:x:1002:\nqbittorrent:x:1003:media\n";
+
+#[test]
+fn parse_mode_octal() {
+    assert_eq!(parse_mode("664"), Some(0o664));
+    assert_eq!(parse_mode("0775"), Some(0o775));
+    assert_eq!(parse_mode("0o2775"), Some(0o2775));
+    assert_eq!(parse_mode("789"), None);
+    assert_eq!(parse_mode("17777"), None);
+    assert_eq!(parse_mode(""), None);
+}
+
+#[test]
+fn parse_owner_ids_and_names() {
+    assert_eq!(
+        parse_owner("1000:1000", PASSWD, GROUP),
+        Some(Owner {
+            uid: Some(1000),
+            gid: Some(1000)
+        })
+    );
+    assert_eq!(
+        parse_owner("media:qbittorrent", PASSWD, GROUP),
+        Some(Owner {
+            uid: Some(1001),
+            gid: Some(1003)
+        })
+    );
+    assert_eq!(
+        parse_owner("media", PASSWD, GROUP),
+        Some(Owner {
+            uid: Some(1001),
+            gid: None
+        })
+    );
+    assert_eq!(
+        parse_owner(":media", PASSWD, GROUP),
+        Some(Owner {
+            uid: None,
+            gid: Some(1002)
+        })
+    );
+    assert_eq!(parse_owner("unknown", PASSWD, GROUP), None);
+    assert_eq!(parse_owner(":", PASSWD, GROUP), None);
+}
+
+#[test]
+fn output_permissions_apply_recursively() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("output_permissions_apply");
+    let output = dir.join("Album [V0]");
+    let disc = output.join("CD1");
+    create_dir_all(&disc).expect("should create dir");
+    let file = disc.join("01 Track.mp3");
+    write(&file, "audio").expect("should write file");
+    let permissions = OutputPermissions {
+        file_mode: Some(0o640),
+        dir_mode: Some(0o750),
+        owner: None,
+    };
+
+    // Act
+    permissions.apply(&output)?;
+
+    // Assert
+    let get_mode = |path| {
+        metadata(path)
+            .expect("should read metadata")
+            .permissions()
+            .mode()
+            & 0o7777
+    };
+    assert_eq!(get_mode(&output), 0o750);
+    assert_eq!(get_mode(&disc), 0o750);
+    assert_eq!(get_mode(&file), 0o640);
+    Ok(())
+}
+
+#[test]
+fn output_permissions_skip_hard_links() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("output_permissions_skip_hard_links");
+    let source = dir.join("cover.jpg");
+    write(&source, "image").expect("should write file");
+    set_permissions(&source, Permissions::from_mode(0o600)).expect("should set mode");
+    let output = dir.join("Album [V0]");
+    create_dir_all(&output).expect("should create dir");
+    let linked = output.join("cover.jpg");
+    hard_link(&source, &linked).expect("should hard link");
+    let file = output.join("01 Track.mp3");
+    write(&file, "audio").expect("should write file");
+    let permissions = OutputPermissions {
+        file_mode: Some(0o644),
+        dir_mode: None,
+        owner: None,
+    };
+
+    // Act
+    permissions.apply(&output)?;
+
+    // Assert
+    let get_mode = |path| {
+        metadata(path)
+            .expect("should read metadata")
+            .permissions()
+            .mode()
+            & 0o7777
+    };
+    assert_eq!(get_mode(&source), 0o600);
+    assert_eq!(get_mode(&linked), 0o600);
+    assert_eq!(get_mode(&file), 0o644);
+    Ok(())
+}
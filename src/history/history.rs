@@ -0,0 +1,364 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{inject, injectable, Ref};
+use log::{info, trace, warn};
+use rogue_logging::Error;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::db::{Hash, Table};
+use crate::errors::{error, json_error, sqlite_error};
+use crate::formats::TargetFormat;
+use crate::fs::PathManager;
+use crate::history::{migrate, HistoryItem};
+use crate::naming::SourceName;
+use crate::options::{CacheOptions, SharedOptions};
+use crate::queue::TimeStamp;
+use crate::source::Source;
+use crate::torrent::TorrentFile;
+use crate::transcode::TranscodeStatus;
+use crate::upload::UploadStatus;
+use crate::verify::VerifyStatus;
+
+/// Name of the history database in the cache directory.
+const HISTORY_FILE_NAME: &str = "history.db";
+
+/// Name of the directory in the cache directory that earlier versions stored the history in.
+const LEGACY_DIR_NAME: &str = "history";
+
+/// Time to wait for another process to finish writing to the database.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Columns of the `sources` table in the order they are read by [`read_row`].
+const COLUMNS: &str =
+    "id, group_id, indexer, name, hash, verify, transcode, upload, created, updated";
+
+/// History of every processed source.
+///
+/// Each source is represented by a [`HistoryItem`] stored in a `SQLite` database by its
+/// indexer and id so lookups don't require the source torrent file. The schema is
+/// created and migrated by [`migrate`] when the database is opened.
+///
+/// The verify, transcode and upload statuses are stored as JSON so they can be queried
+/// with the JSON functions of `SQLite`.
+///
+/// If the cache directory does not exist then nothing is recorded.
+pub struct History {
+    connection: Option<Mutex<Connection>>,
+    indexer: String,
+    paths: Option<Ref<PathManager>>,
+}
+
+#[injectable]
+impl History {
+    /// Create a new [`History`] stored in the database at `path`.
+    #[allow(dead_code)]
+    pub fn from_path(path: &Path, indexer: String) -> Result<Self, Error> {
+        Ok(Self {
+            connection: Some(Mutex::new(open(path, None)?)),
+            indexer,
+            paths: None,
+        })
+    }
+
+    /// DI constructor for [`History`]
+    ///
+    /// The history of earlier versions is imported when the database is created.
+    #[inject]
+    pub fn from_options(
+        cache_options: Ref<CacheOptions>,
+        shared_options: Ref<SharedOptions>,
+        paths: Ref<PathManager>,
+    ) -> Self {
+        let indexer = shared_options.indexer.clone().unwrap_or_default();
+        let cache = cache_options.cache.as_ref().filter(|cache| cache.is_dir());
+        let connection = if let Some(cache) = cache {
+            let path = cache.join(HISTORY_FILE_NAME);
+            match open(&path, Some(&cache.join(LEGACY_DIR_NAME))) {
+                Ok(connection) => Some(Mutex::new(connection)),
+                Err(error) => {
+                    warn!(
+                        "{} to open history database {}: {error}",
+                        "Failed".bold(),
+                        path.display()
+                    );
+                    None
+                }
+            }
+        } else {
+            trace!(
+                "{} history as the cache directory does not exist",
+                "Disabled".bold()
+            );
+            None
+        };
+        Self {
+            connection,
+            indexer,
+            paths: Some(paths),
+        }
+    }
+
+    /// Get an item by source id.
+    pub fn get(&self, id: u32) -> Result<Option<HistoryItem>, Error> {
+        let Some(connection) = self.lock() else {
+            return Ok(None);
+        };
+        let action = "get history item";
+        let sql = format!("SELECT {COLUMNS} FROM sources WHERE indexer = ?1 AND id = ?2");
+        connection
+            .query_row(&sql, params![self.indexer, id], read_row)
+            .optional()
+            .map_err(|e| sqlite_error(e, action))?
+            .map(to_item)
+            .transpose()
+    }
+
+    /// Get every item of the indexer.
+    ///
+    /// Items are sorted by the time they were last processed, most recent first.
+    pub fn get_all(&self) -> Result<Vec<HistoryItem>, Error> {
+        let Some(connection) = self.lock() else {
+            return Ok(Vec::new());
+        };
+        let action = "get history items";
+        let sql = format!("SELECT {COLUMNS} FROM sources WHERE indexer = ?1 ORDER BY updated DESC");
+        let mut statement = connection
+            .prepare(&sql)
+            .map_err(|e| sqlite_error(e, action))?;
+        let rows = statement
+            .query_map(params![self.indexer], read_row)
+            .map_err(|e| sqlite_error(e, action))?;
+        rows.map(|row| row.map_err(|e| sqlite_error(e, action)).and_then(to_item))
+            .collect()
+    }
+
+    /// Add or replace an item.
+    pub fn set(&self, item: &HistoryItem) -> Result<(), Error> {
+        let Some(connection) = self.lock() else {
+            return Ok(());
+        };
+        insert(&connection, item)
+    }
+
+    /// Is the source recorded as verified?
+    #[must_use]
+    pub fn is_verified(&self, source: &Source) -> bool {
+        matches!(
+            self.get(source.torrent.id),
+            Ok(Some(HistoryItem {
+                verify: Some(VerifyStatus { verified: true, .. }),
+                ..
+            }))
+        )
+    }
+
+    /// Is the source recorded as transcoded to every target with the transcodes still present?
+    #[must_use]
+    pub fn is_transcoded(&self, source: &Source, targets: &BTreeSet<TargetFormat>) -> bool {
+        let Ok(Some(HistoryItem {
+            transcode:
+                Some(TranscodeStatus {
+                    success: true,
+                    formats: Some(formats),
+                    ..
+                }),
+            ..
+        })) = self.get(source.torrent.id)
+        else {
+            return false;
+        };
+        targets.iter().all(|target| {
+            formats
+                .iter()
+                .any(|x| x.format == *target && x.path.is_dir())
+        })
+    }
+
+    /// Record the verification of a source.
+    pub async fn record_verify(&self, source: &Source, status: &VerifyStatus) {
+        self.record(source, |item| item.verify = Some(status.clone()))
+            .await;
+    }
+
+    /// Record the transcode of a source.
+    pub async fn record_transcode(&self, source: &Source, status: &TranscodeStatus) {
+        self.record(source, |item| item.transcode = Some(status.clone()))
+            .await;
+    }
+
+    /// Record the upload of a source.
+    pub async fn record_upload(&self, source: &Source, status: &UploadStatus) {
+        self.record(source, |item| item.upload = Some(status.clone()))
+            .await;
+    }
+
+    /// Update the item of a source, creating it if necessary.
+    ///
+    /// Errors are logged as warnings as the history must not prevent processing.
+    async fn record(&self, source: &Source, update: impl FnOnce(&mut HistoryItem)) {
+        if self.connection.is_none() {
+            return;
+        }
+        let mut item = match self.get(source.torrent.id) {
+            Ok(Some(item)) => item,
+            Ok(None) => HistoryItem::new(
+                self.indexer.clone(),
+                source.torrent.id,
+                source.group.id,
+                SourceName::get(&source.metadata),
+            ),
+            Err(error) => {
+                warn!("{} to read history of {source}: {error}", "Failed".bold());
+                return;
+            }
+        };
+        if item.hash.is_none() {
+            item.hash = self.get_info_hash(source).await;
+        }
+        update(&mut item);
+        item.updated = TimeStamp::now();
+        if let Err(error) = self.set(&item) {
+            warn!("{} to record history of {source}: {error}", "Failed".bold());
+        }
+    }
+
+    /// Get the info hash of the source torrent file if it's cached.
+    async fn get_info_hash(&self, source: &Source) -> Option<Hash<20>> {
+        let path = self.paths.as_ref()?.get_source_torrent_path(source);
+        if !path.is_file() {
+            return None;
+        }
+        let torrent = TorrentFile::read(&path).await.ok()?;
+        Hash::from_string(&torrent.get_info_hash()).ok()
+    }
+
+    /// Lock the connection to the database.
+    ///
+    /// Returns `None` if nothing is recorded.
+    fn lock(&self) -> Option<MutexGuard<'_, Connection>> {
+        let connection = self.connection.as_ref()?;
+        Some(
+            connection
+                .lock()
+                .expect("history connection should not be poisoned"),
+        )
+    }
+}
+
+/// Open the database at `path`, creating and migrating it if necessary.
+///
+/// If the database is created then the items in `legacy_dir` are imported.
+fn open(path: &Path, legacy_dir: Option<&Path>) -> Result<Connection, Error> {
+    let action = "open history database";
+    let mut connection = Connection::open(path).map_err(|e| sqlite_error(e, action))?;
+    connection
+        .busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| sqlite_error(e, action))?;
+    migrate(&mut connection, |transaction| {
+        let Some(dir) = legacy_dir.filter(|dir| dir.is_dir()) else {
+            return Ok(());
+        };
+        let items = Table::<20, 1, HistoryItem>::new(dir.to_path_buf()).read_all()?;
+        for item in items.values() {
+            insert(transaction, item)?;
+        }
+        info!(
+            "{} {} sources from the history in {}",
+            "Imported".bold(),
+            items.len(),
+            dir.display()
+        );
+        Ok(())
+    })?;
+    Ok(connection)
+}
+
+/// Add or replace an item.
+fn insert(connection: &Connection, item: &HistoryItem) -> Result<(), Error> {
+    let sql = format!(
+        "INSERT OR REPLACE INTO sources ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+    );
+    connection
+        .execute(
+            &sql,
+            params![
+                item.id,
+                item.group_id,
+                item.indexer,
+                item.name,
+                item.hash.map(|hash| hash.to_hex()),
+                to_json(item.verify.as_ref())?,
+                to_json(item.transcode.as_ref())?,
+                to_json(item.upload.as_ref())?,
+                item.created.to_string(),
+                item.updated.to_string(),
+            ],
+        )
+        .map_err(|e| sqlite_error(e, "set history item"))?;
+    Ok(())
+}
+
+/// Columns of a row of the `sources` table before they are parsed.
+struct HistoryRow {
+    id: u32,
+    group_id: u32,
+    indexer: String,
+    name: String,
+    hash: Option<String>,
+    verify: Option<String>,
+    transcode: Option<String>,
+    upload: Option<String>,
+    created: String,
+    updated: String,
+}
+
+fn read_row(row: &Row<'_>) -> Result<HistoryRow, rusqlite::Error> {
+    Ok(HistoryRow {
+        id: row.get(0)?,
+        group_id: row.get(1)?,
+        indexer: row.get(2)?,
+        name: row.get(3)?,
+        hash: row.get(4)?,
+        verify: row.get(5)?,
+        transcode: row.get(6)?,
+        upload: row.get(7)?,
+        created: row.get(8)?,
+        updated: row.get(9)?,
+    })
+}
+
+fn to_item(row: HistoryRow) -> Result<HistoryItem, Error> {
+    Ok(HistoryItem {
+        id: row.id,
+        group_id: row.group_id,
+        indexer: row.indexer,
+        name: row.name,
+        hash: row.hash.as_deref().map(Hash::from_string).transpose()?,
+        verify: from_json(row.verify.as_deref())?,
+        transcode: from_json(row.transcode.as_deref())?,
+        upload: from_json(row.upload.as_deref())?,
+        created: to_timestamp(&row.created)?,
+        updated: to_timestamp(&row.updated)?,
+    })
+}
+
+fn to_json<T: Serialize>(value: Option<&T>) -> Result<Option<String>, Error> {
+    value
+        .map(|value| serde_json::to_string(value).map_err(|e| json_error(e, "serialize history")))
+        .transpose()
+}
+
+fn from_json<T: DeserializeOwned>(json: Option<&str>) -> Result<Option<T>, Error> {
+    json.map(|json| serde_json::from_str(json).map_err(|e| json_error(e, "deserialize history")))
+        .transpose()
+}
+
+fn to_timestamp(value: &str) -> Result<TimeStamp, Error> {
+    TimeStamp::from_rfc3339(value).map_err(|e| error("deserialize history", e.to_string()))
+}
@@ -0,0 +1,79 @@
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info};
+use rogue_logging::{Colors, Error};
+
+use crate::errors::{error, yaml_error};
+use crate::history::History;
+use crate::options::{HistoryArgs, OptionRule, Options, SharedOptions};
+use crate::summary::RunSummary;
+
+/// Show the history of processed sources.
+#[injectable]
+pub struct HistoryCommand {
+    shared_options: Ref<SharedOptions>,
+    args: Ref<HistoryArgs>,
+    history: Ref<History>,
+    summary: Ref<RunSummary>,
+}
+
+impl HistoryCommand {
+    /// Execute [`HistoryCommand`] from the CLI.
+    ///
+    /// If an id is given then the full history of that source is printed as YAML,
+    /// otherwise a summary of every source is logged.
+    ///
+    /// With `result_format: json` the history is included in the result instead.
+    pub fn execute_cli(&self) -> Result<bool, Error> {
+        OptionRule::check(&self.shared_options.get_errors())?;
+        let indexer = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set")
+            .to_uppercase();
+        if let Some(id) = self.args.history_id {
+            let item = self.history.get(id)?.ok_or_else(|| {
+                error(
+                    "get history",
+                    format!("Source {id} is not in the history for {indexer}"),
+                )
+            })?;
+            if self.summary.is_json() {
+                self.summary.set_data(&item);
+                return Ok(true);
+            }
+            let yaml =
+                serde_yaml::to_string(&item).map_err(|e| yaml_error(e, "serialize history"))?;
+            println!("{yaml}");
+            return Ok(true);
+        }
+        let items = self.history.get_all()?;
+        if self.summary.is_json() {
+            self.summary.set_data(&items);
+        }
+        if items.is_empty() {
+            info!("{} sources in the history for {indexer}", "No".bold());
+            return Ok(true);
+        }
+        info!(
+            "{} {} processed sources in the history for {indexer}",
+            "Found".bold(),
+            items.len()
+        );
+        let pad = items
+            .iter()
+            .map(|x| x.id.to_string().len())
+            .max()
+            .unwrap_or_default();
+        for item in items {
+            info!(
+                "{}: {item} {}",
+                format!("{:pad$}", item.id).bold(),
+                item.get_summary().gray()
+            );
+            debug!("{}", item.updated);
+        }
+        Ok(true)
+    }
+}
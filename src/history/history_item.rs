@@ -0,0 +1,111 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Hash;
+use crate::queue::TimeStamp;
+use crate::transcode::TranscodeStatus;
+use crate::upload::UploadStatus;
+use crate::verify::VerifyStatus;
+
+/// A processed source recorded in the [`History`].
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HistoryItem {
+    /// Source torrent id
+    pub id: u32,
+    /// Source group id
+    pub group_id: u32,
+    /// Source indexer
+    pub indexer: String,
+    /// Source name
+    pub name: String,
+    /// Source info hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<Hash<20>>,
+    /// Most recent verification status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify: Option<VerifyStatus>,
+    /// Most recent transcode status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcode: Option<TranscodeStatus>,
+    /// Most recent upload status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload: Option<UploadStatus>,
+    /// Time the source was first processed
+    pub created: TimeStamp,
+    /// Time the source was last processed
+    pub updated: TimeStamp,
+}
+
+impl HistoryItem {
+    /// Create a new [`HistoryItem`] that has not been processed.
+    #[must_use]
+    pub fn new(indexer: String, id: u32, group_id: u32, name: String) -> Self {
+        Self {
+            id,
+            group_id,
+            indexer,
+            name,
+            hash: None,
+            verify: None,
+            transcode: None,
+            upload: None,
+            created: TimeStamp::now(),
+            updated: TimeStamp::now(),
+        }
+    }
+
+    /// Get a one line summary of what has been done to the source.
+    #[must_use]
+    pub fn get_summary(&self) -> String {
+        let mut parts = Vec::new();
+        match &self.verify {
+            Some(status) if status.verified => parts.push("verified".to_owned()),
+            Some(_) => parts.push("unsuitable".to_owned()),
+            None => {}
+        }
+        if let Some(status) = &self.transcode {
+            let formats = status
+                .formats
+                .iter()
+                .flatten()
+                .map(|x| x.format.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            if status.success {
+                parts.push(format!("transcoded {formats}"));
+            } else {
+                parts.push("transcode failed".to_owned());
+            }
+        }
+        if let Some(status) = &self.upload {
+            let uploads = status
+                .formats
+                .iter()
+                .flatten()
+                .map(|x| match &x.indexer {
+                    Some(indexer) => format!("{} to {indexer} as {}", x.format, x.id),
+                    None => format!("{} as {}", x.format, x.id),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            if uploads.is_empty() {
+                parts.push("upload failed".to_owned());
+            } else {
+                parts.push(format!("uploaded {uploads}"));
+            }
+        }
+        if parts.is_empty() {
+            "not processed".to_owned()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+impl Display for HistoryItem {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.name)
+    }
+}
@@ -0,0 +1,75 @@
+use colored::Colorize;
+use log::{debug, info};
+use rogue_logging::Error;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+
+use crate::errors::sqlite_error;
+
+/// Statements to migrate the history database from each version to the next.
+///
+/// The version of a database is the number of migrations applied, stored as the
+/// `user_version` of the database, so each migration is applied once and in order.
+///
+/// Never change a migration that has been released. Append a new one instead.
+const MIGRATIONS: [&str; 1] = ["
+    CREATE TABLE sources (
+        indexer TEXT NOT NULL,
+        id INTEGER NOT NULL,
+        group_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        hash TEXT,
+        verify TEXT,
+        transcode TEXT,
+        upload TEXT,
+        created TEXT NOT NULL,
+        updated TEXT NOT NULL,
+        PRIMARY KEY (indexer, id)
+    );
+    CREATE INDEX sources_by_updated ON sources (indexer, updated);
+"];
+
+/// Migrate the history database to the latest version.
+///
+/// The migrations are applied in a single transaction together with `import` so a
+/// database is never left part migrated, and a process that opens the database at the
+/// same time waits rather than applying them again.
+///
+/// `import` is called with the transaction if the database was created, so existing
+/// history can be copied into it.
+pub fn migrate(
+    connection: &mut Connection,
+    import: impl FnOnce(&Transaction) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let action = "migrate history database";
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| sqlite_error(e, action))?;
+    let version: usize = transaction
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| sqlite_error(e, action))?;
+    if version >= MIGRATIONS.len() {
+        return Ok(());
+    }
+    for migration in MIGRATIONS.iter().skip(version) {
+        transaction
+            .execute_batch(migration)
+            .map_err(|e| sqlite_error(e, action))?;
+    }
+    if version == 0 {
+        import(&transaction)?;
+    }
+    transaction
+        .pragma_update(None, "user_version", MIGRATIONS.len())
+        .map_err(|e| sqlite_error(e, action))?;
+    transaction.commit().map_err(|e| sqlite_error(e, action))?;
+    if version == 0 {
+        debug!("{} history database", "Created".bold());
+    } else {
+        info!(
+            "{} history database from version {version} to {}",
+            "Migrated".bold(),
+            MIGRATIONS.len()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,12 @@
+pub use history::*;
+pub use history_command::*;
+pub use history_item::*;
+pub use history_schema::*;
+
+pub(crate) mod history;
+pub(crate) mod history_command;
+pub(crate) mod history_item;
+pub(crate) mod history_schema;
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests;
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use crate::formats::TargetFormat::{Flac, _320, V0};
+use crate::history::HistoryItem;
+use crate::queue::TimeStamp;
+use crate::transcode::{TranscodeFormatStatus, TranscodeStatus};
+use crate::upload::{UploadFormatStatus, UploadStatus};
+use crate::verify::VerifyStatus;
+
+#[test]
+fn history_item_get_summary_not_processed() {
+    // Arrange
+    let item = HistoryItem::new("red".to_owned(), 1, 2, "Name".to_owned());
+
+    // Act
+    let summary = item.get_summary();
+
+    // Assert
+    assert_eq!(summary, "not processed");
+}
+
+#[test]
+fn history_item_get_summary_uploaded() {
+    // Arrange
+    let mut item = HistoryItem::new("red".to_owned(), 1, 2, "Name".to_owned());
+    item.verify = Some(VerifyStatus::verified());
+    item.transcode = Some(TranscodeStatus {
+        success: true,
+        formats: Some(
+            [Flac, _320, V0]
+                .into_iter()
+                .map(|format| TranscodeFormatStatus {
+                    format,
+                    path: PathBuf::new(),
+                })
+                .collect(),
+        ),
+        completed: TimeStamp::now(),
+        error: None,
+    });
+    item.upload = Some(UploadStatus {
+        success: true,
+        formats: Some(vec![UploadFormatStatus {
+            format: _320,
+            id: 789,
+            indexer: None,
+        }]),
+        completed: TimeStamp::now(),
+        errors: None,
+    });
+
+    // Act
+    let summary = item.get_summary();
+
+    // Assert
+    assert_eq!(
+        summary,
+        "verified, transcoded FLAC, 320, V0, uploaded 320 as 789"
+    );
+}
+
+#[test]
+fn history_item_get_summary_cross_posted() {
+    // Arrange
+    let mut item = HistoryItem::new("red".to_owned(), 1, 2, "Name".to_owned());
+    item.upload = Some(UploadStatus {
+        success: true,
+        formats: Some(vec![
+            UploadFormatStatus {
+                format: _320,
+                id: 789,
+                indexer: None,
+            },
+            UploadFormatStatus {
+                format: _320,
+                id: 456,
+                indexer: Some("ops".to_owned()),
+            },
+        ]),
+        completed: TimeStamp::now(),
+        errors: None,
+    });
+
+    // Act
+    let summary = item.get_summary();
+
+    // Assert
+    assert_eq!(summary, "uploaded 320 as 789, 320 to ops as 456");
+}
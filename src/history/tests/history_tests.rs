@@ -0,0 +1,143 @@
+use crate::db::{Hash, Table};
+use crate::history::{History, HistoryItem};
+use crate::hosting::HostBuilder;
+use crate::options::{CacheOptions, SharedOptions};
+use crate::queue::TimeStamp;
+use crate::testing::options::TestOptionsFactory;
+use crate::testing::TempDirectory;
+use crate::transcode::TranscodeStatus;
+use crate::verify::VerifyStatus;
+use rogue_logging::Error;
+use rusqlite::Connection;
+use std::fs::create_dir_all;
+
+#[test]
+fn history_set_and_get() -> Result<(), Error> {
+    // Arrange
+    let path = TempDirectory::create("history_set_and_get").join("history.db");
+    let history = History::from_path(&path, "red".to_owned())?;
+    let mut item = HistoryItem::new("red".to_owned(), 123, 456, "Artist - Album".to_owned());
+    item.verify = Some(VerifyStatus::verified());
+
+    // Act
+    history.set(&item)?;
+    let found = history.get(123)?;
+    let missing = history.get(124)?;
+
+    // Assert
+    let found = found.expect("item should be found");
+    assert_eq!(found.group_id, 456);
+    assert_eq!(found.name, "Artist - Album");
+    assert!(found.verify.is_some_and(|x| x.verified));
+    assert!(found.transcode.is_none());
+    assert_eq!(found.created.to_string(), item.created.to_string());
+    assert!(missing.is_none());
+    Ok(())
+}
+
+#[test]
+fn history_set_replaces_item() -> Result<(), Error> {
+    // Arrange
+    let path = TempDirectory::create("history_set_replaces_item").join("history.db");
+    let history = History::from_path(&path, "red".to_owned())?;
+    let mut item = HistoryItem::new("red".to_owned(), 123, 456, "Artist - Album".to_owned());
+    history.set(&item)?;
+    item.transcode = Some(TranscodeStatus {
+        success: true,
+        formats: None,
+        completed: TimeStamp::now(),
+        error: None,
+    });
+
+    // Act
+    history.set(&item)?;
+
+    // Assert
+    let items = history.get_all()?;
+    assert_eq!(items.len(), 1);
+    let found = items.first().expect("item should be found");
+    assert!(found.transcode.as_ref().is_some_and(|x| x.success));
+    Ok(())
+}
+
+#[test]
+fn history_get_all_filters_by_indexer() -> Result<(), Error> {
+    // Arrange
+    let path = TempDirectory::create("history_get_all_filters_by_indexer").join("history.db");
+    let red = History::from_path(&path, "red".to_owned())?;
+    let ops = History::from_path(&path, "ops".to_owned())?;
+    let mut older = HistoryItem::new("red".to_owned(), 1, 1, "Older".to_owned());
+    older.updated = TimeStamp::from_rfc3339("2024-01-01T00:00:00Z").unwrap();
+    let mut newer = HistoryItem::new("red".to_owned(), 2, 2, "Newer".to_owned());
+    newer.updated = TimeStamp::from_rfc3339("2024-06-01T00:00:00Z").unwrap();
+    red.set(&older)?;
+    red.set(&newer)?;
+    ops.set(&HistoryItem::new(
+        "ops".to_owned(),
+        1,
+        1,
+        "Other".to_owned(),
+    ))?;
+
+    // Act
+    let items = red.get_all()?;
+
+    // Assert
+    let names: Vec<&str> = items.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["Newer", "Older"]);
+    assert_eq!(ops.get(1)?.map(|x| x.name), Some("Other".to_owned()));
+    assert_eq!(red.get(1)?.map(|x| x.name), Some("Older".to_owned()));
+    Ok(())
+}
+
+#[test]
+fn history_migrates_once() -> Result<(), Error> {
+    // Arrange
+    let path = TempDirectory::create("history_migrates_once").join("history.db");
+    let history = History::from_path(&path, "red".to_owned())?;
+    history.set(&HistoryItem::new("red".to_owned(), 1, 1, "Kept".to_owned()))?;
+    drop(history);
+
+    // Act
+    let history = History::from_path(&path, "red".to_owned())?;
+
+    // Assert
+    assert_eq!(history.get(1)?.map(|x| x.name), Some("Kept".to_owned()));
+    let connection = Connection::open(&path).unwrap();
+    let version: usize = connection
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .unwrap();
+    assert_eq!(version, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn history_imports_legacy_items() -> Result<(), Error> {
+    // Arrange
+    let cache = TempDirectory::create("history_imports_legacy_items");
+    let legacy_dir = cache.join("history");
+    create_dir_all(&legacy_dir).expect("should create legacy history dir");
+    let table: Table<20, 1, HistoryItem> = Table::new(legacy_dir);
+    let item = HistoryItem::new("red".to_owned(), 123, 456, "Legacy".to_owned());
+    table.set(Hash::new([1; 20]), item).await?;
+    let host = HostBuilder::new()
+        .with_options(TestOptionsFactory::from(SharedOptions {
+            indexer: Some("red".to_owned()),
+            ..SharedOptions::default()
+        }))
+        .with_options(TestOptionsFactory::from(CacheOptions {
+            cache: Some(cache.clone()),
+        }))
+        .build();
+
+    // Act
+    let history = host.services.get_required::<History>();
+
+    // Assert
+    assert_eq!(
+        history.get(123)?.map(|x| x.name.clone()),
+        Some("Legacy".to_owned())
+    );
+    assert!(cache.join("history.db").is_file());
+    Ok(())
+}
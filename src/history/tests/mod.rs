@@ -0,0 +1,2 @@
+mod history_item_tests;
+mod history_tests;
@@ -0,0 +1,31 @@
+use std::fmt::{Display, Formatter};
+
+/// A point in the lifecycle of a source that a hook script can be run at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookEvent {
+    /// A source was verified, whether or not it's suitable for transcoding.
+    Verified,
+    /// A format of a source was transcoded and its torrent created.
+    Transcoded,
+    /// A format of a source was uploaded.
+    Uploaded,
+}
+
+impl HookEvent {
+    /// Get the name of the event as it's passed to the script.
+    #[must_use]
+    pub fn get_name(self) -> &'static str {
+        match self {
+            HookEvent::Verified => "post_verify",
+            HookEvent::Transcoded => "post_transcode",
+            HookEvent::Uploaded => "post_upload",
+        }
+    }
+}
+
+impl Display for HookEvent {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.get_name())
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use rogue_logging::Error;
+use tokio::process::Command;
+
+use crate::errors::{command_error, OutputHandler};
+use crate::hooks::HookEvent;
+use crate::naming::SourceName;
+use crate::options::{HookOptions, SharedOptions};
+use crate::source::Source;
+
+/// Run the script configured for a [`HookEvent`].
+///
+/// The script is passed environment variables describing the source and the outputs
+/// so custom integrations don't require changes to caesura.
+pub struct HookRunner {
+    options: Ref<HookOptions>,
+    shared_options: Ref<SharedOptions>,
+}
+
+#[injectable]
+impl HookRunner {
+    #[must_use]
+    pub fn new(options: Ref<HookOptions>, shared_options: Ref<SharedOptions>) -> Self {
+        Self {
+            options,
+            shared_options,
+        }
+    }
+
+    /// Run the script of the event if one is set.
+    ///
+    /// `vars` are passed to the script in addition to the variables describing the source.
+    ///
+    /// Failures are logged as warnings but don't stop the command.
+    pub async fn run(&self, event: HookEvent, source: &Source, vars: Vec<(&str, String)>) {
+        let Some(path) = self.get_path(event) else {
+            return;
+        };
+        let mut env = self.get_source_vars(event, source);
+        env.extend(vars.into_iter().map(|(key, value)| (key.to_owned(), value)));
+        match execute_hook(path, &env).await {
+            Ok(()) => trace!("{} {event} hook for {source}", "Ran".bold()),
+            Err(error) => {
+                warn!("{} to run {event} hook for {source}", "Failed".bold());
+                warn!("{error}");
+            }
+        }
+    }
+
+    fn get_path(&self, event: HookEvent) -> Option<&PathBuf> {
+        match event {
+            HookEvent::Verified => self.options.post_verify_hook.as_ref(),
+            HookEvent::Transcoded => self.options.post_transcode_hook.as_ref(),
+            HookEvent::Uploaded => self.options.post_upload_hook.as_ref(),
+        }
+    }
+
+    fn get_source_vars(&self, event: HookEvent, source: &Source) -> Vec<(String, String)> {
+        vec![
+            ("CAESURA_EVENT".to_owned(), event.get_name().to_owned()),
+            (
+                "CAESURA_INDEXER".to_owned(),
+                self.shared_options.indexer.clone().unwrap_or_default(),
+            ),
+            (
+                "CAESURA_SOURCE".to_owned(),
+                SourceName::get(&source.metadata),
+            ),
+            (
+                "CAESURA_SOURCE_ID".to_owned(),
+                source.torrent.id.to_string(),
+            ),
+            ("CAESURA_GROUP_ID".to_owned(), source.group.id.to_string()),
+            (
+                "CAESURA_SOURCE_DIR".to_owned(),
+                source.directory.to_string_lossy().to_string(),
+            ),
+        ]
+    }
+}
+
+/// Execute a hook script with environment variables.
+///
+/// The script is expected to exit with a zero code.
+pub(crate) async fn execute_hook(path: &Path, env: &[(String, String)]) -> Result<(), Error> {
+    let program = path.to_string_lossy();
+    let output = Command::new(path)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .output()
+        .await
+        .map_err(|e| command_error(e, "execute hook", &program))?;
+    let output = OutputHandler::execute(output, "execute hook", &program)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        trace!("{}", stdout.trim_end());
+    }
+    Ok(())
+}
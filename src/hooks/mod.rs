@@ -0,0 +1,7 @@
+pub use hook_event::*;
+pub use hook_runner::*;
+
+pub(crate) mod hook_event;
+pub(crate) mod hook_runner;
+#[cfg(test)]
+mod tests;
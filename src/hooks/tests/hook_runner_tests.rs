@@ -0,0 +1,57 @@
+use std::fs::{read_to_string, set_permissions, write, Permissions};
+use std::os::unix::fs::PermissionsExt;
+
+use crate::errors::ErrorCode;
+use crate::hooks::execute_hook;
+use crate::testing::TempDirectory;
+
+#[tokio::test]
+async fn execute_hook_passes_env() {
+    // Arrange
+    let dir = TempDirectory::create("execute_hook_passes_env");
+    let script = dir.join("hook.sh");
+    let output = dir.join("output.txt");
+    write(
+        &script,
+        format!(
+            "#!/bin/sh\necho \"$CAESURA_EVENT $CAESURA_FORMAT\" > {}\n",
+            output.display()
+        ),
+    )
+    .expect("should be able to write script");
+    set_permissions(&script, Permissions::from_mode(0o755))
+        .expect("should be able to set permissions");
+    let env = vec![
+        ("CAESURA_EVENT".to_owned(), "post_transcode".to_owned()),
+        ("CAESURA_FORMAT".to_owned(), "V0".to_owned()),
+    ];
+
+    // Act
+    execute_hook(&script, &env)
+        .await
+        .expect("hook should succeed");
+
+    // Assert
+    let content = read_to_string(output).expect("should be able to read output");
+    assert_eq!(content, "post_transcode V0\n");
+}
+
+#[tokio::test]
+async fn execute_hook_fails_on_exit_code() {
+    // Arrange
+    let dir = TempDirectory::create("execute_hook_fails_on_exit_code");
+    let script = dir.join("hook.sh");
+    write(&script, "#!/bin/sh\necho oops >&2\nexit 3\n").expect("should be able to write script");
+    set_permissions(&script, Permissions::from_mode(0o755))
+        .expect("should be able to set permissions");
+
+    // Act
+    let error = execute_hook(&script, &[])
+        .await
+        .expect_err("hook should fail");
+
+    // Assert
+    assert_eq!(ErrorCode::from_error(&error), ErrorCode::CommandFailed);
+    assert!(error.message.contains("exit code 3"));
+    assert!(error.message.ends_with("oops"));
+}
@@ -0,0 +1 @@
+mod hook_runner_tests;
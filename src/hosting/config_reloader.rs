@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::metadata;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use colored::Colorize;
+use di::{injectable, Ref, RefMut, ServiceProvider};
+use log::{info, trace, warn};
+use serde_yaml::Value;
+
+use crate::batch::BatchCommand;
+use crate::confirm::Confirmation;
+use crate::hosting::{HostBuilder, Shutdown};
+use crate::metrics::Metrics;
+use crate::options::{OptionRule, OptionsProvider};
+use crate::progress::{ProgressEmitter, ProgressLog};
+use crate::queue::Queue;
+use crate::summary::{RunSummary, Timings};
+use crate::tui::TuiState;
+
+/// A key of the config file that was changed.
+///
+/// Only the key is shown so secrets such as `api_key` are not logged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigChange {
+    Added(String),
+    Removed(String),
+    Changed(String),
+}
+
+struct ReloadState {
+    provider: Ref<OptionsProvider>,
+    modified: Option<SystemTime>,
+}
+
+/// Reload the config file of a long running command such as `watch` or `serve`.
+///
+/// If the config file has been modified then a new [`BatchCommand`] is built from it so
+/// the changes apply to subsequent batches. The state of the process such as
+/// [`Shutdown`], [`Metrics`] and [`RunSummary`] is shared with the new [`BatchCommand`].
+pub struct ConfigReloader {
+    services: ServiceProvider,
+    state: Mutex<ReloadState>,
+}
+
+#[injectable]
+impl ConfigReloader {
+    fn new(services: ServiceProvider) -> Self {
+        let provider = services.get_required::<OptionsProvider>();
+        let modified = get_modified(provider.get_config_path());
+        Self {
+            services,
+            state: Mutex::new(ReloadState { provider, modified }),
+        }
+    }
+
+    /// Get a [`BatchCommand`] built from the config file if it has changed since it was
+    /// last read.
+    ///
+    /// Returns `None` if the config file has not changed, or if it can't be read or is
+    /// not valid, in which case the current [`BatchCommand`] should continue to be used.
+    pub fn reload_batch(&self) -> Option<RefMut<BatchCommand>> {
+        let mut state = self.state.lock().expect("state should be lockable");
+        let path = state.provider.get_config_path().to_path_buf();
+        let modified = get_modified(&path);
+        if modified == state.modified {
+            return None;
+        }
+        state.modified = modified;
+        let provider = match state.provider.reload() {
+            Ok(provider) => provider,
+            Err(error) => {
+                warn!("{} to reload config file: {error}", "Failed".bold());
+                return None;
+            }
+        };
+        let changes = get_config_changes(state.provider.get_config(), provider.get_config());
+        if changes.is_empty() {
+            trace!("{} changes to the config file", "No".bold());
+            return None;
+        }
+        let provider = Ref::new(provider);
+        let batch = self.build_batch(provider.clone());
+        let errors = batch
+            .read()
+            .expect("BatchCommand should be readable")
+            .get_errors();
+        if !errors.is_empty() {
+            warn!(
+                "{} changes to the config file as they are not valid",
+                "Ignoring".bold()
+            );
+            OptionRule::show(&errors);
+            return None;
+        }
+        state.provider = provider;
+        info!("{} config file {}", "Reloaded".bold(), path.display());
+        for change in changes {
+            info!("{change}");
+        }
+        Some(batch)
+    }
+
+    fn build_batch(&self, provider: Ref<OptionsProvider>) -> RefMut<BatchCommand> {
+        let services = &self.services;
+        HostBuilder::new()
+            .with_instance(provider)
+            .with_instance(services.get_required::<Shutdown>())
+            .with_instance(services.get_required::<Metrics>())
+            .with_instance(services.get_required::<RunSummary>())
+            .with_instance(services.get_required::<Timings>())
+            .with_instance(services.get_required::<ProgressEmitter>())
+            .with_instance(services.get_required::<ProgressLog>())
+            .with_instance(services.get_required::<TuiState>())
+            .with_instance(services.get_required::<Confirmation>())
+            .with_instance(services.get_required_mut::<Queue>())
+            .build()
+            .services
+            .get_required_mut::<BatchCommand>()
+    }
+}
+
+/// Get the keys that differ between two versions of the config file.
+///
+/// Nested keys such as those of a profile are joined by `.` and ordered by key.
+#[must_use]
+pub fn get_config_changes(old: &str, new: &str) -> Vec<ConfigChange> {
+    let old = flatten(old);
+    let mut new = flatten(new);
+    let mut changes = Vec::new();
+    for (key, value) in old {
+        match new.remove(&key) {
+            None => changes.push(ConfigChange::Removed(key)),
+            Some(new_value) if new_value != value => changes.push(ConfigChange::Changed(key)),
+            Some(_) => {}
+        }
+    }
+    changes.extend(new.into_keys().map(ConfigChange::Added));
+    changes.sort_by(|a, b| a.get_key().cmp(b.get_key()));
+    changes
+}
+
+impl ConfigChange {
+    /// Get the key that was changed.
+    #[must_use]
+    pub fn get_key(&self) -> &str {
+        match self {
+            ConfigChange::Added(key) | ConfigChange::Removed(key) | ConfigChange::Changed(key) => {
+                key
+            }
+        }
+    }
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    metadata(path).and_then(|x| x.modified()).ok()
+}
+
+/// Flatten the mappings of a YAML document into values by their dotted key.
+fn flatten(yaml: &str) -> BTreeMap<String, Value> {
+    let mut values = BTreeMap::new();
+    if let Ok(value) = serde_yaml::from_str::<Value>(yaml) {
+        flatten_value(None, value, &mut values);
+    }
+    values
+}
+
+fn flatten_value(prefix: Option<&str>, value: Value, values: &mut BTreeMap<String, Value>) {
+    match (prefix, value) {
+        (prefix, Value::Mapping(mapping)) => {
+            for (key, value) in mapping {
+                let key = match key {
+                    Value::String(key) => key,
+                    key => serde_yaml::to_string(&key)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_owned(),
+                };
+                let key = match prefix {
+                    Some(prefix) => format!("{prefix}.{key}"),
+                    None => key,
+                };
+                flatten_value(Some(&key), value, values);
+            }
+        }
+        (Some(prefix), value) => {
+            values.insert(prefix.to_owned(), value);
+        }
+        (None, _) => {}
+    }
+}
+
+impl Display for ConfigChange {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigChange::Added(key) => write!(formatter, "{} {key}", "Added".bold()),
+            ConfigChange::Removed(key) => write!(formatter, "{} {key}", "Removed".bold()),
+            ConfigChange::Changed(key) => write!(formatter, "{} {key}", "Changed".bold()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::process::ExitCode;
+
+use rogue_logging::Error;
+
+use crate::cli::CommandArguments;
+use crate::options::CONFIGURATION_DOMAIN;
+use crate::source::API_DOMAIN;
+
+/// Outcome of executing a command, returned as the exit code of the process.
+///
+/// Scripts can use the exit code to determine the class of failure without
+/// parsing the logs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExitStatus {
+    /// `0` The command succeeded.
+    Success,
+    /// `1` An unexpected error occurred.
+    Failure,
+    /// `2` The command line arguments or config file options are invalid.
+    InvalidOptions,
+    /// `3` The source is not suitable for transcoding.
+    VerifyFailed,
+    /// `4` A transcode or spectrogram could not be created.
+    TranscodeFailed,
+    /// `5` The API responded with an error, such as an invalid API key.
+    ApiFailed,
+    /// `6` One or more of the transcodes could not be uploaded.
+    UploadFailed,
+    /// `130` The command was stopped by `SIGINT` or `SIGTERM`.
+    Interrupted,
+}
+
+impl ExitStatus {
+    /// Get the exit code of the process.
+    #[must_use]
+    pub fn get_code(self) -> u8 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Failure => 1,
+            ExitStatus::InvalidOptions => 2,
+            ExitStatus::VerifyFailed => 3,
+            ExitStatus::TranscodeFailed => 4,
+            ExitStatus::ApiFailed => 5,
+            ExitStatus::UploadFailed => 6,
+            ExitStatus::Interrupted => 130,
+        }
+    }
+
+    /// Get the [`ExitStatus`] of a command that completed without an [`Error`] but did not succeed.
+    #[must_use]
+    pub fn from_command(command: &CommandArguments) -> Self {
+        match command {
+            CommandArguments::Verify { .. } => ExitStatus::VerifyFailed,
+            CommandArguments::Spectrogram { .. } | CommandArguments::Transcode { .. } => {
+                ExitStatus::TranscodeFailed
+            }
+            CommandArguments::Upload { .. } => ExitStatus::UploadFailed,
+            _ => ExitStatus::Failure,
+        }
+    }
+
+    /// Get the [`ExitStatus`] of an [`Error`].
+    #[must_use]
+    pub fn from_error(error: &Error) -> Self {
+        if error.domain.as_deref() == Some(CONFIGURATION_DOMAIN) {
+            ExitStatus::InvalidOptions
+        } else if error.status_code.is_some() || error.domain.as_deref() == Some(API_DOMAIN) {
+            ExitStatus::ApiFailed
+        } else {
+            ExitStatus::Failure
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        ExitCode::from(status.get_code())
+    }
+}
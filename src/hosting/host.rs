@@ -1,16 +1,34 @@
 use crate::batch::BatchCommand;
+use crate::clean::CleanCommand;
+use crate::cross_seed::CrossSeedCommand;
+use crate::doctor::DoctorCommand;
+use crate::errors::CommandDiagnostics;
+use crate::history::HistoryCommand;
 use di::ServiceProvider;
 
 use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments;
 use crate::cli::CommandArguments::*;
+use crate::cli::ConfigCommandArguments::{SetKey, Show};
 use crate::cli::QueueCommandArguments::{Add, List, Summary};
+use crate::hosting::{listen_for_signals, ExitStatus, Shutdown};
+use crate::logging::init_logger;
+use crate::notify::{DesktopNotifier, HealthCheck, HealthPing};
 use crate::options::config_command::ConfigCommand;
+use crate::options::init_command::InitCommand;
+use crate::options::set_key_command::SetKeyCommand;
+use crate::options::SharedOptions;
 use crate::queue::queue_summary_command::QueueSummaryCommand;
 use crate::queue::{QueueAddCommand, QueueListCommand};
+use crate::report::ErrorReporter;
+use crate::serve::ServeCommand;
 use crate::spectrogram::SpectrogramCommand;
+use crate::summary::{RunSummary, Timings};
 use crate::transcode::TranscodeCommand;
+use crate::update::SelfUpdateCommand;
 use crate::upload::UploadCommand;
 use crate::verify::VerifyCommand;
+use crate::watch::WatchCommand;
 use rogue_logging::Error;
 use rogue_logging::*;
 
@@ -34,11 +52,109 @@ impl Host {
     /// 1. Configure logging
     /// 2. Determine the command to execute
     /// 3. Execute the command
-    pub async fn execute(&self) -> Result<bool, Error> {
+    /// 4. Show a summary of the sources processed
+    ///    and write the result to stdout if `result_format` is `json`
+    /// 5. Show a desktop notification if enabled
+    ///
+    /// A `SIGINT` or `SIGTERM` requests [`Shutdown`] and the command returns
+    /// [`ExitStatus::Interrupted`] once it has stopped.
+    ///
+    /// Returns the [`ExitStatus`] of the command. If an [`Error`] is returned then
+    /// [`ExitStatus::from_error`] should be used to determine the exit code.
+    pub async fn execute(&self) -> Result<ExitStatus, Error> {
         let logger = self.services.get_required::<Logger>();
-        Logger::init(logger);
-        match ArgumentsParser::get_or_show_help() {
-            Config => self.services.get_required::<ConfigCommand>().execute(),
+        let options = self.services.get_required::<SharedOptions>();
+        init_logger(logger, &options);
+        let summary = self.services.get_required::<RunSummary>();
+        let timings = self.services.get_required::<Timings>();
+        let command = ArgumentsParser::get_or_show_help();
+        let failure = ExitStatus::from_command(&command);
+        let name = command.get_name();
+        ErrorReporter::init(&options, name);
+        CommandDiagnostics::init(options.diagnostics_dir.clone());
+        let desktop_name = DesktopNotifier::get_command_name(&command);
+        let desktop = self.services.get_required::<DesktopNotifier>();
+        if desktop_name.is_some() {
+            desktop.check_options()?;
+        }
+        let shutdown = self.services.get_required::<Shutdown>();
+        tokio::spawn(listen_for_signals(shutdown.clone()));
+        let health = HealthCheck::is_supported(&command)
+            .then(|| self.services.get_required::<HealthCheck>());
+        if let Some(health) = &health {
+            health.ping(HealthPing::Start, String::new()).await;
+        }
+        let result = Box::pin(self.execute_command(command)).await;
+        if let Some(health) = &health {
+            health.ping_result(&result).await;
+        }
+        summary.show();
+        timings.show();
+        summary.write_result(name, &result);
+        if let Some(name) = desktop_name {
+            desktop.notify(name, &result).await;
+        }
+        if shutdown.is_requested() {
+            return Ok(ExitStatus::Interrupted);
+        }
+        Ok(if result? {
+            ExitStatus::Success
+        } else {
+            failure
+        })
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn execute_command(&self, command: CommandArguments) -> Result<bool, Error> {
+        match command {
+            Config { command: None } => {
+                self.services.get_required::<ConfigCommand>().execute(false)
+            }
+            Config {
+                command: Some(Show { origin, .. }),
+            } => self
+                .services
+                .get_required::<ConfigCommand>()
+                .execute(origin),
+            Config {
+                command: Some(SetKey { cookie, .. }),
+            } => self
+                .services
+                .get_required::<SetKeyCommand>()
+                .execute_cli(cookie),
+            CrossSeed { .. } => {
+                self.services
+                    .get_required_mut::<CrossSeedCommand>()
+                    .write()
+                    .expect("CrossSeedCommand should be available to write")
+                    .execute_cli()
+                    .await
+            }
+            Doctor { .. } => {
+                self.services
+                    .get_required::<DoctorCommand>()
+                    .execute_cli()
+                    .await
+            }
+            History { .. } => self.services.get_required::<HistoryCommand>().execute_cli(),
+            Clean { .. } => {
+                self.services
+                    .get_required::<CleanCommand>()
+                    .execute_cli()
+                    .await
+            }
+            Init { .. } => {
+                self.services
+                    .get_required::<InitCommand>()
+                    .execute_cli()
+                    .await
+            }
+            SelfUpdate { .. } => {
+                self.services
+                    .get_required::<SelfUpdateCommand>()
+                    .execute_cli()
+                    .await
+            }
             Batch { .. } => {
                 self.services
                     .get_required_mut::<BatchCommand>()
@@ -105,6 +221,20 @@ impl Host {
                     .execute_cli()
                     .await
             }
+            Serve { .. } => {
+                self.services
+                    .get_required::<ServeCommand>()
+                    .execute_cli()
+                    .await
+            }
+            Watch { .. } => {
+                self.services
+                    .get_required_mut::<WatchCommand>()
+                    .write()
+                    .expect("WatchCommand should be available to write")
+                    .execute_cli()
+                    .await
+            }
         }
     }
 }
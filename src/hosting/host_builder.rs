@@ -7,21 +7,44 @@ use std::time::SystemTime;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
-use crate::batch::BatchCommand;
+use crate::batch::{BatchCommand, BatchPlanner, SourceCleaner};
+use crate::beets::BeetsImporter;
 use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::clean::CleanCommand;
+use crate::confirm::Confirmation;
+use crate::cross_seed::CrossSeedCommand;
+use crate::doctor::DoctorCommand;
 use crate::formats::TargetFormatProvider;
 use crate::fs::PathManager;
-use crate::hosting::Host;
-use crate::jobs::{DebugSubscriber, JobRunner, ProgressBarSubscriber, Publisher};
+use crate::history::{History, HistoryCommand};
+use crate::hooks::HookRunner;
+use crate::hosting::{ConfigReloader, Host, Shutdown};
+use crate::indexer::{IndexerClient, RateLimiter, SessionClient};
+use crate::jobs::{DebugSubscriber, JobRunner, JobServer, ProgressBarSubscriber, Publisher};
+use crate::logging::force_init_logger;
+use crate::metrics::Metrics;
+use crate::musicbrainz::{MusicBrainzClient, MusicBrainzProvider};
+use crate::notify::{DesktopNotifier, HealthCheck, Notifier};
 use crate::options::config_command::ConfigCommand;
+use crate::options::init_command::InitCommand;
+use crate::options::set_key_command::SetKeyCommand;
 use crate::options::*;
+use crate::progress::{ProgressEmitter, ProgressLog};
 use crate::queue::queue_summary_command::QueueSummaryCommand;
 use crate::queue::{Queue, QueueAddCommand, QueueListCommand};
-use crate::source::{IdProvider, SourceProvider};
+use crate::serve::ServeCommand;
+use crate::source::{IdProvider, SourceLocker, SourcePicker, SourceProvider};
 use crate::spectrogram::{SpectrogramCommand, SpectrogramJobFactory};
-use crate::transcode::{AdditionalJobFactory, TranscodeCommand, TranscodeJobFactory};
-use crate::upload::UploadCommand;
-use crate::verify::VerifyCommand;
+use crate::summary::{RunSummary, Timings};
+use crate::torrent::AnnounceProvider;
+use crate::transcode::{
+    AdditionalJobFactory, ClipChecker, ProvenanceProvider, TranscodeCommand, TranscodeJobFactory,
+};
+use crate::tui::{Tui, TuiState, TuiSubscriber};
+use crate::update::SelfUpdateCommand;
+use crate::upload::{AccountChecker, CrossPoster, RequestFiller, UploadCommand, UploadDescription};
+use crate::verify::{VerifyCache, VerifyCommand};
+use crate::watch::{TorrentClient, WatchCommand};
 use gazelle_api::GazelleClientFactory;
 use rogue_logging::Error;
 use rogue_logging::Logger;
@@ -38,7 +61,7 @@ impl Default for HostBuilder {
 
 impl HostBuilder {
     #[must_use]
-    #[allow(clippy::as_conversions)]
+    #[allow(clippy::as_conversions, clippy::too_many_lines)]
     pub fn new() -> HostBuilder {
         let mut this = HostBuilder {
             services: ServiceCollection::new(),
@@ -47,17 +70,31 @@ impl HostBuilder {
             // Add options
             .add(OptionsProvider::singleton())
             .add(BatchOptions::singleton())
+            .add(BeetsOptions::singleton())
             .add(CacheOptions::singleton())
+            .add(CleanArgs::singleton())
             .add(CopyOptions::singleton())
+            .add(CrossSeedOptions::singleton())
+            .add(DesktopOptions::singleton())
+            .add(EnrichOptions::singleton())
             .add(FileOptions::singleton())
+            .add(HistoryOptions::singleton())
+            .add(HookOptions::singleton())
+            .add(NotifyOptions::singleton())
+            .add(PermissionOptions::singleton())
             .add(RunnerOptions::singleton())
+            .add(ServeOptions::singleton())
             .add(SharedOptions::singleton())
             .add(SourceArg::singleton())
             .add(SpectrogramOptions::singleton())
             .add(TargetOptions::singleton())
+            .add(TorrentOptions::singleton())
+            .add(HistoryArgs::singleton())
             .add(QueueAddArgs::singleton())
+            .add(SelfUpdateArgs::singleton())
             .add(UploadOptions::singleton())
             .add(VerifyOptions::singleton())
+            .add(WatchOptions::singleton())
             // Add main services
             .add(singleton_as_self().from(|provider| {
                 let options = provider.get_required::<SharedOptions>();
@@ -70,29 +107,65 @@ impl HostBuilder {
                 Ref::new(logger)
             }))
             .add(PathManager::transient())
+            .add(AnnounceProvider::transient())
             .add(IdProvider::transient())
+            .add(SourcePicker::transient())
             .add(SourceProvider::transient().as_mut())
+            .add(SourceLocker::transient())
             .add(singleton_as_self().from(|provider| {
                 let options = provider.get_required::<SharedOptions>();
+                // Options are validated by each command before the client is used
                 let factory = GazelleClientFactory {
-                    url: options
-                        .indexer_url
-                        .clone()
-                        .expect("indexer_url should be set"),
-                    key: options.api_key.clone().expect("api_key should be set"),
+                    url: options.indexer_url.clone().unwrap_or_default(),
+                    key: options.api_key.clone().unwrap_or_default(),
                     user_agent: format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
                 };
                 let api = factory.create();
                 RefMut::new(Mut::new(api))
             }))
+            .add(RateLimiter::transient())
             .add(JobRunner::transient())
+            .add(JobServer::singleton())
             .add(Publisher::transient())
             .add(DebugSubscriber::transient())
             .add(ProgressBarSubscriber::transient())
+            .add(TuiSubscriber::transient())
+            .add(TuiState::singleton())
+            .add(Tui::transient())
+            .add(ProgressEmitter::singleton())
+            .add(Notifier::transient())
+            .add(DesktopNotifier::transient())
+            .add(HealthCheck::transient())
+            .add(HookRunner::transient())
+            .add(BeetsImporter::transient())
+            .add(History::transient())
+            .add(RunSummary::singleton())
+            .add(Timings::singleton())
+            .add(Confirmation::singleton())
+            .add(Shutdown::singleton())
+            .add(ConfigReloader::transient())
+            .add(Metrics::singleton())
+            .add(ProgressLog::singleton())
             .add(TargetFormatProvider::transient())
+            .add(MusicBrainzClient::singleton())
+            .add(IndexerClient::transient())
+            .add(SessionClient::transient())
+            .add(MusicBrainzProvider::transient())
             // Add config services
             .add(ConfigCommand::transient())
+            .add(InitCommand::transient())
+            .add(SetKeyCommand::transient())
+            // Add doctor services
+            .add(DoctorCommand::transient())
+            // Add update services
+            .add(SelfUpdateCommand::transient())
+            // Add history services
+            .add(HistoryCommand::transient())
+            // Add clean services
+            .add(CleanCommand::transient())
             // Add batch services
+            .add(BatchPlanner::transient())
+            .add(SourceCleaner::transient())
             .add(BatchCommand::transient().as_mut())
             // Add queue services
             .add(QueueAddCommand::transient().as_mut())
@@ -117,12 +190,26 @@ impl HostBuilder {
             }))
             // Add transcode services
             .add(TranscodeCommand::transient())
+            .add(ClipChecker::singleton())
+            .add(ProvenanceProvider::singleton())
             .add(TranscodeJobFactory::transient())
             .add(AdditionalJobFactory::transient())
             // Add upload services
+            .add(AccountChecker::transient())
+            .add(RequestFiller::transient())
+            .add(UploadDescription::transient())
+            .add(CrossPoster::transient())
             .add(UploadCommand::transient().as_mut())
             // Add verify services
-            .add(VerifyCommand::transient().as_mut());
+            .add(VerifyCache::transient())
+            .add(VerifyCommand::transient().as_mut())
+            // Add watch services
+            .add(TorrentClient::transient())
+            .add(WatchCommand::transient().as_mut())
+            // Add serve services
+            .add(ServeCommand::transient())
+            // Add cross seed services
+            .add(CrossSeedCommand::transient().as_mut());
         this
     }
 
@@ -133,12 +220,20 @@ impl HostBuilder {
         self
     }
 
+    /// Use an existing instance rather than creating a new one.
+    #[must_use]
+    pub fn with_instance<T: Send + Sync + 'static>(&mut self, instance: Ref<T>) -> &mut Self {
+        self.services
+            .add(singleton_as_self().from(move |_| instance.clone()));
+        self
+    }
+
     #[must_use]
     pub fn build(&self) -> Host {
         match self.services.build_provider() {
             Ok(services) => Host::new(services),
             Err(error) => {
-                Logger::force_init(PKG_NAME.to_owned());
+                force_init_logger();
                 error!("{} to build the application:", "Failed".bold());
                 error!("{error}");
                 exit(1)
@@ -1,5 +1,15 @@
+pub use crate::errors::log_error;
+pub use config_reloader::*;
+pub use exit_status::*;
 pub use host::*;
 pub use host_builder::*;
+pub use shutdown::*;
 
+mod config_reloader;
+mod exit_status;
 pub mod host;
 mod host_builder;
+pub(crate) mod shutdown;
+
+#[cfg(test)]
+mod tests;
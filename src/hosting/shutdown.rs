@@ -0,0 +1,135 @@
+use std::process::exit;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{error, warn};
+use tokio::signal::ctrl_c;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::Notify;
+use tokio::task::AbortHandle;
+use tokio::time::{sleep, Instant};
+
+use crate::hosting::ExitStatus;
+
+/// Time to wait for aborted jobs to drop their external processes before exiting.
+const ABORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval to check if the aborted jobs have finished.
+const ABORT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Request for the application to stop after a `SIGINT` or `SIGTERM`.
+///
+/// Long running commands check [`Shutdown::is_requested`] between sources so the queue
+/// is left in a consistent state, and running jobs are cancelled which kills their
+/// external processes.
+///
+/// The staging directory is kept rather than cleaned. The next attempt checks each
+/// staged transcode and redoes any that were cut short, so keeping it only saves the
+/// work that was completed. Cleaning it during shutdown would also race the external
+/// processes that are still being killed. Set `no_resume` to start from scratch.
+#[derive(Default)]
+pub struct Shutdown {
+    requested: AtomicBool,
+    notify: Notify,
+    tasks: Mutex<Vec<AbortHandle>>,
+}
+
+#[injectable]
+impl Shutdown {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the application to stop.
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// Has the application been requested to stop?
+    #[must_use]
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// Track a running task so it's aborted if the application is stopped immediately.
+    pub fn track(&self, handle: AbortHandle) {
+        let mut tasks = self.tasks.lock().expect("tasks should not be poisoned");
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(handle);
+    }
+
+    /// Abort the tracked tasks and wait until they finish or the timeout elapses.
+    ///
+    /// Aborting a task drops its future which kills any external process spawned with
+    /// `kill_on_drop`.
+    ///
+    /// Returns `true` if every task finished before the timeout.
+    pub async fn abort_tracked(&self, timeout: Duration) -> bool {
+        let tasks: Vec<AbortHandle> = self
+            .tasks
+            .lock()
+            .expect("tasks should not be poisoned")
+            .drain(..)
+            .collect();
+        for task in &tasks {
+            task.abort();
+        }
+        let deadline = Instant::now() + timeout;
+        while !tasks.iter().all(AbortHandle::is_finished) {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            sleep(ABORT_POLL_INTERVAL).await;
+        }
+        true
+    }
+
+    /// Wait until the application is requested to stop.
+    pub async fn wait(&self) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_requested() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Request [`Shutdown`] on the first `SIGINT` or `SIGTERM`, and exit on the second.
+///
+/// Exiting skips `Drop` so the running jobs are aborted first, and given a moment to
+/// kill their external processes, rather than leaving them orphaned.
+pub(crate) async fn listen_for_signals(shutdown: Ref<Shutdown>) {
+    let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+        warn!("{} to install the SIGTERM handler", "Failed".bold());
+        return;
+    };
+    tokio::select! {
+        _ = ctrl_c() => {},
+        _ = terminate.recv() => {},
+    }
+    warn!(
+        "{} after the current step completes. Send the signal again to stop immediately",
+        "Stopping".bold()
+    );
+    shutdown.request();
+    tokio::select! {
+        _ = ctrl_c() => {},
+        _ = terminate.recv() => {},
+    }
+    error!("{} immediately", "Stopping".bold());
+    if !shutdown.abort_tracked(ABORT_TIMEOUT).await {
+        warn!(
+            "{} to stop every job within {} seconds",
+            "Failed".bold(),
+            ABORT_TIMEOUT.as_secs()
+        );
+    }
+    exit(i32::from(ExitStatus::Interrupted.get_code()));
+}
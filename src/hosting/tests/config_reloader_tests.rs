@@ -0,0 +1,62 @@
+use crate::hosting::{get_config_changes, ConfigChange};
+
+#[test]
+fn get_config_changes_unchanged() {
+    // Arrange
+    let config = "cpus: 4\ntarget:\n  - flac\n  - 320\n";
+
+    // Act
+    let changes = get_config_changes(config, config);
+
+    // Assert
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn get_config_changes_added_removed_changed() {
+    // Arrange
+    let old = "cpus: 4\napi_key: abc\ntarget:\n  - flac\n";
+    let new = "cpus: 8\ntarget:\n  - flac\n  - 320\nnotify_url: http://localhost\n";
+
+    // Act
+    let changes = get_config_changes(old, new);
+
+    // Assert
+    assert_eq!(
+        changes,
+        vec![
+            ConfigChange::Removed("api_key".to_owned()),
+            ConfigChange::Changed("cpus".to_owned()),
+            ConfigChange::Added("notify_url".to_owned()),
+            ConfigChange::Changed("target".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn get_config_changes_nested() {
+    // Arrange
+    let old = "profiles:\n  fast:\n    cpus: 4\n    spectrogram: false\n";
+    let new = "profiles:\n  fast:\n    cpus: 16\n    spectrogram: false\n";
+
+    // Act
+    let changes = get_config_changes(old, new);
+
+    // Assert
+    assert_eq!(
+        changes,
+        vec![ConfigChange::Changed("profiles.fast.cpus".to_owned())]
+    );
+}
+
+#[test]
+fn config_change_display_hides_value() {
+    // Arrange
+    let change = ConfigChange::Changed("api_key".to_owned());
+
+    // Act
+    let output = change.to_string();
+
+    // Assert
+    assert!(output.ends_with("api_key"));
+}
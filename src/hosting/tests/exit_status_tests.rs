@@ -0,0 +1,31 @@
+use crate::errors::error;
+use crate::hosting::ExitStatus;
+use crate::options::{NotSet, OptionRule};
+use crate::source::SourceIssue;
+
+#[test]
+fn exit_status_from_error() {
+    // Arrange
+    let options =
+        OptionRule::check(&[NotSet("api_key".to_owned())]).expect_err("should be an error");
+    let api = SourceIssue::ApiResponse {
+        action: "get torrent".to_owned(),
+        status_code: 401,
+        error: "bad credentials".to_owned(),
+    }
+    .to_error("get source");
+    let other = error("read file", "file is locked".to_owned());
+
+    // Act
+    let options = ExitStatus::from_error(&options);
+    let api = ExitStatus::from_error(&api);
+    let other = ExitStatus::from_error(&other);
+
+    // Assert
+    assert_eq!(options, ExitStatus::InvalidOptions);
+    assert_eq!(options.get_code(), 2);
+    assert_eq!(api, ExitStatus::ApiFailed);
+    assert_eq!(api.get_code(), 5);
+    assert_eq!(other, ExitStatus::Failure);
+    assert_eq!(other.get_code(), 1);
+}
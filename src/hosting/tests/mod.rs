@@ -0,0 +1,3 @@
+mod config_reloader_tests;
+mod exit_status_tests;
+mod shutdown_tests;
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use di::Ref;
+use tokio::task::yield_now;
+use tokio::time::{sleep, timeout};
+
+use crate::hosting::Shutdown;
+
+#[tokio::test]
+async fn shutdown_wait_returns_after_request() {
+    // Arrange
+    let shutdown = Ref::new(Shutdown::new());
+    let waiting = tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move { shutdown.wait().await }
+    });
+
+    // Act
+    yield_now().await;
+    shutdown.request();
+
+    // Assert
+    timeout(Duration::from_secs(5), waiting)
+        .await
+        .expect("wait should return")
+        .expect("task should not panic");
+    assert!(shutdown.is_requested());
+}
+
+#[tokio::test]
+async fn shutdown_wait_returns_if_already_requested() {
+    // Arrange
+    let shutdown = Shutdown::new();
+
+    // Act
+    shutdown.request();
+
+    // Assert
+    timeout(Duration::from_secs(5), shutdown.wait())
+        .await
+        .expect("wait should return");
+}
+
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn shutdown_abort_tracked_drops_running_tasks() {
+    // Arrange
+    let shutdown = Shutdown::new();
+    let dropped = Arc::new(AtomicBool::new(false));
+    let task = tokio::spawn({
+        let flag = DropFlag(dropped.clone());
+        async move {
+            let _flag = flag;
+            sleep(Duration::from_secs(30)).await;
+        }
+    });
+    shutdown.track(task.abort_handle());
+
+    // Act
+    let finished = shutdown.abort_tracked(Duration::from_secs(5)).await;
+
+    // Assert
+    assert!(finished);
+    assert!(dropped.load(Ordering::SeqCst));
+}
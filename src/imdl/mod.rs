@@ -1,7 +0,0 @@
-pub use imdl_command::*;
-pub use torrent_summary::*;
-
-pub(crate) mod imdl_command;
-#[cfg(test)]
-mod tests;
-pub(crate) mod torrent_summary;
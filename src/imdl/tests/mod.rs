@@ -1 +0,0 @@
-mod imdl_tests;
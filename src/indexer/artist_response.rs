@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Response of the artist endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistResponse {
+    pub id: u32,
+    pub name: String,
+    #[serde(default, rename = "torrentgroup")]
+    pub torrent_groups: Vec<ArtistGroup>,
+}
+
+/// A torrent group of an [`ArtistResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistGroup {
+    pub group_id: u32,
+    pub group_name: String,
+    #[serde(default)]
+    pub group_year: Option<u16>,
+    #[serde(default, rename = "torrent")]
+    pub torrents: Vec<ArtistTorrent>,
+}
+
+/// A torrent of an [`ArtistGroup`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistTorrent {
+    pub id: u32,
+    pub media: String,
+    pub format: String,
+    pub encoding: String,
+}
+
+impl ArtistResponse {
+    /// Get the id of every FLAC torrent of the artist.
+    #[must_use]
+    pub fn get_flac_torrent_ids(&self) -> Vec<u32> {
+        self.torrent_groups
+            .iter()
+            .flat_map(|group| &group.torrents)
+            .filter(|torrent| torrent.format.eq_ignore_ascii_case("FLAC"))
+            .map(|torrent| torrent.id)
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Response of the torrent search endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowseResponse {
+    #[serde(default)]
+    pub results: Vec<BrowseGroup>,
+}
+
+/// A torrent group of a [`BrowseResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowseGroup {
+    pub group_id: u32,
+    pub group_name: String,
+    #[serde(default)]
+    pub artist: String,
+    #[serde(default)]
+    pub group_year: Option<u16>,
+    #[serde(default)]
+    pub torrents: Vec<BrowseTorrent>,
+}
+
+/// A torrent of a [`BrowseGroup`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowseTorrent {
+    pub torrent_id: u32,
+    #[serde(default)]
+    pub media: String,
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub encoding: String,
+    #[serde(default)]
+    pub remaster_year: Option<u16>,
+    #[serde(default)]
+    pub remaster_title: String,
+    /// Size in bytes.
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub seeders: u32,
+}
@@ -0,0 +1,69 @@
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+
+/// MP3 encodings that a group without them is reported as missing.
+const MP3_ENCODINGS: [&str; 2] = ["320", "V0 (VBR)"];
+
+/// Response of the collage endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollageResponse {
+    pub id: u32,
+    pub name: String,
+    #[serde(default, rename = "torrentgroups")]
+    pub torrent_groups: Vec<CollageGroup>,
+}
+
+/// A torrent group of a [`CollageResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollageGroup {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub torrents: Vec<CollageTorrent>,
+}
+
+/// A torrent of a [`CollageGroup`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollageTorrent {
+    #[serde(rename = "torrentid")]
+    pub id: u32,
+    pub media: String,
+    pub format: String,
+    pub encoding: String,
+    #[serde(default)]
+    pub seeders: u32,
+}
+
+impl CollageGroup {
+    /// Get the id of every FLAC torrent of the group, best first.
+    ///
+    /// The best FLAC is the one with the most seeders as it's the most likely to be complete
+    /// and the most useful to transcode.
+    #[must_use]
+    pub fn get_flac_torrent_ids(&self) -> Vec<u32> {
+        let mut torrents: Vec<&CollageTorrent> = self
+            .torrents
+            .iter()
+            .filter(|torrent| torrent.format.eq_ignore_ascii_case("FLAC"))
+            .collect();
+        torrents.sort_by_key(|torrent| Reverse(torrent.seeders));
+        torrents.iter().map(|torrent| torrent.id).collect()
+    }
+
+    /// Get the MP3 encodings the group does not have a torrent of.
+    #[must_use]
+    pub fn get_missing_mp3_encodings(&self) -> Vec<&'static str> {
+        MP3_ENCODINGS
+            .into_iter()
+            .filter(|encoding| {
+                !self.torrents.iter().any(|torrent| {
+                    torrent.format.eq_ignore_ascii_case("MP3") && torrent.encoding == *encoding
+                })
+            })
+            .collect()
+    }
+}
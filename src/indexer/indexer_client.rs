@@ -0,0 +1,222 @@
+use std::time::Instant;
+
+use di::{injectable, Ref};
+use gazelle_api::{ApiResponse, TorrentResponse};
+use log::trace;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::{Client, RequestBuilder};
+use rogue_logging::Error;
+use serde::de::DeserializeOwned;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::errors::error;
+use crate::indexer::{
+    ArtistResponse, BrowseResponse, CollageResponse, IndexResponse, RateLimiter,
+    RequestFillResponse, RequestSearchResponse, UserResponse,
+};
+use crate::options::SharedOptions;
+use crate::source::API_DOMAIN;
+use crate::summary::{TimingPhase, Timings};
+
+/// A client for the endpoints of the indexer API that aren't covered by [`GazelleClient`].
+#[injectable]
+pub struct IndexerClient {
+    shared_options: Ref<SharedOptions>,
+    timings: Ref<Timings>,
+    rate_limiter: Ref<RateLimiter>,
+}
+
+impl IndexerClient {
+    /// Get an [`IndexerClient`] for another indexer using its URL and API key.
+    ///
+    /// Requests are rate limited separately from the indexer of the source.
+    #[must_use]
+    pub fn with_indexer(&self, indexer: &str, indexer_url: &str, api_key: &str) -> Self {
+        let shared_options = SharedOptions {
+            indexer: Some(indexer.to_owned()),
+            indexer_url: Some(indexer_url.to_owned()),
+            api_key: Some(api_key.to_owned()),
+            ..(*self.shared_options).clone()
+        };
+        Self {
+            shared_options: Ref::new(shared_options),
+            timings: self.timings.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+        }
+    }
+
+    /// Get an artist and their torrent groups.
+    pub async fn get_artist(&self, id: u32) -> Result<ArtistResponse, Error> {
+        let id = id.to_string();
+        self.get("get artist", &[("action", "artist"), ("id", &id)])
+            .await
+    }
+
+    /// Get a collage and its torrent groups.
+    pub async fn get_collage(&self, id: u32) -> Result<CollageResponse, Error> {
+        let id = id.to_string();
+        self.get("get collage", &[("action", "collage"), ("id", &id)])
+            .await
+    }
+
+    /// Search the torrent groups.
+    pub async fn search_torrents(&self, search: &str) -> Result<BrowseResponse, Error> {
+        self.get(
+            "search torrents",
+            &[("action", "browse"), ("searchstr", search)],
+        )
+        .await
+    }
+
+    /// Search the open requests.
+    pub async fn search_requests(&self, search: &str) -> Result<RequestSearchResponse, Error> {
+        self.get(
+            "search requests",
+            &[("action", "requests"), ("search", search)],
+        )
+        .await
+    }
+
+    /// Fill a request with an uploaded torrent.
+    pub async fn fill_request(
+        &self,
+        request_id: u32,
+        torrent_id: u32,
+    ) -> Result<RequestFillResponse, Error> {
+        let request_id = request_id.to_string();
+        let torrent_id = torrent_id.to_string();
+        self.post(
+            "fill request",
+            &[("action", "requestfill")],
+            &[("requestid", &request_id), ("torrentid", &torrent_id)],
+        )
+        .await
+    }
+
+    /// Get a torrent by its info hash.
+    ///
+    /// Returns `None` if the indexer does not have the torrent.
+    pub async fn get_torrent_by_hash(&self, hash: &str) -> Result<Option<TorrentResponse>, Error> {
+        let hash = hash.to_uppercase();
+        let result = self
+            .get(
+                "get torrent by hash",
+                &[("action", "torrent"), ("hash", &hash)],
+            )
+            .await;
+        match result {
+            Ok(response) => Ok(Some(response)),
+            // Gazelle responds with a failure if the hash is not found
+            Err(error) if matches!(error.status_code, Some(200 | 400)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get the user of the API key and their statistics.
+    pub async fn get_index(&self) -> Result<IndexResponse, Error> {
+        self.get("get index", &[("action", "index")]).await
+    }
+
+    /// Get the profile of a user.
+    pub async fn get_user(&self, id: u32) -> Result<UserResponse, Error> {
+        let id = id.to_string();
+        self.get("get user", &[("action", "user"), ("id", &id)])
+            .await
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        action: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, Error> {
+        let url = self.get_url();
+        trace!("Sending request GET {url}");
+        let request = Client::new().get(&url).query(query);
+        self.send(action, request).await
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        action: &str,
+        query: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> Result<T, Error> {
+        let url = self.get_url();
+        trace!("Sending request POST {url}");
+        let request = Client::new().post(&url).query(query).form(form);
+        self.send(action, request).await
+    }
+
+    fn get_url(&self) -> String {
+        format!(
+            "{}/ajax.php",
+            self.shared_options
+                .indexer_url
+                .clone()
+                .expect("indexer_url should be set")
+        )
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        action: &str,
+        request: RequestBuilder,
+    ) -> Result<T, Error> {
+        let indexer = self.shared_options.indexer.clone().unwrap_or_default();
+        self.rate_limiter.wait_for(&indexer).await;
+        let start = Instant::now();
+        let response = request
+            .header(
+                USER_AGENT,
+                format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+            )
+            .header(ACCEPT, "application/json")
+            .header(
+                AUTHORIZATION,
+                self.shared_options
+                    .api_key
+                    .clone()
+                    .expect("api_key should be set"),
+            )
+            .send()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        let status = response.status();
+        let json = response
+            .text()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        parse_response(action, status.as_u16(), &json)
+    }
+}
+
+/// Get the response of an indexer API request.
+pub fn parse_response<T: DeserializeOwned>(
+    action: &str,
+    status_code: u16,
+    json: &str,
+) -> Result<T, Error> {
+    let response = serde_json::from_str::<ApiResponse<T>>(json).map_err(|e| Error {
+        action: action.to_owned(),
+        message: e.to_string(),
+        domain: Some(API_DOMAIN.to_owned()),
+        status_code: Some(status_code),
+        ..Error::default()
+    })?;
+    let message = match response {
+        ApiResponse {
+            response: Some(response),
+            status,
+            ..
+        } if status == "success" => return Ok(response),
+        ApiResponse { error, .. } => error.unwrap_or_else(|| "Unexpected response".to_owned()),
+    };
+    Err(Error {
+        action: action.to_owned(),
+        message,
+        domain: Some(API_DOMAIN.to_owned()),
+        status_code: Some(status_code),
+        ..Error::default()
+    })
+}
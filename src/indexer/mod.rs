@@ -0,0 +1,21 @@
+pub use artist_response::*;
+pub use browse_response::*;
+pub use collage_response::*;
+pub use indexer_client::*;
+pub use rate_limiter::*;
+pub use request_response::*;
+pub use session_client::*;
+pub use url_helpers::*;
+pub use user_response::*;
+
+pub(crate) mod artist_response;
+pub(crate) mod browse_response;
+pub(crate) mod collage_response;
+pub(crate) mod indexer_client;
+pub(crate) mod rate_limiter;
+pub(crate) mod request_response;
+pub(crate) mod session_client;
+#[cfg(test)]
+mod tests;
+pub(crate) mod url_helpers;
+pub(crate) mod user_response;
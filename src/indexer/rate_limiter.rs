@@ -0,0 +1,181 @@
+use std::fmt::Write as _;
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::trace;
+use tokio::task::spawn_blocking;
+use tokio::time::sleep;
+
+use crate::options::{CacheOptions, SharedOptions};
+
+/// Number of indexer API requests allowed per [`REQUEST_WINDOW`].
+pub const REQUESTS_PER_WINDOW: usize = 10;
+
+/// Duration of the window in which at most [`REQUESTS_PER_WINDOW`] requests are sent.
+pub const REQUEST_WINDOW: Duration = Duration::from_secs(10);
+
+/// Name of the directory in the cache that contains the rate limit files.
+const RATE_LIMIT_DIR_NAME: &str = "rate-limit";
+
+/// Limit the rate of indexer API requests across every process sharing the cache.
+///
+/// The time of each recent request is written to a file in the cache directory, named
+/// by the indexer, so consecutive and concurrent invocations collectively respect the
+/// limit rather than each starting with a fresh budget.
+///
+/// If the file can't be read or written then the request is sent without waiting, as the
+/// client still limits the requests of its own process.
+#[injectable]
+pub struct RateLimiter {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+}
+
+impl RateLimiter {
+    /// Wait until a request can be sent without exceeding the limit, then record it.
+    pub async fn wait(&self) {
+        let indexer = self.shared_options.indexer.clone().unwrap_or_default();
+        self.wait_for(&indexer).await;
+    }
+
+    /// Wait until a request can be sent to another indexer without exceeding its limit,
+    /// then record it.
+    pub async fn wait_for(&self, indexer: &str) {
+        let path = self.get_path(indexer);
+        loop {
+            let file_path = path.clone();
+            let result = spawn_blocking(move || reserve(&file_path, SystemTime::now()))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            let remaining = match result {
+                Ok(remaining) => remaining,
+                Err(e) => {
+                    trace!(
+                        "{} rate limit file {}: {e}",
+                        "Skipped".bold(),
+                        path.display()
+                    );
+                    return;
+                }
+            };
+            let Some(remaining) = remaining else {
+                return;
+            };
+            trace!(
+                "{} {:.3} for the rate limit of other processes",
+                "Waiting".bold(),
+                remaining.as_secs_f64()
+            );
+            sleep(remaining).await;
+        }
+    }
+
+    fn get_path(&self, indexer: &str) -> PathBuf {
+        self.cache_options
+            .cache
+            .clone()
+            .expect("cache should be set")
+            .join(RATE_LIMIT_DIR_NAME)
+            .join(format!("{indexer}.txt"))
+    }
+}
+
+/// Record a request at `now` if the limit allows it.
+///
+/// The file is locked while it's read and written so concurrent processes don't send a
+/// request in the same slot. Waiting for the lock blocks so this must be called with
+/// [`spawn_blocking`].
+///
+/// Returns the duration to wait before trying again, or `None` if the request was
+/// recorded.
+fn reserve(path: &Path, now: SystemTime) -> Result<Option<Duration>, String> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    file.lock().map_err(|e| e.to_string())?;
+    let result = reserve_locked(&mut file, now);
+    let _ = file.unlock();
+    result
+}
+
+fn reserve_locked(file: &mut File, now: SystemTime) -> Result<Option<Duration>, String> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| e.to_string())?;
+    let now = get_millis(now);
+    let mut requests = parse_requests(&contents);
+    if let Some(remaining) = get_remaining(&requests, now) {
+        return Ok(Some(remaining));
+    }
+    requests.retain(|request| is_in_window(*request, now));
+    requests.push(now);
+    let contents = requests.iter().fold(String::new(), |mut output, request| {
+        let _ = writeln!(output, "{request}");
+        output
+    });
+    file.set_len(0).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    file.write_all(contents.as_bytes())
+        .map_err(|e| e.to_string())?;
+    Ok(None)
+}
+
+/// Parse the milliseconds since the Unix epoch of each request of a rate limit file.
+#[must_use]
+pub fn parse_requests(contents: &str) -> Vec<u64> {
+    contents
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
+/// Get the duration until another request is allowed at `now`.
+///
+/// Returns `None` if fewer than [`REQUESTS_PER_WINDOW`] requests were sent in the
+/// [`REQUEST_WINDOW`] before `now`.
+#[must_use]
+pub fn get_remaining(requests: &[u64], now: u64) -> Option<Duration> {
+    let mut recent: Vec<u64> = requests
+        .iter()
+        .copied()
+        .filter(|request| is_in_window(*request, now))
+        .collect();
+    if recent.len() < REQUESTS_PER_WINDOW {
+        return None;
+    }
+    recent.sort_unstable();
+    // The request that has to leave the window before another is allowed
+    let index = recent.len() - REQUESTS_PER_WINDOW;
+    let oldest = recent.get(index).copied().unwrap_or(now);
+    let window = get_window_millis();
+    Some(Duration::from_millis(
+        (oldest + window).saturating_sub(now).max(1),
+    ))
+}
+
+fn is_in_window(request: u64, now: u64) -> bool {
+    request + get_window_millis() > now && request <= now
+}
+
+fn get_window_millis() -> u64 {
+    u64::try_from(REQUEST_WINDOW.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn get_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| u64::try_from(duration.as_millis()).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Value of a request list that allows anything.
+const ANY: &str = "Any";
+
+/// Response of the requests search endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestSearchResponse {
+    #[serde(default)]
+    pub results: Vec<RequestResult>,
+}
+
+/// A request of a [`RequestSearchResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestResult {
+    pub request_id: u32,
+    pub title: String,
+    #[serde(default)]
+    pub year: u16,
+    /// Artists of the request nested by importance.
+    #[serde(default)]
+    pub artists: Value,
+    /// Bitrates allowed by the request as either a `|` separated string or an array.
+    #[serde(default)]
+    pub bitrate_list: Value,
+    /// Formats allowed by the request as either a `|` separated string or an array.
+    #[serde(default)]
+    pub format_list: Value,
+    /// Media allowed by the request as either a `|` separated string or an array.
+    #[serde(default)]
+    pub media_list: Value,
+    /// Bounty in bytes.
+    #[serde(default)]
+    pub bounty: u64,
+    #[serde(default)]
+    pub is_filled: bool,
+    #[serde(default)]
+    pub catalogue_number: String,
+}
+
+/// Response of the request fill endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestFillResponse {
+    #[serde(default)]
+    pub request_id: u32,
+    #[serde(default)]
+    pub torrent_id: u32,
+    /// Bounty received in bytes.
+    #[serde(default)]
+    pub bounty: u64,
+}
+
+impl RequestResult {
+    /// Get the name of every artist of the request.
+    #[must_use]
+    pub fn get_artist_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        collect_names(&self.artists, &mut names);
+        names
+    }
+
+    /// Check if the request allows a bitrate such as `Lossless` or `V0 (VBR)`.
+    #[must_use]
+    pub fn allows_bitrate(&self, bitrate: &str) -> bool {
+        allows(&self.bitrate_list, bitrate)
+    }
+
+    /// Check if the request allows a format such as `FLAC` or `MP3`.
+    #[must_use]
+    pub fn allows_format(&self, format: &str) -> bool {
+        allows(&self.format_list, format)
+    }
+
+    /// Check if the request allows a media such as `CD` or `WEB`.
+    #[must_use]
+    pub fn allows_media(&self, media: &str) -> bool {
+        allows(&self.media_list, media)
+    }
+}
+
+fn collect_names(value: &Value, names: &mut Vec<String>) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                collect_names(item, names);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(Value::String(name)) = map.get("name") {
+                names.push(name.clone());
+            } else {
+                for item in map.values() {
+                    collect_names(item, names);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check if a request list allows a value.
+///
+/// An empty list, or a list containing `Any`, allows every value.
+fn allows(list: &Value, value: &str) -> bool {
+    let items: Vec<&str> = match list {
+        Value::String(list) => list
+            .split(['|', ','])
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect(),
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    items.is_empty()
+        || items
+            .iter()
+            .any(|item| item.eq_ignore_ascii_case(ANY) || item.eq_ignore_ascii_case(value))
+}
@@ -0,0 +1,191 @@
+use std::time::Instant;
+
+use di::{injectable, Ref};
+use log::trace;
+use reqwest::header::{COOKIE, USER_AGENT};
+use reqwest::redirect::{Action, Attempt, Policy};
+use reqwest::{Client, RequestBuilder};
+use rogue_logging::Error;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::errors::error;
+use crate::indexer::RateLimiter;
+use crate::options::SharedOptions;
+use crate::source::API_DOMAIN;
+use crate::summary::{TimingPhase, Timings};
+
+/// Name of the cookie the indexer website stores the session in.
+const SESSION_COOKIE_NAME: &str = "session";
+
+/// Page of the indexer API which must be requested with the API key instead.
+const API_PAGE: &str = "ajax.php";
+
+/// Page the indexer website redirects to if the session is invalid.
+const LOGIN_PAGE: &str = "login.php";
+
+/// Scheme the session cookie is restricted to so it's never sent in clear text.
+const SECURE_SCHEME: &str = "https";
+
+/// Maximum number of redirects to follow.
+const MAX_REDIRECTS: usize = 10;
+
+/// A client for the pages of the indexer website that aren't available from the API.
+///
+/// Requests are authenticated by the `session_cookie` rather than the API key, so
+/// [`IndexerClient`] should be used for anything the API does cover. Requests share the
+/// [`RateLimiter`] of the API.
+///
+/// The cookie is only sent over https, so requests fail if `indexer_url` or a redirect
+/// is not https.
+#[injectable]
+pub struct SessionClient {
+    shared_options: Ref<SharedOptions>,
+    timings: Ref<Timings>,
+    rate_limiter: Ref<RateLimiter>,
+}
+
+impl SessionClient {
+    /// Is `session_cookie` set?
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.shared_options.session_cookie.is_some()
+    }
+
+    /// Get the HTML of a page.
+    ///
+    /// `path` is relative to `indexer_url` such as `reportsv2.php`.
+    pub async fn get_page(
+        &self,
+        action: &str,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<String, Error> {
+        let url = self.get_url(action, path)?;
+        trace!("Sending session request GET {url}");
+        let client = Client::builder()
+            .redirect(Policy::custom(follow_secure_redirect))
+            .build()
+            .map_err(|e| error(action, e.to_string()))?;
+        let request = client.get(&url).query(query);
+        self.send(action, request).await
+    }
+
+    fn get_url(&self, action: &str, path: &str) -> Result<String, Error> {
+        let indexer_url = self
+            .shared_options
+            .indexer_url
+            .clone()
+            .expect("indexer_url should be set");
+        get_session_url(&indexer_url, path).map_err(|message| error(action, message))
+    }
+
+    async fn send(&self, action: &str, request: RequestBuilder) -> Result<String, Error> {
+        let Some(cookie) = &self.shared_options.session_cookie else {
+            return Err(error(
+                action,
+                "session_cookie must be set for features that aren't available from the API"
+                    .to_owned(),
+            ));
+        };
+        self.rate_limiter.wait().await;
+        let start = Instant::now();
+        let response = request
+            .header(
+                USER_AGENT,
+                format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+            )
+            .header(COOKIE, get_cookie_header(cookie))
+            .send()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        let status = response.status();
+        let is_login = is_login_redirect(response.url().path());
+        let html = response
+            .text()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        if is_login {
+            return Err(Error {
+                action: action.to_owned(),
+                message: "Redirected to login so session_cookie is invalid or has expired"
+                    .to_owned(),
+                domain: Some(API_DOMAIN.to_owned()),
+                status_code: Some(status.as_u16()),
+                ..Error::default()
+            });
+        }
+        if !status.is_success() {
+            return Err(Error {
+                action: action.to_owned(),
+                message: format!("Unexpected status: {status}"),
+                domain: Some(API_DOMAIN.to_owned()),
+                status_code: Some(status.as_u16()),
+                ..Error::default()
+            });
+        }
+        Ok(html)
+    }
+}
+
+/// Get the URL of a page of the indexer website.
+///
+/// Returns a description of the problem if `indexer_url` is not https or the page is part
+/// of the API, as the session cookie is never sent to either.
+pub fn get_session_url(indexer_url: &str, path: &str) -> Result<String, String> {
+    if !is_secure(indexer_url) {
+        return Err(format!(
+            "session_cookie is only sent over https but indexer_url is {indexer_url}"
+        ));
+    }
+    let path = path.trim_start_matches('/');
+    let page = path.split(['?', '#']).next().unwrap_or_default();
+    if page.is_empty() {
+        return Err("Path of the page must not be empty".to_owned());
+    }
+    if page.eq_ignore_ascii_case(API_PAGE) {
+        return Err(format!("{API_PAGE} must be requested with the API key"));
+    }
+    Ok(format!("{}/{path}", indexer_url.trim_end_matches('/')))
+}
+
+/// Get the value of the `Cookie` header from `session_cookie`.
+///
+/// A value that already names its cookies is sent as is, otherwise it's the value of the
+/// `session` cookie.
+#[must_use]
+pub fn get_cookie_header(cookie: &str) -> String {
+    let cookie = cookie.trim();
+    if cookie.contains('=') {
+        cookie.to_owned()
+    } else {
+        format!("{SESSION_COOKIE_NAME}={cookie}")
+    }
+}
+
+/// Was the request redirected to the login page?
+#[must_use]
+pub fn is_login_redirect(path: &str) -> bool {
+    path.trim_end_matches('/').ends_with(LOGIN_PAGE)
+}
+
+/// Follow a redirect only if it's to an https URL.
+fn follow_secure_redirect(attempt: Attempt<'_>) -> Action {
+    if attempt.previous().len() >= MAX_REDIRECTS {
+        attempt.error("Too many redirects")
+    } else if attempt.url().scheme() == SECURE_SCHEME {
+        attempt.follow()
+    } else {
+        let message = format!(
+            "Refused to send session_cookie to a redirect that is not https: {}",
+            attempt.url()
+        );
+        attempt.error(message)
+    }
+}
+
+/// Is the URL https?
+fn is_secure(url: &str) -> bool {
+    url.split_once("://")
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(SECURE_SCHEME))
+}
@@ -0,0 +1,56 @@
+use rogue_logging::Error;
+
+use crate::indexer::{parse_response, ArtistResponse};
+
+const ARTIST_JSON: &str = r#"{
+  "status": "success",
+  "response": {
+    "id": 1460,
+    "name": "Logistics",
+    "torrentgroup": [
+      {
+        "groupId": 72189681,
+        "groupName": "Fear Not",
+        "groupYear": 2012,
+        "torrent": [
+          { "id": 29991962, "media": "CD", "format": "FLAC", "encoding": "Lossless" },
+          { "id": 29991963, "media": "CD", "format": "MP3", "encoding": "320" }
+        ]
+      },
+      {
+        "groupId": 72189682,
+        "groupName": "Reality Checkpoint",
+        "groupYear": 2010,
+        "torrent": [
+          { "id": 29991970, "media": "WEB", "format": "FLAC", "encoding": "24bit Lossless" }
+        ]
+      }
+    ]
+  }
+}"#;
+
+#[test]
+fn artist_response_get_flac_torrent_ids() -> Result<(), Error> {
+    // Act
+    let artist: ArtistResponse = parse_response("get artist", 200, ARTIST_JSON)?;
+
+    // Assert
+    assert_eq!(artist.name, "Logistics");
+    assert_eq!(artist.get_flac_torrent_ids(), vec![29_991_962, 29_991_970]);
+    Ok(())
+}
+
+#[test]
+fn parse_response_failure() {
+    // Act
+    let result = parse_response::<ArtistResponse>(
+        "get artist",
+        400,
+        r#"{"status": "failure", "error": "bad id parameter"}"#,
+    );
+
+    // Assert
+    let error = result.expect_err("should fail");
+    assert_eq!(error.message, "bad id parameter");
+    assert_eq!(error.status_code, Some(400));
+}
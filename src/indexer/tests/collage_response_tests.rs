@@ -0,0 +1,75 @@
+use rogue_logging::Error;
+
+use crate::indexer::{parse_response, CollageResponse};
+
+const COLLAGE_JSON: &str = r#"{
+  "status": "success",
+  "response": {
+    "id": 6120,
+    "name": "Drum and Bass Essentials",
+    "torrentgroups": [
+      {
+        "id": 72189681,
+        "name": "Fear Not",
+        "torrents": [
+          { "torrentid": 1, "media": "CD", "format": "FLAC", "encoding": "Lossless", "seeders": 4 },
+          { "torrentid": 2, "media": "WEB", "format": "FLAC", "encoding": "24bit Lossless", "seeders": 12 },
+          { "torrentid": 3, "media": "CD", "format": "MP3", "encoding": "320", "seeders": 30 }
+        ]
+      },
+      {
+        "id": 72189682,
+        "name": "Reality Checkpoint",
+        "torrents": [
+          { "torrentid": 4, "media": "CD", "format": "MP3", "encoding": "V0 (VBR)", "seeders": 8 },
+          { "torrentid": 5, "media": "CD", "format": "MP3", "encoding": "320", "seeders": 8 }
+        ]
+      }
+    ]
+  }
+}"#;
+
+#[test]
+fn collage_group_get_flac_torrent_ids_best_first() -> Result<(), Error> {
+    // Act
+    let collage: CollageResponse = parse_response("get collage", 200, COLLAGE_JSON)?;
+
+    // Assert
+    let groups = &collage.torrent_groups;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        groups
+            .first()
+            .expect("should have group")
+            .get_flac_torrent_ids(),
+        vec![2, 1]
+    );
+    assert!(groups
+        .get(1)
+        .expect("should have group")
+        .get_flac_torrent_ids()
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn collage_group_get_missing_mp3_encodings() -> Result<(), Error> {
+    // Act
+    let collage: CollageResponse = parse_response("get collage", 200, COLLAGE_JSON)?;
+
+    // Assert
+    let groups = &collage.torrent_groups;
+    assert_eq!(
+        groups
+            .first()
+            .expect("should have group")
+            .get_missing_mp3_encodings(),
+        vec!["V0 (VBR)"]
+    );
+    assert!(groups
+        .get(1)
+        .expect("should have group")
+        .get_missing_mp3_encodings()
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod artist_response_tests;
+mod collage_response_tests;
+mod rate_limiter_tests;
+mod session_client_tests;
+mod url_helpers_tests;
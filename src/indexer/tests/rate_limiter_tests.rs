@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use crate::indexer::{get_remaining, parse_requests, REQUESTS_PER_WINDOW};
+
+#[test]
+fn rate_limiter_parse_requests() {
+    // Arrange
+    let contents = "1000\n2000\ninvalid\n\n3000\n";
+
+    // Act
+    let requests = parse_requests(contents);
+
+    // Assert
+    assert_eq!(requests, vec![1000, 2000, 3000]);
+}
+
+#[test]
+fn rate_limiter_allows_below_limit() {
+    // Arrange
+    let requests: Vec<u64> = (0..9).map(|x| 100_000 + x * 100).collect();
+
+    // Act
+    let remaining = get_remaining(&requests, 101_000);
+
+    // Assert
+    assert_eq!(remaining, None);
+}
+
+#[test]
+fn rate_limiter_waits_at_limit() {
+    // Arrange
+    let count = u64::try_from(REQUESTS_PER_WINDOW).expect("count should fit");
+    let requests: Vec<u64> = (0..count).map(|x| 100_000 + x * 100).collect();
+
+    // Act
+    let remaining = get_remaining(&requests, 101_000);
+
+    // Assert
+    assert_eq!(remaining, Some(Duration::from_secs(9)));
+}
+
+#[test]
+fn rate_limiter_ignores_expired_requests() {
+    // Arrange
+    let count = u64::try_from(REQUESTS_PER_WINDOW).expect("count should fit");
+    let mut requests: Vec<u64> = (0..count).map(|x| 80_000 + x * 100).collect();
+    requests.push(105_000);
+
+    // Act
+    let remaining = get_remaining(&requests, 110_000);
+
+    // Assert
+    assert_eq!(remaining, None);
+}
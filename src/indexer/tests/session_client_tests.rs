@@ -0,0 +1,53 @@
+use crate::indexer::*;
+
+#[test]
+fn get_session_url_page() {
+    // Act
+    let output = get_session_url("https://redacted.sh/", "/reportsv2.php?action=report");
+
+    // Assert
+    assert_eq!(
+        output,
+        Ok("https://redacted.sh/reportsv2.php?action=report".to_owned())
+    );
+}
+
+#[test]
+fn get_session_url_rejects_api() {
+    // Act
+    let api = get_session_url("https://redacted.sh", "ajax.php?action=index");
+    let empty = get_session_url("https://redacted.sh", "/");
+
+    // Assert
+    assert!(api.is_err());
+    assert!(empty.is_err());
+}
+
+#[test]
+fn get_session_url_rejects_http() {
+    // Act
+    let http = get_session_url("http://redacted.sh", "reportsv2.php");
+    let relative = get_session_url("redacted.sh", "reportsv2.php");
+
+    // Assert
+    assert!(http.is_err());
+    assert!(relative.is_err());
+}
+
+#[test]
+fn get_cookie_header_session() {
+    // Act
+    let value = get_cookie_header(" a1b2c3d4e5f6 ");
+    let header = get_cookie_header("session=a1b2c3d4e5f6; theme=dark");
+
+    // Assert
+    assert_eq!(value, "session=a1b2c3d4e5f6");
+    assert_eq!(header, "session=a1b2c3d4e5f6; theme=dark");
+}
+
+#[test]
+fn is_login_redirect_path() {
+    // Assert
+    assert!(is_login_redirect("/login.php"));
+    assert!(!is_login_redirect("/reportsv2.php"));
+}
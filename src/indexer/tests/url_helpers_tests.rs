@@ -0,0 +1,42 @@
+use crate::indexer::{get_artist_id, get_collage_id, get_linked_torrent_ids};
+
+#[test]
+fn get_artist_id_from_id_or_url() {
+    assert_eq!(get_artist_id("1460"), Some(1460));
+    assert_eq!(
+        get_artist_id("https://redacted.sh/artist.php?id=1460"),
+        Some(1460)
+    );
+    assert_eq!(get_artist_id("https://redacted.sh/artist.php"), None);
+    assert_eq!(get_artist_id("Logistics"), None);
+}
+
+#[test]
+fn get_collage_id_from_id_or_url() {
+    assert_eq!(get_collage_id("6120"), Some(6120));
+    assert_eq!(
+        get_collage_id("https://orpheus.network/collages.php?id=6120"),
+        Some(6120)
+    );
+    assert_eq!(
+        get_collage_id("https://redacted.sh/artist.php?id=6120"),
+        None
+    );
+}
+
+#[test]
+fn get_linked_torrent_ids_distinct_in_order() {
+    // Arrange
+    let html = r#"
+        <tr><td><a href="torrents.php?id=1460&amp;torrentid=4871992">Logistics</a></td></tr>
+        <tr><td><a href="torrents.php?action=download&amp;id=4871992">DL</a></td></tr>
+        <tr><td><a href="torrents.php?id=2101&amp;torrentid=512">Fabric</a></td></tr>
+        <tr><td><a href="torrents.php?torrentid=4871992#torrent4871992">Logistics</a></td></tr>
+    "#;
+
+    // Act
+    let ids = get_linked_torrent_ids(html);
+
+    // Assert
+    assert_eq!(ids, vec![4_871_992, 512]);
+}
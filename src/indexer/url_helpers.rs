@@ -0,0 +1,51 @@
+use regex::Regex;
+
+/// Get an artist id from either an id or an artist URL.
+///
+/// Examples: `1460`, `https://redacted.sh/artist.php?id=1460`
+#[must_use]
+pub fn get_artist_id(value: &str) -> Option<u32> {
+    get_id(value, r"/artist\.php\?id=(\d+)")
+}
+
+/// Get a collage id from either an id or a collage URL.
+///
+/// Examples: `6120`, `https://redacted.sh/collages.php?id=6120`
+#[must_use]
+pub fn get_collage_id(value: &str) -> Option<u32> {
+    get_id(value, r"/collages\.php\?id=(\d+)")
+}
+
+/// Get the distinct torrent ids linked from the HTML of a page in the order they appear.
+///
+/// Example: `<a href="torrents.php?id=1460&amp;torrentid=4871992">`
+#[must_use]
+pub fn get_linked_torrent_ids(html: &str) -> Vec<u32> {
+    let mut ids = Vec::new();
+    let pattern = Regex::new(r"torrentid=(\d+)").expect("Regex should compile");
+    for captures in pattern.captures_iter(html) {
+        let Some(id) = captures
+            .get(1)
+            .and_then(|id| id.as_str().parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+fn get_id(value: &str, pattern: &str) -> Option<u32> {
+    if let Ok(id) = value.trim().parse::<u32>() {
+        return Some(id);
+    }
+    Regex::new(pattern)
+        .expect("Regex should compile")
+        .captures(value)?
+        .get(1)?
+        .as_str()
+        .parse::<u32>()
+        .ok()
+}
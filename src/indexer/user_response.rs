@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Response of the index endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IndexResponse {
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub userstats: IndexUserStats,
+}
+
+/// Upload and download statistics of an [`IndexResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IndexUserStats {
+    /// Uploaded in bytes.
+    #[serde(default)]
+    pub uploaded: u64,
+    /// Downloaded in bytes.
+    #[serde(default)]
+    pub downloaded: u64,
+    /// Ratio the account must stay above.
+    #[serde(default)]
+    pub requiredratio: f64,
+}
+
+/// Response of the user endpoint of the indexer API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct UserResponse {
+    #[serde(default)]
+    pub personal: UserPersonal,
+}
+
+/// Personal details of a [`UserResponse`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct UserPersonal {
+    /// Does the account have an active warning?
+    #[serde(default)]
+    pub warned: bool,
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
-use crate::errors::task_error;
+use crate::errors::{error, task_error};
+use crate::hosting::Shutdown;
 use crate::jobs::*;
 use di::{injectable, Ref, RefMut};
 use rogue_logging::Error;
@@ -9,15 +10,22 @@ use tokio::task::JoinSet;
 
 /// Execute a [Job] in parallel across a restricted number of threads.
 ///
-/// [Semaphore] is used to limit the number of commands that can be executed concurrently.
+/// [Semaphore] is used to limit the number of commands that can be executed concurrently,
+/// and [`JobServer`] to share the limit with other processes if `shared_cpus` is set.
 /// [`JoinSet`] is used to execute commands in parallel, and collate the results.
 /// [Publisher] is updated by an
 /// [observer design pattern](https://refactoring.guru/design-patterns/observer) when the status
 /// of a [Job] changes.
+///
+/// If [`Shutdown`] is requested the running jobs are aborted, which kills their
+/// external processes. The jobs are also tracked by [`Shutdown`] so they're aborted
+/// if the application is stopped immediately.
 pub struct JobRunner {
     pub semaphore: Arc<Semaphore>,
     pub set: RefMut<JoinSet<Result<(), Error>>>,
     pub publisher: Ref<Publisher>,
+    pub shutdown: Ref<Shutdown>,
+    pub job_server: Ref<JobServer>,
 }
 
 #[injectable]
@@ -27,11 +35,15 @@ impl JobRunner {
         semaphore: Arc<Semaphore>,
         set: RefMut<JoinSet<Result<(), Error>>>,
         publisher: Ref<Publisher>,
+        shutdown: Ref<Shutdown>,
+        job_server: Ref<JobServer>,
     ) -> Self {
         Self {
             semaphore,
             set,
             publisher,
+            shutdown,
+            job_server,
         }
     }
 
@@ -41,19 +53,22 @@ impl JobRunner {
             let id = job.get_id();
             let semaphore = self.semaphore.clone();
             let publisher = self.publisher.clone();
+            let job_server = self.job_server.clone();
             publisher.update(&id, Created);
             let mut set = self.set.write().expect("join set to be writeable");
-            set.spawn(async move {
+            let handle = set.spawn(async move {
                 publisher.update(&id, Queued);
                 let _permit = semaphore
                     .acquire()
                     .await
                     .expect("Semaphore should be available");
+                let _slot = job_server.acquire().await?;
                 publisher.update(&id, Started);
                 job.execute().await?;
                 publisher.update(&id, Completed);
                 Ok(())
             });
+            self.shutdown.track(handle);
         }
     }
 
@@ -61,15 +76,18 @@ impl JobRunner {
     pub fn add_without_publish(&self, jobs: Vec<Job>) {
         for job in jobs {
             let semaphore = self.semaphore.clone();
+            let job_server = self.job_server.clone();
             let mut set = self.set.write().expect("join set to be writeable");
-            set.spawn(async move {
+            let handle = set.spawn(async move {
                 let _permit = semaphore
                     .acquire()
                     .await
                     .expect("Semaphore should be available");
+                let _slot = job_server.acquire().await?;
                 job.execute().await?;
                 Ok(())
             });
+            self.shutdown.track(handle);
         }
     }
 
@@ -86,7 +104,17 @@ impl JobRunner {
             self.publisher.start("");
         }
         let mut set = self.set.write().expect("join set to be writeable");
-        while let Some(result) = set.join_next().await {
+        loop {
+            let result = tokio::select! {
+                result = set.join_next() => result,
+                () = self.shutdown.wait() => {
+                    set.shutdown().await;
+                    return Err(error("execute jobs", "Stopped by shutdown signal".to_owned()));
+                }
+            };
+            let Some(result) = result else {
+                break;
+            };
             let result = match result {
                 Ok(result) => result,
                 Err(e) => {
@@ -0,0 +1,97 @@
+use std::fs::{create_dir_all, File, OpenOptions, TryLockError};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::trace;
+use rogue_logging::Error;
+use tokio::time::sleep;
+
+use crate::errors::{io_error, path_error};
+use crate::options::{CacheOptions, RunnerOptions};
+
+/// Name of the directory in the cache that contains the slot files.
+const JOBS_DIR_NAME: &str = "jobs";
+
+/// Interval to check if a slot has been released by another process.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A slot of [`JobServer`] held by this process.
+///
+/// The slot is released when dropped, or by the OS if the process stops.
+#[derive(Debug)]
+pub struct JobSlot {
+    _file: File,
+}
+
+/// Share the `cpus` limit between every caesura process using the same cache directory.
+///
+/// Similar to the jobserver of `make`, each job must hold one of `cpus` slots. A slot is
+/// a file in the cache directory that is locked by the process running the job so a lock
+/// held by a process that stopped unexpectedly is released by the OS.
+///
+/// Only enabled if `shared_cpus` is set.
+pub struct JobServer {
+    dir: Option<PathBuf>,
+    slots: usize,
+}
+
+#[injectable]
+impl JobServer {
+    #[must_use]
+    pub fn new(runner_options: Ref<RunnerOptions>, cache_options: Ref<CacheOptions>) -> Self {
+        let dir = runner_options.shared_cpus.unwrap_or_default().then(|| {
+            cache_options
+                .cache
+                .clone()
+                .expect("cache should be set")
+                .join(JOBS_DIR_NAME)
+        });
+        Self {
+            dir,
+            slots: usize::from(runner_options.cpus.expect("cpus should be set")),
+        }
+    }
+
+    /// Wait until a slot is available.
+    ///
+    /// Returns `None` if `shared_cpus` is not set.
+    pub async fn acquire(&self) -> Result<Option<JobSlot>, Error> {
+        if self.dir.is_none() {
+            return Ok(None);
+        }
+        loop {
+            if let Some(slot) = self.try_acquire()? {
+                return Ok(Some(slot));
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Acquire a slot if one is available.
+    pub fn try_acquire(&self) -> Result<Option<JobSlot>, Error> {
+        let Some(dir) = &self.dir else {
+            return Ok(None);
+        };
+        create_dir_all(dir).map_err(|e| path_error(e, "create jobs directory", dir))?;
+        for index in 0..self.slots.max(1) {
+            let path = dir.join(format!("{index}.slot"));
+            let file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(|e| path_error(e, "open job slot", &path))?;
+            match file.try_lock() {
+                Ok(()) => {
+                    trace!("{} job slot {}", "Acquired".bold(), path.display());
+                    return Ok(Some(JobSlot { _file: file }));
+                }
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(e)) => return Err(io_error(e, "lock job slot")),
+            }
+        }
+        Ok(None)
+    }
+}
@@ -2,6 +2,7 @@ pub use enums::Status::*;
 pub use enums::*;
 pub use job::*;
 pub use job_runner::*;
+pub use job_server::*;
 pub use publisher::*;
 pub use subscriber::*;
 pub use subscriber_debug::*;
@@ -10,7 +11,10 @@ pub use subscriber_progress_bar::*;
 pub(crate) mod enums;
 pub(crate) mod job;
 pub(crate) mod job_runner;
+pub(crate) mod job_server;
 pub(crate) mod publisher;
 pub(crate) mod subscriber;
 pub(crate) mod subscriber_debug;
 pub(crate) mod subscriber_progress_bar;
+#[cfg(test)]
+mod tests;
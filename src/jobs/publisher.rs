@@ -1,5 +1,7 @@
 use crate::jobs::*;
 use crate::options::SharedOptions;
+use crate::progress::ProgressFormat;
+use crate::tui::TuiSubscriber;
 use di::{injectable, Ref};
 use rogue_logging::Verbosity;
 
@@ -18,9 +20,12 @@ impl Publisher {
         options: Ref<SharedOptions>,
         debug_subscriber: Ref<DebugSubscriber>,
         progress_bar_subscriber: Ref<ProgressBarSubscriber>,
+        tui_subscriber: Ref<TuiSubscriber>,
     ) -> Self {
         let subscriber: Ref<dyn Subscriber + Send + Sync> =
-            if options.verbosity.expect("verbosity should be set").as_num()
+            if options.progress == Some(ProgressFormat::Tui) {
+                tui_subscriber
+            } else if options.verbosity.expect("verbosity should be set").as_num()
                 >= Verbosity::Trace.as_num()
             {
                 debug_subscriber
@@ -1,4 +1,7 @@
 use crate::jobs::*;
+use crate::logging::LogFormat;
+use crate::options::SharedOptions;
+use crate::progress::ProgressFormat;
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
@@ -21,8 +24,17 @@ pub struct ProgressBarSubscriber {
 #[injectable]
 impl ProgressBarSubscriber {
     /// Create a new [`ProgressBarSubscriber`]
-    pub fn new(logger: Ref<Logger>, set: RefMut<JoinSet<Result<(), Error>>>) -> Self {
+    pub fn new(
+        logger: Ref<Logger>,
+        options: Ref<SharedOptions>,
+        set: RefMut<JoinSet<Result<(), Error>>>,
+    ) -> Self {
         let bar = create_progress_bar();
+        if options.log_format == Some(LogFormat::Json)
+            || options.progress == Some(ProgressFormat::Ndjson)
+        {
+            bar.set_draw_target(ProgressDrawTarget::hidden());
+        }
         Self { logger, set, bar }
     }
 }
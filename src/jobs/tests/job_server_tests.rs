@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use di::Ref;
+
+use crate::jobs::JobServer;
+use crate::options::{CacheOptions, RunnerOptions};
+use crate::testing::TempDirectory;
+
+fn get_job_server(cache: PathBuf, shared_cpus: bool) -> JobServer {
+    JobServer::new(
+        Ref::new(RunnerOptions {
+            cpus: Some(2),
+            shared_cpus: Some(shared_cpus),
+        }),
+        Ref::new(CacheOptions { cache: Some(cache) }),
+    )
+}
+
+#[test]
+fn job_server_limits_slots() {
+    // Arrange
+    let cache = TempDirectory::create("job_server_limits_slots");
+    let first_server = get_job_server(cache.clone(), true);
+    let second_server = get_job_server(cache, true);
+
+    // Act
+    let first = first_server.try_acquire().expect("should acquire");
+    let second = second_server.try_acquire().expect("should acquire");
+    let third = second_server.try_acquire().expect("should acquire");
+    let first_is_some = first.is_some();
+    drop(first);
+    let fourth = first_server.try_acquire().expect("should acquire");
+
+    // Assert
+    assert!(first_is_some);
+    assert!(second.is_some());
+    assert!(third.is_none());
+    assert!(fourth.is_some());
+}
+
+#[test]
+fn job_server_disabled() {
+    // Arrange
+    let server = get_job_server(TempDirectory::create("job_server_disabled"), false);
+
+    // Act
+    let slot = server.try_acquire().expect("should acquire");
+
+    // Assert
+    assert!(slot.is_none());
+}
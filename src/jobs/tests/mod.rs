@@ -0,0 +1 @@
+mod job_server_tests;
@@ -1,28 +1,49 @@
 mod batch;
+mod beets;
+mod clean;
 pub mod cli;
+mod confirm;
+mod cross_seed;
 #[allow(dead_code)]
 #[allow(unused_imports)]
 mod db;
 mod dependencies;
+mod doctor;
 mod errors;
 mod eyed3;
 mod formats;
 mod fs;
+mod history;
+mod hooks;
 pub mod hosting;
-mod imdl;
+mod indexer;
 mod jobs;
+mod logging;
+mod metrics;
+mod musicbrainz;
 mod naming;
+mod notify;
 mod options;
+mod progress;
 mod queue;
+mod report;
+mod serve;
 mod source;
 mod spectrogram;
+mod summary;
 #[cfg(test)]
 mod testing;
+mod torrent;
 mod transcode;
+mod tui;
+mod update;
 mod upload;
 mod verify;
+mod watch;
 
 #[allow(clippy::needless_raw_strings)]
+#[allow(clippy::needless_raw_string_hashes)]
+#[allow(clippy::doc_markdown)]
 mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
 }
@@ -0,0 +1,24 @@
+use log::{Log, Metadata, Record};
+
+/// A [`Log`] that forwards each log event to multiple loggers.
+pub struct CompositeLogger {
+    pub loggers: Vec<Box<dyn Log>>,
+}
+
+impl Log for CompositeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.loggers.iter().any(|logger| logger.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        for logger in &self.loggers {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        for logger in &self.loggers {
+            logger.flush();
+        }
+    }
+}
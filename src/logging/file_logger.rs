@@ -0,0 +1,91 @@
+use std::sync::{LazyLock, Mutex};
+
+use chrono::Local;
+use log::{Log, Metadata, Record};
+use regex::Regex;
+use rogue_logging::Verbosity;
+
+use crate::logging::{get_log_source, is_enabled, JsonLogger, LogFormat, RotatingFile};
+
+/// A [`Log`] that appends log events to a [`RotatingFile`].
+///
+/// Colors are removed so the file can be read without a terminal.
+pub struct FileLogger {
+    pub enabled_threshold: Verbosity,
+    pub package_name: String,
+    pub format: LogFormat,
+    file: Mutex<RotatingFile>,
+}
+
+impl FileLogger {
+    #[must_use]
+    pub fn new(
+        enabled_threshold: Verbosity,
+        package_name: String,
+        format: LogFormat,
+        file: RotatingFile,
+    ) -> Self {
+        Self {
+            enabled_threshold,
+            package_name,
+            format,
+            file: Mutex::new(file),
+        }
+    }
+
+    /// Format a [`Record`] as a line of the log file.
+    #[must_use]
+    pub fn format_log(&self, record: &Record) -> String {
+        match self.format {
+            LogFormat::Text => {
+                let time = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+                let level = record.level().as_str();
+                let message = remove_colors(&record.args().to_string());
+                match get_log_source() {
+                    Some(source) => format!("{time} {level:<5} [{source}] {message}"),
+                    None => format!("{time} {level:<5} {message}"),
+                }
+            }
+            LogFormat::Json => {
+                let mut log = JsonLogger::get_log(record);
+                log.message = remove_colors(&log.message);
+                serde_json::to_string(&log).unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        is_enabled(metadata, &self.package_name, self.enabled_threshold)
+    }
+
+    #[allow(clippy::print_stderr)]
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = self.format_log(record);
+        if let Ok(mut file) = self.file.lock() {
+            if let Err(error) = file.write_line(&line) {
+                eprintln!("{error}");
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// ANSI color codes
+static COLORS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("\x1b\\[[0-9;]*m").expect("Regex should compile"));
+
+/// Remove ANSI color codes.
+#[must_use]
+pub fn remove_colors(value: &str) -> String {
+    COLORS.replace_all(value, "").to_string()
+}
@@ -0,0 +1,31 @@
+use log::{Log, Metadata, Record};
+use rogue_logging::Verbosity;
+
+use crate::logging::LogFilter;
+
+/// A [`Log`] that only forwards the log events enabled by a [`LogFilter`].
+///
+/// The inner logger should be enabled at trace level so the filter decides.
+pub struct FilteredLogger {
+    pub inner: Box<dyn Log>,
+    pub package_name: String,
+    pub threshold: Verbosity,
+    pub filter: LogFilter,
+}
+
+impl Log for FilteredLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter
+            .is_enabled(metadata, &self.package_name, self.threshold)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{SecondsFormat, Utc};
+use log::{Log, Metadata, Record};
+use rogue_logging::Verbosity;
+use serde::Serialize;
+
+use crate::logging::{get_log_source, is_enabled};
+
+/// A [`Log`] that writes each log event as a JSON object on a single line.
+pub struct JsonLogger {
+    pub enabled_threshold: Verbosity,
+    pub package_name: String,
+}
+
+/// A log event as serialized by [`JsonLogger`]
+#[derive(Debug, Serialize)]
+pub struct JsonLog {
+    /// UTC date and time in RFC 3339 format with millisecond precision.
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// Id of the source being processed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl JsonLogger {
+    /// Create a [`JsonLog`] from a [`Record`].
+    #[must_use]
+    pub fn get_log(record: &Record) -> JsonLog {
+        JsonLog {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            level: record.level().as_str().to_lowercase(),
+            target: record.target().to_owned(),
+            message: record.args().to_string(),
+            source: get_log_source(),
+        }
+    }
+}
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        is_enabled(metadata, &self.package_name, self.enabled_threshold)
+    }
+
+    #[allow(clippy::print_stderr)]
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            if let Ok(json) = serde_json::to_string(&Self::get_log(record)) {
+                eprintln!("{json}");
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
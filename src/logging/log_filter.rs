@@ -0,0 +1,124 @@
+use clap::ValueEnum;
+use log::{Level, Metadata};
+use rogue_logging::Verbosity;
+
+/// Per-module log levels in the style of `env_logger`.
+///
+/// Directives are separated by a comma and each is either a `target=level`
+/// or a `level` that replaces the default verbosity.
+///
+/// Example: `caesura::transcode=trace,caesura::queue=warn`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LogFilter {
+    /// Verbosity of targets that don't match a directive.
+    pub default: Option<Verbosity>,
+    /// Verbosity of each target prefix.
+    pub directives: Vec<(String, Verbosity)>,
+}
+
+impl LogFilter {
+    /// Parse a [`LogFilter`].
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut filter = LogFilter::default();
+        for directive in value.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let target = target.trim();
+                    if target.is_empty() {
+                        return Err(format!("Missing target: {directive}"));
+                    }
+                    filter
+                        .directives
+                        .push((target.to_owned(), parse_verbosity(level)?));
+                }
+                None => filter.default = Some(parse_verbosity(directive)?),
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Get the verbosity of a target.
+    ///
+    /// The directive with the longest matching target takes precedence.
+    #[must_use]
+    pub fn get_threshold(&self, target: &str, default: Verbosity) -> Verbosity {
+        self.directives
+            .iter()
+            .filter(|(prefix, _)| is_module_of(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or_else(
+                || self.default.unwrap_or(default),
+                |(_, verbosity)| *verbosity,
+            )
+    }
+
+    /// Get the highest verbosity of any target.
+    #[must_use]
+    pub fn get_max(&self, default: Verbosity) -> Verbosity {
+        self.directives
+            .iter()
+            .map(|(_, verbosity)| *verbosity)
+            .chain([self.default.unwrap_or(default)])
+            .max_by_key(|verbosity| verbosity.as_num())
+            .unwrap_or(default)
+    }
+
+    /// Is a log event from the package enabled?
+    #[must_use]
+    pub fn is_enabled(&self, metadata: &Metadata, package_name: &str, default: Verbosity) -> bool {
+        let target = metadata.target();
+        if !target.starts_with(package_name) && !target.starts_with("rogue_logging") {
+            return false;
+        }
+        let threshold = self.get_threshold(target, default);
+        get_verbosity(metadata.level()).as_num() <= threshold.as_num()
+    }
+}
+
+fn parse_verbosity(value: &str) -> Result<Verbosity, String> {
+    Verbosity::from_str(value.trim(), true).map_err(|_| format!("Invalid level: {value}"))
+}
+
+/// Is the target the module or a descendant of the module?
+fn is_module_of(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Get the [`Verbosity`] of a [`Level`].
+#[must_use]
+pub fn get_verbosity(level: Level) -> Verbosity {
+    match level {
+        Level::Error => Verbosity::Error,
+        Level::Warn => Verbosity::Warn,
+        Level::Info => Verbosity::Info,
+        Level::Debug => Verbosity::Debug,
+        Level::Trace => Verbosity::Trace,
+    }
+}
+
+/// Is a log event from the package enabled at the threshold?
+#[must_use]
+pub fn is_enabled(metadata: &Metadata, package_name: &str, threshold: Verbosity) -> bool {
+    LogFilter::default().is_enabled(metadata, package_name, threshold)
+}
+
+/// Adjust a [`Verbosity`] by the number of `-v` and `-q` flags.
+///
+/// Each `-v` increases the verbosity by one level and each `-q` decreases it by one level.
+#[must_use]
+pub fn adjust_verbosity(verbosity: Verbosity, verbose: u8, quiet: u8) -> Verbosity {
+    let levels = [
+        Verbosity::Silent,
+        Verbosity::Error,
+        Verbosity::Warn,
+        Verbosity::Info,
+        Verbosity::Debug,
+        Verbosity::Trace,
+    ];
+    let index = (verbosity.as_num() + usize::from(verbose))
+        .saturating_sub(usize::from(quiet))
+        .min(levels.len() - 1);
+    levels.get(index).copied().unwrap_or(verbosity)
+}
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Format of log events written to the console.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Colored text intended to be read by a person.
+    #[default]
+    Text,
+    /// A JSON object per line intended to be ingested by a log aggregator.
+    ///
+    /// Each object has a `timestamp`, `level`, `target`, `message` and, when a
+    /// source is being processed, the `source` id.
+    Json,
+}
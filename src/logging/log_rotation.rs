@@ -0,0 +1,15 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// When the log file is rotated.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Never rotate the log file.
+    Never,
+    /// Rotate the log file on the first log event of each day.
+    Daily,
+    /// Rotate the log file once it exceeds `log_file_max_size`.
+    #[default]
+    Size,
+}
@@ -0,0 +1,17 @@
+use std::sync::Mutex;
+
+/// Id of the source currently being processed.
+static LOG_SOURCE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Set the id of the source currently being processed so it's included in log events.
+pub fn set_log_source(source: Option<String>) {
+    if let Ok(mut log_source) = LOG_SOURCE.lock() {
+        *log_source = source;
+    }
+}
+
+/// Get the id of the source currently being processed.
+#[must_use]
+pub fn get_log_source() -> Option<String> {
+    LOG_SOURCE.lock().ok().and_then(|x| x.clone())
+}
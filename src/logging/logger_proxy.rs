@@ -0,0 +1,184 @@
+use std::env::var_os;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use colored::control::SHOULD_COLORIZE;
+use di::Ref;
+use log::{set_max_level, LevelFilter, Log, Metadata, Record};
+use rogue_logging::{Logger, TimeFormat, Verbosity};
+
+use crate::built_info::PKG_NAME;
+use crate::logging::{
+    CompositeLogger, FileLogger, FilteredLogger, JsonLogger, LogFormat, RotatingFile,
+};
+use crate::options::SharedOptions;
+use crate::progress::ProgressFormat;
+use crate::tui::TuiLogger;
+
+/// Logger that log events are forwarded to.
+static INNER: RwLock<Option<Box<dyn Log>>> = RwLock::new(None);
+
+/// Global [`Log`] that forwards to the logger set by [`init_logger`].
+///
+/// The `log` crate only allows a logger to be set once, so forwarding allows
+/// the logger used before the options are read to be replaced once they are.
+struct LoggerProxy;
+
+static PROXY: LoggerProxy = LoggerProxy;
+
+impl Log for LoggerProxy {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        INNER
+            .read()
+            .ok()
+            .and_then(|inner| inner.as_ref().map(|logger| logger.enabled(metadata)))
+            .unwrap_or(false)
+    }
+
+    fn log(&self, record: &Record) {
+        if let Ok(inner) = INNER.read() {
+            if let Some(logger) = inner.as_ref() {
+                logger.log(record);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(inner) = INNER.read() {
+            if let Some(logger) = inner.as_ref() {
+                logger.flush();
+            }
+        }
+    }
+}
+
+fn set_logger(logger: Box<dyn Log>, filter: LevelFilter) {
+    if let Ok(mut inner) = INNER.write() {
+        *inner = Some(logger);
+    }
+    // An error indicates the proxy is already set
+    let _ = log::set_logger(&PROXY);
+    set_max_level(filter);
+}
+
+/// Initialize logging with the [`SharedOptions`].
+///
+/// Logs are written to the console in the [`LogFormat`] and, if `log_file` is set,
+/// appended to the log file at its own verbosity.
+///
+/// The `log_filter` applies to both.
+pub fn init_logger(logger: Ref<Logger>, options: &SharedOptions) {
+    let format = options.log_format.expect("log_format should be set");
+    let colors = options.no_color != Some(true);
+    let filter = options.get_log_filter().unwrap_or_default();
+    let package_name = logger.package_name.clone();
+    let mut max = filter.get_max(logger.enabled_threshold);
+    let mut console = get_console_logger(&logger, format, colors);
+    if options.progress == Some(ProgressFormat::Tui) {
+        console = Box::new(TuiLogger { inner: console });
+    }
+    let mut loggers: Vec<Box<dyn Log>> = vec![Box::new(FilteredLogger {
+        inner: console,
+        package_name: package_name.clone(),
+        threshold: logger.enabled_threshold,
+        filter: filter.clone(),
+    })];
+    let mut file_error = None;
+    if let Some(path) = &options.log_file {
+        let verbosity = options
+            .log_file_verbosity
+            .expect("log_file_verbosity should be set");
+        let file = RotatingFile::open(
+            path,
+            options
+                .log_file_rotation
+                .expect("log_file_rotation should be set"),
+            options
+                .log_file_max_size
+                .expect("log_file_max_size should be set"),
+            options.log_file_keep.expect("log_file_keep should be set"),
+        );
+        match file {
+            Ok(file) => {
+                let inner = FileLogger::new(Verbosity::Trace, package_name.clone(), format, file);
+                loggers.push(Box::new(FilteredLogger {
+                    inner: Box::new(inner),
+                    package_name,
+                    threshold: verbosity,
+                    filter: filter.clone(),
+                }));
+                let file_max = filter.get_max(verbosity);
+                if file_max.as_num() > max.as_num() {
+                    max = file_max;
+                }
+            }
+            Err(error) => file_error = Some(error),
+        }
+    }
+    set_logger(Box::new(CompositeLogger { loggers }), get_level_filter(max));
+    if let Some(error) = file_error {
+        error.log();
+    }
+}
+
+/// Get a logger for the console that is enabled at trace level.
+fn get_console_logger(logger: &Logger, format: LogFormat, colors: bool) -> Box<dyn Log> {
+    SHOULD_COLORIZE.set_override(colors && format == LogFormat::Text);
+    match format {
+        LogFormat::Text => Box::new(Logger {
+            enabled_threshold: Verbosity::Trace,
+            time_format: logger.time_format,
+            start: logger.start,
+            package_name: logger.package_name.clone(),
+        }),
+        LogFormat::Json => Box::new(JsonLogger {
+            enabled_threshold: Verbosity::Trace,
+            package_name: logger.package_name.clone(),
+        }),
+    }
+}
+
+/// Is the `NO_COLOR` environment variable set?
+///
+/// See [no-color.org](https://no-color.org/)
+#[must_use]
+pub fn is_no_color_set() -> bool {
+    var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Initialize logging at trace level so logs aren't lost before the options are read.
+///
+/// Does nothing if logging is already initialized.
+pub fn force_init_logger() {
+    let is_initialized = INNER.read().map_or(true, |inner| inner.is_some());
+    if !is_initialized {
+        init_trace_logger(LogFormat::Text);
+    }
+}
+
+/// Initialize logging at trace level in the [`LogFormat`].
+///
+/// This allows the [`LogFormat`] to be applied before the config file is read.
+pub fn init_trace_logger(format: LogFormat) {
+    let logger = Logger {
+        enabled_threshold: Verbosity::Trace,
+        time_format: TimeFormat::Local,
+        start: SystemTime::now(),
+        package_name: PKG_NAME.to_owned(),
+    };
+    set_logger(
+        get_console_logger(&logger, format, !is_no_color_set()),
+        LevelFilter::Trace,
+    );
+}
+
+fn get_level_filter(verbosity: Verbosity) -> LevelFilter {
+    match verbosity {
+        Verbosity::Silent => LevelFilter::Off,
+        Verbosity::Error => LevelFilter::Error,
+        Verbosity::Warn => LevelFilter::Warn,
+        Verbosity::Info => LevelFilter::Info,
+        Verbosity::Debug => LevelFilter::Debug,
+        Verbosity::Trace => LevelFilter::Trace,
+    }
+}
@@ -0,0 +1,23 @@
+pub use composite_logger::*;
+pub use file_logger::*;
+pub use filtered_logger::*;
+pub use json_logger::*;
+pub use log_filter::*;
+pub use log_format::*;
+pub use log_rotation::*;
+pub use log_source::*;
+pub use logger_proxy::*;
+pub use rotating_file::*;
+
+pub(crate) mod composite_logger;
+pub(crate) mod file_logger;
+pub(crate) mod filtered_logger;
+pub(crate) mod json_logger;
+pub(crate) mod log_filter;
+pub(crate) mod log_format;
+pub(crate) mod log_rotation;
+pub(crate) mod log_source;
+pub(crate) mod logger_proxy;
+pub(crate) mod rotating_file;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,123 @@
+use std::fs::{create_dir_all, remove_file, rename, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate};
+use rogue_logging::Error;
+
+use crate::errors::io_error;
+use crate::logging::LogRotation;
+
+/// A file that is appended to and rotated by date or size.
+///
+/// Rotated files are numbered so `caesura.log` is renamed to `caesura.log.1`,
+/// `caesura.log.1` to `caesura.log.2` and so on until `keep` files are retained.
+pub struct RotatingFile {
+    path: PathBuf,
+    rotation: LogRotation,
+    max_size: u64,
+    keep: usize,
+    file: File,
+    size: u64,
+    date: NaiveDate,
+}
+
+impl RotatingFile {
+    /// Open the file for appending, creating it and its parent directories if required.
+    pub fn open(
+        path: &Path,
+        rotation: LogRotation,
+        max_size: u64,
+        keep: usize,
+    ) -> Result<Self, Error> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                create_dir_all(parent).map_err(|e| io_error(e, "create log directory"))?;
+            }
+        }
+        let file = open_file(path)?;
+        let metadata = file
+            .metadata()
+            .map_err(|e| io_error(e, "read log file metadata"))?;
+        let date = metadata
+            .modified()
+            .map_or_else(|_| Local::now(), DateTime::<Local>::from)
+            .date_naive();
+        Ok(Self {
+            path: path.to_path_buf(),
+            rotation,
+            max_size,
+            keep,
+            file,
+            size: metadata.len(),
+            date,
+        })
+    }
+
+    /// Append a line to the file, rotating it first if required.
+    pub fn write_line(&mut self, line: &str) -> Result<(), Error> {
+        let length = u64::try_from(line.len()).unwrap_or(u64::MAX) + 1;
+        if self.is_rotation_due(length, Local::now().date_naive()) {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{line}").map_err(|e| io_error(e, "write log file"))?;
+        self.size += length;
+        Ok(())
+    }
+
+    /// Flush the file.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.file.flush().map_err(|e| io_error(e, "flush log file"))
+    }
+
+    fn is_rotation_due(&self, length: u64, today: NaiveDate) -> bool {
+        match self.rotation {
+            LogRotation::Never => false,
+            LogRotation::Daily => today != self.date,
+            LogRotation::Size => self.size > 0 && self.size + length > self.max_size,
+        }
+    }
+
+    fn rotate(&mut self) -> Result<(), Error> {
+        self.file
+            .flush()
+            .map_err(|e| io_error(e, "flush log file"))?;
+        if self.keep == 0 {
+            remove_file(&self.path).map_err(|e| io_error(e, "remove log file"))?;
+        } else {
+            let oldest = get_rotated_path(&self.path, self.keep);
+            if oldest.exists() {
+                remove_file(&oldest).map_err(|e| io_error(e, "remove rotated log file"))?;
+            }
+            for index in (1..self.keep).rev() {
+                let from = get_rotated_path(&self.path, index);
+                if from.exists() {
+                    rename(&from, get_rotated_path(&self.path, index + 1))
+                        .map_err(|e| io_error(e, "rename rotated log file"))?;
+                }
+            }
+            rename(&self.path, get_rotated_path(&self.path, 1))
+                .map_err(|e| io_error(e, "rename log file"))?;
+        }
+        self.file = open_file(&self.path)?;
+        self.size = 0;
+        self.date = Local::now().date_naive();
+        Ok(())
+    }
+}
+
+fn open_file(path: &Path) -> Result<File, Error> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io_error(e, "open log file"))
+}
+
+/// Get the path of a rotated log file.
+#[must_use]
+pub fn get_rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{index}"));
+    PathBuf::from(path)
+}
@@ -0,0 +1,56 @@
+use log::{Level, Log, MetadataBuilder, Record};
+use rogue_logging::Verbosity;
+
+use crate::logging::*;
+
+#[test]
+fn json_logger_get_log() {
+    // Arrange
+    set_log_source(Some("4871992".to_owned()));
+    let mut builder = Record::builder();
+    builder.level(Level::Info).target("caesura::transcode");
+
+    // Act
+    let log = JsonLogger::get_log(&builder.args(format_args!("Transcoded flac")).build());
+    set_log_source(None);
+    let json = serde_json::to_string(&log).expect("should serialize");
+
+    // Assert
+    assert_eq!(log.level, "info");
+    assert_eq!(log.target, "caesura::transcode");
+    assert_eq!(log.message, "Transcoded flac");
+    assert_eq!(log.source, Some("4871992".to_owned()));
+    assert!(log.timestamp.ends_with('Z'));
+    assert!(json.starts_with("{\"timestamp\":"));
+}
+
+#[test]
+fn json_logger_enabled() {
+    // Arrange
+    let logger = JsonLogger {
+        enabled_threshold: Verbosity::Info,
+        package_name: "caesura".to_owned(),
+    };
+    let info = MetadataBuilder::new()
+        .level(Level::Info)
+        .target("caesura::batch")
+        .build();
+    let debug = MetadataBuilder::new()
+        .level(Level::Debug)
+        .target("caesura::batch")
+        .build();
+    let other = MetadataBuilder::new()
+        .level(Level::Info)
+        .target("reqwest::connect")
+        .build();
+
+    // Act
+    let results = (
+        logger.enabled(&info),
+        logger.enabled(&debug),
+        logger.enabled(&other),
+    );
+
+    // Assert
+    assert_eq!(results, (true, false, false));
+}
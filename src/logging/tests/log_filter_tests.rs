@@ -0,0 +1,67 @@
+use rogue_logging::Verbosity;
+
+use crate::logging::*;
+
+#[test]
+fn log_filter_parse() {
+    // Act
+    let filter = LogFilter::parse("warn, caesura::transcode=trace,caesura::upload=debug")
+        .expect("should parse");
+
+    // Assert
+    assert_eq!(filter.default, Some(Verbosity::Warn));
+    assert_eq!(
+        filter.directives,
+        vec![
+            ("caesura::transcode".to_owned(), Verbosity::Trace),
+            ("caesura::upload".to_owned(), Verbosity::Debug),
+        ]
+    );
+}
+
+#[test]
+fn log_filter_parse_invalid() {
+    // Act
+    let invalid_level = LogFilter::parse("caesura::transcode=loud");
+    let missing_target = LogFilter::parse("=debug");
+
+    // Assert
+    assert!(invalid_level.is_err());
+    assert!(missing_target.is_err());
+}
+
+#[test]
+fn log_filter_get_threshold() {
+    // Arrange
+    let filter =
+        LogFilter::parse("caesura::transcode=warn,caesura::transcode::transcode_job=trace")
+            .expect("should parse");
+
+    // Act
+    let job = filter.get_threshold("caesura::transcode::transcode_job", Verbosity::Info);
+    let command = filter.get_threshold("caesura::transcode::transcode_command", Verbosity::Info);
+    let prefix = filter.get_threshold("caesura::transcoder", Verbosity::Info);
+    let other = filter.get_threshold("caesura::upload", Verbosity::Info);
+
+    // Assert
+    assert_eq!(job, Verbosity::Trace);
+    assert_eq!(command, Verbosity::Warn);
+    assert_eq!(prefix, Verbosity::Info);
+    assert_eq!(other, Verbosity::Info);
+    assert_eq!(filter.get_max(Verbosity::Info), Verbosity::Trace);
+}
+
+#[test]
+fn adjust_verbosity_by_flags() {
+    // Act
+    let debug = adjust_verbosity(Verbosity::Info, 1, 0);
+    let trace = adjust_verbosity(Verbosity::Info, 5, 0);
+    let error = adjust_verbosity(Verbosity::Info, 0, 2);
+    let silent = adjust_verbosity(Verbosity::Info, 0, 9);
+
+    // Assert
+    assert_eq!(debug, Verbosity::Debug);
+    assert_eq!(trace, Verbosity::Trace);
+    assert_eq!(error, Verbosity::Error);
+    assert_eq!(silent, Verbosity::Silent);
+}
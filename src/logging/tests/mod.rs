@@ -0,0 +1,3 @@
+mod json_logger_tests;
+mod log_filter_tests;
+mod rotating_file_tests;
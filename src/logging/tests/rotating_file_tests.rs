@@ -0,0 +1,67 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use crate::logging::*;
+use crate::testing::TempDirectory;
+
+#[test]
+fn rotating_file_rotates_by_size() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let path = dir.join("size.log");
+    let mut file = RotatingFile::open(&path, LogRotation::Size, 10, 2).expect("should open");
+
+    // Act
+    for line in ["first", "second", "third", "fourth"] {
+        file.write_line(line).expect("should write");
+    }
+
+    // Assert
+    assert_eq!(read_to_string(&path).expect("should read"), "fourth\n");
+    assert_eq!(
+        read_to_string(get_rotated_path(&path, 1)).expect("should read"),
+        "third\n"
+    );
+    assert_eq!(
+        read_to_string(get_rotated_path(&path, 2)).expect("should read"),
+        "second\n"
+    );
+    assert!(!get_rotated_path(&path, 3).exists());
+}
+
+#[test]
+fn rotating_file_never_rotates() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let path = dir.join("never.log");
+    let mut file = RotatingFile::open(&path, LogRotation::Never, 1, 2).expect("should open");
+
+    // Act
+    file.write_line("first").expect("should write");
+    file.write_line("second").expect("should write");
+
+    // Assert
+    assert_eq!(
+        read_to_string(&path).expect("should read"),
+        "first\nsecond\n"
+    );
+    assert!(!get_rotated_path(&path, 1).exists());
+}
+
+#[test]
+fn get_rotated_path_appends_index() {
+    // Act
+    let path = get_rotated_path(&PathBuf::from("/var/log/caesura.log"), 3);
+
+    // Assert
+    assert_eq!(path, PathBuf::from("/var/log/caesura.log.3"));
+}
+
+#[test]
+fn remove_colors_removes_ansi_codes() {
+    // Act
+    let output = remove_colors("\x1b[1mTranscoded\x1b[0m \x1b[38;2;112;112;112mflac\x1b[0m");
+
+    // Assert
+    assert_eq!(output, "Transcoded flac");
+}
@@ -1,20 +1,14 @@
-use caesura::hosting::HostBuilder;
+use caesura::hosting::{log_error, ExitStatus, HostBuilder};
 use std::process::ExitCode;
 
 #[tokio::main]
 async fn main() -> ExitCode {
     let host = HostBuilder::new().build();
-    match host.execute().await {
-        Ok(status) => {
-            if status {
-                ExitCode::SUCCESS
-            } else {
-                ExitCode::FAILURE
-            }
-        }
+    match Box::pin(host.execute()).await {
+        Ok(status) => status.into(),
         Err(error) => {
-            error.log();
-            ExitCode::FAILURE
+            log_error(&error);
+            ExitStatus::from_error(&error).into()
         }
     }
 }
@@ -0,0 +1,48 @@
+use std::fmt::Write;
+
+/// A Prometheus histogram of observed values.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    /// Upper bound of each bucket in ascending order.
+    pub bounds: Vec<f64>,
+    /// Number of observations less than or equal to the bound of each bucket.
+    pub counts: Vec<u64>,
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Histogram {
+    #[must_use]
+    pub fn new(bounds: Vec<f64>) -> Self {
+        let counts = vec![0; bounds.len()];
+        Self {
+            bounds,
+            counts,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Add an observed value.
+    pub fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Write the histogram in the Prometheus text format.
+    pub fn write(&self, output: &mut String, name: &str, help: &str) {
+        let _ = writeln!(output, "# HELP {name} {help}");
+        let _ = writeln!(output, "# TYPE {name} histogram");
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            let _ = writeln!(output, "{name}_bucket{{le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(output, "{name}_sum {}", self.sum);
+        let _ = writeln!(output, "{name}_count {}", self.count);
+    }
+}
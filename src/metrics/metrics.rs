@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+use di::injectable;
+use rogue_logging::Error;
+
+use crate::formats::TargetFormat;
+use crate::metrics::Histogram;
+use crate::progress::ProgressEvent;
+
+/// Upper bound in seconds of each bucket of the transcode duration histogram.
+const TRANSCODE_DURATION_BOUNDS: [f64; 9] = [
+    10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0, 3600.0,
+];
+
+/// Status code of an API response indicating the rate limit was exceeded.
+const TOO_MANY_REQUESTS: u16 = 429;
+
+/// Counters and histograms exposed in the Prometheus text format by `MetricsServer`.
+pub struct Metrics {
+    state: Mutex<MetricsState>,
+}
+
+#[derive(Debug)]
+struct MetricsState {
+    sources: u64,
+    verified: u64,
+    verify_failures: BTreeMap<String, u64>,
+    tracks: BTreeMap<TargetFormat, u64>,
+    audio_seconds: u64,
+    transcode_duration: Histogram,
+    torrents: BTreeMap<TargetFormat, u64>,
+    uploads: BTreeMap<TargetFormat, u64>,
+    api_requests: BTreeMap<(String, &'static str), u64>,
+    api_rate_limited: u64,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[injectable]
+impl Metrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MetricsState {
+                sources: 0,
+                verified: 0,
+                verify_failures: BTreeMap::new(),
+                tracks: BTreeMap::new(),
+                audio_seconds: 0,
+                transcode_duration: Histogram::new(TRANSCODE_DURATION_BOUNDS.to_vec()),
+                torrents: BTreeMap::new(),
+                uploads: BTreeMap::new(),
+                api_requests: BTreeMap::new(),
+                api_rate_limited: 0,
+            }),
+        }
+    }
+
+    /// Update the metrics from a [`ProgressEvent`].
+    pub fn record(&self, event: &ProgressEvent) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        match event {
+            ProgressEvent::SourceResolved { .. } => state.sources += 1,
+            ProgressEvent::SourceVerified => state.verified += 1,
+            ProgressEvent::VerifyFailed { rules, .. } => {
+                for rule in rules {
+                    *state.verify_failures.entry(rule.clone()).or_default() += 1;
+                }
+            }
+            ProgressEvent::SpectrogramsCreated { .. } | ProgressEvent::CrossPostSkipped { .. } => {}
+            ProgressEvent::TrackTranscoded { format, .. } => {
+                *state.tracks.entry(*format).or_default() += 1;
+            }
+            ProgressEvent::TranscodeDone {
+                audio_seconds,
+                elapsed_seconds,
+                ..
+            } => {
+                state.audio_seconds += u64::from(*audio_seconds);
+                state.transcode_duration.observe(*elapsed_seconds);
+            }
+            ProgressEvent::TorrentCreated { format, .. } => {
+                *state.torrents.entry(*format).or_default() += 1;
+            }
+            ProgressEvent::UploadDone { format, .. } => {
+                *state.uploads.entry(*format).or_default() += 1;
+            }
+        }
+    }
+
+    /// Count a request to the API.
+    pub fn record_api<T>(&self, action: &str, result: &Result<T, Error>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let outcome = if result.is_ok() { "success" } else { "error" };
+        *state
+            .api_requests
+            .entry((action.to_owned(), outcome))
+            .or_default() += 1;
+        if let Err(error) = result {
+            if error.status_code == Some(TOO_MANY_REQUESTS) {
+                state.api_rate_limited += 1;
+            }
+        }
+    }
+
+    /// Get the metrics in the Prometheus text format.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        let Ok(state) = self.state.lock() else {
+            return output;
+        };
+        write_counter(
+            &mut output,
+            "caesura_sources_processed_total",
+            "Sources retrieved from the API.",
+            &[(String::new(), state.sources)],
+        );
+        write_counter(
+            &mut output,
+            "caesura_sources_verified_total",
+            "Sources suitable for transcoding.",
+            &[(String::new(), state.verified)],
+        );
+        write_counter(
+            &mut output,
+            "caesura_verify_failures_total",
+            "Rules not satisfied by a source.",
+            &get_labeled("rule", &state.verify_failures),
+        );
+        write_counter(
+            &mut output,
+            "caesura_tracks_transcoded_total",
+            "Tracks transcoded to each target format.",
+            &get_labeled_formats(&state.tracks),
+        );
+        write_counter(
+            &mut output,
+            "caesura_audio_transcoded_seconds_total",
+            "Duration of the sources transcoded.",
+            &[(String::new(), state.audio_seconds)],
+        );
+        state.transcode_duration.write(
+            &mut output,
+            "caesura_transcode_duration_seconds",
+            "Time taken to transcode a source to every target format.",
+        );
+        write_counter(
+            &mut output,
+            "caesura_torrents_created_total",
+            "Torrent files created for each target format.",
+            &get_labeled_formats(&state.torrents),
+        );
+        write_counter(
+            &mut output,
+            "caesura_uploads_total",
+            "Transcodes uploaded for each target format.",
+            &get_labeled_formats(&state.uploads),
+        );
+        let api_requests: Vec<(String, u64)> = state
+            .api_requests
+            .iter()
+            .map(|((action, outcome), count)| {
+                (format!("action=\"{action}\",result=\"{outcome}\""), *count)
+            })
+            .collect();
+        write_counter(
+            &mut output,
+            "caesura_api_requests_total",
+            "Requests to the API by action and result.",
+            &api_requests,
+        );
+        write_counter(
+            &mut output,
+            "caesura_api_rate_limited_total",
+            "Requests to the API rejected as the rate limit was exceeded.",
+            &[(String::new(), state.api_rate_limited)],
+        );
+        output
+    }
+}
+
+fn get_labeled(label: &str, values: &BTreeMap<String, u64>) -> Vec<(String, u64)> {
+    values
+        .iter()
+        .map(|(value, count)| (format!("{label}=\"{value}\""), *count))
+        .collect()
+}
+
+fn get_labeled_formats(values: &BTreeMap<TargetFormat, u64>) -> Vec<(String, u64)> {
+    values
+        .iter()
+        .map(|(format, count)| {
+            let format = format.to_string().to_lowercase();
+            (format!("format=\"{format}\""), *count)
+        })
+        .collect()
+}
+
+/// Write a counter in the Prometheus text format.
+///
+/// Each value has the labels to include between the braces, or an empty string for none.
+fn write_counter(output: &mut String, name: &str, help: &str, values: &[(String, u64)]) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} counter");
+    for (labels, count) in values {
+        if labels.is_empty() {
+            let _ = writeln!(output, "{name} {count}");
+        } else {
+            let _ = writeln!(output, "{name}{{{labels}}} {count}");
+        }
+    }
+}
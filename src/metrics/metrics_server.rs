@@ -0,0 +1,69 @@
+use std::net::SocketAddr;
+
+use colored::Colorize;
+use di::Ref;
+use log::{info, trace, warn};
+use rogue_logging::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::errors::io_error;
+use crate::metrics::Metrics;
+
+/// Content type of the Prometheus text format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// A minimal HTTP server that responds to `GET /metrics` with the [`Metrics`].
+pub struct MetricsServer {
+    listener: TcpListener,
+}
+
+impl MetricsServer {
+    /// Bind the server to an address.
+    pub async fn bind(address: SocketAddr) -> Result<Self, Error> {
+        let listener = TcpListener::bind(address)
+            .await
+            .map_err(|e| io_error(e, "bind metrics address"))?;
+        info!("{} metrics at http://{address}/metrics", "Serving".bold());
+        Ok(Self { listener })
+    }
+
+    /// Respond to requests until the process is stopped.
+    pub async fn serve(self, metrics: Ref<Metrics>) {
+        loop {
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    warn!("{} to accept metrics request: {error}", "Failed".bold());
+                    continue;
+                }
+            };
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                if let Err(error) = respond(stream, &metrics).await {
+                    trace!("{} to respond to metrics request: {error}", "Failed".bold());
+                }
+            });
+        }
+    }
+}
+
+#[allow(clippy::absolute_paths)]
+async fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    let mut buffer = [0; 1024];
+    let length = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(buffer.get(..length).unwrap_or_default());
+    let mut parts = request.split_whitespace();
+    let is_metrics = parts.next() == Some("GET") && parts.next() == Some("/metrics");
+    let (status, content_type, body) = if is_metrics {
+        ("200 OK", CONTENT_TYPE, metrics.render())
+    } else {
+        ("404 Not Found", "text/plain", "Not Found\n".to_owned())
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,9 @@
+pub use histogram::*;
+pub use metrics::*;
+pub use metrics_server::*;
+
+pub(crate) mod histogram;
+pub(crate) mod metrics;
+pub(crate) mod metrics_server;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use rogue_logging::Error;
+
+use crate::formats::TargetFormat;
+use crate::metrics::{Histogram, Metrics};
+use crate::progress::ProgressEvent;
+
+#[test]
+fn metrics_render() {
+    // Arrange
+    let metrics = Metrics::new();
+    let events = vec![
+        ProgressEvent::SourceResolved {
+            name: "Artist - Album".to_owned(),
+            directory: PathBuf::from("/srv/content/a"),
+        },
+        ProgressEvent::SourceVerified,
+        ProgressEvent::VerifyFailed {
+            rules: vec!["scene".to_owned(), "missing_tags".to_owned()],
+            issues: Vec::new(),
+        },
+        ProgressEvent::TrackTranscoded {
+            format: TargetFormat::V0,
+            path: PathBuf::from("/srv/output/a/01.mp3"),
+        },
+        ProgressEvent::TrackTranscoded {
+            format: TargetFormat::V0,
+            path: PathBuf::from("/srv/output/a/02.mp3"),
+        },
+        ProgressEvent::TranscodeDone {
+            formats: vec![TargetFormat::V0],
+            audio_seconds: 150,
+            elapsed_seconds: 45.0,
+            directories: vec![PathBuf::from("/srv/output/a")],
+        },
+    ];
+
+    // Act
+    for event in &events {
+        metrics.record(event);
+    }
+    metrics.record_api("get torrent", &Ok::<(), Error>(()));
+    metrics.record_api(
+        "get torrent",
+        &Err::<(), Error>(Error {
+            status_code: Some(429),
+            ..Error::default()
+        }),
+    );
+    let output = metrics.render();
+
+    // Assert
+    let lines: Vec<&str> = output.lines().collect();
+    for expected in [
+        "# TYPE caesura_sources_processed_total counter",
+        "caesura_sources_processed_total 1",
+        "caesura_sources_verified_total 1",
+        "caesura_verify_failures_total{rule=\"missing_tags\"} 1",
+        "caesura_verify_failures_total{rule=\"scene\"} 1",
+        "caesura_tracks_transcoded_total{format=\"v0\"} 2",
+        "caesura_audio_transcoded_seconds_total 150",
+        "caesura_transcode_duration_seconds_bucket{le=\"30\"} 0",
+        "caesura_transcode_duration_seconds_bucket{le=\"60\"} 1",
+        "caesura_transcode_duration_seconds_bucket{le=\"+Inf\"} 1",
+        "caesura_transcode_duration_seconds_sum 45",
+        "caesura_transcode_duration_seconds_count 1",
+        "caesura_api_requests_total{action=\"get torrent\",result=\"error\"} 1",
+        "caesura_api_requests_total{action=\"get torrent\",result=\"success\"} 1",
+        "caesura_api_rate_limited_total 1",
+    ] {
+        assert!(lines.contains(&expected), "missing line: {expected}");
+    }
+}
+
+#[test]
+fn histogram_observe() {
+    // Arrange
+    let mut histogram = Histogram::new(vec![1.0, 5.0]);
+
+    // Act
+    histogram.observe(0.5);
+    histogram.observe(3.0);
+    histogram.observe(10.0);
+
+    // Assert
+    assert_eq!(histogram.counts, vec![1, 2]);
+    assert_eq!(histogram.count, 3);
+    assert!((histogram.sum - 13.5).abs() < f64::EPSILON);
+}
@@ -0,0 +1,11 @@
+pub use musicbrainz_client::*;
+pub use musicbrainz_provider::*;
+pub use musicbrainz_release::*;
+pub use musicbrainz_response::*;
+
+pub(crate) mod musicbrainz_client;
+pub(crate) mod musicbrainz_provider;
+pub(crate) mod musicbrainz_release;
+pub(crate) mod musicbrainz_response;
+#[cfg(test)]
+mod tests;
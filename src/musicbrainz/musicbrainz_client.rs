@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant};
+
+use di::{injectable, Ref};
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::Client;
+use rogue_logging::Error;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::errors::{error, json_error};
+use crate::musicbrainz::{MusicBrainzRelease, ReleaseResponse, SearchResponse};
+use crate::options::EnrichOptions;
+use crate::source::Metadata;
+
+/// Shortest duration between requests as required by the `MusicBrainz` API.
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lowest search score of a release to be considered a match.
+const MIN_SCORE: u8 = 90;
+
+/// Number of search results to choose a match from.
+const SEARCH_LIMIT: &str = "5";
+
+/// Client of the `MusicBrainz` web service.
+///
+/// Requests are rate limited across every command of the process so it must be a singleton.
+pub struct MusicBrainzClient {
+    server_url: String,
+    client: Client,
+    last_request: Mutex<Option<Instant>>,
+}
+
+#[injectable]
+impl MusicBrainzClient {
+    fn new(options: Ref<EnrichOptions>) -> Self {
+        Self {
+            server_url: options
+                .musicbrainz_url
+                .clone()
+                .expect("musicbrainz_url should be set"),
+            client: Client::new(),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Find the release that best matches the metadata of a source.
+    ///
+    /// Returns `None` if no release has a high enough score.
+    pub async fn find_release(
+        &self,
+        metadata: &Metadata,
+    ) -> Result<Option<MusicBrainzRelease>, Error> {
+        let query = get_query(metadata);
+        let search: SearchResponse = self
+            .get(
+                "ws/2/release",
+                &[
+                    ("query", query.as_str()),
+                    ("limit", SEARCH_LIMIT),
+                    ("fmt", "json"),
+                ],
+            )
+            .await?;
+        let Some(found) = select_release(&search.releases, metadata.year) else {
+            return Ok(None);
+        };
+        // Search results don't include the first release date of the release group
+        // or the track lengths
+        let release: ReleaseResponse = self
+            .get(
+                &format!("ws/2/release/{}", found.id),
+                &[("inc", "labels release-groups recordings"), ("fmt", "json")],
+            )
+            .await?;
+        Ok(Some(MusicBrainzRelease::from_response(
+            release,
+            &self.server_url,
+        )))
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, Error> {
+        let action = "request MusicBrainz";
+        self.wait().await;
+        let response = self
+            .client
+            .get(format!("{}/{path}", self.server_url))
+            .query(query)
+            .header(
+                USER_AGENT,
+                format!("{PKG_NAME}/{PKG_VERSION} ( {PKG_HOMEPAGE} )"),
+            )
+            .header(ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error {
+                action: action.to_owned(),
+                message: format!("{} responded with {status}", self.server_url),
+                status_code: Some(status.as_u16()),
+                ..Error::default()
+            });
+        }
+        let json = response
+            .text()
+            .await
+            .map_err(|e| error(action, e.to_string()))?;
+        serde_json::from_str(&json).map_err(|e| json_error(e, action))
+    }
+
+    /// Wait until the request interval has passed since the previous request.
+    async fn wait(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            if let Some(remaining) = REQUEST_INTERVAL.checked_sub(last.elapsed()) {
+                sleep(remaining).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Get the Lucene query to search for the release of a source.
+#[must_use]
+pub fn get_query(metadata: &Metadata) -> String {
+    format!(
+        "release:\"{}\" AND artist:\"{}\"",
+        escape(&metadata.album),
+        escape(&metadata.artist)
+    )
+}
+
+/// Select the search result with a high enough score, preferring one released in `year`.
+#[must_use]
+pub fn select_release(releases: &[ReleaseResponse], year: u16) -> Option<&ReleaseResponse> {
+    let mut matches = releases
+        .iter()
+        .filter(|release| release.score.unwrap_or_default() >= MIN_SCORE);
+    let first = matches.clone().next();
+    let year = year.to_string();
+    matches
+        .find(|release| {
+            release
+                .date
+                .as_deref()
+                .is_some_and(|date| date.starts_with(&year))
+        })
+        .or(first)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,93 @@
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, trace, warn};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{error, path_error, yaml_error};
+use crate::fs::PathManager;
+use crate::musicbrainz::{MusicBrainzClient, MusicBrainzRelease};
+use crate::options::EnrichOptions;
+use crate::queue::TimeStamp;
+use crate::source::Source;
+
+/// Cached result of matching a source to a [`MusicBrainzRelease`].
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MusicBrainzCacheEntry {
+    /// Matched release, or `None` if there was no match.
+    pub release: Option<MusicBrainzRelease>,
+    /// Time the match was made.
+    pub completed: TimeStamp,
+}
+
+/// Get the [`MusicBrainzRelease`] of a source if `enrich_tags` is set.
+///
+/// Matches are cached by torrent id so the transcode and upload of a source only search once.
+#[injectable]
+pub struct MusicBrainzProvider {
+    options: Ref<EnrichOptions>,
+    client: Ref<MusicBrainzClient>,
+    paths: Ref<PathManager>,
+}
+
+impl MusicBrainzProvider {
+    /// Get the [`MusicBrainzRelease`] of a source.
+    ///
+    /// Returns `None` if `enrich_tags` is not set or there is no match.
+    ///
+    /// Failures are logged as warnings as enrichment must not prevent transcoding or uploading.
+    pub async fn get(&self, source: &Source) -> Option<MusicBrainzRelease> {
+        if !self.options.enrich_tags.expect("enrich_tags should be set") {
+            return None;
+        }
+        let path = self.paths.get_musicbrainz_cache_path(source);
+        if let Some(entry) = read_entry(&path) {
+            trace!(
+                "{} cached MusicBrainz match from {}",
+                "Using".bold(),
+                entry.completed
+            );
+            return entry.release;
+        }
+        let release = match self.client.find_release(&source.metadata).await {
+            Ok(release) => release,
+            Err(e) => {
+                warn!("{} to search MusicBrainz: {e}", "Failed".bold());
+                return None;
+            }
+        };
+        if let Some(release) = &release {
+            debug!("{} MusicBrainz release {release}", "Matched".bold());
+        } else {
+            debug!("{} MusicBrainz release matched {source}", "No".bold());
+        }
+        let entry = MusicBrainzCacheEntry {
+            release: release.clone(),
+            completed: TimeStamp::now(),
+        };
+        if let Err(e) = write_entry(&path, &entry) {
+            warn!("{} to cache MusicBrainz match: {e}", "Failed".bold());
+        }
+        release
+    }
+}
+
+fn read_entry(path: &Path) -> Option<MusicBrainzCacheEntry> {
+    let file = File::open(path).ok()?;
+    serde_yaml::from_reader(BufReader::new(file)).ok()
+}
+
+fn write_entry(path: &Path, entry: &MusicBrainzCacheEntry) -> Result<(), Error> {
+    let action = "write MusicBrainz cache";
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| path_error(e, action, parent))?;
+    }
+    let file = File::create(path).map_err(|e| path_error(e, action, path))?;
+    let mut writer = BufWriter::new(file);
+    serde_yaml::to_writer(&mut writer, entry).map_err(|e| yaml_error(e, action))?;
+    writer.flush().map_err(|e| error(action, e.to_string()))
+}
@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use lofty::config::WriteOptions;
+use lofty::prelude::{TagExt, TaggedFileExt};
+use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error;
+use crate::musicbrainz::ReleaseResponse;
+
+/// Catalogue number `MusicBrainz` uses for a release without one.
+const NO_CATALOGUE_NUMBER: &str = "[none]";
+
+/// Metadata of a `MusicBrainz` release matched to a source.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MusicBrainzRelease {
+    /// `MusicBrainz` release id.
+    pub id: String,
+    pub title: String,
+    /// URL of the release on the `MusicBrainz` server.
+    pub url: String,
+    pub catalogue_number: Option<String>,
+    /// ISO 3166-1 code of the country the release was issued in.
+    pub country: Option<String>,
+    /// Date of the earliest release in the release group.
+    pub original_date: Option<String>,
+    /// Length in milliseconds of each track in order of disc then track number.
+    #[serde(default)]
+    pub track_lengths: Vec<Option<u64>>,
+}
+
+impl MusicBrainzRelease {
+    /// Create a [`MusicBrainzRelease`] from a lookup response.
+    #[must_use]
+    pub fn from_response(response: ReleaseResponse, server_url: &str) -> Self {
+        let catalogue_number = response
+            .label_info
+            .into_iter()
+            .filter_map(|label| label.catalog_number)
+            .find(|number| !number.is_empty() && number != NO_CATALOGUE_NUMBER);
+        let original_date = response
+            .release_group
+            .and_then(|group| group.first_release_date)
+            .or(response.date)
+            .filter(|date| !date.is_empty());
+        let track_lengths = response
+            .media
+            .iter()
+            .flat_map(|medium| &medium.tracks)
+            .map(|track| track.length)
+            .collect();
+        Self {
+            url: format!("{server_url}/release/{}", response.id),
+            id: response.id,
+            title: response.title,
+            catalogue_number,
+            country: response.country.filter(|country| !country.is_empty()),
+            original_date,
+            track_lengths,
+        }
+    }
+
+    /// Add each value to the tag if it's not already set.
+    ///
+    /// Returns `true` if the tag was changed.
+    pub fn apply(&self, tag: &mut Tag) -> bool {
+        let mut is_changed = false;
+        let values = [
+            (ItemKey::CatalogNumber, &self.catalogue_number),
+            (get_country_key(tag.tag_type()), &self.country),
+            (ItemKey::OriginalReleaseDate, &self.original_date),
+        ];
+        for (key, value) in values {
+            let Some(value) = value else {
+                continue;
+            };
+            if tag.get_string(&key).is_some() {
+                continue;
+            }
+            if matches!(key, ItemKey::Unknown(_)) {
+                tag.insert_unchecked(TagItem::new(key, ItemValue::Text(value.clone())));
+                is_changed = true;
+            } else {
+                is_changed |= tag.insert_text(key, value.clone());
+            }
+        }
+        if tag.get_string(&ItemKey::MusicBrainzReleaseId).is_none() {
+            is_changed |= tag.insert_text(ItemKey::MusicBrainzReleaseId, self.id.clone());
+        }
+        is_changed
+    }
+
+    /// Add each value to the primary tag of a file if it's not already set.
+    ///
+    /// Files without a tag are left unchanged.
+    pub fn write_to_path(&self, path: &Path) -> Result<(), Error> {
+        let action = "write MusicBrainz tags";
+        let mut file = lofty::read_from_path(path).map_err(|e| error(action, e.to_string()))?;
+        let Some(tag) = file.primary_tag_mut() else {
+            return Ok(());
+        };
+        if self.apply(tag) {
+            tag.save_to_path(path, WriteOptions::default())
+                .map_err(|e| error(action, e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Get the values formatted for the upload description.
+    #[must_use]
+    pub fn get_description(&self) -> String {
+        let mut parts = vec![format!("[url={}]{}[/url]", self.url, self.title)];
+        if let Some(number) = &self.catalogue_number {
+            parts.push(format!("Catalogue number {number}"));
+        }
+        if let Some(country) = &self.country {
+            parts.push(format!("Country {country}"));
+        }
+        if let Some(date) = &self.original_date {
+            parts.push(format!("Original date {date}"));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Get the key of the release country as written by `MusicBrainz` Picard.
+///
+/// There is no [`ItemKey`] for the release country so the key depends on the tag type.
+fn get_country_key(tag_type: TagType) -> ItemKey {
+    let key = if tag_type == TagType::Id3v2 {
+        "MusicBrainz Album Release Country"
+    } else {
+        "RELEASECOUNTRY"
+    };
+    ItemKey::Unknown(key.to_owned())
+}
+
+impl Display for MusicBrainzRelease {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{} ({})", self.title, self.url)
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+/// Response of a `MusicBrainz` release search.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SearchResponse {
+    #[serde(default)]
+    pub releases: Vec<ReleaseResponse>,
+}
+
+/// A release of a `MusicBrainz` search or lookup response.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ReleaseResponse {
+    pub id: String,
+    pub title: String,
+    /// Relevance of a search result from 0 to 100.
+    ///
+    /// `None` for a lookup response.
+    pub score: Option<u8>,
+    pub date: Option<String>,
+    pub country: Option<String>,
+    #[serde(rename = "label-info", default)]
+    pub label_info: Vec<LabelInfoResponse>,
+    #[serde(rename = "release-group")]
+    pub release_group: Option<ReleaseGroupResponse>,
+    /// Discs of the release.
+    ///
+    /// Only included in a lookup response with `inc=recordings`.
+    #[serde(default)]
+    pub media: Vec<MediumResponse>,
+}
+
+/// Label and catalogue number of a [`ReleaseResponse`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LabelInfoResponse {
+    #[serde(rename = "catalog-number")]
+    pub catalog_number: Option<String>,
+}
+
+/// Release group of a [`ReleaseResponse`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ReleaseGroupResponse {
+    #[serde(rename = "first-release-date")]
+    pub first_release_date: Option<String>,
+}
+
+/// Disc of a [`ReleaseResponse`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct MediumResponse {
+    #[serde(default)]
+    pub tracks: Vec<TrackResponse>,
+}
+
+/// Track of a [`MediumResponse`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TrackResponse {
+    /// Length in milliseconds.
+    pub length: Option<u64>,
+}
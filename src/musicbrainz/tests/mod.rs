@@ -0,0 +1,2 @@
+mod musicbrainz_client_tests;
+mod musicbrainz_release_tests;
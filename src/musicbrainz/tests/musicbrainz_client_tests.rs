@@ -0,0 +1,106 @@
+use crate::musicbrainz::{get_query, select_release, ReleaseResponse, SearchResponse};
+use crate::source::Metadata;
+
+fn get_release(id: &str, score: u8, date: &str) -> ReleaseResponse {
+    ReleaseResponse {
+        id: id.to_owned(),
+        title: "Album".to_owned(),
+        score: Some(score),
+        date: Some(date.to_owned()),
+        ..ReleaseResponse::default()
+    }
+}
+
+#[test]
+fn musicbrainz_client_get_query() {
+    // Arrange
+    let metadata = Metadata {
+        artist: "Artist".to_owned(),
+        album: "Album \"Live\"".to_owned(),
+        remaster_title: String::new(),
+        year: 2012,
+        media: "CD".to_owned(),
+    };
+
+    // Act
+    let query = get_query(&metadata);
+
+    // Assert
+    assert_eq!(query, r#"release:"Album \"Live\"" AND artist:"Artist""#);
+}
+
+#[test]
+fn musicbrainz_client_select_release_prefers_year() {
+    // Arrange
+    let releases = vec![
+        get_release("a", 100, "2010-01-01"),
+        get_release("b", 95, "2012-06-01"),
+        get_release("c", 50, "2012-01-01"),
+    ];
+
+    // Act
+    let release = select_release(&releases, 2012);
+
+    // Assert
+    assert_eq!(release.map(|x| x.id.as_str()), Some("b"));
+}
+
+#[test]
+fn musicbrainz_client_select_release_without_year() {
+    // Arrange
+    let releases = vec![
+        get_release("a", 100, "2010-01-01"),
+        get_release("b", 50, "2012-01-01"),
+    ];
+
+    // Act
+    let release = select_release(&releases, 2012);
+
+    // Assert
+    assert_eq!(release.map(|x| x.id.as_str()), Some("a"));
+}
+
+#[test]
+fn musicbrainz_client_select_release_below_score() {
+    // Arrange
+    let releases = vec![get_release("a", 89, "2012-01-01")];
+
+    // Act
+    let release = select_release(&releases, 2012);
+
+    // Assert
+    assert!(release.is_none());
+}
+
+#[test]
+fn musicbrainz_client_deserialize_search() {
+    // Arrange
+    let json = r#"{
+        "created": "2024-01-01T00:00:00.000Z",
+        "count": 1,
+        "releases": [{
+            "id": "abc",
+            "score": 100,
+            "title": "Album",
+            "date": "2012-05-01",
+            "country": "GB",
+            "label-info": [{"catalog-number": "CAT001", "label": {"name": "Label"}}],
+            "release-group": {"id": "def", "first-release-date": "2010-01-01"}
+        }]
+    }"#;
+
+    // Act
+    let response: SearchResponse = serde_json::from_str(json).expect("should deserialize");
+
+    // Assert
+    let release = response.releases.first().expect("should have a release");
+    assert_eq!(release.score, Some(100));
+    assert_eq!(release.country.as_deref(), Some("GB"));
+    assert_eq!(
+        release
+            .label_info
+            .first()
+            .and_then(|x| x.catalog_number.as_deref()),
+        Some("CAT001")
+    );
+}
@@ -0,0 +1,113 @@
+use lofty::tag::{ItemKey, Tag, TagType};
+
+use crate::musicbrainz::{
+    LabelInfoResponse, MediumResponse, MusicBrainzRelease, ReleaseGroupResponse, ReleaseResponse,
+    TrackResponse,
+};
+
+fn get_release() -> MusicBrainzRelease {
+    MusicBrainzRelease {
+        id: "abc".to_owned(),
+        title: "Album".to_owned(),
+        url: "https://musicbrainz.org/release/abc".to_owned(),
+        catalogue_number: Some("CAT001".to_owned()),
+        country: Some("GB".to_owned()),
+        original_date: Some("2010-01-01".to_owned()),
+        track_lengths: vec![Some(215_000), None],
+    }
+}
+
+#[test]
+fn musicbrainz_release_from_response() {
+    // Arrange
+    let response = ReleaseResponse {
+        id: "abc".to_owned(),
+        title: "Album".to_owned(),
+        date: Some("2012-05-01".to_owned()),
+        country: Some("GB".to_owned()),
+        label_info: vec![
+            LabelInfoResponse {
+                catalog_number: Some("[none]".to_owned()),
+            },
+            LabelInfoResponse {
+                catalog_number: Some("CAT001".to_owned()),
+            },
+        ],
+        release_group: Some(ReleaseGroupResponse {
+            first_release_date: Some("2010-01-01".to_owned()),
+        }),
+        media: vec![
+            MediumResponse {
+                tracks: vec![TrackResponse {
+                    length: Some(215_000),
+                }],
+            },
+            MediumResponse {
+                tracks: vec![TrackResponse { length: None }],
+            },
+        ],
+        ..ReleaseResponse::default()
+    };
+
+    // Act
+    let release = MusicBrainzRelease::from_response(response, "https://musicbrainz.org");
+
+    // Assert
+    assert_eq!(release, get_release());
+}
+
+#[test]
+fn musicbrainz_release_apply_fills_missing() {
+    // Arrange
+    let release = get_release();
+    let mut tag = Tag::new(TagType::VorbisComments);
+    tag.insert_text(ItemKey::CatalogNumber, "EXISTING".to_owned());
+
+    // Act
+    let is_changed = release.apply(&mut tag);
+
+    // Assert
+    assert!(is_changed);
+    assert_eq!(tag.get_string(&ItemKey::CatalogNumber), Some("EXISTING"));
+    assert_eq!(
+        tag.get_string(&ItemKey::OriginalReleaseDate),
+        Some("2010-01-01")
+    );
+    assert_eq!(
+        tag.get_string(&ItemKey::Unknown("RELEASECOUNTRY".to_owned())),
+        Some("GB")
+    );
+    assert_eq!(tag.get_string(&ItemKey::MusicBrainzReleaseId), Some("abc"));
+}
+
+#[test]
+fn musicbrainz_release_apply_unchanged() {
+    // Arrange
+    let release = get_release();
+    let mut tag = Tag::new(TagType::Id3v2);
+    release.apply(&mut tag);
+
+    // Act
+    let is_changed = release.apply(&mut tag);
+
+    // Assert
+    assert!(!is_changed);
+}
+
+#[test]
+fn musicbrainz_release_get_description() {
+    // Arrange
+    let release = MusicBrainzRelease {
+        country: None,
+        ..get_release()
+    };
+
+    // Act
+    let description = release.get_description();
+
+    // Assert
+    assert_eq!(
+        description,
+        "[url=https://musicbrainz.org/release/abc]Album[/url] · Catalogue number CAT001 · Original date 2010-01-01"
+    );
+}
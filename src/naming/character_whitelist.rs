@@ -0,0 +1,139 @@
+use std::path::{Component, Path, PathBuf};
+
+use regex::Regex;
+
+/// Separator between the character and the replacement of a `character_replacement`.
+const SEPARATOR: &str = "=>";
+
+/// Used in place of a character that is not allowed and has no replacement.
+const UNKNOWN_REPLACEMENT: char = '_';
+
+/// Characters an indexer permits in the names of uploads.
+///
+/// Each character with a replacement is replaced, then any character that is not
+/// allowed is replaced by `_`.
+///
+/// - `allowed` is a regex that matches a single permitted character such as
+///   `[\p{L}\p{N} ._()\[\]&,'!-]`
+/// - Each replacement is `CHARACTER=>REPLACEMENT` such as `&=>and`
+#[derive(Clone, Debug, Default)]
+pub struct CharacterWhitelist {
+    allowed: Option<Regex>,
+    replacements: Vec<(char, String)>,
+}
+
+impl CharacterWhitelist {
+    /// Create a [`CharacterWhitelist`] from the allowed characters and replacements.
+    ///
+    /// Returns a description of the problem if either can't be parsed.
+    pub fn new(allowed: Option<&str>, replacements: &[String]) -> Result<Self, String> {
+        let allowed = match allowed {
+            Some(allowed) => Some(parse_allowed(allowed)?),
+            None => None,
+        };
+        let replacements = replacements
+            .iter()
+            .map(|replacement| parse_replacement(replacement))
+            .collect::<Result<Vec<_>, _>>()?;
+        let whitelist = Self {
+            allowed,
+            replacements,
+        };
+        for (character, replacement) in &whitelist.replacements {
+            if let Some(disallowed) = replacement.chars().find(|x| !whitelist.is_allowed(*x)) {
+                return Err(format!(
+                    "Replacement of `{character}` contains `{disallowed}` which is not allowed"
+                ));
+            }
+        }
+        Ok(whitelist)
+    }
+
+    /// Parse a replacement from `CHARACTER=>REPLACEMENT`.
+    ///
+    /// Returns a description of the problem if it can't be parsed.
+    pub fn validate_replacement(value: &str) -> Result<(), String> {
+        parse_replacement(value).map(|_| ())
+    }
+
+    /// Check `allowed_characters` is a regex.
+    ///
+    /// Returns a description of the problem if it can't be parsed.
+    pub fn validate_allowed(value: &str) -> Result<(), String> {
+        parse_allowed(value).map(|_| ())
+    }
+
+    /// Is the character permitted?
+    #[must_use]
+    pub fn is_allowed(&self, character: char) -> bool {
+        self.allowed
+            .as_ref()
+            .is_none_or(|allowed| allowed.is_match(character.encode_utf8(&mut [0; 4])))
+    }
+
+    /// Get the distinct characters of a name that are replaced, in order of occurrence.
+    #[must_use]
+    pub fn get_disallowed(&self, input: &str) -> Vec<char> {
+        let mut output = Vec::new();
+        for character in input.chars() {
+            if (self.get_replacement(character).is_some() || !self.is_allowed(character))
+                && !output.contains(&character)
+            {
+                output.push(character);
+            }
+        }
+        output
+    }
+
+    /// Replace the characters of a name that are not permitted.
+    #[must_use]
+    pub fn execute(&self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        for character in input.chars() {
+            if let Some(replacement) = self.get_replacement(character) {
+                output.push_str(replacement);
+            } else if self.is_allowed(character) {
+                output.push(character);
+            } else {
+                output.push(UNKNOWN_REPLACEMENT);
+            }
+        }
+        output
+    }
+
+    /// Replace the characters that are not permitted in every component of a relative path.
+    #[must_use]
+    pub fn execute_path(&self, path: &Path) -> PathBuf {
+        path.components()
+            .map(|component| match component {
+                Component::Normal(name) => PathBuf::from(self.execute(&name.to_string_lossy())),
+                _ => PathBuf::from(component.as_os_str()),
+            })
+            .collect()
+    }
+
+    fn get_replacement(&self, character: char) -> Option<&str> {
+        self.replacements
+            .iter()
+            .find(|(x, _)| *x == character)
+            .map(|(_, replacement)| replacement.as_str())
+    }
+}
+
+fn parse_allowed(value: &str) -> Result<Regex, String> {
+    if value.is_empty() {
+        return Err("Allowed characters must not be empty".to_owned());
+    }
+    Regex::new(&format!("^(?:{value})$")).map_err(|e| e.to_string())
+}
+
+fn parse_replacement(value: &str) -> Result<(char, String), String> {
+    let Some((character, replacement)) = value.split_once(SEPARATOR) else {
+        return Err(format!("Expected `CHARACTER{SEPARATOR}REPLACEMENT`"));
+    };
+    let mut characters = character.chars();
+    let (Some(character), None) = (characters.next(), characters.next()) else {
+        return Err("Expected a single character to replace".to_owned());
+    };
+    Ok((character, replacement.to_owned()))
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+use crate::formats::TranscodeTarget;
+use crate::naming::{Sanitizer, SourceName, TranscodeName};
+use crate::source::Metadata;
+
+/// Placeholders that can be used in a [`LayoutTemplate`].
+pub const LAYOUT_PLACEHOLDERS: [&str; 9] = [
+    "artist",
+    "artist_initial",
+    "album",
+    "remaster_title",
+    "year",
+    "media",
+    "format",
+    "source",
+    "name",
+];
+
+/// Used as the `{artist_initial}` of an artist that doesn't start with a letter.
+const NON_ALPHABETIC_INITIAL: &str = "#";
+
+/// Resolve a directory layout template such as `{artist_initial}/{artist}/{album} ({year}) [{format}]`.
+///
+/// Each `/` separated part of the template is a directory, the last of which is the
+/// transcode directory and the name of the torrent.
+///
+/// Placeholders:
+/// - `{artist}`, `{album}`, `{remaster_title}`, `{year}`, `{media}` from the source
+/// - `{artist_initial}` first letter of the artist in upper case, or `#`
+/// - `{format}` target format such as `FLAC`, `320` or `V0`
+/// - `{source}` default source name such as `Artist - Album [2012]`
+/// - `{name}` default transcode name such as `Artist - Album [2012] [WEB FLAC]`
+pub struct LayoutTemplate;
+
+impl LayoutTemplate {
+    /// Check the template can be resolved.
+    ///
+    /// Returns a description of the first problem found.
+    pub fn validate(template: &str) -> Result<(), String> {
+        if template.starts_with('/') || template.starts_with('\\') {
+            return Err("Template must be relative to the output directory".to_owned());
+        }
+        let mut has_format = false;
+        for part in template.split('/') {
+            if part.trim().is_empty() || part == "." || part == ".." {
+                return Err(format!("Template has an invalid directory: `{part}`"));
+            }
+            for placeholder in get_placeholders(part)? {
+                if !LAYOUT_PLACEHOLDERS.contains(&placeholder) {
+                    return Err(format!(
+                        "Unknown placeholder `{{{placeholder}}}`. Expected one of: {}",
+                        LAYOUT_PLACEHOLDERS.join(", ")
+                    ));
+                }
+                has_format |= matches!(placeholder, "format" | "name");
+            }
+        }
+        if !has_format {
+            return Err("Template must include `{format}` or `{name}` so each target format has its own directory".to_owned());
+        }
+        Ok(())
+    }
+
+    /// Get the path of the transcode directory relative to the output directory.
+    ///
+    /// Each directory is sanitized after the placeholders are replaced so a `/` in a value
+    /// does not create a directory.
+    #[must_use]
+    pub fn get(template: &str, metadata: &Metadata, target: impl TranscodeTarget) -> PathBuf {
+        template
+            .split('/')
+            .map(|part| {
+                let name = replace_placeholders(part, metadata, target);
+                Sanitizer::execute_windows(&Sanitizer::execute(name))
+            })
+            .collect()
+    }
+}
+
+/// Get the name of each placeholder in a part of the template.
+pub(crate) fn get_placeholders(part: &str) -> Result<Vec<&str>, String> {
+    let mut placeholders = Vec::new();
+    let mut rest = part;
+    while let Some(start) = rest.find('{') {
+        let after = rest.get(start + 1..).unwrap_or_default();
+        let Some(end) = after.find('}') else {
+            return Err(format!("Template has an unclosed placeholder: `{part}`"));
+        };
+        placeholders.push(after.get(..end).unwrap_or_default());
+        rest = after.get(end + 1..).unwrap_or_default();
+    }
+    if rest.contains('}') {
+        return Err(format!("Template has an unopened placeholder: `{part}`"));
+    }
+    Ok(placeholders)
+}
+
+/// Replace each placeholder in a single pass so a value containing braces is not replaced.
+fn replace_placeholders(part: &str, metadata: &Metadata, target: impl TranscodeTarget) -> String {
+    replace_placeholders_with(part, |placeholder| get_value(placeholder, metadata, target))
+}
+
+/// Replace each placeholder with the value returned by `get_value`.
+pub(crate) fn replace_placeholders_with<F>(part: &str, get_value: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let mut output = String::new();
+    let mut rest = part;
+    while let Some(start) = rest.find('{') {
+        let after = rest.get(start + 1..).unwrap_or_default();
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        output.push_str(rest.get(..start).unwrap_or_default());
+        let placeholder = after.get(..end).unwrap_or_default();
+        output.push_str(&get_value(placeholder));
+        rest = after.get(end + 1..).unwrap_or_default();
+    }
+    output.push_str(rest);
+    output
+}
+
+fn get_value(placeholder: &str, metadata: &Metadata, target: impl TranscodeTarget) -> String {
+    match placeholder {
+        "artist" => metadata.artist.clone(),
+        "artist_initial" => get_initial(&metadata.artist),
+        "album" => metadata.album.clone(),
+        "remaster_title" => metadata.remaster_title.clone(),
+        "year" => metadata.year.to_string(),
+        "media" => metadata.media.clone(),
+        "format" => target.get_name().to_owned(),
+        "source" => SourceName::get(metadata),
+        "name" => TranscodeName::get(metadata, target),
+        _ => format!("{{{placeholder}}}"),
+    }
+}
+
+pub(crate) fn get_initial(artist: &str) -> String {
+    match artist.chars().find(|x| !x.is_whitespace()) {
+        Some(initial) if initial.is_alphabetic() => initial.to_uppercase().to_string(),
+        _ => NON_ALPHABETIC_INITIAL.to_owned(),
+    }
+}
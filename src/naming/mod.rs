@@ -1,17 +1,29 @@
+pub use character_whitelist::*;
 pub use humanize::*;
+pub use layout_template::*;
+pub use name_substitution::*;
 pub use sanitizer::*;
 pub use shortener::*;
 pub use source_name::*;
+pub use spectrogram_layout::*;
 pub use spectrogram_name::*;
 pub use track_name::*;
 pub use transcode_name::*;
+pub use transliterator::*;
+pub use unicode_form::*;
 
+pub(crate) mod character_whitelist;
 pub(crate) mod humanize;
+pub(crate) mod layout_template;
+pub(crate) mod name_substitution;
 pub(crate) mod sanitizer;
 pub(crate) mod shortener;
 pub(crate) mod source_name;
+pub(crate) mod spectrogram_layout;
 pub(crate) mod spectrogram_name;
 #[cfg(test)]
 mod tests;
 pub(crate) mod track_name;
 pub(crate) mod transcode_name;
+pub(crate) mod transliterator;
+pub(crate) mod unicode_form;
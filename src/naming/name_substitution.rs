@@ -0,0 +1,56 @@
+use regex::Regex;
+
+/// Separator between the pattern and the replacement of a [`NameSubstitution`].
+const SEPARATOR: &str = "=>";
+
+/// A regex substitution applied to the names of output files and directories.
+///
+/// Defined as `PATTERN=>REPLACEMENT` where the replacement can refer to capture groups
+/// with `$1` or `${name}`.
+///
+/// Examples:
+/// - `—=>-` replaces em dashes with hyphens
+/// - ` \(feat\. [^)]*\)=>` removes `(feat. ...)` clauses
+/// - `\s{2,}=> ` collapses whitespace
+#[derive(Clone, Debug)]
+pub struct NameSubstitution {
+    regex: Regex,
+    replacement: String,
+}
+
+impl NameSubstitution {
+    /// Parse a substitution from `PATTERN=>REPLACEMENT`.
+    ///
+    /// Returns a description of the problem if it can't be parsed.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let Some((pattern, replacement)) = value.split_once(SEPARATOR) else {
+            return Err(format!("Expected `PATTERN{SEPARATOR}REPLACEMENT`"));
+        };
+        if pattern.is_empty() {
+            return Err("Pattern must not be empty".to_owned());
+        }
+        let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+        Ok(Self {
+            regex,
+            replacement: replacement.to_owned(),
+        })
+    }
+
+    /// Apply the substitution to every match in the name.
+    #[must_use]
+    pub fn apply(&self, name: &str) -> String {
+        self.regex
+            .replace_all(name, self.replacement.as_str())
+            .into_owned()
+    }
+
+    /// Apply each substitution in order.
+    #[must_use]
+    pub fn apply_all(substitutions: &[NameSubstitution], name: &str) -> String {
+        substitutions
+            .iter()
+            .fold(name.to_owned(), |name, substitution| {
+                substitution.apply(&name)
+            })
+    }
+}
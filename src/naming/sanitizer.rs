@@ -1,3 +1,5 @@
+use std::path::{Component, Path, PathBuf};
+
 const NON_BREAKING_SPACE: char = '\u{00A0}';
 const ZERO_WIDTH_SPACE: char = '\u{200B}';
 #[allow(dead_code)]
@@ -36,6 +38,24 @@ const RESTRICTED: [char; 16] = [
 const RESTRICTED_DIVIDERS: [char; 5] = ['/', '\\', '|', EN_DASH, EM_DASH];
 const DIVIDER_REPLACEMENT: char = '-';
 
+/// Characters that are not permitted in a Windows file name.
+const WINDOWS_RESTRICTED: [char; 6] = [':', '<', '>', '"', '?', '*'];
+
+/// Characters that are not permitted in a Windows file name but can be replaced by a divider.
+const WINDOWS_RESTRICTED_DIVIDERS: [char; 3] = ['/', '\\', '|'];
+
+/// Characters that are removed from the end of a Windows file name.
+const WINDOWS_TRAILING: [char; 2] = ['.', ' '];
+
+/// Device names reserved by Windows regardless of the extension.
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Appended to a reserved device name, or used in place of an empty name.
+const WINDOWS_RESERVED_SUFFIX: char = '_';
+
 pub struct Sanitizer;
 
 impl Sanitizer {
@@ -53,4 +73,82 @@ impl Sanitizer {
             })
             .collect()
     }
+
+    /// Make a file or directory name safe to open on Windows.
+    ///
+    /// Unlike [`Sanitizer::execute`] only the characters that Windows does not permit are
+    /// changed so it's suitable for names copied from the source, and it should be applied
+    /// after [`Sanitizer::execute`] for names that are generated.
+    ///
+    /// - Characters not permitted by Windows and control characters are removed
+    /// - `/`, `\` and `|` are replaced by `-`
+    /// - Trailing dots and spaces are removed
+    /// - Reserved device names such as `CON` or `LPT1.txt` are suffixed with `_`
+    #[must_use]
+    pub fn execute_windows(input: &str) -> String {
+        let output: String = input
+            .chars()
+            .filter(|x| !WINDOWS_RESTRICTED.contains(x) && !x.is_control())
+            .map(|x| {
+                if WINDOWS_RESTRICTED_DIVIDERS.contains(&x) {
+                    DIVIDER_REPLACEMENT
+                } else {
+                    x
+                }
+            })
+            .collect();
+        let mut output = output.trim_end_matches(WINDOWS_TRAILING).to_owned();
+        if output.is_empty() {
+            return WINDOWS_RESERVED_SUFFIX.to_string();
+        }
+        let stem_length = output.find('.').unwrap_or(output.len());
+        let stem = output.get(..stem_length).unwrap_or_default();
+        if is_reserved_name(stem) {
+            output.insert(stem.trim_end().len(), WINDOWS_RESERVED_SUFFIX);
+        }
+        output
+    }
+
+    /// Remove leading spaces and replace each run of spaces with a single space.
+    #[must_use]
+    pub fn execute_spaces(input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        for x in input.trim_start_matches(' ').chars() {
+            if x == ' ' && output.ends_with(' ') {
+                continue;
+            }
+            output.push(x);
+        }
+        output
+    }
+
+    /// Make every component of a relative path safe to open on Windows.
+    #[must_use]
+    pub fn execute_windows_path(path: &Path) -> PathBuf {
+        path.components()
+            .map(|component| match component {
+                Component::Normal(name) => {
+                    PathBuf::from(Self::execute_windows(&name.to_string_lossy()))
+                }
+                _ => PathBuf::from(component.as_os_str()),
+            })
+            .collect()
+    }
+
+    /// Check if every component of a relative path is safe to open on Windows.
+    #[must_use]
+    pub fn is_windows_safe(path: &Path) -> bool {
+        path.components().all(|component| match component {
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                Self::execute_windows(&name) == name
+            }
+            _ => true,
+        })
+    }
+}
+
+fn is_reserved_name(stem: &str) -> bool {
+    let stem = stem.trim_end().to_uppercase();
+    WINDOWS_RESERVED_NAMES.contains(&stem.as_str())
 }
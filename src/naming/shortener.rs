@@ -1,13 +1,32 @@
 use crate::fs::FlacFile;
-use crate::naming::{SourceName, TrackName};
+use crate::naming::{Sanitizer, SourceName, TrackName};
 use crate::source::{Metadata, Source};
 use colored::Colorize;
 use log::info;
 use regex::Regex;
 use rogue_logging::Colors;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 pub struct Shortener;
 
+/// A name in the output that was shortened by `auto_shorten`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ShortenedName {
+    pub before: String,
+    pub after: String,
+}
+
+impl ShortenedName {
+    #[must_use]
+    pub fn new(before: &Path, after: &Path) -> Self {
+        Self {
+            before: before.to_string_lossy().to_string(),
+            after: after.to_string_lossy().to_string(),
+        }
+    }
+}
+
 impl Shortener {
     #[must_use]
     pub fn shorten_album(metadata: &Metadata) -> Option<Metadata> {
@@ -24,7 +43,8 @@ impl Shortener {
 
     pub fn suggest_track_name(flac: &FlacFile) {
         if let Some(file_name) = TrackName::get(flac) {
-            let difference = flac.file_name.len() - file_name.len();
+            let current = Sanitizer::execute_windows(&flac.file_name);
+            let difference = current.len().saturating_sub(file_name.len());
             if difference > 0 {
                 info!(
                     "{} track could save {difference} characters: {}",
@@ -39,7 +59,7 @@ impl Shortener {
         if let Some(shortened) = Shortener::shorten_album(&source.metadata) {
             let before = SourceName::get(&source.metadata);
             let after = SourceName::get(&shortened);
-            let difference = before.len() - after.len();
+            let difference = before.len().saturating_sub(after.len());
             if difference > 0 {
                 info!(
                     "{} directory could save {difference} characters: {}",
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use crate::naming::layout_template::{get_initial, get_placeholders, replace_placeholders_with};
+use crate::naming::{Sanitizer, SourceName, SpectrogramName};
+use crate::source::Metadata;
+
+/// Placeholders that can be used in a [`SpectrogramLayout`].
+pub const SPECTROGRAM_PLACEHOLDERS: [&str; 8] = [
+    "artist",
+    "artist_initial",
+    "album",
+    "remaster_title",
+    "year",
+    "media",
+    "source",
+    "spectrograms",
+];
+
+/// First directory of a [`SpectrogramLayout`] that is resolved to the directory containing
+/// the source rather than the output directory.
+pub const SOURCE_PARENT: &str = "{source_parent}";
+
+/// Resolve a spectrogram directory template such as `spectrograms/{artist} - {album}`.
+///
+/// Each `/` separated part of the template is a directory, relative to the output directory
+/// unless the first is `{source_parent}`.
+///
+/// Placeholders:
+/// - `{artist}`, `{album}`, `{remaster_title}`, `{year}`, `{media}` from the source
+/// - `{artist_initial}` first letter of the artist in upper case, or `#`
+/// - `{source}` default source name such as `Artist - Album [2012]`
+/// - `{spectrograms}` default spectrogram name such as `Artist - Album [2012] [WEB SPECTROGRAMS]`
+pub struct SpectrogramLayout;
+
+impl SpectrogramLayout {
+    /// Check the template can be resolved.
+    ///
+    /// Returns a description of the first problem found.
+    pub fn validate(template: &str) -> Result<(), String> {
+        if template.starts_with('/') || template.starts_with('\\') {
+            return Err(
+                "Template must be relative to the output directory or start with `{source_parent}`"
+                    .to_owned(),
+            );
+        }
+        let parts = get_parts(template);
+        if parts.is_empty() {
+            return Err(
+                "Template must have a directory inside `{source_parent}` so the source is not changed"
+                    .to_owned(),
+            );
+        }
+        for part in parts {
+            if part.trim().is_empty() || part == "." || part == ".." {
+                return Err(format!("Template has an invalid directory: `{part}`"));
+            }
+            for placeholder in get_placeholders(part)? {
+                if !SPECTROGRAM_PLACEHOLDERS.contains(&placeholder) {
+                    return Err(format!(
+                        "Unknown placeholder `{{{placeholder}}}`. Expected one of: {}",
+                        SPECTROGRAM_PLACEHOLDERS.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Is the template relative to the directory containing the source?
+    #[must_use]
+    pub fn is_source_relative(template: &str) -> bool {
+        template.split('/').next() == Some(SOURCE_PARENT)
+    }
+
+    /// Get the path of the spectrogram directory relative to the output directory, or to
+    /// the directory containing the source if [`SpectrogramLayout::is_source_relative`].
+    ///
+    /// Each directory is sanitized after the placeholders are replaced so a `/` in a value
+    /// does not create a directory.
+    #[must_use]
+    pub fn get(template: &str, metadata: &Metadata) -> PathBuf {
+        get_parts(template)
+            .into_iter()
+            .map(|part| {
+                let name =
+                    replace_placeholders_with(part, |placeholder| get_value(placeholder, metadata));
+                Sanitizer::execute_windows(&Sanitizer::execute(name))
+            })
+            .collect()
+    }
+}
+
+/// Get the directories of the template without a leading `{source_parent}`.
+fn get_parts(template: &str) -> Vec<&str> {
+    let mut parts: Vec<&str> = template.split('/').collect();
+    if parts.first() == Some(&SOURCE_PARENT) {
+        parts.remove(0);
+    }
+    parts
+}
+
+fn get_value(placeholder: &str, metadata: &Metadata) -> String {
+    match placeholder {
+        "artist" => metadata.artist.clone(),
+        "artist_initial" => get_initial(&metadata.artist),
+        "album" => metadata.album.clone(),
+        "remaster_title" => metadata.remaster_title.clone(),
+        "year" => metadata.year.to_string(),
+        "media" => metadata.media.clone(),
+        "source" => SourceName::get(metadata),
+        "spectrograms" => SpectrogramName::get(metadata),
+        _ => format!("{{{placeholder}}}"),
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+
+use crate::naming::CharacterWhitelist;
+
+const ALLOWED: &str = r"[\p{L}\p{N} ._()\[\]&,'!-]";
+
+#[test]
+fn character_whitelist_execute() {
+    // Arrange
+    let replacements = vec!["&=>and".to_owned(), "#=>No.".to_owned()];
+    let whitelist =
+        CharacterWhitelist::new(Some(ALLOWED), &replacements).expect("should be valid");
+
+    // Act
+    let replaced = whitelist.execute("Salt & Pepper #1 * Bonus?");
+    let disallowed = whitelist.get_disallowed("Salt & Pepper #1 * Bonus?");
+    let path = whitelist.execute_path(Path::new("A@B/01 Track.flac"));
+
+    // Assert
+    assert_eq!(replaced, "Salt and Pepper No.1 _ Bonus_");
+    assert_eq!(disallowed, vec!['&', '#', '*', '?']);
+    assert_eq!(path, PathBuf::from("A_B/01 Track.flac"));
+}
+
+#[test]
+fn character_whitelist_default_allows_all() {
+    // Arrange
+    let whitelist = CharacterWhitelist::default();
+
+    // Act
+    let output = whitelist.execute("Album & Title?");
+
+    // Assert
+    assert_eq!(output, "Album & Title?");
+    assert!(whitelist.get_disallowed("Album & Title?").is_empty());
+}
+
+#[test]
+fn character_whitelist_new_invalid() {
+    // Act
+    let regex = CharacterWhitelist::new(Some("[a-z"), &[]);
+    let separator = CharacterWhitelist::new(None, &["&and".to_owned()]);
+    let multiple = CharacterWhitelist::new(None, &["&&=>and".to_owned()]);
+    let disallowed = CharacterWhitelist::new(Some("[a-z]"), &["&=>AND".to_owned()]);
+
+    // Assert
+    assert!(regex.is_err());
+    assert!(separator.is_err());
+    assert!(multiple.is_err());
+    assert!(disallowed.is_err());
+}
@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use crate::formats::TargetFormat;
+use crate::naming::{LayoutTemplate, TranscodeName};
+use crate::source::Metadata;
+
+fn get_metadata() -> Metadata {
+    Metadata {
+        artist: "the Artist".to_owned(),
+        album: "Album: Part 1/2".to_owned(),
+        remaster_title: String::new(),
+        year: 2012,
+        media: "WEB".to_owned(),
+    }
+}
+
+#[test]
+fn layout_template_get() {
+    // Arrange
+    let metadata = get_metadata();
+
+    // Act
+    let path = LayoutTemplate::get(
+        "{artist_initial}/{artist}/{album} ({year}) [{format}]",
+        &metadata,
+        TargetFormat::V0,
+    );
+
+    // Assert
+    assert_eq!(
+        path,
+        PathBuf::from("T/the Artist/Album Part 1-2 (2012) [V0]")
+    );
+}
+
+#[test]
+fn layout_template_get_default() {
+    // Arrange
+    let metadata = get_metadata();
+
+    // Act
+    let path = LayoutTemplate::get("{name}", &metadata, TargetFormat::Flac);
+
+    // Assert
+    assert_eq!(
+        path,
+        PathBuf::from(TranscodeName::get(&metadata, TargetFormat::Flac))
+    );
+}
+
+#[test]
+fn layout_template_get_does_not_replace_values() {
+    // Arrange
+    let metadata = Metadata {
+        artist: "2{year}".to_owned(),
+        ..get_metadata()
+    };
+
+    // Act
+    let path = LayoutTemplate::get(
+        "{artist_initial}/{artist} [{format}]",
+        &metadata,
+        TargetFormat::_320,
+    );
+
+    // Assert
+    assert_eq!(path, PathBuf::from("#/2{year} [320]"));
+}
+
+#[test]
+fn layout_template_validate() {
+    // Act
+    let valid = LayoutTemplate::validate("{artist_initial}/{artist}/{album} [{format}]");
+    let unknown = LayoutTemplate::validate("{artist}/{title} [{format}]");
+    let unclosed = LayoutTemplate::validate("{artist}/{album [{format}]");
+    let no_format = LayoutTemplate::validate("{artist}/{album}");
+    let absolute = LayoutTemplate::validate("/{artist}/{name}");
+    let parent = LayoutTemplate::validate("../{name}");
+
+    // Assert
+    assert!(valid.is_ok());
+    assert!(unknown.is_err());
+    assert!(unclosed.is_err());
+    assert!(no_format.is_err());
+    assert!(absolute.is_err());
+    assert!(parent.is_err());
+}
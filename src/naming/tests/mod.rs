@@ -1,3 +1,9 @@
+mod character_whitelist_tests;
 mod humanize_tests;
+mod layout_template_tests;
+mod name_substitution_tests;
 mod sanitizer_tests;
 mod shortener_tests;
+mod spectrogram_layout_tests;
+mod transliterator_tests;
+mod unicode_form_tests;
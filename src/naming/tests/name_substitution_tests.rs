@@ -0,0 +1,41 @@
+use crate::naming::NameSubstitution;
+
+#[test]
+fn name_substitution_apply_all() {
+    // Arrange
+    let substitutions: Vec<NameSubstitution> = ["—=>-", r" \(feat\. [^)]*\)=>", r"\s{2,}=> "]
+        .iter()
+        .map(|x| NameSubstitution::parse(x).expect("should parse"))
+        .collect();
+
+    // Act
+    let name = NameSubstitution::apply_all(&substitutions, "01 Title — Part  Two (feat. Someone)");
+
+    // Assert
+    assert_eq!(name, "01 Title - Part Two");
+}
+
+#[test]
+fn name_substitution_capture_groups() {
+    // Arrange
+    let substitution = NameSubstitution::parse(r"^(\d+)\. =>$1 ").expect("should parse");
+
+    // Act
+    let name = substitution.apply("01. Title");
+
+    // Assert
+    assert_eq!(name, "01 Title");
+}
+
+#[test]
+fn name_substitution_parse_invalid() {
+    // Act
+    let no_separator = NameSubstitution::parse("feat.");
+    let empty = NameSubstitution::parse("=>-");
+    let invalid = NameSubstitution::parse("(feat=>");
+
+    // Assert
+    assert!(no_separator.is_err());
+    assert!(empty.is_err());
+    assert!(invalid.is_err());
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use crate::naming::*;
 
 #[test]
@@ -59,3 +61,59 @@ fn test_contains_valid_emoji() {
     // Assert
     assert_eq!(result, input);
 }
+
+#[test]
+fn execute_windows_removes_restricted_chars() {
+    // Act
+    let result = Sanitizer::execute_windows("01 Who? What: \"Why\" <*> A|B.flac");
+
+    // Assert
+    assert_eq!(result, "01 Who What Why  A-B.flac");
+}
+
+#[test]
+fn execute_windows_removes_trailing_dots_and_spaces() {
+    // Act
+    let result = Sanitizer::execute_windows("Artist - Album Vol. 2. . ");
+
+    // Assert
+    assert_eq!(result, "Artist - Album Vol. 2");
+}
+
+#[test]
+fn execute_windows_suffixes_reserved_names() {
+    // Act
+    let con = Sanitizer::execute_windows("con");
+    let lpt = Sanitizer::execute_windows("LPT1.log");
+    let spaced = Sanitizer::execute_windows("Aux .txt");
+    let console = Sanitizer::execute_windows("Console.txt");
+
+    // Assert
+    assert_eq!(con, "con_");
+    assert_eq!(lpt, "LPT1_.log");
+    assert_eq!(spaced, "Aux_ .txt");
+    assert_eq!(console, "Console.txt");
+}
+
+#[test]
+fn execute_windows_path() {
+    // Arrange
+    let path = PathBuf::from("CD1: Intro/NUL.");
+
+    // Act
+    let result = Sanitizer::execute_windows_path(&path);
+
+    // Assert
+    assert_eq!(result, PathBuf::from("CD1 Intro/NUL_"));
+    assert!(!Sanitizer::is_windows_safe(&path));
+    assert!(Sanitizer::is_windows_safe(&result));
+}
+
+#[test]
+fn execute_spaces() {
+    // Act
+    let result = Sanitizer::execute_spaces("  01  Intro   (Live) ");
+
+    // Assert
+    assert_eq!(result, "01 Intro (Live) ");
+}
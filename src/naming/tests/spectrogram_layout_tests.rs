@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use crate::naming::{SpectrogramLayout, SpectrogramName};
+use crate::source::Metadata;
+
+fn get_metadata() -> Metadata {
+    Metadata {
+        artist: "the Artist".to_owned(),
+        album: "Album: Part 1/2".to_owned(),
+        remaster_title: String::new(),
+        year: 2012,
+        media: "WEB".to_owned(),
+    }
+}
+
+#[test]
+fn spectrogram_layout_get() {
+    // Arrange
+    let metadata = get_metadata();
+
+    // Act
+    let path = SpectrogramLayout::get("spectrograms/{artist} - {album}", &metadata);
+
+    // Assert
+    assert_eq!(
+        path,
+        PathBuf::from("spectrograms/the Artist - Album Part 1-2")
+    );
+}
+
+#[test]
+fn spectrogram_layout_get_default() {
+    // Arrange
+    let metadata = get_metadata();
+
+    // Act
+    let path = SpectrogramLayout::get("{spectrograms}", &metadata);
+
+    // Assert
+    assert_eq!(path, PathBuf::from(SpectrogramName::get(&metadata)));
+    assert!(!SpectrogramLayout::is_source_relative("{spectrograms}"));
+}
+
+#[test]
+fn spectrogram_layout_get_source_parent() {
+    // Arrange
+    let metadata = get_metadata();
+    let template = "{source_parent}/{year} spectrograms";
+
+    // Act
+    let path = SpectrogramLayout::get(template, &metadata);
+
+    // Assert
+    assert_eq!(path, PathBuf::from("2012 spectrograms"));
+    assert!(SpectrogramLayout::is_source_relative(template));
+}
+
+#[test]
+fn spectrogram_layout_validate() {
+    // Act
+    let valid = SpectrogramLayout::validate("spectrograms/{artist_initial}/{spectrograms}");
+    let source_parent = SpectrogramLayout::validate("{source_parent}/{spectrograms}");
+    let only_source_parent = SpectrogramLayout::validate("{source_parent}");
+    let nested_source_parent = SpectrogramLayout::validate("{artist}/{source_parent}");
+    let unknown = SpectrogramLayout::validate("{artist}/{format}");
+    let absolute = SpectrogramLayout::validate("/{spectrograms}");
+    let parent = SpectrogramLayout::validate("../{spectrograms}");
+
+    // Assert
+    assert!(valid.is_ok());
+    assert!(source_parent.is_ok());
+    assert!(only_source_parent.is_err());
+    assert!(nested_source_parent.is_err());
+    assert!(unknown.is_err());
+    assert!(absolute.is_err());
+    assert!(parent.is_err());
+}
@@ -0,0 +1,33 @@
+use crate::naming::Transliterator;
+
+#[test]
+fn transliterator_latin() {
+    // Act
+    let name = Transliterator::execute("Björk – Medúlla (Æther Straße)");
+
+    // Assert
+    assert_eq!(name, "Bjork - Medulla (AEther Strasse)");
+}
+
+#[test]
+fn transliterator_cyrillic_and_greek() {
+    // Act
+    let cyrillic = Transliterator::execute("Кино - Группа крови");
+    let greek = Transliterator::execute("Βαγγέλης");
+
+    // Assert
+    assert_eq!(cyrillic, "Kino - Gruppa krovi");
+    assert_eq!(greek, "Baggelis");
+}
+
+#[test]
+fn transliterator_compatibility_and_unknown() {
+    // Act
+    let full_width = Transliterator::execute("ＡＢＣ１２３");
+    let unknown = Transliterator::execute("坂本龍一 - Ｂ-2 Unit");
+
+    // Assert
+    assert_eq!(full_width, "ABC123");
+    assert_eq!(unknown, "____ - B-2 Unit");
+    assert!(unknown.is_ascii());
+}
@@ -0,0 +1,55 @@
+use std::fs::{create_dir_all, File};
+use std::path::{Path, PathBuf};
+
+use crate::naming::*;
+use crate::testing::TempDirectory;
+
+const NFC: &str = "Bj\u{00F6}rk - Homog\u{00E9}nic";
+const NFD: &str = "Bjo\u{0308}rk - Homoge\u{0301}nic";
+
+#[test]
+fn unicode_form_normalize() {
+    // Act
+    let nfc = UnicodeForm::Nfc.normalize(NFD);
+    let nfd = UnicodeForm::Nfd.normalize(NFC);
+
+    // Assert
+    assert_eq!(nfc, NFC);
+    assert_eq!(nfd, NFD);
+}
+
+#[test]
+fn unicode_form_normalize_path() {
+    // Arrange
+    let path = PathBuf::from(format!("{NFD}/CD1/01 {NFD}.flac"));
+
+    // Act
+    let result = UnicodeForm::Nfc.normalize_path(&path);
+
+    // Assert
+    assert_eq!(result, PathBuf::from(format!("{NFC}/CD1/01 {NFC}.flac")));
+}
+
+#[test]
+fn is_normalized_equal_ignores_form() {
+    // Assert
+    assert!(is_normalized_equal(NFC, NFD));
+    assert!(!is_normalized_equal(NFC, "Bjork - Homogenic"));
+}
+
+#[test]
+fn resolve_normalized_finds_other_form() {
+    // Arrange
+    let base = TempDirectory::create("unicode_form");
+    let sub_dir = base.join(NFD).join("CD1");
+    create_dir_all(&sub_dir).expect("should create directory");
+    File::create(sub_dir.join(format!("01 {NFD}.flac"))).expect("should create file");
+
+    // Act
+    let found = resolve_normalized(&base, &Path::new(NFC).join(format!("CD1/01 {NFC}.flac")));
+    let missing = resolve_normalized(&base, &Path::new(NFC).join("CD2"));
+
+    // Assert
+    assert_eq!(found, Some(sub_dir.join(format!("01 {NFD}.flac"))));
+    assert_eq!(missing, None);
+}
@@ -11,6 +11,6 @@ impl TrackName {
         let track_number = tags.track()?;
         let title = tags.title()?;
         let file_name = format!("{track_number:0>2} {title}");
-        Some(Sanitizer::execute(file_name))
+        Some(Sanitizer::execute_windows(&Sanitizer::execute(file_name)))
     }
 }
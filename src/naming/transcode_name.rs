@@ -1,4 +1,4 @@
-use crate::formats::TargetFormat;
+use crate::formats::TranscodeTarget;
 use crate::naming::{Sanitizer, SourceName};
 use crate::source::Metadata;
 
@@ -6,11 +6,11 @@ pub struct TranscodeName;
 
 impl TranscodeName {
     #[must_use]
-    pub fn get(metadata: &Metadata, target: TargetFormat) -> String {
+    pub fn get(metadata: &Metadata, target: impl TranscodeTarget) -> String {
         let prefix = SourceName::get(metadata);
         let format = target.get_name();
         let media = metadata.media.clone();
         let name = format!("{prefix} [{media} {format}]");
-        Sanitizer::execute(name)
+        Sanitizer::execute_windows(&Sanitizer::execute(name))
     }
 }
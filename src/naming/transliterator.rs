@@ -0,0 +1,237 @@
+use std::path::{Component, Path, PathBuf};
+
+use icu_normalizer::DecomposingNormalizer;
+
+/// Used in place of a character that can't be transliterated.
+const UNKNOWN_REPLACEMENT: char = '_';
+
+/// Transliterate names to ASCII.
+///
+/// - Characters are decomposed so accents and other combining marks are removed
+/// - Compatibility characters such as full width forms and ligatures are replaced
+/// - Latin letters without a decomposition, Greek and Cyrillic are transliterated
+/// - Typographic punctuation is replaced by its ASCII equivalent
+/// - Any other character, such as CJK, is replaced by `_`
+pub struct Transliterator;
+
+impl Transliterator {
+    #[must_use]
+    pub fn execute(input: &str) -> String {
+        let decomposed = DecomposingNormalizer::new_nfkd().normalize(input);
+        let mut output = String::with_capacity(decomposed.len());
+        for character in decomposed.chars() {
+            if character.is_ascii() {
+                output.push(character);
+            } else if let Some(replacement) = get_replacement(character) {
+                output.push_str(replacement);
+            } else if !is_combining_mark(character) {
+                output.push(UNKNOWN_REPLACEMENT);
+            }
+        }
+        output
+    }
+
+    /// Transliterate every component of a relative path to ASCII.
+    #[must_use]
+    pub fn execute_path(path: &Path) -> PathBuf {
+        path.components()
+            .map(|component| match component {
+                Component::Normal(name) => PathBuf::from(Self::execute(&name.to_string_lossy())),
+                _ => PathBuf::from(component.as_os_str()),
+            })
+            .collect()
+    }
+}
+
+fn is_combining_mark(character: char) -> bool {
+    matches!(
+        character,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+fn get_replacement(character: char) -> Option<&'static str> {
+    match character {
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => Some("-"),
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{3000}' => Some(" "),
+        _ => REPLACEMENTS
+            .iter()
+            .find(|(from, _)| *from == character)
+            .map(|(_, to)| *to),
+    }
+}
+
+/// Replacements of characters that have no ASCII decomposition.
+const REPLACEMENTS: &[(char, &str)] = &[
+    // Latin
+    ('ß', "ss"),
+    ('æ', "ae"),
+    ('Æ', "AE"),
+    ('œ', "oe"),
+    ('Œ', "OE"),
+    ('ø', "o"),
+    ('Ø', "O"),
+    ('đ', "d"),
+    ('ð', "d"),
+    ('Đ', "D"),
+    ('Ð', "D"),
+    ('ł', "l"),
+    ('Ł', "L"),
+    ('þ', "th"),
+    ('Þ', "Th"),
+    ('ı', "i"),
+    ('ħ', "h"),
+    ('Ħ', "H"),
+    // Punctuation
+    ('\u{2018}', "'"),
+    ('\u{2019}', "'"),
+    ('\u{201A}', "'"),
+    ('\u{2032}', "'"),
+    ('\u{201C}', "\""),
+    ('\u{201D}', "\""),
+    ('\u{201E}', "\""),
+    ('\u{00AB}', "\""),
+    ('\u{00BB}', "\""),
+    ('\u{2033}', "\""),
+    ('\u{2026}', "..."),
+    ('\u{00B7}', "-"),
+    ('\u{2022}', "-"),
+    ('\u{30FB}', "-"),
+    ('\u{00D7}', "x"),
+    ('\u{3001}', ","),
+    ('\u{3002}', "."),
+    ('\u{300C}', "\""),
+    ('\u{300D}', "\""),
+    ('\u{300E}', "\""),
+    ('\u{300F}', "\""),
+    ('\u{3010}', "["),
+    ('\u{3011}', "]"),
+    ('\u{301C}', "~"),
+    // Greek
+    ('α', "a"),
+    ('Α', "A"),
+    ('β', "b"),
+    ('Β', "B"),
+    ('γ', "g"),
+    ('Γ', "G"),
+    ('δ', "d"),
+    ('Δ', "D"),
+    ('ε', "e"),
+    ('Ε', "E"),
+    ('ζ', "z"),
+    ('Ζ', "Z"),
+    ('η', "i"),
+    ('Η', "I"),
+    ('θ', "th"),
+    ('Θ', "Th"),
+    ('ι', "i"),
+    ('Ι', "I"),
+    ('κ', "k"),
+    ('Κ', "K"),
+    ('λ', "l"),
+    ('Λ', "L"),
+    ('μ', "m"),
+    ('Μ', "M"),
+    ('ν', "n"),
+    ('Ν', "N"),
+    ('ξ', "x"),
+    ('Ξ', "X"),
+    ('ο', "o"),
+    ('Ο', "O"),
+    ('π', "p"),
+    ('Π', "P"),
+    ('ρ', "r"),
+    ('Ρ', "R"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('Σ', "S"),
+    ('τ', "t"),
+    ('Τ', "T"),
+    ('υ', "y"),
+    ('Υ', "Y"),
+    ('φ', "f"),
+    ('Φ', "F"),
+    ('χ', "ch"),
+    ('Χ', "Ch"),
+    ('ψ', "ps"),
+    ('Ψ', "Ps"),
+    ('ω', "o"),
+    ('Ω', "O"),
+    // Cyrillic
+    ('а', "a"),
+    ('А', "A"),
+    ('б', "b"),
+    ('Б', "B"),
+    ('в', "v"),
+    ('В', "V"),
+    ('г', "g"),
+    ('Г', "G"),
+    ('ґ', "g"),
+    ('Ґ', "G"),
+    ('д', "d"),
+    ('Д', "D"),
+    ('е', "e"),
+    ('э', "e"),
+    ('Е', "E"),
+    ('Э', "E"),
+    ('є', "ye"),
+    ('Є', "Ye"),
+    ('ж', "zh"),
+    ('Ж', "Zh"),
+    ('з', "z"),
+    ('З', "Z"),
+    ('и', "i"),
+    ('ы', "y"),
+    ('И', "I"),
+    ('Ы', "Y"),
+    ('і', "i"),
+    ('І', "I"),
+    ('ї', "yi"),
+    ('Ї', "Yi"),
+    ('й', "y"),
+    ('Й', "Y"),
+    ('к', "k"),
+    ('К', "K"),
+    ('л', "l"),
+    ('Л', "L"),
+    ('м', "m"),
+    ('М', "M"),
+    ('н', "n"),
+    ('Н', "N"),
+    ('о', "o"),
+    ('О', "O"),
+    ('п', "p"),
+    ('П', "P"),
+    ('р', "r"),
+    ('Р', "R"),
+    ('с', "s"),
+    ('С', "S"),
+    ('т', "t"),
+    ('Т', "T"),
+    ('у', "u"),
+    ('У', "U"),
+    ('ф', "f"),
+    ('Ф', "F"),
+    ('х', "kh"),
+    ('Х', "Kh"),
+    ('ц', "ts"),
+    ('Ц', "Ts"),
+    ('ч', "ch"),
+    ('Ч', "Ch"),
+    ('ш', "sh"),
+    ('Ш', "Sh"),
+    ('щ', "shch"),
+    ('Щ', "Shch"),
+    ('ъ', ""),
+    ('Ъ', ""),
+    ('ь', ""),
+    ('Ь', ""),
+    ('ю', "yu"),
+    ('Ю', "Yu"),
+    ('я', "ya"),
+    ('Я', "Ya"),
+];
@@ -0,0 +1,75 @@
+use std::fs::read_dir;
+use std::path::{Component, Path, PathBuf};
+
+use clap::ValueEnum;
+use icu_normalizer::{ComposingNormalizer, DecomposingNormalizer};
+use serde::{Deserialize, Serialize};
+
+/// Unicode normalization form of file and directory names.
+///
+/// macOS typically writes names in NFD whereas Linux and the indexers use the
+/// name as given, which is typically NFC.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum UnicodeForm {
+    /// Canonical composition, as used by most Linux systems and the indexers.
+    #[default]
+    Nfc,
+    /// Canonical decomposition, as used by macOS.
+    Nfd,
+}
+
+impl UnicodeForm {
+    /// Normalize a name to the form.
+    #[must_use]
+    pub fn normalize(self, name: &str) -> String {
+        match self {
+            UnicodeForm::Nfc => ComposingNormalizer::new_nfc().normalize(name),
+            UnicodeForm::Nfd => DecomposingNormalizer::new_nfd().normalize(name),
+        }
+    }
+
+    /// Normalize every component of a relative path to the form.
+    #[must_use]
+    pub fn normalize_path(self, path: &Path) -> PathBuf {
+        path.components()
+            .map(|component| match component {
+                Component::Normal(name) => PathBuf::from(self.normalize(&name.to_string_lossy())),
+                _ => PathBuf::from(component.as_os_str()),
+            })
+            .collect()
+    }
+}
+
+/// Check if two names are equal once normalized.
+#[must_use]
+pub fn is_normalized_equal(a: &str, b: &str) -> bool {
+    a == b || UnicodeForm::Nfc.normalize(a) == UnicodeForm::Nfc.normalize(b)
+}
+
+/// Find the path of `relative` within `base` regardless of the normalization form
+/// of each name.
+///
+/// Returns `None` if any component can't be found.
+#[must_use]
+pub fn resolve_normalized(base: &Path, relative: &Path) -> Option<PathBuf> {
+    let mut path = base.to_path_buf();
+    for component in relative.components() {
+        let Component::Normal(name) = component else {
+            path.push(component.as_os_str());
+            continue;
+        };
+        let exact = path.join(name);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+        let name = name.to_string_lossy();
+        let entry = read_dir(&path)
+            .ok()?
+            .flatten()
+            .find(|entry| is_normalized_equal(&entry.file_name().to_string_lossy(), &name))?;
+        path = entry.path();
+    }
+    Some(path)
+}
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use rogue_logging::Error;
+use tokio::process::Command;
+
+use crate::built_info::PKG_NAME;
+use crate::cli::CommandArguments;
+use crate::dependencies::DESKTOP_NOTIFIER;
+use crate::errors::{command_error, OutputHandler};
+use crate::logging::remove_colors;
+use crate::options::{DesktopOptions, OptionRule, Options};
+use crate::summary::RunSummary;
+
+/// Show a desktop notification when a long running command finishes or fails.
+///
+/// Notifications are only shown if `desktop_notify` is set and the command ran for
+/// at least `desktop_notify_after`.
+pub struct DesktopNotifier {
+    options: Ref<DesktopOptions>,
+    summary: Ref<RunSummary>,
+}
+
+#[injectable]
+impl DesktopNotifier {
+    #[must_use]
+    pub fn new(options: Ref<DesktopOptions>, summary: Ref<RunSummary>) -> Self {
+        Self { options, summary }
+    }
+
+    /// Get the name of a command that can show a desktop notification.
+    #[must_use]
+    pub fn get_command_name(command: &CommandArguments) -> Option<&'static str> {
+        match command {
+            CommandArguments::Batch { .. } => Some("Batch"),
+            CommandArguments::Spectrogram { .. } => Some("Spectrogram"),
+            CommandArguments::Transcode { .. } => Some("Transcode"),
+            CommandArguments::Upload { .. } => Some("Upload"),
+            CommandArguments::Verify { .. } => Some("Verify"),
+            _ => None,
+        }
+    }
+
+    /// Validate the [`DesktopOptions`].
+    pub fn check_options(&self) -> Result<(), Error> {
+        OptionRule::check(&self.options.get_errors())
+    }
+
+    /// Show a desktop notification with the result of a command.
+    ///
+    /// Failures are logged as warnings but don't change the result of the command.
+    pub async fn notify(&self, name: &str, result: &Result<bool, Error>) {
+        if self.options.desktop_notify != Some(true) {
+            return;
+        }
+        let elapsed = self.summary.get_elapsed();
+        let after = self
+            .options
+            .get_desktop_notify_after()
+            .expect("desktop_notify_after should be set");
+        if elapsed < after {
+            trace!(
+                "{} desktop notification as {name} finished within {}",
+                "Skipped".bold(),
+                humantime::format_duration(after)
+            );
+            return;
+        }
+        let summary = self
+            .summary
+            .get_lines()
+            .first()
+            .map(|line| remove_colors(line));
+        let (title, body) = get_desktop_message(name, elapsed, result, summary.as_deref());
+        if let Err(error) = show(&title, &body).await {
+            warn!(
+                "{} to show desktop notification: {}",
+                "Failed".bold(),
+                error.message
+            );
+        } else {
+            trace!("{} desktop notification", "Sent".bold());
+        }
+    }
+}
+
+/// Get the title and body of the desktop notification for the result of a command.
+#[must_use]
+pub fn get_desktop_message(
+    name: &str,
+    elapsed: Duration,
+    result: &Result<bool, Error>,
+    summary: Option<&str>,
+) -> (String, String) {
+    let elapsed = humantime::format_duration(elapsed);
+    let (title, mut body) = match result {
+        Ok(true) => (
+            format!("{PKG_NAME} {name} finished"),
+            format!("Completed in {elapsed}"),
+        ),
+        Ok(false) => (
+            format!("{PKG_NAME} {name} failed"),
+            format!("Completed with failures in {elapsed}"),
+        ),
+        Err(error) => (
+            format!("{PKG_NAME} {name} failed"),
+            format!(
+                "Failed to {} after {elapsed}: {}",
+                error.action, error.message
+            ),
+        ),
+    };
+    if let Some(summary) = summary {
+        body.push('\n');
+        body.push_str(summary);
+    }
+    (title, body)
+}
+
+async fn show(title: &str, body: &str) -> Result<(), Error> {
+    let action = "show desktop notification";
+    let mut command = Command::new(DESKTOP_NOTIFIER);
+    if cfg!(target_os = "macos") {
+        command.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"{}\"",
+            escape_apple_script(body),
+            escape_apple_script(title)
+        ));
+    } else {
+        command.arg("--app-name").arg(PKG_NAME).arg(title).arg(body);
+    }
+    let output = command
+        .output()
+        .await
+        .map_err(|e| command_error(e, action, DESKTOP_NOTIFIER))?;
+    OutputHandler::execute(output, action, DESKTOP_NOTIFIER)?;
+    Ok(())
+}
+
+fn escape_apple_script(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
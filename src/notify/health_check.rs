@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use reqwest::Client;
+use rogue_logging::Error;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::cli::CommandArguments;
+use crate::options::NotifyOptions;
+
+/// Longest duration to wait for a ping so an unavailable service doesn't delay the run.
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A signal sent to a healthcheck service.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HealthPing {
+    /// The run started.
+    Start,
+    /// The run succeeded, or a long running command is still working.
+    Success,
+    /// The run failed.
+    Fail,
+}
+
+impl HealthPing {
+    /// Get the URL to ping following the convention of Healthchecks.io.
+    #[must_use]
+    pub fn get_url(self, base: &str) -> String {
+        let base = base.trim_end_matches('/');
+        match self {
+            HealthPing::Start => format!("{base}/start"),
+            HealthPing::Success => base.to_owned(),
+            HealthPing::Fail => format!("{base}/fail"),
+        }
+    }
+}
+
+/// Ping a healthcheck URL at the start, success and failure of each run so a dead man's
+/// switch such as Healthchecks.io alerts when a scheduled pipeline stops working.
+pub struct HealthCheck {
+    options: Ref<NotifyOptions>,
+    client: Client,
+}
+
+#[injectable]
+impl HealthCheck {
+    #[must_use]
+    pub fn new(options: Ref<NotifyOptions>) -> Self {
+        Self {
+            options,
+            client: Client::new(),
+        }
+    }
+
+    /// Is the command pinged if `healthcheck_url` is set?
+    #[must_use]
+    pub fn is_supported(command: &CommandArguments) -> bool {
+        matches!(
+            command,
+            CommandArguments::Batch { .. }
+                | CommandArguments::Transcode { .. }
+                | CommandArguments::Upload { .. }
+                | CommandArguments::Watch { .. }
+                | CommandArguments::Serve { .. }
+        )
+    }
+
+    /// Ping the result of a command.
+    ///
+    /// The message of an [`Error`] is sent as the body of the failure.
+    pub async fn ping_result(&self, result: &Result<bool, Error>) {
+        match result {
+            Ok(true) => self.ping(HealthPing::Success, String::new()).await,
+            Ok(false) => {
+                self.ping(HealthPing::Fail, "Command was unsuccessful".to_owned())
+                    .await;
+            }
+            Err(error) => self.ping(HealthPing::Fail, error.to_string()).await,
+        }
+    }
+
+    /// Ping the healthcheck URL if `healthcheck_url` is set.
+    ///
+    /// Failures are logged as warnings but don't stop the command.
+    pub async fn ping(&self, ping: HealthPing, body: String) {
+        let Some(base) = &self.options.healthcheck_url else {
+            return;
+        };
+        let url = ping.get_url(base);
+        let result = self
+            .client
+            .post(&url)
+            .header(
+                "User-Agent",
+                format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+            )
+            .timeout(PING_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        match result {
+            Ok(_) => trace!("{} healthcheck {ping:?}", "Pinged".bold()),
+            Err(error) => warn!("{} to ping healthcheck: {error}", "Failed".bold()),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub use desktop_notifier::*;
+pub use health_check::*;
+pub use notification::*;
+pub use notifier::*;
+pub use notify_event::*;
+pub use webhook::*;
+
+pub(crate) mod desktop_notifier;
+pub(crate) mod health_check;
+pub(crate) mod notification;
+pub(crate) mod notifier;
+pub(crate) mod notify_event;
+#[cfg(test)]
+mod tests;
+pub(crate) mod webhook;
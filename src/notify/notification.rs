@@ -0,0 +1,23 @@
+use crate::logging::get_log_source;
+use crate::notify::NotifyEvent;
+
+/// A message to send to each webhook.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub event: NotifyEvent,
+    /// Id of the source being processed.
+    pub source: Option<String>,
+    pub message: String,
+}
+
+impl Notification {
+    /// Create a [`Notification`] for the source currently being processed.
+    #[must_use]
+    pub fn new(event: NotifyEvent, message: String) -> Self {
+        Self {
+            event,
+            source: get_log_source(),
+            message,
+        }
+    }
+}
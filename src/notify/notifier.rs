@@ -0,0 +1,69 @@
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use reqwest::Client;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::notify::*;
+use crate::options::NotifyOptions;
+
+/// Post a [`Notification`] to each webhook of `notify_url`.
+pub struct Notifier {
+    options: Ref<NotifyOptions>,
+    client: Client,
+}
+
+#[injectable]
+impl Notifier {
+    #[must_use]
+    pub fn new(options: Ref<NotifyOptions>) -> Self {
+        Self {
+            options,
+            client: Client::new(),
+        }
+    }
+
+    /// Post a [`Notification`] to each webhook if the event is in `notify_on`.
+    ///
+    /// Failures are logged as warnings but don't stop the command.
+    pub async fn notify(&self, event: NotifyEvent, message: String) {
+        let urls = self.options.notify_url.clone().unwrap_or_default();
+        if urls.is_empty() {
+            return;
+        }
+        let events = self.options.notify_on.clone().unwrap_or_default();
+        if !events.contains(&event) {
+            trace!("{} {event} notification due to settings", "Skipped".bold());
+            return;
+        }
+        let notification = Notification::new(event, message);
+        let template = self
+            .options
+            .notify_template
+            .clone()
+            .unwrap_or_else(|| DEFAULT_TEMPLATE.to_owned());
+        for url in urls {
+            let payload = Webhook::from_url(&url).get_payload(&notification, &template);
+            if let Err(error) = self.post(&url, payload).await {
+                warn!("{} to send {event} notification: {error}", "Failed".bold());
+            } else {
+                trace!("{} {event} notification", "Sent".bold());
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, payload: String) -> Result<(), reqwest::Error> {
+        self.client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header(
+                "User-Agent",
+                format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+            )
+            .body(payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
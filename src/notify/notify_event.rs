@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// An event that a notification can be sent for.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// Every track of a source was transcoded to the target formats.
+    TranscodeDone,
+    /// A transcode was uploaded.
+    UploadDone,
+    /// A batch completed but one or more sources failed to transcode or upload.
+    BatchFailed,
+}
+
+impl NotifyEvent {
+    /// Get the name of the event as it's serialized.
+    #[must_use]
+    pub fn get_name(self) -> &'static str {
+        match self {
+            NotifyEvent::TranscodeDone => "transcode_done",
+            NotifyEvent::UploadDone => "upload_done",
+            NotifyEvent::BatchFailed => "batch_failed",
+        }
+    }
+}
+
+impl Display for NotifyEvent {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.get_name())
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use rogue_logging::Error;
+
+use crate::notify::*;
+
+#[test]
+fn desktop_notifier_get_desktop_message() {
+    // Arrange
+    let elapsed = Duration::from_secs(125);
+    let error = Error {
+        action: "get source from options".to_owned(),
+        message: "Not found".to_owned(),
+        ..Error::default()
+    };
+
+    // Act
+    let finished = get_desktop_message(
+        "Verify",
+        elapsed,
+        &Ok(true),
+        Some("Sources: 1 processed, 1 verified, 0 skipped"),
+    );
+    let failures = get_desktop_message("Batch", elapsed, &Ok(false), None);
+    let failed = get_desktop_message("Transcode", elapsed, &Err(error), None);
+
+    // Assert
+    assert_eq!(
+        finished,
+        (
+            "caesura Verify finished".to_owned(),
+            "Completed in 2m 5s\nSources: 1 processed, 1 verified, 0 skipped".to_owned()
+        )
+    );
+    assert_eq!(
+        failures,
+        (
+            "caesura Batch failed".to_owned(),
+            "Completed with failures in 2m 5s".to_owned()
+        )
+    );
+    assert_eq!(
+        failed,
+        (
+            "caesura Transcode failed".to_owned(),
+            "Failed to get source from options after 2m 5s: Not found".to_owned()
+        )
+    );
+}
@@ -0,0 +1,28 @@
+use crate::notify::*;
+
+#[test]
+fn health_ping_get_url() {
+    // Arrange
+    let base = "https://hc-ping.com/eb095278-f28d-448d-87fb-7b75c171a6aa";
+
+    // Act
+    let start = HealthPing::Start.get_url(base);
+    let success = HealthPing::Success.get_url(base);
+    let fail = HealthPing::Fail.get_url(base);
+
+    // Assert
+    assert_eq!(start, format!("{base}/start"));
+    assert_eq!(success, base);
+    assert_eq!(fail, format!("{base}/fail"));
+}
+
+#[test]
+fn health_ping_get_url_trailing_slash() {
+    // Act
+    let start = HealthPing::Start.get_url("https://example.com/ping/abc/");
+    let success = HealthPing::Success.get_url("https://example.com/ping/abc/");
+
+    // Assert
+    assert_eq!(start, "https://example.com/ping/abc/start");
+    assert_eq!(success, "https://example.com/ping/abc");
+}
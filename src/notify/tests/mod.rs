@@ -0,0 +1,3 @@
+mod desktop_notifier_tests;
+mod health_check_tests;
+mod webhook_tests;
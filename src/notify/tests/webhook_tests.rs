@@ -0,0 +1,42 @@
+use crate::notify::*;
+
+#[test]
+fn webhook_from_url() {
+    // Act
+    let discord = Webhook::from_url("https://discord.com/api/webhooks/123/abc");
+    let slack = Webhook::from_url("https://hooks.slack.com/services/T00/B00/abc");
+    let telegram = Webhook::from_url("https://api.telegram.org/bot123:abc/sendMessage?chat_id=456");
+    let generic = Webhook::from_url("https://example.com/discord.com");
+
+    // Assert
+    assert_eq!(discord, Webhook::Discord);
+    assert_eq!(slack, Webhook::Slack);
+    assert_eq!(telegram, Webhook::Telegram);
+    assert_eq!(generic, Webhook::Generic);
+}
+
+#[test]
+fn webhook_get_payload() {
+    // Arrange
+    let notification = Notification {
+        event: NotifyEvent::UploadDone,
+        source: Some("4871992".to_owned()),
+        message: "Uploaded \"V0\"\nhttps://redacted.sh".to_owned(),
+    };
+
+    // Act
+    let discord = Webhook::Discord.get_payload(&notification, DEFAULT_TEMPLATE);
+    let slack = Webhook::Slack.get_payload(&notification, DEFAULT_TEMPLATE);
+    let generic = Webhook::Generic.get_payload(&notification, DEFAULT_TEMPLATE);
+
+    // Assert
+    assert_eq!(
+        discord,
+        r#"{"content":"Uploaded \"V0\"\nhttps://redacted.sh"}"#
+    );
+    assert_eq!(slack, r#"{"text":"Uploaded \"V0\"\nhttps://redacted.sh"}"#);
+    assert_eq!(
+        generic,
+        r#"{"event": "upload_done", "source": "4871992", "message": "Uploaded \"V0\"\nhttps://redacted.sh"}"#
+    );
+}
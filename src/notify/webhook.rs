@@ -0,0 +1,77 @@
+use serde_json::json;
+
+use crate::notify::Notification;
+
+/// Default payload of a [`Webhook::Generic`].
+pub const DEFAULT_TEMPLATE: &str =
+    r#"{"event": "{event}", "source": "{source}", "message": "{message}"}"#;
+
+/// A service that notifications are posted to.
+///
+/// The service is determined by the URL so only the URL needs to be configured.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Webhook {
+    /// A Discord channel webhook such as `https://discord.com/api/webhooks/123/abc`
+    Discord,
+    /// A Slack incoming webhook such as `https://hooks.slack.com/services/T00/B00/abc`
+    Slack,
+    /// The `sendMessage` method of the Telegram bot API such as
+    /// `https://api.telegram.org/bot123:abc/sendMessage?chat_id=456`
+    Telegram,
+    /// Any other URL which is sent the payload template.
+    Generic,
+}
+
+impl Webhook {
+    /// Determine the [`Webhook`] of a URL.
+    #[must_use]
+    pub fn from_url(url: &str) -> Self {
+        let host = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split(['/', '?'])
+            .next()
+            .unwrap_or_default();
+        match host {
+            "discord.com" | "discordapp.com" => Webhook::Discord,
+            "hooks.slack.com" => Webhook::Slack,
+            "api.telegram.org" => Webhook::Telegram,
+            _ => Webhook::Generic,
+        }
+    }
+
+    /// Get the JSON payload to post.
+    #[must_use]
+    pub fn get_payload(self, notification: &Notification, template: &str) -> String {
+        match self {
+            Webhook::Discord => json!({ "content": notification.message }).to_string(),
+            Webhook::Slack | Webhook::Telegram => {
+                json!({ "text": notification.message }).to_string()
+            }
+            Webhook::Generic => render_template(template, notification),
+        }
+    }
+}
+
+/// Replace the `{event}`, `{source}` and `{message}` placeholders of the template.
+///
+/// Values are escaped so they can be used inside a JSON string.
+#[must_use]
+pub fn render_template(template: &str, notification: &Notification) -> String {
+    template
+        .replace("{event}", notification.event.get_name())
+        .replace(
+            "{source}",
+            &escape(notification.source.as_deref().unwrap_or_default()),
+        )
+        .replace("{message}", &escape(&notification.message))
+}
+
+fn escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted
+        .strip_prefix('"')
+        .and_then(|x| x.strip_suffix('"'))
+        .unwrap_or_default()
+        .to_owned()
+}
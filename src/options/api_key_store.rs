@@ -0,0 +1,78 @@
+use keyring::Entry;
+use log::trace;
+use rogue_logging::Error;
+
+use crate::built_info::PKG_NAME;
+use crate::errors::keyring_error;
+
+/// Suffix of the credential store user of the session cookie for an indexer.
+const SESSION_COOKIE_SUFFIX: &str = ":session";
+
+/// Get the OS credential store entry of the API key for an indexer.
+///
+/// The service is the package name and the user is the indexer id
+/// so each indexer has its own API key.
+fn get_entry(indexer: &str) -> Result<Entry, Error> {
+    Entry::new(PKG_NAME, indexer).map_err(|e| keyring_error(e, "get credential store entry"))
+}
+
+/// Get the OS credential store entry of the session cookie for an indexer.
+///
+/// The user is the indexer id followed by [`SESSION_COOKIE_SUFFIX`] so it's separate from
+/// the API key.
+fn get_session_cookie_entry(indexer: &str) -> Result<Entry, Error> {
+    Entry::new(PKG_NAME, &format!("{indexer}{SESSION_COOKIE_SUFFIX}"))
+        .map_err(|e| keyring_error(e, "get credential store entry"))
+}
+
+/// Get the API key of an indexer from the OS credential store.
+///
+/// Returns `None` if no API key is stored or the credential store is unavailable.
+#[must_use]
+pub fn get_stored_api_key(indexer: &str) -> Option<String> {
+    let result = get_entry(indexer).and_then(|entry| {
+        entry
+            .get_password()
+            .map_err(|e| keyring_error(e, "get API key from credential store"))
+    });
+    match result {
+        Ok(api_key) => Some(api_key),
+        Err(error) => {
+            trace!("{error}");
+            None
+        }
+    }
+}
+
+/// Store the API key of an indexer in the OS credential store.
+pub fn store_api_key(indexer: &str, api_key: &str) -> Result<(), Error> {
+    get_entry(indexer)?
+        .set_password(api_key)
+        .map_err(|e| keyring_error(e, "store API key in credential store"))
+}
+
+/// Get the session cookie of an indexer from the OS credential store.
+///
+/// Returns `None` if no session cookie is stored or the credential store is unavailable.
+#[must_use]
+pub fn get_stored_session_cookie(indexer: &str) -> Option<String> {
+    let result = get_session_cookie_entry(indexer).and_then(|entry| {
+        entry
+            .get_password()
+            .map_err(|e| keyring_error(e, "get session cookie from credential store"))
+    });
+    match result {
+        Ok(cookie) => Some(cookie),
+        Err(error) => {
+            trace!("{error}");
+            None
+        }
+    }
+}
+
+/// Store the session cookie of an indexer in the OS credential store.
+pub fn store_session_cookie(indexer: &str, cookie: &str) -> Result<(), Error> {
+    get_session_cookie_entry(indexer)?
+        .set_password(cookie)
+        .map_err(|e| keyring_error(e, "store session cookie in credential store"))
+}
@@ -2,12 +2,15 @@ use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::batch::{BatchOrder, SourceCleanup};
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
 use crate::cli::QueueCommandArguments::List;
 use crate::options::{OptionRule, Options, OptionsProvider};
+use crate::torrent::parse_size;
 
 /// Options for [`BatchCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -57,6 +60,78 @@ pub struct BatchOptions {
     /// Default: `null`
     #[arg(long)]
     pub wait_before_upload: Option<String>,
+
+    /// Stop the batch before the next source once it has run for a duration.
+    ///
+    /// The duration is a string that can be parsed such as `30m`, `4h`, `1h30m`. The
+    /// source being processed is completed, and items that are not processed are left in
+    /// the queue for the next batch, so a batch can be scheduled in an off-peak window.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub max_duration: Option<String>,
+
+    /// Stop the batch once the transcodes and spectrograms it writes exceed a size.
+    ///
+    /// The size is checked before each source so the last source may exceed it. Items
+    /// that are not processed are left in the queue for the next batch.
+    ///
+    /// Examples: `500GiB`, `1TiB`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub max_disk_usage: Option<String>,
+
+    /// Stop the batch if the free space of the output directory falls below a size.
+    ///
+    /// The free space is checked before each source. Items that are not processed are
+    /// left in the queue for the next batch.
+    ///
+    /// Examples: `50GiB`, `512MiB`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub min_free_space: Option<String>,
+
+    /// Criteria to order the items of the batch by, so the most valuable transcodes are
+    /// completed first when `limit` or `max_disk_usage` ends the batch early.
+    ///
+    /// Each criteria breaks the ties of the previous, then items are ordered by name.
+    /// `smallest` and `oldest_snatch` are read from the torrent file of each item.
+    /// `fewest_seeders` and `most_formats` retrieve every queued source from the API
+    /// before the batch starts.
+    ///
+    /// Example: `most_formats --batch-order fewest_seeders`
+    ///
+    /// Default: `[name]`
+    #[arg(long, value_enum)]
+    pub batch_order: Option<Vec<BatchOrder>>,
+
+    /// What to do with the source directory once every enabled step of the batch
+    /// succeeded.
+    ///
+    /// `archive` moves the directory to `source_archive` and `delete` removes it, for
+    /// when only the transcodes are kept. The torrent client can no longer seed a
+    /// source that is moved or deleted. The action is confirmed once before the batch
+    /// starts unless `yes` is set.
+    ///
+    /// Default: `keep`
+    #[arg(long, value_enum)]
+    pub source_cleanup: Option<SourceCleanup>,
+
+    /// Directory to move each source directory to if `source_cleanup` is `archive`.
+    ///
+    /// Examples: `./archive`, `/mnt/cold/music`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub source_archive: Option<PathBuf>,
+
+    /// Should `source_cleanup` only log what would be archived or deleted?
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub source_cleanup_dry_run: Option<bool>,
 }
 
 #[injectable]
@@ -71,6 +146,22 @@ impl BatchOptions {
         humantime::parse_duration(wait_before_upload.as_str()).ok()
     }
 
+    #[must_use]
+    pub fn get_max_duration(&self) -> Option<Duration> {
+        let max_duration = self.max_duration.clone()?;
+        humantime::parse_duration(max_duration.as_str()).ok()
+    }
+
+    #[must_use]
+    pub fn get_max_disk_usage(&self) -> Option<u64> {
+        self.max_disk_usage.as_deref().and_then(parse_size)
+    }
+
+    #[must_use]
+    pub fn get_min_free_space(&self) -> Option<u64> {
+        self.min_free_space.as_deref().and_then(parse_size)
+    }
+
     #[must_use]
     pub fn get_limit(&self) -> Option<usize> {
         if self.no_limit == Some(true) {
@@ -109,6 +200,27 @@ impl Options for BatchOptions {
             self.wait_before_upload
                 .clone_from(&alternative.wait_before_upload);
         }
+        if self.max_duration.is_none() {
+            self.max_duration.clone_from(&alternative.max_duration);
+        }
+        if self.max_disk_usage.is_none() {
+            self.max_disk_usage.clone_from(&alternative.max_disk_usage);
+        }
+        if self.min_free_space.is_none() {
+            self.min_free_space.clone_from(&alternative.min_free_space);
+        }
+        if self.batch_order.is_none() {
+            self.batch_order.clone_from(&alternative.batch_order);
+        }
+        if self.source_cleanup.is_none() {
+            self.source_cleanup = alternative.source_cleanup;
+        }
+        if self.source_archive.is_none() {
+            self.source_archive.clone_from(&alternative.source_archive);
+        }
+        if self.source_cleanup_dry_run.is_none() {
+            self.source_cleanup_dry_run = alternative.source_cleanup_dry_run;
+        }
     }
 
     fn apply_defaults(&mut self) {
@@ -130,35 +242,88 @@ impl Options for BatchOptions {
         if self.no_limit.is_none() {
             self.no_limit = Some(false);
         }
+        if self.batch_order.is_none() {
+            self.batch_order = Some(vec![BatchOrder::Name]);
+        }
+        if self.source_cleanup.is_none() {
+            self.source_cleanup = Some(SourceCleanup::Keep);
+        }
+        if self.source_cleanup_dry_run.is_none() {
+            self.source_cleanup_dry_run = Some(false);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if let Some(wait_before_upload) = &self.wait_before_upload {
             if self.get_wait_before_upload().is_none() {
                 errors.push(OptionRule::DurationInvalid(
-                    "Wait Before Upload".to_owned(),
+                    "wait_before_upload".to_owned(),
                     wait_before_upload.clone(),
                 ));
             }
         }
+        if let Some(max_duration) = &self.max_duration {
+            if self.get_max_duration().is_none() {
+                errors.push(OptionRule::DurationInvalid(
+                    "max_duration".to_owned(),
+                    max_duration.clone(),
+                ));
+            }
+        }
+        for (key, value) in [
+            ("max_disk_usage", &self.max_disk_usage),
+            ("min_free_space", &self.min_free_space),
+        ] {
+            if let Some(value) = value {
+                if parse_size(value).is_none() {
+                    errors.push(OptionRule::SizeInvalid(
+                        key.to_owned(),
+                        value.clone(),
+                        "Expected a size such as 512MiB or 10GiB".to_owned(),
+                    ));
+                }
+            }
+        }
         if self.upload == Some(true) && self.transcode != Some(true) {
             errors.push(OptionRule::Dependent(
-                "Upload".to_owned(),
-                "Transcode".to_owned(),
+                "upload".to_owned(),
+                "transcode".to_owned(),
+            ));
+        }
+        if self.source_cleanup == Some(SourceCleanup::Archive) {
+            match &self.source_archive {
+                Some(path) if !path.is_dir() => errors.push(OptionRule::DoesNotExist(
+                    "source_archive".to_owned(),
+                    path.to_string_lossy().to_string(),
+                )),
+                Some(_) => {}
+                None => errors.push(OptionRule::Dependent(
+                    "source_cleanup".to_owned(),
+                    "source_archive".to_owned(),
+                )),
+            }
+        }
+        if self
+            .source_cleanup
+            .is_some_and(|x| x != SourceCleanup::Keep)
+            && self.transcode != Some(true)
+        {
+            errors.push(OptionRule::Dependent(
+                "source_cleanup".to_owned(),
+                "transcode".to_owned(),
             ));
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
-    #[must_use]
     #[allow(clippy::manual_let_else)]
     fn from_args() -> Option<Self> {
         let options = match ArgumentsParser::get() {
             Some(
                 Batch { batch, .. }
+                | Watch { batch, .. }
+                | Serve { batch, .. }
                 | Queue {
                     command: List { batch, .. },
                 },
@@ -178,6 +343,9 @@ impl Options for BatchOptions {
         if options.upload == Some(false) {
             options.upload = None;
         }
+        if options.source_cleanup_dry_run == Some(false) {
+            options.source_cleanup_dry_run = None;
+        }
         Some(options)
     }
 
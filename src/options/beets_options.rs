@@ -0,0 +1,117 @@
+use std::fmt::{Display, Formatter};
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::beets::BeetsMode;
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::formats::TargetFormat;
+use crate::options::{IsEmpty, OptionRule, Options, OptionsProvider};
+
+/// Options for importing transcodes into a beets library with [`BeetsImporter`]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BeetsOptions {
+    /// Should transcodes be imported into a beets library?
+    ///
+    /// `print` logs a `beet import` command for each transcode and `import` runs it.
+    ///
+    /// The indexer, group id, torrent id and format are set as flexible attributes
+    /// so imported items can be queried with `beet ls caesura_torrent_id:123`.
+    ///
+    /// Default: `null`
+    #[arg(long, value_enum)]
+    pub beets: Option<BeetsMode>,
+
+    /// Formats to import into beets.
+    ///
+    /// Default: `v0`
+    #[arg(long)]
+    pub beets_formats: Option<Vec<TargetFormat>>,
+
+    /// Arguments passed to `beet import` before the transcode directory.
+    ///
+    /// The default imports the files without autotagging so the tags written by
+    /// caesura are kept.
+    ///
+    /// Default: `--quiet` and `--noautotag`
+    #[arg(long, allow_hyphen_values = true)]
+    pub beets_args: Option<Vec<String>>,
+}
+
+#[injectable]
+impl BeetsOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for BeetsOptions {
+    fn get_name() -> String {
+        "Beets Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.beets.is_none() {
+            self.beets = alternative.beets;
+        }
+        if self.beets_formats.is_none() {
+            self.beets_formats.clone_from(&alternative.beets_formats);
+        }
+        if self.beets_args.is_none() {
+            self.beets_args.clone_from(&alternative.beets_args);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.beets_formats.is_none() {
+            self.beets_formats = Some(vec![TargetFormat::V0]);
+        }
+        if self.beets_args.is_none() {
+            self.beets_args = Some(vec!["--quiet".to_owned(), "--noautotag".to_owned()]);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if self.beets.is_some() && self.beets_formats.as_ref().is_some_and(Vec::is_empty) {
+            errors.push(IsEmpty("beets_formats".to_owned()));
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(
+            Batch { beets, .. }
+            | Transcode { beets, .. }
+            | Watch { beets, .. }
+            | Serve { beets, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        Some(beets)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for BeetsOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -46,15 +46,14 @@ impl Options for CacheOptions {
         }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if let Some(cache) = &self.cache {
             if cache.ends_with(".json")
                 || (cache.eq(&PathBuf::from(DEFAULT_CACHE_PATH)) && !cache.is_dir())
             {
                 errors.push(Changed(
-                    "Cache Directory".to_owned(),
+                    "cache".to_owned(),
                     cache.to_string_lossy().to_string(),
                     "In v0.19.0 the cache format changed. A directory is now required.
 Please see the release notes for more details:
@@ -65,21 +64,28 @@ https://github.com/RogueOneEcho/caesura/releases/tag/v0.19.0"
 
             if !cache.is_dir() {
                 errors.push(DoesNotExist(
-                    "Cache Directory".to_owned(),
+                    "cache".to_owned(),
                     cache.to_string_lossy().to_string(),
                 ));
             }
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
-    #[must_use]
     #[allow(clippy::manual_let_else)]
     fn from_args() -> Option<Self> {
         match ArgumentsParser::get() {
             Some(
                 Batch { cache, .. }
+                | Clean { cache, .. }
+                | CrossSeed { cache, .. }
+                | Doctor { cache, .. }
+                | History { cache, .. }
+                | Transcode { cache, .. }
+                | Upload { cache, .. }
+                | Verify { cache, .. }
+                | Watch { cache, .. }
+                | Serve { cache, .. }
                 | Queue {
                     command: Add { cache, .. } | List { cache, .. } | Summary { cache, .. },
                 },
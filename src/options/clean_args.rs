@@ -0,0 +1,113 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use clap::{ArgAction, Args};
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::Clean;
+use crate::options::{DurationInvalid, OptionRule, Options, OptionsProvider};
+
+/// Options for the [`CleanCommand`]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CleanArgs {
+    /// Show what would be removed without removing anything.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub dry_run: Option<bool>,
+
+    /// Duration since a cache entry or staging directory was last modified before it's
+    /// removed.
+    ///
+    /// The duration is a string that can be parsed such as `12h`, `30d`, `1y`.
+    ///
+    /// Default: `30d`
+    #[arg(long)]
+    pub max_age: Option<String>,
+}
+
+#[injectable]
+impl CleanArgs {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl CleanArgs {
+    #[must_use]
+    pub fn get_max_age(&self) -> Option<Duration> {
+        let max_age = self.max_age.clone()?;
+        humantime::parse_duration(max_age.as_str()).ok()
+    }
+}
+
+impl Options for CleanArgs {
+    fn get_name() -> String {
+        "Clean Arguments".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.dry_run.is_none() {
+            self.dry_run = alternative.dry_run;
+        }
+        if self.max_age.is_none() {
+            self.max_age.clone_from(&alternative.max_age);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.dry_run.is_none() {
+            self.dry_run = Some(false);
+        }
+        if self.max_age.is_none() {
+            self.max_age = Some("30d".to_owned());
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(max_age) = &self.max_age {
+            if self.get_max_age().is_none() {
+                errors.push(DurationInvalid("max_age".to_owned(), max_age.clone()));
+            }
+        }
+        errors
+    }
+
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    fn from_args() -> Option<Self> {
+        match ArgumentsParser::get() {
+            Some(Clean { args, .. }) => {
+                let mut args = args;
+                if args.dry_run == Some(false) {
+                    args.dry_run = None;
+                }
+                Some(args)
+            }
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for CleanArgs {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -1,47 +1,106 @@
 use crate::errors::{json_error, yaml_error};
 use crate::options::*;
+use crate::summary::RunSummary;
 use di::{injectable, Ref};
 use rogue_logging::Error;
-use serde_json::Value;
-use std::collections::BTreeMap;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Config a FLAC source is suitable for transcoding.
 #[allow(clippy::struct_field_names)]
 #[injectable]
 pub struct ConfigCommand {
     batch_options: Ref<BatchOptions>,
+    beets_options: Ref<BeetsOptions>,
     cache_options: Ref<CacheOptions>,
+    cross_seed_options: Ref<CrossSeedOptions>,
+    desktop_options: Ref<DesktopOptions>,
+    enrich_options: Ref<EnrichOptions>,
     file_options: Ref<FileOptions>,
+    history_options: Ref<HistoryOptions>,
+    hook_options: Ref<HookOptions>,
+    notify_options: Ref<NotifyOptions>,
+    permission_options: Ref<PermissionOptions>,
     runner_options: Ref<RunnerOptions>,
+    serve_options: Ref<ServeOptions>,
     shared_options: Ref<SharedOptions>,
     spectrogram_options: Ref<SpectrogramOptions>,
     target_options: Ref<TargetOptions>,
+    torrent_options: Ref<TorrentOptions>,
     upload_options: Ref<UploadOptions>,
     verify_options: Ref<VerifyOptions>,
+    watch_options: Ref<WatchOptions>,
+    summary: Ref<RunSummary>,
+    provider: Ref<OptionsProvider>,
 }
 
 impl ConfigCommand {
-    pub fn execute(&self) -> Result<bool, Error> {
+    /// Print the effective value of every option.
+    ///
+    /// If `origin` is set then each value is annotated with where it came from.
+    pub fn execute(&self, origin: bool) -> Result<bool, Error> {
         let options = self
             .get_options_hashmap()
             .map_err(|e| json_error(e, "collate config"))?;
+        if origin {
+            return self.execute_origin(options);
+        }
+        if self.summary.is_json() {
+            self.summary.set_data(&options);
+            return Ok(true);
+        }
         let yaml =
             serde_yaml::to_string(&options).map_err(|e| yaml_error(e, "serialize config"))?;
         println!("{yaml}");
         Ok(true)
     }
 
+    fn execute_origin(&self, options: BTreeMap<String, Value>) -> Result<bool, Error> {
+        let cli = get_cli_keys().map_err(|e| json_error(e, "collate command line arguments"))?;
+        let resolver = self.provider.get_origin_resolver(cli);
+        if self.summary.is_json() {
+            let data: BTreeMap<String, Value> = options
+                .into_iter()
+                .map(|(key, value)| {
+                    let origin = resolver.get(&key).to_string();
+                    (key, json!({ "value": value, "origin": origin }))
+                })
+                .collect();
+            self.summary.set_data(&data);
+            return Ok(true);
+        }
+        for (key, value) in options {
+            let origin = resolver.get(&key);
+            let value =
+                serde_json::to_string(&value).map_err(|e| json_error(e, "serialize config"))?;
+            println!("{key}: {value} # {origin}");
+        }
+        Ok(true)
+    }
+
     fn get_options_hashmap(&self) -> Result<BTreeMap<String, Value>, serde_json::Error> {
         let options = [
             serde_json::to_value(&*self.batch_options)?,
+            serde_json::to_value(&*self.beets_options)?,
             serde_json::to_value(&*self.cache_options)?,
+            serde_json::to_value(&*self.cross_seed_options)?,
+            serde_json::to_value(&*self.desktop_options)?,
+            serde_json::to_value(&*self.enrich_options)?,
             serde_json::to_value(&*self.file_options)?,
+            serde_json::to_value(&*self.history_options)?,
+            serde_json::to_value(&*self.hook_options)?,
+            serde_json::to_value(&*self.notify_options)?,
+            serde_json::to_value(&*self.permission_options)?,
             serde_json::to_value(&*self.runner_options)?,
+            serde_json::to_value(&*self.serve_options)?,
             serde_json::to_value(&*self.shared_options)?,
             serde_json::to_value(&*self.spectrogram_options)?,
             serde_json::to_value(&*self.target_options)?,
+            serde_json::to_value(&*self.torrent_options)?,
             serde_json::to_value(&*self.upload_options)?,
             serde_json::to_value(&*self.verify_options)?,
+            serde_json::to_value(&*self.watch_options)?,
         ];
         let mut data: BTreeMap<String, Value> = BTreeMap::new();
         for option in &options {
@@ -52,3 +111,44 @@ impl ConfigCommand {
         Ok(data)
     }
 }
+
+/// Get the keys of the options set by the command line arguments.
+fn get_cli_keys() -> Result<BTreeSet<String>, serde_json::Error> {
+    let options = [
+        get_cli_value::<BatchOptions>()?,
+        get_cli_value::<BeetsOptions>()?,
+        get_cli_value::<CacheOptions>()?,
+        get_cli_value::<CrossSeedOptions>()?,
+        get_cli_value::<DesktopOptions>()?,
+        get_cli_value::<EnrichOptions>()?,
+        get_cli_value::<FileOptions>()?,
+        get_cli_value::<HistoryOptions>()?,
+        get_cli_value::<HookOptions>()?,
+        get_cli_value::<NotifyOptions>()?,
+        get_cli_value::<PermissionOptions>()?,
+        get_cli_value::<RunnerOptions>()?,
+        get_cli_value::<ServeOptions>()?,
+        get_cli_value::<SharedOptions>()?,
+        get_cli_value::<SpectrogramOptions>()?,
+        get_cli_value::<TargetOptions>()?,
+        get_cli_value::<TorrentOptions>()?,
+        get_cli_value::<UploadOptions>()?,
+        get_cli_value::<VerifyOptions>()?,
+        get_cli_value::<WatchOptions>()?,
+    ];
+    let mut keys = BTreeSet::new();
+    for option in &options {
+        if let Some(map) = option.as_object() {
+            for (key, value) in map {
+                if !value.is_null() {
+                    keys.insert(key.clone());
+                }
+            }
+        }
+    }
+    Ok(keys)
+}
+
+fn get_cli_value<T: Options + Serialize>() -> Result<Value, serde_json::Error> {
+    serde_json::to_value(T::from_args())
+}
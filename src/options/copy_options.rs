@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use crate::options::{Options, OptionsProvider};
+use crate::fs::LinkStrategy;
+use crate::options::{OptionRule, Options, OptionsProvider};
 
 /// Options for copying files during [`TranscodeCommand`] and [`UploadCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -15,9 +16,36 @@ pub struct CopyOptions {
     ///
     /// Enabling this option requires the source and destination to be on the same filesystem or mounted volume.
     ///
+    /// Deprecated: use `link_additional`, `link_content` and `link_torrent` instead.
+    /// If set this is the default of each of them.
+    ///
     /// Default: `false`
     #[arg(long, default_value = None, action = ArgAction::SetTrue)]
     pub hard_link: Option<bool>,
+
+    /// Strategy to place additional files such as images in each transcode directory.
+    ///
+    /// If the strategy fails, such as a hard link across filesystems, the file is copied.
+    ///
+    /// Default: `hardlink` if `hard_link` is set, else `copy`
+    #[arg(long, value_enum)]
+    pub link_additional: Option<LinkStrategy>,
+
+    /// Strategy to place transcodes in the content directory with `copy_transcode_to_content_dir`.
+    ///
+    /// If the strategy fails, such as a hard link across filesystems, the file is copied.
+    ///
+    /// Default: `hardlink` if `hard_link` is set, else `copy`
+    #[arg(long, value_enum)]
+    pub link_content: Option<LinkStrategy>,
+
+    /// Strategy to place torrent files in the `copy_torrent_to` directory.
+    ///
+    /// If the strategy fails, such as a hard link across filesystems, the file is copied.
+    ///
+    /// Default: `hardlink` if `hard_link` is set, else `copy`
+    #[arg(long, value_enum)]
+    pub link_torrent: Option<LinkStrategy>,
 }
 
 #[injectable]
@@ -36,22 +64,42 @@ impl Options for CopyOptions {
         if self.hard_link.is_none() {
             self.hard_link = alternative.hard_link;
         }
+        if self.link_additional.is_none() {
+            self.link_additional = alternative.link_additional;
+        }
+        if self.link_content.is_none() {
+            self.link_content = alternative.link_content;
+        }
+        if self.link_torrent.is_none() {
+            self.link_torrent = alternative.link_torrent;
+        }
     }
 
     fn apply_defaults(&mut self) {
         if self.hard_link.is_none() {
             self.hard_link = Some(false);
         }
+        let default = LinkStrategy::from_hard_link(self.hard_link == Some(true));
+        if self.link_additional.is_none() {
+            self.link_additional = Some(default);
+        }
+        if self.link_content.is_none() {
+            self.link_content = Some(default);
+        }
+        if self.link_torrent.is_none() {
+            self.link_torrent = Some(default);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
-        true
+    fn get_errors(&self) -> Vec<OptionRule> {
+        Vec::new()
     }
 
-    #[must_use]
     fn from_args() -> Option<Self> {
-        let Some(Batch { copy, .. } | Transcode { copy, .. }) = ArgumentsParser::get() else {
+        let Some(
+            Batch { copy, .. } | Transcode { copy, .. } | Watch { copy, .. } | Serve { copy, .. },
+        ) = ArgumentsParser::get()
+        else {
             return None;
         };
         let mut options = copy;
@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::CrossSeed;
+use crate::options::{IsEmpty, NotSet, OptionRule, Options, OptionsProvider};
+
+/// Options for the [`CrossSeedCommand`]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CrossSeedOptions {
+    /// Profiles of the other indexers to search for the source and its transcodes.
+    ///
+    /// Each profile is read from the `profiles` section of the config file and must set
+    /// the `announce_url` and `api_key` of the indexer, or store the API key with
+    /// `caesura config set-key`.
+    ///
+    /// Examples: `ops`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub cross_seed_profile: Option<Vec<String>>,
+
+    /// Directory to write a `.torrent` file to for each match.
+    ///
+    /// The torrent has the source and tracker of the other indexer so a torrent client
+    /// watching the directory can inject it and seed the existing files.
+    ///
+    /// If not set then the matches are only reported.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub cross_seed_torrent_dir: Option<PathBuf>,
+}
+
+#[injectable]
+impl CrossSeedOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for CrossSeedOptions {
+    fn get_name() -> String {
+        "Cross Seed Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.cross_seed_profile.is_none() {
+            self.cross_seed_profile
+                .clone_from(&alternative.cross_seed_profile);
+        }
+        if self.cross_seed_torrent_dir.is_none() {
+            self.cross_seed_torrent_dir
+                .clone_from(&alternative.cross_seed_torrent_dir);
+        }
+    }
+
+    fn apply_defaults(&mut self) {}
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        match &self.cross_seed_profile {
+            None => errors.push(NotSet("cross_seed_profile".to_owned())),
+            Some(profiles) if profiles.is_empty() => {
+                errors.push(IsEmpty("cross_seed_profile".to_owned()));
+            }
+            Some(_) => {}
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(CrossSeed { cross_seed, .. }) = ArgumentsParser::get() else {
+            return None;
+        };
+        let mut options = cross_seed;
+        if options.cross_seed_profile == Some(Vec::new()) {
+            options.cross_seed_profile = None;
+        }
+        Some(options)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for CrossSeedOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use clap::{ArgAction, Args};
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::options::{DurationInvalid, OptionRule, Options, OptionsProvider};
+
+/// Options for showing a desktop notification when a command finishes
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DesktopOptions {
+    /// Show a desktop notification when the command finishes or fails.
+    ///
+    /// Uses `notify-send` on Linux and `osascript` on macOS.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub desktop_notify: Option<bool>,
+
+    /// Minimum duration of the command before a desktop notification is shown.
+    ///
+    /// The duration is a string that can be parsed such as `500ms`, `5m`, `1h30m15s`.
+    ///
+    /// Default: `1m`
+    #[arg(long)]
+    pub desktop_notify_after: Option<String>,
+}
+
+#[injectable]
+impl DesktopOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+
+    #[must_use]
+    pub fn get_desktop_notify_after(&self) -> Option<Duration> {
+        let desktop_notify_after = self.desktop_notify_after.clone()?;
+        humantime::parse_duration(desktop_notify_after.as_str()).ok()
+    }
+}
+
+impl Options for DesktopOptions {
+    fn get_name() -> String {
+        "Desktop Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.desktop_notify.is_none() {
+            self.desktop_notify = alternative.desktop_notify;
+        }
+        if self.desktop_notify_after.is_none() {
+            self.desktop_notify_after
+                .clone_from(&alternative.desktop_notify_after);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.desktop_notify.is_none() {
+            self.desktop_notify = Some(false);
+        }
+        if self.desktop_notify_after.is_none() {
+            self.desktop_notify_after = Some("1m".to_owned());
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(desktop_notify_after) = &self.desktop_notify_after {
+            if self.get_desktop_notify_after().is_none() {
+                errors.push(DurationInvalid(
+                    "desktop_notify_after".to_owned(),
+                    desktop_notify_after.clone(),
+                ));
+            }
+        }
+        errors
+    }
+
+    #[allow(clippy::manual_let_else)]
+    fn from_args() -> Option<Self> {
+        let options = match ArgumentsParser::get() {
+            Some(
+                Batch { desktop, .. }
+                | Spectrogram { desktop, .. }
+                | Transcode { desktop, .. }
+                | Upload { desktop, .. }
+                | Verify { desktop, .. },
+            ) => desktop,
+            _ => return None,
+        };
+        let mut options = options;
+        if options.desktop_notify == Some(false) {
+            options.desktop_notify = None;
+        }
+        Some(options)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for DesktopOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::{Display, Formatter};
+
+use clap::{ArgAction, Args};
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::options::{OptionRule, Options, OptionsProvider, UrlInvalidSuffix, UrlNotHttp};
+
+/// Options for enriching tags with [`MusicBrainzRelease`] metadata
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EnrichOptions {
+    /// Should the source be matched to a `MusicBrainz` release to fill missing tags?
+    ///
+    /// The catalogue number, release country and original release date are written to
+    /// the transcodes if they're not already tagged, and the release is linked in the
+    /// upload description.
+    ///
+    /// During verify the duration of each track is compared with the release.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub enrich_tags: Option<bool>,
+
+    /// URL of the `MusicBrainz` server.
+    ///
+    /// Requests are limited to one per second as required by the `MusicBrainz` API.
+    ///
+    /// Default: `https://musicbrainz.org`
+    #[arg(long)]
+    pub musicbrainz_url: Option<String>,
+}
+
+#[injectable]
+impl EnrichOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for EnrichOptions {
+    fn get_name() -> String {
+        "Enrich Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.enrich_tags.is_none() {
+            self.enrich_tags = alternative.enrich_tags;
+        }
+        if self.musicbrainz_url.is_none() {
+            self.musicbrainz_url
+                .clone_from(&alternative.musicbrainz_url);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.enrich_tags.is_none() {
+            self.enrich_tags = Some(false);
+        }
+        if self.musicbrainz_url.is_none() {
+            self.musicbrainz_url = Some("https://musicbrainz.org".to_owned());
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(url) = &self.musicbrainz_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                errors.push(UrlNotHttp("musicbrainz_url".to_owned(), url.clone()));
+            }
+            if url.ends_with('/') {
+                errors.push(UrlInvalidSuffix("musicbrainz_url".to_owned(), url.clone()));
+            }
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(
+            Batch { enrich, .. }
+            | Transcode { enrich, .. }
+            | Upload { enrich, .. }
+            | Verify { enrich, .. }
+            | Watch { enrich, .. }
+            | Serve { enrich, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        let mut options = enrich;
+        if options.enrich_tags == Some(false) {
+            options.enrich_tags = None;
+        }
+        Some(options)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for EnrichOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use crate::options::{Options, OptionsProvider};
+use crate::fs::{ArchiveFormat, MAX_ARCHIVE_COMPRESSION};
+use crate::options::{CompressionInvalid, OptionRule, Options, OptionsProvider};
 
 /// Options for including additional files during [`TranscodeCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -48,6 +49,32 @@ pub struct FileOptions {
     /// Only applied if the image is greater than `max_file_size`.
     #[arg(long, default_value = None, action = ArgAction::SetTrue)]
     pub no_png_to_jpg: Option<bool>,
+
+    /// Should a `SHA256SUMS` manifest of the files be written to each transcode directory?
+    ///
+    /// The manifest is included in the torrent so downloaders can check the files with
+    /// `sha256sum --check SHA256SUMS` independently of the torrent client.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub checksum_manifest: Option<bool>,
+
+    /// Format of an archive to package each transcode directory into.
+    ///
+    /// The archive is written beside the transcode directory for delivery to cloud
+    /// storage or friends. The transcode directory and torrent are kept.
+    ///
+    /// Default: None
+    #[arg(long)]
+    pub archive: Option<ArchiveFormat>,
+
+    /// Compression level of the archive from `0` for none to `9` for the smallest.
+    ///
+    /// FLAC and MP3 are already compressed so a low level is usually enough.
+    ///
+    /// Default: `6`
+    #[arg(long)]
+    pub archive_compression: Option<u32>,
 }
 
 #[injectable]
@@ -78,6 +105,15 @@ impl Options for FileOptions {
         if self.jpg_quality.is_none() {
             self.jpg_quality = alternative.jpg_quality;
         }
+        if self.checksum_manifest.is_none() {
+            self.checksum_manifest = alternative.checksum_manifest;
+        }
+        if self.archive.is_none() {
+            self.archive = alternative.archive;
+        }
+        if self.archive_compression.is_none() {
+            self.archive_compression = alternative.archive_compression;
+        }
     }
 
     fn apply_defaults(&mut self) {
@@ -96,16 +132,32 @@ impl Options for FileOptions {
         if self.jpg_quality.is_none() {
             self.jpg_quality = Some(80);
         }
+        if self.checksum_manifest.is_none() {
+            self.checksum_manifest = Some(false);
+        }
+        if self.archive_compression.is_none() {
+            self.archive_compression = Some(6);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
-        true
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(compression) = self.archive_compression {
+            if compression > MAX_ARCHIVE_COMPRESSION {
+                errors.push(CompressionInvalid(
+                    "archive_compression".to_owned(),
+                    compression.to_string(),
+                ));
+            }
+        }
+        errors
     }
 
-    #[must_use]
     fn from_args() -> Option<Self> {
-        let Some(Batch { file, .. } | Transcode { file, .. }) = ArgumentsParser::get() else {
+        let Some(
+            Batch { file, .. } | Transcode { file, .. } | Watch { file, .. } | Serve { file, .. },
+        ) = ArgumentsParser::get()
+        else {
             return None;
         };
         let mut options = file;
@@ -115,6 +167,9 @@ impl Options for FileOptions {
         if options.no_png_to_jpg == Some(false) {
             options.no_png_to_jpg = None;
         }
+        if options.checksum_manifest == Some(false) {
+            options.checksum_manifest = None;
+        }
         Some(options)
     }
 
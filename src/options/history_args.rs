@@ -0,0 +1,72 @@
+use std::fmt::{Display, Formatter};
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::History;
+use crate::options::{OptionRule, Options, OptionsProvider};
+
+/// Options for the [`HistoryCommand`]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HistoryArgs {
+    /// Torrent id of a source to show the full history of.
+    ///
+    /// If not set then every processed source is listed.
+    #[arg(value_name = "ID")]
+    pub history_id: Option<u32>,
+}
+
+#[injectable]
+impl HistoryArgs {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for HistoryArgs {
+    fn get_name() -> String {
+        "History Arguments".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.history_id.is_none() {
+            self.history_id = alternative.history_id;
+        }
+    }
+
+    fn apply_defaults(&mut self) {}
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        Vec::new()
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(History { args, .. }) = ArgumentsParser::get() else {
+            return None;
+        };
+        Some(args)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for HistoryArgs {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
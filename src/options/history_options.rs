@@ -0,0 +1,84 @@
+use std::fmt::{Display, Formatter};
+
+use clap::{ArgAction, Args};
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::options::{OptionRule, Options, OptionsProvider};
+
+/// Options for the [`History`] of processed sources
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HistoryOptions {
+    /// Should sources be processed again even if the history records they
+    /// were already verified or transcoded?
+    ///
+    /// The result is still recorded in the history.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub ignore_history: Option<bool>,
+}
+
+#[injectable]
+impl HistoryOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for HistoryOptions {
+    fn get_name() -> String {
+        "History Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.ignore_history.is_none() {
+            self.ignore_history = alternative.ignore_history;
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.ignore_history.is_none() {
+            self.ignore_history = Some(false);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        Vec::new()
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(Transcode { history, .. } | Verify { history, .. }) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        let mut options = history;
+        if options.ignore_history == Some(false) {
+            options.ignore_history = None;
+        }
+        Some(options)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for HistoryOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::options::{DoesNotExist, OptionRule, Options, OptionsProvider};
+
+/// Options for running scripts at points in the lifecycle of a source
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HookOptions {
+    /// Path of a script to run after a source is verified.
+    ///
+    /// The script is run whether or not the source is suitable for transcoding.
+    /// `CAESURA_VERIFIED` is `true` or `false` and `CAESURA_ISSUES` has a line
+    /// for each issue.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub post_verify_hook: Option<PathBuf>,
+
+    /// Path of a script to run after each format of a source is transcoded.
+    ///
+    /// `CAESURA_FORMAT`, `CAESURA_TRANSCODE_DIR` and `CAESURA_TORRENT_PATH` describe
+    /// the transcode.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub post_transcode_hook: Option<PathBuf>,
+
+    /// Path of a script to run after each format of a source is uploaded.
+    ///
+    /// `CAESURA_FORMAT`, `CAESURA_UPLOAD_ID` and `CAESURA_UPLOAD_URL` describe
+    /// the upload.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub post_upload_hook: Option<PathBuf>,
+}
+
+#[injectable]
+impl HookOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for HookOptions {
+    fn get_name() -> String {
+        "Hook Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.post_verify_hook.is_none() {
+            self.post_verify_hook
+                .clone_from(&alternative.post_verify_hook);
+        }
+        if self.post_transcode_hook.is_none() {
+            self.post_transcode_hook
+                .clone_from(&alternative.post_transcode_hook);
+        }
+        if self.post_upload_hook.is_none() {
+            self.post_upload_hook
+                .clone_from(&alternative.post_upload_hook);
+        }
+    }
+
+    fn apply_defaults(&mut self) {}
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        let hooks = [
+            ("post_verify_hook", &self.post_verify_hook),
+            ("post_transcode_hook", &self.post_transcode_hook),
+            ("post_upload_hook", &self.post_upload_hook),
+        ];
+        for (key, path) in hooks {
+            if let Some(path) = path {
+                if !path.is_file() {
+                    errors.push(DoesNotExist(
+                        key.to_owned(),
+                        path.to_string_lossy().to_string(),
+                    ));
+                }
+            }
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        match ArgumentsParser::get() {
+            Some(
+                Batch { hook, .. }
+                | Transcode { hook, .. }
+                | Upload { hook, .. }
+                | Verify { hook, .. }
+                | Watch { hook, .. }
+                | Serve { hook, .. },
+            ) => Some(hook),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for HookOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
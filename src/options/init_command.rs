@@ -0,0 +1,376 @@
+use std::fs::{create_dir_all, remove_file, write, File};
+use std::io::{stdin, stdout, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{error, info, warn};
+use reqwest::Client;
+use rogue_logging::Error;
+use rpassword::prompt_password;
+use serde_json::Value;
+use serde_yaml::Mapping;
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_VERSION};
+use crate::dependencies::get_missing_dependencies;
+use crate::errors::{error, io_error, yaml_error};
+use crate::formats::TargetFormat;
+use crate::options::*;
+
+/// Interactively create a config file.
+///
+/// Existing options are used as the default answer to each question.
+#[allow(clippy::struct_field_names)]
+#[injectable]
+pub struct InitCommand {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    target_options: Ref<TargetOptions>,
+    upload_options: Ref<UploadOptions>,
+}
+
+impl InitCommand {
+    /// Execute [`InitCommand`] from the CLI.
+    ///
+    /// Returns `true` if the config file was written.
+    pub async fn execute_cli(&self) -> Result<bool, Error> {
+        let path = self
+            .shared_options
+            .config
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+        if path.exists()
+            && !confirm(
+                &format!("Overwrite the existing config file {}?", path.display()),
+                false,
+            )?
+        {
+            return Ok(false);
+        }
+        let mut shared = SharedOptions {
+            announce_url: prompt_optional(
+                "Announce URL including passkey (leave empty to fetch it from the API)",
+                self.shared_options.announce_url.clone(),
+            )?,
+            api_key: Some(prompt_secret(
+                "API key with torrent permissions",
+                self.shared_options.api_key.clone(),
+            )?),
+            content: Some(vec![PathBuf::from(prompt(
+                "Content directory of your torrent client",
+                self.shared_options
+                    .content
+                    .as_ref()
+                    .and_then(|x| x.first())
+                    .map(|x| x.to_string_lossy().to_string()),
+            )?)]),
+            output: Some(PathBuf::from(prompt(
+                "Output directory for transcodes and spectrograms",
+                get_path_string(self.shared_options.output.as_ref()),
+            )?)),
+            ..SharedOptions::default()
+        };
+        let mut defaults = shared.clone();
+        defaults.apply_defaults();
+        if defaults.indexer_url.is_none() {
+            shared.indexer = Some(prompt("Indexer id, such as red or ops", None)?);
+            defaults.clone_from(&shared);
+            defaults.apply_defaults();
+        }
+        if defaults.indexer_url.is_none() {
+            shared.indexer_url = Some(prompt("Indexer URL", None)?);
+            defaults.clone_from(&shared);
+            defaults.apply_defaults();
+        }
+        let cache = CacheOptions {
+            cache: Some(PathBuf::from(prompt(
+                "Cache directory",
+                get_path_string(self.cache_options.cache.as_ref()),
+            )?)),
+        };
+        let target = TargetOptions {
+            target: Some(prompt_targets(self.target_options.target.as_ref())?),
+            ..TargetOptions::default()
+        };
+        let copy_torrent_to = prompt_optional(
+            "Auto-add directory of your torrent client (leave empty to skip)",
+            get_path_string(self.upload_options.copy_torrent_to.as_ref()),
+        )?;
+        let upload = UploadOptions {
+            copy_torrent_to: copy_torrent_to.map(PathBuf::from),
+            ..UploadOptions::default()
+        };
+        let mut is_valid = true;
+        for (name, dir) in [
+            ("Content directory", first_content(&defaults)),
+            ("Output directory", defaults.output.clone()),
+            ("Cache directory", cache.cache.clone()),
+            ("Auto-add directory", upload.copy_torrent_to.clone()),
+        ] {
+            if let Some(dir) = dir {
+                is_valid &= check_directory(name, &dir);
+            }
+        }
+        is_valid &= check_dependencies().await;
+        is_valid &= check_api(&defaults).await;
+        if !is_valid && !confirm("Write the config file anyway?", false)? {
+            return Ok(false);
+        }
+        let yaml = to_yaml(&shared, &cache, &target, &upload)?;
+        write(&path, yaml).map_err(|e| io_error(e, "write config file"))?;
+        info!("{} config file {}", "Created".bold(), path.display());
+        Ok(true)
+    }
+}
+
+fn get_path_string(path: Option<&PathBuf>) -> Option<String> {
+    path.map(|x| x.to_string_lossy().to_string())
+}
+
+fn first_content(options: &SharedOptions) -> Option<PathBuf> {
+    options.content.as_ref().and_then(|x| x.first()).cloned()
+}
+
+fn read_answer(question: &str, default: Option<&String>) -> Result<String, Error> {
+    match default {
+        Some(default) if !default.is_empty() => print!("{} [{default}]: ", question.bold()),
+        _ => print!("{}: ", question.bold()),
+    }
+    stdout().flush().map_err(|e| io_error(e, "write prompt"))?;
+    Ok(read_line()?.trim().to_owned())
+}
+
+fn read_line() -> Result<String, Error> {
+    let mut answer = String::new();
+    let length = stdin()
+        .read_line(&mut answer)
+        .map_err(|e| io_error(e, "read answer"))?;
+    if length == 0 {
+        return Err(error("read answer", "No input is available".to_owned()));
+    }
+    Ok(answer)
+}
+
+/// Ask a question until an answer is given.
+fn prompt(question: &str, default: Option<String>) -> Result<String, Error> {
+    loop {
+        if let Some(answer) = prompt_optional(question, default.clone())? {
+            return Ok(answer);
+        }
+        warn!("{} is required", question);
+    }
+}
+
+/// Ask a question until an answer is given without echoing the answer.
+///
+/// The default is never printed so an existing secret isn't revealed.
+///
+/// If stdin is not a terminal then the answer is read from the next line of stdin.
+fn prompt_secret(question: &str, default: Option<String>) -> Result<String, Error> {
+    let default = default.filter(|x| !x.is_empty());
+    let label = if default.is_some() {
+        format!("{} [keep existing]: ", question.bold())
+    } else {
+        format!("{}: ", question.bold())
+    };
+    loop {
+        let answer = if stdin().is_terminal() {
+            prompt_password(&label).map_err(|e| io_error(e, "read answer"))?
+        } else {
+            print!("{label}");
+            stdout().flush().map_err(|e| io_error(e, "write prompt"))?;
+            read_line()?
+        };
+        let answer = answer.trim();
+        if !answer.is_empty() {
+            return Ok(answer.to_owned());
+        }
+        if let Some(default) = &default {
+            return Ok(default.clone());
+        }
+        warn!("{} is required", question);
+    }
+}
+
+/// Ask a question that can be skipped.
+fn prompt_optional(question: &str, default: Option<String>) -> Result<Option<String>, Error> {
+    let answer = read_answer(question, default.as_ref())?;
+    if answer.is_empty() {
+        Ok(default.filter(|x| !x.is_empty()))
+    } else {
+        Ok(Some(answer))
+    }
+}
+
+fn prompt_targets(default: Option<&Vec<TargetFormat>>) -> Result<Vec<TargetFormat>, Error> {
+    let default = default
+        .map_or_else(
+            || vec![TargetFormat::Flac, TargetFormat::_320, TargetFormat::V0],
+            Clone::clone,
+        )
+        .iter()
+        .map(|x| x.get_name().to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ");
+    loop {
+        let answer = prompt("Target formats: flac, 320, v0", Some(default.clone()))?;
+        match parse_targets(&answer) {
+            Ok(targets) => return Ok(targets),
+            Err(error) => warn!("{error}"),
+        }
+    }
+}
+
+/// Parse a comma separated list of [`TargetFormat`].
+pub fn parse_targets(value: &str) -> Result<Vec<TargetFormat>, String> {
+    let targets = value
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(|x| TargetFormat::from_str(x, true).map_err(|_| format!("Invalid target: {x}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    if targets.is_empty() {
+        return Err("At least one target is required".to_owned());
+    }
+    Ok(targets)
+}
+
+fn confirm(question: &str, default: bool) -> Result<bool, Error> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = read_answer(&format!("{question} {hint}"), None)?.to_lowercase();
+    Ok(match answer.as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
+/// Check the directory exists, creating it if required, and is writable.
+fn check_directory(name: &str, dir: &Path) -> bool {
+    if let Err(e) = create_dir_all(dir) {
+        error!(
+            "{} {name} {}: {e}",
+            "Unable to create".bold(),
+            dir.display()
+        );
+        return false;
+    }
+    let test_file = dir.join(format!(".{PKG_NAME}-write-test"));
+    match File::create(&test_file) {
+        Ok(_) => {
+            let _ = remove_file(&test_file);
+            info!("{} {name} is writable: {}", "Checked".bold(), dir.display());
+            true
+        }
+        Err(e) => {
+            error!(
+                "{} {name} {}: {e}",
+                "Unable to write to".bold(),
+                dir.display()
+            );
+            false
+        }
+    }
+}
+
+async fn check_dependencies() -> bool {
+    let missing = get_missing_dependencies().await;
+    for program in &missing {
+        error!("{} dependency: {program}", "Missing".bold());
+    }
+    if missing.is_empty() {
+        info!("{} all dependencies are available", "Checked".bold());
+    }
+    missing.is_empty()
+}
+
+async fn check_api(options: &SharedOptions) -> bool {
+    match get_username(options).await {
+        Ok(username) => {
+            info!("{} API key for user: {username}", "Checked".bold());
+            true
+        }
+        Err(error) => {
+            error.log();
+            false
+        }
+    }
+}
+
+/// Get the username of the API key from the index endpoint of the indexer.
+pub(crate) async fn get_username(options: &SharedOptions) -> Result<String, Error> {
+    let json = get_index(options, "check API key").await?;
+    Ok(json
+        .pointer("/response/username")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_owned())
+}
+
+/// Get the response of the index endpoint of the indexer.
+pub(crate) async fn get_index(options: &SharedOptions, action: &str) -> Result<Value, Error> {
+    let url = options
+        .indexer_url
+        .clone()
+        .ok_or_else(|| error(action, "Indexer URL is not set".to_owned()))?;
+    let api_key = options
+        .api_key
+        .clone()
+        .ok_or_else(|| error(action, "API key is not set".to_owned()))?;
+    let response = Client::new()
+        .get(format!("{url}/ajax.php?action=index"))
+        .header("Authorization", api_key)
+        .header(
+            "User-Agent",
+            format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+        )
+        .send()
+        .await
+        .map_err(|e| error(action, e.to_string()))?;
+    let status = response.status();
+    let json: Value = response
+        .json()
+        .await
+        .map_err(|e| error(action, e.to_string()))?;
+    if !status.is_success() || json.get("status").and_then(Value::as_str) != Some("success") {
+        let message = json
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("Unexpected response")
+            .to_owned();
+        return Err(Error {
+            action: action.to_owned(),
+            message,
+            status_code: Some(status.as_u16()),
+            ..Error::default()
+        });
+    }
+    Ok(json)
+}
+
+/// Serialize the options to YAML excluding any that are not set.
+fn to_yaml(
+    shared: &SharedOptions,
+    cache: &CacheOptions,
+    target: &TargetOptions,
+    upload: &UploadOptions,
+) -> Result<String, Error> {
+    let mut mapping = Mapping::new();
+    for options in [
+        serde_yaml::to_value(shared),
+        serde_yaml::to_value(cache),
+        serde_yaml::to_value(target),
+        serde_yaml::to_value(upload),
+    ] {
+        let options = options.map_err(|e| yaml_error(e, "serialize config"))?;
+        if let Some(options) = options.as_mapping() {
+            for (key, value) in options {
+                if !value.is_null() {
+                    mapping.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    serde_yaml::to_string(&mapping).map_err(|e| yaml_error(e, "serialize config"))
+}
@@ -1,35 +1,69 @@
+pub use api_key_store::*;
 pub use batch_options::*;
+pub use beets_options::*;
 pub use cache_options::*;
+pub use clean_args::*;
 pub use copy_options::*;
+pub use cross_seed_options::*;
+pub use desktop_options::*;
+pub use enrich_options::*;
 pub use file_options::*;
+pub use history_args::*;
+pub use history_options::*;
+pub use hook_options::*;
+pub use notify_options::*;
+pub use option_origin::*;
 pub use options_provider::*;
 pub use options_trait::*;
+pub use permission_options::*;
 pub use queue_add_args::*;
 pub use rules::OptionRule::*;
 pub use rules::*;
 pub use runner_options::*;
+pub use self_update_args::*;
+pub use serve_options::*;
 pub use shared_options::*;
 pub use source_arg::*;
 pub use spectrogram_options::*;
 pub use target_options::*;
+pub use torrent_options::*;
 pub use upload_options::*;
 pub use verify_options::*;
+pub use watch_options::*;
 
+pub(crate) mod api_key_store;
 pub(crate) mod batch_options;
+pub(crate) mod beets_options;
 pub(crate) mod cache_options;
+pub(crate) mod clean_args;
 pub(crate) mod config_command;
 mod copy_options;
+pub(crate) mod cross_seed_options;
+pub(crate) mod desktop_options;
+pub(crate) mod enrich_options;
 pub(crate) mod file_options;
+pub(crate) mod history_args;
+pub(crate) mod history_options;
+pub(crate) mod hook_options;
+pub(crate) mod init_command;
+pub(crate) mod notify_options;
+pub(crate) mod option_origin;
 pub(crate) mod options_provider;
 pub(crate) mod options_trait;
+pub(crate) mod permission_options;
 pub(crate) mod queue_add_args;
 pub(crate) mod rules;
 pub(crate) mod runner_options;
+pub(crate) mod self_update_args;
+pub(crate) mod serve_options;
+pub(crate) mod set_key_command;
 pub(crate) mod shared_options;
 pub(crate) mod source_arg;
 pub(crate) mod spectrogram_options;
 pub(crate) mod target_options;
 #[cfg(test)]
 mod tests;
+pub(crate) mod torrent_options;
 pub(crate) mod upload_options;
 pub(crate) mod verify_options;
+pub(crate) mod watch_options;
@@ -0,0 +1,144 @@
+use std::fmt::{Display, Formatter};
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::notify::NotifyEvent;
+use crate::options::{OptionRule, Options, OptionsProvider, UrlNotHttp};
+
+/// Options for posting notifications to webhooks
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NotifyOptions {
+    /// Webhook URLs to post notifications to.
+    ///
+    /// Discord, Slack and Telegram are determined by the URL. Any other URL is
+    /// sent `notify_template` as a generic webhook.
+    ///
+    /// Examples: `https://discord.com/api/webhooks/123/abc`, `https://hooks.slack.com/services/T00/B00/abc`,
+    /// `https://api.telegram.org/bot123:abc/sendMessage?chat_id=456`, `https://example.com/caesura`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub notify_url: Option<Vec<String>>,
+
+    /// Events to send notifications for.
+    ///
+    /// Default: `transcode_done`, `upload_done`, `batch_failed`
+    #[arg(long, value_enum)]
+    pub notify_on: Option<Vec<NotifyEvent>>,
+
+    /// JSON payload posted to generic webhooks.
+    ///
+    /// `{event}`, `{source}` and `{message}` are replaced by the values of the
+    /// notification, escaped so they can be used inside a JSON string.
+    ///
+    /// Default: `{"event": "{event}", "source": "{source}", "message": "{message}"}`
+    #[arg(long)]
+    pub notify_template: Option<String>,
+
+    /// URL to ping at the start, success and failure of each run.
+    ///
+    /// `/start` is appended to the start ping and `/fail` to the failure ping, as used
+    /// by Healthchecks.io, and the error is sent as the body of a failure. `watch` also
+    /// pings success after each scan of the watch directory so a dead man's switch
+    /// alerts if it stops.
+    ///
+    /// Examples: `https://hc-ping.com/eb095278-f28d-448d-87fb-7b75c171a6aa`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub healthcheck_url: Option<String>,
+}
+
+#[injectable]
+impl NotifyOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for NotifyOptions {
+    fn get_name() -> String {
+        "Notify Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.notify_url.is_none() {
+            self.notify_url.clone_from(&alternative.notify_url);
+        }
+        if self.notify_on.is_none() {
+            self.notify_on.clone_from(&alternative.notify_on);
+        }
+        if self.notify_template.is_none() {
+            self.notify_template
+                .clone_from(&alternative.notify_template);
+        }
+        if self.healthcheck_url.is_none() {
+            self.healthcheck_url
+                .clone_from(&alternative.healthcheck_url);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.notify_on.is_none() {
+            self.notify_on = Some(vec![
+                NotifyEvent::TranscodeDone,
+                NotifyEvent::UploadDone,
+                NotifyEvent::BatchFailed,
+            ]);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        for url in self.notify_url.iter().flatten() {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                errors.push(UrlNotHttp("notify_url".to_owned(), url.clone()));
+            }
+        }
+        if let Some(url) = &self.healthcheck_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                errors.push(UrlNotHttp("healthcheck_url".to_owned(), url.clone()));
+            }
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        match ArgumentsParser::get() {
+            Some(
+                Batch { notify, .. }
+                | Transcode { notify, .. }
+                | Upload { notify, .. }
+                | Watch { notify, .. }
+                | Serve { notify, .. },
+            ) => Some(notify),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for NotifyOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::options::{ENV_PREFIX, PROFILES_KEY};
+
+/// Part of the config file an option was read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigScope {
+    /// Root of the config file.
+    Root,
+    /// Named profile under the `profiles` key.
+    Profile(String),
+    /// Section named after a command such as `verify`.
+    Section(String),
+    /// Command section of a named profile.
+    ProfileSection(String, String),
+}
+
+/// Where the effective value of an option came from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionOrigin {
+    /// Default value as no source set the option.
+    Default,
+    /// Command line argument.
+    Cli(String),
+    /// Environment variable with the [`ENV_PREFIX`].
+    Env(String),
+    /// Config file and the line the option is set on, if it can be found.
+    ConfigFile {
+        path: PathBuf,
+        line: Option<usize>,
+        scope: ConfigScope,
+    },
+    /// API key stored in the OS credential store by `caesura config set-key`.
+    CredentialStore,
+}
+
+impl OptionOrigin {
+    /// Get the origin of an option set by a command line argument.
+    #[must_use]
+    pub fn cli(key: &str) -> Self {
+        Self::Cli(format!("--{}", key.replace('_', "-")))
+    }
+
+    /// Get the origin of an option set by an environment variable.
+    #[must_use]
+    pub fn env(key: &str) -> Self {
+        Self::Env(format!("{ENV_PREFIX}{}", key.to_uppercase()))
+    }
+}
+
+/// Find the [`OptionOrigin`] of each option from the keys set by each source.
+///
+/// Sources are checked in the order of precedence of [`OptionsProvider`].
+pub struct OriginResolver {
+    /// Keys set by command line arguments.
+    pub cli: BTreeSet<String>,
+    /// Keys set by environment variables.
+    pub env: BTreeSet<String>,
+    /// Config file before the profile and command section are applied.
+    pub config: Mapping,
+    pub config_path: PathBuf,
+    /// Profile applied to the config file.
+    pub profile: Option<String>,
+    /// Command section applied to the config file.
+    pub command: Option<String>,
+    /// Keys set by the OS credential store.
+    pub keyring: BTreeSet<String>,
+    /// Line number of each key of the config file.
+    pub lines: BTreeMap<Vec<String>, usize>,
+}
+
+impl OriginResolver {
+    /// Create an [`OriginResolver`] from the YAML documents of each source.
+    #[must_use]
+    pub fn new(
+        cli: BTreeSet<String>,
+        env: &str,
+        config: &str,
+        config_path: PathBuf,
+        profile: Option<String>,
+        command: Option<String>,
+        keyring: Option<&str>,
+    ) -> Self {
+        let config_mapping = get_mapping(config);
+        let profile = profile.or_else(|| {
+            config_mapping
+                .get("profile")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+        });
+        Self {
+            cli,
+            env: get_set_keys(env),
+            config: config_mapping,
+            config_path,
+            profile,
+            command,
+            keyring: keyring.map(get_set_keys).unwrap_or_default(),
+            lines: get_config_lines(config),
+        }
+    }
+
+    /// Get the origin of the effective value of an option.
+    #[must_use]
+    pub fn get(&self, key: &str) -> OptionOrigin {
+        if self.cli.contains(key) {
+            return OptionOrigin::cli(key);
+        }
+        if self.env.contains(key) {
+            return OptionOrigin::env(key);
+        }
+        if let Some(scope) = self.get_config_scope(key) {
+            let path = match &scope {
+                ConfigScope::Root => vec![key.to_owned()],
+                ConfigScope::Profile(name) => {
+                    vec![PROFILES_KEY.to_owned(), name.clone(), key.to_owned()]
+                }
+                ConfigScope::Section(name) => vec![name.clone(), key.to_owned()],
+                ConfigScope::ProfileSection(name, command) => vec![
+                    PROFILES_KEY.to_owned(),
+                    name.clone(),
+                    command.clone(),
+                    key.to_owned(),
+                ],
+            };
+            return OptionOrigin::ConfigFile {
+                path: self.config_path.clone(),
+                line: self.lines.get(&path).copied(),
+                scope,
+            };
+        }
+        if self.keyring.contains(key) {
+            return OptionOrigin::CredentialStore;
+        }
+        OptionOrigin::Default
+    }
+
+    /// Get the part of the config file that sets an option.
+    fn get_config_scope(&self, key: &str) -> Option<ConfigScope> {
+        let profile = self.profile.as_ref().and_then(|profile| {
+            self.config
+                .get(PROFILES_KEY)
+                .and_then(|profiles| profiles.get(profile.as_str()))
+                .map(|options| (profile, options))
+        });
+        if let Some(command) = &self.command {
+            // A command section of the profile replaces the command section of the root
+            let profile_section = profile.and_then(|(name, options)| {
+                options
+                    .get(command.as_str())
+                    .filter(|section| section.is_mapping())
+                    .map(|section| (name, section))
+            });
+            if let Some((name, section)) = profile_section {
+                if has_key(Some(section), key) {
+                    return Some(ConfigScope::ProfileSection(name.clone(), command.clone()));
+                }
+            } else if has_key(self.config.get(command.as_str()), key) {
+                return Some(ConfigScope::Section(command.clone()));
+            }
+        }
+        if let Some((name, options)) = profile {
+            if options.get(key).is_some_and(|value| !value.is_mapping())
+                && has_key(Some(options), key)
+            {
+                return Some(ConfigScope::Profile(name.clone()));
+            }
+        }
+        // No option is a mapping so a mapping is a command section rather than an option
+        match self.config.get(key) {
+            Some(value) if !value.is_null() && !value.is_mapping() => Some(ConfigScope::Root),
+            _ => None,
+        }
+    }
+}
+
+fn get_mapping(yaml: &str) -> Mapping {
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        _ => Mapping::new(),
+    }
+}
+
+/// Get the keys of a YAML mapping that have a value.
+fn get_set_keys(yaml: &str) -> BTreeSet<String> {
+    get_mapping(yaml)
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .filter_map(|(key, _)| key.as_str().map(ToOwned::to_owned))
+        .collect()
+}
+
+fn has_key(options: Option<&Value>, key: &str) -> bool {
+    options
+        .and_then(Value::as_mapping)
+        .and_then(|options| options.get(key))
+        .is_some_and(|value| !value.is_null())
+}
+
+/// Get the line number of each key of a YAML config file.
+///
+/// Each key is identified by its path from the root so `verify_options` of the `ops`
+/// profile is `["profiles", "ops", "verify_options"]`. Only block style mappings are
+/// found, so a key of a flow style mapping such as `{ a: 1 }` has no line.
+///
+/// Line numbers start from `1`. If a key occurs more than once then the first is used.
+#[must_use]
+pub fn get_config_lines(yaml: &str) -> BTreeMap<Vec<String>, usize> {
+    let mut lines = BTreeMap::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    for (index, line) in yaml.lines().enumerate() {
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with('#') || content.starts_with('-') {
+            continue;
+        }
+        let Some((key, _)) = content.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            continue;
+        }
+        let indent = line.len() - content.len();
+        while parents.last().is_some_and(|(parent, _)| *parent >= indent) {
+            parents.pop();
+        }
+        parents.push((indent, key.to_owned()));
+        let path = parents.iter().map(|(_, key)| key.clone()).collect();
+        lines.entry(path).or_insert(index + 1);
+    }
+    lines
+}
+
+impl Display for OptionOrigin {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionOrigin::Default => write!(formatter, "default"),
+            OptionOrigin::Cli(flag) => write!(formatter, "command line {flag}"),
+            OptionOrigin::Env(name) => write!(formatter, "environment variable {name}"),
+            OptionOrigin::ConfigFile { path, line, scope } => {
+                write!(formatter, "{}", path.display())?;
+                if let Some(line) = line {
+                    write!(formatter, ":{line}")?;
+                }
+                match scope {
+                    ConfigScope::Root => Ok(()),
+                    ConfigScope::Profile(name) => write!(formatter, " profile `{name}`"),
+                    ConfigScope::Section(name) => write!(formatter, " section `{name}`"),
+                    ConfigScope::ProfileSection(name, command) => {
+                        write!(formatter, " profile `{name}` section `{command}`")
+                    }
+                }
+            }
+            OptionOrigin::CredentialStore => write!(formatter, "credential store"),
+        }
+    }
+}
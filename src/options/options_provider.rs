@@ -1,31 +1,65 @@
+use std::collections::BTreeSet;
+use std::env::vars_os;
 use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::exit;
 
 use colored::Colorize;
 use di::injectable;
 use log::*;
+use serde_yaml::{Mapping, Value};
 
-use crate::built_info::PKG_NAME;
+use crate::cli::ArgumentsParser;
+use crate::errors::{error, path_error, yaml_error};
+use crate::logging::{force_init_logger, init_trace_logger};
 use crate::options::*;
-use rogue_logging::Logger;
+use rogue_logging::Error;
+
+/// Prefix of environment variables that set options.
+pub const ENV_PREFIX: &str = "CAESURA_";
+
+/// Key of the config file containing the named profiles.
+pub const PROFILES_KEY: &str = "profiles";
 
 /// Retrieve options
 ///
 /// Options are retrieved from multiple sources, and merged in order of precedence:
 /// 1. Command line arguments
-/// 2. Config file defined by the `--config` command line argument
-/// 3. `config.yml` in the current working directory
+/// 2. Environment variables prefixed with `CAESURA_`
+/// 3. Section of the config file named after the command, such as `verify`
+/// 4. Profile defined by the `--profile` command line argument
+/// 5. Config file defined by the `--config` command line argument
+/// 6. `config.yml` in the current working directory
+/// 7. API key stored in the OS credential store by `caesura config set-key`
 pub struct OptionsProvider {
+    env: Option<String>,
     yaml: Option<String>,
+    keyring: Option<String>,
+    /// Config file before the profile is applied.
+    config: Option<String>,
+    config_path: PathBuf,
+    /// Profile set by the command line arguments or environment variables.
+    profile: Option<String>,
 }
 
 #[injectable]
 impl OptionsProvider {
     #[must_use]
     pub fn new() -> Self {
-        let cli_options = SharedOptions::from_args().unwrap_or_default();
-        Self {
-            yaml: Some(read_config_file(&cli_options)),
+        let env = get_env_yaml(get_env_vars());
+        let cli_options = get_cli_options(&env);
+        if let Some(format) = cli_options.log_format {
+            init_trace_logger(format);
+        }
+        let config_path = get_config_path(&cli_options);
+        let config = read_config_file(&config_path);
+        match Self::load(env, &cli_options, config_path, config) {
+            Ok(provider) => provider,
+            Err(error) => {
+                force_init_logger();
+                error.log();
+                exit(1)
+            }
         }
     }
 
@@ -33,34 +67,371 @@ impl OptionsProvider {
     #[must_use]
     pub fn get<T: Options>(&self) -> T {
         let mut options = T::from_args().unwrap_or_default();
-        if let Some(yaml) = &self.yaml {
-            if !yaml.is_empty() {
-                match T::from_yaml(yaml) {
-                    Ok(file_options) => {
-                        options.merge(&file_options);
-                    }
-                    Err(error) => {
-                        Logger::force_init(PKG_NAME.to_owned());
-                        error!("{} to deserialize config file: {}", "Failed".bold(), error);
-                    }
-                }
-            }
-        }
+        merge_env_yaml(&mut options, self.env.as_deref().unwrap_or_default());
+        merge_yaml(&mut options, self.yaml.as_ref(), "config file");
+        merge_yaml(&mut options, self.keyring.as_ref(), "credential store");
         options.apply_defaults();
         options
     }
 }
 
-/// Read the config file
+impl OptionsProvider {
+    /// Read the config file again.
+    ///
+    /// Unlike [`OptionsProvider::new`] an error is returned if the config file can't be
+    /// read or the profile or command section can't be applied.
+    pub fn reload(&self) -> Result<Self, Error> {
+        let env = self.env.clone().unwrap_or_default();
+        let cli_options = get_cli_options(&env);
+        let config = read_to_string(&self.config_path)
+            .map_err(|e| path_error(e, "read config file", &self.config_path))?;
+        Self::load(env, &cli_options, self.config_path.clone(), config)
+    }
+
+    /// Get the path of the config file.
+    #[must_use]
+    pub fn get_config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Get the config file before the profile is applied.
+    #[must_use]
+    pub fn get_config(&self) -> &str {
+        self.config.as_deref().unwrap_or_default()
+    }
+
+    fn load(
+        env: String,
+        cli_options: &SharedOptions,
+        config_path: PathBuf,
+        config: String,
+    ) -> Result<Self, Error> {
+        let errors = get_env_errors(&env);
+        if !errors.is_empty() {
+            force_init_logger();
+            OptionRule::check(&errors)?;
+        }
+        let yaml = apply_profile(&config, cli_options.profile.as_deref())?;
+        let yaml = apply_command_section(
+            &yaml,
+            ArgumentsParser::get_command_name().as_deref(),
+            &ArgumentsParser::get_command_names(),
+        )?;
+        let keyring = get_keyring_yaml(cli_options, &yaml);
+        Ok(Self {
+            env: Some(env),
+            yaml: Some(yaml),
+            keyring,
+            config: Some(config),
+            config_path,
+            profile: cli_options.profile.clone(),
+        })
+    }
+
+    /// Get the [`Options`] of a named profile of the config file.
+    ///
+    /// Unlike [`OptionsProvider::get`] the command line arguments and environment variables
+    /// are not applied as they're intended for the selected profile.
+    pub fn get_profile<T: Options>(&self, profile: &str) -> Result<T, Error> {
+        get_profile_options(self.config.as_deref().unwrap_or_default(), profile)
+    }
+
+    /// Get an [`OriginResolver`] to find where the value of each option came from.
+    ///
+    /// `cli` are the keys set by the command line arguments.
+    #[must_use]
+    pub fn get_origin_resolver(&self, cli: BTreeSet<String>) -> OriginResolver {
+        OriginResolver::new(
+            cli,
+            self.env.as_deref().unwrap_or_default(),
+            self.config.as_deref().unwrap_or_default(),
+            self.config_path.clone(),
+            self.profile.clone(),
+            ArgumentsParser::get_command_name(),
+            self.keyring.as_deref(),
+        )
+    }
+}
+
+/// Get the [`Options`] of a named profile of a config file.
+pub fn get_profile_options<T: Options>(yaml: &str, profile: &str) -> Result<T, Error> {
+    let yaml = apply_profile(yaml, Some(profile))?;
+    let mut options = T::from_yaml(&yaml).map_err(|e| yaml_error(e, "deserialize profile"))?;
+    options.apply_defaults();
+    Ok(options)
+}
+
+fn merge_yaml<T: Options>(options: &mut T, yaml: Option<&String>, source: &str) {
+    let Some(yaml) = yaml else {
+        return;
+    };
+    if yaml.is_empty() {
+        return;
+    }
+    match T::from_yaml(yaml) {
+        Ok(alternative) => {
+            options.merge(&alternative);
+        }
+        Err(error) => {
+            force_init_logger();
+            error!("{} to deserialize {source}: {}", "Failed".bold(), error);
+        }
+    }
+}
+
+/// Merge each option set by environment variables separately.
+///
+/// An option that can't be deserialized is skipped rather than discarding every other
+/// option, and is reported by [`get_env_errors`].
+pub fn merge_env_yaml<T: Options>(options: &mut T, yaml: &str) {
+    for (_, _, yaml) in split_env_yaml(yaml) {
+        if let Ok(alternative) = T::from_yaml(&yaml) {
+            options.merge(&alternative);
+        }
+    }
+}
+
+/// Get an [`OptionRule`] for each option set by environment variables that can't be
+/// deserialized.
+///
+/// Each option is deserialized on its own by every [`Options`] so the invalid variable
+/// is reported rather than the whole document.
+#[must_use]
+pub fn get_env_errors(yaml: &str) -> Vec<OptionRule> {
+    split_env_yaml(yaml)
+        .into_iter()
+        .filter_map(|(key, value, yaml)| {
+            let details = get_deserialize_error(&yaml)?;
+            Some(EnvironmentInvalid(key, value, details))
+        })
+        .collect()
+}
+
+/// Split the YAML document of the environment variables into a document per option.
+///
+/// Returns the key, the value and the document of each option.
+fn split_env_yaml(yaml: &str) -> Vec<(String, String, String)> {
+    let Ok(Value::Mapping(mapping)) = serde_yaml::from_str::<Value>(yaml) else {
+        return Vec::new();
+    };
+    mapping
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?.to_owned();
+            let display = serde_yaml::to_string(&value).ok()?.trim().to_owned();
+            let mut option = Mapping::new();
+            option.insert(Value::String(key.clone()), value);
+            let yaml = serde_yaml::to_string(&option).ok()?;
+            Some((key, display, yaml))
+        })
+        .collect()
+}
+
+/// Get the first error of deserializing a YAML document as each [`Options`].
+fn get_deserialize_error(yaml: &str) -> Option<String> {
+    [
+        BatchOptions::from_yaml(yaml).err(),
+        BeetsOptions::from_yaml(yaml).err(),
+        CacheOptions::from_yaml(yaml).err(),
+        CrossSeedOptions::from_yaml(yaml).err(),
+        DesktopOptions::from_yaml(yaml).err(),
+        EnrichOptions::from_yaml(yaml).err(),
+        FileOptions::from_yaml(yaml).err(),
+        HistoryOptions::from_yaml(yaml).err(),
+        HookOptions::from_yaml(yaml).err(),
+        NotifyOptions::from_yaml(yaml).err(),
+        PermissionOptions::from_yaml(yaml).err(),
+        RunnerOptions::from_yaml(yaml).err(),
+        ServeOptions::from_yaml(yaml).err(),
+        SharedOptions::from_yaml(yaml).err(),
+        SpectrogramOptions::from_yaml(yaml).err(),
+        TargetOptions::from_yaml(yaml).err(),
+        TorrentOptions::from_yaml(yaml).err(),
+        UploadOptions::from_yaml(yaml).err(),
+        VerifyOptions::from_yaml(yaml).err(),
+        WatchOptions::from_yaml(yaml).err(),
+    ]
+    .into_iter()
+    .flatten()
+    .next()
+    .map(|error| error.to_string())
+}
+
+/// Get the environment variables with the [`ENV_PREFIX`].
+fn get_env_vars() -> Vec<(String, String)> {
+    vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .filter(|(key, _)| key.starts_with(ENV_PREFIX))
+        .collect()
+}
+
+/// Convert environment variables to a YAML document of options.
+///
+/// The prefix is removed and the key is converted to lower case so
+/// `CAESURA_API_KEY` is mapped to `api_key`.
+///
+/// Values are parsed as YAML so lists can be set with `[a, b]`.
+#[must_use]
+pub fn get_env_yaml(vars: Vec<(String, String)>) -> String {
+    let mut mapping = Mapping::new();
+    for (key, value) in vars {
+        let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        if name.is_empty() || value.is_empty() {
+            continue;
+        }
+        let value = serde_yaml::from_str::<Value>(&value).unwrap_or(Value::String(value));
+        mapping.insert(Value::String(name.to_lowercase()), value);
+    }
+    if mapping.is_empty() {
+        return "{}".to_owned();
+    }
+    serde_yaml::to_string(&mapping).unwrap_or_else(|_| "{}".to_owned())
+}
+
+/// Apply a named profile to the config file.
+///
+/// The options of the profile replace the options at the root of the config file
+/// and the `profiles` key is removed.
+///
+/// If `profile` is `None` then the `profile` key of the config file is used.
+///
+/// If no profile is set then a config file that can't be parsed is returned
+/// unchanged so the error is reported when each [`Options`] is deserialized.
+pub fn apply_profile(yaml: &str, profile: Option<&str>) -> Result<String, Error> {
+    let mut mapping = match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        Ok(Value::Null) => Mapping::new(),
+        Ok(_) | Err(_) if profile.is_none() => return Ok(yaml.to_owned()),
+        Ok(_) => {
+            return Err(error(
+                "apply profile",
+                "Config file must be a mapping of options".to_owned(),
+            ))
+        }
+        Err(e) => return Err(yaml_error(e, "deserialize config file")),
+    };
+    let profiles = mapping.remove(PROFILES_KEY);
+    let profile = profile.map(ToOwned::to_owned).or_else(|| {
+        mapping
+            .get("profile")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+    });
+    if let Some(profile) = profile {
+        let Some(options) = profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(profile.as_str()))
+            .and_then(Value::as_mapping)
+        else {
+            return Err(error(
+                "apply profile",
+                format!("Profile `{profile}` is not defined in the config file"),
+            ));
+        };
+        for (key, value) in options {
+            mapping.insert(key.clone(), value.clone());
+        }
+    }
+    serde_yaml::to_string(&mapping).map_err(|e| yaml_error(e, "serialize config file"))
+}
+
+/// Apply the section of the config file for a command.
+///
+/// A section is a mapping at the root of the config file named after a command, such as
+/// `verify` or `cross_seed`, so an option can differ per command. The options of the
+/// section of `command` replace the options at the root and every section is removed.
+///
+/// A key named after a command that isn't a mapping, such as the `transcode: true` of the
+/// batch command, is an option rather than a section so it's left unchanged.
+///
+/// A config file that can't be parsed is returned unchanged so the error is reported when
+/// each [`Options`] is deserialized.
+pub fn apply_command_section(
+    yaml: &str,
+    command: Option<&str>,
+    commands: &[String],
+) -> Result<String, Error> {
+    let Ok(Value::Mapping(mut mapping)) = serde_yaml::from_str::<Value>(yaml) else {
+        return Ok(yaml.to_owned());
+    };
+    let mut section = None;
+    let mut is_changed = false;
+    for name in commands {
+        if !mapping.get(name.as_str()).is_some_and(Value::is_mapping) {
+            continue;
+        }
+        let options = mapping.remove(name.as_str());
+        is_changed = true;
+        if command == Some(name.as_str()) {
+            section = options;
+        }
+    }
+    if !is_changed {
+        return Ok(yaml.to_owned());
+    }
+    if let Some(Value::Mapping(options)) = section {
+        for (key, value) in options {
+            mapping.insert(key, value);
+        }
+    }
+    serde_yaml::to_string(&mapping).map_err(|e| yaml_error(e, "serialize config file"))
+}
+
+/// Get a YAML document containing the API key and session cookie from the OS credential
+/// store.
+///
+/// Each is only read from the credential store if it's not set by another source.
+fn get_keyring_yaml(cli_options: &SharedOptions, yaml: &str) -> Option<String> {
+    let mut options = cli_options.clone();
+    if let Ok(config_options) = SharedOptions::from_yaml(yaml) {
+        options.merge(&config_options);
+    }
+    options.apply_defaults();
+    let indexer = options.indexer.as_deref()?;
+    let api_key = if options.api_key.is_some() {
+        None
+    } else {
+        get_stored_api_key(indexer)
+    };
+    let session_cookie = if options.session_cookie.is_some() {
+        None
+    } else {
+        get_stored_session_cookie(indexer)
+    };
+    if api_key.is_none() && session_cookie.is_none() {
+        return None;
+    }
+    let options = SharedOptions {
+        api_key,
+        session_cookie,
+        ..SharedOptions::default()
+    };
+    serde_yaml::to_string(&options).ok()
+}
+
+/// Get the [`SharedOptions`] set by the command line arguments and environment variables.
+fn get_cli_options(env: &str) -> SharedOptions {
+    let mut cli_options = SharedOptions::from_args().unwrap_or_default();
+    merge_env_yaml(&mut cli_options, env);
+    cli_options
+}
+
+/// Get the path of the config file.
 ///
 /// Use the default config path if no path is set on the command line.
-fn read_config_file(options: &SharedOptions) -> String {
-    let path = options
+fn get_config_path(options: &SharedOptions) -> PathBuf {
+    options
         .config
         .clone()
-        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH))
+}
+
+/// Read the config file
+fn read_config_file(path: &Path) -> String {
     read_to_string(path).unwrap_or_else(|error| {
-        Logger::force_init(PKG_NAME.to_owned());
+        force_init_logger();
         warn!("{} to read config file: {}", "Failed".bold(), error);
         "{}".to_owned()
     })
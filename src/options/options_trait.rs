@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::options::OptionRule;
+
 pub trait Options: Clone + Default + Display {
     /// Get a friendly display name.
     fn get_name() -> String;
@@ -10,8 +12,15 @@ pub trait Options: Clone + Default + Display {
     /// Apply default values to [`Self`]
     fn apply_defaults(&mut self);
 
-    /// Validate [`Self`]
-    fn validate(&self) -> bool;
+    /// Get every [`OptionRule`] that [`Self`] does not satisfy
+    fn get_errors(&self) -> Vec<OptionRule>;
+
+    /// Validate [`Self`] and show any errors
+    fn validate(&self) -> bool {
+        let errors = self.get_errors();
+        OptionRule::show(&errors);
+        errors.is_empty()
+    }
 
     /// Get [`Self`] from the command line arguments
     fn from_args() -> Option<Self>;
@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter};
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::fs::{get_owner, parse_mode, OutputPermissions};
+use crate::options::{OptionRule, Options, OptionsProvider, PermissionInvalid};
+
+/// Options for the permissions and owner of created files and directories
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PermissionOptions {
+    /// Octal permission mode of created files such as `664`.
+    ///
+    /// Applied to transcodes, spectrograms and torrent files so they are readable
+    /// by a torrent client running as a different user.
+    ///
+    /// Default: `null` to leave the mode set by the umask
+    #[arg(long)]
+    pub chmod: Option<String>,
+
+    /// Octal permission mode of created directories such as `775`.
+    ///
+    /// Default: `null` to leave the mode set by the umask
+    #[arg(long)]
+    pub dir_chmod: Option<String>,
+
+    /// Owner of created files and directories such as `1000:1000` or `media:media`.
+    ///
+    /// Either the user or group may be omitted: `media` or `:media`.
+    /// Changing the user typically requires running as root.
+    ///
+    /// Default: `null` to leave the owner unchanged
+    #[arg(long)]
+    pub chown: Option<String>,
+}
+
+#[injectable]
+impl PermissionOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+
+    /// Get the permissions to apply to created files and directories.
+    #[must_use]
+    pub fn get_permissions(&self) -> OutputPermissions {
+        OutputPermissions {
+            file_mode: self.chmod.as_deref().and_then(parse_mode),
+            dir_mode: self.dir_chmod.as_deref().and_then(parse_mode),
+            owner: self.chown.as_deref().and_then(get_owner),
+        }
+    }
+}
+
+impl Options for PermissionOptions {
+    fn get_name() -> String {
+        "Permission Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.chmod.is_none() {
+            self.chmod.clone_from(&alternative.chmod);
+        }
+        if self.dir_chmod.is_none() {
+            self.dir_chmod.clone_from(&alternative.dir_chmod);
+        }
+        if self.chown.is_none() {
+            self.chown.clone_from(&alternative.chown);
+        }
+    }
+
+    fn apply_defaults(&mut self) {}
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        for (key, value) in [("chmod", &self.chmod), ("dir_chmod", &self.dir_chmod)] {
+            if let Some(value) = value {
+                if parse_mode(value).is_none() {
+                    errors.push(PermissionInvalid(
+                        key.to_owned(),
+                        value.clone(),
+                        "Expected an octal mode such as 664 or 775".to_owned(),
+                    ));
+                }
+            }
+        }
+        if let Some(value) = &self.chown {
+            if get_owner(value).is_none() {
+                errors.push(PermissionInvalid(
+                    "chown".to_owned(),
+                    value.clone(),
+                    "Expected a known user and group or numeric ids such as 1000:1000".to_owned(),
+                ));
+            }
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(
+            Batch { permission, .. }
+            | Transcode { permission, .. }
+            | Spectrogram { permission, .. }
+            | Upload { permission, .. }
+            | Watch { permission, .. }
+            | Serve { permission, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        Some(permission)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for PermissionOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
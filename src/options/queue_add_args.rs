@@ -3,13 +3,15 @@ use std::path::PathBuf;
 
 use crate::cli::CommandArguments::Queue;
 use crate::cli::{ArgumentsParser, QueueCommandArguments};
-use crate::options::{DoesNotExist, NotSet, OptionRule, Options, OptionsProvider};
-use clap::Args;
+use crate::indexer::{get_artist_id, get_collage_id};
+use crate::options::{DoesNotExist, IdInvalid, NotSet, OptionRule, Options, OptionsProvider};
+use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 use QueueCommandArguments::Add;
 
 /// Options for the [`QueueAddCommand`]
+#[allow(clippy::struct_field_names)]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
 pub struct QueueAddArgs {
     /// A path to either:
@@ -19,6 +21,38 @@ pub struct QueueAddArgs {
     /// Examples: `./torrents`, `/path/to/torrents`, `./queue.yml`
     #[arg(value_name = "PATH")]
     pub queue_add_path: Option<PathBuf>,
+
+    /// An artist id or URL to add every FLAC torrent of the artist.
+    ///
+    /// The `.torrent` file of each FLAC torrent is downloaded to the cache and only
+    /// the torrents found in the content directory are added unless
+    /// `queue_add_missing` is set.
+    ///
+    /// Examples: `1460`, `https://redacted.sh/artist.php?id=1460`
+    #[arg(long)]
+    pub queue_add_artist: Option<String>,
+
+    /// A collage id or URL to add the best FLAC torrent of each group in the collage.
+    ///
+    /// The groups that don't have a 320 or V0 torrent are also reported.
+    ///
+    /// Examples: `6120`, `https://redacted.sh/collages.php?id=6120`
+    #[arg(long)]
+    pub queue_add_collage: Option<String>,
+
+    /// Should the seeding torrents listed on the transcode candidates page be added?
+    ///
+    /// The page is not part of the API so `session_cookie` must be set.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub queue_add_better: Option<bool>,
+
+    /// Should torrents that are not in the content directory also be added?
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub queue_add_missing: Option<bool>,
 }
 
 #[injectable]
@@ -37,25 +71,57 @@ impl Options for QueueAddArgs {
         if self.queue_add_path.is_none() {
             self.queue_add_path.clone_from(&alternative.queue_add_path);
         }
+        if self.queue_add_artist.is_none() {
+            self.queue_add_artist
+                .clone_from(&alternative.queue_add_artist);
+        }
+        if self.queue_add_collage.is_none() {
+            self.queue_add_collage
+                .clone_from(&alternative.queue_add_collage);
+        }
+        if self.queue_add_better.is_none() {
+            self.queue_add_better = alternative.queue_add_better;
+        }
+        if self.queue_add_missing.is_none() {
+            self.queue_add_missing = alternative.queue_add_missing;
+        }
     }
 
-    fn apply_defaults(&mut self) {}
+    fn apply_defaults(&mut self) {
+        if self.queue_add_better.is_none() {
+            self.queue_add_better = Some(false);
+        }
+        if self.queue_add_missing.is_none() {
+            self.queue_add_missing = Some(false);
+        }
+    }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if let Some(path) = &self.queue_add_path {
             if !path.exists() {
                 errors.push(DoesNotExist(
-                    "Queue add path".to_owned(),
+                    "queue_add_path".to_owned(),
                     path.to_string_lossy().to_string(),
                 ));
             }
-        } else {
-            errors.push(NotSet("Queue add path".to_owned()));
+        } else if self.queue_add_artist.is_none()
+            && self.queue_add_collage.is_none()
+            && self.queue_add_better != Some(true)
+        {
+            errors.push(NotSet("queue_add_path".to_owned()));
+        }
+        if let Some(artist) = &self.queue_add_artist {
+            if get_artist_id(artist).is_none() {
+                errors.push(IdInvalid("queue_add_artist".to_owned(), artist.clone()));
+            }
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        if let Some(collage) = &self.queue_add_collage {
+            if get_collage_id(collage).is_none() {
+                errors.push(IdInvalid("queue_add_collage".to_owned(), collage.clone()));
+            }
+        }
+        errors
     }
 
     #[allow(clippy::match_wildcard_for_single_variants)]
@@ -64,7 +130,16 @@ impl Options for QueueAddArgs {
             Some(Queue {
                 command: Add { args, .. },
                 ..
-            }) => Some(args),
+            }) => {
+                let mut options = args;
+                if options.queue_add_better == Some(false) {
+                    options.queue_add_better = None;
+                }
+                if options.queue_add_missing == Some(false) {
+                    options.queue_add_missing = None;
+                }
+                Some(options)
+            }
             _ => None,
         }
     }
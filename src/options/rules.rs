@@ -1,48 +1,440 @@
 use crate::options::*;
 use colored::Colorize;
 use log::error;
+use rogue_logging::Error;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug)]
+/// Domain of the [`Error`] returned when options are invalid.
+pub const CONFIGURATION_DOMAIN: &str = "configuration";
+
+/// A rule that an option does not satisfy.
+///
+/// The first value of each rule is the key of the option as used in the config file.
+#[derive(Clone, Debug)]
 pub enum OptionRule {
     Changed(String, String, String),
     NotSet(String),
     Dependent(String, String),
     IsEmpty(String),
     UrlNotHttp(String, String),
+    UrlNotHttps(String, String),
     UrlInvalidSuffix(String, String),
     DoesNotExist(String, String),
     DurationInvalid(String, String),
+    ScheduleInvalid(String, String),
+    AddressInvalid(String, String),
+    LogFilterInvalid(String, String, String),
+    TemplateInvalid(String, String, String),
+    SubstitutionInvalid(String, String, String),
+    SizeInvalid(String, String, String),
+    PermissionInvalid(String, String, String),
+    DsnInvalid(String, String, String),
+    GainInvalid(String, String),
+    IdInvalid(String, String),
+    CompressionInvalid(String, String),
+    CharactersInvalid(String, String, String),
+    EnvironmentInvalid(String, String, String),
+    TargetInvalid(String, String, String),
 }
 
 impl Display for OptionRule {
-    #[allow(clippy::absolute_paths)]
+    #[allow(clippy::absolute_paths, clippy::too_many_lines)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         let output = match self {
-            Changed(name, value, details) => format!("{name} use has changed: {value}\n{details}"),
-            Dependent(this, that) => format!("{this} requires {that} to be set"),
-            NotSet(name) => format!("{name} is not set"),
-            IsEmpty(name) => format!("{name} must have at least one value"),
-            UrlNotHttp(name, value) => {
-                format!("{name} must start with https:// or http://: {value}")
-            }
-            UrlInvalidSuffix(name, value) => {
-                format!("{name} must not end with /: {value}")
-            }
-            DoesNotExist(name, value) => format!("{name} does not exist: {value}"),
-            DurationInvalid(name, value) => format!("{name} could not be parsed: {value}"),
+            Changed(key, value, details) => {
+                format!("{} use has changed: {value}\n{details}", format_key(key))
+            }
+            Dependent(this, that) => {
+                format!(
+                    "{} requires {} to be set",
+                    format_key(this),
+                    format_key(that)
+                )
+            }
+            NotSet(key) => format!("{} is not set", format_key(key)),
+            IsEmpty(key) => format!("{} must have at least one value", format_key(key)),
+            UrlNotHttp(key, value) => {
+                format!(
+                    "{} must start with https:// or http://: {value}",
+                    format_key(key)
+                )
+            }
+            UrlNotHttps(key, value) => {
+                format!(
+                    "{} must start with https:// for the session cookie to be sent: {value}",
+                    format_key(key)
+                )
+            }
+            UrlInvalidSuffix(key, value) => {
+                format!("{} must not end with /: {value}", format_key(key))
+            }
+            DoesNotExist(key, value) => format!("{} does not exist: {value}", format_key(key)),
+            DurationInvalid(key, value) => {
+                format!("{} could not be parsed: {value}", format_key(key))
+            }
+            ScheduleInvalid(key, value) => {
+                format!(
+                    "{} is not a valid cron expression: {value}",
+                    format_key(key)
+                )
+            }
+            AddressInvalid(key, value) => {
+                format!("{} is not a valid socket address: {value}", format_key(key))
+            }
+            LogFilterInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid log filter: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            TemplateInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid template: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            SubstitutionInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid substitution: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            SizeInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid size: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            PermissionInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid permission: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            DsnInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid Sentry DSN: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            GainInvalid(key, value) => {
+                format!(
+                    "{} must be a negative gain in dB of at least -20: {value}",
+                    format_key(key)
+                )
+            }
+            IdInvalid(key, value) => {
+                format!("{} is not a valid id or URL: {value}", format_key(key))
+            }
+            CompressionInvalid(key, value) => {
+                format!(
+                    "{} must be a compression level from 0 to 9: {value}",
+                    format_key(key)
+                )
+            }
+            CharactersInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid character set: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            TargetInvalid(key, value, details) => {
+                format!(
+                    "{} is not a valid custom target: {value}\n{details}",
+                    format_key(key)
+                )
+            }
+            EnvironmentInvalid(key, value, details) => {
+                format!(
+                    "{} set by {ENV_PREFIX}{} could not be parsed: {value}\n{details}",
+                    format_key(key),
+                    key.to_uppercase()
+                )
+            }
         };
         output.fmt(formatter)
     }
 }
 
 impl OptionRule {
-    pub fn show(errors: &Vec<OptionRule>) {
+    /// Get the key of the option that must be changed to satisfy the rule.
+    #[must_use]
+    pub fn get_key(&self) -> &str {
+        match self {
+            Changed(key, _, _)
+            | Dependent(_, key)
+            | NotSet(key)
+            | IsEmpty(key)
+            | UrlNotHttp(key, _)
+            | UrlNotHttps(key, _)
+            | UrlInvalidSuffix(key, _)
+            | DoesNotExist(key, _)
+            | DurationInvalid(key, _)
+            | ScheduleInvalid(key, _)
+            | AddressInvalid(key, _)
+            | IdInvalid(key, _)
+            | GainInvalid(key, _)
+            | CompressionInvalid(key, _)
+            | CharactersInvalid(key, _, _)
+            | LogFilterInvalid(key, _, _)
+            | TemplateInvalid(key, _, _)
+            | SubstitutionInvalid(key, _, _)
+            | SizeInvalid(key, _, _)
+            | TargetInvalid(key, _, _)
+            | PermissionInvalid(key, _, _)
+            | DsnInvalid(key, _, _)
+            | EnvironmentInvalid(key, _, _) => key,
+        }
+    }
+
+    /// Get an example of a valid value for the option.
+    #[must_use]
+    pub fn get_example(&self) -> Option<String> {
+        let key = self.get_key();
+        let (cli, yaml) = get_example_values(key)?;
+        let flag = get_flag(key);
+        if is_positional(key) {
+            return Some(format!("`{cli}`"));
+        }
+        let cli = if cli.is_empty() {
+            flag
+        } else {
+            format!("{flag} {cli}")
+        };
+        Some(format!("`{cli}` or `{key}: {yaml}` in the config file"))
+    }
+
+    /// Log every error together with an example of a valid value.
+    ///
+    /// Returns an [`Error`] in the `configuration` domain if there are any errors.
+    pub fn check(errors: &[OptionRule]) -> Result<(), Error> {
+        if errors.is_empty() {
+            return Ok(());
+        }
+        OptionRule::show(errors);
+        let keys: Vec<&str> = errors.iter().map(OptionRule::get_key).collect();
+        Err(Error {
+            action: "validate options".to_owned(),
+            message: format!("Invalid options: {}", keys.join(", ")),
+            domain: Some(CONFIGURATION_DOMAIN.to_owned()),
+            ..Error::default()
+        })
+    }
+
+    /// Log every error together with an example of a valid value.
+    pub fn show(errors: &[OptionRule]) {
         if !errors.is_empty() {
             error!("{} configuration", "Invalid".bold());
             for error in errors {
                 error!("{}", error);
+                if let Some(example) = error.get_example() {
+                    error!("  {}: {example}", "Example".bold());
+                }
             }
         }
     }
 }
+
+fn is_positional(key: &str) -> bool {
+    matches!(key, "source" | "queue_add_path" | "watch_path")
+}
+
+/// Get the command line flag or argument name of an option.
+fn get_flag(key: &str) -> String {
+    match key {
+        "source" => "<SOURCE>".to_owned(),
+        "queue_add_path" | "watch_path" => "<PATH>".to_owned(),
+        _ => format!("--{}", key.replace('_', "-")),
+    }
+}
+
+fn format_key(key: &str) -> String {
+    format!("{key} ({})", get_flag(key))
+}
+
+/// Get an example value of an option for the command line and for the config file.
+///
+/// An empty command line value indicates a flag that takes no value.
+#[allow(clippy::too_many_lines)]
+fn get_example_values(key: &str) -> Option<(&'static str, &'static str)> {
+    let values = match key {
+        "announce_url" => (
+            "https://flacsfor.me/a1b2c3d4e5f6/announce",
+            "https://flacsfor.me/a1b2c3d4e5f6/announce",
+        ),
+        "api_key" => ("a1b2c3d4.e5f6a1b2c3d4e5f6", "a1b2c3d4.e5f6a1b2c3d4e5f6"),
+        "session_cookie" => ("a1b2c3d4e5f6a1b2c3d4e5f6", "a1b2c3d4e5f6a1b2c3d4e5f6"),
+        "indexer" => ("red", "red"),
+        "trackers" => ("red,ops", "[red, ops]"),
+        "indexer_url" => ("https://redacted.sh", "https://redacted.sh"),
+        "config" => ("./config.yml", "./config.yml"),
+        "content" => ("/srv/downloads", "[/srv/downloads]"),
+        "output" => ("/srv/output", "/srv/output"),
+        "cache" => ("./cache", "./cache"),
+        "target" => ("flac --target 320 --target v0", "[flac, 320, v0]"),
+        "spectrogram_size" => ("full --spectrogram-size zoom", "[full, zoom]"),
+        "transcode"
+        | "spectrogram"
+        | "upload"
+        | "retry_transcode"
+        | "no_limit"
+        | "source_cleanup_dry_run"
+        | "desktop_notify"
+        | "enrich_tags"
+        | "checksum_manifest"
+        | "no_image_compression"
+        | "no_png_to_jpg"
+        | "ignore_history"
+        | "shared_cpus"
+        | "auto_shorten"
+        | "flatten_nested_dirs"
+        | "no_color"
+        | "normalize_names"
+        | "timings"
+        | "transliterate"
+        | "yes"
+        | "spectrogram_force"
+        | "spectrogram_optimize"
+        | "allow_existing"
+        | "clip_check"
+        | "no_resume"
+        | "provenance_file"
+        | "recheck_formats"
+        | "copy_transcode_to_content_dir"
+        | "dry_run"
+        | "fill_requests"
+        | "lineage"
+        | "upload_extra_targets"
+        | "auto_spectrogram"
+        | "no_hash_check"
+        | "no_verify_cache"
+        | "silence_check"
+        | "queue_add_better" => ("", "true"),
+        "wait_before_upload" | "desktop_notify_after" => ("5m", "5m"),
+        "max_duration" => ("4h", "4h"),
+        "watch_interval" => ("1m", "1m"),
+        "max_age" => ("30d", "30d"),
+        "clip_gain" => ("-1.0", "-1.0"),
+        "archive_compression" => ("6", "6"),
+        "watch_debounce" => ("30s", "30s"),
+        "log_filter" => (
+            "warn,caesura::upload=debug",
+            "\"warn,caesura::upload=debug\"",
+        ),
+        "notify_url" => (
+            "https://discord.com/api/webhooks/123/abc",
+            "[https://discord.com/api/webhooks/123/abc]",
+        ),
+        "watch_schedule" => ("\"0 3 * * *\"", "\"0 3 * * *\""),
+        "metrics_address" | "serve_metrics_address" => ("127.0.0.1:9090", "127.0.0.1:9090"),
+        "client_type" => ("qbittorrent", "qbittorrent"),
+        "client_url" => ("http://localhost:8080", "http://localhost:8080"),
+        "output_layout" => (
+            "\"{artist_initial}/{artist}/{album} ({year}) [{format}]\"",
+            "\"{artist_initial}/{artist}/{album} ({year}) [{format}]\"",
+        ),
+        "spectrogram_layout" => (
+            "\"spectrograms/{artist} - {album}\"",
+            "\"spectrograms/{artist} - {album}\"",
+        ),
+        "name_substitution" => ("\"—=>-\"", "[\"—=>-\"]"),
+        "custom_target" => ("\"V3=V3\"", "[\"V3=V3\"]"),
+        "allowed_characters" => (
+            "\"[\\p{L}\\p{N} ._()&,'!-]\"",
+            "'[\\p{L}\\p{N} ._()&,''!-]'",
+        ),
+        "character_replacement" => ("\"&=>and\"", "[\"&=>and\"]"),
+        "edition_substitution" => (
+            "\"^Self-Released$=>Not On Label\"",
+            "[\"^Self-Released$=>Not On Label\"]",
+        ),
+        "min_buffer" | "min_free_space" => ("10GiB", "10GiB"),
+        "max_disk_usage" => ("500GiB", "500GiB"),
+        "max_source_size" => ("50GiB", "50GiB"),
+        "report_dsn" => (
+            "https://a1b2c3d4e5f6@o123456.ingest.sentry.io/7890",
+            "https://a1b2c3d4e5f6@o123456.ingest.sentry.io/7890",
+        ),
+        "torrent_piece_size" => ("1MiB", "1MiB"),
+        "torrent_min_piece_size" => ("64KiB", "64KiB"),
+        "torrent_max_piece_size" => ("8MiB", "8MiB"),
+        "torrent_max_pieces" => ("2000", "2000"),
+        "chmod" => ("664", "\"664\""),
+        "dir_chmod" => ("775", "\"775\""),
+        "chown" => ("1000:1000", "\"1000:1000\""),
+        "read_ahead" => ("512MiB", "512MiB"),
+        "log_file_max_size" => ("10000000", "10000000"),
+        "max_file_size" => ("750000", "750000"),
+        "limit" => ("10", "10"),
+        "log_file_keep" => ("3", "3"),
+        "max_source_tracks" => ("50", "50"),
+        "cpus" => ("4", "4"),
+        "max_pixel_size" => ("1280", "1280"),
+        "spectrogram_max_width" => ("1920", "1920"),
+        "jpg_quality" => ("80", "80"),
+        "edition_year" => ("2012", "2012"),
+        "min_log_score" => ("95", "95"),
+        "max_duration_difference" => ("2", "2"),
+        "min_track_duration" => ("5", "5"),
+        "min_ratio" => ("0.6", "0.6"),
+        "batch_order" => (
+            "most_formats --batch-order fewest_seeders",
+            "[most_formats, fewest_seeders]",
+        ),
+        "source_cleanup" => ("archive", "archive"),
+        "source_archive" => ("/srv/archive", "/srv/archive"),
+        "beets" => ("import", "import"),
+        "beets_args" => ("--quiet --beets-args --noautotag", "[--quiet, --noautotag]"),
+        "beets_formats" => ("v0 --beets-formats 320", "[v0, 320]"),
+        "cross_seed_profile" => ("ops", "[ops]"),
+        "cross_seed_torrent_dir" => ("/srv/watch", "/srv/watch"),
+        "musicbrainz_url" => ("https://musicbrainz.org", "https://musicbrainz.org"),
+        "archive" => ("tar.gz", "tar.gz"),
+        "post_verify_hook" => ("./hooks/verified.sh", "./hooks/verified.sh"),
+        "post_transcode_hook" => ("./hooks/transcoded.sh", "./hooks/transcoded.sh"),
+        "post_upload_hook" => ("./hooks/uploaded.sh", "./hooks/uploaded.sh"),
+        "healthcheck_url" => (
+            "https://hc-ping.com/a1b2c3d4-e5f6",
+            "https://hc-ping.com/a1b2c3d4-e5f6",
+        ),
+        "notify_on" => (
+            "upload_done --notify-on batch_failed",
+            "[upload_done, batch_failed]",
+        ),
+        "notify_template" => ("'{\"text\": \"{message}\"}'", "'{\"text\": \"{message}\"}'"),
+        "serve_address" => ("127.0.0.1:7070", "127.0.0.1:7070"),
+        "serve_token" | "client_password" => ("a1b2c3d4e5f6", "a1b2c3d4e5f6"),
+        "diagnostics_dir" => ("./diagnostics", "./diagnostics"),
+        "log_file" => ("./caesura.log", "./caesura.log"),
+        "report_file" => ("./errors.ndjson", "./errors.ndjson"),
+        "progress_path" => ("./progress.ndjson", "./progress.ndjson"),
+        "verbosity" | "log_file_verbosity" => ("debug", "debug"),
+        "log_file_rotation" => ("daily", "daily"),
+        "log_format" | "result_format" => ("json", "json"),
+        "log_time" => ("utc", "utc"),
+        "profile" => ("ops", "ops"),
+        "progress" => ("ndjson", "ndjson"),
+        "unicode_form" => ("nfd", "nfd"),
+        "spectrogram_channels" => ("mix", "mix"),
+        "provenance_tag" => ("comment", "comment"),
+        "torrent_version" => ("hybrid", "hybrid"),
+        "account_guard" => ("refuse", "refuse"),
+        "fill_request_match" => ("loose", "loose"),
+        "copy_torrent_to" => ("/srv/torrents", "/srv/torrents"),
+        "edition_title" => ("\"Deluxe Edition\"", "\"Deluxe Edition\""),
+        "edition_record_label" => ("\"Warp Records\"", "\"Warp Records\""),
+        "edition_catalogue_number" => ("WARPCD92", "WARPCD92"),
+        "lineage_template" => ("\"Lineage: {lineage}\"", "\"Lineage: {lineage}\""),
+        "exclude_tags" => ("comedy --exclude-tags audiobook", "[comedy, audiobook]"),
+        "torrent_file" => ("./source.torrent", "./source.torrent"),
+        "torrent_dirs" => ("/srv/torrents", "[/srv/torrents]"),
+        "client_username" => ("admin", "admin"),
+        "client_category" => ("music", "music"),
+        "queue_add_artist" => ("1460", "1460"),
+        "queue_add_collage" => ("6120", "6120"),
+        "source" => ("4871992", "4871992"),
+        "queue_add_path" | "watch_path" => ("./torrents", "./torrents"),
+        _ => return None,
+    };
+    Some(values)
+}
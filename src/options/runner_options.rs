@@ -2,11 +2,11 @@ use std::fmt::{Display, Formatter};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use clap::Args;
+use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 
-use crate::options::{Options, OptionsProvider};
+use crate::options::{OptionRule, Options, OptionsProvider};
 
 /// Options for [`JobRunner`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -16,6 +16,16 @@ pub struct RunnerOptions {
     /// Default: Total number of CPUs
     #[arg(long)]
     pub cpus: Option<u16>,
+
+    /// Should the cpus be shared with other caesura processes?
+    ///
+    /// Every process using the same cache directory waits for one of `cpus` slots before
+    /// starting each job so a manual run alongside `watch` or `serve` doesn't exceed the
+    /// limit. Every process should use the same `cpus`.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub shared_cpus: Option<bool>,
 }
 
 #[injectable]
@@ -34,6 +44,9 @@ impl Options for RunnerOptions {
         if self.cpus.is_none() {
             self.cpus.clone_from(&alternative.cpus);
         }
+        if self.shared_cpus.is_none() {
+            self.shared_cpus = alternative.shared_cpus;
+        }
     }
 
     #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
@@ -41,17 +54,29 @@ impl Options for RunnerOptions {
         if self.cpus.is_none() {
             self.cpus = Some(num_cpus::get() as u16);
         }
+        if self.shared_cpus.is_none() {
+            self.shared_cpus = Some(false);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
-        true
+    fn get_errors(&self) -> Vec<OptionRule> {
+        Vec::new()
     }
 
     fn from_args() -> Option<Self> {
         match ArgumentsParser::get() {
-            Some(Batch { runner, .. } | Spectrogram { runner, .. } | Transcode { runner, .. }) => {
-                Some(runner)
+            Some(
+                Batch { runner, .. }
+                | Spectrogram { runner, .. }
+                | Transcode { runner, .. }
+                | Watch { runner, .. }
+                | Serve { runner, .. },
+            ) => {
+                let mut options = runner;
+                if options.shared_cpus == Some(false) {
+                    options.shared_cpus = None;
+                }
+                Some(options)
             }
             _ => None,
         }
@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use clap::{ArgAction, Args};
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::SelfUpdate;
+use crate::options::{OptionRule, Options, OptionsProvider};
+
+/// Options for the [`SelfUpdateCommand`]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SelfUpdateArgs {
+    /// Only check if a newer version is available without replacing the executable.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub check_only: Option<bool>,
+}
+
+#[injectable]
+impl SelfUpdateArgs {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+}
+
+impl Options for SelfUpdateArgs {
+    fn get_name() -> String {
+        "Self Update Arguments".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.check_only.is_none() {
+            self.check_only = alternative.check_only;
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.check_only.is_none() {
+            self.check_only = Some(false);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        Vec::new()
+    }
+
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    fn from_args() -> Option<Self> {
+        match ArgumentsParser::get() {
+            Some(SelfUpdate { args, .. }) => {
+                let mut args = args;
+                if args.check_only == Some(false) {
+                    args.check_only = None;
+                }
+                Some(args)
+            }
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for SelfUpdateArgs {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
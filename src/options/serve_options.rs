@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use clap::Args;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::Serve;
+use crate::options::{AddressInvalid, OptionRule, Options, OptionsProvider};
+
+/// Default address of the HTTP API.
+const DEFAULT_SERVE_ADDRESS: &str = "127.0.0.1:7070";
+
+/// Options for [`ServeCommand`]
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ServeOptions {
+    /// Address to serve the HTTP API from.
+    ///
+    /// Use `0.0.0.0` to accept requests from other machines, in which case
+    /// `serve_token` should also be set.
+    ///
+    /// Examples: `127.0.0.1:7070`, `0.0.0.0:7070`
+    ///
+    /// Default: `127.0.0.1:7070`
+    #[arg(long)]
+    pub serve_address: Option<String>,
+
+    /// Token required in the `Authorization: Bearer <token>` header of every request.
+    ///
+    /// If not set then requests are not authenticated.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub serve_token: Option<String>,
+
+    /// Address to serve Prometheus metrics from at `/metrics`.
+    ///
+    /// Examples: `127.0.0.1:9090`, `0.0.0.0:9090`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub serve_metrics_address: Option<String>,
+}
+
+#[injectable]
+impl ServeOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+
+    #[must_use]
+    pub fn get_serve_address(&self) -> Option<SocketAddr> {
+        let serve_address = self.serve_address.clone()?;
+        SocketAddr::from_str(serve_address.as_str()).ok()
+    }
+
+    #[must_use]
+    pub fn get_metrics_address(&self) -> Option<SocketAddr> {
+        let metrics_address = self.serve_metrics_address.clone()?;
+        SocketAddr::from_str(metrics_address.as_str()).ok()
+    }
+}
+
+impl Options for ServeOptions {
+    fn get_name() -> String {
+        "Serve Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.serve_address.is_none() {
+            self.serve_address.clone_from(&alternative.serve_address);
+        }
+        if self.serve_token.is_none() {
+            self.serve_token.clone_from(&alternative.serve_token);
+        }
+        if self.serve_metrics_address.is_none() {
+            self.serve_metrics_address
+                .clone_from(&alternative.serve_metrics_address);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.serve_address.is_none() {
+            self.serve_address = Some(DEFAULT_SERVE_ADDRESS.to_owned());
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(serve_address) = &self.serve_address {
+            if self.get_serve_address().is_none() {
+                errors.push(AddressInvalid(
+                    "serve_address".to_owned(),
+                    serve_address.clone(),
+                ));
+            }
+        }
+        if let Some(metrics_address) = &self.serve_metrics_address {
+            if self.get_metrics_address().is_none() {
+                errors.push(AddressInvalid(
+                    "serve_metrics_address".to_owned(),
+                    metrics_address.clone(),
+                ));
+            }
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(Serve { serve, .. }) = ArgumentsParser::get() else {
+            return None;
+        };
+        Some(serve)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for ServeOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
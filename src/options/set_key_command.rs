@@ -0,0 +1,78 @@
+use std::io::{stdin, IsTerminal};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::info;
+use rogue_logging::Error;
+use rpassword::prompt_password;
+
+use crate::errors::{error, io_error};
+use crate::options::*;
+
+/// Store the API key or session cookie in the OS credential store.
+///
+/// They're stored for the indexer so they can be read by [`OptionsProvider`] instead of
+/// being set in the config file.
+#[injectable]
+pub struct SetKeyCommand {
+    shared_options: Ref<SharedOptions>,
+}
+
+impl SetKeyCommand {
+    /// Execute [`SetKeyCommand`] from the CLI.
+    ///
+    /// If `cookie` is set then the session cookie is stored instead of the API key.
+    ///
+    /// The value is taken from `--api-key` or `--session-cookie` if it's set, otherwise
+    /// it's prompted for.
+    ///
+    /// Returns `true` if the value was stored.
+    pub fn execute_cli(&self, cookie: bool) -> Result<bool, Error> {
+        let Some(indexer) = self.shared_options.indexer.clone() else {
+            return OptionRule::check(&[NotSet("indexer".to_owned())]).map(|()| false);
+        };
+        let args = SharedOptions::from_args().unwrap_or_default();
+        let (name, label, value) = if cookie {
+            ("session cookie", "Session cookie", args.session_cookie)
+        } else {
+            ("API key", "API key", args.api_key)
+        };
+        let value = match value {
+            Some(value) => value.trim().to_owned(),
+            None => read_secret(name, label)?,
+        };
+        if value.is_empty() {
+            return Err(error(
+                &format!("read {name}"),
+                format!("{label} must not be empty"),
+            ));
+        }
+        if cookie {
+            store_session_cookie(&indexer, &value)?;
+        } else {
+            store_api_key(&indexer, &value)?;
+        }
+        info!(
+            "{} {name} for {indexer} in the OS credential store",
+            "Stored".bold()
+        );
+        Ok(true)
+    }
+}
+
+/// Read a secret without echoing it to the terminal.
+///
+/// If stdin is not a terminal then the secret is read from the first line of stdin.
+fn read_secret(name: &str, label: &str) -> Result<String, Error> {
+    let action = format!("read {name}");
+    let value = if stdin().is_terminal() {
+        prompt_password(format!("{label}: ")).map_err(|e| io_error(e, &action))?
+    } else {
+        let mut line = String::new();
+        stdin()
+            .read_line(&mut line)
+            .map_err(|e| io_error(e, &action))?;
+        line
+    };
+    Ok(value.trim().to_owned())
+}
@@ -1,22 +1,38 @@
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
+use std::slice::from_ref;
 
 use crate::cli::ArgumentsParser;
-use clap::Args;
+use crate::logging::{adjust_verbosity, is_no_color_set, LogFilter, LogFormat, LogRotation};
+use crate::naming::{
+    CharacterWhitelist, LayoutTemplate, NameSubstitution, SpectrogramLayout, UnicodeForm,
+};
+use crate::progress::ProgressFormat;
+use crate::report::SentryDsn;
+use crate::summary::ResultFormat;
+use crate::torrent::get_tracker_url;
+use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 
-use crate::cli::CommandArguments::{Batch, Queue, Spectrogram, Transcode, Upload, Verify};
+use crate::cli::CommandArguments::{
+    Batch, Clean, Config, CrossSeed, Doctor, History, Init, Queue, SelfUpdate, Serve, Spectrogram,
+    Transcode, Upload, Verify, Watch,
+};
+use crate::cli::ConfigCommandArguments::{SetKey, Show};
 use crate::cli::QueueCommandArguments::{Add, List, Summary};
 use crate::options::{
-    Changed, DoesNotExist, NotSet, OptionRule, Options, OptionsProvider, UrlInvalidSuffix,
-    UrlNotHttp,
+    Changed, CharactersInvalid, DoesNotExist, DsnInvalid, LogFilterInvalid, NotSet, OptionRule,
+    Options, OptionsProvider, SubstitutionInvalid, TemplateInvalid, UrlInvalidSuffix, UrlNotHttp,
+    UrlNotHttps,
 };
 use rogue_logging::{TimeFormat, Verbosity};
 
 pub const DEFAULT_CONFIG_PATH: &str = "config.yml";
 const DEFAULT_CONTENT_PATH: &str = "./content";
 const DEFAULT_OUTPUT_PATH: &str = "./output";
+const DEFAULT_OUTPUT_LAYOUT: &str = "{name}";
+const DEFAULT_SPECTROGRAM_LAYOUT: &str = "{spectrograms}";
 
 /// Options shared by all commands
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -24,6 +40,8 @@ pub struct SharedOptions {
     /// Announce URL including passkey
     ///
     /// Examples: `https://flacsfor.me/a1b2c3d4e5f6/announce`, `https://home.opsfet.ch/a1b2c3d4e5f6/announce`
+    ///
+    /// Default: Fetched from the API if `indexer` is `red` or `ops`
     #[arg(long)]
     pub announce_url: Option<String>,
 
@@ -31,6 +49,17 @@ pub struct SharedOptions {
     #[arg(long)]
     pub api_key: Option<String>,
 
+    /// Session cookie of the indexer website.
+    ///
+    /// Only sent over https to the pages of features that aren't available from the API.
+    /// Either the value of the `session` cookie or a complete `Cookie` header.
+    ///
+    /// Store it with `caesura config set-key --cookie` to keep it out of the config file.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub session_cookie: Option<String>,
+
     /// ID of the tracker as it appears in the source field of a torrent.
     ///
     /// Examples: `red`, `pth`, `ops`
@@ -61,23 +90,326 @@ pub struct SharedOptions {
     #[arg(long, value_enum)]
     pub verbosity: Option<Verbosity>,
 
+    /// Increase the level of logs to display.
+    ///
+    /// `-v` displays `debug` logs and `-vv` displays `trace` logs.
+    ///
+    /// Ignored if `verbosity` is set on the command line.
+    #[arg(short, long, action = ArgAction::Count)]
+    #[serde(skip)]
+    pub verbose: Option<u8>,
+
+    /// Decrease the level of logs to display.
+    ///
+    /// `-q` displays `warn` logs, `-qq` displays `error` logs and `-qqq` displays nothing.
+    ///
+    /// Ignored if `verbosity` is set on the command line.
+    #[arg(short, long, action = ArgAction::Count)]
+    #[serde(skip)]
+    pub quiet: Option<u8>,
+
+    /// Level of logs to display for specific modules.
+    ///
+    /// A comma separated list of `module=level` and an optional `level` that
+    /// replaces `verbosity` for every other module.
+    ///
+    /// Examples: `caesura::transcode=trace`, `warn,caesura::upload=debug`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub log_filter: Option<String>,
+
+    /// Should colors be removed from the logs?
+    ///
+    /// Default: `true` if the `NO_COLOR` environment variable is set, otherwise `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub no_color: Option<bool>,
+
     /// Path to the configuration file.
     ///
     /// Default: `./config.yml`
     #[arg(long)]
     pub config: Option<PathBuf>,
 
+    /// Name of a profile in the config file to apply.
+    ///
+    /// Profiles are defined under the `profiles` key of the config file and
+    /// override the options at the root of the config file.
+    ///
+    /// Examples: `red`, `ops`, `archive`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub profile: Option<String>,
+
     /// Time format to use in logs.
     ///
     /// Default: `datetime`
     #[arg(long)]
     pub log_time: Option<TimeFormat>,
 
+    /// Format of the logs.
+    ///
+    /// Use `json` to write each log as a JSON object on a single line so logs
+    /// can be ingested by a log aggregator such as Loki or Elasticsearch.
+    ///
+    /// Default: `text`
+    #[arg(long, value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Path of a file to append logs to in addition to the console.
+    ///
+    /// Colors are removed and the file uses the same `log_format` as the console.
+    ///
+    /// Examples: `./caesura.log`, `/var/log/caesura/caesura.log`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+
+    /// Level of logs to write to the log file.
+    ///
+    /// Default: `debug`
+    #[arg(long, value_enum)]
+    pub log_file_verbosity: Option<Verbosity>,
+
+    /// When to rotate the log file.
+    ///
+    /// Rotated files are numbered so `caesura.log` becomes `caesura.log.1`.
+    ///
+    /// Default: `size`
+    #[arg(long, value_enum)]
+    pub log_file_rotation: Option<LogRotation>,
+
+    /// Maximum size in bytes of the log file before it's rotated.
+    ///
+    /// Only applies if `log_file_rotation` is `size`.
+    ///
+    /// Default: `10000000`
+    #[arg(long)]
+    pub log_file_max_size: Option<u64>,
+
+    /// Number of rotated log files to keep.
+    ///
+    /// Default: `5`
+    #[arg(long)]
+    pub log_file_keep: Option<usize>,
+
+    /// Format of the progress written while a command is executed.
+    ///
+    /// Use `ndjson` to write a JSON object per line for each step such as
+    /// `source_resolved`, `verify_failed`, `track_transcoded`, `torrent_created`
+    /// and `upload_done` so another program can show the status.
+    ///
+    /// Use `tui` with `batch` for an interactive interface of the queue, the jobs of the
+    /// current step and the most recent logs. Press `p` to pause, `s` to skip the selected
+    /// item, `r` to retry it and `q` to quit. Other commands don't show progress with `tui`.
+    ///
+    /// Default: `bar`
+    #[arg(long, value_enum)]
+    pub progress: Option<ProgressFormat>,
+
+    /// Path of a file or named pipe to write `ndjson` progress events to.
+    ///
+    /// Writing to a named pipe waits until another program opens it for reading.
+    ///
+    /// Examples: `./progress.ndjson`, `/tmp/caesura.pipe`
+    ///
+    /// Default: stdout
+    #[arg(long)]
+    pub progress_path: Option<PathBuf>,
+
+    /// Format of the result written to stdout when a command completes.
+    ///
+    /// Use `json` to write a single JSON object with whether the command succeeded
+    /// and the verify issues, spectrogram directory, transcode directories, torrent
+    /// files and upload ids of each source so the command can be used from a script.
+    ///
+    /// Logs are always written to stderr.
+    ///
+    /// Default: `text`
+    #[arg(long, value_enum)]
+    pub result_format: Option<ResultFormat>,
+
+    /// Should the wall time of each phase be shown when a command completes?
+    ///
+    /// The breakdown includes API requests, verify rules, the hash check, decoding,
+    /// encoding to each format and torrent creation, followed by the slowest tracks,
+    /// so it's clear whether the disk, CPU or API is the bottleneck.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub timings: Option<bool>,
+
+    /// Should confirmation prompts be answered yes automatically?
+    ///
+    /// Uploading to the indexer and replacing an existing output directory can't be
+    /// undone so they are confirmed interactively before they're performed.
+    ///
+    /// If stdin is not a terminal then there's nobody to confirm so the action is
+    /// refused unless `yes` is set. Set `yes` when running from a script, a scheduled
+    /// task or a container.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub yes: Option<bool>,
+
+    /// Unicode normalization form of the names of output files and directories.
+    ///
+    /// Names are also compared regardless of their form when finding the source
+    /// directory and checking the hashes of the source so sources written by macOS
+    /// match the names in the torrent.
+    ///
+    /// Default: `nfc`
+    #[arg(long, value_enum)]
+    pub unicode_form: Option<UnicodeForm>,
+
     /// Directory where transcodes and spectrograms will be written.
     ///
     /// Default: `./output`
     #[arg(long)]
     pub output: Option<PathBuf>,
+
+    /// Template of the transcode directory relative to the output directory.
+    ///
+    /// Each `/` separates a directory. The last directory is also the name of the torrent.
+    ///
+    /// Placeholders: `{artist}`, `{artist_initial}`, `{album}`, `{remaster_title}`, `{year}`,
+    /// `{media}`, `{format}`, `{source}` and `{name}`.
+    ///
+    /// `{format}` or `{name}` is required so each target format has its own directory.
+    ///
+    /// Example: `{artist_initial}/{artist}/{album} ({year}) [{format}]`
+    ///
+    /// Default: `{name}`
+    #[arg(long)]
+    pub output_layout: Option<String>,
+
+    /// Template of the spectrogram directory relative to the output directory.
+    ///
+    /// Each `/` separates a directory. Start with `{source_parent}` to write the
+    /// spectrograms next to the source, in the directory containing it.
+    ///
+    /// Placeholders: `{artist}`, `{artist_initial}`, `{album}`, `{remaster_title}`, `{year}`,
+    /// `{media}`, `{source}` and `{spectrograms}`.
+    ///
+    /// Examples: `spectrograms/{artist} - {album}`, `{source_parent}/{spectrograms}`
+    ///
+    /// Default: `{spectrograms}`
+    #[arg(long)]
+    pub spectrogram_layout: Option<String>,
+
+    /// Should junk wrapper folders and nested disc folders be flattened in the output?
+    ///
+    /// Wrapper folders such as `Album/FLAC/` are removed so the content is at the root of
+    /// the transcode, and folders nested in a folder of the same name such as
+    /// `Disc 1/Disc 1/` are collapsed. The source is never changed.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub flatten_nested_dirs: Option<bool>,
+
+    /// Should names be shortened in the output if a path is too long?
+    ///
+    /// If a transcode path would exceed 180 characters then tracks are named from their
+    /// track number and title, and a parenthetical suffix is removed from the album.
+    /// The source is never changed.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub auto_shorten: Option<bool>,
+
+    /// Should the names of output files and directories be transliterated to ASCII?
+    ///
+    /// Accents are removed and Greek and Cyrillic are transliterated. Characters that
+    /// can't be transliterated, such as CJK, are replaced by `_`.
+    ///
+    /// Tags are not changed so the original titles are kept.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub transliterate: Option<bool>,
+
+    /// Should leading and repeated spaces be removed from the names of output files and
+    /// directories?
+    ///
+    /// Trailing spaces and dots and control characters are always removed so the names
+    /// can be opened on Windows. The verify command warns of each of these in the names
+    /// of the source.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub normalize_names: Option<bool>,
+
+    /// Regex substitutions applied in order to the names of output files and directories.
+    ///
+    /// Each substitution is `PATTERN=>REPLACEMENT` and the replacement can refer to capture
+    /// groups with `$1`. Substitutions are applied before names are shortened or
+    /// transliterated.
+    ///
+    /// Examples: `—=>-`, ` \(feat\. [^)]*\)=>`, `\s{2,}=> `
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub name_substitution: Option<Vec<String>>,
+
+    /// Regex matching a single character the indexer permits in the names of uploads.
+    ///
+    /// Any other character in the names of output files and directories, and so the
+    /// torrent, is replaced by `_` unless `character_replacement` has a replacement. The
+    /// verify command warns of each name of the source that would be changed.
+    ///
+    /// Set in a profile to apply the rules of each indexer.
+    ///
+    /// Example: `[\p{L}\p{N} ._()\[\]&,'!-]`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub allowed_characters: Option<String>,
+
+    /// Replacements of characters in the names of output files and directories.
+    ///
+    /// Each replacement is `CHARACTER=>REPLACEMENT`. Replacements are applied after
+    /// `name_substitution` and `transliterate`, and each replacement must only contain
+    /// `allowed_characters`.
+    ///
+    /// Examples: `&=>and`, `#=>No.`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub character_replacement: Option<Vec<String>>,
+
+    /// Path of a file to append a report of each error and panic to as a line of JSON.
+    ///
+    /// Each report includes the output of a failed external command. The API key,
+    /// announce URL and anything that looks like a secret are removed.
+    ///
+    /// Examples: `./cache/crash-reports.ndjson`, `/var/log/caesura/crash-reports.ndjson`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub report_file: Option<PathBuf>,
+
+    /// Sentry DSN of a project to send a report of each error and panic to.
+    ///
+    /// Reports are scrubbed of secrets in the same way as `report_file`.
+    ///
+    /// Examples: `https://a1b2c3d4e5f6@o123456.ingest.sentry.io/7890`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub report_dsn: Option<String>,
+
+    /// Directory to save the full output of each failed external command to.
+    ///
+    /// Each failure is appended to a file named after the id of the source such as
+    /// `123456.log` with the command line, exit status, stderr and stdout.
+    ///
+    /// Examples: `./cache/diagnostics`, `/var/log/caesura/diagnostics`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub diagnostics_dir: Option<PathBuf>,
 }
 
 #[injectable]
@@ -85,6 +417,101 @@ impl SharedOptions {
     fn new(provider: Ref<OptionsProvider>) -> Self {
         provider.get()
     }
+
+    #[must_use]
+    pub fn get_log_filter(&self) -> Option<LogFilter> {
+        let log_filter = self.log_filter.clone()?;
+        LogFilter::parse(log_filter.as_str()).ok()
+    }
+
+    /// Get the valid name substitutions.
+    #[must_use]
+    pub fn get_name_substitutions(&self) -> Vec<NameSubstitution> {
+        self.name_substitution
+            .iter()
+            .flatten()
+            .filter_map(|substitution| NameSubstitution::parse(substitution).ok())
+            .collect()
+    }
+
+    /// Get the [`CharacterWhitelist`] of `allowed_characters` and `character_replacement`.
+    ///
+    /// Invalid options are ignored as they're reported by validation.
+    #[must_use]
+    pub fn get_character_whitelist(&self) -> CharacterWhitelist {
+        CharacterWhitelist::new(
+            self.allowed_characters.as_deref(),
+            self.character_replacement.as_deref().unwrap_or_default(),
+        )
+        .unwrap_or_default()
+    }
+
+    /// Get the errors of the options that name output files and directories.
+    fn get_naming_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(output_layout) = &self.output_layout {
+            if let Err(details) = LayoutTemplate::validate(output_layout) {
+                errors.push(TemplateInvalid(
+                    "output_layout".to_owned(),
+                    output_layout.clone(),
+                    details,
+                ));
+            }
+        }
+        if let Some(spectrogram_layout) = &self.spectrogram_layout {
+            if let Err(details) = SpectrogramLayout::validate(spectrogram_layout) {
+                errors.push(TemplateInvalid(
+                    "spectrogram_layout".to_owned(),
+                    spectrogram_layout.clone(),
+                    details,
+                ));
+            }
+        }
+        for substitution in self.name_substitution.iter().flatten() {
+            if let Err(details) = NameSubstitution::parse(substitution) {
+                errors.push(SubstitutionInvalid(
+                    "name_substitution".to_owned(),
+                    substitution.clone(),
+                    details,
+                ));
+            }
+        }
+        if let Some(allowed) = &self.allowed_characters {
+            if let Err(details) = CharacterWhitelist::validate_allowed(allowed) {
+                errors.push(CharactersInvalid(
+                    "allowed_characters".to_owned(),
+                    allowed.clone(),
+                    details,
+                ));
+                return errors;
+            }
+        }
+        let replacements = self.character_replacement.as_deref().unwrap_or_default();
+        for replacement in replacements {
+            let result = CharacterWhitelist::validate_replacement(replacement).and_then(|()| {
+                CharacterWhitelist::new(self.allowed_characters.as_deref(), from_ref(replacement))
+                    .map(|_| ())
+            });
+            if let Err(details) = result {
+                errors.push(SubstitutionInvalid(
+                    "character_replacement".to_owned(),
+                    replacement.clone(),
+                    details,
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Apply the `-v` and `-q` flags to `verbosity` if it's not set.
+    fn apply_verbose_flags(mut self) -> Self {
+        let verbose = self.verbose.unwrap_or_default();
+        let quiet = self.quiet.unwrap_or_default();
+        if self.verbosity.is_none() && (verbose > 0 || quiet > 0) {
+            self.verbosity = Some(adjust_verbosity(Verbosity::default(), verbose, quiet));
+        }
+        self
+    }
 }
 
 impl Options for SharedOptions {
@@ -92,6 +519,7 @@ impl Options for SharedOptions {
         "Shared Options".to_owned()
     }
 
+    #[allow(clippy::too_many_lines)]
     fn merge(&mut self, alternative: &Self) {
         if self.announce_url.is_none() {
             self.announce_url.clone_from(&alternative.announce_url);
@@ -99,6 +527,9 @@ impl Options for SharedOptions {
         if self.api_key.is_none() {
             self.api_key.clone_from(&alternative.api_key);
         }
+        if self.session_cookie.is_none() {
+            self.session_cookie.clone_from(&alternative.session_cookie);
+        }
         if self.indexer.is_none() {
             self.indexer.clone_from(&alternative.indexer);
         }
@@ -114,12 +545,98 @@ impl Options for SharedOptions {
         if self.config.is_none() {
             self.config.clone_from(&alternative.config);
         }
+        if self.profile.is_none() {
+            self.profile.clone_from(&alternative.profile);
+        }
         if self.log_time.is_none() {
             self.log_time.clone_from(&alternative.log_time);
         }
+        if self.log_format.is_none() {
+            self.log_format = alternative.log_format;
+        }
+        if self.log_filter.is_none() {
+            self.log_filter.clone_from(&alternative.log_filter);
+        }
+        if self.no_color.is_none() {
+            self.no_color = alternative.no_color;
+        }
+        if self.log_file.is_none() {
+            self.log_file.clone_from(&alternative.log_file);
+        }
+        if self.log_file_verbosity.is_none() {
+            self.log_file_verbosity = alternative.log_file_verbosity;
+        }
+        if self.log_file_rotation.is_none() {
+            self.log_file_rotation = alternative.log_file_rotation;
+        }
+        if self.log_file_max_size.is_none() {
+            self.log_file_max_size = alternative.log_file_max_size;
+        }
+        if self.log_file_keep.is_none() {
+            self.log_file_keep = alternative.log_file_keep;
+        }
+        if self.progress.is_none() {
+            self.progress = alternative.progress;
+        }
+        if self.progress_path.is_none() {
+            self.progress_path.clone_from(&alternative.progress_path);
+        }
+        if self.result_format.is_none() {
+            self.result_format = alternative.result_format;
+        }
+        if self.timings.is_none() {
+            self.timings = alternative.timings;
+        }
+        if self.yes.is_none() {
+            self.yes = alternative.yes;
+        }
+        if self.unicode_form.is_none() {
+            self.unicode_form = alternative.unicode_form;
+        }
         if self.output.is_none() {
             self.output.clone_from(&alternative.output);
         }
+        if self.output_layout.is_none() {
+            self.output_layout.clone_from(&alternative.output_layout);
+        }
+        if self.spectrogram_layout.is_none() {
+            self.spectrogram_layout
+                .clone_from(&alternative.spectrogram_layout);
+        }
+        if self.flatten_nested_dirs.is_none() {
+            self.flatten_nested_dirs = alternative.flatten_nested_dirs;
+        }
+        if self.auto_shorten.is_none() {
+            self.auto_shorten = alternative.auto_shorten;
+        }
+        if self.transliterate.is_none() {
+            self.transliterate = alternative.transliterate;
+        }
+        if self.normalize_names.is_none() {
+            self.normalize_names = alternative.normalize_names;
+        }
+        if self.name_substitution.is_none() {
+            self.name_substitution
+                .clone_from(&alternative.name_substitution);
+        }
+        if self.allowed_characters.is_none() {
+            self.allowed_characters
+                .clone_from(&alternative.allowed_characters);
+        }
+        if self.character_replacement.is_none() {
+            self.character_replacement
+                .clone_from(&alternative.character_replacement);
+        }
+        if self.report_file.is_none() {
+            self.report_file.clone_from(&alternative.report_file);
+        }
+        if self.report_dsn.is_none() {
+            self.report_dsn.clone_from(&alternative.report_dsn);
+        }
+        if self.diagnostics_dir.is_none() {
+            self.diagnostics_dir
+                .clone_from(&alternative.diagnostics_dir);
+        }
     }
 
     fn apply_defaults(&mut self) {
@@ -150,23 +667,74 @@ impl Options for SharedOptions {
         if self.log_time.is_none() {
             self.log_time = Some(TimeFormat::default());
         }
+        if self.log_format.is_none() {
+            self.log_format = Some(LogFormat::default());
+        }
+        if self.no_color.is_none() {
+            self.no_color = Some(is_no_color_set());
+        }
+        if self.log_file_verbosity.is_none() {
+            self.log_file_verbosity = Some(Verbosity::Debug);
+        }
+        if self.log_file_rotation.is_none() {
+            self.log_file_rotation = Some(LogRotation::default());
+        }
+        if self.log_file_max_size.is_none() {
+            self.log_file_max_size = Some(10_000_000);
+        }
+        if self.log_file_keep.is_none() {
+            self.log_file_keep = Some(5);
+        }
+        if self.progress.is_none() {
+            self.progress = Some(ProgressFormat::default());
+        }
+        if self.result_format.is_none() {
+            self.result_format = Some(ResultFormat::default());
+        }
+        if self.timings.is_none() {
+            self.timings = Some(false);
+        }
+        if self.yes.is_none() {
+            self.yes = Some(false);
+        }
+        if self.unicode_form.is_none() {
+            self.unicode_form = Some(UnicodeForm::default());
+        }
         if self.content.is_none() {
             self.content = Some(vec![PathBuf::from(DEFAULT_CONTENT_PATH)]);
         }
         if self.output.is_none() {
             self.output = Some(PathBuf::from(DEFAULT_OUTPUT_PATH));
         }
+        if self.output_layout.is_none() {
+            self.output_layout = Some(DEFAULT_OUTPUT_LAYOUT.to_owned());
+        }
+        if self.spectrogram_layout.is_none() {
+            self.spectrogram_layout = Some(DEFAULT_SPECTROGRAM_LAYOUT.to_owned());
+        }
+        if self.flatten_nested_dirs.is_none() {
+            self.flatten_nested_dirs = Some(false);
+        }
+        if self.auto_shorten.is_none() {
+            self.auto_shorten = Some(false);
+        }
+        if self.transliterate.is_none() {
+            self.transliterate = Some(false);
+        }
+        if self.normalize_names.is_none() {
+            self.normalize_names = Some(false);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    #[allow(clippy::too_many_lines)]
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if let Some(config) = &self.config {
             if config.ends_with(".json")
                 || (config.eq(&PathBuf::from(DEFAULT_CONFIG_PATH)) && !config.is_file())
             {
                 errors.push(Changed(
-                    "Config File".to_owned(),
+                    "config".to_owned(),
                     config.to_string_lossy().to_string(),
                     "In v0.19.0 the config file format changed. A YAML file is now required.
 Please see the release notes for more details:
@@ -176,44 +744,49 @@ https://github.com/RogueOneEcho/caesura/releases/tag/v0.19.0"
             }
             if !config.is_file() {
                 errors.push(DoesNotExist(
-                    "Config File".to_owned(),
+                    "config".to_owned(),
                     config.to_string_lossy().to_string(),
                 ));
             }
         }
         if self.api_key.is_none() {
-            errors.push(NotSet("API Key".to_owned()));
+            errors.push(NotSet("api_key".to_owned()));
         }
         if self.indexer.is_none() {
-            errors.push(NotSet("Indexer".to_owned()));
+            errors.push(NotSet("indexer".to_owned()));
         }
         if self.indexer_url.is_none() {
-            errors.push(NotSet("Indexer URL".to_owned()));
+            errors.push(NotSet("indexer_url".to_owned()));
         } else {
             let indexer_url = self.indexer_url.clone().expect("indexer_url should be set");
             if !indexer_url.starts_with("https://") && !indexer_url.starts_with("http://") {
-                errors.push(UrlNotHttp("Indexer URL".to_owned(), indexer_url.clone()));
+                errors.push(UrlNotHttp("indexer_url".to_owned(), indexer_url.clone()));
+            } else if self.session_cookie.is_some() && !indexer_url.starts_with("https://") {
+                errors.push(UrlNotHttps("indexer_url".to_owned(), indexer_url.clone()));
             }
             if indexer_url.ends_with('/') {
                 errors.push(UrlInvalidSuffix(
-                    "Indexer URL".to_owned(),
+                    "indexer_url".to_owned(),
                     indexer_url.clone(),
                 ));
             }
         }
         if self.announce_url.is_none() {
-            errors.push(NotSet("Announce URL".to_owned()));
+            let is_fetchable = self.indexer.as_deref().and_then(get_tracker_url).is_some();
+            if !is_fetchable {
+                errors.push(NotSet("announce_url".to_owned()));
+            }
         } else {
             let announce_url = self
                 .announce_url
                 .clone()
                 .expect("announce_url should be set");
             if !announce_url.starts_with("https://") && !announce_url.starts_with("http://") {
-                errors.push(UrlNotHttp("Announce URL".to_owned(), announce_url.clone()));
+                errors.push(UrlNotHttp("announce_url".to_owned(), announce_url.clone()));
             }
             if announce_url.ends_with('/') {
                 errors.push(UrlInvalidSuffix(
-                    "Announce URL".to_owned(),
+                    "announce_url".to_owned(),
                     announce_url.clone(),
                 ));
             }
@@ -222,43 +795,95 @@ https://github.com/RogueOneEcho/caesura/releases/tag/v0.19.0"
             for dir in directories {
                 if !dir.exists() || !dir.is_dir() {
                     errors.push(DoesNotExist(
-                        "Content Directory".to_owned(),
+                        "content".to_owned(),
                         dir.to_string_lossy().to_string(),
                     ));
                 }
             }
         } else {
-            errors.push(NotSet("Content Directory".to_owned()));
+            errors.push(NotSet("content".to_owned()));
         }
         if let Some(output_directory) = &self.output {
             if !output_directory.exists() || !output_directory.is_dir() {
                 errors.push(DoesNotExist(
-                    "Output Directory".to_owned(),
+                    "output".to_owned(),
                     output_directory.to_string_lossy().to_string(),
                 ));
             }
         } else {
-            errors.push(NotSet("Output Directory".to_owned()));
+            errors.push(NotSet("output".to_owned()));
+        }
+        errors.append(&mut self.get_naming_errors());
+        if let Some(log_filter) = &self.log_filter {
+            if let Err(details) = LogFilter::parse(log_filter) {
+                errors.push(LogFilterInvalid(
+                    "log_filter".to_owned(),
+                    log_filter.clone(),
+                    details,
+                ));
+            }
+        }
+        if let Some(report_dsn) = &self.report_dsn {
+            if let Err(details) = SentryDsn::parse(report_dsn) {
+                errors.push(DsnInvalid(
+                    "report_dsn".to_owned(),
+                    report_dsn.clone(),
+                    details,
+                ));
+            }
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
     fn from_args() -> Option<Self> {
-        match ArgumentsParser::get() {
-            Some(
-                Batch { shared, .. }
-                | Queue {
-                    command: Add { shared, .. } | List { shared, .. } | Summary { shared, .. },
-                    ..
-                }
-                | Spectrogram { shared, .. }
-                | Transcode { shared, .. }
-                | Verify { shared, .. }
-                | Upload { shared, .. },
-            ) => Some(shared),
-            _ => None,
+        let Some(
+            Batch { shared, .. }
+            | Clean { shared, .. }
+            | Config {
+                command: Some(SetKey { shared, .. } | Show { shared, .. }),
+            }
+            | CrossSeed { shared, .. }
+            | Doctor { shared, .. }
+            | History { shared, .. }
+            | Init { shared }
+            | Queue {
+                command: Add { shared, .. } | List { shared, .. } | Summary { shared, .. },
+                ..
+            }
+            | SelfUpdate { shared, .. }
+            | Spectrogram { shared, .. }
+            | Transcode { shared, .. }
+            | Verify { shared, .. }
+            | Upload { shared, .. }
+            | Watch { shared, .. }
+            | Serve { shared, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        let mut options = shared.apply_verbose_flags();
+        if options.no_color == Some(false) {
+            options.no_color = None;
+        }
+        if options.timings == Some(false) {
+            options.timings = None;
+        }
+        if options.yes == Some(false) {
+            options.yes = None;
+        }
+        if options.flatten_nested_dirs == Some(false) {
+            options.flatten_nested_dirs = None;
+        }
+        if options.auto_shorten == Some(false) {
+            options.auto_shorten = None;
+        }
+        if options.transliterate == Some(false) {
+            options.transliterate = None;
+        }
+        if options.normalize_names == Some(false) {
+            options.normalize_names = None;
         }
+        Some(options)
     }
 
     #[allow(clippy::absolute_paths)]
@@ -1,25 +1,34 @@
 use std::fmt::{Display, Formatter};
 
 use crate::cli::ArgumentsParser;
-use clap::Args;
+use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 
-use crate::cli::CommandArguments::{Spectrogram, Transcode, Upload, Verify};
+use crate::cli::CommandArguments::{CrossSeed, Spectrogram, Transcode, Upload, Verify};
 use crate::options::{NotSet, OptionRule, Options, OptionsProvider};
 
-/// Source argument used by Verify, Spectrogram, Transcode, and Upload commands
+/// Source argument used by Verify, Spectrogram, Transcode, Upload, and Cross Seed commands
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SourceArg {
-    /// Source as: torrent id, path to torrent file, or indexer url.
+    /// Source as: torrent id, path to torrent file, indexer url, or a search.
+    ///
+    /// A search lists the matching FLAC torrents to pick from.
     ///
     /// Examples:
     /// `4871992`,
     /// `path/to/something.torrent`,
-    /// `https://example.com/torrents.php?id=2259978&torrentid=4871992#torrent4871992`, or
-    /// `https://example.com/torrents.php?torrentid=4871992`
+    /// `https://example.com/torrents.php?id=2259978&torrentid=4871992#torrent4871992`,
+    /// `https://example.com/torrents.php?torrentid=4871992`, or
+    /// `"artist - album"`
     #[arg(value_name = "SOURCE")]
     pub source: Option<String>,
+
+    /// Should the first result of a search be picked without prompting?
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub first: Option<bool>,
 }
 
 #[injectable]
@@ -38,28 +47,40 @@ impl Options for SourceArg {
         if self.source.is_none() {
             self.source.clone_from(&alternative.source);
         }
+        if self.first.is_none() {
+            self.first = alternative.first;
+        }
     }
 
-    fn apply_defaults(&mut self) {}
+    fn apply_defaults(&mut self) {
+        if self.first.is_none() {
+            self.first = Some(false);
+        }
+    }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if self.source.is_none() {
-            errors.push(NotSet("Source".to_owned()));
+            errors.push(NotSet("source".to_owned()));
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
     fn from_args() -> Option<Self> {
         match ArgumentsParser::get() {
             Some(
-                Spectrogram { source, .. }
+                CrossSeed { source, .. }
+                | Spectrogram { source, .. }
                 | Transcode { source, .. }
                 | Verify { source, .. }
                 | Upload { source, .. },
-            ) => Some(source),
+            ) => {
+                let mut options = source;
+                if options.first == Some(false) {
+                    options.first = None;
+                }
+                Some(options)
+            }
             _ => None,
         }
     }
@@ -1,14 +1,15 @@
 use std::fmt::{Display, Formatter};
 
 use crate::cli::ArgumentsParser;
-use crate::cli::CommandArguments::{Batch, Spectrogram};
+use crate::cli::CommandArguments::{Batch, Serve, Spectrogram, Watch};
 use crate::options::{IsEmpty, OptionRule, Options, OptionsProvider};
-use crate::spectrogram::Size;
-use clap::Args;
+use crate::spectrogram::{Channels, Size};
+use clap::{ArgAction, Args};
 use di::{injectable, Ref};
 use serde::{Deserialize, Serialize};
 
 /// Options for [`SpectrogramCommand`]
+#[allow(clippy::struct_field_names)]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SpectrogramOptions {
     /// Sizes of spectrograms to generate.
@@ -16,6 +17,48 @@ pub struct SpectrogramOptions {
     /// Default: `full` and `zoom`
     #[arg(long)]
     pub spectrogram_size: Option<Vec<Size>>,
+
+    /// Channels to render in each spectrogram.
+    ///
+    /// `left` renders only the first channel, `mix` renders a mix of every channel,
+    /// `split` renders a separate image of each channel and `stacked` renders every
+    /// channel in one image.
+    ///
+    /// Separate channels reveal joint stereo artifacts and anomalies in a single
+    /// channel that are hidden by a mix.
+    ///
+    /// Default: `left`
+    #[arg(long, value_enum)]
+    pub spectrogram_channels: Option<Channels>,
+
+    /// Should spectrograms be generated again even if they are up to date?
+    ///
+    /// A spectrogram is up to date if it was modified after its FLAC file so
+    /// only the spectrograms of new or changed tracks are generated.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub spectrogram_force: Option<bool>,
+
+    /// Should each spectrogram be quantized to a 256 color palette and recompressed?
+    ///
+    /// Shrinks each image to a fraction of the size of the PNG written by sox, which
+    /// is quicker to upload to an image host that limits the bytes uploaded. Requires
+    /// `convert`.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub spectrogram_optimize: Option<bool>,
+
+    /// Width in pixels to downscale a wider spectrogram to.
+    ///
+    /// The aspect ratio is preserved. Requires `convert`.
+    ///
+    /// Examples: `1500`, `2000`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub spectrogram_max_width: Option<u32>,
 }
 
 #[injectable]
@@ -35,34 +78,65 @@ impl Options for SpectrogramOptions {
             self.spectrogram_size
                 .clone_from(&alternative.spectrogram_size);
         }
+        if self.spectrogram_channels.is_none() {
+            self.spectrogram_channels = alternative.spectrogram_channels;
+        }
+        if self.spectrogram_force.is_none() {
+            self.spectrogram_force = alternative.spectrogram_force;
+        }
+        if self.spectrogram_optimize.is_none() {
+            self.spectrogram_optimize = alternative.spectrogram_optimize;
+        }
+        if self.spectrogram_max_width.is_none() {
+            self.spectrogram_max_width = alternative.spectrogram_max_width;
+        }
     }
 
     fn apply_defaults(&mut self) {
         if self.spectrogram_size.is_none() {
             self.spectrogram_size = Some(vec![Size::Full, Size::Zoom]);
         }
+        if self.spectrogram_channels.is_none() {
+            self.spectrogram_channels = Some(Channels::Left);
+        }
+        if self.spectrogram_force.is_none() {
+            self.spectrogram_force = Some(false);
+        }
+        if self.spectrogram_optimize.is_none() {
+            self.spectrogram_optimize = Some(false);
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         let size = self.spectrogram_size.as_ref();
-        if size.is_none() || size.is_some_and(Vec::is_empty) {
-            errors.push(IsEmpty("Spectrogram Size".to_owned()));
+        if size.is_none_or(Vec::is_empty) {
+            errors.push(IsEmpty("spectrogram_size".to_owned()));
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
-    #[must_use]
     fn from_args() -> Option<SpectrogramOptions> {
-        match ArgumentsParser::get() {
-            Some(Batch { spectrogram, .. } | Spectrogram { spectrogram, .. }) => Some(spectrogram),
-            _ => None,
+        let Some(
+            Batch { spectrogram, .. }
+            | Spectrogram { spectrogram, .. }
+            | Watch { spectrogram, .. }
+            | Serve { spectrogram, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        let mut options = spectrogram;
+        if options.spectrogram_force == Some(false) {
+            options.spectrogram_force = None;
         }
+        if options.spectrogram_optimize == Some(false) {
+            options.spectrogram_optimize = None;
+        }
+        Some(options)
     }
-    #[allow(clippy::absolute_paths)]
 
+    #[allow(clippy::absolute_paths)]
     fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
         serde_json::from_str(json)
     }
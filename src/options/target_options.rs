@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 
 use clap::{ArgAction, Args};
@@ -6,18 +7,48 @@ use serde::{Deserialize, Serialize};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use crate::formats::TargetFormat;
-use crate::options::{IsEmpty, NotSet, OptionRule, Options, OptionsProvider};
+use crate::formats::{CustomTarget, TargetFormat};
+use crate::options::{
+    GainInvalid, IsEmpty, NotSet, OptionRule, Options, OptionsProvider, SizeInvalid, TargetInvalid,
+};
+use crate::torrent::parse_size;
+use crate::transcode::ProvenanceTag;
+
+/// Lowest gain in dB that `clip_gain` can be set to.
+const MIN_CLIP_GAIN: f64 = -20.0;
+
+/// Default size of the source tracks read ahead of the transcode jobs.
+const DEFAULT_READ_AHEAD: &str = "256MiB";
 
 /// Options for [`TranscodeCommand`] and [`VerifyCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
 pub struct TargetOptions {
     /// Formats to attempt to transcode to.
     ///
+    /// `v1`, `v2` and `256` (average bit rate) are personal targets that are only
+    /// uploaded if `upload_extra_targets` is set. Other encoder settings can be set with
+    /// `custom_target`.
+    ///
     /// Default: `flac`, `320` and `v0`
     #[arg(long)]
     pub target: Option<Vec<TargetFormat>>,
 
+    /// MP3 targets with custom `lame` settings for a personal library.
+    ///
+    /// Each target is `NAME=SETTINGS` where the settings are `V0` to `V9` for a variable
+    /// bit rate, `ABR` and a bit rate for an average bit rate, or `CBR` and a bit rate for
+    /// a constant bit rate. The name is used in place of the format in `output_layout` and
+    /// must not be the name of a `target`.
+    ///
+    /// Custom targets are transcoded alongside the other targets but are never uploaded
+    /// or cross-posted, so no `.torrent` file or archive is created for them.
+    ///
+    /// Examples: `V3=V3`, `ABR 192=ABR192`, `Car=CBR128`
+    ///
+    /// Default: none
+    #[arg(long)]
+    pub custom_target: Option<Vec<String>>,
+
     /// Allow transcoding to existing formats
     ///
     /// Note: This is only useful for development and should probably not be used.
@@ -25,6 +56,79 @@ pub struct TargetOptions {
     /// Default: `false`
     #[arg(long, default_value = None, action = ArgAction::SetTrue)]
     pub allow_existing: Option<bool>,
+
+    /// Should the existing formats of the group be re-checked immediately before transcoding?
+    ///
+    /// The targets are otherwise based on the formats when the source was retrieved, so in
+    /// a long batch another user may have uploaded a target in the meantime. Targets that
+    /// now exist are dropped.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub recheck_formats: Option<bool>,
+
+    /// Should every track be transcoded again rather than resuming an earlier attempt?
+    ///
+    /// The staged transcodes of an attempt that failed or was interrupted are kept, and
+    /// the next attempt only transcodes the tracks that are missing or fail to decode,
+    /// have the wrong number of samples, or are missing their tags or cue sheet.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub no_resume: Option<bool>,
+
+    /// Should the sources of MP3 targets be checked for inter-sample peaks over 0 dBFS?
+    ///
+    /// Hot masters often clip when encoded to MP3. A warning is logged if any track clips.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub clip_check: Option<bool>,
+
+    /// Gain in dB applied to every MP3 target of a source that clips.
+    ///
+    /// Must be negative. The gain is passed to `lame` as `--scale` for every track, so the
+    /// relative levels of the album are kept, and recorded in the upload description.
+    ///
+    /// Setting this implies `clip_check`.
+    ///
+    /// Default: None
+    #[arg(long, allow_hyphen_values = true)]
+    pub clip_gain: Option<f64>,
+
+    /// Tag of each output to record the provenance of the transcode in.
+    ///
+    /// The value has the caesura version, the encoder versions and the exact command so a
+    /// trump dispute can be resolved from the files themselves.
+    ///
+    /// Default: `none`
+    #[arg(long, value_enum)]
+    pub provenance_tag: Option<ProvenanceTag>,
+
+    /// Should a `transcode.txt` with the provenance be written to each transcode directory?
+    ///
+    /// The file has the caesura version, the encoder versions and the exact commands, and
+    /// is included in the torrent.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub provenance_file: Option<bool>,
+
+    /// Size of the source tracks to read into memory ahead of the transcode jobs.
+    ///
+    /// Each track is read once, in order, while the previous tracks are decoded and
+    /// encoded, which is faster on spinning disks and network mounts. The jobs of every
+    /// target are ordered by track so each track is released once all its targets have
+    /// started. A job that starts before its track is read, or whose track is larger
+    /// than `read_ahead`, streams the track directly.
+    ///
+    /// Set to `0` to read every track directly. At most `4GiB`.
+    ///
+    /// Examples: `256MiB`, `1GiB`
+    ///
+    /// Default: `256MiB`
+    #[arg(long)]
+    pub read_ahead: Option<String>,
 }
 
 #[injectable]
@@ -34,6 +138,27 @@ impl TargetOptions {
     }
 }
 
+impl TargetOptions {
+    /// Get the valid custom targets.
+    #[must_use]
+    pub fn get_custom_targets(&self) -> Vec<CustomTarget> {
+        self.custom_target
+            .iter()
+            .flatten()
+            .filter_map(|target| CustomTarget::parse(target).ok())
+            .collect()
+    }
+
+    /// Get the size of the source tracks to read ahead in bytes.
+    #[must_use]
+    pub fn get_read_ahead(&self) -> u32 {
+        self.read_ahead
+            .as_deref()
+            .and_then(parse_read_ahead)
+            .expect("read_ahead should be valid")
+    }
+}
+
 impl Options for TargetOptions {
     fn get_name() -> String {
         "Target Options".to_owned()
@@ -43,9 +168,33 @@ impl Options for TargetOptions {
         if self.target.is_none() {
             self.target.clone_from(&alternative.target);
         }
+        if self.custom_target.is_none() {
+            self.custom_target.clone_from(&alternative.custom_target);
+        }
         if self.allow_existing.is_none() {
             self.allow_existing = alternative.allow_existing;
         }
+        if self.recheck_formats.is_none() {
+            self.recheck_formats = alternative.recheck_formats;
+        }
+        if self.no_resume.is_none() {
+            self.no_resume = alternative.no_resume;
+        }
+        if self.clip_check.is_none() {
+            self.clip_check = alternative.clip_check;
+        }
+        if self.clip_gain.is_none() {
+            self.clip_gain = alternative.clip_gain;
+        }
+        if self.provenance_tag.is_none() {
+            self.provenance_tag = alternative.provenance_tag;
+        }
+        if self.provenance_file.is_none() {
+            self.provenance_file = alternative.provenance_file;
+        }
+        if self.read_ahead.is_none() {
+            self.read_ahead.clone_from(&alternative.read_ahead);
+        }
     }
 
     fn apply_defaults(&mut self) {
@@ -56,32 +205,81 @@ impl Options for TargetOptions {
                 TargetFormat::V0,
             ]);
         }
+        if self.custom_target.is_none() {
+            self.custom_target = Some(Vec::new());
+        }
         if self.allow_existing.is_none() {
             self.allow_existing = Some(false);
         }
+        if self.recheck_formats.is_none() {
+            self.recheck_formats = Some(false);
+        }
+        if self.no_resume.is_none() {
+            self.no_resume = Some(false);
+        }
+        if self.clip_check.is_none() {
+            self.clip_check = Some(false);
+        }
+        if self.provenance_tag.is_none() {
+            self.provenance_tag = Some(ProvenanceTag::None);
+        }
+        if self.provenance_file.is_none() {
+            self.provenance_file = Some(false);
+        }
+        if self.read_ahead.is_none() {
+            self.read_ahead = Some(DEFAULT_READ_AHEAD.to_owned());
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
+    fn get_errors(&self) -> Vec<OptionRule> {
         let mut errors: Vec<OptionRule> = Vec::new();
         if let Some(targets) = &self.target {
             if targets.is_empty() {
-                errors.push(IsEmpty("Target format".to_owned()));
+                errors.push(IsEmpty("target".to_owned()));
             }
         } else {
-            errors.push(NotSet("Target format".to_owned()));
+            errors.push(NotSet("target".to_owned()));
+        }
+        let mut names = BTreeSet::new();
+        for value in self.custom_target.iter().flatten() {
+            let details = match CustomTarget::parse(value) {
+                Ok(target) if !names.insert(target.get_name().to_lowercase()) => {
+                    format!("Name is used by another custom target: `{target}`")
+                }
+                Ok(_) => continue,
+                Err(details) => details,
+            };
+            errors.push(TargetInvalid(
+                "custom_target".to_owned(),
+                value.clone(),
+                details,
+            ));
+        }
+        if let Some(gain) = self.clip_gain {
+            if !(MIN_CLIP_GAIN..0.0).contains(&gain) {
+                errors.push(GainInvalid("clip_gain".to_owned(), gain.to_string()));
+            }
+        }
+        if let Some(read_ahead) = &self.read_ahead {
+            if parse_read_ahead(read_ahead).is_none() {
+                errors.push(SizeInvalid(
+                    "read_ahead".to_owned(),
+                    read_ahead.clone(),
+                    "Expected a size of at most 4GiB such as 256MiB or 1GiB".to_owned(),
+                ));
+            }
         }
-        OptionRule::show(&errors);
-        errors.is_empty()
+        errors
     }
 
-    #[must_use]
     fn from_args() -> Option<Self> {
         let Some(
             Batch { target, .. }
             | Transcode { target, .. }
             | Upload { target, .. }
-            | Verify { target, .. },
+            | Verify { target, .. }
+            | Watch { target, .. }
+            | Serve { target, .. },
         ) = ArgumentsParser::get()
         else {
             return None;
@@ -90,10 +288,22 @@ impl Options for TargetOptions {
         if options.allow_existing == Some(false) {
             options.allow_existing = None;
         }
+        if options.recheck_formats == Some(false) {
+            options.recheck_formats = None;
+        }
+        if options.no_resume == Some(false) {
+            options.no_resume = None;
+        }
+        if options.clip_check == Some(false) {
+            options.clip_check = None;
+        }
+        if options.provenance_file == Some(false) {
+            options.provenance_file = None;
+        }
         Some(options)
     }
-    #[allow(clippy::absolute_paths)]
 
+    #[allow(clippy::absolute_paths)]
     fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
         serde_json::from_str(json)
     }
@@ -114,3 +324,8 @@ impl Display for TargetOptions {
         output.fmt(formatter)
     }
 }
+
+/// Parse a read ahead size that fits in the semaphore of the read ahead.
+fn parse_read_ahead(value: &str) -> Option<u32> {
+    parse_size(value).and_then(|size| u32::try_from(size).ok())
+}
@@ -0,0 +1,24 @@
+use crate::options::*;
+
+#[test]
+#[ignore = "requires an OS credential store"]
+fn store_api_key_then_get() {
+    // Arrange
+    let indexer = "caesura-test";
+
+    // Act
+    store_api_key(indexer, "a1b2c3d4").expect("should store API key");
+    let api_key = get_stored_api_key(indexer);
+
+    // Assert
+    assert_eq!(api_key, Some("a1b2c3d4".to_owned()));
+}
+
+#[test]
+fn get_stored_api_key_missing() {
+    // Act
+    let api_key = get_stored_api_key("caesura-test-missing");
+
+    // Assert
+    assert_eq!(api_key, None);
+}
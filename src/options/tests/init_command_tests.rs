@@ -0,0 +1,41 @@
+use crate::formats::TargetFormat;
+use crate::options::init_command::parse_targets;
+
+#[test]
+fn parse_targets_valid() {
+    // Arrange
+    let value = "flac, 320,V0";
+
+    // Act
+    let targets = parse_targets(value);
+
+    // Assert
+    assert_eq!(
+        targets,
+        Ok(vec![TargetFormat::Flac, TargetFormat::_320, TargetFormat::V0])
+    );
+}
+
+#[test]
+fn parse_targets_invalid() {
+    // Arrange
+    let value = "flac, aac";
+
+    // Act
+    let targets = parse_targets(value);
+
+    // Assert
+    assert!(targets.is_err());
+}
+
+#[test]
+fn parse_targets_empty() {
+    // Arrange
+    let value = " , ";
+
+    // Act
+    let targets = parse_targets(value);
+
+    // Assert
+    assert!(targets.is_err());
+}
@@ -1 +1,6 @@
 mod options_tests;
+mod options_provider_tests;
+mod init_command_tests;
+mod rules_tests;
+mod api_key_store_tests;
+mod option_origin_tests;
@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::options::{get_config_lines, ConfigScope, OptionOrigin, OriginResolver};
+
+const CONFIG: &str = "\
+# Comment
+indexer: red
+content:
+  - /srv/downloads
+profile: ops
+profiles:
+  ops:
+    indexer: ops
+    target: [flac]
+verify:
+  no_hash_check: true
+";
+
+#[test]
+fn get_config_lines_finds_nested_keys() {
+    // Act
+    let lines = get_config_lines(CONFIG);
+
+    // Assert
+    let get = |path: &[&str]| {
+        let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+        lines.get(&path).copied()
+    };
+    assert_eq!(get(&["indexer"]), Some(2));
+    assert_eq!(get(&["content"]), Some(3));
+    assert_eq!(get(&["profiles", "ops", "indexer"]), Some(8));
+    assert_eq!(get(&["profiles", "ops", "target"]), Some(9));
+    assert_eq!(get(&["verify", "no_hash_check"]), Some(11));
+    assert_eq!(get(&["no_hash_check"]), None);
+}
+
+#[test]
+fn origin_resolver_applies_precedence() {
+    // Arrange
+    let cli = BTreeSet::from(["cpus".to_owned()]);
+    let resolver = OriginResolver::new(
+        cli,
+        "cpus: 2\nverbosity: debug\n",
+        CONFIG,
+        PathBuf::from("config.yml"),
+        None,
+        Some("verify".to_owned()),
+        Some("api_key: abc\n"),
+    );
+    let config_file = |line, scope| OptionOrigin::ConfigFile {
+        path: PathBuf::from("config.yml"),
+        line: Some(line),
+        scope,
+    };
+
+    // Act
+    let cpus = resolver.get("cpus");
+    let verbosity = resolver.get("verbosity");
+    let indexer = resolver.get("indexer");
+    let content = resolver.get("content");
+    let no_hash_check = resolver.get("no_hash_check");
+    let api_key = resolver.get("api_key");
+    let output = resolver.get("output");
+
+    // Assert
+    assert_eq!(cpus, OptionOrigin::Cli("--cpus".to_owned()));
+    assert_eq!(verbosity, OptionOrigin::Env("CAESURA_VERBOSITY".to_owned()));
+    assert_eq!(
+        indexer,
+        config_file(8, ConfigScope::Profile("ops".to_owned()))
+    );
+    assert_eq!(content, config_file(3, ConfigScope::Root));
+    assert_eq!(
+        no_hash_check,
+        config_file(11, ConfigScope::Section("verify".to_owned()))
+    );
+    assert_eq!(api_key, OptionOrigin::CredentialStore);
+    assert_eq!(output, OptionOrigin::Default);
+    assert_eq!(indexer.to_string(), "config.yml:8 profile `ops`");
+}
+
+#[test]
+fn origin_resolver_prefers_profile_section() {
+    // Arrange
+    let config = "\
+no_hash_check: false
+profiles:
+  ops:
+    verify:
+      no_hash_check: true
+verify:
+  exclude_tags: [test]
+";
+    let resolver = OriginResolver::new(
+        BTreeSet::new(),
+        "",
+        config,
+        PathBuf::from("config.yml"),
+        Some("ops".to_owned()),
+        Some("verify".to_owned()),
+        None,
+    );
+
+    // Act
+    let no_hash_check = resolver.get("no_hash_check");
+    let exclude_tags = resolver.get("exclude_tags");
+
+    // Assert
+    assert_eq!(
+        no_hash_check.to_string(),
+        "config.yml:5 profile `ops` section `verify`"
+    );
+    assert_eq!(exclude_tags, OptionOrigin::Default);
+}
@@ -0,0 +1,259 @@
+use std::path::PathBuf;
+
+use crate::formats::TargetFormat;
+use crate::options::*;
+
+#[test]
+fn get_env_yaml_maps_prefixed_vars() {
+    // Arrange
+    let vars = vec![
+        ("CAESURA_API_KEY".to_owned(), "abc123".to_owned()),
+        ("CAESURA_CPUS".to_owned(), "4".to_owned()),
+        ("CAESURA_CONTENT".to_owned(), "[/a, /b]".to_owned()),
+        ("OTHER_API_KEY".to_owned(), "ignored".to_owned()),
+    ];
+
+    // Act
+    let yaml = get_env_yaml(vars);
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+    let runner = RunnerOptions::from_yaml(&yaml).expect("Should deserialize runner options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("abc123".to_owned()));
+    assert_eq!(
+        shared.content,
+        Some(vec![PathBuf::from("/a"), PathBuf::from("/b")])
+    );
+    assert_eq!(runner.cpus, Some(4));
+}
+
+#[test]
+fn get_env_yaml_numeric_string() {
+    // Arrange
+    let vars = vec![("CAESURA_API_KEY".to_owned(), "123456".to_owned())];
+
+    // Act
+    let yaml = get_env_yaml(vars);
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("123456".to_owned()));
+}
+
+#[test]
+fn get_env_yaml_empty() {
+    // Arrange
+    let vars = vec![("CAESURA_API_KEY".to_owned(), String::new())];
+
+    // Act
+    let yaml = get_env_yaml(vars);
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.api_key, None);
+}
+
+#[test]
+fn apply_profile_overrides_root() {
+    // Arrange
+    let yaml = "api_key: root
+output: ./output
+profiles:
+  ops:
+    api_key: ops
+    target: [flac]
+";
+
+    // Act
+    let yaml = apply_profile(yaml, Some("ops")).expect("Profile should apply");
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+    let target = TargetOptions::from_yaml(&yaml).expect("Should deserialize target options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("ops".to_owned()));
+    assert_eq!(shared.output, Some(PathBuf::from("./output")));
+    assert_eq!(target.target, Some(vec![TargetFormat::Flac]));
+    assert!(!yaml.contains("profiles"));
+}
+
+#[test]
+fn apply_profile_from_config_key() {
+    // Arrange
+    let yaml = "api_key: root
+profile: red
+profiles:
+  red:
+    api_key: red
+";
+
+    // Act
+    let yaml = apply_profile(yaml, None).expect("Profile should apply");
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("red".to_owned()));
+}
+
+#[test]
+fn apply_profile_missing() {
+    // Arrange
+    let yaml = "api_key: root
+profiles:
+  red:
+    api_key: red
+";
+
+    // Act
+    let result = apply_profile(yaml, Some("ops"));
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn apply_profile_none() {
+    // Arrange
+    let yaml = "api_key: root";
+
+    // Act
+    let yaml = apply_profile(yaml, None).expect("Config should be unchanged");
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("root".to_owned()));
+}
+
+#[test]
+fn apply_profile_invalid_without_profile() {
+    // Arrange
+    let yaml = "api_key: [unclosed";
+
+    // Act
+    let result = apply_profile(yaml, None).expect("Config should be unchanged");
+
+    // Assert
+    assert_eq!(result, yaml);
+}
+
+#[test]
+fn apply_command_section_overrides_root() {
+    // Arrange
+    let yaml = "api_key: root
+output: ./output
+verify:
+  output: ./verify
+transcode:
+  output: ./transcode
+";
+    let commands = vec!["verify".to_owned(), "transcode".to_owned()];
+
+    // Act
+    let yaml =
+        apply_command_section(yaml, Some("transcode"), &commands).expect("Section should apply");
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("root".to_owned()));
+    assert_eq!(shared.output, Some(PathBuf::from("./transcode")));
+    assert!(!yaml.contains("verify"));
+}
+
+#[test]
+fn apply_command_section_removes_other_sections() {
+    // Arrange
+    let yaml = "output: ./output
+verify:
+  output: ./verify
+";
+    let commands = vec!["verify".to_owned(), "upload".to_owned()];
+
+    // Act
+    let yaml =
+        apply_command_section(yaml, Some("upload"), &commands).expect("Section should apply");
+    let shared = SharedOptions::from_yaml(&yaml).expect("Should deserialize shared options");
+
+    // Assert
+    assert_eq!(shared.output, Some(PathBuf::from("./output")));
+    assert!(!yaml.contains("verify"));
+}
+
+#[test]
+fn apply_command_section_keeps_options_named_after_commands() {
+    // Arrange
+    let yaml = "transcode: true
+upload:
+  dry_run: true
+";
+    let commands = vec!["transcode".to_owned(), "upload".to_owned()];
+
+    // Act
+    let yaml = apply_command_section(yaml, Some("batch"), &commands).expect("Section should apply");
+    let batch = BatchOptions::from_yaml(&yaml).expect("Should deserialize batch options");
+
+    // Assert
+    assert_eq!(batch.transcode, Some(true));
+    assert!(!yaml.contains("upload"));
+}
+
+#[test]
+fn get_profile_options_applies_defaults() {
+    // Arrange
+    let yaml = "profile: red
+api_key: root
+profiles:
+  red:
+    announce_url: https://flacsfor.me/abc/announce
+  ops:
+    announce_url: https://home.opsfet.ch/abc/announce
+    api_key: ops
+";
+
+    // Act
+    let shared: SharedOptions = get_profile_options(yaml, "ops").expect("Profile should apply");
+
+    // Assert
+    assert_eq!(shared.api_key, Some("ops".to_owned()));
+    assert_eq!(shared.indexer, Some("ops".to_owned()));
+    assert_eq!(
+        shared.indexer_url,
+        Some("https://orpheus.network".to_owned())
+    );
+}
+
+#[test]
+fn merge_env_yaml_keeps_valid_options() {
+    // Arrange
+    let vars = vec![
+        ("CAESURA_CPUS".to_owned(), "four".to_owned()),
+        ("CAESURA_SHARED_CPUS".to_owned(), "true".to_owned()),
+    ];
+    let yaml = get_env_yaml(vars);
+    let mut runner = RunnerOptions::default();
+
+    // Act
+    merge_env_yaml(&mut runner, &yaml);
+
+    // Assert
+    assert_eq!(runner.cpus, None);
+    assert_eq!(runner.shared_cpus, Some(true));
+}
+
+#[test]
+fn get_env_errors_reports_invalid_variable() {
+    // Arrange
+    let vars = vec![
+        ("CAESURA_API_KEY".to_owned(), "abc123".to_owned()),
+        ("CAESURA_CPUS".to_owned(), "four".to_owned()),
+        ("CAESURA_UNKNOWN".to_owned(), "ignored".to_owned()),
+    ];
+    let yaml = get_env_yaml(vars);
+
+    // Act
+    let errors = get_env_errors(&yaml);
+
+    // Assert
+    let keys: Vec<&str> = errors.iter().map(OptionRule::get_key).collect();
+    assert_eq!(keys, vec!["cpus"]);
+    let message = errors.first().expect("should have an error").to_string();
+    assert!(message.starts_with("cpus (--cpus) set by CAESURA_CPUS could not be parsed: four"));
+}
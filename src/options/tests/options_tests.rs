@@ -1,6 +1,9 @@
 use crate::built_info::PKG_NAME;
+use crate::formats::TargetFormat;
 use crate::options::*;
 use rogue_logging::Logger;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[tokio::test]
 async fn batch_options_validate() {
@@ -67,6 +70,106 @@ async fn target_options_validate() {
     assert!(target_options.validate());
 }
 
+#[tokio::test]
+async fn torrent_options_validate() {
+    // Arrange
+    Logger::force_init(PKG_NAME.to_owned());
+    let provider = OptionsProvider::new();
+
+    // Act
+    let torrent_options = provider.get::<TorrentOptions>();
+
+    // Assert
+    assert!(torrent_options.validate());
+}
+
+#[test]
+fn torrent_options_get_errors_piece_size() {
+    // Arrange
+    let options = TorrentOptions {
+        torrent_piece_size: Some("3MiB".to_owned()),
+        torrent_min_piece_size: Some("1 parsec".to_owned()),
+        torrent_max_piece_size: Some("8MiB".to_owned()),
+        torrent_max_pieces: None,
+        torrent_version: None,
+    };
+
+    // Act
+    let errors = options.get_errors();
+
+    // Assert
+    let keys: Vec<&str> = errors.iter().map(OptionRule::get_key).collect();
+    assert_eq!(keys, vec!["torrent_piece_size", "torrent_min_piece_size"]);
+}
+
+#[test]
+fn target_options_get_errors_custom_target() {
+    // Arrange
+    let options = TargetOptions {
+        target: Some(vec![TargetFormat::V0]),
+        custom_target: Some(vec![
+            "V3=V3".to_owned(),
+            "v3=V4".to_owned(),
+            "V0=V0".to_owned(),
+            "Car=CBR100".to_owned(),
+            "Car=CBR128".to_owned(),
+        ]),
+        ..TargetOptions::default()
+    };
+
+    // Act
+    let errors = options.get_errors();
+
+    // Assert
+    let values: Vec<String> = errors
+        .iter()
+        .filter_map(|error| match error {
+            TargetInvalid(_, value, _) => Some(value.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(values, vec!["v3=V4", "V0=V0", "Car=CBR100"]);
+    assert_eq!(options.get_custom_targets().len(), 3);
+}
+
+#[test]
+fn shared_options_get_errors_session_cookie_requires_https() {
+    // Arrange
+    let mut options = SharedOptions {
+        indexer_url: Some("http://redacted.sh".to_owned()),
+        session_cookie: Some("a1b2c3d4e5f6".to_owned()),
+        ..SharedOptions::default()
+    };
+
+    // Act
+    let http = options.get_errors();
+    options.indexer_url = Some("https://redacted.sh".to_owned());
+    let https = options.get_errors();
+
+    // Assert
+    assert!(http
+        .iter()
+        .any(|error| matches!(error, UrlNotHttps(key, _) if key == "indexer_url")));
+    assert!(!https.iter().any(|error| matches!(error, UrlNotHttps(_, _))));
+}
+
+#[test]
+fn hook_options_get_errors_missing_script() {
+    // Arrange
+    let options = HookOptions {
+        post_verify_hook: None,
+        post_transcode_hook: Some(PathBuf::from("/does/not/exist.sh")),
+        post_upload_hook: Some(PathBuf::from("Cargo.toml")),
+    };
+
+    // Act
+    let errors = options.get_errors();
+
+    // Assert
+    let keys: Vec<&str> = errors.iter().map(OptionRule::get_key).collect();
+    assert_eq!(keys, vec!["post_transcode_hook"]);
+}
+
 #[tokio::test]
 async fn upload_options_validate() {
     // Arrange
@@ -105,3 +208,25 @@ async fn shared_options_validate() {
     // Assert
     assert!(shared_options.validate());
 }
+
+#[test]
+fn batch_options_max_duration() {
+    // Arrange
+    let valid = BatchOptions {
+        max_duration: Some("1h30m".to_owned()),
+        ..BatchOptions::default()
+    };
+    let invalid = BatchOptions {
+        max_duration: Some("forever".to_owned()),
+        ..BatchOptions::default()
+    };
+
+    // Act
+    let duration = valid.get_max_duration();
+    let errors = invalid.get_errors();
+
+    // Assert
+    assert_eq!(duration, Some(Duration::from_mins(90)));
+    assert!(valid.get_errors().is_empty());
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,114 @@
+use crate::options::*;
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[test]
+fn shared_options_get_errors_returns_all() {
+    // Arrange
+    let options = SharedOptions {
+        indexer_url: Some("example.com/".to_owned()),
+        output: Some(PathBuf::from("/this/path/does/not/exist")),
+        ..SharedOptions::default()
+    };
+
+    // Act
+    let errors = options.get_errors();
+
+    // Assert
+    let keys: Vec<&str> = errors.iter().map(OptionRule::get_key).collect();
+    assert_eq!(
+        keys,
+        vec![
+            "api_key",
+            "indexer",
+            "indexer_url",
+            "indexer_url",
+            "announce_url",
+            "content",
+            "output"
+        ]
+    );
+}
+
+#[test]
+fn option_rule_display_includes_flag() {
+    // Arrange
+    let rule = NotSet("api_key".to_owned());
+
+    // Act
+    let output = rule.to_string();
+
+    // Assert
+    assert_eq!(output, "api_key (--api-key) is not set");
+}
+
+#[test]
+fn option_rule_get_example() {
+    // Act
+    let example = DurationInvalid("wait_before_upload".to_owned(), "soon".to_owned()).get_example();
+    let dependent = Dependent("upload".to_owned(), "transcode".to_owned()).get_example();
+    let positional = NotSet("source".to_owned()).get_example();
+    let unknown = NotSet("unknown".to_owned()).get_example();
+
+    // Assert
+    assert_eq!(
+        example,
+        Some("`--wait-before-upload 5m` or `wait_before_upload: 5m` in the config file".to_owned())
+    );
+    assert_eq!(
+        dependent,
+        Some("`--transcode` or `transcode: true` in the config file".to_owned())
+    );
+    assert_eq!(positional, Some("`4871992`".to_owned()));
+    assert_eq!(unknown, None);
+}
+
+#[test]
+fn option_rule_get_example_covers_every_key() {
+    // Arrange
+    let options = [
+        get_keys(&BatchOptions::default()),
+        get_keys(&BeetsOptions::default()),
+        get_keys(&CacheOptions::default()),
+        get_keys(&CrossSeedOptions::default()),
+        get_keys(&DesktopOptions::default()),
+        get_keys(&EnrichOptions::default()),
+        get_keys(&FileOptions::default()),
+        get_keys(&HistoryOptions::default()),
+        get_keys(&HookOptions::default()),
+        get_keys(&NotifyOptions::default()),
+        get_keys(&PermissionOptions::default()),
+        get_keys(&RunnerOptions::default()),
+        get_keys(&ServeOptions::default()),
+        get_keys(&SharedOptions::default()),
+        get_keys(&SpectrogramOptions::default()),
+        get_keys(&TargetOptions::default()),
+        get_keys(&TorrentOptions::default()),
+        get_keys(&UploadOptions::default()),
+        get_keys(&VerifyOptions::default()),
+        get_keys(&WatchOptions::default()),
+    ];
+
+    // Act
+    let missing: Vec<String> = options
+        .concat()
+        .into_iter()
+        .filter(|key| NotSet(key.clone()).get_example().is_none())
+        .collect();
+
+    // Assert
+    assert!(
+        missing.is_empty(),
+        "Options without an example: {missing:?}"
+    );
+}
+
+fn get_keys<T: Serialize>(options: &T) -> Vec<String> {
+    let value = serde_json::to_value(options).expect("options should serialize");
+    value
+        .as_object()
+        .expect("options should serialize to an object")
+        .keys()
+        .cloned()
+        .collect()
+}
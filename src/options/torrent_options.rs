@@ -0,0 +1,224 @@
+use std::fmt::{Display, Formatter};
+
+use clap::Args;
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::*;
+use crate::options::{OptionRule, Options, OptionsProvider, SizeInvalid};
+use crate::torrent::{is_valid_piece_length, parse_size, PieceLength, TorrentVersion};
+
+/// Value of `torrent_piece_size` to choose the piece size from the content size.
+const AUTO: &str = "auto";
+
+/// Options for creating torrents in [`TranscodeCommand`]
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TorrentOptions {
+    /// Piece size of created torrents.
+    ///
+    /// Either `auto` to choose from the content size or a power of two of at least 16 KiB
+    /// such as `256KiB`, `1MiB` or `4MiB`.
+    ///
+    /// Default: `auto`
+    #[arg(long)]
+    pub torrent_piece_size: Option<String>,
+
+    /// Smallest piece size to choose with `torrent_piece_size: auto`.
+    ///
+    /// Default: `16KiB`
+    #[arg(long)]
+    pub torrent_min_piece_size: Option<String>,
+
+    /// Largest piece size to choose with `torrent_piece_size: auto`.
+    ///
+    /// Default: `16MiB`
+    #[arg(long)]
+    pub torrent_max_piece_size: Option<String>,
+
+    /// Most pieces a torrent should have with `torrent_piece_size: auto`.
+    ///
+    /// The piece size is doubled until the torrent is within the limit
+    /// or `torrent_max_piece_size` is reached.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub torrent_max_pieces: Option<u64>,
+
+    /// Version of the metadata of created torrents.
+    ///
+    /// `v2` and `hybrid` torrents include a merkle tree of each file. Only use them if the
+    /// indexer accepts them.
+    ///
+    /// Default: `v1`
+    #[arg(long, value_enum)]
+    pub torrent_version: Option<TorrentVersion>,
+}
+
+#[injectable]
+impl TorrentOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+
+    /// Get the rules to choose the piece length of a created torrent.
+    #[must_use]
+    pub fn get_piece_length(&self) -> PieceLength {
+        let default = PieceLength::default();
+        PieceLength {
+            fixed: self
+                .torrent_piece_size
+                .as_deref()
+                .filter(|x| !x.eq_ignore_ascii_case(AUTO))
+                .and_then(parse_size),
+            min: self
+                .torrent_min_piece_size
+                .as_deref()
+                .and_then(parse_size)
+                .unwrap_or(default.min),
+            max: self
+                .torrent_max_piece_size
+                .as_deref()
+                .and_then(parse_size)
+                .unwrap_or(default.max),
+            max_count: self.torrent_max_pieces,
+        }
+    }
+
+    /// Log a warning if the indexer is known to reject the torrent version.
+    pub fn warn_unsupported_version(&self, indexer: &str) {
+        let version = self.torrent_version.unwrap_or_default();
+        if !version.is_supported_by(indexer) {
+            let version = format!("{version:?}").to_lowercase();
+            warn!(
+                "{} {version} torrents are not supported by {indexer} so uploads may be rejected",
+                "Warning".bold()
+            );
+        }
+    }
+}
+
+impl Options for TorrentOptions {
+    fn get_name() -> String {
+        "Torrent Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.torrent_piece_size.is_none() {
+            self.torrent_piece_size
+                .clone_from(&alternative.torrent_piece_size);
+        }
+        if self.torrent_min_piece_size.is_none() {
+            self.torrent_min_piece_size
+                .clone_from(&alternative.torrent_min_piece_size);
+        }
+        if self.torrent_max_piece_size.is_none() {
+            self.torrent_max_piece_size
+                .clone_from(&alternative.torrent_max_piece_size);
+        }
+        if self.torrent_max_pieces.is_none() {
+            self.torrent_max_pieces = alternative.torrent_max_pieces;
+        }
+        if self.torrent_version.is_none() {
+            self.torrent_version = alternative.torrent_version;
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.torrent_piece_size.is_none() {
+            self.torrent_piece_size = Some(AUTO.to_owned());
+        }
+        if self.torrent_min_piece_size.is_none() {
+            self.torrent_min_piece_size = Some("16KiB".to_owned());
+        }
+        if self.torrent_max_piece_size.is_none() {
+            self.torrent_max_piece_size = Some("16MiB".to_owned());
+        }
+        if self.torrent_version.is_none() {
+            self.torrent_version = Some(TorrentVersion::V1);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(size) = &self.torrent_piece_size {
+            if !size.eq_ignore_ascii_case(AUTO) {
+                check_piece_size(&mut errors, "torrent_piece_size", size);
+            }
+        }
+        if let Some(size) = &self.torrent_min_piece_size {
+            check_piece_size(&mut errors, "torrent_min_piece_size", size);
+        }
+        if let Some(size) = &self.torrent_max_piece_size {
+            check_piece_size(&mut errors, "torrent_max_piece_size", size);
+        }
+        if errors.is_empty() {
+            let rules = self.get_piece_length();
+            if rules.min > rules.max {
+                errors.push(SizeInvalid(
+                    "torrent_max_piece_size".to_owned(),
+                    self.torrent_max_piece_size.clone().unwrap_or_default(),
+                    "Must not be smaller than torrent_min_piece_size".to_owned(),
+                ));
+            }
+        }
+        if self.torrent_max_pieces == Some(0) {
+            errors.push(SizeInvalid(
+                "torrent_max_pieces".to_owned(),
+                "0".to_owned(),
+                "Must be at least 1".to_owned(),
+            ));
+        }
+        errors
+    }
+
+    fn from_args() -> Option<Self> {
+        let Some(
+            Batch { torrent, .. }
+            | Transcode { torrent, .. }
+            | Watch { torrent, .. }
+            | Serve { torrent, .. },
+        ) = ArgumentsParser::get()
+        else {
+            return None;
+        };
+        Some(torrent)
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+fn check_piece_size(errors: &mut Vec<OptionRule>, key: &str, value: &str) {
+    let details = match parse_size(value) {
+        None => "Expected a size such as 256KiB or 1MiB",
+        Some(size) if !is_valid_piece_length(size) => "Must be a power of two of at least 16 KiB",
+        Some(_) => return,
+    };
+    errors.push(SizeInvalid(
+        key.to_owned(),
+        value.to_owned(),
+        details.to_owned(),
+    ));
+}
+
+impl Display for TorrentOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
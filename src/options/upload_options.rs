@@ -7,7 +7,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use crate::options::{Options, OptionsProvider};
+use crate::naming::NameSubstitution;
+use crate::options::{
+    IsEmpty, OptionRule, Options, OptionsProvider, SizeInvalid, SubstitutionInvalid, TemplateInvalid,
+};
+use crate::torrent::parse_size;
+use crate::upload::{AccountGuard, LineageTemplate, RequestMatch, DEFAULT_LINEAGE_TEMPLATE};
 
 /// Options for including additional files during [`TranscodeCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -35,6 +40,134 @@ pub struct UploadOptions {
     /// Default: `false`
     #[arg(long, default_value = None, action = ArgAction::SetTrue)]
     pub dry_run: Option<bool>,
+
+    /// Should the personal `v1`, `v2` and `256` targets be uploaded?
+    ///
+    /// By default they are transcoded but skipped by upload.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub upload_extra_targets: Option<bool>,
+
+    /// Should open requests matching an upload be filled?
+    ///
+    /// After each upload the open requests are searched for the album and the first
+    /// request matching the artist, title, format, bitrate and media is filled.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub fill_requests: Option<bool>,
+
+    /// How strictly must a request match an upload to be filled?
+    ///
+    /// `strict` also requires the year and, if the request has one,
+    /// the catalogue number to match. `loose` ignores them.
+    ///
+    /// Default: `strict`
+    #[arg(long, value_enum)]
+    pub fill_request_match: Option<RequestMatch>,
+
+    /// Should the ratio, buffer and warnings of the account be checked before uploading?
+    ///
+    /// `warn` logs a warning if the account is at risk and `refuse` skips the upload.
+    /// The account on each indexer of `trackers` is also checked before cross-posting.
+    ///
+    /// Default: `off`
+    #[arg(long, value_enum)]
+    pub account_guard: Option<AccountGuard>,
+
+    /// Minimum ratio of the account checked by `account_guard`.
+    ///
+    /// Default: The required ratio of the account
+    #[arg(long)]
+    pub min_ratio: Option<f64>,
+
+    /// Minimum buffer of the account checked by `account_guard` such as `10GiB`.
+    ///
+    /// The buffer is how much can be downloaded before the ratio falls below the
+    /// required ratio.
+    ///
+    /// Default: Not set
+    #[arg(long)]
+    pub min_buffer: Option<String>,
+
+    /// Year of the edition to upload, replacing the year of the source.
+    ///
+    /// Required to upload a source of an unconfirmed edition. As it applies to every
+    /// upload it's intended for a single upload from the command line.
+    ///
+    /// Default: The remaster year of the source, or the year of the group
+    #[arg(long)]
+    pub edition_year: Option<u16>,
+
+    /// Title of the edition to upload, replacing the remaster title of the source.
+    ///
+    /// Default: The remaster title of the source
+    #[arg(long)]
+    pub edition_title: Option<String>,
+
+    /// Record label of the edition to upload, replacing the record label of the source.
+    ///
+    /// Default: The remaster record label of the source
+    #[arg(long)]
+    pub edition_record_label: Option<String>,
+
+    /// Catalogue number of the edition to upload, replacing the catalogue number of the
+    /// source.
+    ///
+    /// Default: The remaster catalogue number of the source
+    #[arg(long)]
+    pub edition_catalogue_number: Option<String>,
+
+    /// Regex substitutions applied in order to the title, record label and catalogue
+    /// number of the edition to upload.
+    ///
+    /// Each substitution is `PATTERN=>REPLACEMENT` and the replacement can refer to capture
+    /// groups with `$1`.
+    ///
+    /// Examples: `^Self-Released$=>Not On Label`, `\s{2,}=> `
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub edition_substitution: Option<Vec<String>>,
+
+    /// Should the lineage of the source be quoted in the description of each upload?
+    ///
+    /// The lines of the source description about its lineage or rip, such as the ripping
+    /// software or the transfer chain of a vinyl, are quoted so provenance isn't lost
+    /// across format editions.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub lineage: Option<bool>,
+
+    /// Template of the lineage quoted in the description of each upload.
+    ///
+    /// Placeholders: `{lineage}`, `{description}`, `{uploader}`, `{url}`
+    ///
+    /// `{lineage}` is only the lines about the lineage or rip while `{description}` is
+    /// the whole source description.
+    ///
+    /// Default: `[b]Lineage[/b] of the [url={url}]source[/url] uploaded by {uploader}\n[pre]{lineage}[/pre]`
+    #[arg(long)]
+    pub lineage_template: Option<String>,
+
+    /// Profiles of other indexers to also upload each transcode to.
+    ///
+    /// Each profile is read from the `profiles` section of the config file and must set
+    /// the `indexer`, `indexer_url`, `announce_url` and `api_key` of the indexer, or store
+    /// the API key with `caesura config set-key`.
+    ///
+    /// The source must also be on the other indexer. A `.torrent` file with the source and
+    /// tracker of the other indexer is created for each transcode that isn't already in
+    /// its group there. Profiles of the indexer the source is from are skipped as it's
+    /// always uploaded to.
+    ///
+    /// Examples: `red,ops`
+    ///
+    /// Default: `null`
+    #[arg(long, value_delimiter = ',')]
+    pub trackers: Option<Vec<String>>,
 }
 
 #[injectable]
@@ -42,6 +175,22 @@ impl UploadOptions {
     fn new(provider: Ref<OptionsProvider>) -> Self {
         provider.get()
     }
+
+    /// Get the valid edition substitutions.
+    #[must_use]
+    pub fn get_edition_substitutions(&self) -> Vec<NameSubstitution> {
+        self.edition_substitution
+            .iter()
+            .flatten()
+            .filter_map(|substitution| NameSubstitution::parse(substitution).ok())
+            .collect()
+    }
+
+    /// Get the minimum buffer in bytes.
+    #[must_use]
+    pub fn get_min_buffer(&self) -> Option<u64> {
+        self.min_buffer.as_deref().and_then(parse_size)
+    }
 }
 
 impl Options for UploadOptions {
@@ -60,6 +209,52 @@ impl Options for UploadOptions {
         if self.dry_run.is_none() {
             self.dry_run = alternative.dry_run;
         }
+        if self.upload_extra_targets.is_none() {
+            self.upload_extra_targets = alternative.upload_extra_targets;
+        }
+        if self.fill_requests.is_none() {
+            self.fill_requests = alternative.fill_requests;
+        }
+        if self.fill_request_match.is_none() {
+            self.fill_request_match = alternative.fill_request_match;
+        }
+        if self.account_guard.is_none() {
+            self.account_guard = alternative.account_guard;
+        }
+        if self.min_ratio.is_none() {
+            self.min_ratio = alternative.min_ratio;
+        }
+        if self.min_buffer.is_none() {
+            self.min_buffer.clone_from(&alternative.min_buffer);
+        }
+        if self.edition_year.is_none() {
+            self.edition_year = alternative.edition_year;
+        }
+        if self.edition_title.is_none() {
+            self.edition_title.clone_from(&alternative.edition_title);
+        }
+        if self.edition_record_label.is_none() {
+            self.edition_record_label
+                .clone_from(&alternative.edition_record_label);
+        }
+        if self.edition_catalogue_number.is_none() {
+            self.edition_catalogue_number
+                .clone_from(&alternative.edition_catalogue_number);
+        }
+        if self.edition_substitution.is_none() {
+            self.edition_substitution
+                .clone_from(&alternative.edition_substitution);
+        }
+        if self.lineage.is_none() {
+            self.lineage = alternative.lineage;
+        }
+        if self.lineage_template.is_none() {
+            self.lineage_template
+                .clone_from(&alternative.lineage_template);
+        }
+        if self.trackers.is_none() {
+            self.trackers.clone_from(&alternative.trackers);
+        }
     }
 
     fn apply_defaults(&mut self) {
@@ -69,16 +264,69 @@ impl Options for UploadOptions {
         if self.dry_run.is_none() {
             self.dry_run = Some(false);
         }
+        if self.upload_extra_targets.is_none() {
+            self.upload_extra_targets = Some(false);
+        }
+        if self.fill_requests.is_none() {
+            self.fill_requests = Some(false);
+        }
+        if self.fill_request_match.is_none() {
+            self.fill_request_match = Some(RequestMatch::Strict);
+        }
+        if self.account_guard.is_none() {
+            self.account_guard = Some(AccountGuard::Off);
+        }
+        if self.lineage.is_none() {
+            self.lineage = Some(false);
+        }
+        if self.lineage_template.is_none() {
+            self.lineage_template = Some(DEFAULT_LINEAGE_TEMPLATE.to_owned());
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
-        true
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(min_buffer) = &self.min_buffer {
+            if parse_size(min_buffer).is_none() {
+                errors.push(SizeInvalid(
+                    "min_buffer".to_owned(),
+                    min_buffer.clone(),
+                    "Expected a size such as 512MiB or 10GiB".to_owned(),
+                ));
+            }
+        }
+        for substitution in self.edition_substitution.iter().flatten() {
+            if let Err(details) = NameSubstitution::parse(substitution) {
+                errors.push(SubstitutionInvalid(
+                    "edition_substitution".to_owned(),
+                    substitution.clone(),
+                    details,
+                ));
+            }
+        }
+        if let Some(lineage_template) = &self.lineage_template {
+            if let Err(details) = LineageTemplate::validate(lineage_template) {
+                errors.push(TemplateInvalid(
+                    "lineage_template".to_owned(),
+                    lineage_template.clone(),
+                    details,
+                ));
+            }
+        }
+        if self.trackers.as_ref().is_some_and(Vec::is_empty) {
+            errors.push(IsEmpty("trackers".to_owned()));
+        }
+        errors
     }
 
-    #[must_use]
     fn from_args() -> Option<Self> {
-        let Some(Upload { upload, .. } | Batch { upload, .. }) = ArgumentsParser::get() else {
+        let Some(
+            Upload { upload, .. }
+            | Batch { upload, .. }
+            | Watch { upload, .. }
+            | Serve { upload, .. },
+        ) = ArgumentsParser::get()
+        else {
             return None;
         };
         let mut options = upload;
@@ -88,6 +336,18 @@ impl Options for UploadOptions {
         if options.dry_run == Some(false) {
             options.dry_run = None;
         }
+        if options.upload_extra_targets == Some(false) {
+            options.upload_extra_targets = None;
+        }
+        if options.fill_requests == Some(false) {
+            options.fill_requests = None;
+        }
+        if options.lineage == Some(false) {
+            options.lineage = None;
+        }
+        if options.trackers == Some(Vec::new()) {
+            options.trackers = None;
+        }
         Some(options)
     }
 
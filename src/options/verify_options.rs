@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 use clap::{ArgAction, Args};
 use di::{injectable, Ref};
@@ -6,7 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::cli::ArgumentsParser;
 use crate::cli::CommandArguments::*;
-use crate::options::{Options, OptionsProvider};
+use crate::options::{DoesNotExist, OptionRule, Options, OptionsProvider};
+use crate::torrent::parse_size;
+use crate::verify::PERFECT_LOG_SCORE;
 
 /// Options for [`VerifyCommand`]
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
@@ -19,11 +22,106 @@ pub struct VerifyOptions {
     #[arg(long, default_value = None, action = ArgAction::SetTrue)]
     pub no_hash_check: Option<bool>,
 
+    /// Should the cached result of the tag, stream and hash checks be ignored?
+    ///
+    /// The checks are cached in the cache directory and reused until a file of the
+    /// source is added, removed or modified.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub no_verify_cache: Option<bool>,
+
     /// Should sources with specific tags be excluded?
     ///
     /// Default: None
     #[arg(long)]
     pub exclude_tags: Option<Vec<String>>,
+
+    /// Lowest score of an EAC or XLD rip log before a warning is logged.
+    ///
+    /// A low score indicates a bad rip which is likely to be trumped or reported.
+    ///
+    /// Default: `100`
+    #[arg(long)]
+    pub min_log_score: Option<i32>,
+
+    /// Most seconds a track may differ from its `MusicBrainz` length before a warning is logged.
+    ///
+    /// Tracks are only compared if `enrich_tags` is set and the matched release has the
+    /// same number of tracks. A large difference indicates a different edition or a
+    /// truncated file.
+    ///
+    /// Default: `2`
+    #[arg(long)]
+    pub max_duration_difference: Option<u32>,
+
+    /// Should each track be decoded to find silence?
+    ///
+    /// A warning is logged for a track that is entirely silent or has a silence of a
+    /// minute or more, which may be a hidden track or a pregap mistake.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub silence_check: Option<bool>,
+
+    /// Fewest seconds a track may last before a warning is logged.
+    ///
+    /// An implausibly short track can indicate a rip error. Set to `0` to disable.
+    ///
+    /// Default: `5`
+    #[arg(long)]
+    pub min_track_duration: Option<u64>,
+
+    /// Should spectrograms be generated for tracks that the checks find suspicious?
+    ///
+    /// Spectrograms are generated for every track of a lossy master or lossy web source,
+    /// a 24-bit source padded from 16-bit, a hi-res source upsampled from a lower sample
+    /// rate, and for each track with a stream issue or a silence or duration anomaly. The spectrogram directory is recorded in the history.
+    ///
+    /// Default: `false`
+    #[arg(long, default_value = None, action = ArgAction::SetTrue)]
+    pub auto_spectrogram: Option<bool>,
+
+    /// Largest source the batch command will process.
+    ///
+    /// Larger sources such as box sets are skipped and reported at the end of the batch
+    /// so they can be transcoded manually. Other commands are unaffected.
+    ///
+    /// Examples: `5GiB`, `50GiB`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub max_source_size: Option<String>,
+
+    /// Most tracks of a source the batch command will process.
+    ///
+    /// Sources with more tracks are skipped and reported at the end of the batch so they
+    /// can be transcoded manually. Other commands are unaffected.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub max_source_tracks: Option<usize>,
+
+    /// Path of the `.torrent` file of the source to use for the hash check.
+    ///
+    /// The file is only used if its comment has the torrent id of the source, otherwise
+    /// the `.torrent` file is downloaded from the API.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub torrent_file: Option<PathBuf>,
+
+    /// Directories to search for the `.torrent` file of the source before downloading it.
+    ///
+    /// Such as a watch directory or the session directory of a torrent client. Every
+    /// `.torrent` file of each directory is read until one has the torrent id of the
+    /// source in its comment.
+    ///
+    /// Examples: `./torrents`, `/config/qBittorrent/BT_backup`
+    ///
+    /// Default: `[]`
+    #[arg(long)]
+    pub torrent_dirs: Option<Vec<PathBuf>>,
 }
 
 #[injectable]
@@ -31,6 +129,11 @@ impl VerifyOptions {
     fn new(provider: Ref<OptionsProvider>) -> Self {
         provider.get()
     }
+
+    #[must_use]
+    pub fn get_max_source_size(&self) -> Option<u64> {
+        self.max_source_size.as_deref().and_then(parse_size)
+    }
 }
 
 impl Options for VerifyOptions {
@@ -42,34 +145,125 @@ impl Options for VerifyOptions {
         if self.no_hash_check.is_none() {
             self.no_hash_check = alternative.no_hash_check;
         }
+        if self.no_verify_cache.is_none() {
+            self.no_verify_cache = alternative.no_verify_cache;
+        }
         if self.exclude_tags.is_none() {
             self.exclude_tags.clone_from(&alternative.exclude_tags);
         }
+        if self.min_log_score.is_none() {
+            self.min_log_score = alternative.min_log_score;
+        }
+        if self.max_duration_difference.is_none() {
+            self.max_duration_difference = alternative.max_duration_difference;
+        }
+        if self.silence_check.is_none() {
+            self.silence_check = alternative.silence_check;
+        }
+        if self.min_track_duration.is_none() {
+            self.min_track_duration = alternative.min_track_duration;
+        }
+        if self.auto_spectrogram.is_none() {
+            self.auto_spectrogram = alternative.auto_spectrogram;
+        }
+        if self.max_source_size.is_none() {
+            self.max_source_size
+                .clone_from(&alternative.max_source_size);
+        }
+        if self.max_source_tracks.is_none() {
+            self.max_source_tracks = alternative.max_source_tracks;
+        }
+        if self.torrent_file.is_none() {
+            self.torrent_file.clone_from(&alternative.torrent_file);
+        }
+        if self.torrent_dirs.is_none() {
+            self.torrent_dirs.clone_from(&alternative.torrent_dirs);
+        }
     }
 
     fn apply_defaults(&mut self) {
         if self.no_hash_check.is_none() {
             self.no_hash_check = Some(false);
         }
+        if self.no_verify_cache.is_none() {
+            self.no_verify_cache = Some(false);
+        }
         if self.exclude_tags.is_none() {
             self.exclude_tags = Some(Vec::new());
         }
+        if self.min_log_score.is_none() {
+            self.min_log_score = Some(PERFECT_LOG_SCORE);
+        }
+        if self.max_duration_difference.is_none() {
+            self.max_duration_difference = Some(2);
+        }
+        if self.silence_check.is_none() {
+            self.silence_check = Some(false);
+        }
+        if self.min_track_duration.is_none() {
+            self.min_track_duration = Some(5);
+        }
+        if self.auto_spectrogram.is_none() {
+            self.auto_spectrogram = Some(false);
+        }
+        if self.torrent_dirs.is_none() {
+            self.torrent_dirs = Some(Vec::new());
+        }
     }
 
-    #[must_use]
-    fn validate(&self) -> bool {
-        true
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(max_source_size) = &self.max_source_size {
+            if parse_size(max_source_size).is_none() {
+                errors.push(OptionRule::SizeInvalid(
+                    "max_source_size".to_owned(),
+                    max_source_size.clone(),
+                    "Expected a size such as 512MiB or 10GiB".to_owned(),
+                ));
+            }
+        }
+        if let Some(path) = &self.torrent_file {
+            if !path.is_file() {
+                errors.push(DoesNotExist(
+                    "torrent_file".to_owned(),
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+        }
+        for path in self.torrent_dirs.iter().flatten() {
+            if !path.is_dir() {
+                errors.push(DoesNotExist(
+                    "torrent_dirs".to_owned(),
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+        }
+        errors
     }
 
-    #[must_use]
     fn from_args() -> Option<Self> {
-        let Some(Batch { verify, .. } | Verify { verify, .. }) = ArgumentsParser::get() else {
+        let Some(
+            Batch { verify, .. }
+            | Verify { verify, .. }
+            | Watch { verify, .. }
+            | Serve { verify, .. },
+        ) = ArgumentsParser::get()
+        else {
             return None;
         };
         let mut options = verify;
         if options.no_hash_check == Some(false) {
             options.no_hash_check = None;
         }
+        if options.no_verify_cache == Some(false) {
+            options.no_verify_cache = None;
+        }
+        if options.silence_check == Some(false) {
+            options.silence_check = None;
+        }
+        if options.auto_spectrogram == Some(false) {
+            options.auto_spectrogram = None;
+        }
         Some(options)
     }
 
@@ -0,0 +1,276 @@
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::Args;
+use croner::Cron;
+use di::{injectable, Ref};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ArgumentsParser;
+use crate::cli::CommandArguments::Watch;
+use crate::options::{
+    AddressInvalid, DoesNotExist, DurationInvalid, NotSet, OptionRule, Options, OptionsProvider,
+    ScheduleInvalid, UrlNotHttp,
+};
+use crate::watch::TorrentClientKind;
+
+/// Options for [`WatchCommand`]
+#[allow(clippy::struct_field_names)]
+#[derive(Args, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WatchOptions {
+    /// Directory to watch for new `.torrent` files.
+    ///
+    /// Not required if `client_type` is set.
+    ///
+    /// Examples: `./torrents`, `/path/to/torrents`
+    #[arg(value_name = "PATH")]
+    pub watch_path: Option<PathBuf>,
+
+    /// Duration to wait between each scan of the watch directory.
+    ///
+    /// The duration is a string that can be parsed such as `500ms`, `5m`, `1h30m15s`.
+    ///
+    /// Default: `1m`
+    #[arg(long)]
+    pub watch_interval: Option<String>,
+
+    /// Duration a file must be unchanged before it is processed.
+    ///
+    /// This prevents files that are still being written from being processed.
+    ///
+    /// The duration is a string that can be parsed such as `500ms`, `5m`, `1h30m15s`.
+    ///
+    /// Default: `30s`
+    #[arg(long)]
+    pub watch_debounce: Option<String>,
+
+    /// Cron schedule to trigger a batch pass over the queue.
+    ///
+    /// The batch pass is triggered even if no new files have been found.
+    ///
+    /// Uses the standard five part cron format in local time such as `0 3 * * *` for 03:00 every night.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub watch_schedule: Option<String>,
+
+    /// Address to serve Prometheus metrics from at `/metrics`.
+    ///
+    /// Examples: `127.0.0.1:9090`, `0.0.0.0:9090`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub metrics_address: Option<String>,
+
+    /// Torrent client to poll for completed torrents.
+    ///
+    /// Each scan also adds the torrents that have completed in `client_category` since
+    /// the last scan to the queue, so sources are processed as soon as they're snatched.
+    /// Torrents that aren't on the indexer are skipped.
+    ///
+    /// Default: `null`
+    #[arg(long, value_enum)]
+    pub client_type: Option<TorrentClientKind>,
+
+    /// URL of the torrent client API.
+    ///
+    /// Examples: `http://localhost:8080` for qBittorrent,
+    /// `http://localhost:9091/transmission/rpc` for Transmission
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub client_url: Option<String>,
+
+    /// Username to log in to the torrent client with.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub client_username: Option<String>,
+
+    /// Password to log in to the torrent client with.
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub client_password: Option<String>,
+
+    /// Category of qBittorrent or label of Transmission of the torrents to process.
+    ///
+    /// If not set then every completed torrent is checked.
+    ///
+    /// Examples: `music`, `red`
+    ///
+    /// Default: `null`
+    #[arg(long)]
+    pub client_category: Option<String>,
+}
+
+#[injectable]
+impl WatchOptions {
+    fn new(provider: Ref<OptionsProvider>) -> Self {
+        provider.get()
+    }
+
+    #[must_use]
+    pub fn get_watch_interval(&self) -> Option<Duration> {
+        let watch_interval = self.watch_interval.clone()?;
+        humantime::parse_duration(watch_interval.as_str()).ok()
+    }
+
+    #[must_use]
+    pub fn get_watch_debounce(&self) -> Option<Duration> {
+        let watch_debounce = self.watch_debounce.clone()?;
+        humantime::parse_duration(watch_debounce.as_str()).ok()
+    }
+
+    #[must_use]
+    pub fn get_watch_schedule(&self) -> Option<Cron> {
+        let watch_schedule = self.watch_schedule.clone()?;
+        Cron::from_str(watch_schedule.as_str()).ok()
+    }
+
+    #[must_use]
+    pub fn get_metrics_address(&self) -> Option<SocketAddr> {
+        let metrics_address = self.metrics_address.clone()?;
+        SocketAddr::from_str(metrics_address.as_str()).ok()
+    }
+}
+
+impl Options for WatchOptions {
+    fn get_name() -> String {
+        "Watch Options".to_owned()
+    }
+
+    fn merge(&mut self, alternative: &Self) {
+        if self.watch_path.is_none() {
+            self.watch_path.clone_from(&alternative.watch_path);
+        }
+        if self.watch_interval.is_none() {
+            self.watch_interval.clone_from(&alternative.watch_interval);
+        }
+        if self.watch_debounce.is_none() {
+            self.watch_debounce.clone_from(&alternative.watch_debounce);
+        }
+        if self.watch_schedule.is_none() {
+            self.watch_schedule.clone_from(&alternative.watch_schedule);
+        }
+        if self.metrics_address.is_none() {
+            self.metrics_address
+                .clone_from(&alternative.metrics_address);
+        }
+        if self.client_type.is_none() {
+            self.client_type = alternative.client_type;
+        }
+        if self.client_url.is_none() {
+            self.client_url.clone_from(&alternative.client_url);
+        }
+        if self.client_username.is_none() {
+            self.client_username
+                .clone_from(&alternative.client_username);
+        }
+        if self.client_password.is_none() {
+            self.client_password
+                .clone_from(&alternative.client_password);
+        }
+        if self.client_category.is_none() {
+            self.client_category
+                .clone_from(&alternative.client_category);
+        }
+    }
+
+    fn apply_defaults(&mut self) {
+        if self.watch_interval.is_none() {
+            self.watch_interval = Some("1m".to_owned());
+        }
+        if self.watch_debounce.is_none() {
+            self.watch_debounce = Some("30s".to_owned());
+        }
+    }
+
+    fn get_errors(&self) -> Vec<OptionRule> {
+        let mut errors: Vec<OptionRule> = Vec::new();
+        if let Some(path) = &self.watch_path {
+            if !path.is_dir() {
+                errors.push(DoesNotExist(
+                    "watch_path".to_owned(),
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+        } else if self.client_type.is_none() {
+            errors.push(NotSet("watch_path".to_owned()));
+        }
+        if self.client_type.is_some() {
+            match &self.client_url {
+                None => errors.push(NotSet("client_url".to_owned())),
+                Some(url) if !url.starts_with("https://") && !url.starts_with("http://") => {
+                    errors.push(UrlNotHttp("client_url".to_owned(), url.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(watch_interval) = &self.watch_interval {
+            if self.get_watch_interval().is_none() {
+                errors.push(DurationInvalid(
+                    "watch_interval".to_owned(),
+                    watch_interval.clone(),
+                ));
+            }
+        }
+        if let Some(watch_debounce) = &self.watch_debounce {
+            if self.get_watch_debounce().is_none() {
+                errors.push(DurationInvalid(
+                    "watch_debounce".to_owned(),
+                    watch_debounce.clone(),
+                ));
+            }
+        }
+        if let Some(watch_schedule) = &self.watch_schedule {
+            if self.get_watch_schedule().is_none() {
+                errors.push(ScheduleInvalid(
+                    "watch_schedule".to_owned(),
+                    watch_schedule.clone(),
+                ));
+            }
+        }
+        if let Some(metrics_address) = &self.metrics_address {
+            if self.get_metrics_address().is_none() {
+                errors.push(AddressInvalid(
+                    "metrics_address".to_owned(),
+                    metrics_address.clone(),
+                ));
+            }
+        }
+        errors
+    }
+
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    fn from_args() -> Option<Self> {
+        match ArgumentsParser::get() {
+            Some(Watch { watch, .. }) => Some(watch),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::absolute_paths)]
+    fn from_json(json: &str) -> Result<Self, serde_json::error::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl Display for WatchOptions {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = if let Ok(yaml) = serde_yaml::to_string(self) {
+            yaml
+        } else {
+            format!("{self:?}")
+        };
+        output.fmt(formatter)
+    }
+}
@@ -0,0 +1,11 @@
+pub use progress_emitter::*;
+pub use progress_event::*;
+pub use progress_format::*;
+pub use progress_log::*;
+
+pub(crate) mod progress_emitter;
+pub(crate) mod progress_event;
+pub(crate) mod progress_format;
+pub(crate) mod progress_log;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use std::fs::OpenOptions;
+use std::io::{stdout, Write};
+use std::sync::Mutex;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+
+use crate::metrics::Metrics;
+use crate::options::SharedOptions;
+use crate::progress::*;
+use crate::summary::RunSummary;
+
+/// Write a [`ProgressEvent`] as a JSON object per line when `progress` is `ndjson`.
+///
+/// Events are written to `progress_path` if it's set, which can be a named pipe,
+/// otherwise to stdout.
+///
+/// Every event is also recorded by [`RunSummary`], [`Metrics`] and [`ProgressLog`].
+pub struct ProgressEmitter {
+    writer: Mutex<Option<Box<dyn Write + Send>>>,
+    summary: Ref<RunSummary>,
+    metrics: Ref<Metrics>,
+    log: Ref<ProgressLog>,
+}
+
+#[injectable]
+impl ProgressEmitter {
+    #[must_use]
+    pub fn new(
+        options: Ref<SharedOptions>,
+        summary: Ref<RunSummary>,
+        metrics: Ref<Metrics>,
+        log: Ref<ProgressLog>,
+    ) -> Self {
+        let writer = if options.progress == Some(ProgressFormat::Ndjson) {
+            get_writer(&options)
+        } else {
+            None
+        };
+        Self {
+            writer: Mutex::new(writer),
+            summary,
+            metrics,
+            log,
+        }
+    }
+
+    /// Write a [`ProgressEvent`] for the source currently being processed.
+    ///
+    /// Failures are logged but don't stop the command.
+    pub fn emit(&self, event: ProgressEvent) {
+        let line = ProgressLine::new(event);
+        self.summary.record(&line);
+        self.metrics.record(&line.event);
+        self.log.record(&line);
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+        let Some(output) = writer.as_mut() else {
+            return;
+        };
+        let line = match serde_json::to_string(&line) {
+            Ok(line) => line,
+            Err(error) => {
+                trace!("{} to serialize progress event: {error}", "Failed".bold());
+                return;
+            }
+        };
+        if let Err(error) = writeln!(output, "{line}").and_then(|()| output.flush()) {
+            warn!("{} to write progress event: {error}", "Failed".bold());
+            *writer = None;
+        }
+    }
+}
+
+fn get_writer(options: &SharedOptions) -> Option<Box<dyn Write + Send>> {
+    let Some(path) = &options.progress_path else {
+        return Some(Box::new(stdout()));
+    };
+    match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Some(Box::new(file)),
+        Err(error) => {
+            warn!(
+                "{} to open progress path {}: {error}",
+                "Failed".bold(),
+                path.display()
+            );
+            None
+        }
+    }
+}
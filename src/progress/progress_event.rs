@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::formats::TargetFormat;
+use crate::logging::get_log_source;
+
+/// A step in the lifecycle of a source.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// The source was retrieved from the API and matched to a directory.
+    SourceResolved { name: String, directory: PathBuf },
+    /// The source is suitable for transcoding.
+    SourceVerified,
+    /// The source is not suitable for transcoding.
+    ///
+    /// `rules` has the type of each [`SourceIssue`](crate::source::SourceIssue)
+    /// and `issues` has the matching description.
+    VerifyFailed {
+        rules: Vec<String>,
+        issues: Vec<String>,
+    },
+    /// Spectrograms of each track of the source were created.
+    SpectrogramsCreated { count: usize, path: PathBuf },
+    /// A track was transcoded to a target format.
+    TrackTranscoded { format: TargetFormat, path: PathBuf },
+    /// Every track of the source was transcoded to the target formats.
+    ///
+    /// `audio_seconds` is the duration of the source and `elapsed_seconds` is the
+    /// time taken to transcode it.
+    TranscodeDone {
+        formats: Vec<TargetFormat>,
+        audio_seconds: u32,
+        elapsed_seconds: f64,
+        directories: Vec<PathBuf>,
+    },
+    /// A torrent file was created for a target format.
+    TorrentCreated { format: TargetFormat, path: PathBuf },
+    /// A target format was uploaded.
+    UploadDone {
+        format: TargetFormat,
+        id: u32,
+        url: String,
+        /// Indexer the format was cross-posted to, or `None` for the indexer of the source.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        indexer: Option<String>,
+    },
+    /// A target format was not cross-posted to another indexer as it was not transcoded,
+    /// because the indexer of the source already has it.
+    CrossPostSkipped {
+        format: TargetFormat,
+        indexer: String,
+    },
+}
+
+/// A [`ProgressEvent`] as serialized by [`ProgressEmitter`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProgressLine {
+    /// UTC date and time in RFC 3339 format with millisecond precision.
+    pub timestamp: String,
+    /// Id of the source being processed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
+}
+
+impl ProgressLine {
+    /// Create a [`ProgressLine`] for the source currently being processed.
+    #[must_use]
+    pub fn new(event: ProgressEvent) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            source: get_log_source(),
+            event,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Format of the progress written while a command is executed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressFormat {
+    /// A progress bar in the console intended to be read by a person.
+    #[default]
+    Bar,
+    /// A [`ProgressEvent`] as a JSON object per line intended to be read by another program.
+    Ndjson,
+    /// An interactive interface in the console of the queue, the jobs of the current step
+    /// and the most recent logs, with keys to pause, skip or retry items of a batch.
+    Tui,
+}
@@ -0,0 +1,50 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use di::injectable;
+
+use crate::progress::ProgressLine;
+
+/// Most [`ProgressLine`] kept by [`ProgressLog`].
+const MAX_LINES: usize = 500;
+
+/// Keep the most recent [`ProgressLine`] so a long-running service can report them.
+pub struct ProgressLog {
+    lines: Mutex<VecDeque<ProgressLine>>,
+}
+
+impl Default for ProgressLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[injectable]
+impl ProgressLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Add a [`ProgressLine`], removing the oldest if there are more than [`MAX_LINES`].
+    pub fn record(&self, line: &ProgressLine) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() >= MAX_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line.clone());
+    }
+
+    /// Get the recorded lines, oldest first.
+    #[must_use]
+    pub fn get_lines(&self) -> Vec<ProgressLine> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,2 @@
+mod progress_emitter_tests;
+mod progress_log_tests;
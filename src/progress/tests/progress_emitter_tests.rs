@@ -0,0 +1,79 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use di::Ref;
+
+use crate::formats::TargetFormat;
+use crate::metrics::Metrics;
+use crate::options::SharedOptions;
+use crate::progress::*;
+use crate::summary::RunSummary;
+use crate::testing::TempDirectory;
+
+#[test]
+fn progress_emitter_writes_ndjson() {
+    // Arrange
+    let path = TempDirectory::create("caesura").join("progress.ndjson");
+    let options = Ref::new(SharedOptions {
+        progress: Some(ProgressFormat::Ndjson),
+        progress_path: Some(path.clone()),
+        ..SharedOptions::default()
+    });
+    let summary = Ref::new(RunSummary::new(options.clone()));
+    let emitter = ProgressEmitter::new(
+        options,
+        summary,
+        Ref::new(Metrics::new()),
+        Ref::new(ProgressLog::new()),
+    );
+    let events = vec![
+        ProgressEvent::VerifyFailed {
+            rules: vec!["scene".to_owned()],
+            issues: vec!["Scene releases are not supported".to_owned()],
+        },
+        ProgressEvent::TorrentCreated {
+            format: TargetFormat::_320,
+            path: PathBuf::from("/output/Album [CD 320].red.torrent"),
+        },
+    ];
+
+    // Act
+    for event in events.clone() {
+        emitter.emit(event);
+    }
+
+    // Assert
+    let output = read_to_string(&path).expect("should read");
+    let lines: Vec<ProgressLine> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("should deserialize"))
+        .collect();
+    let actual: Vec<ProgressEvent> = lines.into_iter().map(|line| line.event).collect();
+    assert_eq!(actual, events);
+    assert!(output.starts_with(r#"{"timestamp":"#));
+    assert!(output.contains(r#""event":"torrent_created","format":"320""#));
+}
+
+#[test]
+fn progress_emitter_bar_writes_nothing() {
+    // Arrange
+    let path = TempDirectory::create("caesura").join("progress.ndjson");
+    let options = Ref::new(SharedOptions {
+        progress: Some(ProgressFormat::Bar),
+        progress_path: Some(path.clone()),
+        ..SharedOptions::default()
+    });
+    let summary = Ref::new(RunSummary::new(options.clone()));
+    let emitter = ProgressEmitter::new(
+        options,
+        summary,
+        Ref::new(Metrics::new()),
+        Ref::new(ProgressLog::new()),
+    );
+
+    // Act
+    emitter.emit(ProgressEvent::SourceVerified);
+
+    // Assert
+    assert!(!path.exists());
+}
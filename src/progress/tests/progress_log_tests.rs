@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use crate::formats::TargetFormat;
+use crate::progress::*;
+
+#[test]
+fn progress_log_keeps_most_recent() {
+    // Arrange
+    let log = ProgressLog::new();
+
+    // Act
+    for _ in 0..600 {
+        log.record(&ProgressLine::new(ProgressEvent::SourceVerified));
+    }
+    log.record(&ProgressLine::new(ProgressEvent::TorrentCreated {
+        format: TargetFormat::V0,
+        path: PathBuf::from("a.torrent"),
+    }));
+
+    // Assert
+    let lines = log.get_lines();
+    assert_eq!(lines.len(), 500);
+    assert!(matches!(
+        lines.last().map(|line| &line.event),
+        Some(ProgressEvent::TorrentCreated { .. })
+    ));
+}
@@ -3,9 +3,9 @@ use std::fs::create_dir;
 use std::path::PathBuf;
 
 use crate::db::{Hash, Table};
-use crate::imdl::ImdlCommand;
 use crate::options::CacheOptions;
 use crate::queue::QueueItem;
+use crate::torrent::TorrentFile;
 use crate::transcode::TranscodeStatus;
 use crate::verify::VerifyStatus;
 use di::{inject, injectable, Ref};
@@ -126,10 +126,10 @@ impl Queue {
     /// Insert torrent files into the queue if they are not already present
     /// Returns the number of items added
     pub async fn insert_new_torrent_files(&mut self, paths: Vec<PathBuf>) -> Result<usize, Error> {
-        let stream = iter(paths.into_iter());
+        let stream = iter(paths);
         let items: BTreeMap<_, _> = stream
             .filter_map(|path| async {
-                let torrent = match ImdlCommand::show(&path).await {
+                let torrent = match TorrentFile::read(&path).await.map(|x| x.get_summary()) {
                     Ok(torrent) => Some(torrent),
                     Err(error) => {
                         error!("Failed to read torrent: {}\n{error}", path.display());
@@ -1,40 +1,68 @@
 use crate::db::Hash;
-use crate::errors::{error, io_error, yaml_error};
-use crate::fs::DirectoryReader;
-use crate::options::{CacheOptions, Options, QueueAddArgs, SharedOptions};
+use crate::errors::{error, io_error, path_error, yaml_error};
+use crate::fs::{DirectoryReader, PathManager};
+use crate::indexer::{
+    get_artist_id, get_collage_id, get_linked_torrent_ids, CollageGroup, IndexerClient,
+    RateLimiter, SessionClient,
+};
+use crate::metrics::Metrics;
+use crate::options::{CacheOptions, NotSet, OptionRule, Options, QueueAddArgs, SharedOptions};
 use crate::queue::{Queue, QueueItem, QueueStatus};
+use crate::torrent::TorrentFile;
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
-use log::{info, trace};
+use gazelle_api::GazelleClient;
+use log::{debug, info, trace, warn};
 use rogue_logging::Error;
 use std::collections::BTreeMap;
-use std::fs::File;
+use std::fs::{write, File};
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-/// Add a directory of `.torrent` files to the queue
+/// Add a directory of `.torrent` files, a queue file, the torrents of an artist or collage, or
+/// the transcode candidates to the queue
 #[injectable]
 pub struct QueueAddCommand {
     shared_options: Ref<SharedOptions>,
     cache_options: Ref<CacheOptions>,
     args: Ref<QueueAddArgs>,
     queue: RefMut<Queue>,
+    paths: Ref<PathManager>,
+    indexer: Ref<IndexerClient>,
+    api: RefMut<GazelleClient>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    session: Ref<SessionClient>,
 }
 
 impl QueueAddCommand {
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.shared_options.validate()
-            || !self.cache_options.validate()
-            || !self.args.validate()
-        {
-            return Ok(false);
+        let mut errors = [
+            self.shared_options.get_errors(),
+            self.cache_options.get_errors(),
+            self.args.get_errors(),
+        ]
+        .concat();
+        if self.args.queue_add_better == Some(true) && !self.session.is_enabled() {
+            errors.push(NotSet("session_cookie".to_owned()));
         }
-        let path = self
-            .args
-            .queue_add_path
-            .clone()
-            .expect("source should be set");
-        let status = self.execute(path).await?;
+        OptionRule::check(&errors)?;
+        let status = if let Some(artist) = &self.args.queue_add_artist {
+            let id = get_artist_id(artist).expect("artist id should be valid");
+            self.execute_artist(id).await?
+        } else if let Some(collage) = &self.args.queue_add_collage {
+            let id = get_collage_id(collage).expect("collage id should be valid");
+            self.execute_collage(id).await?
+        } else if self.args.queue_add_better == Some(true) {
+            self.execute_better().await?
+        } else {
+            let path = self
+                .args
+                .queue_add_path
+                .clone()
+                .expect("source should be set");
+            self.execute(path).await?
+        };
         info!("{} {} items to the queue", "Added".bold(), status.added);
         trace!(
             "{} {} items already in the queue",
@@ -114,4 +142,132 @@ impl QueueAddCommand {
             excluded: found - added,
         })
     }
+
+    async fn execute_artist(&mut self, id: u32) -> Result<QueueStatus, Error> {
+        let artist = self.indexer.get_artist(id).await?;
+        let ids = artist.get_flac_torrent_ids();
+        info!(
+            "{} {} FLAC torrents by {}",
+            "Found".bold(),
+            ids.len(),
+            artist.name
+        );
+        let candidates: Vec<Vec<u32>> = ids.into_iter().map(|id| vec![id]).collect();
+        self.execute_candidates(&candidates).await
+    }
+
+    async fn execute_collage(&mut self, id: u32) -> Result<QueueStatus, Error> {
+        let collage = self.indexer.get_collage(id).await?;
+        info!(
+            "{} {} groups in collage {}",
+            "Found".bold(),
+            collage.torrent_groups.len(),
+            collage.name
+        );
+        for group in &collage.torrent_groups {
+            let missing = group.get_missing_mp3_encodings();
+            if !missing.is_empty() {
+                info!("{} is missing {}", group.name, missing.join(", "));
+            }
+        }
+        let candidates: Vec<Vec<u32>> = collage
+            .torrent_groups
+            .iter()
+            .map(CollageGroup::get_flac_torrent_ids)
+            .filter(|ids| !ids.is_empty())
+            .collect();
+        self.execute_candidates(&candidates).await
+    }
+
+    /// Add the seeding torrents listed on the transcode candidates page.
+    ///
+    /// The page is not part of the API so it's requested with the [`SessionClient`].
+    async fn execute_better(&mut self) -> Result<QueueStatus, Error> {
+        let html = self
+            .session
+            .get_page(
+                "get transcode candidates",
+                "better.php",
+                &[("method", "transcode"), ("filter", "seeding")],
+            )
+            .await?;
+        let ids = get_linked_torrent_ids(&html);
+        info!(
+            "{} {} seeding transcode candidates",
+            "Found".bold(),
+            ids.len()
+        );
+        let candidates: Vec<Vec<u32>> = ids.into_iter().map(|id| vec![id]).collect();
+        self.execute_candidates(&candidates).await
+    }
+
+    /// Download the `.torrent` files of torrent ids and add them to the queue.
+    ///
+    /// Each item of `candidates` is a list of torrent ids in order of preference and only
+    /// the first that's in the content directory is added. If `queue_add_missing` is set
+    /// then the first that can be downloaded is added instead.
+    async fn execute_candidates(&mut self, candidates: &[Vec<u32>]) -> Result<QueueStatus, Error> {
+        let include_missing = self
+            .args
+            .queue_add_missing
+            .expect("queue_add_missing should be set");
+        let mut paths = Vec::new();
+        for ids in candidates {
+            for id in ids {
+                let path = match self.download_torrent(*id).await {
+                    Ok(path) => path,
+                    Err(e) => {
+                        warn!("{} to download torrent {id}: {e}", "Failed".bold());
+                        continue;
+                    }
+                };
+                if include_missing || self.is_in_content(&path).await {
+                    paths.push(path);
+                    break;
+                }
+                debug!(
+                    "{} torrent {id} as it's not in the content directory",
+                    "Excluding".bold()
+                );
+            }
+        }
+        let found = paths.len();
+        info!("{} {found} torrents to add", "Found".bold());
+        let mut queue = self.queue.write().expect("queue should be writeable");
+        let added = queue.insert_new_torrent_files(paths).await?;
+        Ok(QueueStatus {
+            success: true,
+            added,
+            excluded: candidates.len() - added,
+        })
+    }
+
+    /// Download the `.torrent` file of a torrent to the cache if it isn't already cached.
+    async fn download_torrent(&self, id: u32) -> Result<PathBuf, Error> {
+        let path = self.paths.get_cached_torrent_path(id);
+        if path.is_file() {
+            return Ok(path);
+        }
+        trace!("{} torrent file {id}", "Downloading".bold());
+        let mut api = self.api.write().expect("API should be available");
+        self.rate_limiter.wait().await;
+        let result = api.get_torrent_file_as_buffer(id).await;
+        self.metrics.record_api("get torrent file", &result);
+        write(&path, result?).map_err(|e| path_error(e, "write torrent file", &path))?;
+        Ok(path)
+    }
+
+    /// Check if the content of a `.torrent` file is in a content directory.
+    async fn is_in_content(&self, path: &Path) -> bool {
+        let Ok(torrent) = TorrentFile::read(path).await else {
+            return false;
+        };
+        let name = torrent.get_summary().name;
+        self.shared_options
+            .content
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .any(|dir| dir.join(&name).is_dir())
+    }
 }
@@ -1,7 +1,7 @@
 use crate::db::Hash;
-use crate::imdl::TorrentSummary;
 use crate::source::get_torrent_id_from_torrent_url;
 use crate::spectrogram::SpectrogramStatus;
+use crate::torrent::TorrentSummary;
 use crate::transcode::TranscodeStatus;
 use crate::upload::UploadStatus;
 use crate::verify::VerifyStatus;
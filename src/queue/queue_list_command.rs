@@ -1,5 +1,6 @@
-use crate::options::{BatchOptions, CacheOptions, Options, SharedOptions};
-use crate::queue::Queue;
+use crate::options::{BatchOptions, CacheOptions, OptionRule, Options, SharedOptions};
+use crate::queue::{Queue, QueueItem};
+use crate::summary::RunSummary;
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use log::{debug, error, info};
@@ -12,16 +13,18 @@ pub struct QueueListCommand {
     cache_options: Ref<CacheOptions>,
     batch_options: Ref<BatchOptions>,
     queue: RefMut<Queue>,
+    summary: Ref<RunSummary>,
 }
 
 impl QueueListCommand {
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.shared_options.validate()
-            || !self.cache_options.validate()
-            || !self.batch_options.validate()
-        {
-            return Ok(false);
-        }
+        let errors = [
+            self.shared_options.get_errors(),
+            self.cache_options.get_errors(),
+            self.batch_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
         let mut queue = self.queue.write().expect("Queue should be writeable");
         let transcode_enabled = self
             .batch_options
@@ -52,6 +55,9 @@ impl QueueListCommand {
                 indexer.to_uppercase()
             );
             info!("{} the `queue` command to add items", "Use".bold());
+            if self.summary.is_json() {
+                self.summary.set_data(&Vec::<QueueItem>::new());
+            }
             return Ok(true);
         }
         let found = items.len();
@@ -62,6 +68,7 @@ impl QueueListCommand {
         );
         let pad = found.to_string().len();
         let mut index = 1;
+        let mut listed = Vec::new();
         for hash in items {
             let Some(item) = queue.get(hash)? else {
                 error!("{} to retrieve {hash} from the queue", "Failed".bold());
@@ -74,6 +81,10 @@ impl QueueListCommand {
                 debug!("{id}");
             }
             index += 1;
+            listed.push(item);
+        }
+        if self.summary.is_json() {
+            self.summary.set_data(&listed);
         }
         Ok(true)
     }
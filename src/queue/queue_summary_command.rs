@@ -1,8 +1,9 @@
 use crate::errors::yaml_error;
-use crate::options::{CacheOptions, Options};
+use crate::options::{CacheOptions, OptionRule, Options};
 use crate::queue::Queue;
 use crate::queue::QueueSummary;
 use crate::spectrogram::SpectrogramStatus;
+use crate::summary::RunSummary;
 use crate::transcode::TranscodeStatus;
 use crate::upload::UploadStatus;
 use crate::verify::VerifyStatus;
@@ -14,14 +15,18 @@ use rogue_logging::Error;
 pub struct QueueSummaryCommand {
     cache_options: Ref<CacheOptions>,
     queue: RefMut<Queue>,
+    summary: Ref<RunSummary>,
 }
 
 impl QueueSummaryCommand {
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.cache_options.validate() {
-            return Ok(false);
-        }
+        let errors = self.cache_options.get_errors();
+        OptionRule::check(&errors)?;
         let summary = self.execute().await?;
+        if self.summary.is_json() {
+            self.summary.set_data(&summary);
+            return Ok(true);
+        }
         let yaml = serde_yaml::to_string(&summary)
             .map_err(|e| yaml_error(e, "serialize queue summary"))?;
         println!("{yaml}");
@@ -46,7 +51,7 @@ impl QueueSummaryCommand {
                 Some(VerifyStatus {
                     verified: false, ..
                 }) => summary.verify_verified_false += 1,
-            };
+            }
             match item.spectrogram {
                 None => summary.spectrogram_none += 1,
                 Some(SpectrogramStatus { success: true, .. }) => {
@@ -55,19 +60,19 @@ impl QueueSummaryCommand {
                 Some(SpectrogramStatus { success: false, .. }) => {
                     summary.spectrogram_success_false += 1;
                 }
-            };
+            }
             match item.transcode {
                 None => summary.transcode_none += 1,
                 Some(TranscodeStatus { success: true, .. }) => summary.transcode_success_true += 1,
                 Some(TranscodeStatus { success: false, .. }) => {
                     summary.transcode_success_false += 1;
                 }
-            };
+            }
             match item.upload {
                 None => summary.upload_none += 1,
                 Some(UploadStatus { success: true, .. }) => summary.upload_success_true += 1,
                 Some(UploadStatus { success: false, .. }) => summary.upload_success_false += 1,
-            };
+            }
         }
         Ok(summary)
     }
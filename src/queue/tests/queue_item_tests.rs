@@ -1,6 +1,6 @@
 use super::super::*;
 use crate::db::Hash;
-use crate::imdl::TorrentSummary;
+use crate::torrent::TorrentSummary;
 use std::path::PathBuf;
 
 #[test]
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct TimeStamp {
     datetime: DateTime<Utc>,
 }
@@ -16,22 +16,27 @@ impl TimeStamp {
         }
     }
 
-    #[allow(dead_code)]
     pub fn from_rfc3339(s: &str) -> Result<Self, chrono::ParseError> {
         let datetime = DateTime::parse_from_rfc3339(s)?.with_timezone(&Utc);
         Ok(TimeStamp { datetime })
     }
 }
 
+impl fmt::Display for TimeStamp {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self
+            .datetime
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        write!(formatter, "{s}")
+    }
+}
+
 impl Serialize for TimeStamp {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let s = self
-            .datetime
-            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-        serializer.serialize_str(&s)
+        serializer.serialize_str(&self.to_string())
     }
 }
 
@@ -42,7 +47,7 @@ impl<'de> Deserialize<'de> for TimeStamp {
     {
         struct TimeStampVisitor;
 
-        impl<'de> Visitor<'de> for TimeStampVisitor {
+        impl Visitor<'_> for TimeStampVisitor {
             type Value = TimeStamp;
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a valid ISO 8601 date string")
@@ -0,0 +1,147 @@
+use std::fmt::Write;
+use std::panic::PanicHookInfo;
+use std::process;
+
+use chrono::{SecondsFormat, Utc};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::built_info::{PKG_NAME, PKG_VERSION};
+use crate::report::scrub_secrets;
+
+/// Kind of failure of a [`CrashReport`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    /// An [`Error`] that was logged.
+    Error,
+    /// A panic.
+    Panic,
+}
+
+/// A report of an error or panic written by [`ErrorReporter`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CrashReport {
+    pub timestamp: String,
+    pub version: String,
+    pub command: String,
+    pub kind: ReportKind,
+    pub action: String,
+    /// Message of the error, including the output of an external command.
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// File and line of a panic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl CrashReport {
+    /// Create a [`CrashReport`] of an [`Error`].
+    #[must_use]
+    pub fn from_error(error: &Error, command: &str) -> Self {
+        Self {
+            timestamp: get_timestamp(),
+            version: PKG_VERSION.to_owned(),
+            command: command.to_owned(),
+            kind: ReportKind::Error,
+            action: error.action.clone(),
+            message: error.message.clone(),
+            domain: error.domain.clone(),
+            status_code: error.status_code,
+            location: None,
+        }
+    }
+
+    /// Create a [`CrashReport`] of a panic.
+    #[must_use]
+    pub fn from_panic(info: &PanicHookInfo, command: &str) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|x| (*x).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_owned());
+        Self {
+            timestamp: get_timestamp(),
+            version: PKG_VERSION.to_owned(),
+            command: command.to_owned(),
+            kind: ReportKind::Panic,
+            action: "run".to_owned(),
+            message,
+            domain: None,
+            status_code: None,
+            location: info
+                .location()
+                .map(|location| format!("{}:{}", location.file(), location.line())),
+        }
+    }
+
+    /// Remove secrets from the action and message.
+    #[must_use]
+    pub fn scrub(mut self, secrets: &[String]) -> Self {
+        self.action = scrub_secrets(&self.action, secrets);
+        self.message = scrub_secrets(&self.message, secrets);
+        self
+    }
+
+    /// Get the report as an event for the Sentry store endpoint.
+    ///
+    /// <https://develop.sentry.dev/sdk/data-model/event-payloads/>
+    #[must_use]
+    pub fn to_sentry_event(&self) -> Value {
+        let level = match self.kind {
+            ReportKind::Error => "error",
+            ReportKind::Panic => "fatal",
+        };
+        let mut tags = Map::new();
+        tags.insert("command".to_owned(), json!(self.command));
+        if let Some(domain) = &self.domain {
+            tags.insert("domain".to_owned(), json!(domain));
+        }
+        if let Some(status_code) = self.status_code {
+            tags.insert("status_code".to_owned(), json!(status_code.to_string()));
+        }
+        if let Some(location) = &self.location {
+            tags.insert("location".to_owned(), json!(location));
+        }
+        json!({
+            "event_id": self.get_event_id(),
+            "timestamp": self.timestamp,
+            "platform": "other",
+            "level": level,
+            "logger": PKG_NAME,
+            "release": format!("{PKG_NAME}@{PKG_VERSION}"),
+            "tags": tags,
+            "exception": {
+                "values": [{
+                    "type": format!("Failed to {}", self.action),
+                    "value": self.message,
+                }]
+            },
+        })
+    }
+
+    /// Get a unique id of the event as 32 hexadecimal characters.
+    fn get_event_id(&self) -> String {
+        let hash = Sha256::digest(format!(
+            "{}{}{}{}",
+            self.timestamp,
+            process::id(),
+            self.action,
+            self.message
+        ));
+        hash.iter().take(16).fold(String::new(), |mut output, byte| {
+            let _ = write!(output, "{byte:02x}");
+            output
+        })
+    }
+}
+
+fn get_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::Write;
+use std::panic::{set_hook, take_hook};
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::sync::{Mutex, OnceLock};
+use std::thread::spawn;
+use std::time::Duration;
+
+use reqwest::Client;
+use rogue_logging::Error;
+use tokio::runtime::Builder;
+
+use crate::built_info::{PKG_NAME, PKG_VERSION};
+use crate::options::SharedOptions;
+use crate::report::{CrashReport, SentryDsn};
+
+/// Most time to wait for a report to be sent to Sentry.
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The [`ErrorReporter`] of the process, if `report_file` or `report_dsn` is set.
+static REPORTER: OnceLock<ErrorReporter> = OnceLock::new();
+
+/// Report errors and panics to a file and to Sentry so rare failures of `watch` and
+/// `serve` can be diagnosed after the fact.
+///
+/// Each [`CrashReport`] is appended to `report_file` as a line of JSON and sent to the
+/// Sentry project of `report_dsn`. Secrets are removed before a report is written.
+///
+/// An error is only reported once per process.
+pub struct ErrorReporter {
+    command: String,
+    file: Option<PathBuf>,
+    dsn: Option<SentryDsn>,
+    secrets: Vec<String>,
+    reported: Mutex<BTreeSet<(String, String)>>,
+}
+
+impl ErrorReporter {
+    /// Start reporting errors and panics if `report_file` or `report_dsn` is set.
+    pub fn init(options: &SharedOptions, command: &str) {
+        let dsn = options
+            .report_dsn
+            .as_deref()
+            .and_then(|dsn| SentryDsn::parse(dsn).ok());
+        if options.report_file.is_none() && dsn.is_none() {
+            return;
+        }
+        let secrets = [
+            options.api_key.clone(),
+            options.session_cookie.clone(),
+            options.announce_url.clone(),
+            options.report_dsn.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let reporter = ErrorReporter {
+            command: command.to_owned(),
+            file: options.report_file.clone(),
+            dsn,
+            secrets,
+            reported: Mutex::new(BTreeSet::new()),
+        };
+        if REPORTER.set(reporter).is_err() {
+            return;
+        }
+        let default_hook = take_hook();
+        set_hook(Box::new(move |info| {
+            if let Some(reporter) = REPORTER.get() {
+                reporter.report(CrashReport::from_panic(info, &reporter.command));
+            }
+            default_hook(info);
+        }));
+    }
+
+    fn report(&self, report: CrashReport) {
+        let report = report.scrub(&self.secrets);
+        if let Ok(mut reported) = self.reported.lock() {
+            if !reported.insert((report.action.clone(), report.message.clone())) {
+                return;
+            }
+        }
+        if let Some(path) = &self.file {
+            if let Err(e) = write_report(path, &report) {
+                eprintln!("Failed to write crash report to {}: {e}", path.display());
+            }
+        }
+        if let Some(dsn) = &self.dsn {
+            if let Err(e) = send_report(dsn, &report) {
+                eprintln!("Failed to send crash report: {e}");
+            }
+        }
+    }
+}
+
+/// Report an [`Error`] if `report_file` or `report_dsn` is set.
+pub fn report_error(error: &Error) {
+    if let Some(reporter) = REPORTER.get() {
+        reporter.report(CrashReport::from_error(error, &reporter.command));
+    }
+}
+
+fn write_report(path: &PathBuf, report: &CrashReport) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let line = serde_json::to_string(report).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{line}").map_err(|e| e.to_string())
+}
+
+/// Send a report to Sentry, waiting at most [`SEND_TIMEOUT`].
+///
+/// The report is sent from a separate thread with its own runtime as errors and panics
+/// may be reported from within or outside of the async runtime.
+fn send_report(dsn: &SentryDsn, report: &CrashReport) -> Result<(), String> {
+    let url = dsn.store_url.clone();
+    let auth = format!(
+        "Sentry sentry_version=7, sentry_client={PKG_NAME}/{PKG_VERSION}, sentry_key={}",
+        dsn.key
+    );
+    let event = report.to_sentry_event();
+    let (sender, receiver) = channel();
+    spawn(move || {
+        let result = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())
+            .and_then(|runtime| {
+                runtime.block_on(async {
+                    let response = Client::new()
+                        .post(&url)
+                        .header("X-Sentry-Auth", auth)
+                        .json(&event)
+                        .timeout(SEND_TIMEOUT)
+                        .send()
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    let status = response.status();
+                    if status.is_success() {
+                        Ok(())
+                    } else {
+                        Err(format!("Sentry responded with {status}"))
+                    }
+                })
+            });
+        let _ = sender.send(result);
+    });
+    receiver
+        .recv_timeout(SEND_TIMEOUT)
+        .map_err(|_| "Timed out".to_owned())?
+}
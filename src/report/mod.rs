@@ -0,0 +1,11 @@
+pub use crash_report::*;
+pub use error_reporter::*;
+pub use scrub::*;
+pub use sentry_dsn::*;
+
+pub(crate) mod crash_report;
+pub(crate) mod error_reporter;
+pub(crate) mod scrub;
+pub(crate) mod sentry_dsn;
+#[cfg(test)]
+mod tests;
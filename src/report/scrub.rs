@@ -0,0 +1,41 @@
+use regex::Regex;
+
+/// Replacement of a secret in a [`CrashReport`].
+pub const REDACTED: &str = "[redacted]";
+
+/// Patterns of secrets that may appear in a [`CrashReport`] and their replacements.
+///
+/// - Passkeys and hashes of 32 hexadecimal characters
+/// - API keys such as `a1b2c3d4.e5f6a1b2c3d4e5f6`
+/// - Query parameters and headers such as `token=abc` or `Bearer abc`
+/// - The token of a Discord webhook
+const PATTERNS: [(&str, &str); 5] = [
+    (r"\b[0-9a-fA-F]{32}\b", REDACTED),
+    (r"\b[0-9a-fA-F]{8}\.[0-9a-fA-F]{16,}\b", REDACTED),
+    (
+        r"(?i)\b(token|key|passkey|authkey|secret|password)=[^&\s]+",
+        "$1=[redacted]",
+    ),
+    (r"(?i)\b(bearer|basic) \S+", "$1 [redacted]"),
+    (r"webhooks/(\d+)/[\w-]+", "webhooks/$1/[redacted]"),
+];
+
+/// Remove secrets from text before it's written to a [`CrashReport`].
+///
+/// Each of the known secrets, such as the API key and announce URL, is replaced and then
+/// anything that looks like a secret.
+#[must_use]
+pub fn scrub_secrets(text: &str, secrets: &[String]) -> String {
+    let mut output = text.to_owned();
+    for secret in secrets {
+        if !secret.is_empty() {
+            output = output.replace(secret.as_str(), REDACTED);
+        }
+    }
+    for (pattern, replacement) in PATTERNS {
+        if let Ok(regex) = Regex::new(pattern) {
+            output = regex.replace_all(&output, replacement).into_owned();
+        }
+    }
+    output
+}
@@ -0,0 +1,52 @@
+use reqwest::Url;
+
+/// A Sentry DSN such as `https://KEY@o123.ingest.sentry.io/456`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SentryDsn {
+    /// URL of the store endpoint of the project.
+    pub store_url: String,
+    /// Public key of the project.
+    pub key: String,
+}
+
+impl SentryDsn {
+    /// Parse a DSN.
+    ///
+    /// Returns a description of the problem if it can't be parsed.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let url = Url::parse(value).map_err(|e| e.to_string())?;
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return Err("Expected an https:// or http:// URL".to_owned());
+        }
+        let key = url.username();
+        if key.is_empty() {
+            return Err(
+                "Expected the public key before the host such as https://KEY@HOST/PROJECT"
+                    .to_owned(),
+            );
+        }
+        let host = url.host_str().ok_or_else(|| "Expected a host".to_owned())?;
+        let path = url.path().trim_matches('/');
+        let (prefix, project) = match path.rsplit_once('/') {
+            Some((prefix, project)) => (format!("/{prefix}"), project),
+            None => (String::new(), path),
+        };
+        if project.is_empty() {
+            return Err(
+                "Expected the project id after the host such as https://KEY@HOST/PROJECT"
+                    .to_owned(),
+            );
+        }
+        let port = url
+            .port()
+            .map(|port| format!(":{port}"))
+            .unwrap_or_default();
+        Ok(Self {
+            store_url: format!(
+                "{}://{host}{port}{prefix}/api/{project}/store/",
+                url.scheme()
+            ),
+            key: key.to_owned(),
+        })
+    }
+}
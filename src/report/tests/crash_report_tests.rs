@@ -0,0 +1,37 @@
+use rogue_logging::Error;
+
+use crate::report::{CrashReport, ReportKind};
+
+#[test]
+fn crash_report_from_error_scrubbed() {
+    // Arrange
+    let error = Error {
+        action: "transcode".to_owned(),
+        message: "sox exited with exit code 2\nFailed for letmein".to_owned(),
+        domain: Some("external command".to_owned()),
+        ..Error::default()
+    };
+
+    // Act
+    let report = CrashReport::from_error(&error, "batch").scrub(&["letmein".to_owned()]);
+    let event = report.to_sentry_event();
+
+    // Assert
+    assert_eq!(report.kind, ReportKind::Error);
+    assert_eq!(report.command, "batch".to_owned());
+    assert_eq!(
+        report.message,
+        "sox exited with exit code 2\nFailed for [redacted]".to_owned()
+    );
+    assert_eq!(
+        event.pointer("/tags/domain").and_then(|x| x.as_str()),
+        Some("external command")
+    );
+    assert_eq!(
+        event
+            .pointer("/event_id")
+            .and_then(|x| x.as_str())
+            .map(str::len),
+        Some(32)
+    );
+}
@@ -0,0 +1,3 @@
+mod crash_report_tests;
+mod scrub_tests;
+mod sentry_dsn_tests;
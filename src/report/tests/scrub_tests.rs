@@ -0,0 +1,36 @@
+use crate::report::scrub_secrets;
+
+#[test]
+fn scrub_secrets_known() {
+    // Arrange
+    let secrets = vec!["letmein".to_owned(), String::new()];
+
+    // Act
+    let output = scrub_secrets("Failed with letmein", &secrets);
+
+    // Assert
+    assert_eq!(output, "Failed with [redacted]");
+}
+
+#[test]
+fn scrub_secrets_patterns() {
+    // Arrange
+    let text = "GET https://flacsfor.me/0123456789abcdef0123456789abcdef/announce
+API key a1b2c3d4.e5f6a1b2c3d4e5f6a1b2
+https://example.com/?action=index&authkey=abc123&id=1
+Authorization: Bearer abc.def
+https://discord.com/api/webhooks/123/abc-DEF_ghi";
+
+    // Act
+    let output = scrub_secrets(text, &[]);
+
+    // Assert
+    assert_eq!(
+        output,
+        "GET https://flacsfor.me/[redacted]/announce
+API key [redacted]
+https://example.com/?action=index&authkey=[redacted]&id=1
+Authorization: Bearer [redacted]
+https://discord.com/api/webhooks/123/[redacted]"
+    );
+}
@@ -0,0 +1,41 @@
+use crate::report::SentryDsn;
+
+#[test]
+fn sentry_dsn_parse() {
+    // Act
+    let dsn = SentryDsn::parse("https://a1b2c3@o123.ingest.sentry.io/456");
+
+    // Assert
+    assert_eq!(
+        dsn,
+        Ok(SentryDsn {
+            store_url: "https://o123.ingest.sentry.io/api/456/store/".to_owned(),
+            key: "a1b2c3".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn sentry_dsn_parse_with_port_and_prefix() {
+    // Act
+    let dsn = SentryDsn::parse("http://a1b2c3@localhost:9000/sentry/7");
+
+    // Assert
+    assert_eq!(
+        dsn.map(|dsn| dsn.store_url),
+        Ok("http://localhost:9000/sentry/api/7/store/".to_owned())
+    );
+}
+
+#[test]
+fn sentry_dsn_parse_invalid() {
+    // Act
+    let without_key = SentryDsn::parse("https://o123.ingest.sentry.io/456");
+    let without_project = SentryDsn::parse("https://a1b2c3@o123.ingest.sentry.io/");
+    let not_url = SentryDsn::parse("not a url");
+
+    // Assert
+    assert!(without_key.is_err());
+    assert!(without_project.is_err());
+    assert!(not_url.is_err());
+}
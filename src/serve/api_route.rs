@@ -0,0 +1,58 @@
+/// An endpoint of the HTTP API served by [`ServeCommand`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApiRoute {
+    /// `GET /` Get the dashboard.
+    Dashboard,
+    /// `GET /api/status` Get the version and whether a batch is being processed.
+    Status,
+    /// `GET /api/queue` List the items in the queue.
+    ListQueue,
+    /// `POST /api/queue` Add a `.torrent` file or a directory of them to the queue.
+    AddToQueue,
+    /// `GET /api/queue/{hash}` Get an item in the queue by info hash.
+    GetQueueItem(String),
+    /// `GET /api/queue/{hash}/spectrograms` List the spectrogram images of an item.
+    ListSpectrograms(String),
+    /// `GET /api/queue/{hash}/spectrograms/{index}` Get a spectrogram image of an item by
+    /// its index in the list.
+    GetSpectrogram(String, String),
+    /// `GET /api/failures` List the most recent items that failed verification.
+    ListFailures,
+    /// `GET /api/progress` List the most recent progress events.
+    ListProgress,
+    /// `POST /api/batch` Start processing the queue.
+    StartBatch,
+    /// `GET /api/history` List the processed sources.
+    ListHistory,
+    /// `GET /api/history/{id}` Get the report of a processed source by torrent id.
+    GetHistoryItem(String),
+}
+
+impl ApiRoute {
+    /// Get the route of a request.
+    ///
+    /// Returns `None` if the request does not match a route.
+    #[must_use]
+    pub fn from_request(method: &str, segments: &[&str]) -> Option<Self> {
+        let route = match (method, segments) {
+            ("GET", []) => ApiRoute::Dashboard,
+            ("GET", ["api", "status"]) => ApiRoute::Status,
+            ("GET", ["api", "queue"]) => ApiRoute::ListQueue,
+            ("POST", ["api", "queue"]) => ApiRoute::AddToQueue,
+            ("GET", ["api", "queue", hash]) => ApiRoute::GetQueueItem((*hash).to_owned()),
+            ("GET", ["api", "queue", hash, "spectrograms"]) => {
+                ApiRoute::ListSpectrograms((*hash).to_owned())
+            }
+            ("GET", ["api", "queue", hash, "spectrograms", index]) => {
+                ApiRoute::GetSpectrogram((*hash).to_owned(), (*index).to_owned())
+            }
+            ("GET", ["api", "failures"]) => ApiRoute::ListFailures,
+            ("GET", ["api", "progress"]) => ApiRoute::ListProgress,
+            ("POST", ["api", "batch"]) => ApiRoute::StartBatch,
+            ("GET", ["api", "history"]) => ApiRoute::ListHistory,
+            ("GET", ["api", "history", id]) => ApiRoute::GetHistoryItem((*id).to_owned()),
+            _ => return None,
+        };
+        Some(route)
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Most bytes of the request line and headers that are accepted.
+const MAX_HEAD_LENGTH: usize = 16 * 1024;
+
+/// Most bytes of a request body that are accepted.
+const MAX_BODY_LENGTH: usize = 1024 * 1024;
+
+/// Separator between the headers and the body.
+const HEAD_END: &[u8] = b"\r\n\r\n";
+
+/// A minimal HTTP/1.1 request.
+#[derive(Clone, Debug, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Path without the query string.
+    pub path: String,
+    /// Headers by lowercase name.
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Read a request from a stream.
+    ///
+    /// Only requests with a `Content-Length` body are supported.
+    pub async fn read<R: AsyncRead + Unpin>(stream: &mut R) -> Result<Self, Error> {
+        let mut buffer = Vec::new();
+        let mut chunk = [0; 4096];
+        let head_length = loop {
+            if let Some(index) = find_head_end(&buffer) {
+                break index;
+            }
+            if buffer.len() > MAX_HEAD_LENGTH {
+                return Err(invalid("Request headers are too long"));
+            }
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(invalid("Request ended before the headers"));
+            }
+            buffer.extend_from_slice(chunk.get(..read).unwrap_or_default());
+        };
+        let head = String::from_utf8_lossy(buffer.get(..head_length).unwrap_or_default());
+        let mut request = Self::parse_head(&head).ok_or_else(|| invalid("Request is invalid"))?;
+        let length: usize = request
+            .get_header("content-length")
+            .map_or(Ok(0), str::parse)
+            .map_err(|_| invalid("Content-Length is invalid"))?;
+        if length > MAX_BODY_LENGTH {
+            return Err(invalid("Request body is too long"));
+        }
+        let mut body = buffer
+            .get(head_length + HEAD_END.len()..)
+            .unwrap_or_default()
+            .to_vec();
+        while body.len() < length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(invalid("Request ended before the body"));
+            }
+            body.extend_from_slice(chunk.get(..read).unwrap_or_default());
+        }
+        body.truncate(length);
+        request.body = body;
+        Ok(request)
+    }
+
+    /// Parse the request line and headers.
+    #[must_use]
+    pub(crate) fn parse_head(head: &str) -> Option<Self> {
+        let mut lines = head.lines();
+        let mut parts = lines.next()?.split_whitespace();
+        let method = parts.next()?.to_owned();
+        let target = parts.next()?;
+        if !parts.next()?.starts_with("HTTP/") {
+            return None;
+        }
+        let path = target.split('?').next().unwrap_or_default().to_owned();
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_owned()))
+            .collect();
+        Some(Self {
+            method,
+            path,
+            headers,
+            body: Vec::new(),
+        })
+    }
+
+    /// Get a header by case-insensitive name.
+    #[must_use]
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// Get the non-empty segments of the path.
+    #[must_use]
+    pub fn get_segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|x| !x.is_empty()).collect()
+    }
+
+    /// Does the request have the bearer token?
+    #[must_use]
+    pub fn is_authorized(&self, token: &str) -> bool {
+        self.get_header("authorization")
+            .and_then(|x| x.strip_prefix("Bearer "))
+            .is_some_and(|x| x == token)
+    }
+}
+
+fn find_head_end(buffer: &[u8]) -> Option<usize> {
+    buffer
+        .windows(HEAD_END.len())
+        .position(|window| window == HEAD_END)
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,108 @@
+use std::io::Error;
+
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Content type of a JSON body.
+const JSON: &str = "application/json";
+
+/// Content type of an HTML body.
+const HTML: &str = "text/html; charset=utf-8";
+
+/// Content type of a PNG body.
+const PNG: &str = "image/png";
+
+/// A minimal HTTP/1.1 response.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+}
+
+impl HttpResponse {
+    /// Create a response with a value serialized as JSON.
+    #[must_use]
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self {
+                status,
+                content_type: JSON,
+                body,
+            },
+            Err(error) => Self::error(500, &error.to_string()),
+        }
+    }
+
+    /// Create a response with an error message.
+    #[must_use]
+    pub fn error(status: u16, message: &str) -> Self {
+        let body =
+            serde_json::to_vec(&ErrorBody { error: message }).unwrap_or_else(|_| b"{}".to_vec());
+        Self {
+            status,
+            content_type: JSON,
+            body,
+        }
+    }
+
+    /// Create a response with an HTML page.
+    #[must_use]
+    pub fn html(page: &str) -> Self {
+        Self {
+            status: 200,
+            content_type: HTML,
+            body: page.as_bytes().to_vec(),
+        }
+    }
+
+    /// Create a response with a PNG image.
+    #[must_use]
+    pub fn png(image: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: PNG,
+            body: image,
+        }
+    }
+
+    /// Write the response to a stream then close it.
+    pub async fn write<W: AsyncWrite + Unpin>(&self, stream: &mut W) -> Result<(), Error> {
+        stream.write_all(self.to_bytes().as_slice()).await?;
+        stream.shutdown().await
+    }
+
+    /// Get the response as it's sent.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            get_reason(self.status),
+            self.content_type,
+            self.body.len(),
+        )
+        .into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+#[allow(clippy::match_same_arms)]
+fn get_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    }
+}
@@ -0,0 +1,11 @@
+pub use api_route::*;
+pub use http_request::*;
+pub use http_response::*;
+pub use serve_command::*;
+
+pub(crate) mod api_route;
+pub(crate) mod http_request;
+pub(crate) mod http_response;
+pub(crate) mod serve_command;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,408 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref, RefMut};
+use log::{debug, info, trace, warn};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use tokio::fs::read;
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::time::timeout;
+
+use crate::batch::BatchCommand;
+use crate::built_info::PKG_VERSION;
+use crate::db::Hash;
+use crate::errors::{io_error, log_error};
+use crate::fs::DirectoryReader;
+use crate::history::History;
+use crate::hosting::{ConfigReloader, Shutdown};
+use crate::metrics::{Metrics, MetricsServer};
+use crate::options::{CacheOptions, OptionRule, Options, ServeOptions, SharedOptions};
+use crate::progress::ProgressLog;
+use crate::queue::{Queue, QueueItem, TimeStamp};
+use crate::serve::{ApiRoute, HttpRequest, HttpResponse};
+
+/// Duration to wait for a client to send a request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most items returned by `GET /api/failures`.
+const MAX_FAILURES: usize = 50;
+
+/// Page served by `GET /`.
+const DASHBOARD: &str = include_str!("dashboard.html");
+
+/// Body of a `POST /api/queue` request.
+#[derive(Deserialize)]
+struct AddToQueueRequest {
+    /// Path to a `.torrent` file or a directory of them.
+    path: PathBuf,
+    /// Should a batch be started once the files are added?
+    #[serde(default = "default_true")]
+    process: bool,
+}
+
+#[derive(Serialize)]
+struct AddToQueueResponse {
+    added: usize,
+}
+
+/// An item of `GET /api/failures`.
+#[derive(Serialize)]
+struct FailureResponse {
+    name: String,
+    hash: String,
+    id: Option<u32>,
+    completed: TimeStamp,
+    /// Type of each [`SourceIssue`](crate::source::SourceIssue).
+    rules: Vec<String>,
+    /// Description of each [`SourceIssue`](crate::source::SourceIssue).
+    issues: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct StatusResponse {
+    version: &'static str,
+    indexer: String,
+    processing: bool,
+}
+
+/// Serve an HTTP API to add sources to the queue and batch process them.
+///
+/// Requests are handled one at a time while [`BatchCommand`] processes the queue in the
+/// background, so other services can drive caesura without running a command each time.
+///
+/// A batch is started when requested and runs until the queue has no unprocessed items.
+/// A request to start a batch while one is running starts another once it completes.
+///
+/// A dashboard of the queue, progress, failures and spectrograms is served from `/`.
+///
+/// Changes to the config file are applied to the next batch by [`ConfigReloader`].
+#[injectable]
+pub struct ServeCommand {
+    serve_options: Ref<ServeOptions>,
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    batch: RefMut<BatchCommand>,
+    reloader: Ref<ConfigReloader>,
+    history: Ref<History>,
+    progress_log: Ref<ProgressLog>,
+    metrics: Ref<Metrics>,
+    shutdown: Ref<Shutdown>,
+}
+
+impl ServeCommand {
+    /// Execute [`ServeCommand`] from the CLI.
+    ///
+    /// Runs until [`Shutdown`] is requested or an error occurs.
+    pub async fn execute_cli(&self) -> Result<bool, Error> {
+        let errors = [
+            self.serve_options.get_errors(),
+            self.batch
+                .read()
+                .expect("BatchCommand should be readable")
+                .get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
+        let address = self
+            .serve_options
+            .get_serve_address()
+            .expect("serve_address should be set");
+        if let Some(address) = self.serve_options.get_metrics_address() {
+            let server = MetricsServer::bind(address).await?;
+            tokio::spawn(server.serve(self.metrics.clone()));
+        }
+        let listener = TcpListener::bind(address)
+            .await
+            .map_err(|e| io_error(e, "bind serve address"))?;
+        if self.serve_options.serve_token.is_none() && !address.ip().is_loopback() {
+            warn!(
+                "{} serve_token is not set so anyone who can reach {address} can use the API",
+                "Warning".bold()
+            );
+        }
+        info!("{} API at http://{address}/api", "Serving".bold());
+        // The handler has its own queue as the batch holds a lock on the shared queue
+        let queue = Queue::from_options(self.cache_options.clone());
+        let trigger = Notify::new();
+        let processing = AtomicBool::new(false);
+        tokio::select! {
+            () = self.process(&trigger, &processing) => {},
+            () = self.accept(&listener, queue, &trigger, &processing) => {},
+        }
+        info!("{} serving API", "Stopped".bold());
+        Ok(true)
+    }
+
+    /// Execute [`BatchCommand`] each time it's triggered until [`Shutdown`] is requested.
+    ///
+    /// Errors are logged so the server continues.
+    async fn process(&self, trigger: &Notify, processing: &AtomicBool) {
+        let mut batch = self.batch.clone();
+        while !self.shutdown.is_requested() {
+            tokio::select! {
+                () = trigger.notified() => {},
+                () = self.shutdown.wait() => break,
+            }
+            info!("{} batch", "Starting".bold());
+            processing.store(true, Ordering::SeqCst);
+            if let Some(reloaded) = self.reloader.reload_batch() {
+                batch = reloaded;
+            }
+            let result = batch
+                .write()
+                .expect("BatchCommand should be writeable")
+                .execute()
+                .await;
+            processing.store(false, Ordering::SeqCst);
+            if let Err(error) = result {
+                log_error(&error);
+            }
+        }
+    }
+
+    /// Respond to requests until the process is stopped.
+    async fn accept(
+        &self,
+        listener: &TcpListener,
+        mut queue: Queue,
+        trigger: &Notify,
+        processing: &AtomicBool,
+    ) {
+        loop {
+            let mut stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    warn!("{} to accept API request: {error}", "Failed".bold());
+                    continue;
+                }
+            };
+            let response = match timeout(READ_TIMEOUT, HttpRequest::read(&mut stream)).await {
+                Ok(Ok(request)) => {
+                    debug!("{} {} {}", "Received".bold(), request.method, request.path);
+                    self.handle(&request, &mut queue, trigger, processing).await
+                }
+                Ok(Err(error)) => HttpResponse::error(400, &error.to_string()),
+                Err(_) => HttpResponse::error(400, "Timed out reading the request"),
+            };
+            if let Err(error) = response.write(&mut stream).await {
+                trace!("{} to respond to API request: {error}", "Failed".bold());
+            }
+        }
+    }
+
+    async fn handle(
+        &self,
+        request: &HttpRequest,
+        queue: &mut Queue,
+        trigger: &Notify,
+        processing: &AtomicBool,
+    ) -> HttpResponse {
+        let Some(route) = ApiRoute::from_request(&request.method, &request.get_segments()) else {
+            return HttpResponse::error(404, "Not found");
+        };
+        if route == ApiRoute::Dashboard {
+            // The page has no data so the token is only required by the API requests it makes
+            return HttpResponse::html(DASHBOARD);
+        }
+        if let Some(token) = &self.serve_options.serve_token {
+            if !request.is_authorized(token) {
+                return HttpResponse::error(401, "A valid bearer token is required");
+            }
+        }
+        let result = match route {
+            ApiRoute::Dashboard => Ok(HttpResponse::html(DASHBOARD)),
+            ApiRoute::Status => Ok(HttpResponse::json(
+                200,
+                &StatusResponse {
+                    version: PKG_VERSION,
+                    indexer: self.get_indexer(),
+                    processing: processing.load(Ordering::SeqCst),
+                },
+            )),
+            ApiRoute::ListQueue => self.list_queue(queue).await,
+            ApiRoute::AddToQueue => add_to_queue(request, queue, trigger).await,
+            ApiRoute::GetQueueItem(hash) => get_queue_item(&hash, queue),
+            ApiRoute::ListSpectrograms(hash) => Ok(list_spectrograms(&hash, queue)),
+            ApiRoute::GetSpectrogram(hash, index) => {
+                Ok(get_spectrogram(&hash, &index, queue).await)
+            }
+            ApiRoute::ListFailures => self.list_failures(queue).await,
+            ApiRoute::ListProgress => Ok(HttpResponse::json(200, &self.progress_log.get_lines())),
+            ApiRoute::StartBatch => {
+                trigger.notify_one();
+                Ok(HttpResponse::json(
+                    202,
+                    &StatusResponse {
+                        version: PKG_VERSION,
+                        indexer: self.get_indexer(),
+                        processing: true,
+                    },
+                ))
+            }
+            ApiRoute::ListHistory => self
+                .history
+                .get_all()
+                .map(|items| HttpResponse::json(200, &items)),
+            ApiRoute::GetHistoryItem(id) => self.get_history_item(&id),
+        };
+        result.unwrap_or_else(|error| HttpResponse::error(500, &error.to_string()))
+    }
+
+    async fn list_queue(&self, queue: &mut Queue) -> Result<HttpResponse, Error> {
+        let indexer = self.get_indexer();
+        let mut items: Vec<QueueItem> = queue
+            .get_all()
+            .await?
+            .into_values()
+            .filter(|item| item.indexer == indexer)
+            .collect();
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(HttpResponse::json(200, &items))
+    }
+
+    async fn list_failures(&self, queue: &mut Queue) -> Result<HttpResponse, Error> {
+        let indexer = self.get_indexer();
+        let mut failures: Vec<FailureResponse> = queue
+            .get_all()
+            .await?
+            .into_values()
+            .filter(|item| item.indexer == indexer)
+            .filter_map(|item| {
+                let status = item.verify.filter(|status| !status.verified)?;
+                let issues = status.issues.unwrap_or_default();
+                Some(FailureResponse {
+                    name: item.name,
+                    hash: item.hash.to_hex(),
+                    id: item.id,
+                    completed: status.completed,
+                    rules: issues.iter().map(|x| x.get_rule().to_owned()).collect(),
+                    issues: issues.iter().map(ToString::to_string).collect(),
+                })
+            })
+            .collect();
+        failures.sort_by(|a, b| b.completed.cmp(&a.completed));
+        failures.truncate(MAX_FAILURES);
+        Ok(HttpResponse::json(200, &failures))
+    }
+
+    fn get_history_item(&self, id: &str) -> Result<HttpResponse, Error> {
+        let Ok(id) = id.parse::<u32>() else {
+            return Ok(HttpResponse::error(400, "Expected a torrent id"));
+        };
+        Ok(match self.history.get(id)? {
+            Some(item) => HttpResponse::json(200, &item),
+            None => HttpResponse::error(404, "Source is not in the history"),
+        })
+    }
+
+    fn get_indexer(&self) -> String {
+        self.shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set")
+    }
+}
+
+async fn add_to_queue(
+    request: &HttpRequest,
+    queue: &mut Queue,
+    trigger: &Notify,
+) -> Result<HttpResponse, Error> {
+    let body: AddToQueueRequest = match serde_json::from_slice(&request.body) {
+        Ok(body) => body,
+        Err(error) => return Ok(HttpResponse::error(400, &error.to_string())),
+    };
+    let paths = if body.path.is_dir() {
+        DirectoryReader::new()
+            .with_extension("torrent")
+            .with_max_depth(0)
+            .read(&body.path)
+            .map_err(|e| io_error(e, "read torrent directory"))?
+    } else if body.path.is_file() {
+        vec![body.path]
+    } else {
+        return Ok(HttpResponse::error(400, "Path does not exist"));
+    };
+    let added = queue.insert_new_torrent_files(paths).await?;
+    info!("{} {added} items to the queue", "Added".bold());
+    if body.process {
+        trigger.notify_one();
+    }
+    Ok(HttpResponse::json(202, &AddToQueueResponse { added }))
+}
+
+fn get_queue_item(hash: &str, queue: &Queue) -> Result<HttpResponse, Error> {
+    let Ok(hash) = Hash::<20>::from_string(hash) else {
+        return Ok(HttpResponse::error(
+            400,
+            "Expected a 40 character info hash",
+        ));
+    };
+    Ok(match queue.get(hash)? {
+        Some(item) => HttpResponse::json(200, &item),
+        None => HttpResponse::error(404, "Item is not in the queue"),
+    })
+}
+
+fn list_spectrograms(hash: &str, queue: &Queue) -> HttpResponse {
+    match get_spectrogram_paths(hash, queue) {
+        Ok(paths) => {
+            let names: Vec<String> = paths
+                .iter()
+                .filter_map(|path| path.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .collect();
+            HttpResponse::json(200, &names)
+        }
+        Err(response) => response,
+    }
+}
+
+async fn get_spectrogram(hash: &str, index: &str, queue: &Queue) -> HttpResponse {
+    let paths = match get_spectrogram_paths(hash, queue) {
+        Ok(paths) => paths,
+        Err(response) => return response,
+    };
+    let Some(path) = index.parse::<usize>().ok().and_then(|x| paths.get(x)) else {
+        return HttpResponse::error(404, "Spectrogram does not exist");
+    };
+    match read(path).await {
+        Ok(image) => HttpResponse::png(image),
+        Err(error) => HttpResponse::error(500, &error.to_string()),
+    }
+}
+
+/// Get the spectrogram images of a queue item, sorted by path.
+///
+/// The images are only read from the directory recorded by the spectrogram command so a
+/// request can't read any other file.
+fn get_spectrogram_paths(hash: &str, queue: &Queue) -> Result<Vec<PathBuf>, HttpResponse> {
+    let Ok(hash) = Hash::<20>::from_string(hash) else {
+        return Err(HttpResponse::error(
+            400,
+            "Expected a 40 character info hash",
+        ));
+    };
+    let item = queue
+        .get(hash)
+        .map_err(|error| HttpResponse::error(500, &error.to_string()))?
+        .ok_or_else(|| HttpResponse::error(404, "Item is not in the queue"))?;
+    let dir = item
+        .spectrogram
+        .and_then(|status| status.path)
+        .ok_or_else(|| HttpResponse::error(404, "Spectrograms have not been created"))?;
+    let mut paths = DirectoryReader::new()
+        .with_extension("png")
+        .read(&dir)
+        .map_err(|error| HttpResponse::error(500, &error.to_string()))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,48 @@
+use crate::serve::ApiRoute;
+
+#[test]
+fn api_route_from_request() {
+    // Arrange
+    let cases = [
+        ("GET", vec![], Some(ApiRoute::Dashboard)),
+        ("GET", vec!["api", "status"], Some(ApiRoute::Status)),
+        ("GET", vec!["api", "queue"], Some(ApiRoute::ListQueue)),
+        ("POST", vec!["api", "queue"], Some(ApiRoute::AddToQueue)),
+        (
+            "GET",
+            vec!["api", "queue", "abc"],
+            Some(ApiRoute::GetQueueItem("abc".to_owned())),
+        ),
+        (
+            "GET",
+            vec!["api", "queue", "abc", "spectrograms"],
+            Some(ApiRoute::ListSpectrograms("abc".to_owned())),
+        ),
+        (
+            "GET",
+            vec!["api", "queue", "abc", "spectrograms", "1"],
+            Some(ApiRoute::GetSpectrogram("abc".to_owned(), "1".to_owned())),
+        ),
+        ("GET", vec!["api", "failures"], Some(ApiRoute::ListFailures)),
+        ("GET", vec!["api", "progress"], Some(ApiRoute::ListProgress)),
+        ("POST", vec!["api", "batch"], Some(ApiRoute::StartBatch)),
+        ("GET", vec!["api", "history"], Some(ApiRoute::ListHistory)),
+        (
+            "GET",
+            vec!["api", "history", "123"],
+            Some(ApiRoute::GetHistoryItem("123".to_owned())),
+        ),
+        ("DELETE", vec!["api", "queue"], None),
+        ("GET", vec!["api", "batch"], None),
+        ("GET", vec!["api"], None),
+        ("POST", vec![], None),
+    ];
+
+    for (method, segments, expected) in cases {
+        // Act
+        let actual = ApiRoute::from_request(method, &segments);
+
+        // Assert
+        assert_eq!(actual, expected, "{method} {segments:?}");
+    }
+}
@@ -0,0 +1,56 @@
+use crate::serve::HttpRequest;
+
+#[test]
+fn http_request_parse_head() {
+    // Arrange
+    let head = "GET /api/queue/abc?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer secret";
+
+    // Act
+    let request = HttpRequest::parse_head(head).expect("request should parse");
+
+    // Assert
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/api/queue/abc");
+    assert_eq!(request.get_header("HOST"), Some("localhost"));
+    assert_eq!(request.get_segments(), vec!["api", "queue", "abc"]);
+    assert!(request.is_authorized("secret"));
+    assert!(!request.is_authorized("other"));
+}
+
+#[test]
+fn http_request_parse_head_invalid() {
+    assert!(HttpRequest::parse_head("").is_none());
+    assert!(HttpRequest::parse_head("GET /").is_none());
+    assert!(HttpRequest::parse_head("GET / SMTP").is_none());
+}
+
+#[tokio::test]
+async fn http_request_read() {
+    // Arrange
+    let mut input: &[u8] =
+        b"POST /api/queue HTTP/1.1\r\nContent-Length: 13\r\n\r\n{\"path\":\"a\"}\n";
+
+    // Act
+    let request = HttpRequest::read(&mut input)
+        .await
+        .expect("request should be read");
+
+    // Assert
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.body, b"{\"path\":\"a\"}\n");
+}
+
+#[tokio::test]
+async fn http_request_read_incomplete() {
+    // Arrange
+    let mut head: &[u8] = b"GET /api/status HTTP/1.1\r\n";
+    let mut body: &[u8] = b"POST /api/queue HTTP/1.1\r\nContent-Length: 100\r\n\r\n{}";
+
+    // Act
+    let head = HttpRequest::read(&mut head).await;
+    let body = HttpRequest::read(&mut body).await;
+
+    // Assert
+    assert!(head.is_err());
+    assert!(body.is_err());
+}
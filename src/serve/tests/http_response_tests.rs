@@ -0,0 +1,31 @@
+use crate::serve::HttpResponse;
+
+#[test]
+fn http_response_to_bytes() {
+    // Arrange
+    let response = HttpResponse::error(404, "Not found");
+
+    // Act
+    let output = String::from_utf8(response.to_bytes()).expect("response should be utf-8");
+
+    // Assert
+    assert_eq!(
+        output,
+        "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 21\r\nConnection: close\r\n\r\n{\"error\":\"Not found\"}"
+    );
+}
+
+#[test]
+fn http_response_to_bytes_binary() {
+    // Arrange
+    let response = HttpResponse::png(vec![0x89, b'P', b'N', b'G']);
+
+    // Act
+    let output = response.to_bytes();
+
+    // Assert
+    assert!(
+        output.starts_with(b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: 4\r\n")
+    );
+    assert!(output.ends_with(&[b'\n', 0x89, b'P', b'N', b'G']));
+}
@@ -0,0 +1,3 @@
+mod api_route_tests;
+mod http_request_tests;
+mod http_response_tests;
@@ -3,9 +3,9 @@ use std::path::{Path, PathBuf};
 use di::{injectable, Ref};
 
 use crate::errors::error;
-use crate::imdl::ImdlCommand;
 use crate::options::{SharedOptions, SourceArg};
 use crate::source::*;
+use crate::torrent::TorrentFile;
 use rogue_logging::Error;
 
 /// Retrieve the id of a source.
@@ -13,6 +13,7 @@ use rogue_logging::Error;
 pub struct IdProvider {
     options: Ref<SharedOptions>,
     arg: Ref<SourceArg>,
+    picker: Ref<SourcePicker>,
 }
 
 impl IdProvider {
@@ -21,6 +22,9 @@ impl IdProvider {
         self.get_by_string(&source_input).await
     }
 
+    /// Get the id of a source from a torrent id, URL or torrent file.
+    ///
+    /// Anything else is a search that a source is picked from.
     pub async fn get_by_string(&self, input: &String) -> Result<u32, Error> {
         if let Ok(id) = input.parse::<u32>() {
             Ok(id)
@@ -36,13 +40,15 @@ impl IdProvider {
                     "File does not exist".to_owned(),
                 ))
             }
-        } else {
+        } else if input.trim().is_empty() {
             Err(error("get source", format!("Unknown source: {input}")))
+        } else {
+            self.picker.pick(input.trim()).await
         }
     }
 
     async fn get_by_file(&self, path: &Path) -> Result<u32, Error> {
-        let summary = ImdlCommand::show(path).await?;
+        let summary = TorrentFile::read(path).await?.get_summary();
         let tracker_id = self.options.indexer.clone().expect("indexer should be set");
         if summary.is_source_equal(&tracker_id) {
             let url = summary.comment.unwrap_or_default();
@@ -2,12 +2,16 @@ pub use id_provider::*;
 pub use metadata::*;
 pub use source::*;
 pub use source_issue::*;
+pub use source_lock::*;
+pub use source_picker::*;
 pub use source_provider::*;
 pub use url_helpers::*;
 
 pub(crate) mod id_provider;
 pub(crate) mod metadata;
 pub(crate) mod source;
+pub(crate) mod source_lock;
+pub(crate) mod source_picker;
 pub(crate) mod source_provider;
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
@@ -7,6 +7,8 @@ use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 
+/// Domain of the [`Error`](rogue_logging::Error) of an [`ApiResponse`].
+pub const API_DOMAIN: &str = "gazelle API";
 pub const MAX_PATH_LENGTH: isize = 180;
 pub const MIN_BIT_RATE_KBPS: u32 = 192;
 pub const MAX_DURATION: u32 = 12 * 60 * 60;
@@ -54,7 +56,9 @@ pub enum SourceIssue {
         expected: usize,
         actual: usize,
     },
-    Imdl {
+    // Verify results cached before the native hash check name it `imdl`
+    #[serde(alias = "imdl")]
+    HashMismatch {
         details: String,
     },
     Length {
@@ -85,6 +89,14 @@ pub enum SourceIssue {
         path: PathBuf,
         count: u32,
     },
+    TooLarge {
+        size: u64,
+        max: u64,
+    },
+    TooManyTracks {
+        count: usize,
+        max: usize,
+    },
     Error {
         domain: String,
         details: String,
@@ -92,6 +104,80 @@ pub enum SourceIssue {
     Other(String),
 }
 
+impl SourceIssue {
+    /// Get the type of the issue as it's serialized.
+    #[must_use]
+    pub fn get_rule(&self) -> &'static str {
+        match self {
+            IdError { .. } => "id_error",
+            GroupMismatch { .. } => "group_mismatch",
+            ApiResponse { .. } => "api_response",
+            Category { .. } => "category",
+            Scene => "scene",
+            LossyMaster => "lossy_master",
+            LossyWeb => "lossy_web",
+            Trumpable => "trumpable",
+            Unconfirmed => "unconfirmed",
+            Excluded { .. } => "excluded",
+            Existing { .. } => "existing",
+            NotSource { .. } => "not_source",
+            MissingDirectory { .. } => "missing_directory",
+            NoFlacs { .. } => "no_flacs",
+            FlacCount { .. } => "flac_count",
+            HashMismatch { .. } => "hash_mismatch",
+            Length { .. } => "length",
+            MissingTags { .. } => "missing_tags",
+            FlacError { .. } => "flac_error",
+            SampleRate { .. } => "sample_rate",
+            BitRate { .. } => "bit_rate",
+            Duration { .. } => "duration",
+            Channels { .. } => "channels",
+            TooLarge { .. } => "too_large",
+            TooManyTracks { .. } => "too_many_tracks",
+            Error { .. } => "error",
+            Other(_) => "other",
+        }
+    }
+
+    /// Get the track of an issue found in the audio stream of a FLAC.
+    ///
+    /// Returns `None` for any other issue.
+    #[must_use]
+    pub fn get_stream_path(&self) -> Option<&PathBuf> {
+        match self {
+            FlacError { path, .. }
+            | SampleRate { path, .. }
+            | BitRate { path, .. }
+            | Duration { path, .. }
+            | Channels { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Convert the issue to an [`Error`].
+    ///
+    /// An [`ApiResponse`] is in the [`API_DOMAIN`] and retains the status code, if
+    /// a response was received, so the failure can be identified as an API error.
+    #[must_use]
+    #[allow(clippy::absolute_paths)]
+    pub fn to_error(&self, action: &str) -> rogue_logging::Error {
+        let (domain, status_code) = match self {
+            ApiResponse { status_code, .. } => (
+                Some(API_DOMAIN.to_owned()),
+                (*status_code != 0).then_some(*status_code),
+            ),
+            _ => (None, None),
+        };
+        rogue_logging::Error {
+            action: action.to_owned(),
+            message: self.to_string(),
+            domain,
+            status_code,
+            ..rogue_logging::Error::default()
+        }
+    }
+}
+
 impl Display for SourceIssue {
     #[allow(clippy::absolute_paths)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
@@ -135,7 +221,7 @@ impl Display for SourceIssue {
             FlacCount { expected, actual } => {
                 format!("Expected {expected} FLACs, found {actual}")
             }
-            Imdl { details } => format!("Files do not match hash:\n{details}"),
+            HashMismatch { details } => format!("Files do not match hash:\n{details}"),
             Length { path, excess } => {
                 format!(
                     "Path is {excess} characters longer than allowed: {}",
@@ -165,6 +251,14 @@ impl Display for SourceIssue {
                 format!("Too many channels: {count}: {}", path.display())
             }
             FlacError { path, error } => format!("FLAC stream error: {error}: {}", path.display()),
+            TooLarge { size, max } => format!(
+                "Source is too large to batch process: {} MiB exceeds {} MiB",
+                size >> 20,
+                max >> 20
+            ),
+            TooManyTracks { count, max } => {
+                format!("Source has too many tracks to batch process: {count} exceeds {max}")
+            }
             Error { domain, details } => format!("A {domain} error occured:\n{details}"),
             Other(details) => details.clone(),
         };
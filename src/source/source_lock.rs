@@ -0,0 +1,125 @@
+use std::fs::{create_dir_all, metadata, remove_file, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::os::unix::prelude::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use rogue_logging::Error;
+
+use crate::errors::{error, io_error, path_error};
+use crate::options::CacheOptions;
+use crate::source::Source;
+
+/// Name of the directory in the cache that contains the lock files.
+const LOCKS_DIR_NAME: &str = "locks";
+
+/// Extension of a lock file.
+const LOCK_FILE_EXTENSION: &str = "lock";
+
+/// Lock of a [`Source`] held by this process.
+///
+/// The lock file is removed when dropped, before the OS releases the lock.
+#[derive(Debug)]
+pub struct SourceLock {
+    path: PathBuf,
+    _file: File,
+}
+
+impl Drop for SourceLock {
+    fn drop(&mut self) {
+        if let Err(error) = remove_file(&self.path) {
+            warn!(
+                "{} to remove lock file {}: {error}",
+                "Failed".bold(),
+                self.path.display()
+            );
+        } else {
+            trace!("{} lock {}", "Released".bold(), self.path.display());
+        }
+    }
+}
+
+/// Acquire a [`SourceLock`] so concurrent processes don't work on the same [`Source`].
+///
+/// The lock file is in the cache directory, named by the torrent id, and is locked
+/// exclusively while held so the OS releases it if the process stops. The file contains
+/// the id of the process that holds it for diagnostics only.
+pub struct SourceLocker {
+    cache_options: Ref<CacheOptions>,
+}
+
+#[injectable]
+impl SourceLocker {
+    #[must_use]
+    pub fn new(cache_options: Ref<CacheOptions>) -> Self {
+        Self { cache_options }
+    }
+
+    /// Acquire the lock of a [`Source`].
+    ///
+    /// Returns `None` if the lock is held by another process.
+    pub fn acquire(&self, source: &Source) -> Result<Option<SourceLock>, Error> {
+        let dir = self
+            .cache_options
+            .cache
+            .clone()
+            .expect("cache should be set")
+            .join(LOCKS_DIR_NAME);
+        acquire(&dir, source.torrent.id)
+    }
+
+    /// Acquire the lock of a [`Source`] or return an [`Error`] if it's held by another process.
+    pub fn acquire_or_error(&self, source: &Source) -> Result<SourceLock, Error> {
+        self.acquire(source)?.ok_or_else(|| {
+            error(
+                "lock source",
+                format!("{source} is being processed by another caesura process"),
+            )
+        })
+    }
+}
+
+/// Acquire the lock of a torrent id from a directory of lock files.
+///
+/// Returns `None` if the lock is held by another process.
+pub(crate) fn acquire(dir: &Path, id: u32) -> Result<Option<SourceLock>, Error> {
+    create_dir_all(dir).map_err(|e| path_error(e, "create lock directory", dir))?;
+    let path = dir.join(format!("{id}.{LOCK_FILE_EXTENSION}"));
+    for _ in 0..2 {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| path_error(e, "open lock file", &path))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(e)) => return Err(path_error(e, "lock file", &path)),
+        }
+        if !is_same_file(&file, &path) {
+            trace!("{} removed lock {}", "Retrying".bold(), path.display());
+            continue;
+        }
+        file.set_len(0)
+            .and_then(|()| writeln!(file, "{}", process::id()))
+            .map_err(|e| io_error(e, "write lock file"))?;
+        trace!("{} lock {}", "Acquired".bold(), path.display());
+        return Ok(Some(SourceLock { path, _file: file }));
+    }
+    Ok(None)
+}
+
+/// Does the path still refer to the locked file?
+///
+/// The previous holder removes the file before releasing the lock so a file opened
+/// before it was removed must not be used.
+fn is_same_file(file: &File, path: &Path) -> bool {
+    let (Ok(locked), Ok(current)) = (file.metadata(), metadata(path)) else {
+        return false;
+    };
+    locked.dev() == current.dev() && locked.ino() == current.ino()
+}
@@ -0,0 +1,167 @@
+use std::fmt::{Display, Formatter};
+use std::io::{stderr, stdin, IsTerminal, Write};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use html_escape::decode_html_entities;
+use log::info;
+use rogue_logging::Error;
+
+use crate::errors::{error, io_error};
+use crate::indexer::{BrowseResponse, IndexerClient};
+use crate::options::SourceArg;
+use crate::tui::is_tui_active;
+
+/// Most search results shown to pick from.
+const MAX_CHOICES: usize = 20;
+
+/// A FLAC torrent of a search that can be picked as the source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchChoice {
+    pub torrent_id: u32,
+    /// Artist, title and year of the group.
+    pub group: String,
+    /// Year and title of the edition.
+    pub edition: String,
+    pub media: String,
+    pub encoding: String,
+    /// Size in bytes.
+    pub size: u64,
+    pub seeders: u32,
+}
+
+/// Pick the source from a search of the indexer.
+///
+/// The search results are listed with their format, media and size to pick from, or the
+/// first result is picked if `first` is set.
+#[injectable]
+pub struct SourcePicker {
+    arg: Ref<SourceArg>,
+    indexer: Ref<IndexerClient>,
+}
+
+impl SourcePicker {
+    /// Search for a source and get the torrent id of the picked result.
+    pub async fn pick(&self, search: &str) -> Result<u32, Error> {
+        let action = "pick source";
+        let response = self.indexer.search_torrents(search).await?;
+        let choices = get_search_choices(&response);
+        let Some(first) = choices.first() else {
+            return Err(error(
+                action,
+                format!("No FLAC torrents match the search: {search}"),
+            ));
+        };
+        if self.arg.first.unwrap_or_default() || choices.len() == 1 {
+            info!("{} {first}", "Picked".bold());
+            return Ok(first.torrent_id);
+        }
+        if !stdin().is_terminal() || is_tui_active() {
+            return Err(error(
+                action,
+                "Unable to pick a search result as stdin is not a terminal. Set `first` to pick the first result".to_owned(),
+            ));
+        }
+        let choices: Vec<SearchChoice> = choices.into_iter().take(MAX_CHOICES).collect();
+        loop {
+            if let Some(index) = parse_choice(&read_choice(&choices)?, choices.len()) {
+                let choice = choices.get(index).expect("choice should exist");
+                return Ok(choice.torrent_id);
+            }
+        }
+    }
+}
+
+/// Get the FLAC torrents of a search in the order they were returned.
+#[must_use]
+pub fn get_search_choices(response: &BrowseResponse) -> Vec<SearchChoice> {
+    let mut choices = Vec::new();
+    for group in &response.results {
+        let mut name = decode_html_entities(&group.group_name).to_string();
+        if !group.artist.is_empty() {
+            name = format!("{} - {name}", decode_html_entities(&group.artist));
+        }
+        if let Some(year) = group.group_year.filter(|year| *year > 0) {
+            name = format!("{name} ({year})");
+        }
+        for torrent in &group.torrents {
+            if !torrent.format.eq_ignore_ascii_case("FLAC") {
+                continue;
+            }
+            let edition = [
+                torrent
+                    .remaster_year
+                    .filter(|year| *year > 0)
+                    .map(|year| year.to_string())
+                    .unwrap_or_default(),
+                decode_html_entities(&torrent.remaster_title).to_string(),
+            ]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+            choices.push(SearchChoice {
+                torrent_id: torrent.torrent_id,
+                group: name.clone(),
+                edition,
+                media: torrent.media.clone(),
+                encoding: torrent.encoding.clone(),
+                size: torrent.size,
+                seeders: torrent.seeders,
+            });
+        }
+    }
+    choices
+}
+
+/// Parse the number of a picked choice.
+///
+/// Returns the zero based index, or `None` if the answer isn't one of the `count` choices.
+#[must_use]
+pub fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    let number = answer.trim().parse::<usize>().ok()?;
+    (1..=count).contains(&number).then(|| number - 1)
+}
+
+/// Write the choices to stderr, so stdout is left for the result, and read a line.
+fn read_choice(choices: &[SearchChoice]) -> Result<String, Error> {
+    let mut stderr = stderr();
+    choices
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, choice)| writeln!(stderr, "{:>3}. {choice}", index + 1))
+        .and_then(|()| write!(stderr, "{} [1-{}]: ", "Pick a source".bold(), choices.len()))
+        .and_then(|()| stderr.flush())
+        .map_err(|e| io_error(e, "write prompt"))?;
+    let mut answer = String::new();
+    let length = stdin()
+        .read_line(&mut answer)
+        .map_err(|e| io_error(e, "read answer"))?;
+    if length == 0 {
+        return Err(error("read answer", "No input is available".to_owned()));
+    }
+    Ok(answer)
+}
+
+impl Display for SearchChoice {
+    #[allow(
+        clippy::absolute_paths,
+        clippy::as_conversions,
+        clippy::cast_precision_loss
+    )]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.group)?;
+        if !self.edition.is_empty() {
+            write!(formatter, " [{}]", self.edition)?;
+        }
+        write!(
+            formatter,
+            " {} {} {:.1} MiB, {} seeders, torrent {}",
+            self.media,
+            self.encoding,
+            self.size as f64 / 1024.0 / 1024.0,
+            self.seeders,
+            self.torrent_id
+        )
+    }
+}
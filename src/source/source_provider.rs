@@ -1,13 +1,21 @@
 use crate::formats::{ExistingFormat, ExistingFormatProvider};
+use crate::indexer::RateLimiter;
+use crate::logging::set_log_source;
+use crate::metrics::Metrics;
+use crate::naming::resolve_normalized;
 use crate::options::SharedOptions;
+use crate::progress::{ProgressEmitter, ProgressEvent};
 use crate::source::SourceIssue;
 use crate::source::*;
+use crate::summary::{TimingPhase, Timings};
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use gazelle_api::{GazelleClient, Torrent};
 use html_escape::decode_html_entities;
 use log::{trace, warn};
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// Retrieve [Source] from the [Api] via a [provider design pattern](https://en.wikipedia.org/wiki/Provider_model)
 #[injectable]
@@ -15,12 +23,41 @@ pub struct SourceProvider {
     api: RefMut<GazelleClient>,
     options: Ref<SharedOptions>,
     id_provider: Ref<IdProvider>,
+    progress: Ref<ProgressEmitter>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    timings: Ref<Timings>,
 }
 
 impl SourceProvider {
+    /// Get the [`Source`] of a torrent id.
+    ///
+    /// The id is set as the source of subsequent log events.
     pub async fn get(&mut self, id: u32) -> Result<Source, SourceIssue> {
+        set_log_source(Some(id.to_string()));
+        let result = self.get_source(id).await;
+        let event = match &result {
+            Ok(source) => ProgressEvent::SourceResolved {
+                name: source.to_string(),
+                directory: source.directory.clone(),
+            },
+            Err(issue) => ProgressEvent::VerifyFailed {
+                rules: vec![issue.get_rule().to_owned()],
+                issues: vec![issue.to_string()],
+            },
+        };
+        self.progress.emit(event);
+        result
+    }
+
+    async fn get_source(&mut self, id: u32) -> Result<Source, SourceIssue> {
         let mut api = self.api.write().expect("API should be available to read");
-        let response = match api.get_torrent(id).await {
+        self.rate_limiter.wait().await;
+        let start = Instant::now();
+        let result = api.get_torrent(id).await;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        self.metrics.record_api("get torrent", &result);
+        let response = match result {
             Ok(response) => response,
             Err(error) => Err(SourceIssue::ApiResponse {
                 action: "get torrent".to_owned(),
@@ -30,7 +67,12 @@ impl SourceProvider {
         };
         let torrent = response.torrent;
         let group = response.group;
-        let response = match api.get_torrent_group(group.id).await {
+        self.rate_limiter.wait().await;
+        let start = Instant::now();
+        let result = api.get_torrent_group(group.id).await;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        self.metrics.record_api("get torrent group", &result);
+        let response = match result {
             Ok(response) => response,
             Err(error) => Err(SourceIssue::ApiResponse {
                 action: "get torrent group".to_owned(),
@@ -66,20 +108,37 @@ impl SourceProvider {
         })
     }
 
+    /// Get the existing formats of the group of a [`Source`] from the API.
+    ///
+    /// Unlike [`Source::existing`] this includes formats uploaded since the source was
+    /// retrieved.
+    pub async fn get_existing(
+        &self,
+        source: &Source,
+    ) -> Result<BTreeSet<ExistingFormat>, SourceIssue> {
+        let mut api = self.api.write().expect("API should be available to read");
+        self.rate_limiter.wait().await;
+        let start = Instant::now();
+        let result = api.get_torrent_group(source.group.id).await;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        self.metrics.record_api("get torrent group", &result);
+        let response = result.map_err(|error| SourceIssue::ApiResponse {
+            action: "get torrent group".to_owned(),
+            status_code: error.status_code.unwrap_or_default(),
+            error: error.message,
+        })?;
+        Ok(ExistingFormatProvider::get(
+            &source.torrent,
+            &response.torrents,
+        ))
+    }
+
     fn get_source_directory(&self, torrent: &Torrent) -> Result<PathBuf, SourceIssue> {
-        let path = decode_html_entities(&torrent.file_path).to_string();
-        let directories: Vec<PathBuf> = self
-            .options
-            .content
-            .clone()
-            .expect("content should be set")
-            .iter()
-            .map(|x| x.join(path.clone()))
-            .filter(|x| x.exists() && x.is_dir())
-            .collect();
+        let content = self.options.content.clone().expect("content should be set");
+        let directories = get_source_directories(&content, &torrent.file_path);
         if directories.is_empty() {
             return Err(SourceIssue::MissingDirectory {
-                path: PathBuf::from(path),
+                path: PathBuf::from(decode_html_entities(&torrent.file_path).to_string()),
             });
         } else if directories.len() > 1 {
             warn!(
@@ -102,3 +161,17 @@ impl SourceProvider {
         }
     }
 }
+
+/// Get the directories matching the path of a source in each content directory.
+///
+/// The path is decoded as the API HTML encodes it, and each name is matched regardless
+/// of its unicode normalization form.
+#[must_use]
+pub fn get_source_directories(content: &[PathBuf], path: &str) -> Vec<PathBuf> {
+    let path = decode_html_entities(path).to_string();
+    content
+        .iter()
+        .filter_map(|dir| resolve_normalized(dir, Path::new(&path)))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
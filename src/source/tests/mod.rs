@@ -1,2 +1,4 @@
+mod source_lock_tests;
+mod source_picker_tests;
 mod source_provider_tests;
 mod url_helpers_tests;
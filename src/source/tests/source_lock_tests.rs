@@ -0,0 +1,49 @@
+use std::fs::write;
+
+use crate::source::source_lock::acquire;
+use crate::testing::TempDirectory;
+
+#[test]
+fn source_lock_acquire_is_exclusive() {
+    // Arrange
+    let dir = TempDirectory::create("source_lock_acquire_is_exclusive");
+
+    // Act
+    let first = acquire(&dir, 123).expect("should acquire");
+    let second = acquire(&dir, 123).expect("should acquire");
+    let other = acquire(&dir, 456).expect("should acquire");
+
+    // Assert
+    assert!(first.is_some());
+    assert!(second.is_none());
+    assert!(other.is_some());
+}
+
+#[test]
+fn source_lock_released_on_drop() {
+    // Arrange
+    let dir = TempDirectory::create("source_lock_released_on_drop");
+    let lock = acquire(&dir, 123).expect("should acquire");
+
+    assert!(dir.join("123.lock").exists());
+
+    // Act
+    drop(lock);
+
+    // Assert
+    assert!(!dir.join("123.lock").exists());
+    assert!(acquire(&dir, 123).expect("should acquire").is_some());
+}
+
+#[test]
+fn source_lock_replaces_stale_lock() {
+    // Arrange
+    let dir = TempDirectory::create("source_lock_replaces_stale_lock");
+    write(dir.join("123.lock"), format!("{}\n", u32::MAX)).expect("should write lock");
+
+    // Act
+    let lock = acquire(&dir, 123).expect("should acquire");
+
+    // Assert
+    assert!(lock.is_some());
+}
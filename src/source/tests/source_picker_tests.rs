@@ -0,0 +1,103 @@
+use rogue_logging::Error;
+
+use crate::indexer::{parse_response, BrowseResponse};
+use crate::source::{get_search_choices, parse_choice};
+
+const BROWSE_JSON: &str = r#"{
+  "status": "success",
+  "response": {
+    "currentPage": 1,
+    "pages": 1,
+    "results": [
+      {
+        "groupId": 72189681,
+        "groupName": "Fear Not",
+        "artist": "Logistics &amp; Friends",
+        "groupYear": 2012,
+        "torrents": [
+          {
+            "torrentId": 29991962,
+            "media": "CD",
+            "format": "FLAC",
+            "encoding": "Lossless",
+            "remasterYear": 2013,
+            "remasterTitle": "Deluxe",
+            "size": 314572800,
+            "seeders": 12
+          },
+          {
+            "torrentId": 29991963,
+            "media": "CD",
+            "format": "MP3",
+            "encoding": "320",
+            "size": 104857600,
+            "seeders": 30
+          }
+        ]
+      },
+      {
+        "groupId": 72189682,
+        "groupName": "Reality Checkpoint",
+        "artist": "Logistics",
+        "groupYear": 2010,
+        "torrents": [
+          {
+            "torrentId": 29991970,
+            "media": "WEB",
+            "format": "FLAC",
+            "encoding": "24bit Lossless",
+            "remasterYear": 0,
+            "remasterTitle": "",
+            "size": 1048576,
+            "seeders": 1
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+#[test]
+fn get_search_choices_flacs() -> Result<(), Error> {
+    // Arrange
+    let response: BrowseResponse = parse_response("search torrents", 200, BROWSE_JSON)?;
+
+    // Act
+    let choices = get_search_choices(&response);
+
+    // Assert
+    let ids: Vec<u32> = choices.iter().map(|choice| choice.torrent_id).collect();
+    assert_eq!(ids, vec![29_991_962, 29_991_970]);
+    assert_eq!(
+        choices.first().map(ToString::to_string),
+        Some(
+            "Logistics & Friends - Fear Not (2012) [2013 Deluxe] CD Lossless 300.0 MiB, 12 seeders, torrent 29991962"
+                .to_owned()
+        )
+    );
+    assert_eq!(
+        choices.get(1).map(ToString::to_string),
+        Some(
+            "Logistics - Reality Checkpoint (2010) WEB 24bit Lossless 1.0 MiB, 1 seeders, torrent 29991970"
+                .to_owned()
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_choice_range() {
+    // Act
+    let first = parse_choice(" 1\n", 3);
+    let last = parse_choice("3", 3);
+    let zero = parse_choice("0", 3);
+    let over = parse_choice("4", 3);
+    let text = parse_choice("one", 3);
+
+    // Assert
+    assert_eq!(first, Some(0));
+    assert_eq!(last, Some(2));
+    assert_eq!(zero, None);
+    assert_eq!(over, None);
+    assert_eq!(text, None);
+}
@@ -0,0 +1,44 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Audio channels rendered in a spectrogram.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Channels {
+    /// Only the first channel.
+    #[default]
+    Left,
+    /// A mix of every channel.
+    Mix,
+    /// A separate image of each channel.
+    Split,
+    /// Every channel stacked in a single image.
+    Stacked,
+}
+
+impl Channels {
+    /// Get the `remix` effect arguments of sox for each image to render.
+    ///
+    /// `None` renders every channel of the file stacked in one image.
+    #[must_use]
+    pub fn get_remixes(self, channel_count: u32) -> Vec<Option<String>> {
+        match self {
+            Channels::Left => vec![Some("1".to_owned())],
+            Channels::Mix => vec![Some("-".to_owned())],
+            Channels::Split => (1..=channel_count.max(1))
+                .map(|channel| Some(channel.to_string()))
+                .collect(),
+            Channels::Stacked => vec![None],
+        }
+    }
+}
+
+/// Get the suffix of the file name of a spectrogram of a single channel.
+#[must_use]
+pub fn get_channel_suffix(channel: &str) -> String {
+    match channel {
+        "1" => "left".to_owned(),
+        "2" => "right".to_owned(),
+        channel => format!("channel{channel}"),
+    }
+}
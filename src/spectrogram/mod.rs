@@ -1,9 +1,13 @@
+pub use channels::*;
+pub use optimize::*;
 pub use size::*;
 pub use spectrogram_command::*;
 pub use spectrogram_job::*;
 pub use spectrogram_job_factory::*;
 pub use spectrogram_status::*;
 
+pub(crate) mod channels;
+pub(crate) mod optimize;
 mod spectrogram_job;
 
 pub(crate) mod size;
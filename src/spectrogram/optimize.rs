@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use crate::dependencies::CONVERT;
+use crate::transcode::CommandInfo;
+
+/// Number of colors of the palette a spectrogram is quantized to.
+const PALETTE_COLORS: &str = "256";
+
+/// Information needed to shrink a spectrogram image in place.
+pub struct Optimize {
+    /// Path to the PNG image.
+    pub path: PathBuf,
+    /// Should the image be quantized to a palette and recompressed?
+    pub palette: bool,
+    /// Width in pixels to downscale a wider image to.
+    pub max_width: Option<u32>,
+}
+
+impl Optimize {
+    /// Create a convert command that replaces the image.
+    ///
+    /// Returns `None` if there is nothing to optimize.
+    #[must_use]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_info(self) -> Option<CommandInfo> {
+        if !self.palette && self.max_width.is_none() {
+            return None;
+        }
+        let path = self.path.to_string_lossy().to_string();
+        let mut args = vec![path.clone()];
+        if let Some(max_width) = self.max_width {
+            args.push("-resize".to_owned());
+            args.push(format!("{max_width}x>"));
+        }
+        let output = if self.palette {
+            args.push("-strip".to_owned());
+            args.push("-colors".to_owned());
+            args.push(PALETTE_COLORS.to_owned());
+            args.push("-define".to_owned());
+            args.push("png:compression-level=9".to_owned());
+            format!("PNG8:{path}")
+        } else {
+            path
+        };
+        args.push(output);
+        Some(CommandInfo {
+            program: CONVERT.to_owned(),
+            args,
+        })
+    }
+}
@@ -2,14 +2,18 @@ use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use log::{debug, info};
 
-use crate::errors::error;
+use crate::errors::log_error;
 use crate::fs::*;
 use crate::jobs::JobRunner;
-use crate::options::{Options, SharedOptions, SourceArg, SpectrogramOptions};
+use crate::options::{
+    OptionRule, Options, PermissionOptions, SharedOptions, SourceArg, SpectrogramOptions,
+};
+use crate::progress::{ProgressEmitter, ProgressEvent};
 use crate::queue::TimeStamp;
 use crate::source::{Source, SourceProvider};
 use crate::spectrogram::*;
 use rogue_logging::Error;
+use std::path::PathBuf;
 
 /// Generate spectrograms for each track of a FLAC source.
 #[injectable]
@@ -17,10 +21,12 @@ pub struct SpectrogramCommand {
     arg: Ref<SourceArg>,
     shared_options: Ref<SharedOptions>,
     spectrogram_options: Ref<SpectrogramOptions>,
+    permission_options: Ref<PermissionOptions>,
     source_provider: RefMut<SourceProvider>,
     paths: Ref<PathManager>,
     factory: Ref<SpectrogramJobFactory>,
     runner: Ref<JobRunner>,
+    progress: Ref<ProgressEmitter>,
 }
 
 impl SpectrogramCommand {
@@ -30,22 +36,24 @@ impl SpectrogramCommand {
     ///
     /// Returns `true` if the spectrogram generation succeeds.
     pub async fn execute_cli(&self) -> Result<bool, Error> {
-        if !self.arg.validate()
-            || !self.shared_options.validate()
-            || !self.spectrogram_options.validate()
-        {
-            return Ok(false);
-        }
+        let errors = [
+            self.arg.get_errors(),
+            self.shared_options.get_errors(),
+            self.spectrogram_options.get_errors(),
+            self.permission_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
         let source = self
             .source_provider
             .write()
             .expect("Source provider should be writeable")
             .get_from_options()
             .await
-            .map_err(|e| error("get source from options", e.to_string()))?;
+            .map_err(|e| e.to_error("get source from options"))?;
         let status = self.execute(&source).await;
         if let Some(error) = &status.error {
-            error.log();
+            log_error(error);
         }
         Ok(status.success)
     }
@@ -58,16 +66,49 @@ impl SpectrogramCommand {
     #[must_use]
     pub async fn execute(&self, source: &Source) -> SpectrogramStatus {
         info!("{} spectrograms for {}", "Creating".bold(), source);
-        let collection = Collector::get_flacs(&source.directory);
-        let jobs = self.factory.create(&collection, source);
+        let flacs = Collector::get_flacs(&source.directory);
+        self.execute_flacs(source, &flacs).await
+    }
+
+    /// Execute [`SpectrogramCommand`] on some tracks of a [`Source`].
+    ///
+    /// Returns a [`SpectrogramStatus`] indicating the success of the operation and any errors.
+    ///
+    /// Errors are not logged so should be handled by the caller.
+    #[must_use]
+    pub async fn execute_tracks(&self, source: &Source, paths: &[PathBuf]) -> SpectrogramStatus {
+        info!(
+            "{} spectrograms for {} tracks of {}",
+            "Creating".bold(),
+            paths.len(),
+            source
+        );
+        let flacs: Vec<FlacFile> = Collector::get_flacs(&source.directory)
+            .into_iter()
+            .filter(|flac| paths.contains(&flac.path))
+            .collect();
+        self.execute_flacs(source, &flacs).await
+    }
+
+    async fn execute_flacs(&self, source: &Source, flacs: &[FlacFile]) -> SpectrogramStatus {
+        let jobs = self.factory.create(flacs, source);
         let count = jobs.len();
         self.runner.add(jobs);
-        match self.runner.execute().await {
+        let path = self.paths.get_spectrogram_dir(source);
+        let result = self
+            .runner
+            .execute()
+            .await
+            .and_then(|()| self.permission_options.get_permissions().apply(&path));
+        match result {
             Ok(()) => {
                 info!("{} {count} spectrograms for {source}", "Created".bold());
-                let path = self.paths.get_spectrogram_dir(source);
                 let path_display = path.to_string_lossy().to_string();
                 debug!("in {path_display}");
+                self.progress.emit(ProgressEvent::SpectrogramsCreated {
+                    count,
+                    path: path.clone(),
+                });
                 SpectrogramStatus {
                     success: true,
                     path: Some(path),
@@ -1,11 +1,12 @@
+use log::trace;
 use rogue_logging::Error;
 use std::fs::create_dir_all;
 use std::path::PathBuf;
 use std::process::Output;
 use tokio::process::Command;
 
-use crate::dependencies::SOX;
-use crate::errors::{command_error, io_error, OutputHandler};
+use crate::dependencies::{CONVERT, SOX};
+use crate::errors::{command_error, get_command_line, path_error, OutputHandler};
 use crate::spectrogram::*;
 
 /// A command to generate a spectrogram image of a FLAC file using sox.
@@ -19,6 +20,12 @@ pub struct SpectrogramJob {
     pub output_path: PathBuf,
     pub image_title: String,
     pub size: Size,
+    /// Channels to mix with the sox `remix` effect or `None` to stack every channel.
+    pub remix: Option<String>,
+    /// Should the image be quantized to a palette and recompressed?
+    pub optimize: bool,
+    /// Width in pixels to downscale a wider image to.
+    pub max_width: Option<u32>,
 }
 
 impl SpectrogramJob {
@@ -29,20 +36,42 @@ impl SpectrogramJob {
             .parent()
             .expect("output path should have a parent");
         create_dir_all(output_dir)
-            .map_err(|e| io_error(e, "create spectrogram output directory"))?;
+            .map_err(|e| path_error(e, "create spectrogram output directory", output_dir))?;
         match self.size {
             Size::Full => self.execute_full().await,
             Size::Zoom => self.execute_zoom().await,
         }?;
+        self.execute_optimize().await
+    }
+
+    /// Shrink the image if `spectrogram_optimize` or `spectrogram_max_width` is set.
+    async fn execute_optimize(self) -> Result<(), Error> {
+        let optimize = Optimize {
+            path: self.output_path,
+            palette: self.optimize,
+            max_width: self.max_width,
+        };
+        let Some(info) = optimize.to_info() else {
+            return Ok(());
+        };
+        trace!("{info}");
+        let line = info.display();
+        let output = info
+            .to_command()
+            .output()
+            .await
+            .map_err(|e| command_error(e, "execute optimize spectrogram", CONVERT))?;
+        OutputHandler::execute_command(output, "optimize spectrogram", CONVERT, &line)?;
         Ok(())
     }
 
     async fn execute_zoom(&self) -> Result<Output, Error> {
-        let output = Command::new(SOX)
+        let mut command = Command::new(SOX);
+        command
+            .kill_on_drop(true)
             .arg(&self.source_path)
             .arg("-n")
-            .arg("remix")
-            .arg("1")
+            .args(self.get_remix_args())
             .arg("spectrogram")
             .arg("-x")
             .arg("500")
@@ -61,19 +90,22 @@ impl SpectrogramJob {
             .arg("-c")
             .arg("red_oxide")
             .arg("-o")
-            .arg(&self.output_path)
+            .arg(&self.output_path);
+        let line = get_command_line(&command);
+        let output = command
             .output()
             .await
             .map_err(|e| command_error(e, "execute generate spectrogram", SOX))?;
-        OutputHandler::execute(output, "generate spectrogram", "IMDL")
+        OutputHandler::execute_command(output, "generate spectrogram", SOX, &line)
     }
 
     async fn execute_full(&self) -> Result<Output, Error> {
-        let output = Command::new(SOX)
+        let mut command = Command::new(SOX);
+        command
+            .kill_on_drop(true)
             .arg(&self.source_path)
             .arg("-n")
-            .arg("remix")
-            .arg("1")
+            .args(self.get_remix_args())
             .arg("spectrogram")
             .arg("-x")
             .arg("3000")
@@ -88,10 +120,19 @@ impl SpectrogramJob {
             .arg("-c")
             .arg("red_oxide")
             .arg("-o")
-            .arg(&self.output_path)
+            .arg(&self.output_path);
+        let line = get_command_line(&command);
+        let output = command
             .output()
             .await
             .map_err(|e| command_error(e, "execute generate spectrogram", SOX))?;
-        OutputHandler::execute(output, "generate spectrogram", "IMDL")
+        OutputHandler::execute_command(output, "generate spectrogram", SOX, &line)
+    }
+
+    fn get_remix_args(&self) -> Vec<&str> {
+        match &self.remix {
+            Some(remix) => vec!["remix", remix],
+            None => Vec::new(),
+        }
     }
 }
@@ -1,24 +1,65 @@
+use std::fs::metadata;
+use std::path::Path;
+
 use di::{injectable, Ref};
+use log::debug;
 
 use crate::fs::{FlacFile, PathManager};
 use crate::jobs::Job;
+use crate::options::SpectrogramOptions;
 use crate::source::Source;
 use crate::spectrogram::*;
 
 /// A factory for creating [`SpectrogramJob`] from multiple flac files.
 #[injectable]
 pub struct SpectrogramJobFactory {
+    options: Ref<SpectrogramOptions>,
     paths: Ref<PathManager>,
 }
 
 impl SpectrogramJobFactory {
     /// Create a [`SpectrogramJob`] for each [`FlacFile`] in the [`Vec<FlacFile>`].
+    ///
+    /// With `spectrogram_channels: split` a job is created for each channel of each file.
+    ///
+    /// Spectrograms that are up to date are skipped unless `spectrogram_force` is set.
     #[must_use]
     pub fn create(&self, flacs: &[FlacFile], source: &Source) -> Vec<Job> {
+        let channels = self.options.spectrogram_channels.unwrap_or_default();
+        let force = self.options.spectrogram_force.unwrap_or_default();
         let mut jobs = Vec::new();
+        let mut skipped = 0_usize;
         for (index, flac) in flacs.iter().enumerate() {
-            jobs.push(self.create_single(source, index, flac, Size::Zoom));
-            jobs.push(self.create_single(source, index, flac, Size::Full));
+            let channel_count = if channels == Channels::Split {
+                flac.get_stream_info().map_or(2, |info| info.channels)
+            } else {
+                2
+            };
+            let remixes = channels.get_remixes(channel_count);
+            let is_split = remixes.len() > 1;
+            for remix in remixes {
+                let channel = if is_split { remix.clone() } else { None };
+                for size in [Size::Zoom, Size::Full] {
+                    let job = self.create_single(
+                        source,
+                        index,
+                        flac,
+                        size,
+                        remix.clone(),
+                        channel.as_deref(),
+                    );
+                    if let Job::Spectrogram(spectrogram) = &job {
+                        if !force && is_up_to_date(&flac.path, &spectrogram.output_path) {
+                            skipped += 1;
+                            continue;
+                        }
+                    }
+                    jobs.push(job);
+                }
+            }
+        }
+        if skipped > 0 {
+            debug!("Skipping {skipped} spectrograms that are up to date");
         }
         jobs
     }
@@ -29,26 +70,63 @@ impl SpectrogramJobFactory {
     ///
     /// * `flac_file`: Path to the flac file.
     /// * `size`: Size of the spectrogram to create.
-    fn create_single(&self, source: &Source, index: usize, flac: &FlacFile, size: Size) -> Job {
-        let out_filename = flac.file_name.clone()
-            + match size {
-                Size::Full => ".full.png",
-                Size::Zoom => ".zoom.png",
-            };
-        let id = format!("Spectrogram {size:<4?}{index:>3}");
+    /// * `remix`: Channels to render or `None` to stack every channel.
+    /// * `channel`: Channel of a split spectrogram to include in the file name.
+    fn create_single(
+        &self,
+        source: &Source,
+        index: usize,
+        flac: &FlacFile,
+        size: Size,
+        remix: Option<String>,
+        channel: Option<&str>,
+    ) -> Job {
+        let channel_suffix = channel.map(get_channel_suffix);
+        let mut out_filename = self.paths.get_output_name(&flac.file_name);
+        if let Some(suffix) = &channel_suffix {
+            out_filename.push('.');
+            out_filename.push_str(suffix);
+        }
+        out_filename.push_str(match size {
+            Size::Full => ".full.png",
+            Size::Zoom => ".zoom.png",
+        });
+        let id = match channel {
+            Some(channel) => format!("Spectrogram {size:<4?}{index:>3}.{channel}"),
+            None => format!("Spectrogram {size:<4?}{index:>3}"),
+        };
         let source_path = flac.get_path_string();
         let output_path = self
             .paths
             .get_spectrogram_dir(source)
-            .join(&flac.sub_dir)
+            .join(self.paths.get_output_sub_dir(&flac.sub_dir))
             .join(out_filename);
-        let image_title = flac.file_name.clone();
+        let image_title = match channel_suffix {
+            Some(suffix) => format!("{} ({suffix})", flac.file_name),
+            None => flac.file_name.clone(),
+        };
         Job::Spectrogram(SpectrogramJob {
             id,
             source_path,
             output_path,
             image_title,
             size,
+            remix,
+            optimize: self.options.spectrogram_optimize.unwrap_or_default(),
+            max_width: self.options.spectrogram_max_width,
         })
     }
 }
+
+/// Check if a spectrogram exists and was modified after its FLAC file.
+#[must_use]
+pub fn is_up_to_date(flac: &Path, spectrogram: &Path) -> bool {
+    let (Ok(flac), Ok(spectrogram)) = (metadata(flac), metadata(spectrogram)) else {
+        return false;
+    };
+    let (Ok(flac_modified), Ok(spectrogram_modified)) = (flac.modified(), spectrogram.modified())
+    else {
+        return false;
+    };
+    spectrogram.len() > 0 && spectrogram_modified >= flac_modified
+}
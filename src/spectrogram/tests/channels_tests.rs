@@ -0,0 +1,20 @@
+use crate::spectrogram::{get_channel_suffix, Channels};
+
+#[test]
+fn channels_get_remixes() {
+    assert_eq!(Channels::Left.get_remixes(2), vec![Some("1".to_owned())]);
+    assert_eq!(Channels::Mix.get_remixes(2), vec![Some("-".to_owned())]);
+    assert_eq!(Channels::Stacked.get_remixes(2), vec![None]);
+    assert_eq!(
+        Channels::Split.get_remixes(2),
+        vec![Some("1".to_owned()), Some("2".to_owned())]
+    );
+    assert_eq!(Channels::Split.get_remixes(1), vec![Some("1".to_owned())]);
+}
+
+#[test]
+fn channels_get_suffix() {
+    assert_eq!(get_channel_suffix("1"), "left");
+    assert_eq!(get_channel_suffix("2"), "right");
+    assert_eq!(get_channel_suffix("3"), "channel3");
+}
@@ -1 +1,4 @@
+mod channels_tests;
+mod optimize_tests;
 mod spectrogram_command_tests;
+mod spectrogram_job_factory_tests;
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use crate::spectrogram::Optimize;
+
+#[test]
+fn optimize_none() {
+    // Arrange
+    let optimize = Optimize {
+        path: PathBuf::from("01.full.png"),
+        palette: false,
+        max_width: None,
+    };
+
+    // Act
+    let info = optimize.to_info();
+
+    // Assert
+    assert!(info.is_none());
+}
+
+#[test]
+fn optimize_palette_and_width() {
+    // Arrange
+    let optimize = Optimize {
+        path: PathBuf::from("01.full.png"),
+        palette: true,
+        max_width: Some(1500),
+    };
+
+    // Act
+    let info = optimize.to_info().expect("info should be created");
+
+    // Assert
+    assert_eq!(
+        info.display(),
+        "convert 01.full.png -resize 1500x> -strip -colors 256 -define png:compression-level=9 PNG8:01.full.png"
+    );
+}
+
+#[test]
+fn optimize_width_only() {
+    // Arrange
+    let optimize = Optimize {
+        path: PathBuf::from("01.zoom.png"),
+        palette: false,
+        max_width: Some(400),
+    };
+
+    // Act
+    let info = optimize.to_info().expect("info should be created");
+
+    // Assert
+    assert_eq!(
+        info.display(),
+        "convert 01.zoom.png -resize 400x> 01.zoom.png"
+    );
+}
@@ -0,0 +1,37 @@
+use std::fs::{write, File};
+use std::time::{Duration, SystemTime};
+
+use crate::spectrogram::is_up_to_date;
+use crate::testing::TempDirectory;
+
+#[test]
+fn is_up_to_date_compares_modified_times() {
+    // Arrange
+    let dir = TempDirectory::create("spectrogram_is_up_to_date");
+    let flac = dir.join("01 Track.flac");
+    let spectrogram = dir.join("01 Track.full.png");
+    write(&flac, "flac").expect("should write flac");
+    let missing = is_up_to_date(&flac, &spectrogram);
+    write(&spectrogram, "png").expect("should write spectrogram");
+    let now = SystemTime::now();
+    let set_modified = |path, time| {
+        File::options()
+            .write(true)
+            .open(path)
+            .expect("should open file")
+            .set_modified(time)
+            .expect("should set modified time");
+    };
+    set_modified(&flac, now);
+    set_modified(&spectrogram, now + Duration::from_secs(1));
+
+    // Act
+    let newer = is_up_to_date(&flac, &spectrogram);
+    set_modified(&flac, now + Duration::from_secs(2));
+    let older = is_up_to_date(&flac, &spectrogram);
+
+    // Assert
+    assert!(!missing);
+    assert!(newer);
+    assert!(!older);
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::summary::SourceResult;
+
+/// Result of a command as written to stdout with `result_format: json`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CommandResult {
+    /// Name of the subcommand.
+    pub command: String,
+    /// Did the command succeed?
+    pub success: bool,
+    /// Message of the error that stopped the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Stable [`ErrorCode`](crate::errors::ErrorCode) of the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Time since the command started, rounded down to whole seconds.
+    pub elapsed_seconds: u64,
+    /// Each source processed by the command in the order it was started.
+    pub sources: Vec<SourceResult>,
+    /// Output of a command that reports data rather than processing sources,
+    /// such as `config`, `history` or `queue summary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
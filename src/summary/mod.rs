@@ -0,0 +1,13 @@
+pub use command_result::*;
+pub use result_format::*;
+pub use run_summary::*;
+pub use source_result::*;
+pub use timings::*;
+
+pub(crate) mod command_result;
+pub(crate) mod result_format;
+pub(crate) mod run_summary;
+pub(crate) mod source_result;
+#[cfg(test)]
+mod tests;
+pub(crate) mod timings;
@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Format of the result written to stdout when a command completes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultFormat {
+    /// Only the summary is logged, intended to be read by a person.
+    #[default]
+    Text,
+    /// A [`CommandResult`] as a JSON object intended to be read by another program.
+    Json,
+}
@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info, warn};
+use rogue_logging::Error;
+use serde::Serialize;
+
+use crate::errors::ErrorCode;
+use crate::formats::TargetFormat;
+use crate::naming::join_humanized;
+use crate::options::SharedOptions;
+use crate::progress::{ProgressEvent, ProgressLine};
+use crate::summary::{CommandResult, ResultFormat, SourceResult, UploadResult};
+
+/// Maximum number of failing rules to include in the summary.
+const TOP_RULES: usize = 3;
+
+/// Collate each [`ProgressEvent`] so a summary can be shown at the end of a command.
+///
+/// With `result_format: json` the [`SourceResult`] of each source is also written to
+/// stdout as a [`CommandResult`].
+pub struct RunSummary {
+    start: Instant,
+    output: Option<PathBuf>,
+    format: ResultFormat,
+    totals: Mutex<SummaryTotals>,
+    sources: Mutex<Vec<SourceResult>>,
+    data: Mutex<Option<serde_json::Value>>,
+}
+
+/// Totals of the [`ProgressEvent`] recorded by [`RunSummary`].
+#[derive(Debug, Default)]
+pub struct SummaryTotals {
+    pub sources: BTreeSet<String>,
+    pub verified: usize,
+    pub skipped: usize,
+    pub rules: BTreeMap<String, usize>,
+    pub tracks: usize,
+    pub formats: BTreeSet<TargetFormat>,
+    pub audio_seconds: u64,
+    pub directories: Vec<PathBuf>,
+    pub torrents: usize,
+    pub uploads: usize,
+    /// Uploads to an indexer other than that of the source.
+    pub cross_posts: usize,
+    /// Formats not cross-posted as they were not transcoded.
+    pub skipped_cross_posts: usize,
+}
+
+#[injectable]
+impl RunSummary {
+    #[must_use]
+    pub fn new(options: Ref<SharedOptions>) -> Self {
+        Self {
+            start: Instant::now(),
+            output: options.output.clone(),
+            format: options.result_format.unwrap_or_default(),
+            totals: Mutex::new(SummaryTotals::default()),
+            sources: Mutex::new(Vec::new()),
+            data: Mutex::new(None),
+        }
+    }
+
+    /// Add a [`ProgressLine`] to the totals and the [`SourceResult`] of its source.
+    pub fn record(&self, line: &ProgressLine) {
+        if let Some(source) = &line.source {
+            self.record_source(source, &line.event);
+        }
+        let Ok(mut totals) = self.totals.lock() else {
+            return;
+        };
+        if let Some(source) = &line.source {
+            totals.sources.insert(source.clone());
+        }
+        match &line.event {
+            ProgressEvent::SourceResolved { .. } | ProgressEvent::SpectrogramsCreated { .. } => {}
+            ProgressEvent::SourceVerified => totals.verified += 1,
+            ProgressEvent::VerifyFailed { rules, .. } => {
+                totals.skipped += 1;
+                for rule in rules {
+                    *totals.rules.entry(rule.clone()).or_default() += 1;
+                }
+            }
+            ProgressEvent::TrackTranscoded { .. } => totals.tracks += 1,
+            ProgressEvent::TranscodeDone {
+                formats,
+                audio_seconds,
+                directories,
+                ..
+            } => {
+                totals.formats.extend(formats);
+                totals.audio_seconds += u64::from(*audio_seconds);
+                totals.directories.extend(directories.iter().cloned());
+            }
+            ProgressEvent::TorrentCreated { .. } => totals.torrents += 1,
+            ProgressEvent::UploadDone { indexer, .. } => {
+                totals.uploads += 1;
+                if indexer.is_some() {
+                    totals.cross_posts += 1;
+                }
+            }
+            ProgressEvent::CrossPostSkipped { .. } => totals.skipped_cross_posts += 1,
+        }
+    }
+
+    fn record_source(&self, id: &str, event: &ProgressEvent) {
+        let Ok(mut sources) = self.sources.lock() else {
+            return;
+        };
+        let index = if let Some(index) = sources.iter().position(|x| x.id == id) {
+            index
+        } else {
+            sources.push(SourceResult {
+                id: id.to_owned(),
+                ..SourceResult::default()
+            });
+            sources.len() - 1
+        };
+        let Some(source) = sources.get_mut(index) else {
+            return;
+        };
+        match event {
+            ProgressEvent::SourceResolved { name, directory } => {
+                source.name = Some(name.clone());
+                source.directory = Some(directory.clone());
+            }
+            ProgressEvent::SourceVerified => source.verified = Some(true),
+            ProgressEvent::VerifyFailed { rules, issues } => {
+                source.verified = Some(false);
+                source.rules.clone_from(rules);
+                source.issues.clone_from(issues);
+            }
+            ProgressEvent::SpectrogramsCreated { path, .. } => {
+                source.spectrograms = Some(path.clone());
+            }
+            ProgressEvent::TrackTranscoded { .. } | ProgressEvent::CrossPostSkipped { .. } => {}
+            ProgressEvent::TranscodeDone { directories, .. } => {
+                source.transcodes.extend(directories.iter().cloned());
+            }
+            ProgressEvent::TorrentCreated { path, .. } => source.torrents.push(path.clone()),
+            ProgressEvent::UploadDone {
+                format,
+                id,
+                url,
+                indexer,
+            } => source.uploads.push(UploadResult {
+                format: *format,
+                id: *id,
+                url: url.clone(),
+                indexer: indexer.clone(),
+            }),
+        }
+    }
+
+    /// Should the result be written as JSON rather than as text?
+    #[must_use]
+    pub fn is_json(&self) -> bool {
+        self.format == ResultFormat::Json
+    }
+
+    /// Set the data of a command that reports data rather than processing sources.
+    pub fn set_data<T: Serialize>(&self, value: &T) {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(error) => {
+                warn!("{} to serialize result: {error}", "Failed".bold());
+                return;
+            }
+        };
+        if let Ok(mut data) = self.data.lock() {
+            *data = Some(value);
+        }
+    }
+
+    /// Get the [`CommandResult`] of a command.
+    #[must_use]
+    pub fn get_result(&self, command: &str, result: &Result<bool, Error>) -> CommandResult {
+        let (success, error) = match result {
+            Ok(success) => (*success, None),
+            Err(error) => (false, Some(error)),
+        };
+        CommandResult {
+            command: command.to_owned(),
+            success,
+            error: error.map(|e| e.message.clone()),
+            error_code: error.map(|e| ErrorCode::from_error(e).get_code().to_owned()),
+            elapsed_seconds: self.get_elapsed().as_secs(),
+            sources: self.sources.lock().map(|x| x.clone()).unwrap_or_default(),
+            data: self.data.lock().ok().and_then(|x| x.clone()),
+        }
+    }
+
+    /// Write the [`CommandResult`] to stdout as a single line of JSON if
+    /// `result_format` is `json`.
+    pub fn write_result(&self, command: &str, result: &Result<bool, Error>) {
+        if !self.is_json() {
+            return;
+        }
+        match serde_json::to_string(&self.get_result(command, result)) {
+            Ok(json) => println!("{json}"),
+            Err(error) => warn!("{} to serialize result: {error}", "Failed".bold()),
+        }
+    }
+
+    /// Get the lines of the summary.
+    ///
+    /// Returns an empty [`Vec`] if no sources were processed.
+    #[must_use]
+    #[allow(clippy::integer_division)]
+    pub fn get_lines(&self) -> Vec<String> {
+        let Ok(totals) = self.totals.lock() else {
+            return Vec::new();
+        };
+        if totals.sources.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![format!(
+            "{}: {} processed, {} verified, {} skipped",
+            "Sources".bold(),
+            totals.sources.len(),
+            totals.verified,
+            totals.skipped
+        )];
+        if !totals.rules.is_empty() {
+            let mut rules: Vec<(&String, &usize)> = totals.rules.iter().collect();
+            rules.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            let rules: Vec<String> = rules
+                .into_iter()
+                .take(TOP_RULES)
+                .map(|(rule, count)| format!("{count} {rule}"))
+                .collect();
+            lines.push(format!(
+                "{}: {}",
+                "Top failing rules".bold(),
+                rules.join(", ")
+            ));
+        }
+        if totals.tracks > 0 {
+            lines.push(format!(
+                "{}: {} tracks to {} with {} minutes of audio",
+                "Transcoded".bold(),
+                totals.tracks,
+                join_humanized(&totals.formats),
+                totals.audio_seconds / 60
+            ));
+        }
+        if totals.torrents > 0 || totals.uploads > 0 {
+            let cross_posts = if totals.cross_posts > 0 {
+                format!(" of which {} cross-posted", totals.cross_posts)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "{}: {} created, {} uploaded{cross_posts}",
+                "Torrents".bold(),
+                totals.torrents,
+                totals.uploads
+            ));
+        }
+        if totals.skipped_cross_posts > 0 {
+            lines.push(format!(
+                "{}: {} formats were not transcoded as the indexer of the source has them",
+                "Skipped cross-posts".bold(),
+                totals.skipped_cross_posts
+            ));
+        }
+        let elapsed = self.get_elapsed();
+        lines.push(format!(
+            "{}: {}",
+            "Elapsed".bold(),
+            humantime::format_duration(elapsed)
+        ));
+        if !totals.directories.is_empty() {
+            if let Some(output) = &self.output {
+                lines.push(format!("{}: {}", "Output".bold(), output.display()));
+            }
+        }
+        lines
+    }
+
+    /// Get the time since the command started, rounded down to whole seconds.
+    #[must_use]
+    pub fn get_elapsed(&self) -> Duration {
+        Duration::from_secs(self.start.elapsed().as_secs())
+    }
+
+    /// Log the summary if any sources were processed.
+    pub fn show(&self) {
+        let lines = self.get_lines();
+        if lines.is_empty() {
+            return;
+        }
+        info!("{}", "Summary".bold());
+        for line in lines {
+            info!("  {line}");
+        }
+        if let Ok(totals) = self.totals.lock() {
+            for directory in &totals.directories {
+                debug!("  {}", directory.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::formats::TargetFormat;
+
+/// Result of processing a source, collated from each [`ProgressEvent`].
+///
+/// [`ProgressEvent`]: crate::progress::ProgressEvent
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SourceResult {
+    /// Id of the source.
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+    /// Is the source suitable for transcoding?
+    ///
+    /// `None` if the source was not verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    /// Type of each issue that failed verification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+    /// Description of each issue that failed verification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
+    /// Directory of the created spectrograms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spectrograms: Option<PathBuf>,
+    /// Directory of each transcode.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transcodes: Vec<PathBuf>,
+    /// Path of each created `.torrent` file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub torrents: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uploads: Vec<UploadResult>,
+}
+
+/// A target format uploaded by [`UploadCommand`](crate::upload::UploadCommand).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UploadResult {
+    pub format: TargetFormat,
+    /// Id of the uploaded torrent.
+    pub id: u32,
+    pub url: String,
+    /// Indexer the format was cross-posted to, or `None` for the indexer of the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexer: Option<String>,
+}
@@ -0,0 +1,2 @@
+mod run_summary_tests;
+mod timings_tests;
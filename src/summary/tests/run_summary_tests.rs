@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+
+use colored::control::SHOULD_COLORIZE;
+use di::Ref;
+
+use crate::errors::error;
+use crate::formats::TargetFormat;
+use crate::options::SharedOptions;
+use crate::progress::{ProgressEvent, ProgressLine};
+use crate::summary::{ResultFormat, RunSummary, SourceResult, UploadResult};
+
+fn get_line(source: &str, event: ProgressEvent) -> ProgressLine {
+    ProgressLine {
+        timestamp: String::new(),
+        source: Some(source.to_owned()),
+        event,
+    }
+}
+
+fn get_failed(rules: &[&str]) -> ProgressEvent {
+    ProgressEvent::VerifyFailed {
+        rules: rules.iter().map(ToString::to_string).collect(),
+        issues: Vec::new(),
+    }
+}
+
+#[test]
+fn run_summary_get_lines() {
+    // Arrange
+    SHOULD_COLORIZE.set_override(false);
+    let summary = RunSummary::new(Ref::new(SharedOptions {
+        output: Some(PathBuf::from("/srv/output")),
+        ..SharedOptions::default()
+    }));
+    let lines = vec![
+        get_line("1", ProgressEvent::SourceVerified),
+        get_line("2", get_failed(&["scene", "missing_tags"])),
+        get_line("3", get_failed(&["missing_tags", "length"])),
+        get_line("4", get_failed(&["category"])),
+        get_line(
+            "1",
+            ProgressEvent::TrackTranscoded {
+                format: TargetFormat::_320,
+                path: PathBuf::from("/srv/output/a/01.mp3"),
+            },
+        ),
+        get_line(
+            "1",
+            ProgressEvent::TrackTranscoded {
+                format: TargetFormat::V0,
+                path: PathBuf::from("/srv/output/b/01.mp3"),
+            },
+        ),
+        get_line(
+            "1",
+            ProgressEvent::TranscodeDone {
+                formats: vec![TargetFormat::_320, TargetFormat::V0],
+                audio_seconds: 150,
+                elapsed_seconds: 12.5,
+                directories: vec![
+                    PathBuf::from("/srv/output/a"),
+                    PathBuf::from("/srv/output/b"),
+                ],
+            },
+        ),
+    ];
+
+    // Act
+    for line in &lines {
+        summary.record(line);
+    }
+    let output = summary.get_lines();
+
+    // Assert
+    let (elapsed, output): (Vec<String>, Vec<String>) =
+        output.into_iter().partition(|x| x.starts_with("Elapsed: "));
+    assert_eq!(elapsed.len(), 1);
+    assert_eq!(
+        output,
+        vec![
+            "Sources: 4 processed, 1 verified, 3 skipped",
+            "Top failing rules: 2 missing_tags, 1 category, 1 length",
+            "Transcoded: 2 tracks to 320 & V0 with 2 minutes of audio",
+            "Output: /srv/output",
+        ]
+    );
+}
+
+#[test]
+fn run_summary_get_lines_skipped_cross_posts() {
+    // Arrange
+    SHOULD_COLORIZE.set_override(false);
+    let summary = RunSummary::new(Ref::new(SharedOptions::default()));
+    let line = get_line(
+        "1",
+        ProgressEvent::CrossPostSkipped {
+            format: TargetFormat::V0,
+            indexer: "ops".to_owned(),
+        },
+    );
+
+    // Act
+    summary.record(&line);
+    let output = summary.get_lines();
+
+    // Assert
+    assert!(output.contains(
+        &"Skipped cross-posts: 1 formats were not transcoded as the indexer of the source has them"
+            .to_owned()
+    ));
+}
+
+#[test]
+fn run_summary_without_sources() {
+    // Arrange
+    let summary = RunSummary::new(Ref::new(SharedOptions::default()));
+
+    // Act
+    let output = summary.get_lines();
+
+    // Assert
+    assert!(output.is_empty());
+}
+
+#[test]
+fn run_summary_get_result() {
+    // Arrange
+    let summary = RunSummary::new(Ref::new(SharedOptions {
+        result_format: Some(ResultFormat::Json),
+        ..SharedOptions::default()
+    }));
+    let lines = vec![
+        get_line(
+            "2",
+            ProgressEvent::SourceResolved {
+                name: "Artist - Album".to_owned(),
+                directory: PathBuf::from("/srv/content/a"),
+            },
+        ),
+        get_line("2", ProgressEvent::SourceVerified),
+        get_line(
+            "2",
+            ProgressEvent::SpectrogramsCreated {
+                count: 2,
+                path: PathBuf::from("/srv/output/a [spectrograms]"),
+            },
+        ),
+        get_line(
+            "2",
+            ProgressEvent::TorrentCreated {
+                format: TargetFormat::V0,
+                path: PathBuf::from("/srv/output/a.torrent"),
+            },
+        ),
+        get_line(
+            "2",
+            ProgressEvent::UploadDone {
+                format: TargetFormat::V0,
+                id: 123,
+                url: "https://example.com/torrents.php?torrentid=123".to_owned(),
+                indexer: None,
+            },
+        ),
+        get_line("1", get_failed(&["scene"])),
+    ];
+
+    // Act
+    for line in &lines {
+        summary.record(line);
+    }
+    let result = summary.get_result("batch", &Ok(true));
+
+    // Assert
+    assert!(summary.is_json());
+    assert_eq!(result.command, "batch");
+    assert!(result.success);
+    assert_eq!(result.error_code, None);
+    assert_eq!(
+        result.sources,
+        vec![
+            SourceResult {
+                id: "2".to_owned(),
+                name: Some("Artist - Album".to_owned()),
+                directory: Some(PathBuf::from("/srv/content/a")),
+                verified: Some(true),
+                spectrograms: Some(PathBuf::from("/srv/output/a [spectrograms]")),
+                torrents: vec![PathBuf::from("/srv/output/a.torrent")],
+                uploads: vec![UploadResult {
+                    format: TargetFormat::V0,
+                    id: 123,
+                    url: "https://example.com/torrents.php?torrentid=123".to_owned(),
+                    indexer: None,
+                }],
+                ..SourceResult::default()
+            },
+            SourceResult {
+                id: "1".to_owned(),
+                verified: Some(false),
+                rules: vec!["scene".to_owned()],
+                ..SourceResult::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn run_summary_get_result_error() {
+    // Arrange
+    let summary = RunSummary::new(Ref::new(SharedOptions::default()));
+    summary.set_data(&vec!["a", "b"]);
+
+    // Act
+    let result = summary.get_result(
+        "history",
+        &Err(error("get history", "Not found".to_owned())),
+    );
+    let json = serde_json::to_string(&result).expect("result should serialize");
+
+    // Assert
+    assert!(!summary.is_json());
+    assert!(!result.success);
+    assert_eq!(result.error.as_deref(), Some("Not found"));
+    assert_eq!(result.error_code.as_deref(), Some("E900"));
+    assert!(json.contains(r#""sources":[],"data":["a","b"]"#));
+}
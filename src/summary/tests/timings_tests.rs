@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use colored::control::SHOULD_COLORIZE;
+use di::Ref;
+
+use crate::formats::TargetFormat;
+use crate::options::SharedOptions;
+use crate::summary::{TimingPhase, Timings};
+
+#[test]
+fn timings_get_lines() {
+    // Arrange
+    SHOULD_COLORIZE.set_override(false);
+    let timings = Timings::new(Ref::new(SharedOptions {
+        timings: Some(true),
+        ..SharedOptions::default()
+    }));
+    timings.record(TimingPhase::Api, Duration::from_millis(250));
+    timings.record(TimingPhase::Api, Duration::from_millis(500));
+    timings.record(TimingPhase::HashCheck, Duration::from_secs(2));
+    timings.record(
+        TimingPhase::Encode(TargetFormat::V0),
+        Duration::from_secs(3),
+    );
+    timings.record_track(
+        TargetFormat::V0,
+        "01 Intro.mp3".to_owned(),
+        Duration::from_secs(1),
+    );
+    timings.record_track(
+        TargetFormat::V0,
+        "02 Fear Not.mp3".to_owned(),
+        Duration::from_secs(2),
+    );
+
+    // Act
+    let lines = timings.get_lines();
+
+    // Assert
+    assert_eq!(
+        lines,
+        vec![
+            "API: 2 in 0.750s",
+            "Hash check: 1 in 2.000s",
+            "Encode V0: 1 in 3.000s",
+            "Slowest tracks: V0 02 Fear Not.mp3 in 2.000s, V0 01 Intro.mp3 in 1.000s",
+        ]
+    );
+}
+
+#[test]
+fn timings_disabled() {
+    // Arrange
+    let timings = Timings::new(Ref::new(SharedOptions::default()));
+
+    // Act
+    timings.record(TimingPhase::Api, Duration::from_secs(1));
+
+    // Assert
+    assert!(timings.get_lines().is_empty());
+}
@@ -0,0 +1,187 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info};
+
+use crate::formats::TargetFormat;
+use crate::options::SharedOptions;
+
+/// Maximum number of tracks to include in the breakdown.
+const SLOWEST_TRACKS: usize = 5;
+
+/// A phase of a command measured by [`Timings`].
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TimingPhase {
+    /// Requests to the API.
+    Api,
+    /// Checks of the verify rules other than the hash check.
+    Verify,
+    /// Check of the source against the hashes of its torrent.
+    HashCheck,
+    /// Decoding the source FLAC files.
+    Decode,
+    /// Encoding to a target format.
+    Encode(TargetFormat),
+    /// Creating torrent files.
+    Torrent,
+}
+
+impl Display for TimingPhase {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimingPhase::Api => write!(formatter, "API"),
+            TimingPhase::Verify => write!(formatter, "Verify rules"),
+            TimingPhase::HashCheck => write!(formatter, "Hash check"),
+            TimingPhase::Decode => write!(formatter, "Decode"),
+            TimingPhase::Encode(format) => write!(formatter, "Encode {format}"),
+            TimingPhase::Torrent => write!(formatter, "Torrent creation"),
+        }
+    }
+}
+
+/// Wall time spent in each [`TimingPhase`] and on each track.
+///
+/// Only recorded if `timings` is set so the breakdown can be shown at the end of a command.
+pub struct Timings {
+    enabled: bool,
+    state: Mutex<TimingsState>,
+}
+
+#[derive(Debug, Default)]
+struct TimingsState {
+    phases: BTreeMap<TimingPhase, PhaseTotal>,
+    tracks: Vec<TrackTiming>,
+}
+
+/// Total wall time of a [`TimingPhase`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PhaseTotal {
+    /// Number of times the phase was recorded.
+    pub count: usize,
+    /// Sum of the wall time of each time the phase was recorded.
+    pub elapsed: Duration,
+}
+
+/// Wall time to transcode a track to a target format.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackTiming {
+    pub format: TargetFormat,
+    pub name: String,
+    pub elapsed: Duration,
+}
+
+#[injectable]
+impl Timings {
+    #[must_use]
+    pub fn new(options: Ref<SharedOptions>) -> Self {
+        Self {
+            enabled: options.timings.unwrap_or_default(),
+            state: Mutex::new(TimingsState::default()),
+        }
+    }
+
+    /// Add the wall time of a [`TimingPhase`].
+    pub fn record(&self, phase: TimingPhase, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let total = state.phases.entry(phase).or_default();
+        total.count += 1;
+        total.elapsed += elapsed;
+    }
+
+    /// Add the wall time of transcoding a track.
+    pub fn record_track(&self, format: TargetFormat, name: String, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.tracks.push(TrackTiming {
+            format,
+            name,
+            elapsed,
+        });
+    }
+
+    /// Get the lines of the breakdown.
+    ///
+    /// Returns an empty [`Vec`] if nothing was recorded.
+    #[must_use]
+    pub fn get_lines(&self) -> Vec<String> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut lines: Vec<String> = state
+            .phases
+            .iter()
+            .map(|(phase, total)| {
+                format!(
+                    "{}: {} in {}",
+                    phase.to_string().bold(),
+                    total.count,
+                    format_duration(total.elapsed)
+                )
+            })
+            .collect();
+        if !state.tracks.is_empty() {
+            let mut tracks: Vec<&TrackTiming> = state.tracks.iter().collect();
+            tracks.sort_by_key(|track| Reverse(track.elapsed));
+            let tracks: Vec<String> = tracks
+                .into_iter()
+                .take(SLOWEST_TRACKS)
+                .map(|track| {
+                    format!(
+                        "{} {} in {}",
+                        track.format,
+                        track.name,
+                        format_duration(track.elapsed)
+                    )
+                })
+                .collect();
+            lines.push(format!(
+                "{}: {}",
+                "Slowest tracks".bold(),
+                tracks.join(", ")
+            ));
+        }
+        lines
+    }
+
+    /// Log the breakdown if `timings` is set.
+    pub fn show(&self) {
+        let lines = self.get_lines();
+        if lines.is_empty() {
+            return;
+        }
+        info!("{}", "Timings".bold());
+        for line in lines {
+            info!("  {line}");
+        }
+        if let Ok(state) = self.state.lock() {
+            for track in &state.tracks {
+                debug!(
+                    "  {} {} in {}",
+                    track.format,
+                    track.name,
+                    format_duration(track.elapsed)
+                );
+            }
+        }
+    }
+}
+
+/// Format a duration in seconds with millisecond precision.
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
@@ -0,0 +1,145 @@
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, trace};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::db::Hash;
+use crate::errors::{error, path_error, yaml_error};
+use crate::options::init_command::get_index;
+use crate::options::{CacheOptions, SharedOptions};
+
+/// Permission mode of the cache file so the passkey is only readable by the owner.
+const CACHE_MODE: u32 = 0o600;
+
+/// Cached announce URL fetched from the index endpoint of an indexer.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct AnnounceCacheEntry {
+    /// ID of the indexer the announce URL is for.
+    pub indexer: String,
+    /// SHA-256 hash of the API key used to fetch the announce URL.
+    pub api_key_hash: String,
+    /// Announce URL including passkey.
+    pub announce_url: String,
+}
+
+/// Get the announce URL of created torrents.
+///
+/// If `announce_url` isn't set then the passkey is fetched from the index endpoint
+/// of the indexer and cached so it's only fetched once.
+#[injectable]
+pub struct AnnounceProvider {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+}
+
+impl AnnounceProvider {
+    /// Get the announce URL including passkey.
+    pub async fn get(&self) -> Result<String, Error> {
+        if let Some(announce_url) = &self.shared_options.announce_url {
+            return Ok(announce_url.clone());
+        }
+        let action = "get announce URL";
+        let indexer = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        let api_key_hash = get_api_key_hash(
+            self.shared_options
+                .api_key
+                .as_deref()
+                .expect("api_key should be set"),
+        );
+        let path = self.get_cache_path();
+        if let Some(entry) = read_entry(&path) {
+            if entry.indexer == indexer && entry.api_key_hash == api_key_hash {
+                trace!("{} cached announce URL", "Using".bold());
+                return Ok(entry.announce_url);
+            }
+        }
+        let index = get_index(&self.shared_options, action).await?;
+        let announce_url = get_announce_url(&indexer, &index)?;
+        debug!("{} announce URL from the {indexer} API", "Fetched".bold());
+        let entry = AnnounceCacheEntry {
+            indexer,
+            api_key_hash,
+            announce_url: announce_url.clone(),
+        };
+        write_entry(&path, &entry)?;
+        Ok(announce_url)
+    }
+
+    fn get_cache_path(&self) -> PathBuf {
+        self.cache_options
+            .cache
+            .clone()
+            .expect("cache should be set")
+            .join("announce.yml")
+    }
+}
+
+/// Get the tracker URL of an indexer that the passkey is appended to.
+#[must_use]
+pub fn get_tracker_url(indexer: &str) -> Option<&'static str> {
+    match indexer {
+        "red" => Some("https://flacsfor.me"),
+        "ops" => Some("https://home.opsfet.ch"),
+        _ => None,
+    }
+}
+
+/// Get the announce URL from the response of the index endpoint.
+pub fn get_announce_url(indexer: &str, index: &Value) -> Result<String, Error> {
+    let action = "get announce URL";
+    let tracker_url = get_tracker_url(indexer).ok_or_else(|| {
+        error(
+            action,
+            format!("The tracker of {indexer} is unknown so announce_url must be set"),
+        )
+    })?;
+    let passkey = index
+        .pointer("/response/passkey")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if passkey.is_empty() || !passkey.chars().all(|x| x.is_ascii_alphanumeric()) {
+        return Err(error(
+            action,
+            format!("The {indexer} API did not respond with a valid passkey"),
+        ));
+    }
+    Ok(format!("{tracker_url}/{passkey}/announce"))
+}
+
+fn get_api_key_hash(api_key: &str) -> String {
+    Hash::<32>::new(Sha256::digest(api_key.as_bytes()).into()).to_hex()
+}
+
+fn read_entry(path: &Path) -> Option<AnnounceCacheEntry> {
+    let file = File::open(path).ok()?;
+    serde_yaml::from_reader(BufReader::new(file)).ok()
+}
+
+fn write_entry(path: &Path, entry: &AnnounceCacheEntry) -> Result<(), Error> {
+    let action = "write announce URL cache";
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| path_error(e, action, parent))?;
+    }
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(CACHE_MODE)
+        .open(path)
+        .map_err(|e| path_error(e, action, path))?;
+    let mut writer = BufWriter::new(file);
+    serde_yaml::to_writer(&mut writer, entry).map_err(|e| yaml_error(e, action))?;
+    writer.flush().map_err(|e| error(action, e.to_string()))
+}
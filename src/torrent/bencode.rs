@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+
+/// Deepest nesting of lists and dictionaries that is decoded.
+///
+/// A `.torrent` file is only nested a few levels deep, so this prevents a crafted file
+/// from exhausting the stack.
+const MAX_DEPTH: usize = 64;
+
+/// A bencoded value as used by `.torrent` files.
+///
+/// <https://www.bittorrent.org/beps/bep_0003.html#bencoding>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Bencode {
+    Integer(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Bencode>),
+    Dictionary(BTreeMap<Vec<u8>, Bencode>),
+}
+
+impl Bencode {
+    /// Decode a bencoded value.
+    ///
+    /// Returns a description of the problem if the bytes are not a single bencoded value.
+    pub fn decode(bytes: &[u8]) -> Result<Bencode, String> {
+        let mut decoder = Decoder {
+            bytes,
+            position: 0,
+            depth: 0,
+        };
+        let value = decoder.decode_value()?;
+        if decoder.position != bytes.len() {
+            return Err(format!(
+                "Unexpected data after the value at byte {}",
+                decoder.position
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Get the raw bytes of a value in a bencoded dictionary.
+    ///
+    /// The info hash of a torrent is the hash of the raw bytes of the `info` dictionary
+    /// so it must not be re-encoded.
+    pub fn get_raw_value<'a>(bytes: &'a [u8], key: &str) -> Result<Option<&'a [u8]>, String> {
+        let mut decoder = Decoder {
+            bytes,
+            position: 0,
+            depth: 0,
+        };
+        decoder.expect(b'd')?;
+        while decoder.peek()? != b'e' {
+            let name = decoder.decode_bytes()?;
+            let start = decoder.position;
+            decoder.decode_value()?;
+            if name == key.as_bytes() {
+                return Ok(bytes.get(start..decoder.position));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Encode the value.
+    ///
+    /// Dictionary keys are sorted as required by the specification.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.encode_to(&mut output);
+        output
+    }
+
+    fn encode_to(&self, output: &mut Vec<u8>) {
+        match self {
+            Bencode::Integer(value) => {
+                output.push(b'i');
+                output.extend_from_slice(value.to_string().as_bytes());
+                output.push(b'e');
+            }
+            Bencode::Bytes(value) => encode_bytes(value, output),
+            Bencode::List(values) => {
+                output.push(b'l');
+                for value in values {
+                    value.encode_to(output);
+                }
+                output.push(b'e');
+            }
+            Bencode::Dictionary(values) => {
+                output.push(b'd');
+                for (key, value) in values {
+                    encode_bytes(key, output);
+                    value.encode_to(output);
+                }
+                output.push(b'e');
+            }
+        }
+    }
+
+    /// Get a value of a dictionary by key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Bencode> {
+        match self {
+            Bencode::Dictionary(values) => values.get(key.as_bytes()),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Bencode::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Bencode::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a UTF-8 string, replacing any invalid sequences.
+    #[must_use]
+    pub fn as_string(&self) -> Option<String> {
+        self.as_bytes()
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+    }
+
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[Bencode]> {
+        match self {
+            Bencode::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Create a [`Bencode::Bytes`] from a string.
+    #[must_use]
+    pub fn string(value: &str) -> Bencode {
+        Bencode::Bytes(value.as_bytes().to_vec())
+    }
+}
+
+fn encode_bytes(value: &[u8], output: &mut Vec<u8>) {
+    output.extend_from_slice(value.len().to_string().as_bytes());
+    output.push(b':');
+    output.extend_from_slice(value);
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// Number of lists and dictionaries the current value is inside.
+    depth: usize,
+}
+
+impl Decoder<'_> {
+    fn peek(&self) -> Result<u8, String> {
+        self.bytes
+            .get(self.position)
+            .copied()
+            .ok_or_else(|| "Unexpected end of data".to_owned())
+    }
+
+    fn next(&mut self) -> Result<u8, String> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        let position = self.position;
+        let byte = self.next()?;
+        if byte == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected `{}` at byte {position} but found `{}`",
+                char::from(expected),
+                char::from(byte)
+            ))
+        }
+    }
+
+    fn decode_value(&mut self) -> Result<Bencode, String> {
+        match self.peek()? {
+            b'i' => self.decode_integer().map(Bencode::Integer),
+            b'l' => {
+                self.enter()?;
+                let mut values = Vec::new();
+                while self.peek()? != b'e' {
+                    values.push(self.decode_value()?);
+                }
+                self.leave();
+                Ok(Bencode::List(values))
+            }
+            b'd' => {
+                self.enter()?;
+                let mut values = BTreeMap::new();
+                while self.peek()? != b'e' {
+                    let key = self.decode_bytes()?;
+                    let value = self.decode_value()?;
+                    values.insert(key, value);
+                }
+                self.leave();
+                Ok(Bencode::Dictionary(values))
+            }
+            b'0'..=b'9' => self.decode_bytes().map(Bencode::Bytes),
+            byte => Err(format!(
+                "Unexpected `{}` at byte {}",
+                char::from(byte),
+                self.position
+            )),
+        }
+    }
+
+    /// Consume the start of a list or dictionary.
+    fn enter(&mut self) -> Result<(), String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!(
+                "Nested deeper than {MAX_DEPTH} levels at byte {}",
+                self.position
+            ));
+        }
+        self.depth += 1;
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Consume the end of a list or dictionary.
+    fn leave(&mut self) {
+        self.depth -= 1;
+        self.position += 1;
+    }
+
+    fn decode_integer(&mut self) -> Result<i64, String> {
+        self.expect(b'i')?;
+        let digits = self.read_until(b'e')?;
+        digits
+            .parse()
+            .map_err(|_| format!("Invalid integer: {digits}"))
+    }
+
+    fn decode_bytes(&mut self) -> Result<Vec<u8>, String> {
+        let digits = self.read_until(b':')?;
+        let length: usize = digits
+            .parse()
+            .map_err(|_| format!("Invalid length: {digits}"))?;
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("Length {length} exceeds the end of data"))?;
+        let value = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| "Unexpected end of data".to_owned())?
+            .to_vec();
+        self.position = end;
+        Ok(value)
+    }
+
+    /// Read the ASCII characters up to, and consume, the terminator.
+    fn read_until(&mut self, terminator: u8) -> Result<String, String> {
+        let start = self.position;
+        while self.peek()? != terminator {
+            self.position += 1;
+        }
+        let value = self.bytes.get(start..self.position).unwrap_or_default();
+        self.position += 1;
+        Ok(String::from_utf8_lossy(value).into_owned())
+    }
+}
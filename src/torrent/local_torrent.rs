@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use log::trace;
+use tokio::fs::read_dir;
+
+use crate::source::get_torrent_id_from_torrent_url;
+use crate::torrent::TorrentFile;
+
+/// Find a local `.torrent` file of a torrent id.
+///
+/// A file matches if the torrent url of its comment has the id and its source is the
+/// indexer. Each of `files` is checked first, then every `.torrent` file of each of
+/// `dirs`.
+///
+/// Returns `None` if no file matches.
+pub async fn find_local_torrent(
+    id: u32,
+    indexer: &str,
+    files: &[PathBuf],
+    dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    for file in files {
+        if is_match(file, id, indexer).await {
+            return Some(file.clone());
+        }
+    }
+    for dir in dirs {
+        let Ok(mut entries) = read_dir(dir).await else {
+            trace!("Failed to read torrent directory: {}", dir.display());
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let file = entry.path();
+            if file.extension().is_some_and(|x| x == "torrent")
+                && is_match(&file, id, indexer).await
+            {
+                return Some(file);
+            }
+        }
+    }
+    None
+}
+
+/// Is the `.torrent` file of the torrent id on the indexer?
+///
+/// A torrent without a source is assumed to be of the indexer.
+async fn is_match(path: &Path, id: u32, indexer: &str) -> bool {
+    let Ok(torrent) = TorrentFile::read(path).await else {
+        return false;
+    };
+    let is_indexer = torrent
+        .info
+        .source
+        .as_deref()
+        .is_none_or(|source| source.eq_ignore_ascii_case(indexer));
+    let torrent_id = torrent
+        .comment
+        .as_deref()
+        .and_then(get_torrent_id_from_torrent_url);
+    is_indexer && torrent_id == Some(id)
+}
@@ -0,0 +1,145 @@
+use std::io::{Error, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::torrent::ReadAhead;
+
+/// Length of a SHA-256 hash of a block or merkle node.
+pub const MERKLE_HASH_LENGTH: usize = 32;
+
+/// Length of the blocks that are the leaves of a v2 merkle tree.
+pub const BLOCK_LENGTH: u64 = 16 * 1024;
+
+/// A SHA-256 hash of a block or merkle node.
+pub type MerkleHash = [u8; MERKLE_HASH_LENGTH];
+
+/// Merkle tree hashes of a file in a v2 torrent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleHashes {
+    /// Root of the merkle tree of the file.
+    pub root: MerkleHash,
+    /// Hash of each piece of the file.
+    ///
+    /// Only included in the torrent if the file is larger than one piece.
+    pub piece_layer: Vec<MerkleHash>,
+}
+
+/// Hash a file as a v2 merkle tree of 16 KiB blocks.
+///
+/// <https://www.bittorrent.org/beps/bep_0052.html#merkle-tree>
+pub struct MerkleHasher;
+
+impl MerkleHasher {
+    /// Hash a file.
+    ///
+    /// Returns `None` for an empty file as it does not have a merkle tree.
+    ///
+    /// This is blocking so should be called from a blocking task.
+    #[allow(dead_code)]
+    pub fn execute(path: &Path, piece_length: u64) -> Result<Option<MerkleHashes>, Error> {
+        let mut hashes = Self::execute_files(vec![path.to_path_buf()], piece_length)?;
+        Ok(hashes.pop().flatten())
+    }
+
+    /// Hash each file in order.
+    ///
+    /// The files are read by [`ReadAhead`] so the next file is read while the previous is
+    /// hashed.
+    ///
+    /// This is blocking so should be called from a blocking task.
+    pub fn execute_files(
+        paths: Vec<PathBuf>,
+        piece_length: u64,
+    ) -> Result<Vec<Option<MerkleHashes>>, Error> {
+        if piece_length < BLOCK_LENGTH || !piece_length.is_power_of_two() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Piece length must be a power of two of at least 16 KiB",
+            ));
+        }
+        let blocks_per_piece =
+            usize::try_from(piece_length.checked_div(BLOCK_LENGTH).unwrap_or_default())
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Piece length is too large"))?;
+        let count = paths.len();
+        let mut reader = ReadAhead::open(paths);
+        let mut hashes = Vec::new();
+        for index in 0..count {
+            if index > 0 {
+                reader.next_file()?;
+            }
+            let leaves = hash_blocks(&mut reader)?;
+            hashes.push(get_hashes(leaves, blocks_per_piece));
+        }
+        Ok(hashes)
+    }
+}
+
+/// Get the merkle tree hashes of a file from the hash of each block.
+fn get_hashes(leaves: Vec<MerkleHash>, blocks_per_piece: usize) -> Option<MerkleHashes> {
+    if leaves.is_empty() {
+        return None;
+    }
+    if leaves.len() <= blocks_per_piece {
+        let root = get_root(leaves, [0; MERKLE_HASH_LENGTH]);
+        return Some(MerkleHashes {
+            root,
+            piece_layer: vec![root],
+        });
+    }
+    let piece_layer: Vec<MerkleHash> = leaves
+        .chunks(blocks_per_piece)
+        .map(|chunk| {
+            let mut chunk = chunk.to_vec();
+            chunk.resize(blocks_per_piece, [0; MERKLE_HASH_LENGTH]);
+            get_root(chunk, [0; MERKLE_HASH_LENGTH])
+        })
+        .collect();
+    let padding = get_root(
+        vec![[0; MERKLE_HASH_LENGTH]; blocks_per_piece],
+        [0; MERKLE_HASH_LENGTH],
+    );
+    let root = get_root(piece_layer.clone(), padding);
+    Some(MerkleHashes { root, piece_layer })
+}
+
+/// Hash each 16 KiB block of the current file of the reader.
+fn hash_blocks(reader: &mut ReadAhead) -> Result<Vec<MerkleHash>, Error> {
+    let mut leaves = Vec::new();
+    let mut buffer = vec![0; 16 * 1024];
+    loop {
+        let mut filled = 0;
+        while let Some(remaining) = buffer.get_mut(filled..) {
+            let read = reader.read(remaining)?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        if filled == 0 {
+            break;
+        }
+        leaves.push(Sha256::digest(buffer.get(..filled).unwrap_or_default()).into());
+    }
+    Ok(leaves)
+}
+
+/// Get the root of a merkle tree.
+///
+/// The layer is padded with `padding` to a power of two.
+fn get_root(mut layer: Vec<MerkleHash>, padding: MerkleHash) -> MerkleHash {
+    layer.resize(layer.len().next_power_of_two(), padding);
+    while layer.len() > 1 {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                for node in pair {
+                    hasher.update(node);
+                }
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+    layer.first().copied().unwrap_or(padding)
+}
@@ -0,0 +1,29 @@
+pub use announce_provider::*;
+pub use bencode::*;
+pub use local_torrent::*;
+pub use merkle_hasher::*;
+pub use piece_hasher::*;
+pub use piece_length::*;
+pub use read_ahead::*;
+pub use torrent_creator::*;
+pub use torrent_file::*;
+pub use torrent_info::*;
+pub use torrent_summary::*;
+pub use torrent_verifier::*;
+pub use torrent_version::*;
+
+pub(crate) mod announce_provider;
+pub(crate) mod bencode;
+pub(crate) mod local_torrent;
+pub(crate) mod merkle_hasher;
+pub(crate) mod piece_hasher;
+pub(crate) mod piece_length;
+pub(crate) mod read_ahead;
+#[cfg(test)]
+mod tests;
+pub(crate) mod torrent_creator;
+pub(crate) mod torrent_file;
+pub(crate) mod torrent_info;
+pub(crate) mod torrent_summary;
+pub(crate) mod torrent_verifier;
+pub(crate) mod torrent_version;
@@ -0,0 +1,129 @@
+use std::io::{Error, ErrorKind, Read};
+use std::mem::take;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, sync_channel, SyncSender};
+use std::sync::Mutex;
+use std::thread::scope;
+
+use crate::torrent::{get_hash, ReadAhead, PIECE_HASH_LENGTH};
+
+/// Most bytes of pieces that are read ahead of the hashing threads.
+const READ_AHEAD_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Hash the content of files as torrent pieces.
+///
+/// The files are treated as one continuous stream so a piece may span the end of one
+/// file and the start of the next.
+///
+/// If the pieces are aligned then each file except the last is padded with zeros to the
+/// end of its last piece, as required by hybrid torrents.
+///
+/// Files are read by [`ReadAhead`] and the pieces are hashed in parallel by a pool of
+/// threads.
+pub struct PieceHasher;
+
+impl PieceHasher {
+    /// Hash the files in order.
+    ///
+    /// This is blocking so should be called from a blocking task.
+    pub fn execute(
+        files: &[PathBuf],
+        piece_length: u64,
+        is_aligned: bool,
+    ) -> Result<Vec<[u8; PIECE_HASH_LENGTH]>, Error> {
+        let piece_length = usize::try_from(piece_length)
+            .ok()
+            .filter(|x| *x > 0)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Piece length is invalid"))?;
+        let threads = get_thread_count(piece_length);
+        let (piece_sender, piece_receiver) = sync_channel::<(usize, Vec<u8>)>(threads);
+        let piece_receiver = Mutex::new(piece_receiver);
+        let (hash_sender, hash_receiver) = channel();
+        let count = scope(|scope| {
+            for _ in 0..threads {
+                let hash_sender = hash_sender.clone();
+                let piece_receiver = &piece_receiver;
+                scope.spawn(move || loop {
+                    let piece = piece_receiver
+                        .lock()
+                        .expect("piece receiver should not be poisoned")
+                        .recv();
+                    let Ok((index, buffer)) = piece else {
+                        break;
+                    };
+                    if hash_sender.send((index, get_hash(&buffer))).is_err() {
+                        break;
+                    }
+                });
+            }
+            read_pieces(files, piece_length, is_aligned, piece_sender)
+        })?;
+        drop(hash_sender);
+        let mut pieces = vec![[0; PIECE_HASH_LENGTH]; count];
+        for (index, hash) in hash_receiver {
+            if let Some(piece) = pieces.get_mut(index) {
+                *piece = hash;
+            }
+        }
+        Ok(pieces)
+    }
+}
+
+/// Read the files as pieces and send each to be hashed.
+///
+/// Returns the number of pieces.
+fn read_pieces(
+    files: &[PathBuf],
+    piece_length: usize,
+    is_aligned: bool,
+    sender: SyncSender<(usize, Vec<u8>)>,
+) -> Result<usize, Error> {
+    let closed = || Error::other("Piece hashing threads stopped unexpectedly");
+    let mut count = 0;
+    let mut buffer = vec![0; piece_length];
+    let mut filled = 0;
+    let mut reader = ReadAhead::open(files.to_vec());
+    for index in 0..files.len() {
+        if index > 0 {
+            reader.next_file()?;
+        }
+        while let Some(remaining) = buffer.get_mut(filled..) {
+            let read = reader.read(remaining)?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+            if filled == piece_length {
+                let piece = take(&mut buffer);
+                sender.send((count, piece)).map_err(|_| closed())?;
+                buffer = vec![0; piece_length];
+                count += 1;
+                filled = 0;
+            }
+        }
+        let is_last = index + 1 == files.len();
+        if is_aligned && !is_last && filled > 0 {
+            // The remainder of the buffer is already zeroed
+            let piece = take(&mut buffer);
+            sender.send((count, piece)).map_err(|_| closed())?;
+            buffer = vec![0; piece_length];
+            count += 1;
+            filled = 0;
+        }
+    }
+    if filled > 0 {
+        buffer.truncate(filled);
+        sender.send((count, buffer)).map_err(|_| closed())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Get the number of hashing threads.
+///
+/// Limited by the CPU count and so the pieces in memory don't exceed [`READ_AHEAD_LENGTH`].
+fn get_thread_count(piece_length: usize) -> usize {
+    num_cpus::get()
+        .min(READ_AHEAD_LENGTH.checked_div(piece_length).unwrap_or(1))
+        .max(1)
+}
@@ -0,0 +1,92 @@
+/// Smallest piece length that torrents are created with by default.
+pub const MIN_PIECE_LENGTH: u64 = 16 * 1024;
+
+/// Largest piece length that torrents are created with by default.
+pub const MAX_PIECE_LENGTH: u64 = 16 * 1024 * 1024;
+
+/// Rules to choose the piece length of a created torrent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PieceLength {
+    /// Piece length to use regardless of the content size.
+    pub fixed: Option<u64>,
+    /// Smallest piece length to choose automatically.
+    pub min: u64,
+    /// Largest piece length to choose automatically.
+    pub max: u64,
+    /// Most pieces a torrent should have.
+    ///
+    /// The piece length is doubled until the count is within the limit or `max` is reached.
+    pub max_count: Option<u64>,
+}
+
+impl Default for PieceLength {
+    fn default() -> Self {
+        Self {
+            fixed: None,
+            min: MIN_PIECE_LENGTH,
+            max: MAX_PIECE_LENGTH,
+            max_count: None,
+        }
+    }
+}
+
+impl PieceLength {
+    /// Get the piece length for content of the given size.
+    #[must_use]
+    pub fn get(&self, content_size: u64) -> u64 {
+        if let Some(fixed) = self.fixed {
+            return fixed;
+        }
+        let mut length = get_piece_length(content_size).clamp(self.min, self.max);
+        if let Some(max_count) = self.max_count {
+            while length < self.max && content_size.div_ceil(length) > max_count {
+                length = length.saturating_mul(2).min(self.max);
+            }
+        }
+        length
+    }
+}
+
+/// Get the default piece length for content of the given size.
+///
+/// Matches the piece length chosen by `imdl` so torrents are unchanged: `2^(⌈log₂ size⌉ / 2 + 4)`
+/// bytes clamped between 16 KiB and 16 MiB.
+#[must_use]
+pub fn get_piece_length(content_size: u64) -> u64 {
+    let exponent = if content_size <= 1 {
+        0
+    } else {
+        u64::BITS - (content_size - 1).leading_zeros()
+    };
+    1_u64
+        .checked_shl((exponent >> 1) + 4)
+        .unwrap_or(MAX_PIECE_LENGTH)
+        .clamp(MIN_PIECE_LENGTH, MAX_PIECE_LENGTH)
+}
+
+/// Parse a size in bytes with an optional binary unit such as `512KiB` or `4 MiB`.
+///
+/// `K` and `M` are treated as `KiB` and `MiB`.
+#[must_use]
+pub fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let number: u64 = digits.parse().ok()?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
+
+/// Check a piece length is a power of two of at least 16 KiB.
+#[must_use]
+pub fn is_valid_piece_length(length: u64) -> bool {
+    length >= MIN_PIECE_LENGTH && length.is_power_of_two()
+}
@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::io::{Error, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread::spawn;
+
+/// Length of each block read from a file.
+///
+/// Large reads reduce the round trips to network storage such as NFS or SMB.
+const BLOCK_LENGTH: usize = 4 * 1024 * 1024;
+
+/// Most blocks that are read ahead of the consumer.
+const BLOCK_COUNT: usize = 8;
+
+enum Block {
+    Data(Vec<u8>),
+    EndOfFile,
+    Failed(Error),
+}
+
+/// Read files in order on a background thread so IO overlaps with hashing.
+///
+/// Files are read in large blocks up to a fixed number of blocks ahead of the consumer,
+/// and the next file is opened while the previous is still being consumed.
+///
+/// [`Read`] returns `0` at the end of each file, after which [`ReadAhead::next_file`]
+/// moves to the next file.
+pub struct ReadAhead {
+    receiver: Receiver<Block>,
+    block: Vec<u8>,
+    position: usize,
+    is_end_of_file: bool,
+}
+
+impl ReadAhead {
+    /// Start reading the files.
+    #[must_use]
+    pub fn open(paths: Vec<PathBuf>) -> Self {
+        Self::with_blocks(paths, BLOCK_LENGTH, BLOCK_COUNT)
+    }
+
+    /// Start reading the files with a custom block length and count.
+    #[must_use]
+    pub fn with_blocks(paths: Vec<PathBuf>, block_length: usize, block_count: usize) -> Self {
+        let (sender, receiver) = sync_channel(block_count.max(1));
+        let block_length = block_length.max(1);
+        spawn(move || read_files(&paths, block_length, &sender));
+        Self {
+            receiver,
+            block: Vec::new(),
+            position: 0,
+            is_end_of_file: false,
+        }
+    }
+
+    /// Move to the next file, discarding anything unread of the current file.
+    pub fn next_file(&mut self) -> Result<(), Error> {
+        while !self.is_end_of_file {
+            self.receive()?;
+        }
+        self.block.clear();
+        self.position = 0;
+        self.is_end_of_file = false;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<(), Error> {
+        let block = self
+            .receiver
+            .recv()
+            .map_err(|_| Error::other("Read ahead thread stopped unexpectedly"))?;
+        match block {
+            Block::Data(data) => {
+                self.block = data;
+                self.position = 0;
+            }
+            Block::EndOfFile => self.is_end_of_file = true,
+            Block::Failed(error) => return Err(error),
+        }
+        Ok(())
+    }
+}
+
+impl Read for ReadAhead {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        while self.position >= self.block.len() {
+            if self.is_end_of_file {
+                return Ok(0);
+            }
+            self.receive()?;
+        }
+        let available = self.block.get(self.position..).unwrap_or_default();
+        let length = available.len().min(buffer.len());
+        buffer
+            .get_mut(..length)
+            .unwrap_or_default()
+            .copy_from_slice(available.get(..length).unwrap_or_default());
+        self.position += length;
+        Ok(length)
+    }
+}
+
+/// Read each file as blocks, stopping at the first error or if the consumer is dropped.
+fn read_files(paths: &[PathBuf], block_length: usize, sender: &SyncSender<Block>) {
+    for path in paths {
+        if let Err(error) = read_file(path, block_length, sender) {
+            let _ = sender.send(Block::Failed(error));
+            return;
+        }
+        if sender.send(Block::EndOfFile).is_err() {
+            return;
+        }
+    }
+}
+
+fn read_file(path: &Path, block_length: usize, sender: &SyncSender<Block>) -> Result<(), Error> {
+    let mut file = File::open(path)?;
+    loop {
+        let mut block = vec![0; block_length];
+        let mut filled = 0;
+        while let Some(remaining) = block.get_mut(filled..) {
+            if remaining.is_empty() {
+                break;
+            }
+            let read = file.read(remaining)?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        if filled == 0 {
+            return Ok(());
+        }
+        block.truncate(filled);
+        if sender.send(Block::Data(block)).is_err() {
+            return Err(Error::other("Read ahead consumer stopped"));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use serde_json::json;
+
+use crate::torrent::get_announce_url;
+
+#[test]
+fn get_announce_url_from_index() {
+    // Arrange
+    let index = json!({
+        "status": "success",
+        "response": { "username": "user", "passkey": "a1b2c3d4e5f6" }
+    });
+
+    // Act
+    let red = get_announce_url("red", &index).expect("should get red announce URL");
+    let ops = get_announce_url("ops", &index).expect("should get ops announce URL");
+
+    // Assert
+    assert_eq!(red, "https://flacsfor.me/a1b2c3d4e5f6/announce");
+    assert_eq!(ops, "https://home.opsfet.ch/a1b2c3d4e5f6/announce");
+}
+
+#[test]
+fn get_announce_url_rejects_invalid() {
+    // Arrange
+    let index = json!({ "response": { "passkey": "a1b2/../c3" } });
+    let missing = json!({ "response": { "username": "user" } });
+
+    // Act
+    let invalid = get_announce_url("red", &index);
+    let missing = get_announce_url("red", &missing);
+    let unknown = get_announce_url("other", &json!({ "response": { "passkey": "a1b2" } }));
+
+    // Assert
+    assert!(invalid.is_err());
+    assert!(missing.is_err());
+    assert!(unknown.is_err());
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::torrent::Bencode;
+
+#[test]
+fn decode_and_encode_round_trip() {
+    // Arrange
+    let bytes = b"d3:agei-3e4:listl1:ai1ee4:name4:spame";
+
+    // Act
+    let value = Bencode::decode(bytes).expect("should decode");
+
+    // Assert
+    assert_eq!(value.get("age").and_then(Bencode::as_integer), Some(-3));
+    assert_eq!(
+        value.get("name").and_then(Bencode::as_string),
+        Some("spam".to_owned())
+    );
+    assert_eq!(
+        value.get("list").and_then(Bencode::as_list).map(<[_]>::len),
+        Some(2)
+    );
+    assert_eq!(value.encode(), bytes.to_vec());
+}
+
+#[test]
+fn encode_sorts_keys() {
+    // Arrange
+    let mut values = BTreeMap::new();
+    values.insert(b"b".to_vec(), Bencode::Integer(1));
+    values.insert(b"a".to_vec(), Bencode::string("x"));
+
+    // Act
+    let bytes = Bencode::Dictionary(values).encode();
+
+    // Assert
+    assert_eq!(bytes, b"d1:a1:x1:bi1ee".to_vec());
+}
+
+#[test]
+fn decode_invalid() {
+    assert!(Bencode::decode(b"i12").is_err());
+    assert!(Bencode::decode(b"5:abc").is_err());
+    assert!(Bencode::decode(b"i1ei2e").is_err());
+    assert!(Bencode::decode(b"x").is_err());
+}
+
+#[test]
+fn get_raw_value() {
+    // Arrange
+    let bytes = b"d8:announce1:a4:infod4:name1:bee";
+
+    // Act
+    let raw = Bencode::get_raw_value(bytes, "info").expect("should decode");
+
+    // Assert
+    assert_eq!(raw, Some(b"d4:name1:be".as_slice()));
+}
+
+#[test]
+fn decode_length_overflow() {
+    // Arrange
+    let bytes = b"18446744073709551615:abc";
+
+    // Act
+    let result = Bencode::decode(bytes);
+
+    // Assert
+    let error = result.expect_err("overflowing length should not decode");
+    assert!(error.contains("exceeds the end of data"));
+}
+
+#[test]
+fn decode_too_deep() {
+    // Arrange
+    let depth = 10_000;
+    let bytes = ["l".repeat(depth), "e".repeat(depth)].concat();
+
+    // Act
+    let result = Bencode::decode(bytes.as_bytes());
+
+    // Assert
+    let error = result.expect_err("deeply nested list should not decode");
+    assert!(error.starts_with("Nested deeper than"));
+}
+
+#[test]
+fn decode_nested() {
+    // Arrange
+    let bytes = b"d4:infod5:filesld6:lengthi1e4:pathl1:aeeeee";
+
+    // Act
+    let result = Bencode::decode(bytes);
+
+    // Assert
+    assert!(result.is_ok());
+}
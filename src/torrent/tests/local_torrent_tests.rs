@@ -0,0 +1,62 @@
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+use std::slice::from_ref;
+
+use crate::testing::TempDirectory;
+use crate::torrent::find_local_torrent;
+
+fn write_torrent(path: &Path, id: u32, source: &str) {
+    let comment = format!("https://example.com/torrents.php?torrentid={id}");
+    let bytes = format!(
+        "d8:announce9:http://a/7:comment{}:{comment}4:infod6:lengthi5e4:name5:a.txt12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaa7:privatei1e6:source{}:{source}ee",
+        comment.len(),
+        source.len()
+    );
+    write(path, bytes).expect("Should be able to write torrent");
+}
+
+#[tokio::test]
+async fn find_local_torrent_in_dir() {
+    // Arrange
+    let dir = TempDirectory::create("find_local_torrent_in_dir");
+    write_torrent(&dir.join("other.torrent"), 456, "RED");
+    write_torrent(&dir.join("ops.torrent"), 123, "OPS");
+    write_torrent(&dir.join("source.torrent"), 123, "RED");
+
+    // Act
+    let found = find_local_torrent(123, "red", &[], from_ref(&dir)).await;
+
+    // Assert
+    assert_eq!(found, Some(dir.join("source.torrent")));
+}
+
+#[tokio::test]
+async fn find_local_torrent_prefers_file() {
+    // Arrange
+    let dir = TempDirectory::create("find_local_torrent_prefers_file");
+    let file = dir.join("file").with_extension("torrent");
+    write_torrent(&file, 123, "RED");
+    let other_dir = dir.join("dir");
+    create_dir_all(&other_dir).expect("Should be able to create dir");
+    write_torrent(&other_dir.join("source.torrent"), 123, "RED");
+
+    // Act
+    let found = find_local_torrent(123, "red", from_ref(&file), &[other_dir]).await;
+
+    // Assert
+    assert_eq!(found, Some(file));
+}
+
+#[tokio::test]
+async fn find_local_torrent_none() {
+    // Arrange
+    let dir = TempDirectory::create("find_local_torrent_none");
+    let file = dir.join("other.torrent");
+    write_torrent(&file, 456, "RED");
+
+    // Act
+    let found = find_local_torrent(123, "red", &[file], &[dir, PathBuf::from("/missing")]).await;
+
+    // Assert
+    assert_eq!(found, None);
+}
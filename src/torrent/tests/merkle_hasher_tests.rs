@@ -0,0 +1,85 @@
+use std::fs::write;
+
+use sha2::{Digest, Sha256};
+
+use crate::testing::TempDirectory;
+use crate::torrent::{MerkleHash, MerkleHasher, BLOCK_LENGTH};
+
+fn hash(bytes: &[u8]) -> MerkleHash {
+    Sha256::digest(bytes).into()
+}
+
+fn parent(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    hash(&[left.as_slice(), right.as_slice()].concat())
+}
+
+#[test]
+fn execute_single_block() {
+    // Arrange
+    let path = TempDirectory::create("merkle_single_block").join("a");
+    write(&path, b"abc").expect("should write file");
+
+    // Act
+    let hashes = MerkleHasher::execute(&path, BLOCK_LENGTH).expect("should hash");
+
+    // Assert
+    let hashes = hashes.expect("should have hashes");
+    assert_eq!(hashes.root, hash(b"abc"));
+}
+
+#[test]
+fn execute_pads_piece_layer() {
+    // Arrange
+    let path = TempDirectory::create("merkle_pads_piece_layer").join("a");
+    let block = usize::try_from(BLOCK_LENGTH).expect("should fit");
+    let content = [vec![1; block], vec![2; block], vec![3; 100]].concat();
+    write(&path, &content).expect("should write file");
+    let first = hash(&vec![1; block]);
+    let second = hash(&vec![2; block]);
+    let third = hash(&[3; 100]);
+
+    // Act
+    let hashes = MerkleHasher::execute(&path, BLOCK_LENGTH).expect("should hash");
+
+    // Assert
+    let hashes = hashes.expect("should have hashes");
+    assert_eq!(hashes.piece_layer, vec![first, second, third]);
+    let expected = parent(&parent(&first, &second), &parent(&third, &[0; 32]));
+    assert_eq!(hashes.root, expected);
+}
+
+#[test]
+fn execute_empty_file() {
+    // Arrange
+    let path = TempDirectory::create("merkle_empty_file").join("a");
+    write(&path, b"").expect("should write file");
+
+    // Act
+    let hashes = MerkleHasher::execute(&path, BLOCK_LENGTH).expect("should hash");
+
+    // Assert
+    assert!(hashes.is_none());
+}
+
+#[test]
+fn execute_files_hashes_each_file() {
+    // Arrange
+    let dir = TempDirectory::create("merkle_execute_files");
+    let first = dir.join("a");
+    let second = dir.join("b");
+    let third = dir.join("c");
+    write(&first, b"abc").expect("should write file");
+    write(&second, b"").expect("should write file");
+    write(&third, b"def").expect("should write file");
+
+    // Act
+    let hashes =
+        MerkleHasher::execute_files(vec![first, second, third], BLOCK_LENGTH).expect("should hash");
+
+    // Assert
+    let roots: Vec<Option<MerkleHash>> = hashes
+        .into_iter()
+        .map(|hashes| hashes.map(|x| x.root))
+        .collect();
+    assert_eq!(roots, vec![Some(hash(b"abc")), None, Some(hash(b"def"))]);
+}
@@ -0,0 +1,9 @@
+mod announce_provider_tests;
+mod bencode_tests;
+mod local_torrent_tests;
+mod merkle_hasher_tests;
+mod piece_hasher_tests;
+mod piece_length_tests;
+mod read_ahead_tests;
+mod torrent_file_tests;
+mod torrent_verifier_tests;
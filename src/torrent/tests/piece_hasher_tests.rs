@@ -0,0 +1,72 @@
+use std::fs::write;
+
+use crate::testing::TempDirectory;
+use crate::torrent::{get_hash, PieceHasher};
+
+#[test]
+fn execute_spans_files() {
+    // Arrange
+    let dir = TempDirectory::create("piece_hasher_spans_files");
+    let first = dir.join("a");
+    let second = dir.join("b");
+    write(&first, b"abc").expect("should write file");
+    write(&second, b"defgh").expect("should write file");
+
+    // Act
+    let pieces = PieceHasher::execute(&[first, second], 4, false).expect("should hash");
+
+    // Assert
+    assert_eq!(pieces, vec![get_hash(b"abcd"), get_hash(b"efgh")]);
+}
+
+#[test]
+fn execute_many_pieces_in_order() {
+    // Arrange
+    let dir = TempDirectory::create("piece_hasher_many_pieces");
+    let content: Vec<u8> = (0..10_000_u32)
+        .map(|x| u8::try_from(x % 251).expect("should fit in u8"))
+        .collect();
+    let first = dir.join("a");
+    let second = dir.join("b");
+    let (start, end) = content.split_at(3_333);
+    write(&first, start).expect("should write file");
+    write(&second, end).expect("should write file");
+    let expected: Vec<_> = content.chunks(64).map(get_hash).collect();
+
+    // Act
+    let pieces = PieceHasher::execute(&[first, second], 64, false).expect("should hash");
+
+    // Assert
+    assert_eq!(pieces, expected);
+}
+
+#[test]
+fn execute_missing_file() {
+    // Arrange
+    let dir = TempDirectory::create("piece_hasher_missing_file");
+
+    // Act
+    let result = PieceHasher::execute(&[dir.join("missing")], 64, false);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn execute_aligned_pads_files() {
+    // Arrange
+    let dir = TempDirectory::create("piece_hasher_aligned");
+    let first = dir.join("a");
+    let second = dir.join("b");
+    write(&first, b"abc").expect("should write file");
+    write(&second, b"defgh").expect("should write file");
+
+    // Act
+    let pieces = PieceHasher::execute(&[first, second], 4, true).expect("should hash");
+
+    // Assert
+    assert_eq!(
+        pieces,
+        vec![get_hash(b"abc\0"), get_hash(b"defg"), get_hash(b"h")]
+    );
+}
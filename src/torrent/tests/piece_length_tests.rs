@@ -0,0 +1,91 @@
+use crate::torrent::{get_piece_length, is_valid_piece_length, parse_size, PieceLength};
+
+const MIB: u64 = 1024 * 1024;
+
+#[test]
+fn get_piece_length_matches_imdl() {
+    assert_eq!(get_piece_length(0), 16 * 1024);
+    assert_eq!(get_piece_length(100 * MIB), 128 * 1024);
+    assert_eq!(get_piece_length(300 * MIB), 256 * 1024);
+    assert_eq!(get_piece_length(u64::MAX), 16 * MIB);
+}
+
+#[test]
+fn piece_length_fixed() {
+    // Arrange
+    let rules = PieceLength {
+        fixed: Some(4 * MIB),
+        ..PieceLength::default()
+    };
+
+    // Act
+    let length = rules.get(100 * MIB);
+
+    // Assert
+    assert_eq!(length, 4 * MIB);
+}
+
+#[test]
+fn piece_length_min() {
+    // Arrange
+    let rules = PieceLength {
+        min: MIB,
+        ..PieceLength::default()
+    };
+
+    // Act
+    let length = rules.get(100 * MIB);
+
+    // Assert
+    assert_eq!(length, MIB);
+}
+
+#[test]
+fn piece_length_max_count() {
+    // Arrange
+    let rules = PieceLength {
+        max_count: Some(100),
+        ..PieceLength::default()
+    };
+
+    // Act
+    let length = rules.get(100 * MIB);
+
+    // Assert
+    assert_eq!(length, MIB);
+}
+
+#[test]
+fn piece_length_max_count_does_not_exceed_max() {
+    // Arrange
+    let rules = PieceLength {
+        max: 2 * MIB,
+        max_count: Some(1),
+        ..PieceLength::default()
+    };
+
+    // Act
+    let length = rules.get(100 * MIB);
+
+    // Assert
+    assert_eq!(length, 2 * MIB);
+}
+
+#[test]
+fn parse_size_units() {
+    assert_eq!(parse_size("16384"), Some(16384));
+    assert_eq!(parse_size("512KiB"), Some(512 * 1024));
+    assert_eq!(parse_size("4 MiB"), Some(4 * MIB));
+    assert_eq!(parse_size("1m"), Some(MIB));
+    assert_eq!(parse_size("1.5MiB"), None);
+    assert_eq!(parse_size("MiB"), None);
+    assert_eq!(parse_size("1 parsec"), None);
+}
+
+#[test]
+fn is_valid_piece_length_power_of_two() {
+    assert!(is_valid_piece_length(16 * 1024));
+    assert!(is_valid_piece_length(8 * MIB));
+    assert!(!is_valid_piece_length(8 * 1024));
+    assert!(!is_valid_piece_length(3 * MIB));
+}
@@ -0,0 +1,70 @@
+use std::fs::write;
+use std::io::Read;
+
+use crate::testing::TempDirectory;
+use crate::torrent::ReadAhead;
+
+#[test]
+fn read_ahead_reads_files_in_order() {
+    // Arrange
+    let dir = TempDirectory::create("read_ahead_files_in_order");
+    let first = dir.join("a");
+    let second = dir.join("b");
+    write(&first, b"abcdefg").expect("should write file");
+    write(&second, b"hij").expect("should write file");
+    let mut reader = ReadAhead::with_blocks(vec![first, second], 2, 1);
+
+    // Act
+    let mut first_content = Vec::new();
+    reader
+        .read_to_end(&mut first_content)
+        .expect("should read first file");
+    reader.next_file().expect("should move to second file");
+    let mut second_content = Vec::new();
+    reader
+        .read_to_end(&mut second_content)
+        .expect("should read second file");
+
+    // Assert
+    assert_eq!(first_content, b"abcdefg".to_vec());
+    assert_eq!(second_content, b"hij".to_vec());
+}
+
+#[test]
+fn read_ahead_next_file_discards_unread() {
+    // Arrange
+    let dir = TempDirectory::create("read_ahead_discards_unread");
+    let first = dir.join("a");
+    let second = dir.join("b");
+    write(&first, b"abcdefg").expect("should write file");
+    write(&second, b"hij").expect("should write file");
+    let mut reader = ReadAhead::with_blocks(vec![first, second], 2, 1);
+    let mut buffer = [0; 3];
+    reader
+        .read_exact(&mut buffer)
+        .expect("should read first file");
+
+    // Act
+    reader.next_file().expect("should move to second file");
+    let mut content = Vec::new();
+    reader
+        .read_to_end(&mut content)
+        .expect("should read second file");
+
+    // Assert
+    assert_eq!(content, b"hij".to_vec());
+}
+
+#[test]
+fn read_ahead_missing_file() {
+    // Arrange
+    let path = TempDirectory::create("read_ahead_missing_file").join("missing");
+    let mut reader = ReadAhead::open(vec![path]);
+
+    // Act
+    let mut content = Vec::new();
+    let result = reader.read_to_end(&mut content);
+
+    // Assert
+    assert!(result.is_err());
+}
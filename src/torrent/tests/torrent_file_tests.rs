@@ -0,0 +1,68 @@
+use rogue_logging::Error;
+use std::path::PathBuf;
+
+use crate::fs::DirectoryReader;
+use crate::testing::TORRENTS_SAMPLES_DIR;
+use crate::torrent::TorrentFile;
+
+#[tokio::test]
+#[ignore = "requires sample torrent files"]
+async fn read() -> Result<(), Error> {
+    // Arrange
+    let paths = DirectoryReader::new()
+        .with_extension("torrent")
+        .read(&PathBuf::from(TORRENTS_SAMPLES_DIR))
+        .expect("Directory should exist");
+    let path = paths.first().expect("Should be at least one sample");
+
+    // Act
+    let summary = TorrentFile::read(path).await?.get_summary();
+
+    // Assert
+    assert!(!summary.files.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn from_bytes_single_file() -> Result<(), Error> {
+    // Arrange
+    let bytes = b"d8:announce9:http://a/4:infod6:lengthi5e4:name5:a.txt12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaa7:privatei1e6:source3:REDee";
+
+    // Act
+    let torrent = TorrentFile::from_bytes(bytes)?;
+    let summary = torrent.get_summary();
+
+    // Assert
+    assert_eq!(summary.name, "a.txt");
+    assert_eq!(summary.files, vec!["a.txt".to_owned()]);
+    assert_eq!(summary.content_size, 5);
+    assert_eq!(summary.piece_count, 1);
+    assert!(summary.private);
+    assert!(summary.is_source_equal("red"));
+    assert_eq!(summary.announce_list, vec![vec!["http://a/".to_owned()]]);
+    assert_eq!(torrent.to_bytes(), bytes.to_vec());
+    Ok(())
+}
+
+#[test]
+fn with_source() -> Result<(), Error> {
+    // Arrange
+    let bytes = b"d8:announce9:http://a/4:infod6:lengthi5e4:name5:a.txt12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaa7:privatei1e6:source3:REDee";
+    let expected = b"d8:announce9:http://b/4:infod6:lengthi5e4:name5:a.txt12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaa7:privatei1e6:source3:OPSee";
+    let torrent = TorrentFile::from_bytes(bytes)?;
+
+    // Act
+    let other = torrent.with_source(Some("OPS".to_owned()), "http://b/".to_owned());
+    let same = torrent.with_source(Some("RED".to_owned()), "http://b/".to_owned());
+
+    // Assert
+    assert_eq!(other.to_bytes(), expected.to_vec());
+    assert_eq!(
+        other.info_hash,
+        TorrentFile::from_bytes(expected)?.info_hash
+    );
+    assert_ne!(other.info_hash, torrent.info_hash);
+    assert_eq!(same.info_hash, torrent.info_hash);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::fs::{create_dir_all, read, write};
+
+use rogue_logging::Error;
+
+use crate::testing::TempDirectory;
+use crate::torrent::{PieceLength, TorrentCreator, TorrentFile, TorrentVerifier, TorrentVersion};
+
+#[tokio::test]
+async fn create_then_verify() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("torrent_create_then_verify");
+    let content_dir = dir.join("Album [FLAC]");
+    create_dir_all(content_dir.join("CD1")).expect("should create content dir");
+    write(
+        content_dir.join("CD1").join("01 Track.flac"),
+        vec![1; 40_000],
+    )
+    .expect("should write file");
+    write(content_dir.join("02 Track.flac"), vec![2; 10_000]).expect("should write file");
+    write(content_dir.join(".hidden"), "hidden").expect("should write file");
+    let torrent_path = dir.join("Album.torrent");
+
+    // Act
+    TorrentCreator::create(
+        &content_dir,
+        &torrent_path,
+        "https://example.com/announce".to_owned(),
+        "red".to_owned(),
+        &PieceLength::default(),
+        TorrentVersion::V1,
+    )
+    .await?;
+    let summary = TorrentFile::read(&torrent_path).await?.get_summary();
+    let issue = TorrentVerifier::verify(&torrent_path, &content_dir).await?;
+
+    // Assert
+    assert!(summary.private);
+    assert_eq!(summary.source, Some("RED".to_owned()));
+    assert_eq!(summary.file_count, 2);
+    assert_eq!(summary.content_size, 50_000);
+    assert_eq!(summary.piece_size, 16 * 1024);
+    assert!(issue.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_reports_changed_content() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("torrent_verify_changed");
+    let content_dir = dir.join("Album [FLAC]");
+    create_dir_all(&content_dir).expect("should create content dir");
+    write(content_dir.join("01 Track.flac"), vec![1; 32_768]).expect("should write file");
+    write(content_dir.join("02 Track.flac"), vec![2; 10_000]).expect("should write file");
+    let torrent_path = dir.join("Album.torrent");
+    TorrentCreator::create(
+        &content_dir,
+        &torrent_path,
+        "https://example.com/announce".to_owned(),
+        "red".to_owned(),
+        &PieceLength::default(),
+        TorrentVersion::V1,
+    )
+    .await?;
+    write(content_dir.join("02 Track.flac"), vec![3; 10_000]).expect("should write file");
+    let buffer = read(&torrent_path).expect("should read torrent");
+
+    // Act
+    let issue = TorrentVerifier::verify(&torrent_path, &content_dir).await?;
+    let issues = TorrentVerifier::verify_from_buffer(&buffer, &content_dir).await?;
+
+    // Assert
+    let details = issue.expect("should be an issue").to_string();
+    assert!(details.contains("02 Track.flac"));
+    assert!(!details.contains("01 Track.flac"));
+    assert_eq!(issues.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_with_fixed_piece_length() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("torrent_create_fixed_piece_length");
+    let content_dir = dir.join("Album [FLAC]");
+    create_dir_all(&content_dir).expect("should create content dir");
+    write(content_dir.join("01 Track.flac"), vec![1; 100_000]).expect("should write file");
+    let torrent_path = dir.join("Album.torrent");
+    let piece_length = PieceLength {
+        fixed: Some(64 * 1024),
+        ..PieceLength::default()
+    };
+
+    // Act
+    TorrentCreator::create(
+        &content_dir,
+        &torrent_path,
+        "https://example.com/announce".to_owned(),
+        "red".to_owned(),
+        &piece_length,
+        TorrentVersion::V1,
+    )
+    .await?;
+    let summary = TorrentFile::read(&torrent_path).await?.get_summary();
+    let issue = TorrentVerifier::verify(&torrent_path, &content_dir).await?;
+
+    // Assert
+    assert_eq!(summary.piece_size, 64 * 1024);
+    assert_eq!(summary.piece_count, 2);
+    assert!(issue.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_then_verify_v2_and_hybrid() -> Result<(), Error> {
+    for (name, version) in [
+        ("torrent_create_then_verify_v2", TorrentVersion::V2),
+        ("torrent_create_then_verify_hybrid", TorrentVersion::Hybrid),
+    ] {
+        // Arrange
+        let dir = TempDirectory::create(name);
+        let content_dir = dir.join("Album [FLAC]");
+        create_dir_all(&content_dir).expect("should create content dir");
+        write(content_dir.join("01 Track.flac"), vec![1; 40_000]).expect("should write file");
+        write(content_dir.join("02 Track.flac"), vec![2; 10_000]).expect("should write file");
+        let torrent_path = dir.join("Album.torrent");
+
+        // Act
+        let created = TorrentCreator::create(
+            &content_dir,
+            &torrent_path,
+            "https://example.com/announce".to_owned(),
+            "red".to_owned(),
+            &PieceLength::default(),
+            version,
+        )
+        .await?;
+        let torrent = TorrentFile::read(&torrent_path).await?;
+        let issue = TorrentVerifier::verify(&torrent_path, &content_dir).await?;
+        write(content_dir.join("02 Track.flac"), vec![3; 10_000]).expect("should write file");
+        let changed = TorrentVerifier::verify(&torrent_path, &content_dir).await?;
+
+        // Assert
+        assert_eq!(torrent.info.version, version);
+        assert_eq!(torrent.info_hash, created.info_hash);
+        assert_eq!(torrent.piece_layers.len(), 1);
+        let summary = torrent.get_summary();
+        assert_eq!(summary.file_count, 2);
+        assert_eq!(summary.content_size, 50_000);
+        assert!(issue.is_none());
+        let details = changed.expect("should be an issue").to_string();
+        assert!(details.contains("02 Track.flac"));
+        assert!(!details.contains("01 Track.flac"));
+    }
+    Ok(())
+}
+
+#[test]
+fn torrent_version_is_supported_by() {
+    assert!(TorrentVersion::V1.is_supported_by("red"));
+    assert!(!TorrentVersion::V2.is_supported_by("red"));
+    assert!(!TorrentVersion::Hybrid.is_supported_by("OPS"));
+    assert!(TorrentVersion::Hybrid.is_supported_by("other"));
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use log::trace;
+use rogue_logging::Error;
+use tokio::fs::copy;
+use tokio::task::spawn_blocking;
+
+use crate::built_info::{PKG_NAME, PKG_VERSION};
+use crate::errors::{error, io_error};
+use crate::fs::DirectoryReader;
+use crate::naming::Sanitizer;
+use crate::torrent::{
+    MerkleHasher, MerkleHashes, PieceHasher, PieceLength, TorrentEntry, TorrentFile, TorrentInfo,
+    TorrentVerifier, TorrentVersion, PIECE_HASH_LENGTH,
+};
+
+/// Create `.torrent` files.
+pub struct TorrentCreator;
+
+impl TorrentCreator {
+    /// Create a private torrent of the content directory and write it to `output_path`.
+    ///
+    /// Hidden files are not included.
+    ///
+    /// The piece length is chosen by the [`PieceLength`] rules.
+    ///
+    /// Returns an [`Error`] if the name of any file can't be opened on Windows.
+    pub async fn create(
+        content_dir: &Path,
+        output_path: &Path,
+        announce_url: String,
+        source: String,
+        piece_length: &PieceLength,
+        version: TorrentVersion,
+    ) -> Result<TorrentFile, Error> {
+        let action = "create torrent";
+        check_windows_safe(content_dir)?;
+        let name = content_dir
+            .file_name()
+            .map(|x| x.to_string_lossy().to_string())
+            .ok_or_else(|| error(action, "Content directory does not have a name".to_owned()))?;
+        let paths = get_content_files(content_dir)?;
+        let mut files = Vec::new();
+        for path in &paths {
+            let length = path.metadata().map_err(|e| io_error(e, action))?.len();
+            let path = path.strip_prefix(content_dir).unwrap_or(path).to_path_buf();
+            files.push(TorrentEntry {
+                path,
+                length,
+                pieces_root: None,
+                is_padding: false,
+            });
+        }
+        let content_size = files.iter().map(|file| file.length).sum();
+        let piece_length = piece_length.get(content_size);
+        let (pieces, hashes) = spawn_blocking(move || hash_content(&paths, piece_length, version))
+            .await
+            .map_err(|e| error(action, e.to_string()))?
+            .map_err(|e| io_error(e, "hash torrent content"))?;
+        let mut piece_layers = BTreeMap::new();
+        for (file, hashes) in files.iter_mut().zip(hashes) {
+            let Some(hashes) = hashes else {
+                continue;
+            };
+            file.pieces_root = Some(hashes.root);
+            if file.length > piece_length {
+                piece_layers.insert(hashes.root, hashes.piece_layer);
+            }
+        }
+        if version == TorrentVersion::Hybrid {
+            files = add_padding(files, piece_length);
+        }
+        let info = TorrentInfo {
+            name,
+            piece_length,
+            pieces,
+            files,
+            private: true,
+            source: Some(source.to_uppercase()),
+            version,
+        };
+        let creation_date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or_default();
+        let comment = format!("Created with {PKG_NAME} v{PKG_VERSION}");
+        let torrent = TorrentFile::new(info, piece_layers, announce_url, comment, creation_date);
+        torrent.write(output_path).await?;
+        Ok(torrent)
+    }
+
+    /// Duplicate a .torrent file
+    ///
+    /// Copy if the source and announce are the same.
+    ///
+    /// Otherwise, verify content is unchanged and re-create with new source.
+    pub async fn duplicate(
+        from: &Path,
+        to: &Path,
+        content_dir: &Path,
+        announce_url: String,
+        source: String,
+        piece_length: &PieceLength,
+        version: TorrentVersion,
+    ) -> Result<bool, Error> {
+        let torrent = TorrentFile::read(from).await?.get_summary();
+        let torrent_announce = torrent.announce_list.first().and_then(|x| x.first());
+        if torrent.is_source_equal(&source) && torrent_announce == Some(&announce_url) {
+            trace!(
+                "{} {:?} to {:?}",
+                "Copying".bold(),
+                from.file_name(),
+                to.file_name()
+            );
+            copy(&from, &to)
+                .await
+                .map_err(|e| io_error(e, "duplicate torrent"))?;
+            return Ok(true);
+        }
+        if !content_dir.is_dir() {
+            trace!(
+                "Torrent content directory does not exist: {}",
+                content_dir.display()
+            );
+            return Ok(false);
+        }
+        let verify_issues = TorrentVerifier::verify(from, content_dir).await?;
+        if verify_issues.is_some() {
+            trace!(
+                "Torrent content failed verification: {:?}",
+                from.file_name()
+            );
+            return Ok(false);
+        }
+        TorrentCreator::create(content_dir, to, announce_url, source, piece_length, version)
+            .await?;
+        Ok(true)
+    }
+}
+
+/// Hash the v1 pieces and the v2 merkle tree of each file as required by the version.
+///
+/// This is blocking so should be called from a blocking task.
+#[allow(clippy::type_complexity)]
+fn hash_content(
+    paths: &[PathBuf],
+    piece_length: u64,
+    version: TorrentVersion,
+) -> Result<(Vec<[u8; PIECE_HASH_LENGTH]>, Vec<Option<MerkleHashes>>), io::Error> {
+    let pieces = if version.has_v1() {
+        let is_aligned = version == TorrentVersion::Hybrid;
+        PieceHasher::execute(paths, piece_length, is_aligned)?
+    } else {
+        Vec::new()
+    };
+    let hashes = if version.has_v2() {
+        MerkleHasher::execute_files(paths.to_vec(), piece_length)?
+    } else {
+        Vec::new()
+    };
+    Ok((pieces, hashes))
+}
+
+/// Add a padding file after each file, except the last, so every file starts on a piece boundary.
+fn add_padding(files: Vec<TorrentEntry>, piece_length: u64) -> Vec<TorrentEntry> {
+    let count = files.len();
+    let mut output = Vec::new();
+    for (index, file) in files.into_iter().enumerate() {
+        let remainder = file.length % piece_length;
+        output.push(file);
+        if remainder > 0 && index + 1 < count {
+            output.push(TorrentEntry::padding(piece_length - remainder));
+        }
+    }
+    output
+}
+
+/// Get the files to include in the torrent sorted by path.
+fn get_content_files(content_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files: Vec<PathBuf> = DirectoryReader::new()
+        .read(content_dir)
+        .map_err(|e| io_error(e, "read torrent content"))?
+        .into_iter()
+        .filter(|path| !is_hidden(path.strip_prefix(content_dir).unwrap_or(path)))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// Check the name of the content directory and every file within it can be opened on Windows.
+fn check_windows_safe(content_dir: &Path) -> Result<(), Error> {
+    let action = "check torrent content";
+    let files = DirectoryReader::new()
+        .read(content_dir)
+        .map_err(|e| io_error(e, action))?;
+    let name = content_dir
+        .file_name()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let unsafe_paths: Vec<String> = Some(name)
+        .into_iter()
+        .chain(
+            files
+                .iter()
+                .filter_map(|file| file.strip_prefix(content_dir).ok())
+                .map(Path::to_path_buf),
+        )
+        .filter(|path| !Sanitizer::is_windows_safe(path))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    if unsafe_paths.is_empty() {
+        Ok(())
+    } else {
+        Err(error(
+            action,
+            format!(
+                "Names can't be opened on Windows: {}",
+                unsafe_paths.join(", ")
+            ),
+        ))
+    }
+}
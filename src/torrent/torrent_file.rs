@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use rogue_logging::Error;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use tokio::fs::{read, write};
+
+use crate::errors::{error, io_error};
+use crate::torrent::{Bencode, MerkleHash, TorrentInfo, TorrentSummary, MERKLE_HASH_LENGTH};
+
+/// Length of a SHA-1 hash of a piece.
+pub const PIECE_HASH_LENGTH: usize = 20;
+
+/// Metadata of a `.torrent` file.
+///
+/// <https://www.bittorrent.org/beps/bep_0003.html#metainfo-files>
+#[derive(Clone, Debug)]
+pub struct TorrentFile {
+    pub announce: Option<String>,
+    pub announce_list: Vec<Vec<String>>,
+    pub comment: Option<String>,
+    pub created_by: Option<String>,
+    pub creation_date: Option<u64>,
+    pub info: TorrentInfo,
+    /// SHA-1 hash of the bencoded `info` dictionary.
+    ///
+    /// For a v2 torrent this is the SHA-256 hash truncated to 20 bytes.
+    pub info_hash: [u8; PIECE_HASH_LENGTH],
+    /// Hashes of the pieces of each v2 file larger than one piece by merkle root.
+    pub piece_layers: BTreeMap<MerkleHash, Vec<MerkleHash>>,
+    /// Size of the bencoded torrent in bytes.
+    pub size: u64,
+}
+
+impl TorrentFile {
+    /// Read a `.torrent` file.
+    pub async fn read(path: &Path) -> Result<TorrentFile, Error> {
+        let bytes = read(path).await.map_err(|e| io_error(e, "read torrent"))?;
+        TorrentFile::from_bytes(&bytes)
+    }
+
+    /// Write the `.torrent` file, replacing any existing file.
+    pub async fn write(&self, path: &Path) -> Result<(), Error> {
+        write(path, self.to_bytes())
+            .await
+            .map_err(|e| io_error(e, "write torrent"))
+    }
+
+    /// Create a [`TorrentFile`] with the [`TorrentInfo`].
+    #[must_use]
+    pub fn new(
+        info: TorrentInfo,
+        piece_layers: BTreeMap<MerkleHash, Vec<MerkleHash>>,
+        announce: String,
+        comment: String,
+        creation_date: u64,
+    ) -> Self {
+        let info_hash = get_info_hash(&info, &info.to_bencode().encode());
+        let mut torrent = TorrentFile {
+            announce: Some(announce),
+            announce_list: Vec::new(),
+            comment: Some(comment),
+            created_by: None,
+            creation_date: Some(creation_date),
+            info,
+            info_hash,
+            piece_layers,
+            size: 0,
+        };
+        torrent.size = u64::try_from(torrent.to_bytes().len()).unwrap_or(u64::MAX);
+        torrent
+    }
+
+    /// Create a copy of the torrent for another indexer.
+    ///
+    /// The `source` of the [`TorrentInfo`] is replaced so the info hash is recalculated,
+    /// and the tracker is replaced with `announce`.
+    #[must_use]
+    pub fn with_source(&self, source: Option<String>, announce: String) -> Self {
+        let mut info = self.info.clone();
+        info.source = source;
+        let info_hash = get_info_hash(&info, &info.to_bencode().encode());
+        let mut torrent = TorrentFile {
+            announce: Some(announce),
+            announce_list: Vec::new(),
+            info,
+            info_hash,
+            ..self.clone()
+        };
+        torrent.size = u64::try_from(torrent.to_bytes().len()).unwrap_or(u64::MAX);
+        torrent
+    }
+
+    /// Decode a `.torrent` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<TorrentFile, Error> {
+        let action = "decode torrent";
+        let value = Bencode::decode(bytes).map_err(|e| error(action, e))?;
+        let raw_info = Bencode::get_raw_value(bytes, "info")
+            .map_err(|e| error(action, e))?
+            .ok_or_else(|| {
+                error(
+                    action,
+                    "Torrent does not have an info dictionary".to_owned(),
+                )
+            })?;
+        let info = value.get("info").ok_or_else(|| {
+            error(
+                action,
+                "Torrent does not have an info dictionary".to_owned(),
+            )
+        })?;
+        let announce_list = value
+            .get("announce-list")
+            .and_then(Bencode::as_list)
+            .unwrap_or_default()
+            .iter()
+            .map(|tier| {
+                tier.as_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(Bencode::as_string)
+                    .collect()
+            })
+            .collect();
+        let info = TorrentInfo::from_bencode(info).map_err(|e| error(action, e))?;
+        let info_hash = get_info_hash(&info, raw_info);
+        let piece_layers = get_piece_layers(&value);
+        Ok(TorrentFile {
+            announce: value.get("announce").and_then(Bencode::as_string),
+            announce_list,
+            comment: value.get("comment").and_then(Bencode::as_string),
+            created_by: value.get("created by").and_then(Bencode::as_string),
+            creation_date: value
+                .get("creation date")
+                .and_then(Bencode::as_integer)
+                .and_then(|x| u64::try_from(x).ok()),
+            info,
+            info_hash,
+            piece_layers,
+            size: u64::try_from(bytes.len()).unwrap_or(u64::MAX),
+        })
+    }
+
+    /// Encode the `.torrent` file.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut values = BTreeMap::new();
+        if let Some(announce) = &self.announce {
+            values.insert(b"announce".to_vec(), Bencode::string(announce));
+        }
+        if !self.announce_list.is_empty() {
+            let tiers = self
+                .announce_list
+                .iter()
+                .map(|tier| Bencode::List(tier.iter().map(|x| Bencode::string(x)).collect()))
+                .collect();
+            values.insert(b"announce-list".to_vec(), Bencode::List(tiers));
+        }
+        if let Some(comment) = &self.comment {
+            values.insert(b"comment".to_vec(), Bencode::string(comment));
+        }
+        if let Some(created_by) = &self.created_by {
+            values.insert(b"created by".to_vec(), Bencode::string(created_by));
+        }
+        if let Some(creation_date) = self.creation_date {
+            values.insert(
+                b"creation date".to_vec(),
+                Bencode::Integer(i64::try_from(creation_date).unwrap_or(i64::MAX)),
+            );
+        }
+        values.insert(b"info".to_vec(), self.info.to_bencode());
+        if !self.piece_layers.is_empty() {
+            let layers = self
+                .piece_layers
+                .iter()
+                .map(|(root, layer)| (root.to_vec(), Bencode::Bytes(layer.concat())))
+                .collect();
+            values.insert(b"piece layers".to_vec(), Bencode::Dictionary(layers));
+        }
+        Bencode::Dictionary(values).encode()
+    }
+
+    /// Get the info hash as lower case hexadecimal.
+    #[must_use]
+    pub fn get_info_hash(&self) -> String {
+        self.info_hash
+            .iter()
+            .fold(String::new(), |mut output, byte| {
+                let _ = write!(output, "{byte:02x}");
+                output
+            })
+    }
+
+    /// Get a [`TorrentSummary`].
+    ///
+    /// If there is no `announce-list` then the `announce` is the only tier.
+    #[must_use]
+    pub fn get_summary(&self) -> TorrentSummary {
+        let announce_list = if self.announce_list.is_empty() {
+            self.announce.iter().map(|x| vec![x.clone()]).collect()
+        } else {
+            self.announce_list.clone()
+        };
+        TorrentSummary {
+            name: self.info.name.clone(),
+            comment: self.comment.clone(),
+            creation_date: self.creation_date,
+            created_by: self.created_by.clone(),
+            source: self.info.source.clone(),
+            info_hash: self.get_info_hash(),
+            torrent_size: self.size,
+            content_size: self.info.get_content_size(),
+            private: self.info.private,
+            tracker: self.announce.clone(),
+            announce_list,
+            update_url: None,
+            dht_nodes: Vec::new(),
+            piece_size: self.info.piece_length,
+            piece_count: self.info.pieces.len(),
+            file_count: self.info.get_content_files().count(),
+            files: self
+                .info
+                .get_content_files()
+                .map(|file| {
+                    if file.path.as_os_str().is_empty() {
+                        self.info.name.clone()
+                    } else {
+                        Path::new(&self.info.name)
+                            .join(&file.path)
+                            .to_string_lossy()
+                            .to_string()
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Get the SHA-1 hash of the bytes.
+#[must_use]
+pub fn get_hash(bytes: &[u8]) -> [u8; PIECE_HASH_LENGTH] {
+    Sha1::digest(bytes).into()
+}
+
+/// Get the info hash used to identify the torrent to trackers and peers.
+///
+/// A v2 torrent uses the SHA-256 hash truncated to 20 bytes.
+fn get_info_hash(info: &TorrentInfo, raw_info: &[u8]) -> [u8; PIECE_HASH_LENGTH] {
+    if info.version.has_v1() {
+        return get_hash(raw_info);
+    }
+    let hash: MerkleHash = Sha256::digest(raw_info).into();
+    let mut truncated = [0; PIECE_HASH_LENGTH];
+    truncated.copy_from_slice(hash.get(..PIECE_HASH_LENGTH).unwrap_or_default());
+    truncated
+}
+
+fn get_piece_layers(value: &Bencode) -> BTreeMap<MerkleHash, Vec<MerkleHash>> {
+    let Some(Bencode::Dictionary(layers)) = value.get("piece layers") else {
+        return BTreeMap::new();
+    };
+    layers
+        .iter()
+        .filter_map(|(root, layer)| {
+            let root = MerkleHash::try_from(root.as_slice()).ok()?;
+            let layer = layer
+                .as_bytes()?
+                .chunks_exact(MERKLE_HASH_LENGTH)
+                .filter_map(|chunk| MerkleHash::try_from(chunk).ok())
+                .collect();
+            Some((root, layer))
+        })
+        .collect()
+}
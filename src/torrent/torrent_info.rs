@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::torrent::{Bencode, MerkleHash, TorrentVersion, PIECE_HASH_LENGTH};
+
+/// Name of the directory of BEP 47 padding files.
+const PADDING_DIR_NAME: &str = ".pad";
+
+/// The `info` dictionary of a [`TorrentFile`].
+#[derive(Clone, Debug)]
+pub struct TorrentInfo {
+    pub name: String,
+    pub piece_length: u64,
+    /// SHA-1 hash of each v1 piece.
+    ///
+    /// Empty for a v2 torrent.
+    pub pieces: Vec<[u8; PIECE_HASH_LENGTH]>,
+    /// Files of the torrent in order.
+    ///
+    /// A single file torrent has one entry with an empty path.
+    pub files: Vec<TorrentEntry>,
+    pub private: bool,
+    pub source: Option<String>,
+    pub version: TorrentVersion,
+}
+
+/// A file of a [`TorrentInfo`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TorrentEntry {
+    /// Path relative to the content directory.
+    pub path: PathBuf,
+    pub length: u64,
+    /// Root of the v2 merkle tree of the file.
+    ///
+    /// `None` for a v1 torrent or an empty file.
+    pub pieces_root: Option<MerkleHash>,
+    /// Is this a BEP 47 padding file that aligns the next file to a piece boundary?
+    ///
+    /// Padding files are not present in the content directory.
+    pub is_padding: bool,
+}
+
+impl TorrentEntry {
+    /// Create a BEP 47 padding file.
+    #[must_use]
+    pub fn padding(length: u64) -> Self {
+        Self {
+            path: Path::new(PADDING_DIR_NAME).join(length.to_string()),
+            length,
+            pieces_root: None,
+            is_padding: true,
+        }
+    }
+}
+
+impl TorrentInfo {
+    /// Get the total length of the files excluding padding.
+    #[must_use]
+    pub fn get_content_size(&self) -> u64 {
+        self.get_content_files().map(|file| file.length).sum()
+    }
+
+    /// Get the files excluding padding.
+    pub fn get_content_files(&self) -> impl Iterator<Item = &TorrentEntry> {
+        self.files.iter().filter(|file| !file.is_padding)
+    }
+
+    fn is_single_file(&self) -> bool {
+        self.files.len() == 1
+            && self
+                .files
+                .first()
+                .is_some_and(|file| file.path.as_os_str().is_empty())
+    }
+
+    pub(crate) fn from_bencode(value: &Bencode) -> Result<TorrentInfo, String> {
+        let name = value
+            .get("name")
+            .and_then(Bencode::as_string)
+            .ok_or_else(|| "Torrent does not have a name".to_owned())?;
+        let piece_length = value
+            .get("piece length")
+            .and_then(Bencode::as_integer)
+            .and_then(|x| u64::try_from(x).ok())
+            .filter(|x| *x > 0)
+            .ok_or_else(|| "Torrent does not have a valid piece length".to_owned())?;
+        let has_v2 = value.get("meta version").and_then(Bencode::as_integer) == Some(2);
+        let has_v1 = value.get("pieces").is_some();
+        let version = match (has_v1, has_v2) {
+            (true, true) => TorrentVersion::Hybrid,
+            (false, true) => TorrentVersion::V2,
+            (true, false) => TorrentVersion::V1,
+            (false, false) => return Err("Torrent does not have pieces".to_owned()),
+        };
+        let pieces = if has_v1 {
+            get_pieces(value)?
+        } else {
+            Vec::new()
+        };
+        let mut files = if has_v1 {
+            get_v1_files(value)?
+        } else {
+            Vec::new()
+        };
+        if has_v2 {
+            let tree = value
+                .get("file tree")
+                .ok_or_else(|| "Torrent does not have a file tree".to_owned())?;
+            let mut tree_files = Vec::new();
+            walk_file_tree(tree, &PathBuf::new(), &mut tree_files)?;
+            if let [file] = tree_files.as_mut_slice() {
+                if file.path == Path::new(&name) {
+                    file.path = PathBuf::new();
+                }
+            }
+            if has_v1 {
+                for file in &mut files {
+                    file.pieces_root = tree_files
+                        .iter()
+                        .find(|x| x.path == file.path)
+                        .and_then(|x| x.pieces_root);
+                }
+            } else {
+                files = tree_files;
+            }
+        }
+        Ok(TorrentInfo {
+            name,
+            piece_length,
+            pieces,
+            files,
+            private: value.get("private").and_then(Bencode::as_integer) == Some(1),
+            source: value.get("source").and_then(Bencode::as_string),
+            version,
+        })
+    }
+
+    pub(crate) fn to_bencode(&self) -> Bencode {
+        let mut values = BTreeMap::new();
+        if self.version.has_v1() {
+            if self.is_single_file() {
+                values.insert(
+                    b"length".to_vec(),
+                    Bencode::Integer(to_integer(self.get_content_size())),
+                );
+            } else {
+                let files = self.files.iter().map(entry_to_bencode).collect();
+                values.insert(b"files".to_vec(), Bencode::List(files));
+            }
+            values.insert(b"pieces".to_vec(), Bencode::Bytes(self.pieces.concat()));
+        }
+        if self.version.has_v2() {
+            values.insert(b"meta version".to_vec(), Bencode::Integer(2));
+            values.insert(b"file tree".to_vec(), self.get_file_tree());
+        }
+        values.insert(b"name".to_vec(), Bencode::string(&self.name));
+        values.insert(
+            b"piece length".to_vec(),
+            Bencode::Integer(to_integer(self.piece_length)),
+        );
+        if self.private {
+            values.insert(b"private".to_vec(), Bencode::Integer(1));
+        }
+        if let Some(source) = &self.source {
+            values.insert(b"source".to_vec(), Bencode::string(source));
+        }
+        Bencode::Dictionary(values)
+    }
+
+    /// Get the v2 `file tree` dictionary.
+    fn get_file_tree(&self) -> Bencode {
+        let mut tree = Bencode::Dictionary(BTreeMap::new());
+        for file in self.get_content_files() {
+            let path = if file.path.as_os_str().is_empty() {
+                PathBuf::from(&self.name)
+            } else {
+                file.path.clone()
+            };
+            let mut leaf = BTreeMap::new();
+            leaf.insert(
+                b"length".to_vec(),
+                Bencode::Integer(to_integer(file.length)),
+            );
+            if let Some(root) = file.pieces_root {
+                leaf.insert(b"pieces root".to_vec(), Bencode::Bytes(root.to_vec()));
+            }
+            let mut node = &mut tree;
+            for component in path.components() {
+                let key = component.as_os_str().to_string_lossy().as_bytes().to_vec();
+                let Bencode::Dictionary(children) = node else {
+                    break;
+                };
+                node = children
+                    .entry(key)
+                    .or_insert_with(|| Bencode::Dictionary(BTreeMap::new()));
+            }
+            if let Bencode::Dictionary(children) = node {
+                children.insert(Vec::new(), Bencode::Dictionary(leaf));
+            }
+        }
+        tree
+    }
+}
+
+fn get_pieces(value: &Bencode) -> Result<Vec<[u8; PIECE_HASH_LENGTH]>, String> {
+    let pieces = value
+        .get("pieces")
+        .and_then(Bencode::as_bytes)
+        .ok_or_else(|| "Torrent does not have pieces".to_owned())?;
+    if pieces.len() % PIECE_HASH_LENGTH != 0 {
+        return Err("Torrent pieces are not a multiple of 20 bytes".to_owned());
+    }
+    let pieces = pieces
+        .chunks_exact(PIECE_HASH_LENGTH)
+        .map(|chunk| {
+            let mut hash = [0; PIECE_HASH_LENGTH];
+            hash.copy_from_slice(chunk);
+            hash
+        })
+        .collect();
+    Ok(pieces)
+}
+
+fn get_v1_files(value: &Bencode) -> Result<Vec<TorrentEntry>, String> {
+    if let Some(files) = value.get("files").and_then(Bencode::as_list) {
+        return files.iter().map(entry_from_bencode).collect();
+    }
+    let length = value
+        .get("length")
+        .and_then(Bencode::as_integer)
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or_else(|| "Torrent does not have files or a length".to_owned())?;
+    Ok(vec![TorrentEntry {
+        path: PathBuf::new(),
+        length,
+        pieces_root: None,
+        is_padding: false,
+    }])
+}
+
+fn entry_from_bencode(value: &Bencode) -> Result<TorrentEntry, String> {
+    let length = value
+        .get("length")
+        .and_then(Bencode::as_integer)
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or_else(|| "Torrent file does not have a length".to_owned())?;
+    let components = value
+        .get("path")
+        .and_then(Bencode::as_list)
+        .ok_or_else(|| "Torrent file does not have a path".to_owned())?;
+    let mut path = PathBuf::new();
+    for component in components {
+        let component = component
+            .as_string()
+            .ok_or_else(|| "Torrent file path is invalid".to_owned())?;
+        path.push(check_component(&component)?);
+    }
+    let is_padding = value
+        .get("attr")
+        .and_then(Bencode::as_string)
+        .is_some_and(|attr| attr.contains('p'));
+    Ok(TorrentEntry {
+        path,
+        length,
+        pieces_root: None,
+        is_padding,
+    })
+}
+
+fn entry_to_bencode(entry: &TorrentEntry) -> Bencode {
+    let path = entry
+        .path
+        .components()
+        .map(|component| Bencode::string(&component.as_os_str().to_string_lossy()))
+        .collect();
+    let mut values = BTreeMap::new();
+    if entry.is_padding {
+        values.insert(b"attr".to_vec(), Bencode::string("p"));
+    }
+    values.insert(
+        b"length".to_vec(),
+        Bencode::Integer(to_integer(entry.length)),
+    );
+    values.insert(b"path".to_vec(), Bencode::List(path));
+    Bencode::Dictionary(values)
+}
+
+/// Add the files of a v2 `file tree` dictionary to `files`.
+fn walk_file_tree(
+    node: &Bencode,
+    path: &Path,
+    files: &mut Vec<TorrentEntry>,
+) -> Result<(), String> {
+    let Bencode::Dictionary(children) = node else {
+        return Err("Torrent file tree is invalid".to_owned());
+    };
+    for (key, child) in children {
+        if key.is_empty() {
+            let length = child
+                .get("length")
+                .and_then(Bencode::as_integer)
+                .and_then(|x| u64::try_from(x).ok())
+                .ok_or_else(|| "Torrent file does not have a length".to_owned())?;
+            let pieces_root = child
+                .get("pieces root")
+                .and_then(Bencode::as_bytes)
+                .and_then(|x| MerkleHash::try_from(x).ok());
+            files.push(TorrentEntry {
+                path: path.to_path_buf(),
+                length,
+                pieces_root,
+                is_padding: false,
+            });
+        } else {
+            let component = String::from_utf8_lossy(key);
+            walk_file_tree(child, &path.join(check_component(&component)?), files)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check a path component can't escape the content directory.
+fn check_component(component: &str) -> Result<&str, String> {
+    if component.is_empty() || component == "." || component == ".." || component.contains('/') {
+        Err(format!("Torrent file path is unsafe: {component}"))
+    } else {
+        Ok(component)
+    }
+}
+
+fn to_integer(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use rogue_logging::Error;
+use tokio::task::spawn_blocking;
+
+use crate::errors::{error, io_error};
+use crate::source::SourceIssue;
+use crate::source::SourceIssue::HashMismatch;
+use crate::torrent::{MerkleHasher, PieceHasher, TorrentFile};
+
+/// Verify content against the metadata of a `.torrent` file.
+pub struct TorrentVerifier;
+
+impl TorrentVerifier {
+    /// Verify files match the torrent metadata.
+    pub async fn verify(
+        torrent_file: &Path,
+        directory: &Path,
+    ) -> Result<Option<SourceIssue>, Error> {
+        let torrent = TorrentFile::read(torrent_file).await?;
+        let details = get_mismatches(&torrent, directory).await?;
+        if details.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(HashMismatch {
+                details: details.join("\n"),
+            }))
+        }
+    }
+
+    /// Verify files match the torrent metadata.
+    #[allow(dead_code)]
+    pub async fn verify_from_buffer(
+        buffer: &[u8],
+        directory: &Path,
+    ) -> Result<Vec<SourceIssue>, Error> {
+        let torrent = TorrentFile::from_bytes(buffer)?;
+        let details = get_mismatches(&torrent, directory).await?;
+        if details.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Ok(vec![HashMismatch {
+                details: details.join("\n"),
+            }])
+        }
+    }
+}
+
+/// Get a description of each way the content does not match the torrent.
+///
+/// The pieces are only hashed if every file exists with the expected length.
+///
+/// The v1 pieces are checked if present, otherwise the v2 merkle root of each file.
+async fn get_mismatches(torrent: &TorrentFile, directory: &Path) -> Result<Vec<String>, Error> {
+    let mut details = Vec::new();
+    let mut paths = Vec::new();
+    for file in torrent.info.get_content_files() {
+        let path = if file.path.as_os_str().is_empty() {
+            directory.to_path_buf()
+        } else {
+            directory.join(&file.path)
+        };
+        match path.metadata() {
+            Ok(metadata) if metadata.is_file() => {
+                if metadata.len() != file.length {
+                    details.push(format!(
+                        "Expected {} bytes but found {}: {}",
+                        file.length,
+                        metadata.len(),
+                        file.path.display()
+                    ));
+                }
+            }
+            _ => details.push(format!("Missing file: {}", file.path.display())),
+        }
+        paths.push(path);
+    }
+    if !details.is_empty() {
+        return Ok(details);
+    }
+    if torrent.info.version.has_v1() {
+        get_piece_mismatches(torrent, paths).await
+    } else {
+        get_merkle_mismatches(torrent, paths).await
+    }
+}
+
+async fn get_piece_mismatches(
+    torrent: &TorrentFile,
+    paths: Vec<PathBuf>,
+) -> Result<Vec<String>, Error> {
+    let mut details = Vec::new();
+    let piece_length = torrent.info.piece_length;
+    let is_aligned = torrent.info.files.iter().any(|file| file.is_padding);
+    let actual = spawn_blocking(move || PieceHasher::execute(&paths, piece_length, is_aligned))
+        .await
+        .map_err(|e| error("verify torrent", e.to_string()))?
+        .map_err(|e| io_error(e, "hash torrent content"))?;
+    if actual.len() != torrent.info.pieces.len() {
+        details.push(format!(
+            "Expected {} pieces but found {}",
+            torrent.info.pieces.len(),
+            actual.len()
+        ));
+        return Ok(details);
+    }
+    let mismatched: Vec<usize> = actual
+        .iter()
+        .zip(&torrent.info.pieces)
+        .enumerate()
+        .filter(|(_, (actual, expected))| actual != expected)
+        .map(|(index, _)| index)
+        .collect();
+    for path in get_files_of_pieces(torrent, &mismatched) {
+        details.push(format!("Hash mismatch: {}", path.display()));
+    }
+    Ok(details)
+}
+
+async fn get_merkle_mismatches(
+    torrent: &TorrentFile,
+    paths: Vec<PathBuf>,
+) -> Result<Vec<String>, Error> {
+    let piece_length = torrent.info.piece_length;
+    let actual = spawn_blocking(move || MerkleHasher::execute_files(paths, piece_length))
+        .await
+        .map_err(|e| error("verify torrent", e.to_string()))?
+        .map_err(|e| io_error(e, "hash torrent content"))?;
+    let details = torrent
+        .info
+        .get_content_files()
+        .zip(actual)
+        .filter(|(file, actual)| file.pieces_root != actual.as_ref().map(|x| x.root))
+        .map(|(file, _)| format!("Hash mismatch: {}", file.path.display()))
+        .collect();
+    Ok(details)
+}
+
+/// Get the files that contain any of the pieces.
+fn get_files_of_pieces(torrent: &TorrentFile, pieces: &[usize]) -> Vec<PathBuf> {
+    let piece_length = torrent.info.piece_length;
+    let mut start = 0;
+    let mut files = Vec::new();
+    for file in &torrent.info.files {
+        let end = start + file.length;
+        let is_affected = pieces.iter().any(|piece| {
+            let piece_start = u64::try_from(*piece).unwrap_or(u64::MAX) * piece_length;
+            let piece_end = piece_start + piece_length;
+            piece_start < end && start < piece_end
+        });
+        if is_affected && !file.is_padding {
+            files.push(file.path.clone());
+        }
+        start = end;
+    }
+    files
+}
@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Indexers known to reject torrents with v2 metadata.
+const V2_UNSUPPORTED_INDEXERS: [&str; 2] = ["red", "ops"];
+
+/// Version of the metadata of a created torrent.
+///
+/// <https://www.bittorrent.org/beps/bep_0052.html>
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TorrentVersion {
+    /// Original metadata hashed with SHA-1.
+    #[default]
+    V1,
+    /// Metadata with a per-file merkle tree hashed with SHA-256.
+    V2,
+    /// Both v1 and v2 metadata so clients of either version can use the torrent.
+    Hybrid,
+}
+
+impl TorrentVersion {
+    /// Does the torrent include v1 metadata?
+    #[must_use]
+    pub fn has_v1(self) -> bool {
+        matches!(self, TorrentVersion::V1 | TorrentVersion::Hybrid)
+    }
+
+    /// Does the torrent include v2 metadata?
+    #[must_use]
+    pub fn has_v2(self) -> bool {
+        matches!(self, TorrentVersion::V2 | TorrentVersion::Hybrid)
+    }
+
+    /// Is the version accepted by the indexer?
+    ///
+    /// Indexers that are not known are assumed to accept any version.
+    #[must_use]
+    pub fn is_supported_by(self, indexer: &str) -> bool {
+        !self.has_v2()
+            || !V2_UNSUPPORTED_INDEXERS
+                .iter()
+                .any(|x| x.eq_ignore_ascii_case(indexer))
+    }
+}
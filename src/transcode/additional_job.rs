@@ -22,12 +22,13 @@ impl AdditionalJob {
         );
         let info = self.resize.to_info();
         trace!("{info}");
+        let line = info.display();
         let output = info
             .to_command()
             .output()
             .await
             .map_err(|e| command_error(e, "execute resize image", CONVERT))?;
-        OutputHandler::execute(output, "resize image", "convert")?;
+        OutputHandler::execute_command(output, "resize image", CONVERT, &line)?;
         Ok(())
     }
 }
@@ -1,5 +1,5 @@
-use crate::errors::io_error;
-use crate::formats::target_format::TargetFormat;
+use crate::errors::path_error;
+use crate::formats::TranscodeTarget;
 use crate::fs::{AdditionalFile, PathManager};
 use crate::jobs::Job;
 use crate::options::{CopyOptions, FileOptions};
@@ -10,7 +10,7 @@ use colored::Colorize;
 use di::{injectable, Ref};
 use log::{trace, warn};
 use rogue_logging::Error;
-use tokio::fs::{copy, create_dir_all, hard_link};
+use tokio::fs::create_dir_all;
 
 #[injectable]
 pub struct AdditionalJobFactory {
@@ -25,7 +25,7 @@ impl AdditionalJobFactory {
         &self,
         files: &[AdditionalFile],
         source: &Source,
-        target: TargetFormat,
+        target: impl TranscodeTarget,
     ) -> Result<Vec<Job>, Error> {
         let mut jobs = Vec::new();
         for (index, file) in files.iter().enumerate() {
@@ -43,14 +43,14 @@ impl AdditionalJobFactory {
         index: usize,
         file: &AdditionalFile,
         source: &Source,
-        target: TargetFormat,
+        target: impl TranscodeTarget,
     ) -> Result<Option<Job>, Error> {
         let source_path = file.path.clone();
         let output_dir = self
             .paths
-            .get_transcode_target_dir(source, target)
-            .join(&file.sub_dir);
-        let mut output_path = output_dir.join(&file.file_name);
+            .get_staged_target_dir(source, target)
+            .join(self.paths.get_output_sub_dir(&file.sub_dir));
+        let mut output_path = output_dir.join(self.paths.get_output_name(&file.file_name));
         let size = file.get_size().await?;
         let max_file_size = self
             .file_options
@@ -63,7 +63,7 @@ impl AdditionalJobFactory {
             .expect("no_image_compression should be set");
         create_dir_all(&output_dir)
             .await
-            .map_err(|e| io_error(e, "create directories for additional file"))?;
+            .map_err(|e| path_error(e, "create directories for additional file", &output_dir))?;
         let extension = source_path
             .extension()
             .expect("Source has extension")
@@ -78,24 +78,15 @@ impl AdditionalJobFactory {
                     source_path.display()
                 );
             }
-            let hard_link_option = self
+            let strategy = self
                 .copy_options
-                .hard_link
-                .expect("hard_link should be set");
-            let verb = if hard_link_option {
-                hard_link(&source_path, &output_path)
-                    .await
-                    .map_err(|e| io_error(e, "hard link additional file"))?;
-                "Hard Linked"
-            } else {
-                copy(&source_path, &output_path)
-                    .await
-                    .map_err(|e| io_error(e, "copy additional file"))?;
-                "Copied"
-            };
+                .link_additional
+                .expect("link_additional should be set")
+                .execute(&source_path, &output_path)
+                .await?;
             trace!(
                 "{} {} to {}",
-                verb.bold(),
+                strategy.get_verb().bold(),
                 &source_path.display(),
                 &output_path.display()
             );
@@ -114,7 +105,7 @@ impl AdditionalJobFactory {
                 output_path.set_extension("jpg");
             }
         }
-        let id = format!("Additional {target:<7?}{index:>3}");
+        let id = format!("Additional {:<7}{index:>3}", target.to_string());
         let max_pixel_size = self
             .file_options
             .max_pixel_size
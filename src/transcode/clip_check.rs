@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::path::Path;
+
+use claxon::FlacReader;
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::claxon_error;
+
+/// Factor the source is oversampled by to find inter-sample peaks.
+const OVERSAMPLING: u32 = 4;
+
+/// Number of samples either side of an interpolated point used by the filter.
+const HALF_TAPS: usize = 8;
+
+/// Amplitude below which neighboring samples are not interpolated.
+///
+/// An inter-sample peak is rarely more than a few dB over the samples either side, so
+/// skipping quieter samples avoids most of the cost of oversampling.
+const INTERPOLATION_THRESHOLD: f64 = 0.5;
+
+/// Peaks of the source FLACs of a transcode.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ClipAnalysis {
+    /// Highest inter-sample peak of any track in dBFS.
+    pub true_peak: f64,
+    /// Number of tracks with an inter-sample peak over 0 dBFS.
+    pub clipping_tracks: usize,
+}
+
+impl ClipAnalysis {
+    /// Analyze the peaks of each FLAC.
+    pub fn read(paths: &[&Path]) -> Result<Self, Error> {
+        let mut analysis = Self {
+            true_peak: f64::NEG_INFINITY,
+            clipping_tracks: 0,
+        };
+        for path in paths {
+            let peak = to_decibels(get_true_peak(path)?);
+            if peak > 0.0 {
+                analysis.clipping_tracks += 1;
+            }
+            analysis.true_peak = analysis.true_peak.max(peak);
+        }
+        Ok(analysis)
+    }
+
+    /// Will an MP3 transcode likely clip?
+    #[must_use]
+    pub fn is_clipping(&self) -> bool {
+        self.clipping_tracks > 0
+    }
+}
+
+/// Measure the highest peak of a FLAC including the peaks between samples.
+///
+/// Returns the amplitude relative to full scale so `1.0` is 0 dBFS.
+pub fn get_true_peak(path: &Path) -> Result<f64, Error> {
+    let mut reader = FlacReader::open(path).map_err(|e| claxon_error(e, "read FLAC"))?;
+    let info = reader.streaminfo();
+    let mut meter = PeakMeter::new(info.channels, info.bits_per_sample);
+    for sample in reader.samples() {
+        meter.push(sample.map_err(|e| claxon_error(e, "decode FLAC"))?);
+    }
+    Ok(meter.get_peak())
+}
+
+/// Convert an amplitude relative to full scale to dBFS.
+#[must_use]
+pub fn to_decibels(amplitude: f64) -> f64 {
+    20.0 * amplitude.log10()
+}
+
+/// Convert a gain in dB to the factor samples are multiplied by.
+#[must_use]
+pub fn to_scale(gain: f64) -> f64 {
+    10.0_f64.powf(gain / 20.0)
+}
+
+/// Measure the true peak of interleaved samples by oversampling each channel.
+///
+/// The points between samples are interpolated with a windowed sinc filter in the manner
+/// of ITU-R BS.1770.
+pub struct PeakMeter {
+    full_scale: f64,
+    channels: Vec<VecDeque<f64>>,
+    channel: usize,
+    phases: Vec<[f64; HALF_TAPS * 2]>,
+    peak: f64,
+}
+
+impl PeakMeter {
+    #[must_use]
+    pub fn new(channels: u32, bits_per_sample: u32) -> Self {
+        let channels = usize::try_from(channels.max(1)).unwrap_or(1);
+        Self {
+            full_scale: f64::from(1_u32 << bits_per_sample.clamp(1, 32).saturating_sub(1)),
+            channels: vec![VecDeque::with_capacity(HALF_TAPS * 2 + 1); channels],
+            channel: 0,
+            phases: (1..OVERSAMPLING).map(get_coefficients).collect(),
+            peak: 0.0,
+        }
+    }
+
+    /// Add the next interleaved sample.
+    pub fn push(&mut self, sample: i32) {
+        let value = f64::from(sample) / self.full_scale;
+        self.peak = self.peak.max(value.abs());
+        let channel_count = self.channels.len();
+        let Some(history) = self.channels.get_mut(self.channel) else {
+            return;
+        };
+        self.channel = (self.channel + 1) % channel_count;
+        history.push_back(value);
+        if history.len() > HALF_TAPS * 2 {
+            history.pop_front();
+        }
+        if history.len() < HALF_TAPS * 2 {
+            return;
+        }
+        let before = history.get(HALF_TAPS - 1).copied().unwrap_or_default();
+        let after = history.get(HALF_TAPS).copied().unwrap_or_default();
+        if before.abs().max(after.abs()) < INTERPOLATION_THRESHOLD {
+            return;
+        }
+        for coefficients in &self.phases {
+            let interpolated: f64 = history
+                .iter()
+                .zip(coefficients)
+                .map(|(value, coefficient)| value * coefficient)
+                .sum();
+            self.peak = self.peak.max(interpolated.abs());
+        }
+    }
+
+    /// Get the highest peak relative to full scale.
+    #[must_use]
+    pub fn get_peak(&self) -> f64 {
+        self.peak
+    }
+}
+
+/// Get the filter coefficients to interpolate the point `phase / OVERSAMPLING` of the way
+/// between the two center samples.
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+fn get_coefficients(phase: u32) -> [f64; HALF_TAPS * 2] {
+    let offset = f64::from(phase) / f64::from(OVERSAMPLING);
+    let mut coefficients = [0.0; HALF_TAPS * 2];
+    for (index, coefficient) in coefficients.iter_mut().enumerate() {
+        let distance = index as f64 - (HALF_TAPS - 1) as f64 - offset;
+        let window = 0.5 * (1.0 + (PI * distance / HALF_TAPS as f64).cos());
+        *coefficient = get_sinc(distance) * window;
+    }
+    let sum: f64 = coefficients.iter().sum();
+    for coefficient in &mut coefficients {
+        *coefficient /= sum;
+    }
+    coefficients
+}
+
+fn get_sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, warn};
+
+use crate::fs::Collector;
+use crate::options::TargetOptions;
+use crate::source::Source;
+use crate::transcode::ClipAnalysis;
+
+/// Detect sources that will clip when transcoded to MP3.
+///
+/// Each source is analyzed once and the result shared by every target and track.
+pub struct ClipChecker {
+    options: Ref<TargetOptions>,
+    analyses: Mutex<BTreeMap<u32, Option<ClipAnalysis>>>,
+}
+
+#[injectable]
+impl ClipChecker {
+    #[must_use]
+    pub fn new(options: Ref<TargetOptions>) -> Self {
+        Self {
+            options,
+            analyses: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Get the peaks of a source if `clip_check` or `clip_gain` is set.
+    ///
+    /// Failures are logged as warnings as the check must not prevent transcoding.
+    #[must_use]
+    pub fn get(&self, source: &Source) -> Option<ClipAnalysis> {
+        if !self.options.clip_check.unwrap_or_default() && self.options.clip_gain.is_none() {
+            return None;
+        }
+        let mut analyses = self.analyses.lock().ok()?;
+        *analyses
+            .entry(source.torrent.id)
+            .or_insert_with(|| analyze(source))
+    }
+
+    /// Get the gain in dB to apply to MP3 targets of a source.
+    ///
+    /// Returns `None` unless `clip_gain` is set and the source clips.
+    #[must_use]
+    pub fn get_gain(&self, source: &Source) -> Option<f64> {
+        let gain = self.options.clip_gain?;
+        self.get(source)?.is_clipping().then_some(gain)
+    }
+}
+
+fn analyze(source: &Source) -> Option<ClipAnalysis> {
+    let flacs = Collector::get_flacs(&source.directory);
+    let paths: Vec<&Path> = flacs.iter().map(|flac| flac.path.as_path()).collect();
+    let analysis = match ClipAnalysis::read(&paths) {
+        Ok(analysis) => analysis,
+        Err(error) => {
+            warn!("{} to check for clipping: {error}", "Failed".bold());
+            return None;
+        }
+    };
+    if analysis.is_clipping() {
+        warn!(
+            "{} {} of {} tracks have inter-sample peaks over 0 dBFS, up to {:+.2} dBFS, so MP3 transcodes may clip",
+            "Clipping".bold(),
+            analysis.clipping_tracks,
+            flacs.len(),
+            analysis.true_peak
+        );
+    } else {
+        debug!(
+            "{} true peak of {:+.2} dBFS",
+            "No clipping".bold(),
+            analysis.true_peak
+        );
+    }
+    Some(analysis)
+}
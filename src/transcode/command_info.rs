@@ -15,7 +15,7 @@ impl CommandInfo {
     #[allow(clippy::wrong_self_convention)]
     pub fn to_command(self) -> Command {
         let mut cmd = Command::new(self.program);
-        cmd.args(self.args);
+        cmd.args(self.args).kill_on_drop(true);
         cmd
     }
 
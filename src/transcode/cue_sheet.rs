@@ -0,0 +1,378 @@
+use std::fmt::Write as _;
+use std::fs::{read, write};
+use std::path::Path;
+
+use rogue_logging::Error;
+
+use crate::errors::{error, path_error};
+
+/// Marker at the start of a FLAC file.
+const FLAC_MARKER: &[u8; 4] = b"fLaC";
+
+/// Type of the STREAMINFO metadata block.
+const STREAMINFO_BLOCK: u8 = 0;
+
+/// Type of the CUESHEET metadata block.
+const CUESHEET_BLOCK: u8 = 5;
+
+/// Flag set in the header of the last metadata block.
+const LAST_BLOCK_FLAG: u8 = 0x80;
+
+/// Length of the media catalog number field.
+const CATALOG_LENGTH: usize = 128;
+
+/// Length of the ISRC field of a track.
+const ISRC_LENGTH: usize = 12;
+
+/// Number of reserved bytes following the CD flag.
+const CUESHEET_RESERVED_LENGTH: usize = 258;
+
+/// Number of reserved bytes following the track flags.
+const TRACK_RESERVED_LENGTH: usize = 13;
+
+/// Number of reserved bytes following the index number.
+const INDEX_RESERVED_LENGTH: usize = 3;
+
+/// Track number of the lead-out track.
+const LEAD_OUT_TRACK: u8 = 170;
+
+/// Sample rate of a CD.
+const CD_SAMPLE_RATE: u32 = 44_100;
+
+/// Frames per second of a cue sheet timestamp.
+const FRAMES_PER_SECOND: u64 = 75;
+
+/// Embedded CUESHEET metadata block of a FLAC.
+///
+/// <https://xiph.org/flac/format.html#metadata_block_cuesheet>
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CueSheet {
+    /// Sample rate that the offsets are relative to.
+    pub sample_rate: u32,
+    pub catalog: String,
+    pub lead_in: u64,
+    pub is_cd: bool,
+    pub tracks: Vec<CueTrack>,
+}
+
+/// Track of a [`CueSheet`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CueTrack {
+    /// Offset in samples from the start of the audio.
+    pub offset: u64,
+    pub number: u8,
+    pub isrc: String,
+    pub is_audio: bool,
+    pub pre_emphasis: bool,
+    pub indices: Vec<CueIndex>,
+}
+
+/// Index point of a [`CueTrack`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CueIndex {
+    /// Offset in samples from the start of the track.
+    pub offset: u64,
+    pub number: u8,
+}
+
+/// A metadata block of a FLAC.
+struct MetadataBlock {
+    block_type: u8,
+    data: Vec<u8>,
+}
+
+impl CueSheet {
+    /// Read the CUESHEET block of a FLAC.
+    ///
+    /// Returns `None` if the FLAC doesn't have one.
+    pub fn read(path: &Path) -> Result<Option<CueSheet>, Error> {
+        let bytes = read(path).map_err(|e| path_error(e, "read FLAC", path))?;
+        let (blocks, _) = read_blocks(&bytes).map_err(|e| error("read FLAC", e))?;
+        let sample_rate = blocks
+            .iter()
+            .find(|block| block.block_type == STREAMINFO_BLOCK)
+            .and_then(|block| get_sample_rate(&block.data))
+            .ok_or_else(|| error("read FLAC", "FLAC does not have a STREAMINFO".to_owned()))?;
+        let cue_sheet = blocks
+            .iter()
+            .find(|block| block.block_type == CUESHEET_BLOCK)
+            .and_then(|block| CueSheet::from_bytes(&block.data, sample_rate));
+        Ok(cue_sheet)
+    }
+
+    /// Parse the data of a CUESHEET block.
+    #[must_use]
+    pub fn from_bytes(data: &[u8], sample_rate: u32) -> Option<CueSheet> {
+        let mut reader = Reader { data, position: 0 };
+        let catalog = reader.read_string(CATALOG_LENGTH)?;
+        let lead_in = reader.read_u64()?;
+        let is_cd = reader.read_u8()? & 0x80 != 0;
+        reader.skip(CUESHEET_RESERVED_LENGTH)?;
+        let track_count = reader.read_u8()?;
+        let mut tracks = Vec::new();
+        for _ in 0..track_count {
+            let offset = reader.read_u64()?;
+            let number = reader.read_u8()?;
+            let isrc = reader.read_string(ISRC_LENGTH)?;
+            let flags = reader.read_u8()?;
+            reader.skip(TRACK_RESERVED_LENGTH)?;
+            let index_count = reader.read_u8()?;
+            let mut indices = Vec::new();
+            for _ in 0..index_count {
+                let offset = reader.read_u64()?;
+                let number = reader.read_u8()?;
+                reader.skip(INDEX_RESERVED_LENGTH)?;
+                indices.push(CueIndex { offset, number });
+            }
+            tracks.push(CueTrack {
+                offset,
+                number,
+                isrc,
+                is_audio: flags & 0x80 == 0,
+                pre_emphasis: flags & 0x40 != 0,
+                indices,
+            });
+        }
+        Some(CueSheet {
+            sample_rate,
+            catalog,
+            lead_in,
+            is_cd,
+            tracks,
+        })
+    }
+
+    /// Get the data of a CUESHEET block.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        push_string(&mut data, &self.catalog, CATALOG_LENGTH);
+        data.extend(self.lead_in.to_be_bytes());
+        data.push(if self.is_cd { 0x80 } else { 0 });
+        data.extend([0; CUESHEET_RESERVED_LENGTH]);
+        data.push(u8::try_from(self.tracks.len()).unwrap_or(u8::MAX));
+        for track in &self.tracks {
+            data.extend(track.offset.to_be_bytes());
+            data.push(track.number);
+            push_string(&mut data, &track.isrc, ISRC_LENGTH);
+            let mut flags = 0;
+            if !track.is_audio {
+                flags |= 0x80;
+            }
+            if track.pre_emphasis {
+                flags |= 0x40;
+            }
+            data.push(flags);
+            data.extend([0; TRACK_RESERVED_LENGTH]);
+            data.push(u8::try_from(track.indices.len()).unwrap_or(u8::MAX));
+            for index in &track.indices {
+                data.extend(index.offset.to_be_bytes());
+                data.push(index.number);
+                data.extend([0; INDEX_RESERVED_LENGTH]);
+            }
+        }
+        data
+    }
+
+    /// Convert the offsets to a different sample rate.
+    ///
+    /// The CD flag is cleared unless the new sample rate is that of a CD.
+    #[allow(clippy::integer_division)]
+    #[must_use]
+    pub fn resample(mut self, sample_rate: u32) -> CueSheet {
+        if sample_rate == self.sample_rate || self.sample_rate == 0 {
+            return self;
+        }
+        let scale = |offset: u64| {
+            let scaled =
+                u128::from(offset) * u128::from(sample_rate) / u128::from(self.sample_rate);
+            u64::try_from(scaled).unwrap_or(u64::MAX)
+        };
+        self.lead_in = scale(self.lead_in);
+        for track in &mut self.tracks {
+            track.offset = scale(track.offset);
+            for index in &mut track.indices {
+                index.offset = scale(index.offset);
+            }
+        }
+        self.is_cd = sample_rate == CD_SAMPLE_RATE && self.is_cd;
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Get the cue sheet as the text of a `.cue` file for `file_name`.
+    ///
+    /// The lead-out track is omitted.
+    #[must_use]
+    pub fn to_cue(&self, file_name: &str) -> String {
+        let mut output = String::new();
+        if !self.catalog.is_empty() {
+            let _ = writeln!(output, "CATALOG {}", self.catalog);
+        }
+        let _ = writeln!(
+            output,
+            "FILE \"{}\" {}",
+            file_name.replace('"', "'"),
+            get_file_type(file_name)
+        );
+        for track in &self.tracks {
+            if track.number == LEAD_OUT_TRACK {
+                continue;
+            }
+            let mode = if track.is_audio {
+                "AUDIO"
+            } else {
+                "MODE1/2352"
+            };
+            let _ = writeln!(output, "  TRACK {:02} {mode}", track.number);
+            if !track.isrc.is_empty() {
+                let _ = writeln!(output, "    ISRC {}", track.isrc);
+            }
+            if track.pre_emphasis {
+                let _ = writeln!(output, "    FLAGS PRE");
+            }
+            for index in &track.indices {
+                let timestamp = self.get_timestamp(track.offset + index.offset);
+                let _ = writeln!(output, "    INDEX {:02} {timestamp}", index.number);
+            }
+        }
+        output
+    }
+
+    /// Get an offset in samples as an `mm:ss:ff` timestamp.
+    #[allow(clippy::integer_division)]
+    fn get_timestamp(&self, offset: u64) -> String {
+        let rate = u64::from(self.sample_rate.max(1));
+        let frames = offset * FRAMES_PER_SECOND / rate;
+        let seconds = frames / FRAMES_PER_SECOND;
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 60,
+            seconds % 60,
+            frames % FRAMES_PER_SECOND
+        )
+    }
+}
+
+/// Replace the CUESHEET block of a FLAC.
+///
+/// The block is inserted after the STREAMINFO. The audio frames are unchanged.
+pub fn write_cue_sheet(path: &Path, cue_sheet: &CueSheet) -> Result<(), Error> {
+    let action = "write cue sheet";
+    let bytes = read(path).map_err(|e| path_error(e, action, path))?;
+    let (mut blocks, audio_start) = read_blocks(&bytes).map_err(|e| error(action, e))?;
+    blocks.retain(|block| block.block_type != CUESHEET_BLOCK);
+    let position = blocks
+        .iter()
+        .position(|block| block.block_type == STREAMINFO_BLOCK)
+        .map_or(0, |index| index + 1);
+    blocks.insert(
+        position,
+        MetadataBlock {
+            block_type: CUESHEET_BLOCK,
+            data: cue_sheet.to_bytes(),
+        },
+    );
+    let audio = bytes.get(audio_start..).unwrap_or_default();
+    let mut output = Vec::with_capacity(bytes.len() + 1024);
+    output.extend(FLAC_MARKER);
+    let last = blocks.len().saturating_sub(1);
+    for (index, block) in blocks.iter().enumerate() {
+        let length = u32::try_from(block.data.len())
+            .map_err(|_| error(action, "Metadata block is too large".to_owned()))?;
+        let mut header = length.to_be_bytes();
+        header[0] = block.block_type | if index == last { LAST_BLOCK_FLAG } else { 0 };
+        output.extend(header);
+        output.extend(&block.data);
+    }
+    output.extend(audio);
+    write(path, output).map_err(|e| path_error(e, action, path))
+}
+
+/// Read the metadata blocks of a FLAC.
+///
+/// Returns the blocks and the position of the first audio frame.
+fn read_blocks(bytes: &[u8]) -> Result<(Vec<MetadataBlock>, usize), String> {
+    if bytes.get(..FLAC_MARKER.len()) != Some(FLAC_MARKER.as_slice()) {
+        return Err("File is not a FLAC".to_owned());
+    }
+    let mut position = FLAC_MARKER.len();
+    let mut blocks = Vec::new();
+    loop {
+        let header = bytes
+            .get(position..position + 4)
+            .ok_or_else(|| "FLAC metadata is truncated".to_owned())?;
+        let flags = header.first().copied().unwrap_or_default();
+        let length = header
+            .iter()
+            .skip(1)
+            .fold(0_usize, |length, byte| (length << 8) | usize::from(*byte));
+        position += 4;
+        let data = bytes
+            .get(position..position + length)
+            .ok_or_else(|| "FLAC metadata is truncated".to_owned())?;
+        blocks.push(MetadataBlock {
+            block_type: flags & !LAST_BLOCK_FLAG,
+            data: data.to_vec(),
+        });
+        position += length;
+        if flags & LAST_BLOCK_FLAG != 0 {
+            return Ok((blocks, position));
+        }
+    }
+}
+
+/// Get the sample rate from the data of a STREAMINFO block.
+fn get_sample_rate(data: &[u8]) -> Option<u32> {
+    let bytes = data.get(10..13)?;
+    let value = bytes
+        .iter()
+        .fold(0_u32, |value, byte| (value << 8) | u32::from(*byte));
+    Some(value >> 4)
+}
+
+fn get_file_type(file_name: &str) -> &'static str {
+    if file_name.to_lowercase().ends_with(".mp3") {
+        "MP3"
+    } else {
+        "WAVE"
+    }
+}
+
+fn push_string(data: &mut Vec<u8>, value: &str, length: usize) {
+    let mut bytes: Vec<u8> = value.bytes().take(length).collect();
+    bytes.resize(length, 0);
+    data.extend(bytes);
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, length: usize) -> Option<&[u8]> {
+        let bytes = self.data.get(self.position..self.position + length)?;
+        self.position += length;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, length: usize) -> Option<()> {
+        self.take(length).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.take(1)?.first().copied()
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().ok()?;
+        Some(u64::from_be_bytes(bytes))
+    }
+
+    fn read_string(&mut self, length: usize) -> Option<String> {
+        let bytes = self.take(length)?;
+        let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(length);
+        Some(String::from_utf8_lossy(bytes.get(..end)?).to_string())
+    }
+}
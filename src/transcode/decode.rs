@@ -2,6 +2,7 @@ use crate::dependencies::{FLAC, SOX};
 use crate::transcode::CommandInfo;
 use std::path::PathBuf;
 
+#[derive(Clone)]
 pub struct Decode {
     /// Path to the input file
     pub input: PathBuf,
@@ -10,44 +11,59 @@ pub struct Decode {
 }
 
 impl Decode {
-    /// Get the [`CommandInfo`] for the decode command.
+    /// Get the [`CommandInfo`] for each stage of the decode, reading the FLAC from stdin
+    /// and writing a WAV to stdout.
+    ///
+    /// The FLAC is decoded then resampled by a separate stage if `resample_rate` is set.
     #[must_use]
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_info(self) -> CommandInfo {
-        match self.resample_rate {
-            Some(rate) => decode_with_resample(self.input, rate),
-            None => decode_without_resample(self.input),
+    pub fn to_stages(self) -> Vec<CommandInfo> {
+        let mut stages = vec![decode_flac()];
+        if let Some(rate) = self.resample_rate {
+            stages.push(resample_wav(rate));
         }
+        stages
     }
 }
 
-fn decode_with_resample(input: PathBuf, resample_rate: u32) -> CommandInfo {
+/// Get the [`CommandInfo`] to decode a FLAC from stdin to a WAV on stdout.
+#[must_use]
+pub fn decode_flac() -> CommandInfo {
     CommandInfo {
-        program: SOX.to_owned(),
-        args: vec![
-            input.to_string_lossy().to_string(),
-            "-G".to_owned(),
-            "-b".to_owned(),
-            "16".to_owned(),
-            "-t".to_owned(),
-            "wav".to_owned(),
-            "-".to_owned(),
-            "rate".to_owned(),
-            "-v".to_owned(),
-            "-L".to_owned(),
-            resample_rate.to_string(),
-            "dither".to_owned(),
-        ],
+        program: FLAC.to_owned(),
+        args: vec!["-dcs".to_owned(), "-".to_owned()],
     }
 }
 
-fn decode_without_resample(input: PathBuf) -> CommandInfo {
+/// Get the arguments for `sox` to resample to 16 bit at `resample_rate`.
+#[must_use]
+pub fn get_sox_effect_args(resample_rate: u32) -> Vec<String> {
+    vec![
+        "rate".to_owned(),
+        "-v".to_owned(),
+        "-L".to_owned(),
+        resample_rate.to_string(),
+        "dither".to_owned(),
+    ]
+}
+
+fn resample_wav(resample_rate: u32) -> CommandInfo {
     CommandInfo {
-        program: FLAC.to_owned(),
-        args: vec![
-            "-dcs".to_owned(),
-            "--".to_owned(),
-            input.to_string_lossy().to_string(),
-        ],
+        program: SOX.to_owned(),
+        args: [
+            vec![
+                "-t".to_owned(),
+                "wav".to_owned(),
+                "-".to_owned(),
+                "-G".to_owned(),
+                "-b".to_owned(),
+                "16".to_owned(),
+                "-t".to_owned(),
+                "wav".to_owned(),
+                "-".to_owned(),
+            ],
+            get_sox_effect_args(resample_rate),
+        ]
+        .concat(),
     }
 }
@@ -1,15 +1,18 @@
 use crate::dependencies::{FLAC, LAME};
-use crate::formats::TargetFormat;
-use crate::formats::TargetFormat::{Flac, V0, _320};
+use crate::formats::TargetFormat::{Flac, _256, _320, V0, V1, V2};
+use crate::formats::{CustomTarget, TargetFormat};
 use crate::transcode::CommandInfo;
 use std::path::PathBuf;
 
 /// Information required to create an encode command [`Command`].
+#[derive(Clone)]
 pub struct Encode {
     /// Path to the input file
     pub output: PathBuf,
     /// Optional resample rate
     pub format: TargetFormat,
+    /// Factor to scale the samples of an MP3 by to prevent clipping.
+    pub scale: Option<f64>,
 }
 
 impl Encode {
@@ -17,21 +20,66 @@ impl Encode {
     #[must_use]
     #[allow(clippy::wrong_self_convention)]
     pub fn to_info(self) -> CommandInfo {
-        match self.format {
-            Flac => encode_flac(self.output),
+        let info = match self.format {
+            Flac => return encode_flac(self.output),
             _320 => encode_mp3_320(self.output),
-            V0 => encode_mp3_v0(self.output),
-        }
+            V0 => encode_mp3_vbr(self.output, 0),
+            V1 => encode_mp3_vbr(self.output, 1),
+            V2 => encode_mp3_vbr(self.output, 2),
+            _256 => encode_mp3_abr(self.output, 256),
+        };
+        add_scale(info, self.scale)
     }
 }
 
-fn encode_mp3_v0(output_path: PathBuf) -> CommandInfo {
+/// Information required to encode an MP3 of a [`CustomTarget`].
+#[derive(Clone)]
+pub struct CustomEncode {
+    /// Path to the output file
+    pub output: PathBuf,
+    pub target: CustomTarget,
+    /// Factor to scale the samples by to prevent clipping.
+    pub scale: Option<f64>,
+}
+
+impl CustomEncode {
+    /// Get the [`CommandInfo`] for the encode command.
+    #[must_use]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_info(self) -> CommandInfo {
+        let info = CommandInfo {
+            program: LAME.to_owned(),
+            args: [
+                vec!["-S".to_owned()],
+                self.target.get_lame_args(),
+                vec![
+                    "--ignore-tag-errors".to_owned(),
+                    "-".to_owned(),
+                    self.output.to_string_lossy().to_string(),
+                ],
+            ]
+            .concat(),
+        };
+        add_scale(info, self.scale)
+    }
+}
+
+/// Add `--scale` to the arguments of `lame` if a scale is set.
+fn add_scale(mut info: CommandInfo, scale: Option<f64>) -> CommandInfo {
+    if let Some(scale) = scale {
+        info.args
+            .splice(1..1, ["--scale".to_owned(), format!("{scale:.4}")]);
+    }
+    info
+}
+
+fn encode_mp3_vbr(output_path: PathBuf, quality: u8) -> CommandInfo {
     CommandInfo {
         program: LAME.to_owned(),
         args: vec![
             "-S".to_owned(),
             "-V".to_owned(),
-            "0".to_owned(),
+            quality.to_string(),
             "--vbr-new".to_owned(),
             "--ignore-tag-errors".to_owned(),
             "-".to_owned(),
@@ -55,6 +103,21 @@ fn encode_mp3_320(output_path: PathBuf) -> CommandInfo {
     }
 }
 
+fn encode_mp3_abr(output_path: PathBuf, bit_rate: u16) -> CommandInfo {
+    CommandInfo {
+        program: LAME.to_owned(),
+        args: vec![
+            "-S".to_owned(),
+            "-h".to_owned(),
+            "--abr".to_owned(),
+            bit_rate.to_string(),
+            "--ignore-tag-errors".to_owned(),
+            "-".to_owned(),
+            output_path.to_string_lossy().to_string(),
+        ],
+    }
+}
+
 fn encode_flac(output_path: PathBuf) -> CommandInfo {
     CommandInfo {
         program: FLAC.to_owned(),
@@ -1,10 +1,19 @@
 pub use additional_job::*;
 pub use additional_job_factory::*;
+pub use clip_check::*;
+pub use clip_checker::*;
 pub use command_info::*;
+pub use cue_sheet::*;
 pub use decode::*;
 pub use encode::*;
+pub use pipeline::*;
+pub use provenance::*;
+pub use provenance_provider::*;
 pub use resample::*;
+pub use resume_check::*;
+pub use sample_check::*;
 pub use streaminfo_helpers::*;
+pub use track_prefetcher::*;
 pub use transcode_command::*;
 pub use transcode_job::*;
 pub use transcode_job_factory::*;
@@ -13,14 +22,23 @@ pub use variant::*;
 
 pub(crate) mod additional_job;
 pub(crate) mod additional_job_factory;
+pub(crate) mod clip_check;
+pub(crate) mod clip_checker;
 pub(crate) mod command_info;
+pub(crate) mod cue_sheet;
 pub(crate) mod decode;
 pub(crate) mod encode;
+pub(crate) mod pipeline;
+pub(crate) mod provenance;
+pub(crate) mod provenance_provider;
 pub(crate) mod resample;
 mod resize;
+pub(crate) mod resume_check;
+pub(crate) mod sample_check;
 mod streaminfo_helpers;
 #[cfg(test)]
 mod tests;
+pub(crate) mod track_prefetcher;
 pub(crate) mod transcode_command;
 pub(crate) mod transcode_job;
 pub(crate) mod transcode_job_factory;
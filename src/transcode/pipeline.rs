@@ -0,0 +1,177 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process::{Output, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use futures::future::join_all;
+use futures::Future;
+use log::trace;
+use rogue_logging::Error;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::join;
+use tokio::process::Child;
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::errors::{command_error, io_error, path_error};
+use crate::transcode::CommandInfo;
+
+/// Size of each chunk passed from one stage to the next.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks that can wait between two stages.
+///
+/// A stage can run up to `1MiB` ahead of the next before it waits.
+const CHANNEL_CAPACITY: usize = 16;
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Source track to write to the first stage of a [`Pipeline`].
+pub enum PipelineInput {
+    /// Contents read ahead into memory.
+    Memory(Arc<Vec<u8>>),
+    /// Path to read the track from.
+    File(PathBuf),
+}
+
+/// Output of a stage of a [`Pipeline`] once it exited.
+pub struct StageOutput {
+    pub program: String,
+    pub line: String,
+    pub output: Output,
+    /// Wall time from the start of the pipeline until the stage exited.
+    pub elapsed: Duration,
+}
+
+/// Commands that each read from stdin and write to stdout, connected by bounded
+/// channels.
+///
+/// The source track is read in chunks and sent to the first stage, and the stdout of
+/// each stage is sent to the stdin of the next. Each stage runs concurrently so a slow
+/// stage holds up the earlier stages only once its channel is full.
+///
+/// The last stage writes its output to a file so its stdout is discarded.
+pub struct Pipeline {
+    pub input: PipelineInput,
+    pub stages: Vec<CommandInfo>,
+}
+
+impl Pipeline {
+    /// Execute every stage and wait for them to exit.
+    ///
+    /// Returns the output of each stage in order. A stage that could not be spawned
+    /// is an error, but a stage that failed is left for the caller to check.
+    pub async fn execute(self) -> Result<Vec<StageOutput>, Error> {
+        let source: Box<dyn AsyncRead + Send + Unpin> = match self.input {
+            PipelineInput::Memory(data) => Box::new(Cursor::new(SharedTrack(data))),
+            PipelineInput::File(path) => Box::new(
+                File::open(&path)
+                    .await
+                    .map_err(|e| path_error(e, "open source track", &path))?,
+            ),
+        };
+        let count = self.stages.len();
+        let (sender, mut receiver) = channel(CHANNEL_CAPACITY);
+        let mut tasks: Vec<Task> = vec![Box::pin(read_chunks(source, sender))];
+        let mut children = Vec::new();
+        for (index, info) in self.stages.into_iter().enumerate() {
+            let is_last = index + 1 == count;
+            let program = info.program.clone();
+            let line = info.display();
+            trace!("Executing pipeline stage: {line}");
+            let mut child = info
+                .to_command()
+                .stdin(Stdio::piped())
+                .stdout(if is_last {
+                    Stdio::null()
+                } else {
+                    Stdio::piped()
+                })
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| command_error(e, "spawn pipeline stage", &program))?;
+            let stdin = child.stdin.take().expect("should be able to take stdin");
+            tasks.push(Box::pin(write_chunks(receiver, stdin)));
+            let (sender, next) = channel(CHANNEL_CAPACITY);
+            if let Some(stdout) = child.stdout.take() {
+                tasks.push(Box::pin(read_chunks(stdout, sender)));
+            }
+            receiver = next;
+            children.push((program, line, child));
+        }
+        let start = Instant::now();
+        let waits = children
+            .into_iter()
+            .map(|(program, line, child)| wait_for_stage(program, line, child, start));
+        let (_, outputs) = join!(join_all(tasks), join_all(waits));
+        outputs.into_iter().collect()
+    }
+}
+
+async fn wait_for_stage(
+    program: String,
+    line: String,
+    child: Child,
+    start: Instant,
+) -> Result<StageOutput, Error> {
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| io_error(e, "wait for pipeline stage"))?;
+    Ok(StageOutput {
+        program,
+        line,
+        output,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Read chunks until the end and send them to the next stage.
+///
+/// Stops early if the next stage closed its stdin so the previous stage is not left
+/// writing to a full pipe.
+async fn read_chunks<R: AsyncRead + Unpin>(mut reader: R, sender: Sender<Vec<u8>>) {
+    loop {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        match reader.read(&mut chunk).await {
+            Ok(0) => return,
+            Ok(length) => {
+                chunk.truncate(length);
+                if sender.send(chunk).await.is_err() {
+                    return;
+                }
+            }
+            Err(error) => {
+                trace!("{} to read pipeline chunk: {error}", "Failed".bold());
+                return;
+            }
+        }
+    }
+}
+
+/// Write each chunk to the stdin of a stage then close it.
+///
+/// A failure is only logged as the stage reports the incomplete input itself.
+async fn write_chunks<W: AsyncWrite + Unpin>(mut receiver: Receiver<Vec<u8>>, mut writer: W) {
+    while let Some(chunk) = receiver.recv().await {
+        if let Err(error) = writer.write_all(&chunk).await {
+            trace!("{} to write pipeline chunk: {error}", "Failed".bold());
+            return;
+        }
+    }
+    if let Err(error) = writer.shutdown().await {
+        trace!("{} to close pipeline stdin: {error}", "Failed".bold());
+    }
+}
+
+/// Contents of a track read ahead into memory, shared with the other jobs of the track.
+struct SharedTrack(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedTrack {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
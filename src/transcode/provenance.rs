@@ -0,0 +1,119 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use clap::ValueEnum;
+use lofty::config::WriteOptions;
+use lofty::file::TaggedFileExt;
+use lofty::prelude::TagExt;
+use lofty::read_from_path;
+use lofty::tag::ItemKey;
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::built_info::{PKG_NAME, PKG_REPOSITORY, PKG_VERSION};
+use crate::errors::error;
+use crate::formats::TargetFormat;
+
+/// File name of the provenance sidecar written to a transcode directory.
+pub const PROVENANCE_FILE_NAME: &str = "transcode.txt";
+
+/// Tag of each output the provenance of the transcode is written to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvenanceTag {
+    /// Don't tag the provenance.
+    #[default]
+    None,
+    /// `ENCODER` of a FLAC or `TSSE` of an MP3.
+    Encoder,
+    /// `COMMENT` of a FLAC or `COMM` of an MP3.
+    Comment,
+}
+
+impl ProvenanceTag {
+    /// Get the key of the tag, or `None` if the provenance isn't tagged.
+    #[must_use]
+    pub fn get_key(self) -> Option<ItemKey> {
+        match self {
+            ProvenanceTag::None => None,
+            ProvenanceTag::Encoder => Some(ItemKey::EncoderSoftware),
+            ProvenanceTag::Comment => Some(ItemKey::Comment),
+        }
+    }
+}
+
+/// Versions of caesura and the encoders used by a transcode.
+///
+/// Recorded in the outputs so a trump dispute can be resolved from the files themselves.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Provenance {
+    /// Tag of each output the provenance is written to.
+    pub tag: ProvenanceTag,
+    /// First line of the `--version` output of each encoder.
+    pub encoders: Vec<String>,
+}
+
+impl Provenance {
+    /// Get the key and value of the provenance tag of an output transcoded by `command`.
+    ///
+    /// Returns `None` if the provenance isn't tagged.
+    #[must_use]
+    pub fn get_tag(&self, command: &str) -> Option<(ItemKey, String)> {
+        let key = self.tag.get_key()?;
+        Some((key, self.get_tag_value(command)))
+    }
+
+    /// Get the value of the provenance tag of an output transcoded by `command`.
+    #[must_use]
+    pub fn get_tag_value(&self, command: &str) -> String {
+        let mut value = format!("{PKG_NAME} v{PKG_VERSION}");
+        if !self.encoders.is_empty() {
+            let _ = write!(value, " ({})", self.encoders.join(", "));
+        }
+        let _ = write!(value, ": {command}");
+        value
+    }
+
+    /// Get the contents of the [`PROVENANCE_FILE_NAME`] sidecar of a transcode.
+    ///
+    /// `commands` are the distinct commands used to transcode the tracks.
+    #[must_use]
+    pub fn get_file_contents(
+        &self,
+        source_url: &str,
+        format: TargetFormat,
+        commands: &[String],
+    ) -> String {
+        let mut contents = String::new();
+        let _ = writeln!(contents, "Transcoded with {PKG_NAME} v{PKG_VERSION}");
+        let _ = writeln!(contents, "{PKG_REPOSITORY}");
+        let _ = writeln!(contents);
+        let _ = writeln!(contents, "Source: {source_url}");
+        let _ = writeln!(contents, "Format: {}", format.get_name());
+        if !self.encoders.is_empty() {
+            let _ = writeln!(contents);
+            let _ = writeln!(contents, "Encoders:");
+            for encoder in &self.encoders {
+                let _ = writeln!(contents, "{encoder}");
+            }
+        }
+        let _ = writeln!(contents);
+        let _ = writeln!(contents, "Commands:");
+        for command in commands {
+            let _ = writeln!(contents, "{command}");
+        }
+        contents
+    }
+}
+
+/// Write a text tag to the primary tag of a file.
+pub fn write_tag_to_path(path: &Path, key: ItemKey, value: String) -> Result<(), Error> {
+    let action = "write provenance tag";
+    let mut file = read_from_path(path).map_err(|e| error(action, e.to_string()))?;
+    let Some(tag) = file.primary_tag_mut() else {
+        return Ok(());
+    };
+    tag.insert_text(key, value);
+    tag.save_to_path(path, WriteOptions::default())
+        .map_err(|e| error(action, e.to_string()))
+}
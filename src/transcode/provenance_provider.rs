@@ -0,0 +1,61 @@
+use di::{injectable, Ref};
+use tokio::sync::OnceCell;
+
+use crate::dependencies::{get_dependency_version, FLAC, LAME, SOX};
+use crate::options::TargetOptions;
+use crate::transcode::{Provenance, ProvenanceTag};
+
+/// Encoders whose versions are recorded in the [`Provenance`].
+const ENCODERS: [&str; 3] = [FLAC, LAME, SOX];
+
+/// Get the [`Provenance`] recorded in the outputs of a transcode.
+///
+/// The encoder versions are only read once.
+pub struct ProvenanceProvider {
+    options: Ref<TargetOptions>,
+    provenance: OnceCell<Ref<Provenance>>,
+}
+
+#[injectable]
+impl ProvenanceProvider {
+    #[must_use]
+    pub fn new(options: Ref<TargetOptions>) -> Self {
+        Self {
+            options,
+            provenance: OnceCell::new(),
+        }
+    }
+
+    /// Is the provenance tagged or written to a sidecar?
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.options
+            .provenance_tag
+            .and_then(ProvenanceTag::get_key)
+            .is_some()
+            || self.options.provenance_file.unwrap_or_default()
+    }
+
+    /// Get the [`Provenance`] if `provenance_tag` or `provenance_file` is set.
+    pub async fn get(&self) -> Option<Ref<Provenance>> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let provenance = self
+            .provenance
+            .get_or_init(|| async {
+                let mut encoders = Vec::new();
+                for program in ENCODERS {
+                    if let Some(version) = get_dependency_version(program).await {
+                        if !version.is_empty() {
+                            encoders.push(version);
+                        }
+                    }
+                }
+                let tag = self.options.provenance_tag.unwrap_or_default();
+                Ref::new(Provenance { tag, encoders })
+            })
+            .await;
+        Some(provenance.clone())
+    }
+}
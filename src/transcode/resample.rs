@@ -1,8 +1,9 @@
 use crate::dependencies::SOX;
-use crate::transcode::CommandInfo;
+use crate::transcode::{decode_flac, get_sox_effect_args, CommandInfo};
 use std::path::PathBuf;
 
 /// Information needed to resample a FLAC.
+#[derive(Clone)]
 pub struct Resample {
     /// Path to the input file
     pub input: PathBuf,
@@ -14,24 +15,30 @@ pub struct Resample {
 }
 
 impl Resample {
-    /// Create a new resample command.
+    /// Get the [`CommandInfo`] for each stage of the resample, reading the FLAC from
+    /// stdin.
+    ///
+    /// The FLAC is decoded by one stage then resampled and encoded to the output by the
+    /// next.
     #[must_use]
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_info(self) -> CommandInfo {
-        CommandInfo {
+    pub fn to_stages(self) -> Vec<CommandInfo> {
+        let resample = CommandInfo {
             program: SOX.to_owned(),
-            args: vec![
-                self.input.to_string_lossy().to_string(),
-                "-G".to_owned(),
-                "-b".to_owned(),
-                "16".to_owned(),
-                self.output.to_string_lossy().to_string(),
-                "rate".to_owned(),
-                "-v".to_owned(),
-                "-L".to_owned(),
-                self.resample_rate.to_string(),
-                "dither".to_owned(),
-            ],
-        }
+            args: [
+                vec![
+                    "-t".to_owned(),
+                    "wav".to_owned(),
+                    "-".to_owned(),
+                    "-G".to_owned(),
+                    "-b".to_owned(),
+                    "16".to_owned(),
+                    self.output.to_string_lossy().to_string(),
+                ],
+                get_sox_effect_args(self.resample_rate),
+            ]
+            .concat(),
+        };
+        vec![decode_flac(), resample]
     }
 }
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use claxon::FlacReader;
+use lofty::file::TaggedFileExt;
+use lofty::prelude::Accessor;
+use lofty::read_from_path;
+use rogue_logging::Error;
+
+use crate::errors::{claxon_error, error};
+use crate::formats::TranscodeTarget;
+use crate::transcode::{check_sample_count, CueSheet, SampleCount};
+
+/// Check an output left by an earlier attempt at a transcode is complete.
+///
+/// The output must have as many samples as its source and be tagged. A FLAC is decoded
+/// so a corrupt frame is found. If the source has a cue sheet then it must have been
+/// written to the FLAC or to a sidecar `.cue` of an MP3.
+///
+/// Returns an [`Error`] describing why the output must be transcoded again.
+pub fn check_existing_output(
+    path: &Path,
+    format: impl TranscodeTarget,
+    source_samples: Option<SampleCount>,
+    has_cue_sheet: bool,
+) -> Result<(), Error> {
+    let action = "check existing output";
+    if !path.is_file() {
+        return Err(error(
+            action,
+            format!("Output does not exist: {}", path.display()),
+        ));
+    }
+    if let Some(source_samples) = source_samples {
+        check_sample_count(path, format, source_samples)?;
+    }
+    if !format.is_mp3() {
+        let mut reader =
+            FlacReader::open(path).map_err(|e| claxon_error(e, "read existing output"))?;
+        for sample in reader.samples() {
+            sample.map_err(|e| claxon_error(e, "decode existing output"))?;
+        }
+    }
+    let file = read_from_path(path).map_err(|e| error(action, e.to_string()))?;
+    if file.primary_tag().and_then(Accessor::title).is_none() {
+        return Err(error(
+            action,
+            format!("Output is not tagged: {}", path.display()),
+        ));
+    }
+    if has_cue_sheet {
+        let has_output_cue_sheet = if format.is_mp3() {
+            path.with_extension("cue").is_file()
+        } else {
+            CueSheet::read(path)?.is_some()
+        };
+        if !has_output_cue_sheet {
+            return Err(error(
+                action,
+                format!("Output is missing its cue sheet: {}", path.display()),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::path::Path;
+
+use claxon::FlacReader;
+use lofty::file::AudioFile;
+use lofty::read_from_path;
+use rogue_logging::Error;
+
+use crate::errors::{claxon_error, error};
+use crate::formats::TranscodeTarget;
+
+/// Number of samples in an MP3 frame.
+const MP3_FRAME_SAMPLES: u64 = 1152;
+
+/// Number of frames of encoder delay and padding an MP3 may differ from its source by.
+///
+/// LAME adds 1105 samples of delay and pads the last frame, and the duration of an MP3 is
+/// only known to the millisecond.
+const MP3_TOLERANCE_FRAMES: u64 = 3;
+
+/// Number of samples a resampled FLAC may differ from its source by due to rounding.
+const RESAMPLE_TOLERANCE: u64 = 2;
+
+/// Number of samples and sample rate of a source FLAC.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SampleCount {
+    pub samples: u64,
+    pub sample_rate: u32,
+}
+
+impl SampleCount {
+    /// Get the number of samples at another sample rate.
+    #[must_use]
+    #[allow(clippy::integer_division)]
+    pub fn at_rate(&self, sample_rate: u32) -> u64 {
+        if sample_rate == self.sample_rate || self.sample_rate == 0 {
+            return self.samples;
+        }
+        let samples = u128::from(self.samples) * u128::from(sample_rate);
+        let rate = u128::from(self.sample_rate);
+        u64::try_from((samples + rate / 2) / rate).unwrap_or(u64::MAX)
+    }
+}
+
+/// Get the number of samples an output may differ from its source by.
+#[must_use]
+pub fn get_sample_tolerance(format: impl TranscodeTarget, resampled: bool) -> u64 {
+    if format.is_mp3() {
+        MP3_FRAME_SAMPLES * MP3_TOLERANCE_FRAMES
+    } else if resampled {
+        RESAMPLE_TOLERANCE
+    } else {
+        0
+    }
+}
+
+/// Check the output of a transcode has as many samples as its source.
+///
+/// A decoder or encoder that fails part way through can leave a truncated output that
+/// otherwise looks valid, breaking gapless playback of the album.
+pub fn check_sample_count(
+    path: &Path,
+    format: impl TranscodeTarget,
+    source: SampleCount,
+) -> Result<(), Error> {
+    let action = "check sample count";
+    let output = if format.is_mp3() {
+        get_mp3_sample_count(path)?
+    } else {
+        get_flac_sample_count(path)?
+    };
+    let expected = source.at_rate(output.sample_rate);
+    let tolerance = get_sample_tolerance(format, output.sample_rate != source.sample_rate);
+    if output.samples.abs_diff(expected) > tolerance {
+        return Err(error(
+            action,
+            format!(
+                "Output has {} samples but the source has {expected}: {}",
+                output.samples,
+                path.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn get_flac_sample_count(path: &Path) -> Result<SampleCount, Error> {
+    let reader = FlacReader::open(path).map_err(|e| claxon_error(e, "read output FLAC"))?;
+    let info = reader.streaminfo();
+    Ok(SampleCount {
+        samples: info.samples.unwrap_or_default(),
+        sample_rate: info.sample_rate,
+    })
+}
+
+/// Get the number of samples of an MP3 from its duration.
+#[allow(clippy::integer_division)]
+fn get_mp3_sample_count(path: &Path) -> Result<SampleCount, Error> {
+    let file = read_from_path(path).map_err(|e| error("read output MP3", e.to_string()))?;
+    let properties = file.properties();
+    let sample_rate = properties.sample_rate().unwrap_or_default();
+    let millis = u64::try_from(properties.duration().as_millis()).unwrap_or(u64::MAX);
+    Ok(SampleCount {
+        samples: millis * u64::from(sample_rate) / 1000,
+        sample_rate,
+    })
+}
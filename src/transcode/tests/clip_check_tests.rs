@@ -0,0 +1,71 @@
+use std::f64::consts::PI;
+use std::path::PathBuf;
+
+use crate::formats::TargetFormat;
+use crate::transcode::{to_decibels, to_scale, Encode, PeakMeter};
+
+/// Push a stereo sine wave of a quarter of the sample rate to a 16-bit [`PeakMeter`].
+///
+/// With a phase of 45° every sample is at 71% of the amplitude so the peaks are between
+/// the samples.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+fn get_sine_peak(amplitude: f64) -> f64 {
+    let mut meter = PeakMeter::new(2, 16);
+    for index in 0..1000 {
+        let phase = PI / 2.0 * f64::from(index) + PI / 4.0;
+        let sample = (amplitude * 32768.0 * phase.sin()).round() as i32;
+        meter.push(sample);
+        meter.push(sample);
+    }
+    meter.get_peak()
+}
+
+#[test]
+fn peak_meter_inter_sample_peak() {
+    // Act
+    let peak = get_sine_peak(1.2);
+
+    // Assert
+    assert!(peak > 1.15, "should find the peak between samples: {peak}");
+    assert!(peak < 1.25, "should not overshoot the peak: {peak}");
+}
+
+#[test]
+fn peak_meter_quiet() {
+    // Act
+    let peak = get_sine_peak(0.4);
+
+    // Assert
+    assert!(
+        (peak - 0.4 * 0.5_f64.sqrt()).abs() < 0.001,
+        "should only measure the samples below the threshold: {peak}"
+    );
+}
+
+#[test]
+fn clip_check_conversions() {
+    // Act
+    let decibels = to_decibels(0.5);
+    let scale = to_scale(-6.0);
+
+    // Assert
+    assert!((decibels + 6.02).abs() < 0.01);
+    assert!((scale - 0.501).abs() < 0.001);
+}
+
+#[test]
+fn encode_mp3_with_scale() {
+    // Arrange
+    let encode = Encode {
+        output: PathBuf::from("output.mp3"),
+        format: TargetFormat::V0,
+        scale: Some(to_scale(-1.0)),
+    };
+
+    // Act
+    let info = encode.to_info();
+
+    // Assert
+    assert_eq!(info.args.get(1).map(String::as_str), Some("--scale"));
+    assert_eq!(info.args.get(2).map(String::as_str), Some("0.8913"));
+}
@@ -0,0 +1,127 @@
+use std::fs::{read, write};
+
+use crate::testing::TempDirectory;
+use crate::transcode::{write_cue_sheet, CueIndex, CueSheet, CueTrack};
+
+fn get_cue_sheet() -> CueSheet {
+    CueSheet {
+        sample_rate: 44_100,
+        catalog: "0123456789012".to_owned(),
+        lead_in: 88_200,
+        is_cd: true,
+        tracks: vec![
+            CueTrack {
+                offset: 0,
+                number: 1,
+                isrc: "GBAAA1000001".to_owned(),
+                is_audio: true,
+                pre_emphasis: false,
+                indices: vec![CueIndex {
+                    offset: 0,
+                    number: 1,
+                }],
+            },
+            CueTrack {
+                offset: 10_584_000,
+                number: 170,
+                is_audio: true,
+                ..CueTrack::default()
+            },
+        ],
+    }
+}
+
+/// Create a minimal FLAC with a STREAMINFO block and placeholder audio frames.
+fn get_flac(sample_rate: u32) -> Vec<u8> {
+    let mut streaminfo = vec![0; 34];
+    let packed = (sample_rate << 12).to_be_bytes();
+    streaminfo
+        .get_mut(10..13)
+        .expect("streaminfo should have a sample rate")
+        .copy_from_slice(packed.get(..3).expect("should have 3 bytes"));
+    let mut bytes = b"fLaC".to_vec();
+    bytes.extend([0x80, 0, 0, 34]);
+    bytes.extend(streaminfo);
+    bytes.extend([0xFF, 0xF8, 1, 2, 3]);
+    bytes
+}
+
+#[test]
+fn cue_sheet_round_trip() {
+    // Arrange
+    let cue_sheet = get_cue_sheet();
+
+    // Act
+    let bytes = cue_sheet.to_bytes();
+    let output = CueSheet::from_bytes(&bytes, 44_100);
+
+    // Assert
+    assert_eq!(output, Some(cue_sheet));
+}
+
+#[test]
+fn cue_sheet_resample() {
+    // Arrange
+    let cue_sheet = CueSheet {
+        sample_rate: 96_000,
+        lead_in: 0,
+        ..get_cue_sheet()
+    };
+
+    // Act
+    let output = cue_sheet.resample(48_000);
+
+    // Assert
+    assert_eq!(output.sample_rate, 48_000);
+    assert_eq!(
+        output.tracks.get(1).map(|track| track.offset),
+        Some(5_292_000)
+    );
+    assert!(!output.is_cd);
+}
+
+#[test]
+fn cue_sheet_to_cue() {
+    // Arrange
+    let mut cue_sheet = get_cue_sheet();
+    if let Some(track) = cue_sheet.tracks.first_mut() {
+        track.indices.push(CueIndex {
+            offset: 2_646_588,
+            number: 2,
+        });
+    }
+
+    // Act
+    let output = cue_sheet.to_cue("01 Track.mp3");
+
+    // Assert
+    assert_eq!(
+        output,
+        "CATALOG 0123456789012
+FILE \"01 Track.mp3\" MP3
+  TRACK 01 AUDIO
+    ISRC GBAAA1000001
+    INDEX 01 00:00:00
+    INDEX 02 01:00:01
+"
+    );
+}
+
+#[test]
+fn write_cue_sheet_to_flac() {
+    // Arrange
+    let dir = TempDirectory::create("write_cue_sheet_to_flac");
+    let path = dir.join("01 Track.flac");
+    write(&path, get_flac(44_100)).expect("should be able to write FLAC");
+    let cue_sheet = get_cue_sheet();
+
+    // Act
+    write_cue_sheet(&path, &cue_sheet).expect("should be able to write cue sheet");
+
+    // Assert
+    let output = CueSheet::read(&path).expect("should be able to read FLAC");
+    assert_eq!(output, Some(cue_sheet));
+    let bytes = read(&path).expect("should be able to read FLAC");
+    assert!(bytes.ends_with(&[0xFF, 0xF8, 1, 2, 3]));
+    assert_eq!(bytes.get(4), Some(&0));
+}
@@ -1 +1,8 @@
+mod clip_check_tests;
+mod cue_sheet_tests;
+mod pipeline_tests;
+mod provenance_tests;
+mod resume_check_tests;
+mod sample_check_tests;
+mod track_prefetcher_tests;
 mod transcode_command_tests;
@@ -0,0 +1,108 @@
+use std::fs::{read, write};
+use std::sync::Arc;
+
+use rogue_logging::Error;
+
+use crate::testing::TempDirectory;
+use crate::transcode::{CommandInfo, Pipeline, PipelineInput};
+
+/// Larger than every channel between the stages so each stage has to wait for the next.
+const INPUT_SIZE: usize = 4 * 1024 * 1024;
+
+fn get_input() -> Vec<u8> {
+    b"abcdefghij"
+        .iter()
+        .copied()
+        .cycle()
+        .take(INPUT_SIZE)
+        .collect()
+}
+
+fn stage(program: &str, args: &[&str]) -> CommandInfo {
+    CommandInfo {
+        program: program.to_owned(),
+        args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn pipeline_passes_output_to_next_stage() -> Result<(), Error> {
+    // Arrange
+    let output = TempDirectory::create("pipeline_passes_output_to_next_stage").join("output");
+    let input = get_input();
+    let pipeline = Pipeline {
+        input: PipelineInput::Memory(Arc::new(input.clone())),
+        stages: vec![
+            stage("tr", &["a-z", "A-Z"]),
+            stage("cat", &[]),
+            stage("tee", &[output.to_string_lossy().as_ref()]),
+        ],
+    };
+
+    // Act
+    let outputs = pipeline.execute().await?;
+
+    // Assert
+    assert_eq!(outputs.len(), 3);
+    assert!(outputs.iter().all(|output| output.output.status.success()));
+    let expected = input.to_ascii_uppercase();
+    assert_eq!(read(&output).expect("should read output"), expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn pipeline_reads_input_file() -> Result<(), Error> {
+    // Arrange
+    let dir = TempDirectory::create("pipeline_reads_input_file");
+    let input = dir.join("input");
+    let output = dir.join("output");
+    write(&input, get_input()).expect("should write input");
+    let pipeline = Pipeline {
+        input: PipelineInput::File(input),
+        stages: vec![stage("tee", &[output.to_string_lossy().as_ref()])],
+    };
+
+    // Act
+    pipeline.execute().await?;
+
+    // Assert
+    assert_eq!(read(&output).expect("should read output"), get_input());
+    Ok(())
+}
+
+#[tokio::test]
+async fn pipeline_stage_closes_early() -> Result<(), Error> {
+    // Arrange
+    let output = TempDirectory::create("pipeline_stage_closes_early").join("output");
+    let pipeline = Pipeline {
+        input: PipelineInput::Memory(Arc::new(get_input())),
+        stages: vec![
+            stage("head", &["-c", "10"]),
+            stage("tee", &[output.to_string_lossy().as_ref()]),
+        ],
+    };
+
+    // Act
+    let outputs = pipeline.execute().await?;
+
+    // Assert
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(read(&output).expect("should read output"), b"abcdefghij");
+    Ok(())
+}
+
+#[tokio::test]
+async fn pipeline_missing_input_file() {
+    // Arrange
+    let input = TempDirectory::create("pipeline_missing_input_file").join("missing");
+    let pipeline = Pipeline {
+        input: PipelineInput::File(input),
+        stages: vec![stage("cat", &[])],
+    };
+
+    // Act
+    let result = pipeline.execute().await;
+
+    // Assert
+    assert!(result.is_err());
+}
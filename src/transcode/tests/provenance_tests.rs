@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use crate::built_info::{PKG_NAME, PKG_VERSION};
+use crate::formats::TargetFormat;
+use crate::transcode::{Decode, Encode, Provenance, ProvenanceTag, Variant};
+
+fn get_provenance(tag: ProvenanceTag) -> Provenance {
+    Provenance {
+        tag,
+        encoders: vec![
+            "flac 1.4.3".to_owned(),
+            "LAME 64bits version 3.100".to_owned(),
+        ],
+    }
+}
+
+#[test]
+fn variant_get_display_replaces_paths() {
+    // Arrange
+    let variant = Variant::Transcode(
+        Decode {
+            input: PathBuf::from("/source/01 Track.flac"),
+            resample_rate: None,
+        },
+        Encode {
+            format: TargetFormat::V0,
+            output: PathBuf::from("/output/01 Track.mp3"),
+            scale: None,
+        },
+    );
+
+    // Act
+    let display = variant.get_display();
+
+    // Assert
+    assert_eq!(
+        display,
+        "flac -dcs - | lame -S -V 0 --vbr-new --ignore-tag-errors - output.mp3"
+    );
+}
+
+#[test]
+fn provenance_get_tag() {
+    // Arrange
+    let tagged = get_provenance(ProvenanceTag::Comment);
+    let untagged = get_provenance(ProvenanceTag::None);
+
+    // Act
+    let tag = tagged.get_tag("lame -V 0");
+    let none = untagged.get_tag("lame -V 0");
+
+    // Assert
+    let (_, value) = tag.expect("should have tag");
+    assert_eq!(
+        value,
+        format!("{PKG_NAME} v{PKG_VERSION} (flac 1.4.3, LAME 64bits version 3.100): lame -V 0")
+    );
+    assert!(none.is_none());
+}
+
+#[test]
+fn provenance_get_file_contents() {
+    // Arrange
+    let provenance = get_provenance(ProvenanceTag::None);
+    let commands = vec!["sox input.flac output.flac".to_owned()];
+
+    // Act
+    let contents =
+        provenance.get_file_contents("https://example.com/1", TargetFormat::Flac, &commands);
+
+    // Assert
+    assert!(contents.starts_with(&format!("Transcoded with {PKG_NAME} v{PKG_VERSION}\n")));
+    assert!(contents.contains("\nSource: https://example.com/1\nFormat: FLAC\n"));
+    assert!(contents.contains("\nEncoders:\nflac 1.4.3\nLAME 64bits version 3.100\n"));
+    assert!(contents.ends_with("\nCommands:\nsox input.flac output.flac\n"));
+}
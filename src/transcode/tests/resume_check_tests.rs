@@ -0,0 +1,40 @@
+use std::fs::write;
+
+use crate::formats::TargetFormat;
+use crate::testing::TempDirectory;
+use crate::transcode::{check_existing_output, SampleCount};
+
+#[test]
+fn check_existing_output_missing() {
+    // Arrange
+    let dir = TempDirectory::create("check_existing_output_missing");
+    let path = dir.join("01 Track.mp3");
+
+    // Act
+    let result = check_existing_output(&path, TargetFormat::V0, None, false);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn check_existing_output_truncated() {
+    // Arrange
+    let dir = TempDirectory::create("check_existing_output_truncated");
+    let flac = dir.join("01 Track.flac");
+    let mp3 = dir.join("01 Track.mp3");
+    write(&flac, b"fLaC").expect("should write file");
+    write(&mp3, b"ID3").expect("should write file");
+    let samples = SampleCount {
+        samples: 44100,
+        sample_rate: 44100,
+    };
+
+    // Act
+    let flac_result = check_existing_output(&flac, TargetFormat::Flac, Some(samples), false);
+    let mp3_result = check_existing_output(&mp3, TargetFormat::_320, None, false);
+
+    // Assert
+    assert!(flac_result.is_err());
+    assert!(mp3_result.is_err());
+}
@@ -0,0 +1,32 @@
+use crate::formats::TargetFormat;
+use crate::transcode::{get_sample_tolerance, SampleCount};
+
+#[test]
+fn sample_count_at_rate() {
+    // Arrange
+    let source = SampleCount {
+        samples: 9_600_001,
+        sample_rate: 96000,
+    };
+
+    // Act
+    let same = source.at_rate(96000);
+    let resampled = source.at_rate(48000);
+
+    // Assert
+    assert_eq!(same, 9_600_001);
+    assert_eq!(resampled, 4_800_001);
+}
+
+#[test]
+fn sample_check_get_sample_tolerance() {
+    // Act
+    let flac = get_sample_tolerance(TargetFormat::Flac, false);
+    let resampled = get_sample_tolerance(TargetFormat::Flac, true);
+    let mp3 = get_sample_tolerance(TargetFormat::V0, false);
+
+    // Assert
+    assert_eq!(flac, 0);
+    assert!(resampled > 0);
+    assert!(mp3 > 1105, "should allow for the delay added by LAME");
+}
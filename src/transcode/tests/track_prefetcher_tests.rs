@@ -0,0 +1,100 @@
+use std::fs::write;
+use std::time::Duration;
+
+use di::Ref;
+use tokio::time::timeout;
+
+use crate::testing::TempDirectory;
+use crate::transcode::TrackPrefetcher;
+
+#[tokio::test]
+async fn track_prefetcher_reads_ahead() {
+    // Arrange
+    let dir = TempDirectory::create("track_prefetcher_reads_ahead");
+    let first = dir.join("01 First.flac");
+    let second = dir.join("02 Second.flac");
+    write(&first, b"first").expect("should write first");
+    write(&second, b"second").expect("should write second");
+    let paths = vec![first.clone(), first.clone(), second.clone()];
+    let prefetcher = Ref::new(TrackPrefetcher::new(paths, 11));
+
+    // Act
+    prefetcher.start().await.expect("reader should complete");
+    let first_a = prefetcher.take(&first).await;
+    let first_b = prefetcher.take(&first).await;
+    let first_c = prefetcher.take(&first).await;
+    let second = prefetcher.take(&second).await;
+
+    // Assert
+    assert_eq!(first_a.as_deref(), Some(&b"first".to_vec()));
+    assert_eq!(first_b.as_deref(), Some(&b"first".to_vec()));
+    assert_eq!(first_c, None);
+    assert_eq!(second.as_deref(), Some(&b"second".to_vec()));
+}
+
+#[tokio::test]
+async fn track_prefetcher_read_directly() {
+    // Arrange
+    let dir = TempDirectory::create("track_prefetcher_read_directly");
+    let early = dir.join("01 Early.flac");
+    let missing = dir.join("02 Missing.flac");
+    write(&early, b"early").expect("should write early");
+    let paths = vec![early.clone(), missing.clone()];
+    let prefetcher = Ref::new(TrackPrefetcher::new(paths, 5));
+
+    // Act
+    let early = prefetcher.take(&early).await;
+    prefetcher.start().await.expect("reader should complete");
+    let missing = prefetcher.take(&missing).await;
+    let unknown = prefetcher.take(&dir.join("03 Unknown.flac")).await;
+
+    // Assert
+    assert_eq!(early, None);
+    assert_eq!(missing, None);
+    assert_eq!(unknown, None);
+}
+
+#[tokio::test]
+async fn track_prefetcher_limits_bytes() {
+    // Arrange
+    let dir = TempDirectory::create("track_prefetcher_limits_bytes");
+    let first = dir.join("01 First.flac");
+    let second = dir.join("02 Second.flac");
+    write(&first, b"first").expect("should write first");
+    write(&second, b"other").expect("should write second");
+    let paths = vec![first.clone(), second.clone()];
+    let prefetcher = Ref::new(TrackPrefetcher::new(paths, 5));
+
+    // Act
+    let mut reader = prefetcher.start();
+    let blocked = timeout(Duration::from_millis(100), &mut reader).await;
+    let first = prefetcher.take(&first).await;
+    reader.await.expect("reader should complete");
+    let second = prefetcher.take(&second).await;
+
+    // Assert
+    assert!(blocked.is_err());
+    assert_eq!(first.as_deref(), Some(&b"first".to_vec()));
+    assert_eq!(second.as_deref(), Some(&b"other".to_vec()));
+}
+
+#[tokio::test]
+async fn track_prefetcher_skips_larger_than_capacity() {
+    // Arrange
+    let dir = TempDirectory::create("track_prefetcher_skips_larger_than_capacity");
+    let large = dir.join("01 Large.flac");
+    let small = dir.join("02 Small.flac");
+    write(&large, b"larger").expect("should write large");
+    write(&small, b"small").expect("should write small");
+    let paths = vec![large.clone(), small.clone()];
+    let prefetcher = Ref::new(TrackPrefetcher::new(paths, 5));
+
+    // Act
+    prefetcher.start().await.expect("reader should complete");
+    let large = prefetcher.take(&large).await;
+    let small = prefetcher.take(&small).await;
+
+    // Assert
+    assert_eq!(large, None);
+    assert_eq!(small.as_deref(), Some(&b"small".to_vec()));
+}
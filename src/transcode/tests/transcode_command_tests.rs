@@ -1,7 +1,7 @@
 use crate::built_info::PKG_NAME;
-use crate::formats::TargetFormat::{Flac, V0, _320};
-use crate::formats::TargetFormatProvider;
-use crate::fs::DirectoryReader;
+use crate::formats::TargetFormat::{Flac, _320, V0};
+use crate::formats::{CustomTarget, TargetFormatProvider};
+use crate::fs::{DirectoryReader, LinkStrategy, PathManager};
 use crate::hosting::HostBuilder;
 use crate::options::{CopyOptions, SharedOptions, SourceArg, TargetOptions};
 use crate::source::SourceProvider;
@@ -19,6 +19,7 @@ async fn transcode_command() -> Result<(), Error> {
     Logger::force_init(PKG_NAME.to_owned());
     let source_options = TestOptionsFactory::from(SourceArg {
         source: Some("206675".to_owned()),
+        first: None,
     });
     let shared_options = TestOptionsFactory::from(SharedOptions {
         output: Some(TempDirectory::create("caesura")),
@@ -27,9 +28,12 @@ async fn transcode_command() -> Result<(), Error> {
     let target_options = TestOptionsFactory::from(TargetOptions {
         allow_existing: Some(true),
         target: Some(vec![Flac, _320, V0]),
+        ..TargetOptions::default()
     });
     let copy_options = TestOptionsFactory::from(CopyOptions {
         hard_link: Some(true),
+        link_additional: Some(LinkStrategy::Hardlink),
+        ..CopyOptions::default()
     });
     let output_dir = shared_options.output.clone().expect("output should be set");
     let host = HostBuilder::new()
@@ -76,3 +80,63 @@ async fn transcode_command() -> Result<(), Error> {
     assert_eq!(hard_links, 2);
     Ok(())
 }
+
+#[tokio::test]
+async fn transcode_command_custom_target() -> Result<(), Error> {
+    // Arrange
+    Logger::force_init(PKG_NAME.to_owned());
+    let source_options = TestOptionsFactory::from(SourceArg {
+        source: Some("206675".to_owned()),
+        first: None,
+    });
+    let shared_options = TestOptionsFactory::from(SharedOptions {
+        output: Some(TempDirectory::create("transcode_command_custom_target")),
+        ..SharedOptions::default()
+    });
+    let target_options = TestOptionsFactory::from(TargetOptions {
+        allow_existing: Some(true),
+        target: Some(vec![V0]),
+        custom_target: Some(vec!["Car=CBR128".to_owned()]),
+        ..TargetOptions::default()
+    });
+    let output_dir = shared_options.output.clone().expect("output should be set");
+    let host = HostBuilder::new()
+        .with_options(source_options)
+        .with_options(shared_options)
+        .with_options(target_options)
+        .build();
+    let provider = host.services.get_required_mut::<SourceProvider>();
+    let transcoder = host.services.get_required::<TranscodeCommand>();
+    let paths = host.services.get_required::<PathManager>();
+    let source = provider
+        .write()
+        .expect("Source provider should be writeable")
+        .get_from_options()
+        .await
+        .expect("Source provider should not fail");
+    let target = CustomTarget::parse("Car=CBR128").expect("should parse");
+
+    // Act
+    transcoder.execute_cli().await?;
+
+    // Assert
+    let flac_count = DirectoryReader::new()
+        .with_extension("flac")
+        .read(&source.directory)
+        .expect("Should be able to read source dir")
+        .len();
+    let custom_files = DirectoryReader::new()
+        .with_extension("mp3")
+        .read(&paths.get_transcode_target_dir(&source, &target))
+        .expect("Should be able to read custom target dir");
+    assert_eq!(custom_files.len(), flac_count);
+    let torrents = DirectoryReader::new()
+        .with_extension("torrent")
+        .read(&output_dir)
+        .expect("Should be able to read dir");
+    assert!(torrents
+        .iter()
+        .all(|path| !path.to_string_lossy().contains("Car")));
+    assert!(!torrents.is_empty());
+    Ok(())
+}
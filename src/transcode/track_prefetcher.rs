@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use colored::Colorize;
+use di::Ref;
+use log::trace;
+use tokio::fs::{metadata, read};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+
+/// Progress of reading a track into memory.
+enum TrackState {
+    /// Not read yet.
+    Waiting,
+    /// Being read.
+    Reading,
+    /// Read into memory.
+    Read(Arc<Vec<u8>>),
+    /// Could not be read, or is larger than the read ahead, so each job reads it
+    /// directly.
+    Direct,
+}
+
+struct TrackEntry {
+    state: TrackState,
+    /// Number of jobs that have not yet taken the track.
+    remaining: usize,
+    /// Slot of the read ahead held until every job has taken the track.
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Read the source tracks of the transcode jobs into memory ahead of the jobs.
+///
+/// Each track is read once, in order, so reading the next track overlaps decoding and
+/// encoding the current one. At most `capacity` bytes are held at once, and a track is
+/// released once every job that uses it has taken it. A track larger than `capacity` is
+/// not read ahead so each job streams it directly.
+///
+/// A job never waits for a track that hasn't started to be read, it reads the track
+/// directly instead. The read ahead therefore can't hold up the jobs even if they
+/// start out of order.
+pub struct TrackPrefetcher {
+    tracks: Mutex<BTreeMap<PathBuf, TrackEntry>>,
+    order: Vec<PathBuf>,
+    /// Bytes that can be held at once.
+    capacity: u32,
+    permits: Arc<Semaphore>,
+    notify: Notify,
+}
+
+impl TrackPrefetcher {
+    /// Create a [`TrackPrefetcher`] for the source path of each job in the order they
+    /// are executed.
+    ///
+    /// A path that occurs more than once is read once and released after its last job.
+    #[must_use]
+    pub fn new(paths: Vec<PathBuf>, capacity: u32) -> Self {
+        let mut tracks: BTreeMap<PathBuf, TrackEntry> = BTreeMap::new();
+        let mut order = Vec::new();
+        for path in paths {
+            if let Some(entry) = tracks.get_mut(&path) {
+                entry.remaining += 1;
+                continue;
+            }
+            order.push(path.clone());
+            tracks.insert(
+                path,
+                TrackEntry {
+                    state: TrackState::Waiting,
+                    remaining: 1,
+                    permit: None,
+                },
+            );
+        }
+        Self {
+            tracks: Mutex::new(tracks),
+            order,
+            capacity,
+            permits: Arc::new(Semaphore::new(
+                usize::try_from(capacity).expect("u32 should fit in usize"),
+            )),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Start reading the tracks in the background.
+    ///
+    /// The returned task should be aborted once the jobs are done as it waits for every
+    /// track to be taken.
+    #[must_use]
+    pub fn start(self: &Ref<Self>) -> JoinHandle<()> {
+        let prefetcher = self.clone();
+        tokio::spawn(async move { prefetcher.read_all().await })
+    }
+
+    /// Take the contents of a track.
+    ///
+    /// Waits if the track is being read. Returns `None` if the track should be read
+    /// directly, as it isn't known, hasn't started to be read, is larger than the read
+    /// ahead, or could not be read.
+    pub async fn take(&self, path: &Path) -> Option<Arc<Vec<u8>>> {
+        loop {
+            let notified = self.notify.notified();
+            {
+                let mut tracks = self.tracks.lock().expect("tracks should be lockable");
+                let entry = tracks.get_mut(path)?;
+                let data = match &entry.state {
+                    TrackState::Reading => None,
+                    TrackState::Read(data) => Some(Some(data.clone())),
+                    TrackState::Waiting | TrackState::Direct => Some(None),
+                };
+                if let Some(data) = data {
+                    entry.remaining = entry.remaining.saturating_sub(1);
+                    if entry.remaining == 0 {
+                        tracks.remove(path);
+                    }
+                    return data;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    async fn read_all(&self) {
+        for path in &self.order {
+            let size = metadata(path).await.map_or(0, |metadata| metadata.len());
+            let Some(permits) = get_permits(size, self.capacity) else {
+                trace!(
+                    "{} read ahead of {} as it's larger than the read ahead",
+                    "Skipped".bold(),
+                    path.display()
+                );
+                self.set_state(path, TrackState::Direct, None);
+                self.notify.notify_waiters();
+                continue;
+            };
+            let Ok(permit) = self.permits.clone().acquire_many_owned(permits).await else {
+                return;
+            };
+            if !self.set_state(path, TrackState::Reading, None) {
+                continue;
+            }
+            let state = match read(path).await {
+                Ok(data) => TrackState::Read(Arc::new(data)),
+                Err(error) => {
+                    trace!(
+                        "{} to read ahead {}: {error}",
+                        "Failed".bold(),
+                        path.display()
+                    );
+                    TrackState::Direct
+                }
+            };
+            let permit = matches!(state, TrackState::Read(_)).then_some(permit);
+            self.set_state(path, state, permit);
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Set the state of a track if any job has yet to take it.
+    ///
+    /// Returns `false` if every job has taken the track.
+    fn set_state(
+        &self,
+        path: &Path,
+        state: TrackState,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> bool {
+        let mut tracks = self.tracks.lock().expect("tracks should be lockable");
+        let Some(entry) = tracks.get_mut(path) else {
+            return false;
+        };
+        entry.state = state;
+        entry.permit = permit;
+        true
+    }
+}
+
+/// Get the semaphore permits to read a track of `size` bytes, of at least one.
+///
+/// Returns `None` if the track is larger than `capacity` so can't be read ahead.
+fn get_permits(size: u64, capacity: u32) -> Option<u32> {
+    u32::try_from(size)
+        .ok()
+        .map(|permits| permits.max(1))
+        .filter(|permits| *permits <= capacity)
+}
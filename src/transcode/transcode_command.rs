@@ -1,16 +1,33 @@
-use crate::errors::{error, io_error};
-use crate::formats::{TargetFormat, TargetFormatProvider};
-use crate::fs::{Collector, PathManager};
-use crate::imdl::ImdlCommand;
+use crate::beets::BeetsImporter;
+use crate::confirm::{ConfirmAction, Confirmation};
+use crate::errors::{error, io_error, log_error, path_error};
+use crate::formats::{CustomTarget, TargetFormat, TargetFormatProvider, TranscodeTarget};
+use crate::fs::{
+    create_archive, create_checksum_manifest, get_archive_path, move_staged, Collector,
+    DirectoryReader, FlacFile, PathManager,
+};
+use crate::history::History;
+use crate::hooks::{HookEvent, HookRunner};
+use crate::jobs::Job;
 use crate::jobs::Job::Additional;
 use crate::jobs::JobRunner;
+use crate::musicbrainz::MusicBrainzProvider;
 use crate::naming::join_humanized;
-use crate::options::{CopyOptions, FileOptions, Options, SharedOptions, SourceArg, TargetOptions};
+use crate::notify::{Notifier, NotifyEvent};
+use crate::options::{
+    BeetsOptions, CopyOptions, EnrichOptions, FileOptions, HistoryOptions, HookOptions,
+    NotifyOptions, OptionRule, Options, PermissionOptions, SharedOptions, SourceArg, TargetOptions,
+    TorrentOptions,
+};
+use crate::progress::{ProgressEmitter, ProgressEvent};
 use crate::queue::TimeStamp;
 use crate::source::*;
+use crate::summary::{TimingPhase, Timings};
+use crate::torrent::{AnnounceProvider, TorrentCreator};
 use crate::transcode::{
-    AdditionalJob, AdditionalJobFactory, TranscodeFormatStatus, TranscodeJobFactory,
-    TranscodeStatus,
+    get_duration, AdditionalJob, AdditionalJobFactory, OutputTarget, ProvenanceProvider,
+    TrackPrefetcher, TranscodeFormatStatus, TranscodeJobFactory, TranscodeStatus,
+    PROVENANCE_FILE_NAME,
 };
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
@@ -18,7 +35,10 @@ use log::*;
 use rogue_logging::Colors;
 use rogue_logging::Error;
 use std::collections::BTreeSet;
-use tokio::fs::{copy, hard_link};
+use std::fs::remove_file;
+use std::time::Instant;
+use tokio::fs::{copy, remove_dir_all, write};
+use tokio::task::{spawn_blocking, JoinHandle};
 
 /// Transcode each track of a FLAC source to the target formats.
 #[injectable]
@@ -26,14 +46,32 @@ pub struct TranscodeCommand {
     arg: Ref<SourceArg>,
     shared_options: Ref<SharedOptions>,
     target_options: Ref<TargetOptions>,
+    torrent_options: Ref<TorrentOptions>,
     source_provider: RefMut<SourceProvider>,
+    locker: Ref<SourceLocker>,
     copy_options: Ref<CopyOptions>,
     file_options: Ref<FileOptions>,
+    notify_options: Ref<NotifyOptions>,
+    hook_options: Ref<HookOptions>,
+    history_options: Ref<HistoryOptions>,
+    enrich_options: Ref<EnrichOptions>,
+    beets_options: Ref<BeetsOptions>,
+    permission_options: Ref<PermissionOptions>,
     paths: Ref<PathManager>,
     targets: Ref<TargetFormatProvider>,
     transcode_job_factory: Ref<TranscodeJobFactory>,
     additional_job_factory: Ref<AdditionalJobFactory>,
     runner: Ref<JobRunner>,
+    progress: Ref<ProgressEmitter>,
+    notifier: Ref<Notifier>,
+    hooks: Ref<HookRunner>,
+    history: Ref<History>,
+    musicbrainz: Ref<MusicBrainzProvider>,
+    beets: Ref<BeetsImporter>,
+    announce: Ref<AnnounceProvider>,
+    timings: Ref<Timings>,
+    confirmation: Ref<Confirmation>,
+    provenance: Ref<ProvenanceProvider>,
 }
 
 impl TranscodeCommand {
@@ -41,36 +79,119 @@ impl TranscodeCommand {
     ///
     /// [`Source`] is retrieved from the CLI arguments.
     ///
+    /// Sources the [`History`] records as transcoded are skipped unless `ignore_history` is set.
+    ///
     /// Returns `true` if all the transcodes succeeds.
     pub async fn execute_cli(&self) -> Result<bool, Error> {
-        if !self.arg.validate()
-            || !self.shared_options.validate()
-            || !self.target_options.validate()
-            || !self.file_options.validate()
-        {
-            return Ok(false);
+        let errors = [
+            self.arg.get_errors(),
+            self.shared_options.get_errors(),
+            self.target_options.get_errors(),
+            self.torrent_options.get_errors(),
+            self.file_options.get_errors(),
+            self.notify_options.get_errors(),
+            self.hook_options.get_errors(),
+            self.history_options.get_errors(),
+            self.enrich_options.get_errors(),
+            self.beets_options.get_errors(),
+            self.permission_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
+        if let Some(indexer) = &self.shared_options.indexer {
+            self.torrent_options.warn_unsupported_version(indexer);
         }
-        let source = self
+        let mut source = self
             .source_provider
             .write()
             .expect("Source provider should be writeable")
             .get_from_options()
             .await
-            .map_err(|e| error("get source from options", e.to_string()))?;
-        let status = self.execute(&source).await;
+            .map_err(|e| e.to_error("get source from options"))?;
+        let _lock = self.locker.acquire_or_error(&source)?;
+        if self.is_transcoded_in_history(&source) {
+            info!(
+                "{} {source} as the history records it as transcoded",
+                "Skipped".bold()
+            );
+            return Ok(true);
+        }
+        let status = self.execute(&mut source).await;
         if let Some(error) = &status.error {
-            error.log();
+            log_error(error);
         }
         Ok(status.success)
     }
 
     /// Execute [`TranscodeCommand`] on a [`Source`].
     ///
+    /// If `recheck_formats` is set then the existing formats of the source are refreshed
+    /// first so the caller can upload with the same targets.
+    ///
     /// Returns a [`TranscodeStatus`] indicating the success of the operation and any errors.
     ///
     /// Errors are not logged so should be handled by the caller.
     #[must_use]
-    pub async fn execute(&self, source: &Source) -> TranscodeStatus {
+    pub async fn execute(&self, source: &mut Source) -> TranscodeStatus {
+        self.recheck_formats(source).await;
+        let status = self.transcode(source).await;
+        self.history.record_transcode(source, &status).await;
+        status
+    }
+
+    /// Refresh the existing formats of a [`Source`] from the API if `recheck_formats` is set.
+    ///
+    /// Failures are logged as warnings and the existing formats of the source are kept.
+    async fn recheck_formats(&self, source: &mut Source) {
+        if !self
+            .target_options
+            .recheck_formats
+            .expect("recheck_formats should be set")
+        {
+            return;
+        }
+        let result = self
+            .source_provider
+            .read()
+            .expect("Source provider should be readable")
+            .get_existing(source)
+            .await;
+        let existing = match result {
+            Ok(existing) => existing,
+            Err(issue) => {
+                warn!("{} to re-check the formats of {source}", "Failed".bold());
+                warn!("{issue}");
+                return;
+            }
+        };
+        let before = self.targets.get(source.format, &source.existing);
+        let after = self.targets.get(source.format, &existing);
+        let dropped: Vec<TargetFormat> = before.difference(&after).copied().collect();
+        if dropped.is_empty() {
+            trace!("{} formats of {source}", "Re-checked".bold());
+        } else {
+            info!(
+                "{} {} as uploaded since {source} was verified",
+                "Dropping".bold(),
+                join_humanized(&dropped)
+            );
+        }
+        source.existing = existing;
+    }
+
+    fn is_transcoded_in_history(&self, source: &Source) -> bool {
+        if self
+            .history_options
+            .ignore_history
+            .expect("ignore_history should be set")
+        {
+            return false;
+        }
+        let targets = self.targets.get(source.format, &source.existing);
+        !targets.is_empty() && self.history.is_transcoded(source, &targets)
+    }
+
+    async fn transcode(&self, source: &Source) -> TranscodeStatus {
         let targets = self.targets.get(source.format, &source.existing);
         let mut status = TranscodeStatus {
             success: false,
@@ -95,20 +216,59 @@ impl TranscodeCommand {
             status.success = true;
             return status;
         }
-        if let Err(error) = self.execute_transcode(source, &targets).await {
+        if let Err(error) = self.confirm_overwrite(source, &targets) {
             status.error = Some(error);
             status.completed = TimeStamp::now();
             return status;
         }
-        if let Err(error) = self.execute_additional(source, &targets).await {
+        let custom = self.get_custom_targets(source);
+        let start = Instant::now();
+        if let Err(error) = self.execute_staged(source, &targets, &custom).await {
+            if self.is_resume() {
+                debug!(
+                    "{} staged transcodes of {source} to resume",
+                    "Keeping".bold()
+                );
+            } else {
+                self.remove_staging_dir(source).await;
+            }
             status.error = Some(error);
             status.completed = TimeStamp::now();
             return status;
         }
-        if let Err(error) = self.execute_torrent(source, &targets).await {
-            status.error = Some(error);
-            status.completed = TimeStamp::now();
-            return status;
+        self.progress.emit(ProgressEvent::TranscodeDone {
+            formats: targets.iter().copied().collect(),
+            audio_seconds: get_total_duration(&Collector::get_flacs(&source.directory)),
+            elapsed_seconds: start.elapsed().as_secs_f64(),
+            directories: targets
+                .iter()
+                .map(|&target| self.paths.get_transcode_target_dir(source, target))
+                .collect(),
+        });
+        self.notifier
+            .notify(
+                NotifyEvent::TranscodeDone,
+                format!("Transcoded {source} to {}", join_humanized(&targets)),
+            )
+            .await;
+        for target in &targets {
+            let transcode_dir = self.paths.get_transcode_target_dir(source, *target);
+            let vars = vec![
+                ("CAESURA_FORMAT", target.to_string()),
+                (
+                    "CAESURA_TRANSCODE_DIR",
+                    transcode_dir.to_string_lossy().to_string(),
+                ),
+                (
+                    "CAESURA_TORRENT_PATH",
+                    self.paths
+                        .get_torrent_path(source, *target, true)
+                        .to_string_lossy()
+                        .to_string(),
+                ),
+            ];
+            self.hooks.run(HookEvent::Transcoded, source, vars).await;
+            self.beets.execute(source, *target, &transcode_dir).await;
         }
         status.success = true;
         status
@@ -137,10 +297,87 @@ impl TranscodeCommand {
         out
     }
 
-    async fn execute_transcode(
+    /// Get the custom targets that haven't been transcoded yet.
+    ///
+    /// A custom target has no torrent file so it's complete once its output directory exists.
+    fn get_custom_targets(&self, source: &Source) -> Vec<CustomTarget> {
+        let mut out = Vec::new();
+        for target in self.target_options.get_custom_targets() {
+            let output = self.paths.get_transcode_target_dir(source, &target);
+            if output.exists() {
+                debug!("{} existing {target} transcode", "Found".bold());
+                trace!("{}", output.display());
+            } else {
+                out.push(target);
+            }
+        }
+        out
+    }
+
+    /// Confirm the output directories left by an earlier attempt can be replaced.
+    ///
+    /// Returns an [`Error`] if replacing an output directory is not confirmed so the
+    /// source isn't transcoded for nothing.
+    fn confirm_overwrite(
+        &self,
+        source: &Source,
+        targets: &BTreeSet<TargetFormat>,
+    ) -> Result<(), Error> {
+        for target in targets {
+            let output = self.paths.get_transcode_target_dir(source, *target);
+            if !output.exists() {
+                continue;
+            }
+            let question = format!("Replace existing output directory {}?", output.display());
+            if !self
+                .confirmation
+                .confirm(ConfirmAction::Overwrite, &question)?
+            {
+                return Err(error(
+                    "transcode",
+                    format!(
+                        "Replacing the existing output directory was not confirmed: {}",
+                        output.display()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the transcodes, additional files and torrents to the staging directory then
+    /// move them into the output directory.
+    ///
+    /// Nothing is moved into the output directory unless every target succeeds.
+    ///
+    /// Custom targets are only transcoded and moved as they are never uploaded.
+    async fn execute_staged(
         &self,
         source: &Source,
         targets: &BTreeSet<TargetFormat>,
+        custom: &[CustomTarget],
+    ) -> Result<(), Error> {
+        let outputs = get_output_targets(targets, custom);
+        if self.is_resume() {
+            self.prune_staging_dir(source, &outputs)?;
+        } else {
+            self.remove_staging_dir(source).await;
+        }
+        self.execute_transcode(source, &outputs).await?;
+        self.execute_additional(source, &outputs).await?;
+        self.execute_provenance_file(source, targets).await?;
+        self.execute_checksum_manifest(source, targets).await?;
+        self.execute_torrent(source, targets).await?;
+        self.execute_archive(source, targets).await?;
+        self.execute_move(source, targets, custom).await?;
+        self.remove_staging_dir(source).await;
+        Ok(())
+    }
+
+    async fn execute_transcode(
+        &self,
+        source: &Source,
+        targets: &[OutputTarget<'_>],
     ) -> Result<(), Error> {
         let flacs = Collector::get_flacs(&source.directory);
         info!(
@@ -150,11 +387,45 @@ impl TranscodeCommand {
             flacs.len().to_string().gray(),
             source
         );
+        let release = self.musicbrainz.get(source).await.map(Ref::new);
+        let provenance = self
+            .provenance
+            .get()
+            .await
+            .filter(|provenance| provenance.tag.get_key().is_some());
+        let mut all_jobs = Vec::new();
         for target in targets {
-            let jobs = self.transcode_job_factory.create(&flacs, source, *target)?;
-            self.runner.add(jobs);
+            let mut jobs = match target {
+                OutputTarget::Format(format) => {
+                    self.transcode_job_factory
+                        .create(&flacs, source, *format, release.as_ref())?
+                }
+                OutputTarget::Custom(target) => self.transcode_job_factory.create_custom(
+                    &flacs,
+                    source,
+                    target,
+                    release.as_ref(),
+                )?,
+            };
+            for job in &mut jobs {
+                if let Job::Transcode(transcode) = job {
+                    transcode.provenance.clone_from(&provenance);
+                }
+            }
+            if self.is_resume() {
+                jobs = keep_existing(jobs, *target);
+            }
+            all_jobs.append(&mut jobs);
         }
-        self.runner.execute().await?;
+        let read_ahead = self.target_options.get_read_ahead();
+        let reader = (read_ahead > 0 && !all_jobs.is_empty())
+            .then(|| start_read_ahead(&mut all_jobs, &flacs, read_ahead));
+        self.runner.add(all_jobs);
+        let result = self.runner.execute().await;
+        if let Some(reader) = reader {
+            reader.abort();
+        }
+        result?;
         info!("{} {}", "Transcoded".bold(), source);
         Ok(())
     }
@@ -162,9 +433,9 @@ impl TranscodeCommand {
     async fn execute_additional(
         &self,
         source: &Source,
-        targets: &BTreeSet<TargetFormat>,
+        targets: &[OutputTarget<'_>],
     ) -> Result<(), Error> {
-        let files = Collector::get_additional(&source.directory);
+        let files = Collector::get_additional(&self.paths.get_content_root(source));
         debug!(
             "{} {} additional files",
             "Adding".bold(),
@@ -175,19 +446,19 @@ impl TranscodeCommand {
             .additional_job_factory
             .create(&files, source, *first_target)
             .await?;
-        let from_prefix = self.paths.get_transcode_target_dir(source, *first_target);
+        let from_prefix = self.paths.get_staged_target_dir(source, *first_target);
         self.runner.add_without_publish(jobs);
         self.runner.execute_without_publish().await?;
-        let hard_link_option = self
+        let link_additional = self
             .copy_options
-            .hard_link
-            .expect("hard_link should be set");
+            .link_additional
+            .expect("link_additional should be set");
         for target in targets.iter().skip(1) {
             let jobs = self
                 .additional_job_factory
                 .create(&files, source, *target)
                 .await?;
-            let output = self.paths.get_transcode_target_dir(source, *target);
+            let output = self.paths.get_staged_target_dir(source, *target);
             for job in jobs {
                 if let Additional(AdditionalJob { resize, .. }) = job {
                     let from = from_prefix.clone().join(
@@ -196,20 +467,10 @@ impl TranscodeCommand {
                             .strip_prefix(&output)
                             .expect("should have prefix"),
                     );
-                    let verb = if hard_link_option {
-                        hard_link(&from, &resize.output)
-                            .await
-                            .map_err(|e| io_error(e, "hard link additional file"))?;
-                        "Hard Linked"
-                    } else {
-                        copy(&from, &resize.output)
-                            .await
-                            .map_err(|e| io_error(e, "copy additional file"))?;
-                        "Copied"
-                    };
+                    let strategy = link_additional.execute(&from, &resize.output).await?;
                     trace!(
                         "{} {} to {}",
-                        verb.bold(),
+                        strategy.get_verb().bold(),
                         from.display(),
                         resize.output.display()
                     );
@@ -221,42 +482,344 @@ impl TranscodeCommand {
         Ok(())
     }
 
+    /// Write a [`PROVENANCE_FILE_NAME`] to each staged target directory if `provenance_file`
+    /// is set.
+    ///
+    /// The file lists each distinct command used to transcode the tracks.
+    async fn execute_provenance_file(
+        &self,
+        source: &Source,
+        targets: &BTreeSet<TargetFormat>,
+    ) -> Result<(), Error> {
+        if !self
+            .target_options
+            .provenance_file
+            .expect("provenance_file should be set")
+        {
+            return Ok(());
+        }
+        let Some(provenance) = self.provenance.get().await else {
+            return Ok(());
+        };
+        let base = self
+            .shared_options
+            .indexer_url
+            .clone()
+            .expect("indexer_url should be set");
+        let source_url = get_permalink(&base, source.group.id, source.torrent.id);
+        let flacs = Collector::get_flacs(&source.directory);
+        for target in targets {
+            let mut commands: Vec<String> = Vec::new();
+            for (index, flac) in flacs.iter().enumerate() {
+                let job = self
+                    .transcode_job_factory
+                    .create_single(index, flac, source, *target)?;
+                if let Job::Transcode(transcode) = job {
+                    let command = transcode.variant.get_display();
+                    if !commands.contains(&command) {
+                        commands.push(command);
+                    }
+                }
+            }
+            let path = self
+                .paths
+                .get_staged_target_dir(source, *target)
+                .join(PROVENANCE_FILE_NAME);
+            let contents = provenance.get_file_contents(&source_url, *target, &commands);
+            write(&path, contents)
+                .await
+                .map_err(|e| path_error(e, "write provenance file", &path))?;
+            trace!("{} provenance file {}", "Created".bold(), path.display());
+        }
+        Ok(())
+    }
+
+    /// Write a checksum manifest to each staged target directory if `checksum_manifest` is set.
+    async fn execute_checksum_manifest(
+        &self,
+        source: &Source,
+        targets: &BTreeSet<TargetFormat>,
+    ) -> Result<(), Error> {
+        if !self
+            .file_options
+            .checksum_manifest
+            .expect("checksum_manifest should be set")
+        {
+            return Ok(());
+        }
+        for target in targets {
+            let dir = self.paths.get_staged_target_dir(source, *target);
+            let path = spawn_blocking(move || create_checksum_manifest(&dir))
+                .await
+                .map_err(|e| error("create checksum manifest", e.to_string()))??;
+            trace!("{} checksum manifest {}", "Created".bold(), path.display());
+        }
+        Ok(())
+    }
+
     async fn execute_torrent(
         &self,
         source: &Source,
         targets: &BTreeSet<TargetFormat>,
     ) -> Result<(), Error> {
         debug!("{} torrents {}", "Creating".bold(), source);
+        let announce_url = self.announce.get().await?;
         for target in targets {
-            let content_dir = self.paths.get_transcode_target_dir(source, *target);
-            let path_without_indexer = self.paths.get_torrent_path(source, *target, false);
-            let announce_url = self
-                .shared_options
-                .announce_url
-                .clone()
-                .expect("announce_url should be set");
+            let content_dir = self.paths.get_staged_target_dir(source, *target);
+            let path = self.paths.get_staged_torrent_path(source, *target);
             let indexer = self
                 .shared_options
                 .indexer
                 .clone()
                 .expect("indexer should be set");
-            ImdlCommand::create(&content_dir, &path_without_indexer, announce_url, indexer).await?;
-            trace!(
-                "{} torrent {}",
-                "Created".bold(),
-                path_without_indexer.display()
-            );
+            let start = Instant::now();
+            TorrentCreator::create(
+                &content_dir,
+                &path,
+                announce_url.clone(),
+                indexer,
+                &self.torrent_options.get_piece_length(),
+                self.torrent_options
+                    .torrent_version
+                    .expect("torrent_version should be set"),
+            )
+            .await?;
+            self.timings.record(TimingPhase::Torrent, start.elapsed());
+            trace!("{} torrent {}", "Created".bold(), path.display());
+        }
+        debug!("{} torrents {}", "Created".bold(), source);
+        Ok(())
+    }
+
+    /// Package each staged target directory into an archive if `archive` is set.
+    async fn execute_archive(
+        &self,
+        source: &Source,
+        targets: &BTreeSet<TargetFormat>,
+    ) -> Result<(), Error> {
+        let Some(format) = self.file_options.archive else {
+            return Ok(());
+        };
+        let compression = self
+            .file_options
+            .archive_compression
+            .expect("archive_compression should be set");
+        debug!("{} {format} archives {}", "Creating".bold(), source);
+        for target in targets {
+            let dir = self.paths.get_staged_target_dir(source, *target);
+            let path = create_archive(&dir, format, compression).await?;
+            trace!("{} archive {}", "Created".bold(), path.display());
+        }
+        Ok(())
+    }
+
+    /// Move the staged transcodes, archives and torrents into the output directory.
+    ///
+    /// The torrent files are moved after every transcode directory so a torrent file is
+    /// never present without its content.
+    async fn execute_move(
+        &self,
+        source: &Source,
+        targets: &BTreeSet<TargetFormat>,
+        custom: &[CustomTarget],
+    ) -> Result<(), Error> {
+        let permissions = self.permission_options.get_permissions();
+        for target in custom {
+            let staged = self.paths.get_staged_target_dir(source, target);
+            let output = self.paths.get_transcode_target_dir(source, target);
+            move_staged(&staged, &output).await?;
+            permissions.apply(&output)?;
+            trace!("{} {}", "Moved".bold(), output.display());
+        }
+        for target in targets {
+            let staged = self.paths.get_staged_target_dir(source, *target);
+            let output = self.paths.get_transcode_target_dir(source, *target);
+            move_staged(&staged, &output).await?;
+            permissions.apply(&output)?;
+            trace!("{} {}", "Moved".bold(), output.display());
+            if let Some(format) = self.file_options.archive {
+                let staged = get_archive_path(&staged, format);
+                let output = get_archive_path(&output, format);
+                move_staged(&staged, &output).await?;
+                permissions.apply(&output)?;
+                trace!("{} archive {}", "Moved".bold(), output.display());
+            }
+        }
+        for target in targets {
+            let staged = self.paths.get_staged_torrent_path(source, *target);
+            let path_without_indexer = self.paths.get_torrent_path(source, *target, false);
             let path_with_indexer = self.paths.get_torrent_path(source, *target, true);
-            copy(&path_without_indexer, &path_with_indexer)
+            copy(&staged, &path_with_indexer)
                 .await
                 .map_err(|e| io_error(e, "copy torrent file"))?;
-            trace!(
-                "{} torrent {}",
-                "Created".bold(),
-                path_with_indexer.display()
-            );
+            move_staged(&staged, &path_without_indexer).await?;
+            permissions.apply(&path_with_indexer)?;
+            permissions.apply(&path_without_indexer)?;
+            trace!("{} torrent {}", "Moved".bold(), path_with_indexer.display());
+            self.progress.emit(ProgressEvent::TorrentCreated {
+                format: *target,
+                path: path_with_indexer,
+            });
         }
-        debug!("{} torrents {}", "Created".bold(), source);
+        debug!("{} transcodes {}", "Moved".bold(), source);
         Ok(())
     }
+
+    /// Should the staged transcodes of an earlier attempt be kept?
+    fn is_resume(&self) -> bool {
+        !self
+            .target_options
+            .no_resume
+            .expect("no_resume should be set")
+    }
+
+    /// Remove everything from the staging directory of a [`Source`] except the transcodes
+    /// of the targets so an earlier attempt can be resumed.
+    ///
+    /// Additional files, checksum manifests and torrents are always created again.
+    fn prune_staging_dir(
+        &self,
+        source: &Source,
+        targets: &[OutputTarget<'_>],
+    ) -> Result<(), Error> {
+        let dir = self.paths.get_staging_dir(source);
+        if !dir.exists() {
+            return Ok(());
+        }
+        let flacs = Collector::get_flacs(&source.directory);
+        let mut keep = BTreeSet::new();
+        for target in targets {
+            for flac in &flacs {
+                let path = self.paths.get_staged_transcode_path(source, *target, flac);
+                if target.is_mp3() {
+                    keep.insert(path.with_extension("cue"));
+                }
+                keep.insert(path);
+            }
+        }
+        let action = "prune staging directory";
+        let paths = DirectoryReader::new()
+            .read(&dir)
+            .map_err(|e| path_error(e, action, &dir))?;
+        for path in paths {
+            if !keep.contains(&path) {
+                remove_file(&path).map_err(|e| path_error(e, action, &path))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the staging directory of a [`Source`].
+    ///
+    /// Errors are logged but otherwise ignored as the staging directory is only left over.
+    async fn remove_staging_dir(&self, source: &Source) {
+        let dir = self.paths.get_staging_dir(source);
+        if !dir.exists() {
+            return;
+        }
+        if let Err(e) = remove_dir_all(&dir).await {
+            warn!(
+                "{} to remove staging directory {}: {e}",
+                "Failed".bold(),
+                dir.display()
+            );
+        }
+    }
+}
+
+/// Get the targets to transcode to with the custom targets last.
+fn get_output_targets<'a>(
+    targets: &BTreeSet<TargetFormat>,
+    custom: &'a [CustomTarget],
+) -> Vec<OutputTarget<'a>> {
+    targets
+        .iter()
+        .map(|&format| OutputTarget::Format(format))
+        .chain(custom.iter().map(OutputTarget::Custom))
+        .collect()
+}
+
+/// Order the jobs by track and start reading the tracks ahead of the jobs.
+///
+/// Returns the task reading the tracks which should be aborted once the jobs are done.
+fn start_read_ahead(jobs: &mut [Job], flacs: &[FlacFile], capacity: u32) -> JoinHandle<()> {
+    order_by_track(jobs, flacs);
+    let paths = jobs
+        .iter()
+        .filter_map(|job| match job {
+            Job::Transcode(transcode) => Some(transcode.get_input().to_path_buf()),
+            _ => None,
+        })
+        .collect();
+    let prefetcher = Ref::new(TrackPrefetcher::new(paths, capacity));
+    for job in jobs {
+        if let Job::Transcode(transcode) = job {
+            transcode.prefetcher = Some(prefetcher.clone());
+        }
+    }
+    prefetcher.start()
+}
+
+/// Order the jobs of every target by the track they transcode, keeping the order of the
+/// targets for each track, so each track is read ahead once for all its targets.
+fn order_by_track(jobs: &mut [Job], flacs: &[FlacFile]) {
+    jobs.sort_by_key(|job| match job {
+        Job::Transcode(transcode) => flacs
+            .iter()
+            .position(|flac| flac.path == transcode.get_input())
+            .unwrap_or(usize::MAX),
+        _ => usize::MAX,
+    });
+}
+
+/// Remove the jobs whose output was completed by an earlier attempt.
+///
+/// An incomplete output is removed so it's transcoded again.
+fn keep_existing(jobs: Vec<Job>, target: OutputTarget<'_>) -> Vec<Job> {
+    let total = jobs.len();
+    let mut remaining = Vec::new();
+    for job in jobs {
+        let Job::Transcode(transcode) = &job else {
+            remaining.push(job);
+            continue;
+        };
+        let (_, path) = transcode.get_output();
+        if !path.exists() {
+            remaining.push(job);
+            continue;
+        }
+        match transcode.check_existing() {
+            Ok(()) => trace!("{} {}", "Keeping".bold(), path.display()),
+            Err(error) => {
+                debug!(
+                    "{} incomplete {}: {error}",
+                    "Replacing".bold(),
+                    path.display()
+                );
+                if let Err(e) = remove_file(&path) {
+                    warn!("{} to remove {}: {e}", "Failed".bold(), path.display());
+                }
+                remaining.push(job);
+            }
+        }
+    }
+    let kept = total - remaining.len();
+    if kept > 0 {
+        info!(
+            "{} {kept} of {total} {target} tracks transcoded by an earlier attempt",
+            "Keeping".bold()
+        );
+    }
+    remaining
+}
+
+/// Get the total duration in seconds of the FLAC files.
+///
+/// Files that can't be read are excluded.
+fn get_total_duration(flacs: &[FlacFile]) -> u32 {
+    flacs
+        .iter()
+        .filter_map(|flac| flac.get_stream_info().ok())
+        .filter_map(|info| get_duration(&info))
+        .sum()
 }
@@ -1,35 +1,84 @@
-use crate::errors::{command_error, error, io_error, OutputHandler};
-use crate::transcode::{Decode, Encode, Resample, Variant};
+use crate::dependencies::METAFLAC;
+use crate::errors::{command_error, error, get_command_line, path_error, OutputHandler};
+use crate::formats::TranscodeTarget;
+use crate::musicbrainz::MusicBrainzRelease;
+use crate::progress::{ProgressEmitter, ProgressEvent};
+use crate::summary::{TimingPhase, Timings};
+use crate::transcode::{
+    check_existing_output, check_sample_count, write_cue_sheet, write_tag_to_path, CommandInfo,
+    CueSheet, Decode, OutputTarget, Pipeline, PipelineInput, Provenance, Resample, SampleCount,
+    TrackPrefetcher, Variant,
+};
+use colored::Colorize;
+use di::Ref;
 use lofty::config::WriteOptions;
 use lofty::prelude::TagExt;
 use lofty::tag::ItemKey::{Popularimeter, Work};
 use lofty::tag::Tag;
 use log::{trace, warn};
 use rogue_logging::Error;
-use std::fs::create_dir_all;
-use std::process::Stdio;
-use tokio::join;
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
 
 pub struct TranscodeJob {
     pub id: String,
     pub variant: Variant,
     pub tags: Option<Tag>,
+    /// Release to fill missing tags from if `enrich_tags` is set.
+    pub release: Option<Ref<MusicBrainzRelease>>,
+    /// Embedded cue sheet of the source.
+    ///
+    /// Written to the output FLAC, or to a sidecar `.cue` of an MP3.
+    pub cue_sheet: Option<CueSheet>,
+    /// Number of samples of the source to check the output against.
+    pub source_samples: Option<SampleCount>,
+    /// Provenance to tag the output with if `provenance_tag` is set.
+    pub provenance: Option<Ref<Provenance>>,
+    /// Read ahead to take the source track from instead of reading it directly.
+    pub prefetcher: Option<Ref<TrackPrefetcher>>,
+    pub progress: Ref<ProgressEmitter>,
+    pub timings: Ref<Timings>,
 }
 
 impl TranscodeJob {
     pub async fn execute(self) -> Result<(), Error> {
-        let output_path = match &self.variant {
-            Variant::Transcode(_, encode) => encode.output.clone(),
-            Variant::Resample(resample) => resample.output.clone(),
-        };
+        let start = Instant::now();
+        let (target, output_path) = self.variant.get_output();
+        let provenance_tag = self
+            .provenance
+            .as_ref()
+            .and_then(|provenance| provenance.get_tag(&self.variant.get_display()));
         let output_dir = output_path
             .parent()
             .expect("output path should have a parent");
-        create_dir_all(output_dir).map_err(|e| io_error(e, "create transcode output directory"))?;
-        match self.variant {
-            Variant::Transcode(decode, encode) => execute_transcode(decode, encode).await?,
-            Variant::Resample(resample) => execute_resample(resample).await?,
+        create_dir_all(output_dir)
+            .map_err(|e| path_error(e, "create transcode output directory", output_dir))?;
+        let prefetched = match &self.prefetcher {
+            Some(prefetcher) => prefetcher.take(self.get_input()).await,
+            None => None,
+        };
+        let input = match prefetched {
+            Some(data) => PipelineInput::Memory(data),
+            None => PipelineInput::File(self.get_input().to_path_buf()),
         };
+        match &self.variant {
+            Variant::Transcode(decode, encode) => {
+                let encode = encode.clone().to_info();
+                let decode_elapsed = execute_transcode(decode.clone(), encode, input).await?;
+                self.timings.record(TimingPhase::Decode, decode_elapsed);
+            }
+            Variant::Custom(decode, encode) => {
+                let encode = encode.clone().to_info();
+                let decode_elapsed = execute_transcode(decode.clone(), encode, input).await?;
+                self.timings.record(TimingPhase::Decode, decode_elapsed);
+            }
+            Variant::Resample(resample) => execute_resample(resample.clone(), input).await?,
+        }
+        if let Some(source_samples) = self.source_samples {
+            check_sample_count(&output_path, target, source_samples)?;
+        }
         if let Some(mut tags) = self.tags {
             let exclude = [Popularimeter, Work];
             for key in exclude {
@@ -38,58 +87,153 @@ impl TranscodeJob {
                     tags.remove_key(&key);
                 }
             }
+            if let Some(release) = &self.release {
+                release.apply(&mut tags);
+            }
+            if let Some((key, value)) = provenance_tag {
+                tags.insert_text(key, value);
+            }
             tags.save_to_path(&output_path, WriteOptions::default())
                 .map_err(|e| error("write tags", e.to_string()))?;
+        } else {
+            if let Some(release) = &self.release {
+                release.write_to_path(&output_path)?;
+            }
+            if let Some((key, value)) = provenance_tag {
+                write_tag_to_path(&output_path, key, value)?;
+            }
         }
+        if let Some(cue_sheet) = &self.cue_sheet {
+            if target.is_mp3() {
+                write_sidecar_cue_sheet(&output_path, cue_sheet)?;
+            } else {
+                write_cue_sheet(&output_path, cue_sheet)?;
+            }
+        }
+        if !target.is_mp3() {
+            add_seek_points(&output_path).await;
+        }
+        // Custom targets are not uploaded so only the targets of the indexer are measured
+        let OutputTarget::Format(format) = target else {
+            return Ok(());
+        };
+        let elapsed = start.elapsed();
+        self.timings.record(TimingPhase::Encode(format), elapsed);
+        let name = output_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.timings.record_track(format, name, elapsed);
+        self.progress.emit(ProgressEvent::TrackTranscoded {
+            format,
+            path: output_path,
+        });
         Ok(())
     }
+
+    /// Get the path of the source track.
+    #[must_use]
+    pub fn get_input(&self) -> &Path {
+        self.variant.get_input()
+    }
+
+    /// Get the target and path of the output.
+    #[must_use]
+    pub fn get_output(&self) -> (OutputTarget<'_>, PathBuf) {
+        self.variant.get_output()
+    }
+
+    /// Check the output left by an earlier attempt is complete so it can be kept.
+    pub fn check_existing(&self) -> Result<(), Error> {
+        let (target, output_path) = self.get_output();
+        check_existing_output(
+            &output_path,
+            target,
+            self.source_samples,
+            self.cue_sheet.is_some(),
+        )
+    }
+}
+
+/// Decode, resample if required, then encode as a [`Pipeline`].
+///
+/// Returns the wall time until the decode stages exited.
+async fn execute_transcode(
+    decode: Decode,
+    encode: CommandInfo,
+    input: PipelineInput,
+) -> Result<Duration, Error> {
+    let mut stages = decode.to_stages();
+    stages.push(encode);
+    let mut outputs = Pipeline { input, stages }.execute().await?;
+    let encode_output = outputs.pop().expect("pipeline should have an encode stage");
+    let decode_elapsed = outputs
+        .last()
+        .map(|output| output.elapsed)
+        .unwrap_or_default();
+    for output in outputs {
+        if let Err(error) =
+            OutputHandler::execute_command(output.output, "decode", &output.program, &output.line)
+        {
+            warn!("Decode was not successful: {}", error.message);
+        }
+    }
+    OutputHandler::execute_command(
+        encode_output.output,
+        "execute transcode job",
+        &encode_output.program,
+        &encode_output.line,
+    )?;
+    Ok(decode_elapsed)
 }
 
-async fn execute_transcode(decode: Decode, encode: Encode) -> Result<(), Error> {
-    let decode_info = decode.to_info();
-    let encode_info = encode.to_info();
-    trace!("Executing transcode: {decode_info} | {encode_info}");
-    let decode_program = decode_info.program.clone();
-    let mut decode_command = decode_info
-        .to_command()
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| command_error(e, "spawn decode", &decode_program))?;
-    let pipe: Stdio = decode_command
-        .stdout
-        .take()
-        .expect("should be able to take stdout")
-        .try_into()
-        .expect("should be able to convert stdout to pipe");
-    let encode_program = encode_info.program.clone();
-    let encode_command = encode_info
-        .to_command()
-        .stdin(pipe)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| command_error(e, "spawn decode", &encode_program))?;
-    let (decode_result, encode_output) =
-        join!(decode_command.wait(), encode_command.wait_with_output());
-    let decode_exit = decode_result.map_err(|e| io_error(e, "wait for decode"))?;
-    let encode_output = encode_output.map_err(|e| io_error(e, "wait for encode"))?;
-    if !decode_exit.success() {
-        warn!("Decode was not successful: {decode_exit}");
+/// Resample a FLAC to a FLAC as a [`Pipeline`].
+async fn execute_resample(resample: Resample, input: PipelineInput) -> Result<(), Error> {
+    let stages = resample.to_stages();
+    let outputs = Pipeline { input, stages }.execute().await?;
+    for output in outputs {
+        OutputHandler::execute_command(
+            output.output,
+            "execute resample job",
+            &output.program,
+            &output.line,
+        )?;
     }
-    OutputHandler::execute(encode_output, "execute resample job", "transcode")?;
     Ok(())
 }
 
-async fn execute_resample(resample: Resample) -> Result<(), Error> {
-    let info = resample.to_info();
-    trace!("Executing resample: {info}");
-    let program = info.program.clone();
-    let output = info
-        .to_command()
+/// Write a `.cue` file next to an MP3 that refers to it.
+fn write_sidecar_cue_sheet(output_path: &Path, cue_sheet: &CueSheet) -> Result<(), Error> {
+    let file_name = output_path
+        .file_name()
+        .expect("output path should have a file name")
+        .to_string_lossy();
+    let path = output_path.with_extension("cue");
+    write(&path, cue_sheet.to_cue(&file_name)).map_err(|e| path_error(e, "write cue sheet", &path))
+}
+
+/// Regenerate the seek table of a FLAC with a point every 10 seconds.
+///
+/// A FLAC encoded from a pipe has no seek table as the length isn't known in advance.
+///
+/// Failures are logged as warnings as the seek table is optional.
+async fn add_seek_points(path: &Path) {
+    let mut command = Command::new(METAFLAC);
+    command.arg("--add-seekpoint=10s").arg(path);
+    let line = get_command_line(&command);
+    let output = command
         .output()
         .await
-        .map_err(|e| command_error(e, "execute resample job", &program))?;
-    OutputHandler::execute(output, "execute resample job", "transcode")?;
-    Ok(())
+        .map_err(|e| command_error(e, "add seek points", METAFLAC))
+        .and_then(|output| {
+            OutputHandler::execute_command(output, "add seek points", METAFLAC, &line)
+        });
+    if let Err(error) = output {
+        warn!(
+            "{} to add seek points to {}",
+            "Failed".bold(),
+            path.display()
+        );
+        warn!("{error}");
+    }
 }
@@ -1,10 +1,16 @@
+use colored::Colorize;
 use di::{injectable, Ref};
+use log::trace;
 
 use crate::errors::claxon_error;
 use crate::formats::target_format::TargetFormat;
+use crate::formats::{CustomTarget, TranscodeTarget};
 use crate::fs::{convert_to_id3v2, fix_track_numbering, get_vorbis_tags, FlacFile, PathManager};
 use crate::jobs::Job;
+use crate::musicbrainz::MusicBrainzRelease;
+use crate::progress::ProgressEmitter;
 use crate::source::Source;
+use crate::summary::Timings;
 use crate::transcode::transcode_job::TranscodeJob;
 use crate::transcode::*;
 use rogue_logging::Error;
@@ -13,19 +19,53 @@ use rogue_logging::Error;
 #[injectable]
 pub struct TranscodeJobFactory {
     paths: Ref<PathManager>,
+    clip_checker: Ref<ClipChecker>,
+    progress: Ref<ProgressEmitter>,
+    timings: Ref<Timings>,
 }
 
 impl TranscodeJobFactory {
     /// Create a [`TranscodeJob`] for each [`FlacFile`] in the [`Vec<FlacFile>`].
+    ///
+    /// If a `release` is provided then missing tags of each output are filled from it.
     pub fn create(
         &self,
         flacs: &[FlacFile],
         source: &Source,
         format: TargetFormat,
+        release: Option<&Ref<MusicBrainzRelease>>,
+    ) -> Result<Vec<Job>, Error> {
+        self.create_all(flacs, source, OutputTarget::Format(format), release)
+    }
+
+    /// Create a [`TranscodeJob`] of a [`CustomTarget`] for each [`FlacFile`] in the
+    /// [`Vec<FlacFile>`].
+    ///
+    /// If a `release` is provided then missing tags of each output are filled from it.
+    pub fn create_custom(
+        &self,
+        flacs: &[FlacFile],
+        source: &Source,
+        target: &CustomTarget,
+        release: Option<&Ref<MusicBrainzRelease>>,
+    ) -> Result<Vec<Job>, Error> {
+        self.create_all(flacs, source, OutputTarget::Custom(target), release)
+    }
+
+    fn create_all(
+        &self,
+        flacs: &[FlacFile],
+        source: &Source,
+        target: OutputTarget<'_>,
+        release: Option<&Ref<MusicBrainzRelease>>,
     ) -> Result<Vec<Job>, Error> {
         let mut jobs = Vec::new();
         for (index, flac) in flacs.iter().enumerate() {
-            jobs.push(self.create_single(index, flac, source, format)?);
+            let mut job = self.create_for_target(index, flac, source, target)?;
+            if let Job::Transcode(transcode) = &mut job {
+                transcode.release = release.cloned();
+            }
+            jobs.push(job);
         }
         Ok(jobs)
     }
@@ -37,34 +77,68 @@ impl TranscodeJobFactory {
         flac: &FlacFile,
         source: &Source,
         format: TargetFormat,
+    ) -> Result<Job, Error> {
+        self.create_for_target(index, flac, source, OutputTarget::Format(format))
+    }
+
+    fn create_for_target(
+        &self,
+        index: usize,
+        flac: &FlacFile,
+        source: &Source,
+        target: OutputTarget<'_>,
     ) -> Result<Job, Error> {
         let info = flac
             .get_stream_info()
             .map_err(|e| claxon_error(e, "read FLAC"))?;
-        let id = format!("Transcode {:<4}{index:>3}", format.to_string());
-        let output_path = self.paths.get_transcode_path(source, format, flac);
-        let variant = if matches!(format, TargetFormat::Flac) && is_resample_required(&info) {
-            Variant::Resample(Resample {
-                input: flac.path.clone(),
-                output: output_path.clone(),
-                resample_rate: get_resample_rate(&info)?,
-            })
-        } else {
-            let resample_rate = is_resample_required(&info)
-                .then(|| get_resample_rate(&info))
-                .transpose()?;
-            Variant::Transcode(
-                Decode {
+        let id = format!("Transcode {:<4}{index:>3}", target.to_string());
+        let output_path = self.paths.get_staged_transcode_path(source, target, flac);
+        let resample_rate = is_resample_required(&info)
+            .then(|| get_resample_rate(&info))
+            .transpose()?;
+        let cue_sheet = match CueSheet::read(&flac.path) {
+            Ok(cue_sheet) => cue_sheet
+                .map(|cue_sheet| cue_sheet.resample(resample_rate.unwrap_or(info.sample_rate))),
+            Err(error) => {
+                trace!("{} to read cue sheet: {error}", "Failed".bold());
+                None
+            }
+        };
+        let decode = Decode {
+            input: flac.path.clone(),
+            resample_rate,
+        };
+        let scale = target
+            .is_mp3()
+            .then(|| self.clip_checker.get_gain(source))
+            .flatten()
+            .map(to_scale);
+        let variant = match (target, resample_rate) {
+            (OutputTarget::Format(TargetFormat::Flac), Some(resample_rate)) => {
+                Variant::Resample(Resample {
                     input: flac.path.clone(),
+                    output: output_path.clone(),
                     resample_rate,
-                },
+                })
+            }
+            (OutputTarget::Format(format), _) => Variant::Transcode(
+                decode,
                 Encode {
                     format,
                     output: output_path.clone(),
+                    scale,
                 },
-            )
+            ),
+            (OutputTarget::Custom(target), _) => Variant::Custom(
+                decode,
+                Box::new(CustomEncode {
+                    output: output_path.clone(),
+                    target: target.clone(),
+                    scale,
+                }),
+            ),
         };
-        let tags = if matches!(format, TargetFormat::_320 | TargetFormat::V0) {
+        let tags = if target.is_mp3() {
             let mut tags = get_vorbis_tags(flac)?;
             convert_to_id3v2(&mut tags);
             let _ = fix_track_numbering(&mut tags);
@@ -72,6 +146,20 @@ impl TranscodeJobFactory {
         } else {
             None
         };
-        Ok(Job::Transcode(TranscodeJob { id, variant, tags }))
+        Ok(Job::Transcode(TranscodeJob {
+            id,
+            variant,
+            tags,
+            release: None,
+            cue_sheet,
+            source_samples: info.samples.map(|samples| SampleCount {
+                samples,
+                sample_rate: info.sample_rate,
+            }),
+            provenance: None,
+            prefetcher: None,
+            progress: self.progress.clone(),
+            timings: self.timings.clone(),
+        }))
     }
 }
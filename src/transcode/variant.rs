@@ -1,8 +1,122 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::formats::{CustomTarget, TargetFormat, TranscodeTarget};
+
+use crate::transcode::command_info::CommandInfo;
 use crate::transcode::decode::Decode;
-use crate::transcode::encode::Encode;
+use crate::transcode::encode::{CustomEncode, Encode};
 use crate::transcode::resample::Resample;
 
 pub enum Variant {
     Transcode(Decode, Encode),
     Resample(Resample),
+    /// Transcode to a [`CustomTarget`].
+    ///
+    /// [`CustomTarget`]: crate::formats::CustomTarget
+    Custom(Decode, Box<CustomEncode>),
+}
+
+/// Target of the output of a [`Variant`].
+#[derive(Clone, Copy, Debug)]
+pub enum OutputTarget<'a> {
+    Format(TargetFormat),
+    Custom(&'a CustomTarget),
+}
+
+impl Variant {
+    /// Get the path of the source track.
+    #[must_use]
+    pub fn get_input(&self) -> &Path {
+        match self {
+            Variant::Transcode(decode, _) | Variant::Custom(decode, _) => &decode.input,
+            Variant::Resample(resample) => &resample.input,
+        }
+    }
+
+    /// Get the target and path of the output.
+    #[must_use]
+    pub fn get_output(&self) -> (OutputTarget<'_>, PathBuf) {
+        match self {
+            Variant::Transcode(_, encode) => {
+                (OutputTarget::Format(encode.format), encode.output.clone())
+            }
+            Variant::Resample(resample) => (
+                OutputTarget::Format(TargetFormat::Flac),
+                resample.output.clone(),
+            ),
+            Variant::Custom(_, encode) => {
+                (OutputTarget::Custom(&encode.target), encode.output.clone())
+            }
+        }
+    }
+
+    /// Get a string representation of the CLI command of each stage with the output
+    /// path replaced by `output.{extension}`.
+    ///
+    /// Intended to record the exact settings of a transcode without the local paths.
+    #[must_use]
+    pub fn get_display(&self) -> String {
+        let stages = match self {
+            Variant::Transcode(decode, encode) => {
+                let mut encode = encode.clone();
+                encode.output = get_output_placeholder(&encode.output);
+                let mut stages = decode.clone().to_stages();
+                stages.push(encode.to_info());
+                stages
+            }
+            Variant::Resample(resample) => {
+                let mut resample = resample.clone();
+                resample.output = get_output_placeholder(&resample.output);
+                resample.to_stages()
+            }
+            Variant::Custom(decode, encode) => {
+                let mut encode = encode.clone();
+                encode.output = get_output_placeholder(&encode.output);
+                let mut stages = decode.clone().to_stages();
+                stages.push(encode.to_info());
+                stages
+            }
+        };
+        let stages: Vec<String> = stages.iter().map(CommandInfo::display).collect();
+        stages.join(" | ")
+    }
+}
+
+fn get_output_placeholder(output: &Path) -> PathBuf {
+    let extension = output
+        .extension()
+        .expect("output should have an extension")
+        .to_string_lossy();
+    PathBuf::from(format!("output.{extension}"))
+}
+
+impl TranscodeTarget for OutputTarget<'_> {
+    fn get_name(&self) -> &str {
+        match self {
+            OutputTarget::Format(format) => format.get_name(),
+            OutputTarget::Custom(target) => target.get_name(),
+        }
+    }
+
+    fn get_file_extension(&self) -> String {
+        match self {
+            OutputTarget::Format(format) => format.get_file_extension(),
+            OutputTarget::Custom(target) => TranscodeTarget::get_file_extension(target),
+        }
+    }
+
+    fn is_mp3(&self) -> bool {
+        match self {
+            OutputTarget::Format(format) => format.is_mp3(),
+            OutputTarget::Custom(target) => TranscodeTarget::is_mp3(target),
+        }
+    }
+}
+
+impl Display for OutputTarget<'_> {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.get_name())
+    }
 }
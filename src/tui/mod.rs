@@ -0,0 +1,13 @@
+pub use tui::*;
+pub use tui_logger::*;
+pub use tui_renderer::*;
+pub use tui_state::*;
+pub use tui_subscriber::*;
+
+#[cfg(test)]
+mod tests;
+pub(crate) mod tui;
+pub(crate) mod tui_logger;
+pub(crate) mod tui_renderer;
+pub(crate) mod tui_state;
+pub(crate) mod tui_subscriber;
@@ -0,0 +1,2 @@
+mod tui_renderer_tests;
+mod tui_state_tests;
@@ -0,0 +1,79 @@
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Cell;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Terminal;
+
+use crate::db::Hash;
+use crate::hosting::Shutdown;
+use crate::tui::{get_header, handle_key, render, ItemStatus, TuiState};
+
+fn get_state() -> TuiState {
+    let state = TuiState::new();
+    state.set_items(vec![
+        (Hash::new([1; 20]), "Fear Not".to_owned()),
+        (Hash::new([2; 20]), "Reality Checkpoint".to_owned()),
+    ]);
+    state.set_status(Hash::new([1; 20]), ItemStatus::Done);
+    state.set_status(Hash::new([2; 20]), ItemStatus::Active);
+    state.set_step("Transcoding");
+    state
+}
+
+#[test]
+fn tui_render() {
+    // Arrange
+    let state = get_state();
+    state.toggle_pause();
+    let snapshot = state.get_snapshot();
+    let logs = vec!["INFO  Transcoding Reality Checkpoint".to_owned()];
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("should create terminal");
+
+    // Act
+    terminal
+        .draw(|frame| render(frame, &snapshot, &logs))
+        .expect("should draw");
+
+    // Assert
+    let buffer = terminal.backend().buffer();
+    let content: String = buffer
+        .content()
+        .iter()
+        .map(Cell::symbol)
+        .collect();
+    assert_eq!(
+        get_header(&snapshot),
+        "caesura batch: 1 of 2 processed, 0 failed, 0 skipped [paused]"
+    );
+    assert!(content.contains("caesura batch: 1 of 2 processed"));
+    assert!(content.contains("✓ Fear Not"));
+    assert!(content.contains("▶ Reality Checkpoint"));
+    assert!(content.contains("Transcoding"));
+    assert!(content.contains("INFO  Transcoding Reality Checkpoint"));
+}
+
+#[test]
+fn tui_handle_key() {
+    // Arrange
+    let state = get_state();
+    let shutdown = Shutdown::new();
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    // Act
+    let pause = handle_key(&state, &shutdown, press(KeyCode::Char('p')));
+    let up = handle_key(&state, &shutdown, press(KeyCode::Up));
+    let first_quit = handle_key(&state, &shutdown, press(KeyCode::Char('q')));
+    let second_quit = handle_key(
+        &state,
+        &shutdown,
+        KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    );
+
+    // Assert
+    assert!(!pause);
+    assert!(!up);
+    assert!(state.is_paused());
+    assert_eq!(state.get_snapshot().selected, 0);
+    assert!(!first_quit);
+    assert!(shutdown.is_requested());
+    assert!(second_quit);
+}
@@ -0,0 +1,89 @@
+use crate::db::Hash;
+use crate::jobs::Status;
+use crate::tui::{ItemStatus, TuiState};
+
+fn get_state() -> TuiState {
+    let state = TuiState::new();
+    state.set_items(vec![
+        (Hash::new([1; 20]), "Fear Not".to_owned()),
+        (Hash::new([2; 20]), "Reality Checkpoint".to_owned()),
+        (Hash::new([3; 20]), "Crash Bang Wallop".to_owned()),
+    ]);
+    state
+}
+
+#[test]
+fn tui_state_skip_selected() {
+    // Arrange
+    let state = get_state();
+    state.set_status(Hash::new([1; 20]), ItemStatus::Active);
+    state.select_next();
+
+    // Act
+    state.request_skip();
+
+    // Assert
+    assert!(!state.is_skip_requested(Hash::new([1; 20])));
+    assert!(state.is_skip_requested(Hash::new([2; 20])));
+    state.set_status(Hash::new([2; 20]), ItemStatus::Skipped);
+    assert!(!state.is_skip_requested(Hash::new([2; 20])));
+}
+
+#[test]
+fn tui_state_retry_failed() {
+    // Arrange
+    let state = get_state();
+    state.set_status(Hash::new([1; 20]), ItemStatus::Done);
+    state.set_status(Hash::new([2; 20]), ItemStatus::Failed);
+
+    // Act
+    state.request_retry();
+    state.select_next();
+    state.request_retry();
+
+    // Assert
+    assert_eq!(state.take_retries(), vec![Hash::new([2; 20])]);
+    assert!(state.take_retries().is_empty());
+    let snapshot = state.get_snapshot();
+    let statuses: Vec<ItemStatus> = snapshot.items.iter().map(|item| item.status).collect();
+    assert_eq!(
+        statuses,
+        vec![ItemStatus::Done, ItemStatus::Pending, ItemStatus::Pending]
+    );
+}
+
+#[test]
+fn tui_state_jobs_of_step() {
+    // Arrange
+    let state = get_state();
+    state.set_step("Transcoding");
+
+    // Act
+    state.update_job("Transcode V0     0", &Status::Created);
+    state.update_job("Transcode V0     1", &Status::Created);
+    state.update_job("Transcode V0     0", &Status::Started);
+    state.update_job("Transcode V0     0", &Status::Completed);
+    state.update_job("Transcode V0     1", &Status::Started);
+
+    // Assert
+    let snapshot = state.get_snapshot();
+    assert_eq!(snapshot.step.as_deref(), Some("Transcoding"));
+    assert_eq!(snapshot.jobs.len(), 2);
+    assert_eq!(snapshot.jobs.iter().filter(|job| job.completed).count(), 1);
+    state.set_step("Uploading");
+    assert!(state.get_snapshot().jobs.is_empty());
+}
+
+#[test]
+fn tui_state_toggle_pause() {
+    // Arrange
+    let state = get_state();
+
+    // Act
+    state.toggle_pause();
+
+    // Assert
+    assert!(state.is_paused());
+    state.toggle_pause();
+    assert!(!state.is_paused());
+}
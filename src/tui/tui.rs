@@ -0,0 +1,118 @@
+use std::io::{stdout, IsTerminal};
+use std::process::exit;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{spawn, JoinHandle};
+use std::time::Duration;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::warn;
+use ratatui::crossterm::event::{poll, read, Event, KeyEventKind};
+use ratatui::{restore, try_init};
+
+use crate::hosting::{ExitStatus, Shutdown};
+use crate::options::SharedOptions;
+use crate::progress::ProgressFormat;
+use crate::tui::{get_log_lines, handle_key, render, set_tui_active, TuiState, LOG_LINES};
+
+/// Interval to redraw the console and check for key presses.
+const TICK: Duration = Duration::from_millis(250);
+
+/// An interactive interface of a batch in the console when `progress` is `tui`.
+///
+/// The queue, the jobs of the current step and the most recent logs are drawn in
+/// place of the logs, and keys pause, skip or retry items.
+#[injectable]
+pub struct Tui {
+    options: Ref<SharedOptions>,
+    state: Ref<TuiState>,
+    shutdown: Ref<Shutdown>,
+}
+
+/// Stop drawing [`Tui`] and restore the console when dropped.
+pub struct TuiGuard {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Tui {
+    /// Is `progress` set to `tui`?
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.options.progress == Some(ProgressFormat::Tui)
+    }
+
+    /// Start drawing the console on a separate thread.
+    ///
+    /// Returns `None` if `progress` isn't `tui` or stdout isn't a terminal.
+    #[must_use]
+    pub fn start(&self) -> Option<TuiGuard> {
+        if !self.is_enabled() {
+            return None;
+        }
+        if !stdout().is_terminal() {
+            warn!(
+                "{} the interactive interface as stdout is not a terminal",
+                "Skipping".bold()
+            );
+            return None;
+        }
+        let mut terminal = match try_init() {
+            Ok(terminal) => terminal,
+            Err(error) => {
+                warn!(
+                    "{} to start the interactive interface: {error}",
+                    "Failed".bold()
+                );
+                return None;
+            }
+        };
+        set_tui_active(true);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let state = self.state.clone();
+        let shutdown = self.shutdown.clone();
+        let handle = spawn(move || {
+            while !thread_stop.load(Ordering::SeqCst) {
+                let snapshot = state.get_snapshot();
+                let logs = get_log_lines(usize::from(LOG_LINES));
+                let _ = terminal.draw(|frame| render(frame, &snapshot, &logs));
+                if !poll(TICK).unwrap_or(false) {
+                    continue;
+                }
+                let Ok(Event::Key(key)) = read() else {
+                    continue;
+                };
+                if key.kind == KeyEventKind::Press && handle_key(&state, &shutdown, key) {
+                    stop_drawing();
+                    exit(i32::from(ExitStatus::Interrupted.get_code()));
+                }
+            }
+            stop_drawing();
+        });
+        Some(TuiGuard {
+            stop,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for TuiGuard {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Restore the console and write the log lines that were kept while drawing.
+#[allow(clippy::print_stderr)]
+fn stop_drawing() {
+    restore();
+    set_tui_active(false);
+    for line in get_log_lines(usize::MAX) {
+        eprintln!("{line}");
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use log::{Log, Metadata, Record};
+
+use crate::logging::{get_log_source, remove_colors};
+
+/// Most log lines kept while [`Tui`] is active.
+const MAX_LINES: usize = 200;
+
+/// Is [`Tui`] drawing the console?
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Log lines written while [`Tui`] is active, oldest first.
+static LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// A [`Log`] that keeps log events for [`Tui`] while it's drawing the console,
+/// and otherwise forwards them to the console logger.
+pub struct TuiLogger {
+    pub inner: Box<dyn Log>,
+}
+
+impl Log for TuiLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !ACTIVE.load(Ordering::SeqCst) {
+            self.inner.log(record);
+            return;
+        }
+        let level = record.level().as_str();
+        let message = remove_colors(&record.args().to_string());
+        let line = match get_log_source() {
+            Some(source) => format!("{level:<5} [{source}] {message}"),
+            None => format!("{level:<5} {message}"),
+        };
+        push_log_line(line);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Set whether [`Tui`] is drawing the console so log events are kept rather than written.
+pub fn set_tui_active(active: bool) {
+    ACTIVE.store(active, Ordering::SeqCst);
+}
+
+/// Is [`Tui`] drawing the console?
+#[must_use]
+pub fn is_tui_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Keep a log line, removing the oldest if there are more than [`MAX_LINES`].
+pub fn push_log_line(line: String) {
+    let Ok(mut lines) = LINES.lock() else {
+        return;
+    };
+    if lines.len() >= MAX_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
+/// Get the most recent log lines, oldest first.
+#[must_use]
+pub fn get_log_lines(count: usize) -> Vec<String> {
+    let Ok(lines) = LINES.lock() else {
+        return Vec::new();
+    };
+    let skip = lines.len().saturating_sub(count);
+    lines.iter().skip(skip).cloned().collect()
+}
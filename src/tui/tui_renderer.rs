@@ -0,0 +1,152 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Gauge, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::hosting::Shutdown;
+use crate::tui::{push_log_line, ItemStatus, TuiSnapshot, TuiState};
+
+/// Number of log lines shown by [`Tui`].
+pub const LOG_LINES: u16 = 8;
+
+/// Keys shown at the bottom of [`Tui`].
+const HELP: &str = "↑/↓ select  p pause  s skip  r retry  q quit";
+
+/// Draw a [`TuiSnapshot`] and the most recent log lines.
+#[allow(clippy::indexing_slicing)]
+pub fn render(frame: &mut Frame, snapshot: &TuiSnapshot, logs: &[String]) {
+    let [header, queue, progress, log, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(4),
+        Constraint::Length(LOG_LINES + 2),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    frame.render_widget(Paragraph::new(get_header(snapshot)), header);
+    render_queue(frame, snapshot, queue);
+    render_progress(frame, snapshot, progress);
+    frame.render_widget(
+        Paragraph::new(logs.join("\n")).block(Block::bordered().title(" Logs ")),
+        log,
+    );
+    frame.render_widget(
+        Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+}
+
+/// Get the header line with the totals of the queue.
+#[must_use]
+pub fn get_header(snapshot: &TuiSnapshot) -> String {
+    let count = |status| {
+        snapshot
+            .items
+            .iter()
+            .filter(|item| item.status == status)
+            .count()
+    };
+    let mut header = format!(
+        "caesura batch: {} of {} processed, {} failed, {} skipped",
+        count(ItemStatus::Done),
+        snapshot.items.len(),
+        count(ItemStatus::Failed),
+        count(ItemStatus::Skipped)
+    );
+    if snapshot.paused {
+        header.push_str(" [paused]");
+    }
+    header
+}
+
+fn render_queue(frame: &mut Frame, snapshot: &TuiSnapshot, area: Rect) {
+    let items: Vec<ListItem> = snapshot
+        .items
+        .iter()
+        .map(|item| {
+            let (symbol, color) = match item.status {
+                ItemStatus::Pending => ("·", Color::Gray),
+                ItemStatus::Active => ("▶", Color::Cyan),
+                ItemStatus::Done => ("✓", Color::Green),
+                ItemStatus::Failed => ("✗", Color::Red),
+                ItemStatus::Skipped => ("-", Color::DarkGray),
+            };
+            ListItem::new(format!("{symbol} {}", item.name)).style(Style::default().fg(color))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(" Queue "))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(snapshot.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_precision_loss,
+    clippy::indexing_slicing
+)]
+fn render_progress(frame: &mut Frame, snapshot: &TuiSnapshot, area: Rect) {
+    let title = format!(" {} ", snapshot.step.as_deref().unwrap_or("Waiting"));
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [gauge, running] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+    let total = snapshot.jobs.len();
+    let completed = snapshot.jobs.iter().filter(|job| job.completed).count();
+    let ratio = if total == 0 {
+        0.0
+    } else {
+        completed as f64 / total as f64
+    };
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::Blue))
+            .ratio(ratio)
+            .label(format!("{completed}/{total}")),
+        gauge,
+    );
+    let running_jobs: Vec<&str> = snapshot
+        .jobs
+        .iter()
+        .filter(|job| job.started && !job.completed)
+        .map(|job| job.id.as_str())
+        .collect();
+    frame.render_widget(
+        Paragraph::new(running_jobs.join(", ")).style(Style::default().fg(Color::DarkGray)),
+        running,
+    );
+}
+
+/// Apply a key pressed in [`Tui`].
+///
+/// Returns `true` if the application should stop immediately as shutdown was already
+/// requested.
+pub fn handle_key(state: &TuiState, shutdown: &Shutdown, key: KeyEvent) -> bool {
+    let is_interrupt =
+        key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        _ if is_interrupt => return request_shutdown(shutdown),
+        KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+        KeyCode::Char('p' | ' ') => state.toggle_pause(),
+        KeyCode::Char('s') => state.request_skip(),
+        KeyCode::Char('r') => state.request_retry(),
+        KeyCode::Char('q') => return request_shutdown(shutdown),
+        _ => {}
+    }
+    false
+}
+
+fn request_shutdown(shutdown: &Shutdown) -> bool {
+    if shutdown.is_requested() {
+        return true;
+    }
+    shutdown.request();
+    push_log_line(
+        "WARN  Stopping after the current step. Press q again to stop immediately".to_owned(),
+    );
+    false
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use di::injectable;
+use tokio::time::sleep;
+
+use crate::db::Hash;
+use crate::hosting::Shutdown;
+use crate::jobs::Status;
+
+/// Interval to check if the batch has been resumed or an item retried.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Status of an item of the queue shown by [`Tui`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ItemStatus {
+    /// Waiting to be processed.
+    Pending,
+    /// Currently being processed.
+    Active,
+    /// Processed successfully.
+    Done,
+    /// Transcode or upload failed.
+    Failed,
+    /// Skipped as it's unsuitable, locked or skipped from the keyboard.
+    Skipped,
+}
+
+/// An item of the queue shown by [`Tui`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TuiItem {
+    pub hash: Hash<20>,
+    pub name: String,
+    pub status: ItemStatus,
+}
+
+/// A job of the current step shown by [`Tui`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TuiJob {
+    pub id: String,
+    pub started: bool,
+    pub completed: bool,
+}
+
+/// Everything shown by [`Tui`].
+#[derive(Clone, Debug, Default)]
+pub struct TuiSnapshot {
+    /// Items of the queue in the order they are processed.
+    pub items: Vec<TuiItem>,
+    /// Index of the item selected with the arrow keys.
+    pub selected: usize,
+    /// Step of the active item such as `Transcoding`.
+    pub step: Option<String>,
+    /// Jobs of the current step.
+    pub jobs: Vec<TuiJob>,
+    /// Is the batch paused before the next item?
+    pub paused: bool,
+}
+
+/// State of the batch shared between [`BatchCommand`] and [`Tui`].
+///
+/// Keys pressed in [`Tui`] request a pause, skip or retry which [`BatchCommand`]
+/// applies between the steps of an item.
+#[derive(Default)]
+pub struct TuiState {
+    snapshot: Mutex<TuiSnapshot>,
+    skips: Mutex<BTreeSet<Hash<20>>>,
+    retries: Mutex<Vec<Hash<20>>>,
+}
+
+#[injectable]
+impl TuiState {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a copy of everything shown by [`Tui`].
+    #[must_use]
+    pub fn get_snapshot(&self) -> TuiSnapshot {
+        self.snapshot
+            .lock()
+            .map(|snapshot| snapshot.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the items of the queue as pending.
+    pub fn set_items(&self, items: Vec<(Hash<20>, String)>) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.items = items
+                .into_iter()
+                .map(|(hash, name)| TuiItem {
+                    hash,
+                    name,
+                    status: ItemStatus::Pending,
+                })
+                .collect();
+            snapshot.selected = 0;
+        }
+    }
+
+    /// Set the status of an item.
+    ///
+    /// An active item is selected so the selection follows the batch.
+    pub fn set_status(&self, hash: Hash<20>, status: ItemStatus) {
+        if let Ok(mut skips) = self.skips.lock() {
+            skips.remove(&hash);
+        }
+        let Ok(mut snapshot) = self.snapshot.lock() else {
+            return;
+        };
+        let Some(index) = snapshot.items.iter().position(|item| item.hash == hash) else {
+            return;
+        };
+        if let Some(item) = snapshot.items.get_mut(index) {
+            item.status = status;
+        }
+        if status == ItemStatus::Active {
+            snapshot.selected = index;
+            snapshot.step = None;
+            snapshot.jobs.clear();
+        }
+    }
+
+    /// Set the step of the active item, removing the jobs of the previous step.
+    pub fn set_step(&self, step: &str) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.step = Some(step.to_owned());
+            snapshot.jobs.clear();
+        }
+    }
+
+    /// Update the status of a job of the current step.
+    pub fn update_job(&self, id: &str, status: &Status) {
+        let Ok(mut snapshot) = self.snapshot.lock() else {
+            return;
+        };
+        let index = if let Some(index) = snapshot.jobs.iter().position(|job| job.id == id) {
+            index
+        } else {
+            snapshot.jobs.push(TuiJob {
+                id: id.to_owned(),
+                started: false,
+                completed: false,
+            });
+            snapshot.jobs.len() - 1
+        };
+        let Some(job) = snapshot.jobs.get_mut(index) else {
+            return;
+        };
+        match status {
+            Status::Created | Status::Queued => {}
+            Status::Started => job.started = true,
+            Status::Completed => job.completed = true,
+        }
+    }
+
+    /// Move the selection to the previous item.
+    pub fn select_previous(&self) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.selected = snapshot.selected.saturating_sub(1);
+        }
+    }
+
+    /// Move the selection to the next item.
+    pub fn select_next(&self) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            if snapshot.selected + 1 < snapshot.items.len() {
+                snapshot.selected += 1;
+            }
+        }
+    }
+
+    /// Pause the batch before the next item, or resume it if already paused.
+    pub fn toggle_pause(&self) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.paused = !snapshot.paused;
+        }
+    }
+
+    /// Is the batch paused?
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.snapshot.lock().is_ok_and(|snapshot| snapshot.paused)
+    }
+
+    /// Request the selected item is skipped.
+    ///
+    /// A pending item is skipped when it's reached and the active item is skipped
+    /// after its current step.
+    pub fn request_skip(&self) {
+        let Some(item) = self.get_selected() else {
+            return;
+        };
+        if matches!(item.status, ItemStatus::Pending | ItemStatus::Active) {
+            if let Ok(mut skips) = self.skips.lock() {
+                skips.insert(item.hash);
+            }
+        }
+    }
+
+    /// Has the item been requested to be skipped?
+    #[must_use]
+    pub fn is_skip_requested(&self, hash: Hash<20>) -> bool {
+        self.skips.lock().is_ok_and(|skips| skips.contains(&hash))
+    }
+
+    /// Request the selected item is processed again if it failed or was skipped.
+    pub fn request_retry(&self) {
+        let Some(item) = self.get_selected() else {
+            return;
+        };
+        if !matches!(item.status, ItemStatus::Failed | ItemStatus::Skipped) {
+            return;
+        }
+        if let Ok(mut retries) = self.retries.lock() {
+            if !retries.contains(&item.hash) {
+                retries.push(item.hash);
+            }
+        }
+        self.set_status(item.hash, ItemStatus::Pending);
+    }
+
+    /// Take the items requested to be processed again.
+    #[must_use]
+    pub fn take_retries(&self) -> Vec<Hash<20>> {
+        self.retries
+            .lock()
+            .map(|mut retries| retries.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Wait while the batch is paused.
+    pub async fn wait_while_paused(&self, shutdown: &Shutdown) {
+        while self.is_paused() && !shutdown.is_requested() {
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Wait until an item is retried.
+    ///
+    /// Returns `None` when shutdown is requested.
+    pub async fn wait_for_retry(&self, shutdown: &Shutdown) -> Option<Vec<Hash<20>>> {
+        loop {
+            if shutdown.is_requested() {
+                return None;
+            }
+            let retries = self.take_retries();
+            if !retries.is_empty() {
+                return Some(retries);
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    fn get_selected(&self) -> Option<TuiItem> {
+        let snapshot = self.snapshot.lock().ok()?;
+        snapshot.items.get(snapshot.selected).cloned()
+    }
+}
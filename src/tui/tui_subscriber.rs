@@ -0,0 +1,23 @@
+use di::{injectable, Ref};
+
+use crate::jobs::{Status, Subscriber};
+use crate::tui::TuiState;
+
+/// A [Subscriber] that updates the jobs shown by [`Tui`].
+#[injectable]
+pub struct TuiSubscriber {
+    state: Ref<TuiState>,
+}
+
+impl Subscriber for TuiSubscriber {
+    /// Called when a new scope is started.
+    fn start(&self, _scope_id: &str) {}
+
+    /// Called when a scope is finished.
+    fn finish(&self, _scope_id: &str) {}
+
+    /// Called when the status of a job changes.
+    fn update(&self, job_id: &str, status: Status) {
+        self.state.update_job(job_id, &status);
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+/// A release from the GitHub API.
+#[derive(Clone, Debug, Deserialize)]
+pub struct GitHubRelease {
+    /// Tag of the release such as `v0.25.0`.
+    pub tag_name: String,
+    pub html_url: String,
+    pub assets: Vec<GitHubAsset>,
+}
+
+/// A file attached to a [`GitHubRelease`].
+#[derive(Clone, Debug, Deserialize)]
+pub struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl GitHubRelease {
+    /// Get the version of the release without the `v` prefix.
+    #[must_use]
+    pub fn get_version(&self) -> &str {
+        self.tag_name.strip_prefix('v').unwrap_or(&self.tag_name)
+    }
+
+    /// Get an asset by name.
+    #[must_use]
+    pub fn get_asset(&self, name: &str) -> Option<&GitHubAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
@@ -0,0 +1,7 @@
+pub use github_release::*;
+pub use self_update_command::*;
+
+pub(crate) mod github_release;
+pub(crate) mod self_update_command;
+#[cfg(test)]
+mod tests;
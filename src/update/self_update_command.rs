@@ -0,0 +1,185 @@
+use std::cmp::Ordering::{Equal, Greater, Less};
+use std::env::current_exe;
+use std::fs::{rename, set_permissions, write, Permissions};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info, trace};
+use reqwest::{Client, Response};
+use rogue_logging::Error;
+use sha2::{Digest, Sha256};
+
+use crate::built_info::{PKG_HOMEPAGE, PKG_NAME, PKG_REPOSITORY, PKG_VERSION, TARGET};
+use crate::db::Hash;
+use crate::errors::{error, io_error, path_error};
+use crate::options::SelfUpdateArgs;
+use crate::update::GitHubRelease;
+
+/// Extension of the asset with the SHA-256 checksum of a binary.
+const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// Replace the running executable with the latest release from GitHub.
+#[injectable]
+pub struct SelfUpdateCommand {
+    args: Ref<SelfUpdateArgs>,
+}
+
+impl SelfUpdateCommand {
+    /// Execute [`SelfUpdateCommand`] from the CLI.
+    ///
+    /// The binary is only replaced if its checksum matches the checksum asset of the release.
+    ///
+    /// Returns `true` if the executable is up to date.
+    pub async fn execute_cli(&self) -> Result<bool, Error> {
+        let client = Client::new();
+        let release = get_latest_release(&client).await?;
+        let latest = release.get_version();
+        if !is_newer(PKG_VERSION, latest) {
+            info!("{} the latest version: {PKG_VERSION}", "Running".bold());
+            return Ok(true);
+        }
+        info!(
+            "{} version {latest} is available: {}",
+            "Newer".bold(),
+            release.html_url
+        );
+        if self.args.check_only.expect("check_only should be set") {
+            return Ok(false);
+        }
+        let name = get_asset_name();
+        let binary = release.get_asset(&name).ok_or_else(|| {
+            error(
+                "update executable",
+                format!("Release {latest} does not have a binary for {TARGET}: {name}"),
+            )
+        })?;
+        let checksum_name = format!("{name}.{CHECKSUM_EXTENSION}");
+        let checksum = release.get_asset(&checksum_name).ok_or_else(|| {
+            error(
+                "update executable",
+                format!("Release {latest} does not have a checksum: {checksum_name}"),
+            )
+        })?;
+        let expected = download(&client, &checksum.browser_download_url).await?;
+        let expected = parse_checksum(&String::from_utf8_lossy(&expected)).ok_or_else(|| {
+            error(
+                "verify checksum",
+                format!("Checksum is not valid: {checksum_name}"),
+            )
+        })?;
+        debug!("{} {}", "Downloading".bold(), binary.browser_download_url);
+        let bytes = download(&client, &binary.browser_download_url).await?;
+        let actual = Hash::<32>::new(Sha256::digest(&bytes).into()).to_hex();
+        if actual != expected {
+            return Err(error(
+                "verify checksum",
+                format!("Expected {expected} but the download was {actual}"),
+            ));
+        }
+        trace!("{} checksum {actual}", "Verified".bold());
+        let exe = current_exe().map_err(|e| io_error(e, "get current executable"))?;
+        replace_executable(&exe, &bytes)?;
+        info!(
+            "{} {} from {PKG_VERSION} to {latest}",
+            "Updated".bold(),
+            exe.display()
+        );
+        Ok(true)
+    }
+}
+
+async fn get_latest_release(client: &Client) -> Result<GitHubRelease, Error> {
+    let action = "get latest release";
+    let url = format!(
+        "{}/releases/latest",
+        PKG_REPOSITORY.replace("https://github.com/", "https://api.github.com/repos/")
+    );
+    client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .header(
+            "User-Agent",
+            format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+        )
+        .send()
+        .await
+        .and_then(Response::error_for_status)
+        .map_err(|e| error(action, e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| error(action, e.to_string()))
+}
+
+async fn download(client: &Client, url: &str) -> Result<Vec<u8>, Error> {
+    let action = "download release asset";
+    let bytes = client
+        .get(url)
+        .header(
+            "User-Agent",
+            format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+        )
+        .send()
+        .await
+        .and_then(Response::error_for_status)
+        .map_err(|e| error(action, e.to_string()))?
+        .bytes()
+        .await
+        .map_err(|e| error(action, e.to_string()))?;
+    Ok(bytes.to_vec())
+}
+
+/// Write the new executable alongside the current one then rename it into place.
+///
+/// The rename is atomic so the executable is never partially written.
+fn replace_executable(exe: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let temp = exe.with_extension("new");
+    write(&temp, bytes).map_err(|e| path_error(e, "write new executable", &temp))?;
+    set_permissions(&temp, Permissions::from_mode(0o755))
+        .map_err(|e| path_error(e, "set permissions of new executable", &temp))?;
+    rename(&temp, exe).map_err(|e| path_error(e, "replace executable", exe))?;
+    Ok(())
+}
+
+/// Get the name of the release asset for the platform this was built for.
+#[must_use]
+pub(crate) fn get_asset_name() -> String {
+    format!("{PKG_NAME}-{TARGET}")
+}
+
+/// Is the `latest` version newer than the `current` version?
+///
+/// Versions are compared by their numeric `major.minor.patch` components. A pre-release
+/// of the same version is older than the release.
+#[must_use]
+pub(crate) fn is_newer(current: &str, latest: &str) -> bool {
+    let (current_numbers, current_pre) = parse_version(current);
+    let (latest_numbers, latest_pre) = parse_version(latest);
+    match latest_numbers.cmp(&current_numbers) {
+        Greater => true,
+        Less => false,
+        Equal => current_pre && !latest_pre,
+    }
+}
+
+/// Parse the numeric components of a version and whether it's a pre-release.
+fn parse_version(version: &str) -> (Vec<u64>, bool) {
+    let (numbers, pre) = match version.split_once(['-', '+']) {
+        Some((numbers, _)) => (numbers, version.contains('-')),
+        None => (version, false),
+    };
+    let numbers = numbers
+        .split('.')
+        .map(|x| x.parse().unwrap_or_default())
+        .collect();
+    (numbers, pre)
+}
+
+/// Parse a checksum file in the format of `sha256sum`.
+#[must_use]
+pub(crate) fn parse_checksum(content: &str) -> Option<String> {
+    let checksum = content.split_whitespace().next()?.to_lowercase();
+    let is_valid = checksum.len() == 64 && checksum.chars().all(|x| x.is_ascii_hexdigit());
+    is_valid.then_some(checksum)
+}
@@ -0,0 +1 @@
+mod self_update_command_tests;
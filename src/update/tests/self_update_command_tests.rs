@@ -0,0 +1,71 @@
+use crate::built_info::TARGET;
+use crate::update::{get_asset_name, is_newer, parse_checksum, GitHubAsset, GitHubRelease};
+
+const CHECKSUM: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+#[test]
+fn is_newer_compares_numeric_components() {
+    assert!(is_newer("0.9.0", "0.10.0"));
+    assert!(is_newer("1.2.3", "1.2.4"));
+    assert!(is_newer("1.2.3", "2.0.0"));
+    assert!(!is_newer("0.10.0", "0.9.0"));
+    assert!(!is_newer("1.2.3", "1.2.3"));
+}
+
+#[test]
+fn is_newer_prefers_release_over_pre_release() {
+    assert!(is_newer("1.0.0-beta.1", "1.0.0"));
+    assert!(!is_newer("1.0.0", "1.0.0-beta.1"));
+    assert!(is_newer("1.0.0-beta.1", "1.0.1-beta.1"));
+}
+
+#[test]
+fn parse_checksum_sha256sum_format() {
+    // Arrange
+    let content = format!("{}  caesura-{TARGET}\n", CHECKSUM.to_uppercase());
+
+    // Act
+    let checksum = parse_checksum(&content);
+
+    // Assert
+    assert_eq!(checksum.as_deref(), Some(CHECKSUM));
+}
+
+#[test]
+fn parse_checksum_invalid() {
+    assert_eq!(parse_checksum(""), None);
+    assert_eq!(parse_checksum("abc123  caesura"), None);
+    assert_eq!(parse_checksum(&CHECKSUM.replace('9', "z")), None);
+}
+
+#[test]
+fn release_get_asset_for_target() {
+    // Arrange
+    let name = get_asset_name();
+    let release = GitHubRelease {
+        tag_name: "v1.2.3".to_owned(),
+        html_url: "https://github.com/RogueOneEcho/caesura/releases/tag/v1.2.3".to_owned(),
+        assets: vec![
+            GitHubAsset {
+                name: "caesura".to_owned(),
+                browser_download_url: "https://example.com/caesura".to_owned(),
+            },
+            GitHubAsset {
+                name: name.clone(),
+                browser_download_url: format!("https://example.com/{name}"),
+            },
+        ],
+    };
+
+    // Act
+    let asset = release.get_asset(&name);
+
+    // Assert
+    assert_eq!(release.get_version(), "1.2.3");
+    assert_eq!(name, format!("caesura-{TARGET}"));
+    assert_eq!(
+        asset.map(|x| x.browser_download_url.as_str()),
+        Some(format!("https://example.com/{name}").as_str())
+    );
+    assert!(release.get_asset("caesura-unknown").is_none());
+}
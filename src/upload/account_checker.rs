@@ -0,0 +1,158 @@
+use std::fmt::{Display, Formatter};
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use rogue_logging::Error;
+
+use crate::cross_seed::CrossSeedIndexer;
+use crate::errors::error;
+use crate::indexer::{IndexUserStats, IndexerClient};
+use crate::metrics::Metrics;
+use crate::options::UploadOptions;
+use crate::upload::AccountGuard;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// A reason the account is at risk.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountIssue {
+    /// Ratio is below the minimum.
+    LowRatio { ratio: f64, min: f64 },
+    /// Buffer in GiB is below the minimum.
+    LowBuffer { buffer: f64, min: f64 },
+    /// The account has an active warning.
+    Warned,
+}
+
+impl Display for AccountIssue {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountIssue::LowRatio { ratio, min } => {
+                write!(formatter, "Ratio of {ratio:.2} is below {min:.2}")
+            }
+            AccountIssue::LowBuffer { buffer, min } => {
+                write!(formatter, "Buffer of {buffer:.2} GiB is below {min:.2} GiB")
+            }
+            AccountIssue::Warned => write!(formatter, "Account has an active warning"),
+        }
+    }
+}
+
+/// Check the statistics of the account before uploading.
+#[injectable]
+pub struct AccountChecker {
+    upload_options: Ref<UploadOptions>,
+    indexer: Ref<IndexerClient>,
+    metrics: Ref<Metrics>,
+}
+
+impl AccountChecker {
+    /// Check the account according to `account_guard`.
+    ///
+    /// Issues are logged as warnings.
+    ///
+    /// Returns an error if `account_guard` is `refuse` and the account is at risk or
+    /// couldn't be checked.
+    pub async fn execute(&self) -> Result<(), Error> {
+        self.check(&self.indexer, "the account").await
+    }
+
+    /// Check the account on another indexer according to `account_guard`.
+    ///
+    /// The same checks and thresholds are applied as for the indexer of the source.
+    pub async fn execute_for(&self, indexer: &CrossSeedIndexer) -> Result<(), Error> {
+        let client =
+            self.indexer
+                .with_indexer(&indexer.indexer, &indexer.indexer_url, &indexer.api_key);
+        let account = format!("the account on {}", indexer.indexer.to_uppercase());
+        self.check(&client, &account).await
+    }
+
+    async fn check(&self, client: &IndexerClient, account: &str) -> Result<(), Error> {
+        let guard = self
+            .upload_options
+            .account_guard
+            .expect("account_guard should be set");
+        if guard == AccountGuard::Off {
+            return Ok(());
+        }
+        let issues = match self.get_issues(client).await {
+            Ok(issues) => issues,
+            Err(error) if guard == AccountGuard::Warn => {
+                warn!("{} to check {account}: {error}", "Failed".bold());
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        if issues.is_empty() {
+            trace!("{} {account} is not at risk", "Checked".bold());
+            return Ok(());
+        }
+        for issue in &issues {
+            warn!("{issue}");
+        }
+        if guard == AccountGuard::Refuse {
+            return Err(error(
+                "check account",
+                format!("Refusing to upload as {account} is at risk. Set account_guard to warn to upload regardless"),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get_issues(&self, client: &IndexerClient) -> Result<Vec<AccountIssue>, Error> {
+        let result = client.get_index().await;
+        self.metrics.record_api("get index", &result);
+        let index = result?;
+        let result = client.get_user(index.id).await;
+        self.metrics.record_api("get user", &result);
+        let user = result?;
+        Ok(get_account_issues(
+            &index.userstats,
+            user.personal.warned,
+            self.upload_options.min_ratio,
+            self.upload_options.get_min_buffer(),
+        ))
+    }
+}
+
+/// Get the reasons the account is at risk.
+///
+/// If `min_ratio` is not set the required ratio of the account is the minimum.
+///
+/// The buffer is how much can be downloaded before the ratio falls below the required ratio.
+/// It is only checked if `min_buffer` is set.
+#[must_use]
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub fn get_account_issues(
+    stats: &IndexUserStats,
+    warned: bool,
+    min_ratio: Option<f64>,
+    min_buffer: Option<u64>,
+) -> Vec<AccountIssue> {
+    let mut issues = Vec::new();
+    let uploaded = stats.uploaded as f64;
+    let downloaded = stats.downloaded as f64;
+    let min = min_ratio.unwrap_or(stats.requiredratio);
+    if downloaded > 0.0 {
+        let ratio = uploaded / downloaded;
+        if ratio < min {
+            issues.push(AccountIssue::LowRatio { ratio, min });
+        }
+    }
+    if let Some(min) = min_buffer {
+        if stats.requiredratio > 0.0 {
+            let buffer = (uploaded / stats.requiredratio - downloaded) / GIB;
+            let min = min as f64 / GIB;
+            if buffer < min {
+                issues.push(AccountIssue::LowBuffer { buffer, min });
+            }
+        }
+    }
+    if warned {
+        issues.push(AccountIssue::Warned);
+    }
+    issues
+}
@@ -0,0 +1,15 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Action taken before uploading if the account is at risk.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountGuard {
+    /// The account is not checked.
+    #[default]
+    Off,
+    /// Log a warning and continue with the upload.
+    Warn,
+    /// Refuse to upload.
+    Refuse,
+}
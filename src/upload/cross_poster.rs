@@ -0,0 +1,312 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use colored::Colorize;
+use di::{injectable, Ref, RefMut};
+use gazelle_api::{GazelleClient, GazelleClientFactory, TorrentResponse, UploadForm};
+use log::{info, trace, warn};
+use reqwest::Client;
+use rogue_logging::Error;
+use tokio::fs::write;
+
+use crate::built_info::*;
+use crate::confirm::{ConfirmAction, Confirmation};
+use crate::cross_seed::CrossSeedIndexer;
+use crate::errors::{error, log_error, path_error};
+use crate::formats::{ExistingFormatProvider, TargetFormat, TargetFormatProvider};
+use crate::fs::PathManager;
+use crate::indexer::RateLimiter;
+use crate::metrics::Metrics;
+use crate::notify::{Notifier, NotifyEvent};
+use crate::options::{OptionsProvider, SharedOptions, UploadOptions};
+use crate::progress::{ProgressEmitter, ProgressEvent};
+use crate::source::Source;
+use crate::summary::{TimingPhase, Timings};
+use crate::torrent::TorrentFile;
+use crate::upload::{
+    AccountChecker, UploadDescription, UploadEdition, UploadFormatStatus, MUSIC_CATEGORY_ID,
+};
+
+/// Cross-post the transcodes of a source to the indexers of the profiles of `trackers`.
+#[injectable]
+pub struct CrossPoster {
+    shared_options: Ref<SharedOptions>,
+    upload_options: Ref<UploadOptions>,
+    provider: Ref<OptionsProvider>,
+    api: RefMut<GazelleClient>,
+    paths: Ref<PathManager>,
+    targets: Ref<TargetFormatProvider>,
+    description: Ref<UploadDescription>,
+    progress: Ref<ProgressEmitter>,
+    notifier: Ref<Notifier>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    timings: Ref<Timings>,
+    confirmation: Ref<Confirmation>,
+    account_checker: Ref<AccountChecker>,
+}
+
+impl CrossPoster {
+    /// Get the indexer of each profile of `trackers`.
+    ///
+    /// Profiles of the indexer the source is from are skipped.
+    pub fn get_indexers(&self) -> Result<Vec<CrossSeedIndexer>, Error> {
+        let Some(profiles) = &self.upload_options.trackers else {
+            return Ok(Vec::new());
+        };
+        let own = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        CrossSeedIndexer::from_profiles(&self.provider, profiles, &own)
+    }
+
+    /// Cross-post the transcodes of a source to each indexer.
+    ///
+    /// Returns the uploaded formats and the error of every format or indexer that
+    /// failed. An indexer that fails doesn't stop the remaining indexers.
+    pub async fn execute(
+        &self,
+        source: &Source,
+        edition: &UploadEdition,
+        indexers: &[CrossSeedIndexer],
+    ) -> (Vec<UploadFormatStatus>, Vec<Error>) {
+        let mut formats = Vec::new();
+        let mut errors = Vec::new();
+        for indexer in indexers {
+            if let Err(error) = self
+                .cross_post(source, edition, indexer, &mut formats, &mut errors)
+                .await
+            {
+                log_error(&error);
+                errors.push(error);
+            }
+        }
+        (formats, errors)
+    }
+
+    /// Upload each transcode that's missing from the group of the source on another
+    /// indexer.
+    ///
+    /// The source is found on the other indexer by the info hash of its `.torrent` file,
+    /// then a `.torrent` file with the source and tracker of the other indexer is written
+    /// for each transcode.
+    ///
+    /// The account on the other indexer is checked according to `account_guard` first.
+    ///
+    /// A format that's missing from the other indexer but exists on the indexer of the
+    /// source was never transcoded so it's skipped with a warning. The error of each
+    /// format is added to `errors` so the remaining formats are still uploaded.
+    async fn cross_post(
+        &self,
+        source: &Source,
+        edition: &UploadEdition,
+        indexer: &CrossSeedIndexer,
+        formats: &mut Vec<UploadFormatStatus>,
+        errors: &mut Vec<Error>,
+    ) -> Result<(), Error> {
+        if !self.upload_options.dry_run.expect("dry_run should be set") {
+            self.account_checker.execute_for(indexer).await?;
+        }
+        let torrent = self.read_source_torrent(source).await?;
+        let Some(other) = self.find_source(&torrent, indexer).await? else {
+            return Err(error(
+                "cross post",
+                format!("Source was not found on {}", indexer.profile),
+            ));
+        };
+        let factory = GazelleClientFactory {
+            url: indexer.indexer_url.clone(),
+            key: indexer.api_key.clone(),
+            user_agent: format!("{PKG_NAME}/{PKG_VERSION} ({PKG_HOMEPAGE})"),
+        };
+        let mut api = factory.create();
+        self.rate_limiter.wait_for(&indexer.indexer).await;
+        let start = Instant::now();
+        let result = api.get_torrent_group(other.group.id).await;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        self.metrics.record_api("get torrent group", &result);
+        let group_torrents = result?.torrents;
+        let existing = ExistingFormatProvider::get(&other.torrent, &group_torrents);
+        let upload_extra_targets = self
+            .upload_options
+            .upload_extra_targets
+            .expect("upload_extra_targets should be set");
+        let name = indexer.indexer.to_uppercase();
+        let targets = self
+            .targets
+            .get(source.format, &existing)
+            .into_iter()
+            .filter(|target| upload_extra_targets || !target.is_extra());
+        let (targets, skipped) = split_transcoded(targets, |target| {
+            self.paths.get_torrent_path(source, target, true)
+        });
+        for target in skipped {
+            warn!(
+                "{} cross post of {target} to {name} as it was not transcoded",
+                "Skipping".bold()
+            );
+            self.progress.emit(ProgressEvent::CrossPostSkipped {
+                format: target,
+                indexer: indexer.indexer.clone(),
+            });
+        }
+        for target in targets {
+            match self
+                .cross_post_target(source, edition, indexer, &mut api, &other, target)
+                .await
+            {
+                Ok(Some(format)) => formats.push(format),
+                Ok(None) => {}
+                Err(error) => {
+                    log_error(&error);
+                    errors.push(error);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Upload a transcode to another indexer.
+    ///
+    /// Returns `None` if the upload was skipped.
+    async fn cross_post_target(
+        &self,
+        source: &Source,
+        edition: &UploadEdition,
+        indexer: &CrossSeedIndexer,
+        api: &mut GazelleClient,
+        other: &TorrentResponse,
+        target: TargetFormat,
+    ) -> Result<Option<UploadFormatStatus>, Error> {
+        let name = indexer.indexer.to_uppercase();
+        let source_url = indexer.get_torrent_url(other.group.id, other.torrent.id);
+        let transcode_path = self.paths.get_torrent_path(source, target, true);
+        let path = self
+            .paths
+            .get_cross_post_torrent_path(source, target, &indexer.indexer);
+        TorrentFile::read(&transcode_path)
+            .await?
+            .with_source(Some(indexer.get_source()), indexer.announce_url.clone())
+            .write(&path)
+            .await?;
+        let form = UploadForm {
+            path,
+            category_id: MUSIC_CATEGORY_ID,
+            remaster_year: edition.year,
+            remaster_title: edition.title.clone(),
+            remaster_record_label: edition.record_label.clone(),
+            remaster_catalogue_number: edition.catalogue_number.clone(),
+            format: target.get_file_extension().to_uppercase(),
+            bitrate: target.get_bitrate().to_owned(),
+            media: edition.media.clone(),
+            release_desc: self.description.create(source, target, &source_url).await,
+            group_id: other.group.id,
+        };
+        if self.upload_options.dry_run.expect("dry_run should be set") {
+            warn!("{} upload as this is a dry run", "Skipping".bold());
+            info!(
+                "{} data of {target} for {source} to {name}:",
+                "Upload".bold()
+            );
+            info!("{}", form);
+            return Ok(None);
+        }
+        let question = format!("Upload {target} for {source} to {name}?");
+        if !self
+            .confirmation
+            .confirm(ConfirmAction::Upload, &question)?
+        {
+            warn!(
+                "{} upload of {target} to {name} as it was not confirmed",
+                "Skipping".bold()
+            );
+            return Ok(None);
+        }
+        self.rate_limiter.wait_for(&indexer.indexer).await;
+        let start = Instant::now();
+        let result = api.upload_torrent(form).await;
+        self.timings.record(TimingPhase::Api, start.elapsed());
+        self.metrics.record_api("upload torrent", &result);
+        let response = result?;
+        info!("{} {target} for {source} to {name}", "Uploaded".bold());
+        let id = response.get_torrent_id();
+        let link = indexer.get_torrent_url(response.get_group_id(), id);
+        info!("{link}");
+        self.notifier
+            .notify(
+                NotifyEvent::UploadDone,
+                format!("Uploaded {target} for {source} to {name}\n{link}"),
+            )
+            .await;
+        self.progress.emit(ProgressEvent::UploadDone {
+            format: target,
+            id,
+            url: link,
+            indexer: Some(indexer.indexer.clone()),
+        });
+        Ok(Some(UploadFormatStatus {
+            format: target,
+            id,
+            indexer: Some(indexer.indexer.clone()),
+        }))
+    }
+
+    /// Read the `.torrent` file of the source, downloading it if it's not cached.
+    async fn read_source_torrent(&self, source: &Source) -> Result<TorrentFile, Error> {
+        let path = self.paths.get_source_torrent_path(source);
+        if !path.is_file() {
+            trace!(
+                "{} torrent file as it's not cached: {}",
+                "Downloading".bold(),
+                path.display()
+            );
+            let mut api = self.api.write().expect("API should be available");
+            self.rate_limiter.wait().await;
+            let result = api.get_torrent_file_as_buffer(source.torrent.id).await;
+            self.metrics.record_api("get torrent file", &result);
+            write(&path, result?)
+                .await
+                .map_err(|e| path_error(e, "write torrent file", &path))?;
+        }
+        TorrentFile::read(&path).await
+    }
+
+    /// Find the source on another indexer by the info hash of its `.torrent` file.
+    ///
+    /// Returns `None` if the other indexer does not have the source.
+    async fn find_source(
+        &self,
+        torrent: &TorrentFile,
+        indexer: &CrossSeedIndexer,
+    ) -> Result<Option<TorrentResponse>, Error> {
+        let client = Client::new();
+        // Older torrents may have been uploaded without a source
+        for source in [Some(indexer.get_source()), None] {
+            let info_hash = torrent
+                .with_source(source, indexer.announce_url.clone())
+                .get_info_hash();
+            self.rate_limiter.wait_for(&indexer.indexer).await;
+            let result = indexer.lookup(&client, &info_hash).await;
+            self.metrics.record_api("look up torrent by hash", &result);
+            if let Some(response) = result? {
+                return Ok(Some(response));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Split the targets into those with a transcode `.torrent` file and those without.
+///
+/// A format that exists on the indexer of the source is never transcoded so it can't be
+/// cross-posted to another indexer.
+pub(crate) fn split_transcoded(
+    targets: impl IntoIterator<Item = TargetFormat>,
+    get_torrent_path: impl Fn(TargetFormat) -> PathBuf,
+) -> (Vec<TargetFormat>, Vec<TargetFormat>) {
+    targets
+        .into_iter()
+        .partition(|target| get_torrent_path(*target).is_file())
+}
@@ -0,0 +1,145 @@
+use std::sync::LazyLock;
+
+use html_escape::decode_html_entities;
+use regex::Regex;
+
+use crate::naming::layout_template::{get_placeholders, replace_placeholders_with};
+
+/// Placeholders that can be used in a `lineage_template`.
+pub const LINEAGE_PLACEHOLDERS: [&str; 4] = ["lineage", "description", "uploader", "url"];
+
+/// Template used if `lineage_template` is not set.
+pub const DEFAULT_LINEAGE_TEMPLATE: &str =
+    "[b]Lineage[/b] of the [url={url}]source[/url] uploaded by {uploader}\n[pre]{lineage}[/pre]";
+
+/// Most lines of the source description included as lineage.
+const MAX_LINEAGE_LINES: usize = 20;
+
+/// Words of a line in a source description that describe its lineage or rip.
+const LINEAGE_WORDS: [&str; 23] = [
+    "lineage",
+    "provenance",
+    "rip",
+    "ripped",
+    "ripper",
+    "ripping",
+    "eac",
+    "xld",
+    "dbpoweramp",
+    "cuetools",
+    "accuraterip",
+    "source",
+    "transfer",
+    "transferred",
+    "equipment",
+    "chain",
+    "turntable",
+    "cartridge",
+    "stylus",
+    "preamp",
+    "adc",
+    "dac",
+    "interface",
+];
+
+static BBCODE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[/?[a-zA-Z*]+(=[^\]]*)?\]").expect("regex should be valid"));
+
+/// Quote the lineage of a source in the description of its transcodes.
+///
+/// Placeholders:
+/// - `{lineage}` lines of the source description about its lineage or rip
+/// - `{description}` whole source description
+/// - `{uploader}` username of the uploader of the source
+/// - `{url}` permalink of the source
+pub struct LineageTemplate;
+
+impl LineageTemplate {
+    /// Check the template only has known placeholders.
+    ///
+    /// Returns a description of the first problem found.
+    pub fn validate(template: &str) -> Result<(), String> {
+        for placeholder in get_placeholders(template)? {
+            if !LINEAGE_PLACEHOLDERS.contains(&placeholder) {
+                return Err(format!(
+                    "Unknown placeholder `{{{placeholder}}}`. Expected one of: {}",
+                    LINEAGE_PLACEHOLDERS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the template for a source description.
+    ///
+    /// Returns `None` if the description is empty, or if the template includes
+    /// `{lineage}` and no lines about the lineage are found.
+    #[must_use]
+    pub fn get(template: &str, description: &str, uploader: &str, url: &str) -> Option<String> {
+        let description = get_plain_text(description);
+        if description.is_empty() {
+            return None;
+        }
+        let placeholders = get_placeholders(template).unwrap_or_default();
+        let lineage = if placeholders.contains(&"lineage") {
+            get_lineage(&description)?
+        } else {
+            String::new()
+        };
+        let output = replace_placeholders_with(template, |placeholder| match placeholder {
+            "lineage" => lineage.clone(),
+            "description" => description.clone(),
+            "uploader" => uploader.to_owned(),
+            "url" => url.to_owned(),
+            _ => format!("{{{placeholder}}}"),
+        });
+        Some(output)
+    }
+}
+
+/// Get the lines of a plain text description about its lineage or rip.
+///
+/// A line is included if it has one of the [`LINEAGE_WORDS`]. If such a line ends with
+/// `:` then it's a heading and the lines that follow it are included until a blank line.
+///
+/// Returns `None` if no lines are found.
+#[must_use]
+pub fn get_lineage(description: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut is_section = false;
+    for line in description.lines().map(str::trim) {
+        if line.is_empty() {
+            is_section = false;
+            continue;
+        }
+        if is_lineage_line(line) {
+            lines.push(line);
+            is_section = line.ends_with(':');
+        } else if is_section {
+            lines.push(line);
+        }
+        if lines.len() >= MAX_LINEAGE_LINES {
+            break;
+        }
+    }
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Remove the `BBCode` tags and HTML entities of a description.
+///
+/// The tags are removed rather than kept so that quoting only some of the lines can't
+/// leave a tag unclosed.
+#[must_use]
+pub fn get_plain_text(description: &str) -> String {
+    let description = decode_html_entities(description).replace("\r\n", "\n");
+    BBCODE_TAG.replace_all(&description, "").trim().to_owned()
+}
+
+fn is_lineage_line(line: &str) -> bool {
+    line.split(|c: char| !c.is_alphanumeric())
+        .any(|word| LINEAGE_WORDS.contains(&word.to_lowercase().as_str()))
+}
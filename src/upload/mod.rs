@@ -1,5 +1,23 @@
+pub use account_checker::*;
+pub use account_guard::*;
+pub use cross_poster::*;
+pub use lineage::*;
+pub use request_filler::*;
+pub use request_match::*;
 pub use upload_command::*;
+pub use upload_description::*;
+pub use upload_edition::*;
 pub use upload_status::*;
 
+pub(crate) mod account_checker;
+pub(crate) mod account_guard;
+pub(crate) mod cross_poster;
+pub(crate) mod lineage;
+pub(crate) mod request_filler;
+pub(crate) mod request_match;
+#[cfg(test)]
+mod tests;
 pub(crate) mod upload_command;
+pub(crate) mod upload_description;
+pub(crate) mod upload_edition;
 pub(crate) mod upload_status;
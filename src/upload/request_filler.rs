@@ -0,0 +1,160 @@
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, info, trace};
+use rogue_logging::Error;
+
+use crate::formats::TargetFormat;
+use crate::indexer::{IndexerClient, RequestResult};
+use crate::metrics::Metrics;
+use crate::options::UploadOptions;
+use crate::source::Source;
+use crate::upload::RequestMatch;
+
+const MIB: u64 = 1024 * 1024;
+const GIB: u64 = 1024 * MIB;
+
+/// An uploaded release to match against open requests.
+#[derive(Clone, Debug, Default)]
+pub struct RequestCandidate {
+    pub artists: Vec<String>,
+    pub title: String,
+    /// Year of the edition.
+    pub year: u16,
+    /// Year of the original release.
+    pub original_year: u16,
+    pub catalogue_number: String,
+    pub format: String,
+    pub bitrate: String,
+    pub media: String,
+}
+
+impl RequestCandidate {
+    /// Create a [`RequestCandidate`] for a transcode of a source.
+    #[must_use]
+    pub fn new(source: &Source, target: TargetFormat) -> Self {
+        let mut artists = Vec::new();
+        if let Some(info) = &source.group.music_info {
+            for artist in info.artists.iter().chain(info.dj.iter()) {
+                artists.push(artist.name.clone());
+            }
+        }
+        artists.push(source.metadata.artist.clone());
+        Self {
+            artists,
+            title: source.metadata.album.clone(),
+            year: source.metadata.year,
+            original_year: source.group.year,
+            catalogue_number: source.torrent.remaster_catalogue_number.clone(),
+            format: target.get_file_extension().to_uppercase(),
+            bitrate: target.get_bitrate().to_owned(),
+            media: source.torrent.media.clone(),
+        }
+    }
+}
+
+/// Fill open requests that exactly match an upload.
+#[injectable]
+pub struct RequestFiller {
+    upload_options: Ref<UploadOptions>,
+    indexer: Ref<IndexerClient>,
+    metrics: Ref<Metrics>,
+}
+
+impl RequestFiller {
+    /// Fill the first open request matching the uploaded torrent.
+    ///
+    /// Returns the id of the filled request, or `None` if no request matched.
+    pub async fn execute(
+        &self,
+        source: &Source,
+        target: TargetFormat,
+        torrent_id: u32,
+    ) -> Result<Option<u32>, Error> {
+        let strictness = self
+            .upload_options
+            .fill_request_match
+            .expect("fill_request_match should be set");
+        let candidate = RequestCandidate::new(source, target);
+        let result = self.indexer.search_requests(&candidate.title).await;
+        self.metrics.record_api("search requests", &result);
+        let requests = result?.results;
+        trace!(
+            "{} {} requests for {}",
+            "Found".bold(),
+            requests.len(),
+            candidate.title
+        );
+        let Some(request) = requests
+            .iter()
+            .find(|request| is_request_match(request, &candidate, strictness))
+        else {
+            debug!("{} open request matches {target} for {source}", "No".bold());
+            return Ok(None);
+        };
+        let result = self
+            .indexer
+            .fill_request(request.request_id, torrent_id)
+            .await;
+        self.metrics.record_api("fill request", &result);
+        let response = result?;
+        info!(
+            "{} request {} with {target} for {source} receiving a bounty of {}",
+            "Filled".bold(),
+            request.request_id,
+            format_bounty(response.bounty)
+        );
+        Ok(Some(request.request_id))
+    }
+}
+
+/// Check if an open request matches an uploaded release.
+#[must_use]
+pub fn is_request_match(
+    request: &RequestResult,
+    candidate: &RequestCandidate,
+    strictness: RequestMatch,
+) -> bool {
+    if request.is_filled
+        || normalize(&request.title) != normalize(&candidate.title)
+        || !request.allows_format(&candidate.format)
+        || !request.allows_bitrate(&candidate.bitrate)
+        || !request.allows_media(&candidate.media)
+    {
+        return false;
+    }
+    let artists: Vec<String> = candidate.artists.iter().map(|x| normalize(x)).collect();
+    if !request
+        .get_artist_names()
+        .iter()
+        .any(|name| artists.contains(&normalize(name)))
+    {
+        return false;
+    }
+    if strictness == RequestMatch::Loose {
+        return true;
+    }
+    let catalogue_number = normalize(&request.catalogue_number);
+    (request.year == candidate.year || request.year == candidate.original_year)
+        && (catalogue_number.is_empty()
+            || catalogue_number == normalize(&candidate.catalogue_number))
+}
+
+/// Normalize a name so it can be compared regardless of case and punctuation.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|x| x.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Format a bounty in bytes as MiB or GiB.
+#[allow(clippy::integer_division)]
+fn format_bounty(bytes: u64) -> String {
+    if bytes >= GIB {
+        let tenths = bytes * 10 / GIB;
+        format!("{}.{} GiB", tenths / 10, tenths % 10)
+    } else {
+        format!("{} MiB", bytes / MIB)
+    }
+}
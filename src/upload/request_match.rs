@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Strictness of matching an upload to an open request.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestMatch {
+    /// Artist, title, year, catalogue number, format, bitrate and media must all match.
+    #[default]
+    Strict,
+    /// Artist, title, format, bitrate and media must match.
+    Loose,
+}
@@ -0,0 +1,102 @@
+use rogue_logging::Error;
+
+use crate::indexer::{parse_response, IndexResponse, IndexUserStats};
+use crate::upload::{get_account_issues, AccountIssue};
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+const INDEX_JSON: &str = r#"{
+  "status": "success",
+  "response": {
+    "username": "example",
+    "id": 42,
+    "authkey": "abc",
+    "passkey": "def",
+    "userstats": {
+      "uploaded": 32212254720,
+      "downloaded": 21474836480,
+      "ratio": 1.5,
+      "requiredratio": 0.6,
+      "class": "Member"
+    }
+  }
+}"#;
+
+#[test]
+fn get_account_issues_none() -> Result<(), Error> {
+    // Arrange
+    let index: IndexResponse = parse_response("get index", 200, INDEX_JSON)?;
+
+    // Act
+    let issues = get_account_issues(&index.userstats, false, None, Some(10 * GIB));
+
+    // Assert
+    assert_eq!(index.id, 42);
+    assert!(issues.is_empty());
+    Ok(())
+}
+
+#[test]
+fn get_account_issues_below_thresholds() {
+    // Arrange
+    let stats = IndexUserStats {
+        uploaded: 10 * GIB,
+        downloaded: 20 * GIB,
+        requiredratio: 0.6,
+    };
+
+    // Act
+    let issues = get_account_issues(&stats, true, Some(1.0), Some(GIB));
+
+    // Assert
+    let buffer = (10.0 / 0.6) - 20.0;
+    assert_eq!(
+        issues,
+        vec![
+            AccountIssue::LowRatio {
+                ratio: 0.5,
+                min: 1.0
+            },
+            AccountIssue::LowBuffer { buffer, min: 1.0 },
+            AccountIssue::Warned,
+        ]
+    );
+}
+
+#[test]
+fn get_account_issues_defaults_to_required_ratio() {
+    // Arrange
+    let stats = IndexUserStats {
+        uploaded: 5 * GIB,
+        downloaded: 10 * GIB,
+        requiredratio: 0.6,
+    };
+
+    // Act
+    let issues = get_account_issues(&stats, false, None, None);
+
+    // Assert
+    assert_eq!(
+        issues,
+        vec![AccountIssue::LowRatio {
+            ratio: 0.5,
+            min: 0.6
+        }]
+    );
+}
+
+#[test]
+fn get_account_issues_nothing_downloaded() {
+    // Arrange
+    let stats = IndexUserStats {
+        uploaded: 0,
+        downloaded: 0,
+        requiredratio: 0.0,
+    };
+
+    // Act
+    let issues = get_account_issues(&stats, false, Some(1.0), Some(GIB));
+
+    // Assert
+    assert!(issues.is_empty());
+}
@@ -0,0 +1,43 @@
+use std::fs::write;
+
+use crate::formats::TargetFormat;
+use crate::hosting::HostBuilder;
+use crate::options::UploadOptions;
+use crate::testing::options::TestOptionsFactory;
+use crate::testing::TempDirectory;
+use crate::upload::{split_transcoded, CrossPoster};
+
+#[test]
+fn split_transcoded_skips_formats_of_source_indexer() {
+    // Arrange
+    // The indexer of the source has V0 so only 320 was transcoded, while the other
+    // indexer is missing both.
+    let dir = TempDirectory::create("split_transcoded_skips_formats_of_source_indexer");
+    write(dir.join("320.torrent"), b"torrent").expect("should write torrent");
+    let targets = [TargetFormat::_320, TargetFormat::V0];
+
+    // Act
+    let (transcoded, skipped) =
+        split_transcoded(targets, |target| dir.join(format!("{target}.torrent")));
+
+    // Assert
+    assert_eq!(transcoded, vec![TargetFormat::_320]);
+    assert_eq!(skipped, vec![TargetFormat::V0]);
+}
+
+#[tokio::test]
+async fn cross_poster_get_indexers_without_trackers() {
+    // Arrange
+    let upload_options = TestOptionsFactory::from(UploadOptions {
+        trackers: None,
+        ..UploadOptions::default()
+    });
+    let host = HostBuilder::new().with_options(upload_options).build();
+    let cross_poster = host.services.get_required::<CrossPoster>();
+
+    // Act
+    let indexers = cross_poster.get_indexers().expect("should get indexers");
+
+    // Assert
+    assert!(indexers.is_empty());
+}
@@ -0,0 +1,65 @@
+use crate::upload::{get_lineage, get_plain_text, LineageTemplate, DEFAULT_LINEAGE_TEMPLATE};
+
+const DESCRIPTION: &str = "[b]Lineage:[/b]\r\nVinyl &gt; Technics SL-1200\r\nAudio-Technica AT-OC9\r\n\r\nThanks to everyone who helped!\r\n[size=1]Ripped with [url=https://example.com]EAC[/url][/size]";
+
+#[test]
+fn get_lineage_includes_headed_section_and_rip_lines() {
+    // Arrange
+    let description = get_plain_text(DESCRIPTION);
+
+    // Act
+    let lineage = get_lineage(&description);
+
+    // Assert
+    assert_eq!(
+        lineage,
+        Some(
+            "Lineage:\nVinyl > Technics SL-1200\nAudio-Technica AT-OC9\nRipped with EAC".to_owned()
+        )
+    );
+}
+
+#[test]
+fn get_lineage_returns_none_without_lineage() {
+    // Arrange
+    let description = "Enjoy!\nThanks to everyone who helped.";
+
+    // Act
+    let lineage = get_lineage(description);
+
+    // Assert
+    assert_eq!(lineage, None);
+}
+
+#[test]
+fn lineage_template_get() {
+    // Arrange
+    let url = "https://redacted.sh/torrents.php?id=1&torrentid=2";
+
+    // Act
+    let line = LineageTemplate::get(DEFAULT_LINEAGE_TEMPLATE, DESCRIPTION, "uploader", url);
+    let empty = LineageTemplate::get(DEFAULT_LINEAGE_TEMPLATE, "[b][/b]", "uploader", url);
+
+    // Assert
+    let line = line.expect("should resolve template");
+    assert!(line.starts_with(&format!(
+        "[b]Lineage[/b] of the [url={url}]source[/url] uploaded by uploader\n[pre]Lineage:"
+    )));
+    assert!(line.ends_with("Ripped with EAC[/pre]"));
+    assert_eq!(empty, None);
+}
+
+#[test]
+fn lineage_template_validate() {
+    // Arrange
+    let valid = "{uploader}: {description}";
+    let invalid = "{lineage} {artist}";
+
+    // Act
+    let valid = LineageTemplate::validate(valid);
+    let invalid = LineageTemplate::validate(invalid);
+
+    // Assert
+    assert!(valid.is_ok());
+    assert!(invalid.is_err());
+}
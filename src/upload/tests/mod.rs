@@ -0,0 +1,5 @@
+mod account_checker_tests;
+mod cross_post_tests;
+mod lineage_tests;
+mod request_filler_tests;
+mod upload_edition_tests;
@@ -0,0 +1,131 @@
+use rogue_logging::Error;
+
+use crate::indexer::{parse_response, RequestResult, RequestSearchResponse};
+use crate::upload::{is_request_match, RequestCandidate, RequestMatch};
+
+const REQUESTS_JSON: &str = r#"{
+  "status": "success",
+  "response": {
+    "currentPage": 1,
+    "pages": 1,
+    "results": [
+      {
+        "requestId": 101,
+        "title": "Fear Not",
+        "year": 2018,
+        "artists": [[{ "id": 7, "name": "Logistics" }]],
+        "bitrateList": "320|V0 (VBR)",
+        "formatList": "MP3",
+        "mediaList": "Any",
+        "bounty": 1610612736,
+        "isFilled": false,
+        "catalogueNumber": "HOSP123"
+      },
+      {
+        "requestId": 102,
+        "title": "Fear Not",
+        "year": 2018,
+        "artists": [[{ "id": 7, "name": "Logistics" }]],
+        "bitrateList": ["Lossless"],
+        "formatList": ["FLAC"],
+        "mediaList": ["CD"],
+        "bounty": 104857600,
+        "isFilled": false,
+        "catalogueNumber": ""
+      }
+    ]
+  }
+}"#;
+
+fn get_requests() -> Result<Vec<RequestResult>, Error> {
+    let response: RequestSearchResponse = parse_response("search requests", 200, REQUESTS_JSON)?;
+    Ok(response.results)
+}
+
+fn get_candidate() -> RequestCandidate {
+    RequestCandidate {
+        artists: vec!["Logistics".to_owned()],
+        title: "Fear Not".to_owned(),
+        year: 2018,
+        original_year: 2018,
+        catalogue_number: "HOSP 123".to_owned(),
+        format: "MP3".to_owned(),
+        bitrate: "V0 (VBR)".to_owned(),
+        media: "WEB".to_owned(),
+    }
+}
+
+#[test]
+fn request_result_lists_and_artists() -> Result<(), Error> {
+    // Act
+    let requests = get_requests()?;
+
+    // Assert
+    let request = requests.first().expect("should have a request");
+    assert_eq!(request.get_artist_names(), vec!["Logistics".to_owned()]);
+    assert!(request.allows_format("MP3"));
+    assert!(!request.allows_format("FLAC"));
+    assert!(request.allows_bitrate("320"));
+    assert!(!request.allows_bitrate("Lossless"));
+    assert!(request.allows_media("Vinyl"));
+    let request = requests.get(1).expect("should have a second request");
+    assert!(request.allows_media("CD"));
+    assert!(!request.allows_media("WEB"));
+    Ok(())
+}
+
+#[test]
+fn is_request_match_strict() -> Result<(), Error> {
+    // Arrange
+    let requests = get_requests()?;
+    let mp3 = requests.first().expect("should have a request");
+    let flac = requests.get(1).expect("should have a second request");
+    let candidate = get_candidate();
+
+    // Act
+    // Assert
+    assert!(is_request_match(mp3, &candidate, RequestMatch::Strict));
+    assert!(!is_request_match(flac, &candidate, RequestMatch::Strict));
+    let other_year = RequestCandidate {
+        year: 2020,
+        original_year: 2019,
+        ..get_candidate()
+    };
+    assert!(!is_request_match(mp3, &other_year, RequestMatch::Strict));
+    let other_catalogue = RequestCandidate {
+        catalogue_number: "HOSP456".to_owned(),
+        ..get_candidate()
+    };
+    assert!(!is_request_match(
+        mp3,
+        &other_catalogue,
+        RequestMatch::Strict
+    ));
+    Ok(())
+}
+
+#[test]
+fn is_request_match_loose() -> Result<(), Error> {
+    // Arrange
+    let requests = get_requests()?;
+    let mp3 = requests.first().expect("should have a request");
+    let candidate = RequestCandidate {
+        year: 2020,
+        original_year: 2019,
+        catalogue_number: String::new(),
+        ..get_candidate()
+    };
+    let other_artist = RequestCandidate {
+        artists: vec!["London Elektricity".to_owned()],
+        ..get_candidate()
+    };
+    let mut filled = mp3.clone();
+    filled.is_filled = true;
+
+    // Act
+    // Assert
+    assert!(is_request_match(mp3, &candidate, RequestMatch::Loose));
+    assert!(!is_request_match(mp3, &other_artist, RequestMatch::Loose));
+    assert!(!is_request_match(&filled, &candidate, RequestMatch::Loose));
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use gazelle_api::{Group, Torrent};
+
+use crate::options::UploadOptions;
+use crate::upload::{get_media, UploadEdition};
+
+fn get_torrent() -> Torrent {
+    Torrent {
+        media: "Web".to_owned(),
+        remastered: true,
+        remaster_year: Some(2018),
+        remaster_title: " Deluxe Edition ".to_owned(),
+        remaster_record_label: "Hospital Records".to_owned(),
+        remaster_catalogue_number: "NHS123".to_owned(),
+        ..Torrent::default()
+    }
+}
+
+fn get_group() -> Group {
+    Group {
+        year: 2008,
+        ..Group::default()
+    }
+}
+
+#[test]
+fn upload_edition_from_torrent() {
+    // Arrange
+    let torrent = get_torrent();
+
+    // Act
+    let edition = UploadEdition::new(&torrent, &get_group(), &UploadOptions::default());
+
+    // Assert
+    assert_eq!(
+        edition,
+        Ok(UploadEdition {
+            year: 2018,
+            title: "Deluxe Edition".to_owned(),
+            record_label: "Hospital Records".to_owned(),
+            catalogue_number: "NHS123".to_owned(),
+            media: "WEB".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn upload_edition_original_release_uses_group_year() {
+    // Arrange
+    let torrent = Torrent {
+        remastered: false,
+        remaster_year: None,
+        ..get_torrent()
+    };
+
+    // Act
+    let edition = UploadEdition::new(&torrent, &get_group(), &UploadOptions::default())
+        .expect("edition should be determined");
+
+    // Assert
+    assert_eq!(edition.year, 2008);
+}
+
+#[test]
+fn upload_edition_unconfirmed_requires_year() {
+    // Arrange
+    let torrent = Torrent {
+        remaster_year: Some(0),
+        ..get_torrent()
+    };
+    let options = UploadOptions {
+        edition_year: Some(2010),
+        ..UploadOptions::default()
+    };
+
+    // Act
+    let without_year = UploadEdition::new(&torrent, &get_group(), &UploadOptions::default());
+    let with_year =
+        UploadEdition::new(&torrent, &get_group(), &options).expect("edition should be determined");
+
+    // Assert
+    assert!(without_year.is_err());
+    assert_eq!(with_year.year, 2010);
+}
+
+#[test]
+fn upload_edition_overrides_and_substitutions() {
+    // Arrange
+    let options = UploadOptions {
+        edition_title: Some(String::new()),
+        edition_substitution: Some(vec![
+            "^Hospital Records$=>Hospital".to_owned(),
+            "^NHS=>NHS-".to_owned(),
+        ]),
+        ..UploadOptions::default()
+    };
+
+    // Act
+    let edition = UploadEdition::new(&get_torrent(), &get_group(), &options)
+        .expect("edition should be determined");
+
+    // Assert
+    assert_eq!(edition.title, String::new());
+    assert_eq!(edition.record_label, "Hospital".to_owned());
+    assert_eq!(edition.catalogue_number, "NHS-123".to_owned());
+}
+
+#[test]
+fn get_media_normalizes() {
+    // Act
+    let media = ["web", "Blu-ray", "BLURAY", "vinyl", "Reel"].map(get_media);
+
+    // Assert
+    assert_eq!(
+        media,
+        [
+            Some("WEB"),
+            Some("Blu-Ray"),
+            Some("Blu-Ray"),
+            Some("Vinyl"),
+            None
+        ]
+    );
+}
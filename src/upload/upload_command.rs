@@ -1,27 +1,38 @@
 use std::ops::Not;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::time::Instant;
 
 use colored::Colorize;
 use di::{injectable, Ref, RefMut};
-use log::{info, trace, warn};
-use tokio::fs::{copy, hard_link};
+use log::{debug, info, trace, warn};
 
-use crate::built_info::*;
-use crate::errors::{error, io_error};
-use crate::eyed3::EyeD3Command;
+use crate::confirm::{ConfirmAction, Confirmation};
+use crate::errors::{error, log_error};
 use crate::formats::{TargetFormat, TargetFormatProvider};
-use crate::fs::{copy_dir, Collector, PathManager};
-use crate::imdl::ImdlCommand;
-use crate::jobs::Job;
-use crate::options::{CopyOptions, Options, SharedOptions, SourceArg, UploadOptions};
+use crate::fs::{copy_dir, PathManager};
+use crate::history::History;
+use crate::hooks::{HookEvent, HookRunner};
+use crate::indexer::RateLimiter;
+use crate::metrics::Metrics;
+use crate::notify::{Notifier, NotifyEvent};
+use crate::options::{
+    CopyOptions, EnrichOptions, HookOptions, NotifyOptions, OptionRule, Options, PermissionOptions,
+    SharedOptions, SourceArg, UploadOptions,
+};
+use crate::progress::{ProgressEmitter, ProgressEvent};
 use crate::queue::TimeStamp;
-use crate::source::{get_permalink, Source, SourceProvider};
-use crate::transcode::{TranscodeJobFactory, Variant};
-use crate::upload::{UploadFormatStatus, UploadStatus};
+use crate::source::{get_permalink, Source, SourceLocker, SourceProvider};
+use crate::summary::{TimingPhase, Timings};
+use crate::torrent::TorrentVerifier;
+use crate::upload::{
+    AccountChecker, CrossPoster, RequestFiller, UploadDescription, UploadEdition,
+    UploadFormatStatus, UploadStatus,
+};
 use gazelle_api::{GazelleClient, UploadForm};
 use rogue_logging::Error;
 
-const MUSIC_CATEGORY_ID: u8 = 0;
+/// Id of the music category of the upload form.
+pub(crate) const MUSIC_CATEGORY_ID: u8 = 0;
 
 /// Upload transcodes of a FLAC source.
 #[injectable]
@@ -30,11 +41,27 @@ pub struct UploadCommand {
     shared_options: Ref<SharedOptions>,
     upload_options: Ref<UploadOptions>,
     copy_options: Ref<CopyOptions>,
+    notify_options: Ref<NotifyOptions>,
+    hook_options: Ref<HookOptions>,
+    enrich_options: Ref<EnrichOptions>,
+    permission_options: Ref<PermissionOptions>,
     source_provider: RefMut<SourceProvider>,
+    locker: Ref<SourceLocker>,
     api: RefMut<GazelleClient>,
     paths: Ref<PathManager>,
     targets: Ref<TargetFormatProvider>,
-    transcode_job_factory: Ref<TranscodeJobFactory>,
+    progress: Ref<ProgressEmitter>,
+    notifier: Ref<Notifier>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    hooks: Ref<HookRunner>,
+    history: Ref<History>,
+    request_filler: Ref<RequestFiller>,
+    timings: Ref<Timings>,
+    confirmation: Ref<Confirmation>,
+    account_checker: Ref<AccountChecker>,
+    description: Ref<UploadDescription>,
+    cross_poster: Ref<CrossPoster>,
 }
 
 impl UploadCommand {
@@ -44,19 +71,25 @@ impl UploadCommand {
     ///
     /// Returns `true` if all the uploads succeed.
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.arg.validate()
-            || !self.shared_options.validate()
-            || !self.upload_options.validate()
-        {
-            return Ok(false);
-        }
+        let errors = [
+            self.arg.get_errors(),
+            self.shared_options.get_errors(),
+            self.upload_options.get_errors(),
+            self.notify_options.get_errors(),
+            self.hook_options.get_errors(),
+            self.enrich_options.get_errors(),
+            self.permission_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
         let source = self
             .source_provider
             .write()
             .expect("Source provider should be writeable")
             .get_from_options()
             .await
-            .map_err(|e| error("get source from options", e.to_string()))?;
+            .map_err(|e| e.to_error("get source from options"))?;
+        let _lock = self.locker.acquire_or_error(&source)?;
         let status = self.execute(&source).await;
         // Errors were already printed as they occurred
         Ok(status.success)
@@ -80,7 +113,52 @@ impl UploadCommand {
         };
         let mut errors = Vec::new();
         let mut formats = Vec::new();
+        if !self.upload_options.dry_run.expect("dry_run should be set") {
+            if let Err(error) = self.account_checker.execute().await {
+                log_error(&error);
+                status.success = false;
+                status.errors = Some(vec![error]);
+                return status;
+            }
+        }
+        let edition = match UploadEdition::new(&source.torrent, &source.group, &self.upload_options)
+        {
+            Ok(edition) => edition,
+            Err(details) => {
+                let error = error("get edition", details);
+                log_error(&error);
+                status.success = false;
+                status.errors = Some(vec![error]);
+                return status;
+            }
+        };
+        let indexers = match self.cross_poster.get_indexers() {
+            Ok(indexers) => indexers,
+            Err(error) => {
+                log_error(&error);
+                status.success = false;
+                status.errors = Some(vec![error]);
+                return status;
+            }
+        };
+        let upload_extra_targets = self
+            .upload_options
+            .upload_extra_targets
+            .expect("upload_extra_targets should be set");
+        let base = self
+            .shared_options
+            .indexer_url
+            .clone()
+            .expect("indexer_url should be set");
+        let source_url = get_permalink(&base, source.group.id, source.torrent.id);
         for target in targets {
+            if target.is_extra() && !upload_extra_targets {
+                debug!(
+                    "{} {target} as upload_extra_targets is not set",
+                    "Skipped".bold()
+                );
+                continue;
+            }
             let torrent_path = self.paths.get_torrent_path(source, target, true);
             if !torrent_path.exists() {
                 warn!("In v0.19.0 the torrent file name format changed.");
@@ -92,17 +170,20 @@ impl UploadCommand {
                         torrent_path.display()
                     ),
                 );
-                error.log();
+                log_error(&error);
                 errors.push(error);
                 status.success = false;
                 continue;
             }
             let target_dir = self.paths.get_transcode_target_dir(source, target);
             trace!("{} content of {}", "Verifying".bold(), target_dir.display());
-            if let Err(e) = ImdlCommand::verify(&torrent_path, &target_dir).await {
-                let error = error("verify torrent content", e.to_string());
-                error.log();
-                error.log();
+            let issue = match TorrentVerifier::verify(&torrent_path, &target_dir).await {
+                Ok(issue) => issue.map(|issue| issue.to_string()),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(details) = issue {
+                let error = error("verify torrent content", details);
+                log_error(&error);
                 errors.push(error);
                 status.success = false;
                 continue;
@@ -133,14 +214,14 @@ impl UploadCommand {
             let form = UploadForm {
                 path: torrent_path,
                 category_id: MUSIC_CATEGORY_ID,
-                remaster_year: source.metadata.year,
-                remaster_title: source.torrent.remaster_title.clone(),
-                remaster_record_label: source.torrent.remaster_record_label.clone(),
-                remaster_catalogue_number: source.torrent.remaster_catalogue_number.clone(),
+                remaster_year: edition.year,
+                remaster_title: edition.title.clone(),
+                remaster_record_label: edition.record_label.clone(),
+                remaster_catalogue_number: edition.catalogue_number.clone(),
                 format: target.get_file_extension().to_uppercase(),
                 bitrate: target.get_bitrate().to_owned(),
-                media: source.torrent.media.clone(),
-                release_desc: self.create_description(source, target).await,
+                media: edition.media.clone(),
+                release_desc: self.description.create(source, target, &source_url).await,
                 group_id: source.group.id,
             };
             if self.upload_options.dry_run.expect("dry_run should be set") {
@@ -149,29 +230,94 @@ impl UploadCommand {
                 info!("{}", form);
                 continue;
             }
-            match api.upload_torrent(form).await {
+            let question = format!(
+                "Upload {target} for {source} to {}?",
+                self.shared_options
+                    .indexer
+                    .clone()
+                    .expect("indexer should be set")
+                    .to_uppercase()
+            );
+            match self.confirmation.confirm(ConfirmAction::Upload, &question) {
+                Ok(true) => {}
+                Ok(false) => {
+                    warn!(
+                        "{} upload of {target} as it was not confirmed",
+                        "Skipping".bold()
+                    );
+                    continue;
+                }
+                Err(error) => {
+                    log_error(&error);
+                    errors.push(error);
+                    status.success = false;
+                    continue;
+                }
+            }
+            self.rate_limiter.wait().await;
+            let start = Instant::now();
+            let result = api.upload_torrent(form).await;
+            self.timings.record(TimingPhase::Api, start.elapsed());
+            self.metrics.record_api("upload torrent", &result);
+            match result {
                 Ok(response) => {
                     info!("{} {target} for {source}", "Uploaded".bold());
-                    let base = &self
-                        .shared_options
-                        .indexer_url
-                        .clone()
-                        .expect("indexer_url should be set");
                     let id = response.get_torrent_id();
-                    let link = get_permalink(base, response.get_group_id(), id);
+                    let link = get_permalink(&base, response.get_group_id(), id);
                     info!("{link}");
-                    formats.push(UploadFormatStatus { format: target, id });
+                    self.notifier
+                        .notify(
+                            NotifyEvent::UploadDone,
+                            format!("Uploaded {target} for {source}\n{link}"),
+                        )
+                        .await;
+                    let vars = vec![
+                        ("CAESURA_FORMAT", target.to_string()),
+                        ("CAESURA_UPLOAD_ID", id.to_string()),
+                        ("CAESURA_UPLOAD_URL", link.clone()),
+                    ];
+                    self.hooks.run(HookEvent::Uploaded, source, vars).await;
+                    self.progress.emit(ProgressEvent::UploadDone {
+                        format: target,
+                        id,
+                        url: link,
+                        indexer: None,
+                    });
+                    formats.push(UploadFormatStatus {
+                        format: target,
+                        id,
+                        indexer: None,
+                    });
+                    if self
+                        .upload_options
+                        .fill_requests
+                        .expect("fill_requests should be set")
+                    {
+                        if let Err(error) = self.request_filler.execute(source, target, id).await {
+                            // If fill_request fails the upload has still succeeded
+                            warn!("{error}");
+                            errors.push(error);
+                        }
+                    }
                 }
                 Err(error) => {
-                    error.log();
+                    log_error(&error);
                     errors.push(error);
                     status.success = false;
-                    continue;
                 }
             }
         }
+        drop(api);
+        let (cross_posted, cross_post_errors) =
+            self.cross_poster.execute(source, &edition, &indexers).await;
+        if !cross_post_errors.is_empty() {
+            status.success = false;
+        }
+        formats.extend(cross_posted);
+        errors.extend(cross_post_errors);
         status.errors = errors.is_empty().not().then_some(errors);
         status.formats = formats.is_empty().not().then_some(formats);
+        self.history.record_upload(source, &status).await;
         status
     }
 
@@ -196,20 +342,17 @@ impl UploadCommand {
             );
             return Ok(());
         }
-        let verb = if self
+        let strategy = self
             .copy_options
-            .hard_link
-            .expect("hard_link should be set")
-        {
-            copy_dir(&source_dir, &target_dir, true).await?;
-            "Hard Linked"
-        } else {
-            copy_dir(&source_dir, &target_dir, false).await?;
-            "Copied"
-        };
+            .link_content
+            .expect("link_content should be set");
+        copy_dir(&source_dir, &target_dir, strategy).await?;
+        self.permission_options
+            .get_permissions()
+            .apply(&target_dir)?;
         trace!(
             "{} {} to {}",
-            verb.bold(),
+            strategy.get_verb().bold(),
             source_dir.display(),
             target_dir.display()
         );
@@ -227,116 +370,21 @@ impl UploadCommand {
             .file_name()
             .expect("torrent path should have a name");
         let target_path = target_dir.join(source_file_name);
-        let verb = if self
+        let strategy = self
             .copy_options
-            .hard_link
-            .expect("hard_link should be set")
-        {
-            hard_link(&source_path, &target_path)
-                .await
-                .map_err(|e| io_error(e, "hard link torrent file"))?;
-            "Hard Linked"
-        } else {
-            copy(&source_path, &target_path)
-                .await
-                .map_err(|e| io_error(e, "copy torrent file"))?;
-            "Copied"
-        };
+            .link_torrent
+            .expect("link_torrent should be set")
+            .execute(&source_path, &target_path)
+            .await?;
+        self.permission_options
+            .get_permissions()
+            .apply(&target_path)?;
         trace!(
             "{} {} to {}",
-            verb.bold(),
+            strategy.get_verb().bold(),
             source_path.display(),
             target_path.display()
         );
         Ok(())
     }
-
-    #[allow(clippy::uninlined_format_args)]
-    async fn create_description(&self, source: &Source, target: TargetFormat) -> String {
-        let base = &self
-            .shared_options
-            .indexer_url
-            .clone()
-            .expect("indexer_url should be set");
-        let source_url = get_permalink(base, source.group.id, source.torrent.id);
-        let source_title = source.format.get_title();
-        let mut lines: Vec<String> = vec![
-            format!(
-                "Transcoded and uploaded with [url={}][b]{}[/b] v{}[/url]",
-                PKG_REPOSITORY, PKG_NAME, PKG_VERSION
-            ),
-            format!("[pad=0|10|0|20]Source[/pad] [url={source_url}]{source_title}[/url]"),
-        ];
-        match self.get_command(source, target) {
-            Ok(transcode_command) => lines.push(format!(
-                "[pad=0|10|0|0]Transcode[/pad] [code]{transcode_command}[/code]"
-            )),
-            Err(error) => warn!("Failed to get transcode command: {error}"),
-        }
-        if matches!(target, TargetFormat::_320 | TargetFormat::V0) {
-            match self.get_details(source, target).await {
-                Ok(details) => {
-                    lines.push(format!(
-                        "[pad=0|10|0|19]Details[/pad] [hide][pre]{details}[/pre][/hide]"
-                    ));
-                }
-                Err(error) => warn!("Failed to get transcode details: {error}"),
-            }
-        }
-        lines.push(format!(
-            "[url={}]Learn how easy it is to create and upload transcodes yourself![/url]",
-            PKG_REPOSITORY
-        ));
-        lines.into_iter().fold(String::new(), |mut output, line| {
-            output.push_str("[quote]");
-            output.push_str(&line);
-            output.push_str("[/quote]");
-            output
-        })
-    }
-
-    pub fn get_command(&self, source: &Source, target: TargetFormat) -> Result<String, Error> {
-        let flacs = Collector::get_flacs(&source.directory);
-        let flac = flacs.first().expect("Should be at least one FLAC");
-        let job = self
-            .transcode_job_factory
-            .create_single(0, flac, source, target)?;
-        let Job::Transcode(job) = job else {
-            return Err(error(
-                "get transcode command",
-                "expected a transcode job".to_owned(),
-            ));
-        };
-        let command = match job.variant {
-            Variant::Transcode(mut decode, mut encode) => {
-                decode.input = PathBuf::from("input.flac");
-                let extension = encode
-                    .output
-                    .extension()
-                    .expect("output should have an extension")
-                    .to_string_lossy();
-                encode.output = PathBuf::from(format!("output.{extension}"));
-                format!(
-                    "{} | {}",
-                    decode.to_info().display(),
-                    encode.to_info().display()
-                )
-            }
-            Variant::Resample(mut resample) => {
-                resample.input = PathBuf::from("input.flac");
-                let extension = resample
-                    .output
-                    .extension()
-                    .expect("output should have an extension")
-                    .to_string_lossy();
-                resample.output = PathBuf::from(format!("output.{extension}"));
-                resample.to_info().display()
-            }
-        };
-        Ok(command)
-    }
-    async fn get_details(&self, source: &Source, target: TargetFormat) -> Result<String, Error> {
-        let path = self.paths.get_transcode_target_dir(source, target);
-        EyeD3Command::display(&path).await
-    }
 }
@@ -0,0 +1,138 @@
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{debug, warn};
+use rogue_logging::Error;
+
+use crate::built_info::*;
+use crate::errors::error;
+use crate::eyed3::EyeD3Command;
+use crate::formats::TargetFormat;
+use crate::fs::{Collector, PathManager};
+use crate::jobs::Job;
+use crate::musicbrainz::MusicBrainzProvider;
+use crate::options::UploadOptions;
+use crate::source::Source;
+use crate::transcode::{ClipChecker, TranscodeJobFactory};
+use crate::upload::LineageTemplate;
+
+/// Create the description of an upload.
+///
+/// The description is the same for every indexer except for the link to the source.
+#[injectable]
+pub struct UploadDescription {
+    upload_options: Ref<UploadOptions>,
+    paths: Ref<PathManager>,
+    transcode_job_factory: Ref<TranscodeJobFactory>,
+    clip_checker: Ref<ClipChecker>,
+    musicbrainz: Ref<MusicBrainzProvider>,
+}
+
+impl UploadDescription {
+    /// Create the description of an upload of a transcode.
+    ///
+    /// `source_url` is the permalink of the source on the indexer being uploaded to.
+    #[allow(clippy::uninlined_format_args)]
+    pub async fn create(&self, source: &Source, target: TargetFormat, source_url: &str) -> String {
+        let source_title = source.format.get_title();
+        let mut lines: Vec<String> = vec![
+            format!(
+                "Transcoded and uploaded with [url={}][b]{}[/b] v{}[/url]",
+                PKG_REPOSITORY, PKG_NAME, PKG_VERSION
+            ),
+            format!("[pad=0|10|0|20]Source[/pad] [url={source_url}]{source_title}[/url]"),
+        ];
+        if let Some(line) = self.get_lineage_line(source, source_url) {
+            lines.push(line);
+        }
+        match self.get_command(source, target) {
+            Ok(transcode_command) => lines.push(format!(
+                "[pad=0|10|0|0]Transcode[/pad] [code]{transcode_command}[/code]"
+            )),
+            Err(error) => warn!("Failed to get transcode command: {error}"),
+        }
+        if target.is_mp3() {
+            if let Some(line) = self.get_gain_line(source) {
+                lines.push(line);
+            }
+        }
+        if let Some(release) = self.musicbrainz.get(source).await {
+            lines.push(format!(
+                "[pad=0|10|0|19]Release[/pad] {}",
+                release.get_description()
+            ));
+        }
+        if target.is_mp3() {
+            match self.get_details(source, target).await {
+                Ok(details) => {
+                    lines.push(format!(
+                        "[pad=0|10|0|19]Details[/pad] [hide][pre]{details}[/pre][/hide]"
+                    ));
+                }
+                Err(error) => warn!("Failed to get transcode details: {error}"),
+            }
+        }
+        lines.push(format!(
+            "[url={}]Learn how easy it is to create and upload transcodes yourself![/url]",
+            PKG_REPOSITORY
+        ));
+        lines.into_iter().fold(String::new(), |mut output, line| {
+            output.push_str("[quote]");
+            output.push_str(&line);
+            output.push_str("[/quote]");
+            output
+        })
+    }
+
+    /// Get the line of the description quoting the lineage of the source if `lineage` is set.
+    fn get_lineage_line(&self, source: &Source, source_url: &str) -> Option<String> {
+        if !self.upload_options.lineage.expect("lineage should be set") {
+            return None;
+        }
+        let template = self
+            .upload_options
+            .lineage_template
+            .as_ref()
+            .expect("lineage_template should be set");
+        let line = LineageTemplate::get(
+            template,
+            &source.torrent.description,
+            &source.torrent.username,
+            source_url,
+        );
+        if line.is_none() {
+            debug!("{} lineage in the source description", "No".bold());
+        }
+        line
+    }
+
+    /// Get the line of the description recording the gain applied to prevent clipping.
+    fn get_gain_line(&self, source: &Source) -> Option<String> {
+        let gain = self.clip_checker.get_gain(source)?;
+        let analysis = self.clip_checker.get(source)?;
+        Some(format!(
+            "[pad=0|10|0|31]Gain[/pad] {gain:+.1} dB applied as {} tracks of the source have inter-sample peaks over 0 dBFS, up to {:+.2} dBFS",
+            analysis.clipping_tracks, analysis.true_peak
+        ))
+    }
+
+    /// Get the command line of the transcode of the first track.
+    pub fn get_command(&self, source: &Source, target: TargetFormat) -> Result<String, Error> {
+        let flacs = Collector::get_flacs(&source.directory);
+        let flac = flacs.first().expect("Should be at least one FLAC");
+        let job = self
+            .transcode_job_factory
+            .create_single(0, flac, source, target)?;
+        let Job::Transcode(job) = job else {
+            return Err(error(
+                "get transcode command",
+                "expected a transcode job".to_owned(),
+            ));
+        };
+        Ok(job.variant.get_display())
+    }
+
+    async fn get_details(&self, source: &Source, target: TargetFormat) -> Result<String, Error> {
+        let path = self.paths.get_transcode_target_dir(source, target);
+        EyeD3Command::display(&path).await
+    }
+}
@@ -0,0 +1,89 @@
+use gazelle_api::{Group, Torrent};
+
+use crate::naming::NameSubstitution;
+use crate::options::UploadOptions;
+
+/// Media accepted by the upload form of the indexer.
+const MEDIA: [&str; 9] = [
+    "CD",
+    "DVD",
+    "Vinyl",
+    "Soundboard",
+    "SACD",
+    "DAT",
+    "Cassette",
+    "WEB",
+    "Blu-Ray",
+];
+
+/// Edition fields of an upload.
+///
+/// A transcode must have the same edition as its source, otherwise it's shown as a
+/// separate edition and is likely to be reported.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UploadEdition {
+    pub year: u16,
+    pub title: String,
+    pub record_label: String,
+    pub catalogue_number: String,
+    pub media: String,
+}
+
+impl UploadEdition {
+    /// Get the edition of an upload from the source torrent.
+    ///
+    /// The `edition_*` options replace the fields of the source and then each
+    /// `edition_substitution` is applied to the title, record label and catalogue number.
+    ///
+    /// An original release without a remaster year uses the year of the group.
+    ///
+    /// Returns a description of the problem if the edition can't be determined, such as
+    /// an unconfirmed edition without an `edition_year`.
+    pub fn new(torrent: &Torrent, group: &Group, options: &UploadOptions) -> Result<Self, String> {
+        let year =
+            match (options.edition_year, torrent.remaster_year) {
+                (Some(year), _) | (None, Some(year)) if year > 0 => year,
+                _ if torrent.remastered => return Err(
+                    "Source is an unconfirmed edition without a year. Set edition_year to upload"
+                        .to_owned(),
+                ),
+                _ => group.year,
+            };
+        let media = get_media(&torrent.media)
+            .ok_or_else(|| format!("Source media is not recognized: {}", torrent.media))?;
+        let substitutions = options.get_edition_substitutions();
+        let get = |value: &Option<String>, fallback: &str| {
+            let value = value.as_deref().unwrap_or(fallback).trim();
+            NameSubstitution::apply_all(&substitutions, value)
+        };
+        Ok(Self {
+            year,
+            title: get(&options.edition_title, &torrent.remaster_title),
+            record_label: get(
+                &options.edition_record_label,
+                &torrent.remaster_record_label,
+            ),
+            catalogue_number: get(
+                &options.edition_catalogue_number,
+                &torrent.remaster_catalogue_number,
+            ),
+            media: media.to_owned(),
+        })
+    }
+}
+
+/// Get the media as accepted by the upload form, ignoring case, spaces and dashes.
+///
+/// For example `Web` is `WEB` and `Bluray` is `Blu-Ray`.
+#[must_use]
+pub fn get_media(media: &str) -> Option<&'static str> {
+    let normalize = |value: &str| -> String {
+        value
+            .chars()
+            .filter(|x| x.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let media = normalize(media);
+    MEDIA.into_iter().find(|x| normalize(x) == media)
+}
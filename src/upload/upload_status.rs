@@ -27,4 +27,9 @@ pub struct UploadFormatStatus {
     pub format: TargetFormat,
     /// ID of the upload
     pub id: u32,
+    /// Indexer the format was cross-posted to
+    ///
+    /// `None` if it was uploaded to the indexer of the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexer: Option<String>,
 }
@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use claxon::FlacReader;
+
+use crate::fs::FlacFile;
+
+/// Bit depth of sources that are checked for padding.
+const CHECKED_BITS_PER_SAMPLE: u32 = 24;
+
+/// Effective bit depth at or below which a 24-bit track is considered padded.
+const PADDED_BITS_PER_SAMPLE: u32 = 16;
+
+/// Seconds of audio decoded from the start of each track.
+const SAMPLED_SECONDS: u64 = 60;
+
+/// Effective bit depth of a 24-bit FLAC measured from its least significant bits.
+///
+/// 16-bit audio padded to 24-bit has at least 8 bits that are zero in every sample.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitDepth {
+    pub path: PathBuf,
+    pub bits_per_sample: u32,
+    /// Number of least significant bits that are zero in every sampled value.
+    pub wasted_bits: u32,
+    /// Number of samples that were read.
+    pub samples: u64,
+}
+
+impl BitDepth {
+    /// Measure the effective bit depth of a FLAC.
+    ///
+    /// Returns `None` if the FLAC is not 24-bit, can't be decoded, or is silent.
+    #[must_use]
+    pub fn read(flac: &FlacFile) -> Option<Self> {
+        let mut reader = FlacReader::open(&flac.path).ok()?;
+        let info = reader.streaminfo();
+        if info.bits_per_sample != CHECKED_BITS_PER_SAMPLE {
+            return None;
+        }
+        let limit = SAMPLED_SECONDS * u64::from(info.sample_rate) * u64::from(info.channels);
+        let mut samples = 0;
+        let values = reader.samples().map_while(Result::ok).take_while(|_| {
+            samples += 1;
+            samples <= limit
+        });
+        let wasted_bits = get_wasted_bits(values, info.bits_per_sample - PADDED_BITS_PER_SAMPLE)?;
+        Some(Self {
+            path: flac.path.clone(),
+            bits_per_sample: info.bits_per_sample,
+            wasted_bits,
+            samples: samples.min(limit),
+        })
+    }
+
+    /// Get the number of bits that carry audio.
+    #[must_use]
+    pub fn get_effective_bits(&self) -> u32 {
+        self.bits_per_sample.saturating_sub(self.wasted_bits)
+    }
+
+    /// Is the track likely to be 16-bit audio padded to 24-bit?
+    #[must_use]
+    pub fn is_padded(&self) -> bool {
+        self.get_effective_bits() <= PADDED_BITS_PER_SAMPLE
+    }
+}
+
+/// Get the number of least significant bits that are zero in every sample.
+///
+/// Reading stops once fewer than `min_wasted_bits` are wasted as the exact count is then
+/// irrelevant.
+///
+/// Returns `None` if every sample is zero.
+#[must_use]
+pub fn get_wasted_bits(samples: impl Iterator<Item = i32>, min_wasted_bits: u32) -> Option<u32> {
+    let mut mask = 0;
+    for sample in samples {
+        mask |= sample;
+        if mask != 0 && mask.trailing_zeros() < min_wasted_bits {
+            break;
+        }
+    }
+    (mask != 0).then(|| mask.trailing_zeros())
+}
+
+impl Display for BitDepth {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{}-bit with {} wasted bits in {} samples: {}",
+            self.get_effective_bits(),
+            self.wasted_bits,
+            self.samples,
+            self.path.display()
+        )
+    }
+}
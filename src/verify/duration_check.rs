@@ -0,0 +1,62 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// A track whose duration differs from the length reported by the metadata API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DurationMismatch {
+    pub path: PathBuf,
+    /// Expected duration in seconds.
+    pub expected: u32,
+    /// Duration of the FLAC in seconds.
+    pub actual: u32,
+}
+
+/// Compare the duration of each track with the expected lengths in the same order.
+///
+/// `tracks` are the path and duration in seconds of each FLAC.
+///
+/// `expected` are the lengths in milliseconds. Tracks without a length are not checked.
+///
+/// Returns the tracks that differ by more than `max_difference` seconds.
+#[must_use]
+pub fn get_duration_mismatches(
+    tracks: &[(PathBuf, u32)],
+    expected: &[Option<u64>],
+    max_difference: u32,
+) -> Vec<DurationMismatch> {
+    tracks
+        .iter()
+        .zip(expected)
+        .filter_map(|((path, actual), expected)| {
+            let expected = to_seconds((*expected)?);
+            (actual.abs_diff(expected) > max_difference).then(|| DurationMismatch {
+                path: path.clone(),
+                expected,
+                actual: *actual,
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::integer_division)]
+fn to_seconds(milliseconds: u64) -> u32 {
+    u32::try_from((milliseconds + 500) / 1000).unwrap_or(u32::MAX)
+}
+
+impl Display for DurationMismatch {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "Expected {} but found {}: {}",
+            format_duration(self.expected),
+            format_duration(self.actual),
+            self.path.display()
+        )
+    }
+}
+
+#[allow(clippy::integer_division)]
+fn format_duration(seconds: u32) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
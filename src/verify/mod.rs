@@ -1,10 +1,26 @@
+pub use bit_depth::*;
+pub use duration_check::*;
+pub use name_check::*;
+pub use rip_log::*;
+pub use silence_check::*;
+pub use size_check::*;
 pub use stream_verifier::*;
+pub use upsampling::*;
+pub use verify_cache::*;
 pub use verify_command::*;
 pub use verify_status::*;
 
+pub(crate) mod bit_depth;
+pub(crate) mod duration_check;
+pub(crate) mod name_check;
+pub(crate) mod rip_log;
+pub(crate) mod silence_check;
+pub(crate) mod size_check;
 mod stream_verifier;
 mod tag_verifier;
 #[cfg(test)]
 mod tests;
+pub(crate) mod upsampling;
+pub(crate) mod verify_cache;
 pub(crate) mod verify_command;
 pub(crate) mod verify_status;
@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter};
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+/// A problem with a file or directory name that breaks some clients or Windows seeders.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum NameProblem {
+    LeadingSpace,
+    TrailingSpace,
+    TrailingDot,
+    DoubleSpace,
+    ControlCharacter,
+    /// Character that is not `allowed_characters` so is replaced in the output names.
+    DisallowedCharacter(char),
+}
+
+/// A file or directory of the source with problems in its name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameIssue {
+    pub path: PathBuf,
+    pub problems: Vec<NameProblem>,
+}
+
+/// Get the problems with a file or directory name.
+#[must_use]
+pub fn get_name_problems(name: &str) -> Vec<NameProblem> {
+    let mut problems = Vec::new();
+    if name.starts_with(' ') {
+        problems.push(NameProblem::LeadingSpace);
+    }
+    if name.ends_with(' ') {
+        problems.push(NameProblem::TrailingSpace);
+    }
+    if name.ends_with('.') {
+        problems.push(NameProblem::TrailingDot);
+    }
+    if name.contains("  ") {
+        problems.push(NameProblem::DoubleSpace);
+    }
+    if name.chars().any(char::is_control) {
+        problems.push(NameProblem::ControlCharacter);
+    }
+    problems
+}
+
+/// Check the name of the source directory and every file and directory inside it with
+/// a function that gets the problems of each name such as [`get_name_problems`].
+///
+/// Directories that can't be read are ignored as they're reported by the other checks.
+#[must_use]
+pub fn get_name_issues(
+    directory: &Path,
+    get_problems: &dyn Fn(&str) -> Vec<NameProblem>,
+) -> Vec<NameIssue> {
+    let mut issues = Vec::new();
+    push_issue(directory, get_problems, &mut issues);
+    read_names(directory, get_problems, &mut issues);
+    issues
+}
+
+fn read_names(
+    directory: &Path,
+    get_problems: &dyn Fn(&str) -> Vec<NameProblem>,
+    issues: &mut Vec<NameIssue>,
+) {
+    let Ok(entries) = read_dir(directory) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    paths.sort();
+    for path in paths {
+        push_issue(&path, get_problems, issues);
+        if path.is_dir() {
+            read_names(&path, get_problems, issues);
+        }
+    }
+}
+
+fn push_issue(
+    path: &Path,
+    get_problems: &dyn Fn(&str) -> Vec<NameProblem>,
+    issues: &mut Vec<NameIssue>,
+) {
+    let Some(name) = path.file_name() else {
+        return;
+    };
+    let problems = get_problems(&name.to_string_lossy());
+    if !problems.is_empty() {
+        issues.push(NameIssue {
+            path: path.to_path_buf(),
+            problems,
+        });
+    }
+}
+
+impl Display for NameProblem {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            NameProblem::LeadingSpace => "leading space",
+            NameProblem::TrailingSpace => "trailing space",
+            NameProblem::TrailingDot => "trailing dot",
+            NameProblem::DoubleSpace => "double space",
+            NameProblem::ControlCharacter => "control character",
+            NameProblem::DisallowedCharacter(character) => {
+                return write!(formatter, "disallowed character `{character}`");
+            }
+        };
+        output.fmt(formatter)
+    }
+}
+
+impl Display for NameIssue {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let problems: Vec<String> = self.problems.iter().map(ToString::to_string).collect();
+        write!(
+            formatter,
+            "Name has a {}: {}",
+            problems.join(", "),
+            self.path.display()
+        )
+    }
+}
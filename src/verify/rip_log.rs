@@ -0,0 +1,233 @@
+use std::fmt::{Display, Formatter};
+use std::fs::read;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Score of a log without deductions.
+pub const PERFECT_LOG_SCORE: i32 = 100;
+
+/// Program that wrote a rip log.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ripper {
+    /// Exact Audio Copy
+    Eac,
+    /// X Lossless Decoder
+    Xld,
+}
+
+/// Score of an EAC or XLD rip log.
+///
+/// The score is an approximation of the logchecker used by gazelle indexers, deducting
+/// points for insecure settings and for tracks that were not ripped accurately. The score
+/// given by the indexer is authoritative.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RipLog {
+    /// Path relative to the source directory.
+    pub path: PathBuf,
+    pub ripper: Ripper,
+    pub score: i32,
+    /// Reason for each deduction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deductions: Vec<String>,
+}
+
+impl RipLog {
+    /// Read and score a log file.
+    ///
+    /// Returns `None` if the file can't be read or was not written by EAC or XLD.
+    #[must_use]
+    pub fn read(path: &Path, relative: &Path) -> Option<Self> {
+        let bytes = read(path).ok()?;
+        Self::parse(relative, &decode(&bytes))
+    }
+
+    /// Score the text of a log.
+    ///
+    /// Returns `None` if the log was not written by EAC or XLD.
+    #[must_use]
+    pub fn parse(path: &Path, text: &str) -> Option<Self> {
+        let (ripper, deductions) = if text.contains("Exact Audio Copy") {
+            (Ripper::Eac, get_eac_deductions(text))
+        } else if text.contains("X Lossless Decoder") {
+            (Ripper::Xld, get_xld_deductions(text))
+        } else {
+            return None;
+        };
+        let score = PERFECT_LOG_SCORE - deductions.iter().map(|(points, _)| points).sum::<i32>();
+        Some(Self {
+            path: path.to_path_buf(),
+            ripper,
+            score,
+            deductions: deductions
+                .into_iter()
+                .map(|(points, reason)| format!("-{points} {reason}"))
+                .collect(),
+        })
+    }
+}
+
+fn get_eac_deductions(text: &str) -> Vec<(i32, String)> {
+    let mut deductions = Vec::new();
+    if get_value(text, "Read mode") != Some("Secure") {
+        deductions.push((20, "Secure mode was not used".to_owned()));
+    }
+    if get_value(text, "Defeat audio cache") != Some("Yes") {
+        deductions.push((10, "Audio cache was not defeated".to_owned()));
+    }
+    if get_value(text, "Make use of C2 pointers") == Some("Yes") {
+        deductions.push((10, "C2 pointers were used".to_owned()));
+    }
+    if get_value(text, "Null samples used in CRC calculations") == Some("No") {
+        deductions.push((
+            5,
+            "Null samples were not used in CRC calculations".to_owned(),
+        ));
+    }
+    if get_value(text, "Gap handling").is_none() {
+        deductions.push((10, "Gap handling was not detected".to_owned()));
+    }
+    let mut tracks = get_tracks(text);
+    if tracks.is_empty() && text.contains("Range status") {
+        deductions.push((30, "Range rip".to_owned()));
+        tracks.push(("range".to_owned(), text));
+    }
+    let mut is_tested = false;
+    for (track, section) in tracks {
+        if section.contains("Suspicious position")
+            || section.contains("Timing problem")
+            || section.contains("Missing samples")
+        {
+            deductions.push((20, format!("Track {track} has read errors")));
+        }
+        if let Some(test) = get_value(section, "Test CRC") {
+            is_tested = true;
+            if get_value(section, "Copy CRC") != Some(test) {
+                deductions.push((10, format!("Track {track} test and copy CRCs do not match")));
+            }
+        }
+    }
+    if !is_tested {
+        deductions.push((10, "Test and copy was not used".to_owned()));
+    }
+    deductions
+}
+
+fn get_xld_deductions(text: &str) -> Vec<(i32, String)> {
+    let mut deductions = Vec::new();
+    if get_value(text, "Ripper mode") != Some("XLD Secure Ripper") {
+        deductions.push((20, "Secure mode was not used".to_owned()));
+    }
+    if get_value(text, "Disable audio cache") != Some("OK") {
+        deductions.push((10, "Audio cache was not disabled".to_owned()));
+    }
+    if get_value(text, "Make use of C2 Error Pointers") == Some("YES") {
+        deductions.push((10, "C2 pointers were used".to_owned()));
+    }
+    if get_value(text, "Gap status").is_none() {
+        deductions.push((10, "Gap handling was not detected".to_owned()));
+    }
+    let mut is_tested = false;
+    for (track, section) in get_tracks(text) {
+        let has_errors = [
+            "Read error",
+            "Skipped (treated as error)",
+            "Inconsistency in error sectors",
+            "Damaged sectors",
+        ]
+        .iter()
+        .any(|key| get_value(section, key).is_some_and(|value| value != "0"));
+        if has_errors {
+            deductions.push((20, format!("Track {track} has read errors")));
+        }
+        if let Some(test) = get_value(section, "CRC32 hash (test run)") {
+            is_tested = true;
+            if get_value(section, "CRC32 hash") != Some(test) {
+                deductions.push((10, format!("Track {track} test and copy CRCs do not match")));
+            }
+        }
+    }
+    if !is_tested {
+        deductions.push((10, "Test and copy was not used".to_owned()));
+    }
+    deductions
+}
+
+/// Get the value of the first `key : value` or `key value` line.
+fn get_value<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    text.lines().find_map(|line| {
+        let rest = line.trim_start().strip_prefix(key)?;
+        if !rest.starts_with([' ', '\t', ':']) {
+            return None;
+        }
+        let rest = rest.trim_start();
+        if let Some(value) = rest.strip_prefix(':') {
+            Some(value.trim())
+        } else if rest.contains(':') {
+            // The line has a longer key such as `CRC32 hash (test run)`
+            None
+        } else {
+            Some(rest.trim())
+        }
+    })
+}
+
+/// Get the number and text of each track section.
+fn get_tracks(text: &str) -> Vec<(String, &str)> {
+    let regex = Regex::new(r"(?m)^\s*Track\s+(\d+)\s*$").expect("regex should compile");
+    let headers: Vec<(usize, String)> = regex
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let start = captures.get(0)?.end();
+            Some((start, captures.get(1)?.as_str().to_owned()))
+        })
+        .collect();
+    let mut tracks = Vec::new();
+    for (index, (start, track)) in headers.iter().enumerate() {
+        let end = headers.get(index + 1).map_or(text.len(), |(next, _)| *next);
+        if let Some(section) = text.get(*start..end) {
+            tracks.push((track.clone(), section));
+        }
+    }
+    tracks
+}
+
+/// Decode a log which EAC writes as UTF-16 and XLD as UTF-8.
+fn decode(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes(chunk.try_into().unwrap_or_default()))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        let rest = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+        String::from_utf8_lossy(rest).into_owned()
+    }
+}
+
+impl Display for Ripper {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Ripper::Eac => "EAC",
+            Ripper::Xld => "XLD",
+        };
+        name.fmt(formatter)
+    }
+}
+
+impl Display for RipLog {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} log scored {}: {}",
+            self.ripper,
+            self.score,
+            self.path.display()
+        )
+    }
+}
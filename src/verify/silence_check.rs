@@ -0,0 +1,239 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use claxon::FlacReader;
+
+use crate::fs::FlacFile;
+
+/// Amplitude relative to full scale below which a sample is silent, equal to -60 dBFS.
+const SILENCE_THRESHOLD: f64 = 0.001;
+
+/// Fewest seconds of silence within a track that are reported as a gap.
+///
+/// A hidden track is typically separated from the end of the previous song by minutes of
+/// silence, while a pause between songs is only a few seconds.
+pub const MIN_SILENCE_GAP: u64 = 60;
+
+/// A span of silence within a track.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SilenceGap {
+    /// Index of the first silent frame.
+    pub start: u64,
+    /// Number of silent frames.
+    pub length: u64,
+}
+
+/// Spans of silence found by [`SilenceScanner`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SilenceScan {
+    /// Number of frames, each of one sample per channel.
+    pub frames: u64,
+    /// Spans of at least the minimum gap length.
+    pub gaps: Vec<SilenceGap>,
+}
+
+impl SilenceScan {
+    /// Is every frame silent?
+    #[must_use]
+    pub fn is_silent(&self) -> bool {
+        match self.gaps.as_slice() {
+            [gap] => gap.start == 0 && gap.length == self.frames,
+            _ => self.frames == 0,
+        }
+    }
+}
+
+/// Find spans of silence in interleaved samples.
+pub struct SilenceScanner {
+    threshold: i64,
+    channels: u64,
+    min_gap: u64,
+    samples: u64,
+    is_frame_silent: bool,
+    gap_start: Option<u64>,
+    scan: SilenceScan,
+}
+
+impl SilenceScanner {
+    /// Create a scanner that reports spans of at least `min_gap` frames.
+    #[must_use]
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss
+    )]
+    pub fn new(channels: u32, bits_per_sample: u32, min_gap: u64) -> Self {
+        let full_scale = (1_u64 << bits_per_sample.clamp(1, 32).saturating_sub(1)) as f64;
+        Self {
+            threshold: (full_scale * SILENCE_THRESHOLD).ceil() as i64,
+            channels: u64::from(channels.max(1)),
+            min_gap: min_gap.max(1),
+            samples: 0,
+            is_frame_silent: true,
+            gap_start: None,
+            scan: SilenceScan::default(),
+        }
+    }
+
+    /// Add the next interleaved sample.
+    pub fn push(&mut self, sample: i32) {
+        if i64::from(sample).abs() >= self.threshold {
+            self.is_frame_silent = false;
+        }
+        self.samples += 1;
+        if !self.samples.is_multiple_of(self.channels) {
+            return;
+        }
+        let frame = self.scan.frames;
+        self.scan.frames += 1;
+        if self.is_frame_silent {
+            self.gap_start.get_or_insert(frame);
+        } else {
+            self.end_gap(frame);
+        }
+        self.is_frame_silent = true;
+    }
+
+    /// Get the spans of silence including one that continues to the end.
+    #[must_use]
+    pub fn finish(mut self) -> SilenceScan {
+        self.end_gap(self.scan.frames);
+        self.scan
+    }
+
+    fn end_gap(&mut self, frame: u64) {
+        let Some(start) = self.gap_start.take() else {
+            return;
+        };
+        let length = frame - start;
+        if length >= self.min_gap {
+            self.scan.gaps.push(SilenceGap { start, length });
+        }
+    }
+}
+
+/// Kind of [`SilenceAnomaly`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SilenceAnomalyKind {
+    /// Every sample is silent.
+    Silent,
+    /// Silence of at least [`MIN_SILENCE_GAP`] seconds, which may hide another track.
+    Gap {
+        /// Seconds from the start of the track.
+        start: u64,
+        /// Seconds of silence.
+        length: u64,
+    },
+    /// The track is shorter than `min_track_duration`.
+    Short {
+        /// Duration of the track in seconds.
+        duration: u64,
+    },
+}
+
+/// A track that is silent, has a long silence, or is implausibly short.
+///
+/// These frequently indicate a rip error or a pregap mistake so are worth reviewing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SilenceAnomaly {
+    pub path: PathBuf,
+    pub kind: SilenceAnomalyKind,
+}
+
+impl SilenceAnomaly {
+    /// Check a FLAC for anomalies.
+    ///
+    /// The duration is read from the stream info. The samples are only decoded to find
+    /// silence if `decode` is true.
+    ///
+    /// Returns an empty [`Vec`] if the FLAC can't be read.
+    #[must_use]
+    pub fn read(flac: &FlacFile, min_duration: u64, decode: bool) -> Vec<Self> {
+        let Ok(mut reader) = FlacReader::open(&flac.path) else {
+            return Vec::new();
+        };
+        let info = reader.streaminfo();
+        let sample_rate = u64::from(info.sample_rate.max(1));
+        let scan = decode.then(|| {
+            let mut scanner = SilenceScanner::new(
+                info.channels,
+                info.bits_per_sample,
+                MIN_SILENCE_GAP * sample_rate,
+            );
+            for sample in reader.samples().map_while(Result::ok) {
+                scanner.push(sample);
+            }
+            scanner.finish()
+        });
+        let frames = info.samples.unwrap_or_default();
+        get_silence_anomalies(&flac.path, frames, sample_rate, scan.as_ref(), min_duration)
+    }
+}
+
+/// Get the anomalies of a track from its number of frames and optional [`SilenceScan`].
+///
+/// An entirely silent track is only reported as silent rather than as a gap.
+#[must_use]
+#[allow(clippy::integer_division)]
+pub fn get_silence_anomalies(
+    path: &Path,
+    frames: u64,
+    sample_rate: u64,
+    scan: Option<&SilenceScan>,
+    min_duration: u64,
+) -> Vec<SilenceAnomaly> {
+    let sample_rate = sample_rate.max(1);
+    let mut kinds = Vec::new();
+    let duration = frames / sample_rate;
+    if duration < min_duration {
+        kinds.push(SilenceAnomalyKind::Short { duration });
+    }
+    if let Some(scan) = scan {
+        if scan.is_silent() {
+            kinds.push(SilenceAnomalyKind::Silent);
+        } else {
+            for gap in &scan.gaps {
+                kinds.push(SilenceAnomalyKind::Gap {
+                    start: gap.start / sample_rate,
+                    length: gap.length / sample_rate,
+                });
+            }
+        }
+    }
+    kinds
+        .into_iter()
+        .map(|kind| SilenceAnomaly {
+            path: path.to_path_buf(),
+            kind,
+        })
+        .collect()
+}
+
+impl Display for SilenceAnomaly {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            SilenceAnomalyKind::Silent => {
+                write!(formatter, "Entirely silent: {}", self.path.display())
+            }
+            SilenceAnomalyKind::Gap { start, length } => write!(
+                formatter,
+                "Silent for {} from {}: {}",
+                format_duration(length),
+                format_duration(start),
+                self.path.display()
+            ),
+            SilenceAnomalyKind::Short { duration } => write!(
+                formatter,
+                "Only {} long: {}",
+                format_duration(duration),
+                self.path.display()
+            ),
+        }
+    }
+}
+
+#[allow(clippy::integer_division)]
+fn format_duration(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
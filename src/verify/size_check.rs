@@ -0,0 +1,27 @@
+use crate::source::SourceIssue;
+
+/// Check a source does not exceed `max_source_size` or `max_source_tracks`.
+///
+/// `size` is the bytes and `tracks` the number of FLACs of the source.
+///
+/// Returns an issue for each limit that is exceeded.
+#[must_use]
+pub fn get_size_issues(
+    size: u64,
+    tracks: usize,
+    max_size: Option<u64>,
+    max_tracks: Option<usize>,
+) -> Vec<SourceIssue> {
+    let mut issues = Vec::new();
+    if let Some(max) = max_size {
+        if size > max {
+            issues.push(SourceIssue::TooLarge { size, max });
+        }
+    }
+    if let Some(max) = max_tracks {
+        if tracks > max {
+            issues.push(SourceIssue::TooManyTracks { count: tracks, max });
+        }
+    }
+    issues
+}
@@ -0,0 +1,67 @@
+use std::iter::repeat;
+use std::path::PathBuf;
+
+use crate::verify::{get_wasted_bits, BitDepth};
+
+#[test]
+fn get_wasted_bits_padded() {
+    // Arrange
+    let samples = [0_i32, 256, -512, 1024, -256, 0x7F_FF00];
+
+    // Act
+    let wasted = get_wasted_bits(samples.into_iter(), 8);
+
+    // Assert
+    assert_eq!(wasted, Some(8));
+}
+
+#[test]
+fn get_wasted_bits_genuine_stops_early() {
+    // Arrange
+    let samples = [256_i32, 3, 512].into_iter().chain(repeat(1));
+
+    // Act
+    let wasted = get_wasted_bits(samples, 8);
+
+    // Assert
+    assert_eq!(wasted, Some(0));
+}
+
+#[test]
+fn get_wasted_bits_silent() {
+    // Arrange
+    let samples = [0_i32; 16];
+
+    // Act
+    let wasted = get_wasted_bits(samples.into_iter(), 8);
+
+    // Assert
+    assert_eq!(wasted, None);
+}
+
+#[test]
+fn bit_depth_is_padded() {
+    // Arrange
+    let padded = BitDepth {
+        path: PathBuf::from("01 Track.flac"),
+        bits_per_sample: 24,
+        wasted_bits: 8,
+        samples: 1000,
+    };
+    let genuine = BitDepth {
+        wasted_bits: 2,
+        ..padded.clone()
+    };
+
+    // Act
+    let padded_result = padded.is_padded();
+    let genuine_result = genuine.is_padded();
+
+    // Assert
+    assert!(padded_result);
+    assert!(!genuine_result);
+    assert_eq!(
+        padded.to_string(),
+        "16-bit with 8 wasted bits in 1000 samples: 01 Track.flac"
+    );
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::verify::{get_duration_mismatches, DurationMismatch};
+
+#[test]
+fn get_duration_mismatches_within_difference() {
+    // Arrange
+    let tracks = vec![
+        (PathBuf::from("01 Track.flac"), 215),
+        (PathBuf::from("02 Track.flac"), 182),
+    ];
+    let expected = vec![Some(216_400), Some(180_000)];
+
+    // Act
+    let mismatches = get_duration_mismatches(&tracks, &expected, 2);
+
+    // Assert
+    assert!(mismatches.is_empty());
+}
+
+#[test]
+fn get_duration_mismatches_beyond_difference() {
+    // Arrange
+    let tracks = vec![
+        (PathBuf::from("01 Track.flac"), 215),
+        (PathBuf::from("02 Track.flac"), 95),
+        (PathBuf::from("03 Track.flac"), 300),
+    ];
+    let expected = vec![Some(215_000), Some(182_000), None];
+
+    // Act
+    let mismatches = get_duration_mismatches(&tracks, &expected, 2);
+
+    // Assert
+    assert_eq!(
+        mismatches,
+        vec![DurationMismatch {
+            path: PathBuf::from("02 Track.flac"),
+            expected: 182,
+            actual: 95,
+        }]
+    );
+    assert_eq!(
+        mismatches.first().map(ToString::to_string),
+        Some("Expected 3:02 but found 1:35: 02 Track.flac".to_owned())
+    );
+}
@@ -1,2 +1,11 @@
+mod bit_depth_tests;
+mod duration_check_tests;
+mod name_check_tests;
+mod rip_log_tests;
+mod silence_check_tests;
+mod size_check_tests;
 mod source_rules_tests;
+mod suspicious_tracks_tests;
+mod upsampling_tests;
+mod verify_cache_tests;
 mod verify_command_tests;
@@ -0,0 +1,54 @@
+use std::fs::{create_dir_all, write};
+
+use crate::testing::TempDirectory;
+use crate::verify::{get_name_issues, get_name_problems, NameIssue, NameProblem};
+
+#[test]
+fn get_name_problems_flags_each_problem() {
+    // Act
+    let clean = get_name_problems("01 Intro.flac");
+    let spaces = get_name_problems(" 01  Intro ");
+    let dot = get_name_problems("Vol.");
+    let control = get_name_problems("01\tIntro.flac");
+
+    // Assert
+    assert!(clean.is_empty());
+    assert_eq!(
+        spaces,
+        vec![
+            NameProblem::LeadingSpace,
+            NameProblem::TrailingSpace,
+            NameProblem::DoubleSpace
+        ]
+    );
+    assert_eq!(dot, vec![NameProblem::TrailingDot]);
+    assert_eq!(control, vec![NameProblem::ControlCharacter]);
+}
+
+#[test]
+fn get_name_issues_checks_nested_names() {
+    // Arrange
+    let dir = TempDirectory::create("get_name_issues_checks_nested_names");
+    let nested = dir.join("CD1.");
+    create_dir_all(&nested).expect("should create nested dir");
+    write(nested.join("01  Intro.flac"), "").expect("should write file");
+    write(dir.join("cover.jpg"), "").expect("should write file");
+
+    // Act
+    let issues = get_name_issues(&dir, &get_name_problems);
+
+    // Assert
+    assert_eq!(
+        issues,
+        vec![
+            NameIssue {
+                path: nested.clone(),
+                problems: vec![NameProblem::TrailingDot],
+            },
+            NameIssue {
+                path: nested.join("01  Intro.flac"),
+                problems: vec![NameProblem::DoubleSpace],
+            },
+        ]
+    );
+}
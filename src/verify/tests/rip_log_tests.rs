@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use crate::verify::{RipLog, Ripper};
+
+const EAC: &str = "Exact Audio Copy V1.6 from 23. October 2020
+
+Used drive  : HL-DT-STBD-RE  WH16NS40   Adapter: 1  ID: 0
+
+Read mode               : Secure
+Utilize accurate stream : Yes
+Defeat audio cache      : Yes
+Make use of C2 pointers : No
+
+Null samples used in CRC calculations       : Yes
+Gap handling                                : Appended to previous track
+
+Track  1
+
+     Filename C:\\Music\\01 Track.wav
+
+     Peak level 98.6 %
+     Test CRC 3A3F2B1C
+     Copy CRC 3A3F2B1C
+     Copy OK
+
+Track  2
+
+     Filename C:\\Music\\02 Track.wav
+
+     Peak level 97.1 %
+     Test CRC 11111111
+     Copy CRC 11111111
+     Copy OK
+";
+
+const XLD: &str = "X Lossless Decoder version 20230627 (157.2)
+
+Ripper mode             : XLD Secure Ripper
+Disable audio cache     : OK
+Make use of C2 Error Pointers : NO
+Gap status              : Analyzed, Appended
+
+Track 01
+    Filename : /Music/01 Track.flac
+    CRC32 hash (test run)  : 1D2F3A4B
+    CRC32 hash             : 1D2F3A4B
+    Statistics
+        Read error                           : 0
+        Skipped (treated as error)           : 0
+        Inconsistency in error sectors       : 0
+        Damaged sectors                      : 0
+";
+
+#[test]
+fn rip_log_eac_perfect() {
+    // Act
+    let log = RipLog::parse(Path::new("rip.log"), EAC).expect("should parse");
+
+    // Assert
+    assert_eq!(log.ripper, Ripper::Eac);
+    assert_eq!(log.score, 100);
+    assert!(log.deductions.is_empty());
+}
+
+#[test]
+fn rip_log_eac_deductions() {
+    // Arrange
+    let text = EAC
+        .replace(
+            "Read mode               : Secure",
+            "Read mode               : Burst",
+        )
+        .replace("Copy CRC 11111111", "Copy CRC 22222222")
+        .replace("Peak level 98.6 %", "Suspicious position 0:02:20");
+
+    // Act
+    let log = RipLog::parse(Path::new("rip.log"), &text).expect("should parse");
+
+    // Assert
+    assert_eq!(log.score, 50);
+    assert_eq!(
+        log.deductions,
+        vec![
+            "-20 Secure mode was not used",
+            "-20 Track 1 has read errors",
+            "-10 Track 2 test and copy CRCs do not match",
+        ]
+    );
+}
+
+#[test]
+fn rip_log_xld_perfect() {
+    // Act
+    let log = RipLog::parse(Path::new("rip.log"), XLD).expect("should parse");
+
+    // Assert
+    assert_eq!(log.ripper, Ripper::Xld);
+    assert_eq!(log.score, 100);
+}
+
+#[test]
+fn rip_log_xld_read_errors() {
+    // Arrange
+    let text = XLD
+        .replace(
+            "Read error                           : 0",
+            "Read error                           : 3",
+        )
+        .replace("    CRC32 hash (test run)  : 1D2F3A4B\n", "");
+
+    // Act
+    let log = RipLog::parse(Path::new("rip.log"), &text).expect("should parse");
+
+    // Assert
+    assert_eq!(log.score, 70);
+    assert_eq!(
+        log.deductions,
+        vec![
+            "-20 Track 01 has read errors",
+            "-10 Test and copy was not used"
+        ]
+    );
+}
+
+#[test]
+fn rip_log_unknown_ripper() {
+    // Act
+    let log = RipLog::parse(Path::new("notes.log"), "Some other log");
+
+    // Assert
+    assert!(log.is_none());
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use crate::verify::{
+    get_silence_anomalies, SilenceAnomaly, SilenceAnomalyKind, SilenceGap, SilenceScan,
+    SilenceScanner,
+};
+
+/// Scan stereo 16-bit frames that are silent where `is_silent` returns true.
+fn scan(frames: u64, is_silent: impl Fn(u64) -> bool) -> SilenceScan {
+    let mut scanner = SilenceScanner::new(2, 16, 10);
+    for frame in 0..frames {
+        let sample = if is_silent(frame) { 8 } else { 16000 };
+        scanner.push(sample);
+        scanner.push(-sample);
+    }
+    scanner.finish()
+}
+
+#[test]
+fn silence_scanner_finds_gaps() {
+    // Act
+    let scan = scan(100, |frame| {
+        (20..40).contains(&frame) || frame >= 95 || frame == 50
+    });
+
+    // Assert
+    assert_eq!(scan.frames, 100);
+    assert_eq!(
+        scan.gaps,
+        vec![SilenceGap {
+            start: 20,
+            length: 20
+        }]
+    );
+    assert!(!scan.is_silent());
+}
+
+#[test]
+fn silence_scanner_trailing_gap() {
+    // Act
+    let scan = scan(100, |frame| frame >= 80);
+
+    // Assert
+    assert_eq!(
+        scan.gaps,
+        vec![SilenceGap {
+            start: 80,
+            length: 20
+        }]
+    );
+}
+
+#[test]
+fn silence_scanner_silent() {
+    // Act
+    let scan = scan(100, |_| true);
+
+    // Assert
+    assert!(scan.is_silent());
+}
+
+#[test]
+fn get_silence_anomalies_kinds() {
+    // Arrange
+    let path = PathBuf::from("01 Track.flac");
+    let silent = SilenceScan {
+        frames: 300,
+        gaps: vec![SilenceGap {
+            start: 0,
+            length: 300,
+        }],
+    };
+    let hidden = SilenceScan {
+        frames: 1000,
+        gaps: vec![SilenceGap {
+            start: 250,
+            length: 600,
+        }],
+    };
+
+    // Act
+    let short = get_silence_anomalies(&path, 300, 100, Some(&silent), 5);
+    let gap = get_silence_anomalies(&path, 1000, 100, Some(&hidden), 5);
+    let unchecked = get_silence_anomalies(&path, 1000, 100, None, 5);
+
+    // Assert
+    let kinds: Vec<SilenceAnomalyKind> = short.into_iter().map(|anomaly| anomaly.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            SilenceAnomalyKind::Short { duration: 3 },
+            SilenceAnomalyKind::Silent
+        ]
+    );
+    assert_eq!(
+        gap,
+        vec![SilenceAnomaly {
+            path: path.clone(),
+            kind: SilenceAnomalyKind::Gap {
+                start: 2,
+                length: 6
+            }
+        }]
+    );
+    assert_eq!(
+        gap.first().map(ToString::to_string),
+        Some("Silent for 0:06 from 0:02: 01 Track.flac".to_owned())
+    );
+    assert!(unchecked.is_empty());
+}
@@ -0,0 +1,28 @@
+use crate::source::SourceIssue;
+use crate::verify::get_size_issues;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+#[test]
+fn get_size_issues_checks_each_limit() {
+    // Act
+    let unlimited = get_size_issues(60 * GIB, 120, None, None);
+    let within = get_size_issues(2 * GIB, 12, Some(50 * GIB), Some(100));
+    let exceeded = get_size_issues(60 * GIB, 120, Some(50 * GIB), Some(100));
+
+    // Assert
+    assert!(unlimited.is_empty());
+    assert!(within.is_empty());
+    assert_eq!(exceeded.len(), 2);
+    assert!(matches!(
+        exceeded.first(),
+        Some(SourceIssue::TooLarge { size, max }) if *size == 60 * GIB && *max == 50 * GIB
+    ));
+    assert!(matches!(
+        exceeded.get(1),
+        Some(SourceIssue::TooManyTracks {
+            count: 120,
+            max: 100
+        })
+    ));
+}
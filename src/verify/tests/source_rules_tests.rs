@@ -28,7 +28,7 @@ fn test_serialize_source_rules_vec() {
         NoFlacs {
             path: PathBuf::from("/path/to/source"),
         },
-        Imdl {
+        HashMismatch {
             details: "abcd1234".to_owned(),
         },
         Length {
@@ -72,7 +72,7 @@ fn test_serialize_source_rules_vec() {
   path: /path/to/source
 - type: no_flacs
   path: /path/to/source
-- type: imdl
+- type: hash_mismatch
   details: abcd1234
 - type: length
   path: /path/to/file
@@ -94,3 +94,19 @@ fn test_serialize_source_rules_vec() {
 ";
     assert_eq!(yaml, expected);
 }
+
+#[test]
+fn test_deserialize_source_rules_imdl_alias() {
+    // Arrange
+    let yaml = "- type: imdl\n  details: abcd1234\n";
+
+    // Act
+    let rules: Vec<SourceIssue> =
+        serde_yaml::from_str(yaml).expect("Failed to deserialize SourceIssue");
+
+    // Assert
+    assert!(matches!(
+        rules.as_slice(),
+        [HashMismatch { details }] if details == "abcd1234"
+    ));
+}
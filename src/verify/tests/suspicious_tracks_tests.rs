@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use crate::fs::FlacFile;
+use crate::source::SourceIssue;
+use crate::verify::get_suspicious_tracks;
+
+fn get_flacs() -> Vec<FlacFile> {
+    let dir = PathBuf::from("/source");
+    ["01 One.flac", "02 Two.flac", "03 Three.flac"]
+        .into_iter()
+        .map(|name| FlacFile::new(dir.join(name), &dir))
+        .collect()
+}
+
+#[test]
+fn get_suspicious_tracks_stream_issues() {
+    // Arrange
+    let flacs = get_flacs();
+    let issues = vec![
+        SourceIssue::SampleRate {
+            path: PathBuf::from("/source/02 Two.flac"),
+            rate: 22050,
+        },
+        SourceIssue::Scene,
+    ];
+    let suspicious = vec![PathBuf::from("/source/03 Three.flac")];
+
+    // Act
+    let tracks = get_suspicious_tracks(&issues, suspicious, &flacs);
+
+    // Assert
+    assert_eq!(
+        tracks,
+        vec![
+            PathBuf::from("/source/03 Three.flac"),
+            PathBuf::from("/source/02 Two.flac")
+        ]
+    );
+}
+
+#[test]
+fn get_suspicious_tracks_lossy_master() {
+    // Arrange
+    let flacs = get_flacs();
+    let issues = vec![SourceIssue::LossyMaster];
+
+    // Act
+    let tracks = get_suspicious_tracks(&issues, Vec::new(), &flacs);
+
+    // Assert
+    assert_eq!(tracks.len(), 3);
+}
+
+#[test]
+fn get_suspicious_tracks_none() {
+    // Act
+    let tracks = get_suspicious_tracks(&[SourceIssue::Scene], Vec::new(), &get_flacs());
+
+    // Assert
+    assert!(tracks.is_empty());
+}
@@ -0,0 +1,84 @@
+use std::f64::consts::PI;
+use std::path::PathBuf;
+
+use crate::verify::{get_cutoff, SpectrumAnalyzer, Upsampling};
+
+const SAMPLE_RATE: u32 = 96_000;
+
+/// Get the power spectrum of one second of 24-bit mono sine waves.
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss
+)]
+fn get_power(frequencies: &[u32]) -> Vec<f64> {
+    let mut analyzer = SpectrumAnalyzer::new(1, 24);
+    let amplitude = f64::from(1 << 22) / frequencies.len() as f64;
+    for frame in 0..SAMPLE_RATE {
+        let time = f64::from(frame) / f64::from(SAMPLE_RATE);
+        let value: f64 = frequencies
+            .iter()
+            .map(|frequency| (2.0 * PI * f64::from(*frequency) * time).sin())
+            .sum();
+        analyzer.push((value * amplitude) as i32);
+    }
+    analyzer.finish()
+}
+
+#[test]
+fn get_cutoff_upsampled() {
+    // Arrange
+    let power = get_power(&[1_000, 5_000, 10_000, 20_000]);
+
+    // Act
+    let cutoff = get_cutoff(&power, SAMPLE_RATE);
+
+    // Assert
+    assert_eq!(cutoff, Some(20_500));
+}
+
+#[test]
+fn get_cutoff_genuine() {
+    // Arrange
+    let power = get_power(&[1_000, 5_000, 10_000, 20_000, 40_000]);
+
+    // Act
+    let cutoff = get_cutoff(&power, SAMPLE_RATE);
+
+    // Assert
+    assert_eq!(cutoff, Some(40_500));
+}
+
+#[test]
+fn get_cutoff_silent() {
+    // Arrange
+    let power = get_power(&[]);
+
+    // Act
+    let cutoff = get_cutoff(&power, SAMPLE_RATE);
+
+    // Assert
+    assert_eq!(cutoff, None);
+}
+
+#[test]
+fn upsampling_is_upsampled() {
+    // Arrange
+    let upsampled = Upsampling {
+        path: PathBuf::from("01 Track.flac"),
+        sample_rate: 96_000,
+        cutoff: 22_000,
+    };
+    let genuine = Upsampling {
+        cutoff: 40_000,
+        ..upsampled.clone()
+    };
+
+    // Assert
+    assert!(upsampled.is_upsampled());
+    assert!(!genuine.is_upsampled());
+    assert_eq!(
+        upsampled.to_string(),
+        "Cutoff at 22.0 kHz of 48.0 kHz: 01 Track.flac"
+    );
+}
@@ -0,0 +1,53 @@
+use std::fs::write;
+
+use crate::queue::TimeStamp;
+use crate::source::SourceIssue::Scene;
+use crate::testing::TempDirectory;
+use crate::verify::{get_fingerprint, read_entry, write_entry, VerifyCacheEntry};
+use rogue_logging::Error;
+
+#[test]
+fn get_fingerprint_changes_with_files() {
+    // Arrange
+    let dir = TempDirectory::create("get_fingerprint_changes_with_files");
+    write(dir.join("a.flac"), "a").expect("should write file");
+
+    // Act
+    let initial = get_fingerprint(&dir);
+    let unchanged = get_fingerprint(&dir);
+    write(dir.join("b.flac"), "b").expect("should write file");
+    let added = get_fingerprint(&dir);
+    write(dir.join("a.flac"), "longer").expect("should write file");
+    let modified = get_fingerprint(&dir);
+
+    // Assert
+    assert!(initial.is_some());
+    assert_eq!(initial, unchanged);
+    assert_ne!(initial, added);
+    assert_ne!(added, modified);
+    assert_eq!(get_fingerprint(&dir.join("missing")), None);
+}
+
+#[test]
+fn read_entry_requires_matching_fingerprint() -> Result<(), Error> {
+    // Arrange
+    let path = TempDirectory::create("read_entry_requires_matching_fingerprint")
+        .join("verify")
+        .join("123.red.yml");
+    let entry = VerifyCacheEntry {
+        fingerprint: "abc".to_owned(),
+        hash_checked: false,
+        issues: vec![Scene],
+        completed: TimeStamp::now(),
+    };
+
+    // Act
+    write_entry(&path, &entry)?;
+
+    // Assert
+    let found = read_entry(&path, "abc", false).expect("entry should match");
+    assert_eq!(found.issues.len(), 1);
+    assert!(read_entry(&path, "def", false).is_none());
+    assert!(read_entry(&path, "abc", true).is_none());
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use claxon::FlacReader;
+
+use crate::fs::FlacFile;
+
+/// Highest sample rate of a source that isn't checked for upsampling.
+const MAX_UNCHECKED_SAMPLE_RATE: u32 = 48_000;
+
+/// Seconds of audio decoded from the start of each track.
+const SAMPLED_SECONDS: u64 = 60;
+
+/// Number of frames in each block of the spectrum.
+const FFT_SIZE: usize = 4096;
+
+/// Width in Hz of the bands the spectrum is grouped into.
+const BAND_WIDTH: u32 = 500;
+
+/// Highest frequency in Hz of the bands the reference level is taken from.
+const REFERENCE_MAX_FREQUENCY: u32 = 16_000;
+
+/// Power of a band relative to the reference level, equal to -60 dB, below which it's
+/// beyond the cutoff.
+const CUTOFF_THRESHOLD: f64 = 1e-6;
+
+/// Least reference level, equal to -80 dB relative to full scale, for the cutoff to be
+/// measured.
+const MIN_REFERENCE_POWER: f64 = 1e-8;
+
+/// Cutoff relative to the Nyquist frequency at or below which a track is considered
+/// upsampled.
+///
+/// Upsampling to double the sample rate leaves nothing above half the Nyquist frequency.
+const UPSAMPLED_RATIO: f64 = 0.55;
+
+/// Frequency above which a hi-res FLAC has little energy, measured from its spectrum.
+///
+/// Audio upsampled from 44.1 or 48 kHz has nothing above 22.05 or 24 kHz, far below the
+/// Nyquist frequency of a 96 or 192 kHz FLAC.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Upsampling {
+    pub path: PathBuf,
+    pub sample_rate: u32,
+    /// Frequency in Hz of the highest band within 60 dB of the loudest audible band.
+    pub cutoff: u32,
+}
+
+impl Upsampling {
+    /// Measure the cutoff of a FLAC.
+    ///
+    /// Returns `None` if the sample rate is 48 kHz or lower, or the FLAC can't be
+    /// decoded, is too short, or too quiet.
+    #[must_use]
+    pub fn read(flac: &FlacFile) -> Option<Self> {
+        let mut reader = FlacReader::open(&flac.path).ok()?;
+        let info = reader.streaminfo();
+        if info.sample_rate <= MAX_UNCHECKED_SAMPLE_RATE {
+            return None;
+        }
+        let limit = SAMPLED_SECONDS * u64::from(info.sample_rate) * u64::from(info.channels);
+        let mut analyzer = SpectrumAnalyzer::new(info.channels, info.bits_per_sample);
+        for sample in reader
+            .samples()
+            .map_while(Result::ok)
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        {
+            analyzer.push(sample);
+        }
+        let cutoff = get_cutoff(&analyzer.finish(), info.sample_rate)?;
+        Some(Self {
+            path: flac.path.clone(),
+            sample_rate: info.sample_rate,
+            cutoff,
+        })
+    }
+
+    /// Get the Nyquist frequency in Hz.
+    #[must_use]
+    pub fn get_nyquist(&self) -> u32 {
+        self.sample_rate >> 1
+    }
+
+    /// Is the track likely to be upsampled from a lower sample rate?
+    #[must_use]
+    pub fn is_upsampled(&self) -> bool {
+        f64::from(self.cutoff) <= f64::from(self.get_nyquist()) * UPSAMPLED_RATIO
+    }
+}
+
+/// Average power spectrum of interleaved samples mixed to mono.
+///
+/// The samples are split into blocks of [`FFT_SIZE`] frames, each with a Hann window.
+pub struct SpectrumAnalyzer {
+    channels: u64,
+    full_scale: f64,
+    samples: u64,
+    frame: f64,
+    block: Vec<f64>,
+    window: Vec<f64>,
+    twiddles: Vec<(f64, f64)>,
+    power: Vec<f64>,
+    blocks: u32,
+}
+
+impl SpectrumAnalyzer {
+    #[must_use]
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_precision_loss,
+        clippy::integer_division
+    )]
+    pub fn new(channels: u32, bits_per_sample: u32) -> Self {
+        let size = FFT_SIZE as f64;
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f64 / size).cos())
+            .collect();
+        let twiddles = (0..FFT_SIZE / 2)
+            .map(|i| {
+                let (sin, cos) = (-2.0 * PI * i as f64 / size).sin_cos();
+                (cos, sin)
+            })
+            .collect();
+        Self {
+            channels: u64::from(channels.max(1)),
+            full_scale: (1_u64 << bits_per_sample.clamp(1, 32).saturating_sub(1)) as f64,
+            samples: 0,
+            frame: 0.0,
+            block: Vec::with_capacity(FFT_SIZE),
+            window,
+            twiddles,
+            power: vec![0.0; FFT_SIZE / 2],
+            blocks: 0,
+        }
+    }
+
+    /// Add the next interleaved sample.
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    pub fn push(&mut self, sample: i32) {
+        self.frame += f64::from(sample);
+        self.samples += 1;
+        if !self.samples.is_multiple_of(self.channels) {
+            return;
+        }
+        let value = self.frame / (self.channels as f64 * self.full_scale);
+        self.frame = 0.0;
+        self.block.push(value);
+        if self.block.len() == FFT_SIZE {
+            self.analyze_block();
+        }
+    }
+
+    /// Get the average power of each frequency bin.
+    ///
+    /// A full scale sine wave has a total power of about `1.5`. Frames of an incomplete
+    /// block are ignored so the result is empty if there are fewer than [`FFT_SIZE`].
+    #[must_use]
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    pub fn finish(self) -> Vec<f64> {
+        if self.blocks == 0 {
+            return Vec::new();
+        }
+        let peak = FFT_SIZE as f64 / 4.0;
+        let divisor = f64::from(self.blocks) * peak * peak;
+        self.power
+            .into_iter()
+            .map(|power| power / divisor)
+            .collect()
+    }
+
+    fn analyze_block(&mut self) {
+        let mut real: Vec<f64> = self
+            .block
+            .iter()
+            .zip(&self.window)
+            .map(|(value, window)| value * window)
+            .collect();
+        let mut imaginary = vec![0.0; FFT_SIZE];
+        fft(&mut real, &mut imaginary, &self.twiddles);
+        for ((power, real), imaginary) in self.power.iter_mut().zip(&real).zip(&imaginary) {
+            *power += real * real + imaginary * imaginary;
+        }
+        self.blocks += 1;
+        self.block.clear();
+    }
+}
+
+/// Transform in place with an iterative radix-2 FFT.
+///
+/// The length must be a power of two and `twiddles` must be the first half of the
+/// roots of unity of that length.
+#[allow(clippy::integer_division)]
+fn fft(real: &mut [f64], imaginary: &mut [f64], twiddles: &[(f64, f64)]) {
+    let length = real.len();
+    let bits = length.trailing_zeros();
+    for i in 0..length {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            real.swap(i, j);
+            imaginary.swap(i, j);
+        }
+    }
+    let mut size = 2;
+    while size <= length {
+        let half = size >> 1;
+        let step = length / size;
+        for (real, imaginary) in real.chunks_mut(size).zip(imaginary.chunks_mut(size)) {
+            let (real_a, real_b) = real.split_at_mut(half);
+            let (imaginary_a, imaginary_b) = imaginary.split_at_mut(half);
+            let pairs = real_a
+                .iter_mut()
+                .zip(imaginary_a.iter_mut())
+                .zip(real_b.iter_mut().zip(imaginary_b.iter_mut()))
+                .zip(twiddles.iter().step_by(step));
+            for (((real_a, imaginary_a), (real_b, imaginary_b)), (cos, sin)) in pairs {
+                let real_t = *real_b * cos - *imaginary_b * sin;
+                let imaginary_t = *real_b * sin + *imaginary_b * cos;
+                *real_b = *real_a - real_t;
+                *imaginary_b = *imaginary_a - imaginary_t;
+                *real_a += real_t;
+                *imaginary_a += imaginary_t;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+/// Get the cutoff in Hz from the power of each frequency bin of [`SpectrumAnalyzer`].
+///
+/// The bins are grouped into bands of [`BAND_WIDTH`]. The cutoff is the upper edge of
+/// the highest band within 60 dB of the loudest band below [`REFERENCE_MAX_FREQUENCY`],
+/// ignoring the first band as it contains any DC offset.
+///
+/// Returns `None` if the loudest band is too quiet to measure.
+#[must_use]
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::integer_division
+)]
+pub fn get_cutoff(power: &[f64], sample_rate: u32) -> Option<u32> {
+    let nyquist = sample_rate >> 1;
+    let bin_width = f64::from(nyquist) / power.len().max(1) as f64;
+    let mut bands = vec![0.0; (nyquist / BAND_WIDTH) as usize + 1];
+    for (bin, power) in power.iter().enumerate() {
+        let frequency = bin as f64 * bin_width;
+        let band = (frequency / f64::from(BAND_WIDTH)) as usize;
+        if let Some(total) = bands.get_mut(band) {
+            *total += power;
+        }
+    }
+    let reference_bands = (REFERENCE_MAX_FREQUENCY / BAND_WIDTH) as usize;
+    let reference = bands
+        .iter()
+        .take(reference_bands)
+        .skip(1)
+        .copied()
+        .fold(0.0, f64::max);
+    if reference < MIN_REFERENCE_POWER {
+        return None;
+    }
+    let threshold = reference * CUTOFF_THRESHOLD;
+    let highest = bands.iter().rposition(|power| *power >= threshold)?;
+    let cutoff = u32::try_from(highest + 1)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(BAND_WIDTH);
+    Some(cutoff.min(nyquist))
+}
+
+impl Display for Upsampling {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "Cutoff at {} of {}: {}",
+            format_frequency(self.cutoff),
+            format_frequency(self.get_nyquist()),
+            self.path.display()
+        )
+    }
+}
+
+fn format_frequency(hz: u32) -> String {
+    format!("{:.1} kHz", f64::from(hz) / 1000.0)
+}
@@ -0,0 +1,132 @@
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use colored::Colorize;
+use di::{injectable, Ref};
+use log::{trace, warn};
+use rogue_logging::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Hash;
+use crate::errors::{error, path_error, yaml_error};
+use crate::fs::{DirectoryReader, PathManager};
+use crate::queue::TimeStamp;
+use crate::source::{Source, SourceIssue};
+use crate::torrent::get_hash;
+
+/// Cached result of the checks of a source that read its files.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VerifyCacheEntry {
+    /// Fingerprint of the source directory when it was checked.
+    pub fingerprint: String,
+    /// Was the hash check performed?
+    pub hash_checked: bool,
+    /// Issues found by the checks.
+    pub issues: Vec<SourceIssue>,
+    /// Time the checks completed.
+    pub completed: TimeStamp,
+}
+
+/// Cache of the tag, stream and hash checks of [`VerifyCommand`].
+///
+/// Entries are keyed by torrent id and invalidated when the fingerprint of the
+/// source directory changes.
+#[injectable]
+pub struct VerifyCache {
+    paths: Ref<PathManager>,
+}
+
+impl VerifyCache {
+    /// Get the cached issues of a source if the files have not changed since it was checked.
+    ///
+    /// If `require_hash_check` is true then an entry without a hash check is ignored.
+    #[must_use]
+    pub fn get(
+        &self,
+        source: &Source,
+        fingerprint: &str,
+        require_hash_check: bool,
+    ) -> Option<Vec<SourceIssue>> {
+        let path = self.paths.get_verify_cache_path(source);
+        let entry = read_entry(&path, fingerprint, require_hash_check)?;
+        trace!(
+            "{} cached verification from {}",
+            "Using".bold(),
+            entry.completed
+        );
+        Some(entry.issues)
+    }
+
+    /// Cache the issues of a source.
+    ///
+    /// Issues caused by an error are not cached so the checks are retried.
+    ///
+    /// Failures are logged as warnings as the cache must not prevent verification.
+    pub fn set(&self, source: &Source, entry: VerifyCacheEntry) {
+        if entry
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, SourceIssue::Error { .. }))
+        {
+            return;
+        }
+        let path = self.paths.get_verify_cache_path(source);
+        if let Err(error) = write_entry(&path, &entry) {
+            warn!("{} to cache verification: {error}", "Failed".bold());
+        }
+    }
+}
+
+/// Read an entry if it matches the fingerprint.
+pub(crate) fn read_entry(
+    path: &Path,
+    fingerprint: &str,
+    require_hash_check: bool,
+) -> Option<VerifyCacheEntry> {
+    let file = File::open(path).ok()?;
+    let entry: VerifyCacheEntry = serde_yaml::from_reader(BufReader::new(file)).ok()?;
+    let is_valid = entry.fingerprint == fingerprint && (entry.hash_checked || !require_hash_check);
+    is_valid.then_some(entry)
+}
+
+/// Write an entry, creating the directory if required.
+pub(crate) fn write_entry(path: &Path, entry: &VerifyCacheEntry) -> Result<(), Error> {
+    let action = "write verify cache";
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| path_error(e, action, parent))?;
+    }
+    let file = File::create(path).map_err(|e| path_error(e, action, path))?;
+    let mut writer = BufWriter::new(file);
+    serde_yaml::to_writer(&mut writer, entry).map_err(|e| yaml_error(e, action))?;
+    writer.flush().map_err(|e| error(action, e.to_string()))
+}
+
+/// Get a fingerprint of the files in a directory.
+///
+/// The fingerprint is a hash of the relative path, length and modified time of every file,
+/// so it's cheap to compute but changes if any file is added, removed or modified.
+///
+/// Returns `None` if the directory can't be read.
+#[must_use]
+pub(crate) fn get_fingerprint(directory: &Path) -> Option<String> {
+    let mut lines = Vec::new();
+    for path in DirectoryReader::new().read(directory).ok()? {
+        let metadata = path.metadata().ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos();
+        let relative = path.strip_prefix(directory).unwrap_or(&path);
+        lines.push(format!(
+            "{}\0{}\0{modified}",
+            relative.to_string_lossy(),
+            metadata.len()
+        ));
+    }
+    lines.sort();
+    Some(Hash::new(get_hash(lines.join("\n").as_bytes())).to_hex())
+}
@@ -2,20 +2,37 @@ use colored::Colorize;
 use di::{injectable, Ref, RefMut};
 use log::*;
 
+use crate::errors::io_error;
 use crate::formats::TargetFormatProvider;
-use crate::fs::{Collector, PathManager};
-use crate::imdl::imdl_command::ImdlCommand;
-use crate::naming::Shortener;
+use crate::fs::{
+    get_content_root, has_nested_dirs, Collector, DirectoryReader, FlacFile, PathManager,
+};
+use crate::history::History;
+use crate::hooks::{HookEvent, HookRunner};
+use crate::indexer::RateLimiter;
+use crate::metrics::Metrics;
+use crate::musicbrainz::MusicBrainzProvider;
+use crate::naming::{resolve_normalized, ShortenedName, Shortener};
 use crate::options::verify_options::VerifyOptions;
-use crate::options::{Options, SharedOptions, SourceArg};
+use crate::options::{
+    EnrichOptions, HistoryOptions, HookOptions, OptionRule, Options, SharedOptions, SourceArg,
+};
+use crate::progress::{ProgressEmitter, ProgressEvent};
+use crate::queue::TimeStamp;
 use crate::source::SourceIssue::*;
 use crate::source::*;
+use crate::spectrogram::{SpectrogramCommand, SpectrogramStatus};
+use crate::summary::{TimingPhase, Timings};
+use crate::torrent::{find_local_torrent, TorrentFile, TorrentVerifier};
+use crate::transcode::get_duration;
 use crate::verify::tag_verifier::TagVerifier;
 use crate::verify::verify_status::VerifyStatus;
 use crate::verify::*;
 use gazelle_api::GazelleClient;
-use rogue_logging::Error;
-use tokio::fs::File;
+use rogue_logging::{Colors, Error};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::fs::{copy, create_dir_all, remove_dir_all, symlink, File};
 use tokio::io::AsyncWriteExt;
 
 /// Verify a FLAC source is suitable for transcoding.
@@ -24,10 +41,22 @@ pub struct VerifyCommand {
     arg: Ref<SourceArg>,
     shared_options: Ref<SharedOptions>,
     verify_options: Ref<VerifyOptions>,
+    hook_options: Ref<HookOptions>,
+    history_options: Ref<HistoryOptions>,
+    enrich_options: Ref<EnrichOptions>,
     source_provider: RefMut<SourceProvider>,
     api: RefMut<GazelleClient>,
     targets: Ref<TargetFormatProvider>,
     paths: Ref<PathManager>,
+    progress: Ref<ProgressEmitter>,
+    metrics: Ref<Metrics>,
+    rate_limiter: Ref<RateLimiter>,
+    hooks: Ref<HookRunner>,
+    history: Ref<History>,
+    cache: Ref<VerifyCache>,
+    musicbrainz: Ref<MusicBrainzProvider>,
+    timings: Ref<Timings>,
+    spectrogram: Ref<SpectrogramCommand>,
 }
 
 impl VerifyCommand {
@@ -37,14 +66,20 @@ impl VerifyCommand {
     ///
     /// [`SourceIssue`] issues are logged as warnings.
     ///
+    /// Sources the [`History`] records as verified are skipped unless `ignore_history` is set.
+    ///
     /// Returns `true` if the source is verified.
     pub async fn execute_cli(&mut self) -> Result<bool, Error> {
-        if !self.arg.validate()
-            || !self.shared_options.validate()
-            || !self.verify_options.validate()
-        {
-            return Ok(false);
-        }
+        let errors = [
+            self.arg.get_errors(),
+            self.shared_options.get_errors(),
+            self.verify_options.get_errors(),
+            self.hook_options.get_errors(),
+            self.history_options.get_errors(),
+            self.enrich_options.get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
         let source = self
             .source_provider
             .write()
@@ -52,7 +87,17 @@ impl VerifyCommand {
             .get_from_options()
             .await;
         let (status, id) = match source {
+            Ok(source) if self.is_verified_in_history(&source) => {
+                info!(
+                    "{} {source} as the history records it as verified",
+                    "Skipped".bold()
+                );
+                return Ok(true);
+            }
             Ok(source) => (self.execute(&source).await, source.to_string()),
+            Err(issue @ ApiResponse { .. }) => {
+                return Err(issue.to_error("get source from options"));
+            }
             Err(issue) => (VerifyStatus::from_issue(issue), "unknown".to_owned()),
         };
         if status.verified {
@@ -75,10 +120,286 @@ impl VerifyCommand {
     pub async fn execute(&mut self, source: &Source) -> VerifyStatus {
         debug!("{} {}", "Verifying".bold(), source);
         let mut issues: Vec<SourceIssue> = Vec::new();
+        let start = Instant::now();
         issues.append(&mut self.api_checks(source));
         issues.append(&mut self.flac_checks(source));
-        issues.append(&mut self.hash_check(source).await);
-        VerifyStatus::from_issues(issues)
+        self.timings.record(TimingPhase::Verify, start.elapsed());
+        issues.append(&mut self.cached_checks(source).await);
+        let start = Instant::now();
+        self.duration_checks(source).await;
+        let mut suspicious = bit_depth_checks(source);
+        for path in upsampling_checks(source)
+            .into_iter()
+            .chain(self.silence_checks(source))
+        {
+            if !suspicious.contains(&path) {
+                suspicious.push(path);
+            }
+        }
+        self.name_checks(source);
+        self.timings.record(TimingPhase::Verify, start.elapsed());
+        let spectrograms = self.spectrogram_checks(source, &issues, suspicious).await;
+        let event = if issues.is_empty() {
+            ProgressEvent::SourceVerified
+        } else {
+            ProgressEvent::VerifyFailed {
+                rules: issues.iter().map(|x| x.get_rule().to_owned()).collect(),
+                issues: issues.iter().map(ToString::to_string).collect(),
+            }
+        };
+        self.progress.emit(event);
+        let vars = vec![
+            ("CAESURA_VERIFIED", issues.is_empty().to_string()),
+            (
+                "CAESURA_ISSUES",
+                issues
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        ];
+        self.hooks.run(HookEvent::Verified, source, vars).await;
+        let status = VerifyStatus::from_issues(issues)
+            .with_shortened(self.get_shortened_names(source))
+            .with_logs(self.log_checks(source))
+            .with_spectrograms(spectrograms);
+        self.history.record_verify(source, &status).await;
+        status
+    }
+
+    fn is_verified_in_history(&self, source: &Source) -> bool {
+        !self
+            .history_options
+            .ignore_history
+            .expect("ignore_history should be set")
+            && self.history.is_verified(source)
+    }
+
+    /// Get the names that `auto_shorten` will shorten in the transcodes.
+    fn get_shortened_names(&self, source: &Source) -> Vec<ShortenedName> {
+        let mut names: Vec<ShortenedName> = Vec::new();
+        for target in self.targets.get(source.format, &source.existing) {
+            for name in self.paths.get_shortened_names(source, target) {
+                if !names.contains(&name) {
+                    info!(
+                        "{} {} to {}",
+                        "Shortening".bold(),
+                        name.before,
+                        name.after.gray()
+                    );
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Compare the duration of each track with the `MusicBrainz` release matched to the source.
+    ///
+    /// Mismatches are logged as warnings as the match may be a different edition.
+    async fn duration_checks(&self, source: &Source) {
+        let Some(release) = self.musicbrainz.get(source).await else {
+            return;
+        };
+        if release.track_lengths.is_empty() {
+            return;
+        }
+        let flacs = Collector::get_flacs(&source.directory);
+        if flacs.len() != release.track_lengths.len() {
+            warn!(
+                "{} release {release} has {} tracks but the source has {}",
+                "MusicBrainz".bold(),
+                release.track_lengths.len(),
+                flacs.len()
+            );
+            return;
+        }
+        let mut tracks = Vec::new();
+        for flac in flacs {
+            let Some(duration) = flac.get_stream_info().ok().as_ref().and_then(get_duration) else {
+                // Unreadable FLACs are reported by the stream checks
+                return;
+            };
+            tracks.push((flac.path.clone(), duration));
+        }
+        let max_difference = self
+            .verify_options
+            .max_duration_difference
+            .expect("max_duration_difference should be set");
+        let mismatches = get_duration_mismatches(&tracks, &release.track_lengths, max_difference);
+        if !mismatches.is_empty() {
+            warn!(
+                "{} durations differ from {release}",
+                mismatches.len().to_string().bold()
+            );
+        }
+        for mismatch in mismatches {
+            warn!("{mismatch}");
+        }
+    }
+
+    /// Check for tracks that are silent, have a long silence, or are implausibly short.
+    ///
+    /// These frequently indicate a rip error or a pregap mistake so are logged as warnings
+    /// to review before uploading.
+    ///
+    /// Returns the tracks with an anomaly.
+    fn silence_checks(&self, source: &Source) -> Vec<PathBuf> {
+        if !source.directory.is_dir() {
+            return Vec::new();
+        }
+        let decode = self
+            .verify_options
+            .silence_check
+            .expect("silence_check should be set");
+        let min_duration = self
+            .verify_options
+            .min_track_duration
+            .expect("min_track_duration should be set");
+        let anomalies: Vec<SilenceAnomaly> = Collector::get_flacs(&source.directory)
+            .iter()
+            .flat_map(|flac| SilenceAnomaly::read(flac, min_duration, decode))
+            .collect();
+        if anomalies.is_empty() {
+            return Vec::new();
+        }
+        warn!(
+            "{} {} silence or duration anomalies which may be a rip error or pregap mistake",
+            "Found".bold(),
+            anomalies.len()
+        );
+        let mut paths = Vec::new();
+        for anomaly in anomalies {
+            warn!("{anomaly}");
+            if !paths.contains(&anomaly.path) {
+                paths.push(anomaly.path);
+            }
+        }
+        paths
+    }
+
+    /// Generate spectrograms of the suspicious tracks if `auto_spectrogram` is set.
+    ///
+    /// Failures are logged as warnings as the spectrograms are only to review.
+    async fn spectrogram_checks(
+        &self,
+        source: &Source,
+        issues: &[SourceIssue],
+        suspicious: Vec<PathBuf>,
+    ) -> Option<SpectrogramStatus> {
+        if !self
+            .verify_options
+            .auto_spectrogram
+            .expect("auto_spectrogram should be set")
+            || !source.directory.is_dir()
+        {
+            return None;
+        }
+        let flacs = Collector::get_flacs(&source.directory);
+        let suspicious = get_suspicious_tracks(issues, suspicious, &flacs);
+        if suspicious.is_empty() {
+            return None;
+        }
+        let status = self.spectrogram.execute_tracks(source, &suspicious).await;
+        if let Some(error) = &status.error {
+            warn!(
+                "{} to create spectrograms of suspicious tracks: {error}",
+                "Failed".bold()
+            );
+        } else if let Some(path) = &status.path {
+            warn!(
+                "{} spectrograms of {} suspicious tracks to review: {}",
+                "Created".bold(),
+                suspicious.len(),
+                path.display()
+            );
+        }
+        Some(status)
+    }
+
+    /// Check the names of the source for spaces, dots and control characters that break
+    /// Windows seeders and some clients, and for characters the indexer doesn't permit.
+    ///
+    /// Problems are logged as warnings as the source itself can't be changed.
+    fn name_checks(&self, source: &Source) {
+        let issues = get_name_issues(&source.directory, &|name| {
+            let mut problems = get_name_problems(name);
+            for character in self.paths.get_disallowed_characters(name) {
+                problems.push(NameProblem::DisallowedCharacter(character));
+            }
+            problems
+        });
+        if issues.is_empty() {
+            return;
+        }
+        for issue in &issues {
+            warn!("{issue}");
+        }
+        let is_disallowed = issues.iter().any(|issue| {
+            issue
+                .problems
+                .iter()
+                .any(|problem| matches!(problem, NameProblem::DisallowedCharacter(_)))
+        });
+        if is_disallowed {
+            warn!(
+                "Characters that are not `allowed_characters` are replaced by `_` in the output names. Set `character_replacement` to replace them with an alternative"
+            );
+        }
+        let is_spacing = issues.iter().any(|issue| {
+            issue
+                .problems
+                .iter()
+                .any(|problem| !matches!(problem, NameProblem::DisallowedCharacter(_)))
+        });
+        if !is_spacing {
+            return;
+        }
+        if self
+            .shared_options
+            .normalize_names
+            .expect("normalize_names should be set")
+        {
+            debug!(
+                "{} leading and repeated spaces from the output names",
+                "Removing".bold()
+            );
+        } else {
+            warn!(
+                "Trailing spaces, dots and control characters are removed from the output names. Set `normalize_names` to also remove leading and repeated spaces"
+            );
+        }
+    }
+
+    /// Score the rip logs of the source.
+    ///
+    /// Logs scoring below `min_log_score` are logged as warnings but the source is still
+    /// verified as the indexer's logchecker is authoritative.
+    fn log_checks(&self, source: &Source) -> Vec<RipLog> {
+        let min_score = self
+            .verify_options
+            .min_log_score
+            .expect("min_log_score should be set");
+        if source.torrent.has_log && i64::from(source.torrent.log_score) < i64::from(min_score) {
+            warn!(
+                "{} log score of {} is below {min_score}",
+                "Indexer".bold(),
+                source.torrent.log_score
+            );
+        }
+        let logs = get_rip_logs(&source.directory);
+        for log in &logs {
+            if log.score < min_score {
+                warn!("{} {log}", "Low".bold());
+                for deduction in &log.deductions {
+                    warn!("{deduction}");
+                }
+            } else {
+                info!("{} {log}", "Checked".bold());
+            }
+        }
+        logs
     }
 
     /// Validate the source against the API.
@@ -143,6 +464,7 @@ impl VerifyCommand {
                 path: source.directory.clone(),
             }];
         }
+        warn_nested_dirs(source, &flacs);
         let mut issues: Vec<SourceIssue> = Vec::new();
         let api_flacs = source.torrent.get_flacs();
         if flacs.len() != api_flacs.len() {
@@ -167,17 +489,6 @@ impl VerifyCommand {
                     too_long = true;
                 }
             }
-            let tags = TagVerifier::execute(&flac, source)
-                .unwrap_or(vec!["failed to retrieve tags".to_owned()]);
-            if !tags.is_empty() {
-                issues.push(MissingTags {
-                    path: flac.path.clone(),
-                    tags,
-                });
-            }
-            for error in StreamVerifier::execute(&flac) {
-                issues.push(error);
-            }
         }
         if too_long {
             Shortener::suggest_album_name(source);
@@ -185,6 +496,43 @@ impl VerifyCommand {
         issues
     }
 
+    /// Run the checks that read the content of the files.
+    ///
+    /// The result is reused from the [`VerifyCache`] if the files have not changed.
+    async fn cached_checks(&mut self, source: &Source) -> Vec<SourceIssue> {
+        let no_cache = self
+            .verify_options
+            .no_verify_cache
+            .expect("no_verify_cache should be set");
+        let hash_checked = !self
+            .verify_options
+            .no_hash_check
+            .expect("no_hash_check should be set");
+        let fingerprint = get_fingerprint(&source.directory);
+        if let Some(fingerprint) = &fingerprint {
+            if !no_cache {
+                if let Some(issues) = self.cache.get(source, fingerprint, hash_checked) {
+                    debug!("{} tag, stream and hash checks as cached", "Skipped".bold());
+                    return issues;
+                }
+            }
+        }
+        let start = Instant::now();
+        let mut issues = content_checks(source);
+        self.timings.record(TimingPhase::Verify, start.elapsed());
+        issues.append(&mut self.hash_check(source).await);
+        if let Some(fingerprint) = fingerprint {
+            let entry = VerifyCacheEntry {
+                fingerprint,
+                hash_checked,
+                issues: issues.clone(),
+                completed: TimeStamp::now(),
+            };
+            self.cache.set(source, entry);
+        }
+        issues
+    }
+
     async fn hash_check(&mut self, source: &Source) -> Vec<SourceIssue> {
         if self
             .verify_options
@@ -194,8 +542,11 @@ impl VerifyCommand {
             debug!("{} hash check due to settings", "Skipped".bold());
             return Vec::new();
         }
-        let mut api = self.api.write().expect("API should be available");
         let torrent_path = self.paths.get_source_torrent_path(source);
+        if !torrent_path.is_file() {
+            self.copy_local_torrent(source, &torrent_path).await;
+        }
+        let mut api = self.api.write().expect("API should be available");
         if !torrent_path.is_file() {
             trace!(
                 "{} torrent file as it's not cached: {}",
@@ -211,7 +562,12 @@ impl VerifyCommand {
                     }]
                 }
             };
-            let buffer = match api.get_torrent_file_as_buffer(source.torrent.id).await {
+            self.rate_limiter.wait().await;
+            let start = Instant::now();
+            let result = api.get_torrent_file_as_buffer(source.torrent.id).await;
+            self.timings.record(TimingPhase::Api, start.elapsed());
+            self.metrics.record_api("get torrent file", &result);
+            let buffer = match result {
                 Ok(buffer) => buffer,
                 Err(e) => {
                     return vec![SourceIssue::Error {
@@ -233,14 +589,275 @@ impl VerifyCommand {
                 }];
             }
         }
-        ImdlCommand::verify(&torrent_path, &source.directory)
+        let start = Instant::now();
+        let directory = match self.get_hash_check_dir(source, &torrent_path).await {
+            Ok(directory) => directory,
+            Err(e) => {
+                return vec![SourceIssue::Error {
+                    domain: "Torrent".to_owned(),
+                    details: e.to_string(),
+                }]
+            }
+        };
+        let issues = TorrentVerifier::verify(&torrent_path, &directory)
             .await
             .unwrap_or_else(|e| {
                 Some(SourceIssue::Error {
-                    domain: "IMDL".to_owned(),
+                    domain: "Torrent".to_owned(),
                     details: e.to_string(),
                 })
             })
-            .map_or_else(Vec::new, |x| vec![x])
+            .map_or_else(Vec::new, |x| vec![x]);
+        if directory != source.directory {
+            let _ = remove_dir_all(&directory).await;
+        }
+        self.timings.record(TimingPhase::HashCheck, start.elapsed());
+        issues
+    }
+
+    /// Copy a local `.torrent` file of the source to the cache so it isn't downloaded.
+    ///
+    /// The `torrent_file` option, the source argument if it's a `.torrent` file and the
+    /// `torrent_dirs` are searched for a file of the torrent id of the source.
+    async fn copy_local_torrent(&self, source: &Source, torrent_path: &Path) {
+        let mut files: Vec<PathBuf> = self.verify_options.torrent_file.iter().cloned().collect();
+        if let Some(path) = self.arg.source.as_deref().map(Path::new) {
+            if path.extension().is_some_and(|x| x == "torrent") && path.is_file() {
+                files.push(path.to_path_buf());
+            }
+        }
+        let dirs = self.verify_options.torrent_dirs.clone().unwrap_or_default();
+        if files.is_empty() && dirs.is_empty() {
+            return;
+        }
+        let indexer = self.shared_options.indexer.clone().unwrap_or_default();
+        let Some(local) = find_local_torrent(source.torrent.id, &indexer, &files, &dirs).await
+        else {
+            trace!(
+                "{} local torrent file of torrent {}",
+                "Could not find".bold(),
+                source.torrent.id
+            );
+            return;
+        };
+        if let Some(parent) = torrent_path.parent() {
+            let _ = create_dir_all(parent).await;
+        }
+        match copy(&local, torrent_path).await {
+            Ok(_) => debug!(
+                "{} local torrent file for the hash check: {}",
+                "Using".bold(),
+                local.display()
+            ),
+            Err(e) => warn!(
+                "{} to copy local torrent file {}: {e}",
+                "Failed".bold(),
+                local.display()
+            ),
+        }
+    }
+
+    /// Get a directory containing the files of the source with the names used by the torrent.
+    ///
+    /// If names only differ from the torrent by their Unicode normalization form, such as
+    /// a source written by macOS, then a directory of symlinks with the names used by the
+    /// torrent is created in the cache so the hashes can be checked.
+    async fn get_hash_check_dir(
+        &self,
+        source: &Source,
+        torrent_path: &Path,
+    ) -> Result<PathBuf, Error> {
+        let summary = TorrentFile::read(torrent_path).await?.get_summary();
+        let files: Vec<PathBuf> = summary
+            .files
+            .iter()
+            .map(|file| {
+                let file = Path::new(file);
+                file.strip_prefix(&summary.name)
+                    .unwrap_or(file)
+                    .to_path_buf()
+            })
+            .collect();
+        if files
+            .iter()
+            .all(|file| source.directory.join(file).exists())
+        {
+            return Ok(source.directory.clone());
+        }
+        let action = "link source for hash check";
+        let directory = self
+            .paths
+            .get_cache_dir()
+            .join("hash_check")
+            .join(source.torrent.id.to_string());
+        if directory.exists() {
+            remove_dir_all(&directory)
+                .await
+                .map_err(|e| io_error(e, action))?;
+        }
+        for file in files {
+            let Some(actual) = resolve_normalized(&source.directory, &file) else {
+                continue;
+            };
+            let link = directory.join(&file);
+            if let Some(parent) = link.parent() {
+                create_dir_all(parent)
+                    .await
+                    .map_err(|e| io_error(e, action))?;
+            }
+            symlink(actual, link)
+                .await
+                .map_err(|e| io_error(e, action))?;
+        }
+        debug!(
+            "{} hashes with normalized names: {}",
+            "Checking".bold(),
+            directory.display()
+        );
+        Ok(directory)
+    }
+}
+
+/// Warn if the source is wrapped in junk folders or has folders nested in a folder of the
+/// same name as they will be copied to the transcodes unless `flatten_nested_dirs` is set.
+fn warn_nested_dirs(source: &Source, flacs: &[FlacFile]) {
+    let root = get_content_root(&source.directory);
+    if root != source.directory {
+        warn!(
+            "{} wrapper folder, set flatten_nested_dirs to remove it from transcodes: {}",
+            "Found".bold(),
+            root.strip_prefix(&source.directory)
+                .unwrap_or(&root)
+                .display()
+        );
+    }
+    if let Some(flac) = flacs.iter().find(|flac| has_nested_dirs(&flac.sub_dir)) {
+        warn!(
+            "{} nested folders of the same name, set flatten_nested_dirs to collapse them in transcodes: {}",
+            "Found".bold(),
+            flac.sub_dir.display()
+        );
+    }
+}
+
+/// Read and score each EAC or XLD log of a source.
+fn get_rip_logs(directory: &Path) -> Vec<RipLog> {
+    let Ok(mut paths) = DirectoryReader::new().with_extension("log").read(directory) else {
+        return Vec::new();
+    };
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| RipLog::read(path, path.strip_prefix(directory).unwrap_or(path)))
+        .collect()
+}
+
+/// Get the tracks that spectrograms should be generated of.
+///
+/// A lossy master or lossy web source is suspicious throughout so every track is
+/// included. Otherwise the tracks with a stream issue are added to the `suspicious`
+/// tracks found by the other checks.
+#[must_use]
+pub fn get_suspicious_tracks(
+    issues: &[SourceIssue],
+    mut suspicious: Vec<PathBuf>,
+    flacs: &[FlacFile],
+) -> Vec<PathBuf> {
+    if issues
+        .iter()
+        .any(|issue| matches!(issue, LossyMaster | LossyWeb))
+    {
+        return flacs.iter().map(|flac| flac.path.clone()).collect();
+    }
+    for path in issues.iter().filter_map(SourceIssue::get_stream_path) {
+        if !suspicious.contains(path) {
+            suspicious.push(path.clone());
+        }
+    }
+    suspicious
+}
+
+/// Measure the effective bit depth of each 24-bit FLAC.
+///
+/// 16-bit audio padded to 24-bit is trumpable so it's logged as a warning with the
+/// bit depth of each track.
+///
+/// Returns the tracks of a padded source.
+fn bit_depth_checks(source: &Source) -> Vec<PathBuf> {
+    if !source.directory.is_dir() {
+        return Vec::new();
+    }
+    let depths: Vec<BitDepth> = Collector::get_flacs(&source.directory)
+        .iter()
+        .filter_map(BitDepth::read)
+        .collect();
+    if depths.is_empty() || !depths.iter().all(BitDepth::is_padded) {
+        for depth in &depths {
+            trace!("{} {depth}", "Measured".bold());
+        }
+        return Vec::new();
+    }
+    warn!(
+        "{} 24-bit source is likely 16-bit audio padded to 24-bit",
+        "Padded".bold()
+    );
+    for depth in &depths {
+        warn!("{depth}");
+    }
+    depths.into_iter().map(|depth| depth.path).collect()
+}
+
+/// Measure the cutoff of each FLAC with a sample rate above 48 kHz.
+///
+/// Audio upsampled to a higher sample rate is trumpable and not worth transcoding as
+/// 24-bit so it's logged as a warning with the cutoff of each track.
+///
+/// Returns the tracks of an upsampled source.
+fn upsampling_checks(source: &Source) -> Vec<PathBuf> {
+    if !source.directory.is_dir() {
+        return Vec::new();
+    }
+    let measurements: Vec<Upsampling> = Collector::get_flacs(&source.directory)
+        .iter()
+        .filter_map(Upsampling::read)
+        .collect();
+    if measurements.is_empty() || !measurements.iter().all(Upsampling::is_upsampled) {
+        for measurement in &measurements {
+            trace!("{} {measurement}", "Measured".bold());
+        }
+        return Vec::new();
+    }
+    warn!(
+        "{} hi-res source is likely upsampled from a lower sample rate",
+        "Upsampled".bold()
+    );
+    for measurement in &measurements {
+        warn!("{measurement}");
+    }
+    measurements
+        .into_iter()
+        .map(|measurement| measurement.path)
+        .collect()
+}
+
+/// Validate the tags and audio stream of each FLAC.
+fn content_checks(source: &Source) -> Vec<SourceIssue> {
+    let mut issues: Vec<SourceIssue> = Vec::new();
+    if !source.directory.is_dir() {
+        return issues;
+    }
+    for flac in Collector::get_flacs(&source.directory) {
+        let tags = TagVerifier::execute(&flac, source)
+            .unwrap_or(vec!["failed to retrieve tags".to_owned()]);
+        if !tags.is_empty() {
+            issues.push(MissingTags {
+                path: flac.path.clone(),
+                tags,
+            });
+        }
+        for error in StreamVerifier::execute(&flac) {
+            issues.push(error);
+        }
     }
+    issues
 }
@@ -1,5 +1,8 @@
+use crate::naming::ShortenedName;
 use crate::queue::TimeStamp;
 use crate::source::SourceIssue;
+use crate::spectrogram::SpectrogramStatus;
+use crate::verify::RipLog;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -8,6 +11,13 @@ pub struct VerifyStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issues: Option<Vec<SourceIssue>>,
     pub completed: TimeStamp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortened: Option<Vec<ShortenedName>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<RipLog>>,
+    /// Spectrograms generated by `auto_spectrogram` for the suspicious tracks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spectrograms: Option<SpectrogramStatus>,
 }
 
 impl VerifyStatus {
@@ -16,6 +26,9 @@ impl VerifyStatus {
             verified: true,
             issues: None,
             completed: TimeStamp::now(),
+            shortened: None,
+            logs: None,
+            spectrograms: None,
         }
     }
     pub fn from_issues(issues: Vec<SourceIssue>) -> Self {
@@ -26,6 +39,9 @@ impl VerifyStatus {
                 verified: false,
                 issues: Some(issues),
                 completed: TimeStamp::now(),
+                shortened: None,
+                logs: None,
+                spectrograms: None,
             }
         }
     }
@@ -34,6 +50,34 @@ impl VerifyStatus {
             verified: false,
             issues: Some(vec![issue]),
             completed: TimeStamp::now(),
+            shortened: None,
+            logs: None,
+            spectrograms: None,
         }
     }
+
+    /// Record the names shortened in the output by `auto_shorten`.
+    #[must_use]
+    pub fn with_shortened(mut self, shortened: Vec<ShortenedName>) -> Self {
+        if !shortened.is_empty() {
+            self.shortened = Some(shortened);
+        }
+        self
+    }
+
+    /// Record the score of each rip log of the source.
+    #[must_use]
+    pub fn with_logs(mut self, logs: Vec<RipLog>) -> Self {
+        if !logs.is_empty() {
+            self.logs = Some(logs);
+        }
+        self
+    }
+
+    /// Record the spectrograms generated for the suspicious tracks.
+    #[must_use]
+    pub fn with_spectrograms(mut self, spectrograms: Option<SpectrogramStatus>) -> Self {
+        self.spectrograms = spectrograms;
+        self
+    }
 }
@@ -0,0 +1,11 @@
+pub use torrent_client::*;
+pub use torrent_client_kind::*;
+pub use watch_command::*;
+pub use watch_helpers::*;
+
+#[cfg(test)]
+mod tests;
+pub(crate) mod torrent_client;
+pub(crate) mod torrent_client_kind;
+pub(crate) mod watch_command;
+pub(crate) mod watch_helpers;
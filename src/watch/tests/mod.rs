@@ -0,0 +1,2 @@
+mod torrent_client_tests;
+mod watch_helpers_tests;
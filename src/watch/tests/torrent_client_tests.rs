@@ -0,0 +1,53 @@
+use crate::watch::*;
+
+#[test]
+fn parse_qbittorrent_torrents_completed() {
+    // Arrange
+    let json = r#"[
+        {"hash": "ABCDEF0123456789ABCDEF0123456789ABCDEF01", "name": "Complete", "progress": 1.0, "category": "music"},
+        {"hash": "0123456789abcdef0123456789abcdef01234567", "name": "Partial", "progress": 0.5, "category": "music"}
+    ]"#;
+
+    // Act
+    let torrents = parse_qbittorrent_torrents(json).expect("should parse");
+
+    // Assert
+    assert_eq!(
+        torrents,
+        vec![ClientTorrent {
+            hash: "abcdef0123456789abcdef0123456789abcdef01".to_owned(),
+            name: "Complete".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn parse_transmission_torrents_filters_label() {
+    // Arrange
+    let json = r#"{
+        "result": "success",
+        "arguments": {
+            "torrents": [
+                {"hashString": "abcdef0123456789abcdef0123456789abcdef01", "name": "Labelled", "percentDone": 1, "labels": ["red"]},
+                {"hashString": "0123456789abcdef0123456789abcdef01234567", "name": "Other", "percentDone": 1, "labels": []},
+                {"hashString": "1111111111111111111111111111111111111111", "name": "Partial", "percentDone": 0.2, "labels": ["red"]}
+            ]
+        }
+    }"#;
+
+    // Act
+    let all = parse_transmission_torrents(json, None).expect("should parse");
+    let labelled = parse_transmission_torrents(json, Some("red")).expect("should parse");
+    let failure = parse_transmission_torrents(r#"{"result": "no such method"}"#, None);
+
+    // Assert
+    assert_eq!(all.len(), 2);
+    assert_eq!(
+        labelled,
+        vec![ClientTorrent {
+            hash: "abcdef0123456789abcdef0123456789abcdef01".to_owned(),
+            name: "Labelled".to_owned(),
+        }]
+    );
+    assert_eq!(failure, Err("no such method".to_owned()));
+}
@@ -0,0 +1,156 @@
+use std::fs::{create_dir, File};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use chrono::{Local, TimeZone};
+use croner::Cron;
+
+use crate::testing::TempDirectory;
+use crate::watch::*;
+
+#[test]
+fn is_settled_recent_file() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let path = dir.join("example.torrent");
+    File::create(&path).expect("Should be able to create file");
+
+    // Act
+    let is_recent_settled = is_settled(&path, Duration::from_mins(1));
+    let is_zero_settled = is_settled(&path, Duration::ZERO);
+
+    // Assert
+    assert!(!is_recent_settled);
+    assert!(is_zero_settled);
+}
+
+#[test]
+fn is_settled_directory_uses_descendants() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let album = dir.join("album");
+    create_dir(&album).expect("Should be able to create dir");
+    File::create(album.join("01.flac")).expect("Should be able to create file");
+
+    // Act
+    let is_recent_settled = is_settled(&album, Duration::from_mins(1));
+
+    // Assert
+    assert!(!is_recent_settled);
+}
+
+#[test]
+fn is_settled_missing_path() {
+    // Arrange
+    let path = PathBuf::from("/this/path/does/not/exist.torrent");
+
+    // Act
+    let is_missing_settled = is_settled(&path, Duration::ZERO);
+
+    // Assert
+    assert!(!is_missing_settled);
+}
+
+#[test]
+fn is_watchable_excludes_other_files() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let torrent = dir.join("example.torrent");
+    let other = dir.join("example.txt");
+    File::create(&torrent).expect("Should be able to create file");
+    File::create(&other).expect("Should be able to create file");
+
+    // Act
+    // Assert
+    assert!(is_watchable(&torrent));
+    assert!(!is_watchable(&other));
+    assert!(!is_watchable(&dir));
+}
+
+#[test]
+fn get_marker_path_uses_file_name_and_modified() {
+    // Arrange
+    let dir = TempDirectory::create("caesura");
+    let markers_dir = PathBuf::from("/cache/watch");
+    let path = dir.join("example.torrent");
+    let file = File::create(&path).expect("Should be able to create file");
+    let first = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let second = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000);
+
+    // Act
+    file.set_modified(first)
+        .expect("Should be able to set modified");
+    let marker = get_marker_path(&markers_dir, &path);
+    file.set_modified(second)
+        .expect("Should be able to set modified");
+    let dropped_again = get_marker_path(&markers_dir, &path);
+
+    // Assert
+    assert_eq!(
+        marker,
+        Some(PathBuf::from(
+            "/cache/watch/files/example.torrent.1000000.processed"
+        ))
+    );
+    assert_ne!(marker, dropped_again);
+}
+
+#[test]
+fn get_client_marker_path_is_separate() {
+    // Arrange
+    let markers_dir = PathBuf::from("/cache/watch");
+
+    // Act
+    let marker = get_client_marker_path(&markers_dir, "ABCDEF");
+
+    // Assert
+    assert_eq!(
+        marker,
+        PathBuf::from("/cache/watch/client/abcdef.processed")
+    );
+}
+
+#[test]
+fn get_next_run_nightly() {
+    // Arrange
+    let schedule = Cron::from_str("0 3 * * *").expect("Schedule should parse");
+    let now = Local
+        .with_ymd_and_hms(2024, 1, 1, 12, 0, 0)
+        .single()
+        .expect("Date should be valid");
+
+    // Act
+    let next_run = get_next_run(&schedule, &now);
+
+    // Assert
+    let expected = Local
+        .with_ymd_and_hms(2024, 1, 2, 3, 0, 0)
+        .single()
+        .expect("Date should be valid");
+    assert_eq!(next_run, Some(expected));
+}
+
+#[test]
+fn get_wait_uses_sooner_of_interval_and_schedule() {
+    // Arrange
+    let now = Local
+        .with_ymd_and_hms(2024, 1, 1, 2, 59, 30)
+        .single()
+        .expect("Date should be valid");
+    let next_run = Local
+        .with_ymd_and_hms(2024, 1, 1, 3, 0, 0)
+        .single()
+        .expect("Date should be valid");
+    let interval = Duration::from_mins(1);
+
+    // Act
+    let without_schedule = get_wait(interval, None, &now);
+    let with_schedule = get_wait(interval, Some(&next_run), &now);
+    let overdue = get_wait(interval, Some(&now), &next_run);
+
+    // Assert
+    assert_eq!(without_schedule, interval);
+    assert_eq!(with_schedule, Duration::from_secs(30));
+    assert_eq!(overdue, Duration::ZERO);
+}
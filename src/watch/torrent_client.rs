@@ -0,0 +1,260 @@
+use di::{injectable, Ref};
+use log::trace;
+use reqwest::header::{CONTENT_TYPE, COOKIE, REFERER, SET_COOKIE};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use rogue_logging::Error;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::errors::error;
+use crate::options::WatchOptions;
+use crate::watch::TorrentClientKind;
+
+/// Header Transmission requires to be echoed back to prevent CSRF.
+const TRANSMISSION_SESSION_HEADER: &str = "X-Transmission-Session-Id";
+
+/// Suffix of the name of the cookie qBittorrent stores the session in.
+///
+/// Recent versions append the port such as `QBT_SID_8080`.
+const QBITTORRENT_COOKIE_NAME: &str = "SID";
+
+/// A torrent that has finished downloading in the torrent client.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClientTorrent {
+    /// Info hash in lowercase hex.
+    pub hash: String,
+    pub name: String,
+}
+
+/// A client for the API of the torrent client set by `client_type`.
+///
+/// Only completed torrents in the `client_category` are returned, which is a category of
+/// qBittorrent or a label of Transmission.
+#[injectable]
+pub struct TorrentClient {
+    options: Ref<WatchOptions>,
+}
+
+impl TorrentClient {
+    /// Is `client_type` set?
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.options.client_type.is_some()
+    }
+
+    /// Get the completed torrents.
+    pub async fn get_completed(&self) -> Result<Vec<ClientTorrent>, Error> {
+        let url = self
+            .options
+            .client_url
+            .clone()
+            .expect("client_url should be set");
+        let url = url.trim_end_matches('/');
+        let category = self.options.client_category.as_deref();
+        match self.options.client_type.expect("client_type should be set") {
+            TorrentClientKind::Qbittorrent => self.get_qbittorrent(url, category).await,
+            TorrentClientKind::Transmission => self.get_transmission(url, category).await,
+        }
+    }
+
+    async fn get_qbittorrent(
+        &self,
+        url: &str,
+        category: Option<&str>,
+    ) -> Result<Vec<ClientTorrent>, Error> {
+        let action = "get torrents from qbittorrent";
+        let client = Client::new();
+        let cookie = match &self.options.client_username {
+            Some(username) => {
+                let password = self.options.client_password.clone().unwrap_or_default();
+                let request = client
+                    .post(format!("{url}/api/v2/auth/login"))
+                    .header(REFERER, url)
+                    .form(&[("username", username.as_str()), ("password", &password)]);
+                let response = send(action, request).await?;
+                let cookie = get_session_cookie(&response)
+                    .ok_or_else(|| error(action, "Login was rejected by qBittorrent".to_owned()))?;
+                Some(cookie)
+            }
+            None => None,
+        };
+        let mut query = vec![("filter", "completed")];
+        if let Some(category) = category {
+            query.push(("category", category));
+        }
+        let mut request = client
+            .get(format!("{url}/api/v2/torrents/info"))
+            .header(REFERER, url)
+            .query(&query);
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, cookie);
+        }
+        let json = read(action, send(action, request).await?).await?;
+        parse_qbittorrent_torrents(&json).map_err(|message| error(action, message))
+    }
+
+    async fn get_transmission(
+        &self,
+        url: &str,
+        category: Option<&str>,
+    ) -> Result<Vec<ClientTorrent>, Error> {
+        let action = "get torrents from transmission";
+        let client = Client::new();
+        let body = json!({
+            "method": "torrent-get",
+            "arguments": { "fields": ["hashString", "name", "percentDone", "labels"] },
+        })
+        .to_string();
+        let mut session_id = String::new();
+        // The first request is rejected with the session id to use
+        for _ in 0..2 {
+            let mut request = client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(TRANSMISSION_SESSION_HEADER, &session_id)
+                .body(body.clone());
+            if let Some(username) = &self.options.client_username {
+                request = request.basic_auth(username, self.options.client_password.as_ref());
+            }
+            trace!("Sending request POST {url}");
+            let response = request
+                .send()
+                .await
+                .map_err(|e| error(action, e.to_string()))?;
+            if response.status() == StatusCode::CONFLICT {
+                session_id = response
+                    .headers()
+                    .get(TRANSMISSION_SESSION_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned();
+                continue;
+            }
+            let json = read(action, check_status(action, response)?).await?;
+            return parse_transmission_torrents(&json, category)
+                .map_err(|message| error(action, message));
+        }
+        Err(error(
+            action,
+            "Transmission did not accept the session id".to_owned(),
+        ))
+    }
+}
+
+async fn send(action: &str, request: RequestBuilder) -> Result<Response, Error> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| error(action, e.to_string()))?;
+    check_status(action, response)
+}
+
+fn check_status(action: &str, response: Response) -> Result<Response, Error> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error {
+            action: action.to_owned(),
+            message: format!("Torrent client responded with {status}"),
+            status_code: Some(status.as_u16()),
+            ..Error::default()
+        });
+    }
+    Ok(response)
+}
+
+async fn read(action: &str, response: Response) -> Result<String, Error> {
+    response
+        .text()
+        .await
+        .map_err(|e| error(action, e.to_string()))
+}
+
+/// Get the `Cookie` header of the qBittorrent session from the response to a login.
+fn get_session_cookie(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| value.split(';').next())
+        .find(|value| {
+            value
+                .split_once('=')
+                .is_some_and(|(name, _)| name.ends_with(QBITTORRENT_COOKIE_NAME))
+        })
+        .map(ToOwned::to_owned)
+}
+
+#[derive(Deserialize)]
+struct QbittorrentTorrent {
+    hash: String,
+    name: String,
+    progress: f64,
+}
+
+/// Get the completed torrents from the response of the qBittorrent `torrents/info`
+/// endpoint.
+///
+/// Returns a description of the problem if it can't be parsed.
+pub fn parse_qbittorrent_torrents(json: &str) -> Result<Vec<ClientTorrent>, String> {
+    let torrents: Vec<QbittorrentTorrent> =
+        serde_json::from_str(json).map_err(|e| e.to_string())?;
+    Ok(torrents
+        .into_iter()
+        .filter(|torrent| torrent.progress >= 1.0)
+        .map(|torrent| ClientTorrent {
+            hash: torrent.hash.to_lowercase(),
+            name: torrent.name,
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct TransmissionResponse {
+    result: String,
+    #[serde(default)]
+    arguments: Option<TransmissionArguments>,
+}
+
+#[derive(Deserialize)]
+struct TransmissionArguments {
+    torrents: Vec<TransmissionTorrent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransmissionTorrent {
+    hash_string: String,
+    name: String,
+    percent_done: f64,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+/// Get the completed torrents with the `category` label from the response of the
+/// Transmission `torrent-get` method.
+///
+/// Returns a description of the problem if it can't be parsed or was not successful.
+pub fn parse_transmission_torrents(
+    json: &str,
+    category: Option<&str>,
+) -> Result<Vec<ClientTorrent>, String> {
+    let response: TransmissionResponse = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    if response.result != "success" {
+        return Err(response.result);
+    }
+    Ok(response
+        .arguments
+        .map(|arguments| arguments.torrents)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|torrent| torrent.percent_done >= 1.0)
+        .filter(|torrent| {
+            category.is_none_or(|category| torrent.labels.iter().any(|x| x == category))
+        })
+        .map(|torrent| ClientTorrent {
+            hash: torrent.hash_string.to_lowercase(),
+            name: torrent.name,
+        })
+        .collect())
+}
@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Torrent client polled for completed torrents by [`WatchCommand`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum TorrentClientKind {
+    /// qBittorrent Web API such as `http://localhost:8080`.
+    Qbittorrent,
+    /// Transmission RPC such as `http://localhost:9091/transmission/rpc`.
+    Transmission,
+}
+
+impl TorrentClientKind {
+    /// Get the name of the client as it's serialized.
+    #[must_use]
+    pub fn get_name(self) -> &'static str {
+        match self {
+            TorrentClientKind::Qbittorrent => "qbittorrent",
+            TorrentClientKind::Transmission => "transmission",
+        }
+    }
+}
+
+impl Display for TorrentClientKind {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_name().fmt(formatter)
+    }
+}
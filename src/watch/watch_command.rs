@@ -0,0 +1,335 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, read_dir, File};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Local;
+use colored::Colorize;
+use di::{injectable, Ref, RefMut};
+use log::{debug, info, trace, warn};
+use rogue_logging::Error;
+use tokio::time::sleep;
+
+use crate::batch::BatchCommand;
+use crate::db::Hash;
+use crate::errors::io_error;
+use crate::hosting::{ConfigReloader, Shutdown};
+use crate::indexer::IndexerClient;
+use crate::metrics::{Metrics, MetricsServer};
+use crate::notify::{HealthCheck, HealthPing};
+use crate::options::{CacheOptions, OptionRule, Options, SharedOptions, WatchOptions};
+use crate::queue::{Queue, QueueItem};
+use crate::torrent::TorrentFile;
+use crate::watch::{
+    get_client_marker_path, get_marker_path, get_next_run, get_wait, is_settled, is_watchable,
+    TorrentClient,
+};
+
+/// Watch a directory and batch process new `.torrent` files.
+///
+/// Each scan of the watch directory:
+/// 1. Finds `.torrent` files that have not been processed
+/// 2. Defers any that have been modified within the debounce duration
+/// 3. Adds the `.torrent` files to the queue
+/// 4. Executes [`BatchCommand`] on the unprocessed items in the queue
+/// 5. Writes a marker file to the cache so they are not processed again
+///
+/// Folders are not processed as a folder can't be matched to a source without its
+/// `.torrent` file. Set `client_type` to process completed torrents instead.
+///
+/// If `client_type` is set then each scan also polls the torrent client, and each
+/// completed torrent that's on the indexer is added to the queue by its info hash.
+///
+/// If `watch_schedule` is set then [`BatchCommand`] is also executed each time the
+/// schedule is due, even if nothing new was found.
+///
+/// If `metrics_address` is set then [`Metrics`] are served from `/metrics`.
+///
+/// If `healthcheck_url` is set then success is pinged after each scan.
+///
+/// Changes to the config file are applied to the next batch by [`ConfigReloader`].
+#[injectable]
+pub struct WatchCommand {
+    shared_options: Ref<SharedOptions>,
+    cache_options: Ref<CacheOptions>,
+    watch_options: Ref<WatchOptions>,
+    batch: RefMut<BatchCommand>,
+    reloader: Ref<ConfigReloader>,
+    queue: RefMut<Queue>,
+    client: Ref<TorrentClient>,
+    indexer: Ref<IndexerClient>,
+    metrics: Ref<Metrics>,
+    health: Ref<HealthCheck>,
+    shutdown: Ref<Shutdown>,
+}
+
+impl WatchCommand {
+    /// Execute [`WatchCommand`] from the CLI.
+    ///
+    /// Runs until [`Shutdown`] is requested or an error occurs.
+    pub async fn execute_cli(&mut self) -> Result<bool, Error> {
+        let errors = [
+            self.watch_options.get_errors(),
+            self.batch
+                .read()
+                .expect("BatchCommand should be readable")
+                .get_errors(),
+        ]
+        .concat();
+        OptionRule::check(&errors)?;
+        let path = self.watch_options.watch_path.clone();
+        let interval = self
+            .watch_options
+            .get_watch_interval()
+            .expect("watch_interval should be set");
+        let debounce = self
+            .watch_options
+            .get_watch_debounce()
+            .expect("watch_debounce should be set");
+        let schedule = self.watch_options.get_watch_schedule();
+        if let Some(address) = self.watch_options.get_metrics_address() {
+            let server = MetricsServer::bind(address).await?;
+            tokio::spawn(server.serve(self.metrics.clone()));
+        }
+        let mut next_run = schedule
+            .as_ref()
+            .and_then(|schedule| get_next_run(schedule, &Local::now()));
+        if let Some(path) = &path {
+            info!("{} {}", "Watching".bold(), path.display());
+        }
+        if let Some(client_type) = self.watch_options.client_type {
+            info!("{} {client_type} for completed torrents", "Polling".bold());
+        }
+        if let Some(next_run) = &next_run {
+            info!("{} batch scheduled for {next_run}", "Next".bold());
+        }
+        while !self.shutdown.is_requested() {
+            if let Some(path) = &path {
+                let processed = self.execute(path, debounce).await?;
+                if processed > 0 {
+                    info!("{} {processed} new torrent files", "Processed".bold());
+                }
+            }
+            if self.client.is_enabled() {
+                let processed = self.execute_client().await?;
+                if processed > 0 {
+                    info!("{} {processed} completed torrents", "Processed".bold());
+                }
+            }
+            self.health.ping(HealthPing::Success, String::new()).await;
+            let now = Local::now();
+            if let (Some(schedule), Some(due)) = (&schedule, &next_run) {
+                if *due <= now {
+                    info!("{} scheduled batch", "Starting".bold());
+                    self.execute_batch().await?;
+                    next_run = get_next_run(schedule, &Local::now());
+                    if let Some(next_run) = &next_run {
+                        info!("{} batch scheduled for {next_run}", "Next".bold());
+                    }
+                }
+            }
+            let wait = get_wait(interval, next_run.as_ref(), &Local::now());
+            trace!("{} {wait:?} before the next scan", "Waiting".bold());
+            tokio::select! {
+                () = sleep(wait) => {},
+                () = self.shutdown.wait() => {},
+            }
+        }
+        info!("{} watching", "Stopped".bold());
+        Ok(true)
+    }
+
+    /// Scan the watch directory once and process any new `.torrent` files.
+    ///
+    /// A marker is only written for a file that was read and added to the queue, so a file
+    /// that can't be read is tried again by the next scan.
+    ///
+    /// Returns the number of files processed.
+    pub async fn execute(&mut self, path: &Path, debounce: Duration) -> Result<usize, Error> {
+        let markers_dir = self.get_markers_dir();
+        let entries = get_pending(path, &markers_dir, debounce)?;
+        if entries.is_empty() {
+            trace!("{} new torrent files", "No".bold());
+            return Ok(0);
+        }
+        debug!("{} {} new torrent files", "Found".bold(), entries.len());
+        let mut items = BTreeMap::new();
+        let mut markers = Vec::new();
+        for (entry, marker) in entries {
+            match TorrentFile::read(&entry).await {
+                Ok(torrent) => {
+                    let item = QueueItem::from_torrent(entry, torrent.get_summary());
+                    items.insert(item.hash, item);
+                    markers.push(marker);
+                }
+                Err(error) => {
+                    warn!(
+                        "{} to read torrent {}: {error}",
+                        "Failed".bold(),
+                        entry.display()
+                    );
+                }
+            }
+        }
+        if markers.is_empty() {
+            return Ok(0);
+        }
+        let added = {
+            let queue = self.queue.read().expect("Queue should be readable");
+            queue.set_many(items, false).await?
+        };
+        info!("{} {added} items to the queue", "Added".bold());
+        self.execute_batch().await?;
+        write_markers(&markers)?;
+        Ok(markers.len())
+    }
+
+    /// Poll the torrent client once and process any newly completed torrents.
+    ///
+    /// Torrents are looked up on the indexer by their info hash. A torrent that can't be
+    /// looked up is checked again by the next poll, otherwise a marker file is written so
+    /// it's not checked again.
+    ///
+    /// Returns the number of torrents added to the queue.
+    pub async fn execute_client(&mut self) -> Result<usize, Error> {
+        let markers_dir = self.get_markers_dir();
+        let torrents = self.client.get_completed().await?;
+        let indexer = self
+            .shared_options
+            .indexer
+            .clone()
+            .expect("indexer should be set");
+        let mut items = BTreeMap::new();
+        let mut markers = Vec::new();
+        for torrent in torrents {
+            let marker = get_client_marker_path(&markers_dir, &torrent.hash);
+            if marker.exists() {
+                continue;
+            }
+            let Ok(hash) = Hash::<20>::from_string(&torrent.hash) else {
+                trace!(
+                    "{} {} as the info hash is not valid",
+                    "Skipping".bold(),
+                    torrent.name
+                );
+                continue;
+            };
+            let is_queued = self
+                .queue
+                .read()
+                .expect("Queue should be readable")
+                .get(hash)?
+                .is_some();
+            if is_queued {
+                markers.push(marker);
+                continue;
+            }
+            match self.indexer.get_torrent_by_hash(&torrent.hash).await {
+                Ok(Some(response)) => {
+                    items.insert(
+                        hash,
+                        QueueItem {
+                            name: torrent.name,
+                            hash,
+                            indexer: indexer.clone(),
+                            id: Some(response.torrent.id),
+                            ..QueueItem::default()
+                        },
+                    );
+                    markers.push(marker);
+                }
+                Ok(None) => {
+                    trace!(
+                        "{} {} as it's not on the indexer",
+                        "Skipping".bold(),
+                        torrent.name
+                    );
+                    markers.push(marker);
+                }
+                Err(error) => {
+                    warn!("{} to look up {}: {error}", "Failed".bold(), torrent.name);
+                }
+            }
+        }
+        if markers.is_empty() {
+            trace!("{} completed torrents", "No".bold());
+            return Ok(0);
+        }
+        let added = if items.is_empty() {
+            0
+        } else {
+            let queue = self.queue.read().expect("Queue should be readable");
+            queue.set_many(items, false).await?
+        };
+        if added > 0 {
+            info!("{} {added} completed torrents to the queue", "Added".bold());
+            self.execute_batch().await?;
+        }
+        write_markers(&markers)?;
+        Ok(added)
+    }
+
+    async fn execute_batch(&mut self) -> Result<bool, Error> {
+        if let Some(batch) = self.reloader.reload_batch() {
+            self.batch = batch;
+        }
+        self.batch
+            .write()
+            .expect("BatchCommand should be writeable")
+            .execute()
+            .await
+    }
+
+    fn get_markers_dir(&self) -> PathBuf {
+        self.cache_options
+            .cache
+            .clone()
+            .expect("cache should be set")
+            .join("watch")
+    }
+}
+
+/// Get each `.torrent` file of the watch directory that's settled and has no marker, with
+/// the path of its marker.
+fn get_pending(
+    path: &Path,
+    markers_dir: &Path,
+    debounce: Duration,
+) -> Result<Vec<(PathBuf, PathBuf)>, Error> {
+    let dir = read_dir(path).map_err(|e| io_error(e, "read watch directory"))?;
+    let mut pending = Vec::new();
+    for entry in dir {
+        let entry = entry.map_err(|e| io_error(e, "read watch directory entry"))?;
+        let entry_path = entry.path();
+        if !is_watchable(&entry_path) {
+            continue;
+        }
+        let Some(marker) = get_marker_path(markers_dir, &entry_path) else {
+            continue;
+        };
+        if marker.exists() {
+            continue;
+        }
+        if !is_settled(&entry_path, debounce) {
+            trace!(
+                "{} {} as it was recently modified",
+                "Deferring".bold(),
+                entry_path.display()
+            );
+            continue;
+        }
+        pending.push((entry_path, marker));
+    }
+    pending.sort();
+    Ok(pending)
+}
+
+fn write_markers(markers: &[PathBuf]) -> Result<(), Error> {
+    for marker in markers {
+        if let Some(dir) = marker.parent() {
+            create_dir_all(dir).map_err(|e| io_error(e, "create watch marker directory"))?;
+        }
+        File::create(marker).map_err(|e| io_error(e, "create watch marker file"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local};
+use croner::Cron;
+
+use crate::fs::DirectoryReader;
+
+const MARKER_EXTENSION: &str = "processed";
+
+/// Name of the directory of the markers of files in the watch directory.
+const FILE_MARKERS_DIR_NAME: &str = "files";
+
+/// Name of the directory of the markers of torrents of the torrent client.
+const CLIENT_MARKERS_DIR_NAME: &str = "client";
+
+/// Get the most recent modified time of a file, or of a directory and its descendants.
+#[must_use]
+pub fn get_last_modified(path: &Path) -> Option<SystemTime> {
+    let modified = get_modified(path)?;
+    if !path.is_dir() {
+        return Some(modified);
+    }
+    let descendants = DirectoryReader::new().read(path).ok()?;
+    let last_modified = descendants
+        .iter()
+        .filter_map(|descendant| get_modified(descendant))
+        .max()
+        .map_or(modified, |descendant| descendant.max(modified));
+    Some(last_modified)
+}
+
+/// Has the path been unchanged for at least `debounce`?
+#[must_use]
+pub fn is_settled(path: &Path, debounce: Duration) -> bool {
+    get_last_modified(path)
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|elapsed| elapsed >= debounce)
+}
+
+/// Is the path a `.torrent` file that can be processed?
+#[must_use]
+pub fn is_watchable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension == "torrent")
+}
+
+/// Get the path of the marker file indicating `path` has been processed.
+///
+/// The marker is named by the file name and the time it was last modified, so a file
+/// that's dropped again with the same name is processed again.
+///
+/// Returns `None` if the modified time can't be read.
+#[must_use]
+pub fn get_marker_path(markers_dir: &Path, path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+    let modified = get_modified(path)?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis();
+    Some(
+        markers_dir
+            .join(FILE_MARKERS_DIR_NAME)
+            .join(format!("{file_name}.{modified}.{MARKER_EXTENSION}")),
+    )
+}
+
+/// Get the path of the marker file indicating a torrent of the torrent client has been
+/// processed.
+#[must_use]
+pub fn get_client_marker_path(markers_dir: &Path, hash: &str) -> PathBuf {
+    markers_dir
+        .join(CLIENT_MARKERS_DIR_NAME)
+        .join(format!("{}.{MARKER_EXTENSION}", hash.to_lowercase()))
+}
+
+/// Get the next time `schedule` is due after `now`.
+#[must_use]
+pub fn get_next_run(schedule: &Cron, now: &DateTime<Local>) -> Option<DateTime<Local>> {
+    schedule.find_next_occurrence(now, false).ok()
+}
+
+/// Get the duration to wait before the next scan.
+///
+/// This is `interval` unless the next scheduled run is sooner.
+#[must_use]
+pub fn get_wait(
+    interval: Duration,
+    next_run: Option<&DateTime<Local>>,
+    now: &DateTime<Local>,
+) -> Duration {
+    let Some(next_run) = next_run else {
+        return interval;
+    };
+    let until = (*next_run - *now).to_std().unwrap_or_default();
+    until.min(interval)
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}